@@ -53,3 +53,40 @@ pub struct ListAttachmentsResponse {
 pub struct AttachmentUrlResponse {
     pub url: String,
 }
+
+/// Request to start a direct-to-storage attachment upload.
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct InitUploadRequest {
+    pub project_id: Uuid,
+    pub filename: String,
+    #[ts(type = "number")]
+    pub size_bytes: i64,
+    pub hash: String,
+}
+
+/// Where to upload an attachment, or whether an identical blob already exists.
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct InitUploadResponse {
+    pub upload_url: String,
+    pub upload_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+    pub skip_upload: bool,
+    pub existing_blob_id: Option<Uuid>,
+}
+
+/// Confirms a finished upload and records the attachment.
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct ConfirmUploadRequest {
+    pub project_id: Uuid,
+    pub upload_id: Uuid,
+    pub filename: String,
+    #[ts(optional)]
+    pub content_type: Option<String>,
+    #[ts(type = "number")]
+    pub size_bytes: i64,
+    pub hash: String,
+    #[ts(optional)]
+    pub issue_id: Option<Uuid>,
+    #[ts(optional)]
+    pub comment_id: Option<Uuid>,
+}
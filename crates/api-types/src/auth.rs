@@ -1,7 +1,18 @@
 use chrono::{DateTime, Duration, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Capabilities granted to an auth session and the access tokens minted for it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SessionScope {
+    #[default]
+    Full,
+    /// May read and stream data but not mutate it (kiosk/dashboard clients).
+    ReadOnly,
+}
+
 #[derive(Debug, Clone, sqlx::FromRow, Serialize)]
 pub struct AuthSession {
     pub id: Uuid,
@@ -13,6 +24,7 @@ pub struct AuthSession {
     pub refresh_token_issued_at: Option<DateTime<Utc>>,
     pub previous_refresh_token_id: Option<Uuid>,
     pub previous_refresh_token_grace_expires_at: Option<DateTime<Utc>>,
+    pub scope: SessionScope,
 }
 
 impl AuthSession {
@@ -0,0 +1,33 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BoardAtQuery {
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Reconstructed board state for a project at a past instant.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BoardAtResponse {
+    pub project_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub columns: Vec<BoardColumnAt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BoardColumnAt {
+    pub status_id: Uuid,
+    /// `None` if the status has since been deleted.
+    pub status_name: Option<String>,
+    pub issues: Vec<BoardIssueAt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BoardIssueAt {
+    pub issue_id: Uuid,
+    /// Current identifiers; `None` if the issue has since been deleted.
+    pub simple_id: Option<String>,
+    pub title: Option<String>,
+}
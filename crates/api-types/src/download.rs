@@ -0,0 +1,10 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// A short-lived, pre-authorized link served by `GET /v1/downloads/{token}`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SignedDownloadUrl {
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+}
@@ -1,7 +1,11 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::Type;
 use ts_rs::TS;
 use uuid::Uuid;
 
+use crate::SignedDownloadUrl;
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ExportRequest {
     pub organization_id: Uuid,
@@ -9,3 +13,35 @@ pub struct ExportRequest {
     pub project_ids: Vec<Uuid>,
     pub include_attachments: bool,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ExportJobStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// A background export. The archive is built asynchronously and fetched via a
+/// signed download URL once `status` is `completed`.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ExportJob {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub status: ExportJobStatus,
+    pub filename: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// The archive is deleted after this time.
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ExportJobResponse {
+    pub job: ExportJob,
+    /// Present once the job has completed.
+    pub download: Option<SignedDownloadUrl>,
+}
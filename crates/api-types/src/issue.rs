@@ -5,7 +5,7 @@ use sqlx::Type;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use crate::some_if_present;
+use crate::{Patch, some_if_present};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
 #[sqlx(type_name = "issue_priority", rename_all = "snake_case")]
@@ -42,8 +42,12 @@ pub struct Issue {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum IssueSortField {
+    /// Board order: status column position, then the manual sort key.
     SortOrder,
+    /// The manual sort key alone, ignoring status.
+    SortKey,
     Priority,
+    TargetDate,
     CreatedAt,
     UpdatedAt,
     Title,
@@ -90,54 +94,33 @@ pub struct UpdateIssueRequest {
         skip_serializing_if = "Option::is_none"
     )]
     pub title: Option<String>,
-    #[serde(
-        default,
-        deserialize_with = "some_if_present",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub description: Option<Option<String>>,
-    #[serde(
-        default,
-        deserialize_with = "some_if_present",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub priority: Option<Option<IssuePriority>>,
-    #[serde(
-        default,
-        deserialize_with = "some_if_present",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub start_date: Option<Option<DateTime<Utc>>>,
-    #[serde(
-        default,
-        deserialize_with = "some_if_present",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub target_date: Option<Option<DateTime<Utc>>>,
-    #[serde(
-        default,
-        deserialize_with = "some_if_present",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub completed_at: Option<Option<DateTime<Utc>>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    #[ts(optional = nullable, as = "Option<String>")]
+    pub description: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    #[ts(optional = nullable, as = "Option<IssuePriority>")]
+    pub priority: Patch<IssuePriority>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    #[ts(optional = nullable, as = "Option<DateTime<Utc>>")]
+    pub start_date: Patch<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    #[ts(optional = nullable, as = "Option<DateTime<Utc>>")]
+    pub target_date: Patch<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    #[ts(optional = nullable, as = "Option<DateTime<Utc>>")]
+    pub completed_at: Patch<DateTime<Utc>>,
     #[serde(
         default,
         deserialize_with = "some_if_present",
         skip_serializing_if = "Option::is_none"
     )]
     pub sort_order: Option<f64>,
-    #[serde(
-        default,
-        deserialize_with = "some_if_present",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub parent_issue_id: Option<Option<Uuid>>,
-    #[serde(
-        default,
-        deserialize_with = "some_if_present",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub parent_issue_sort_order: Option<Option<f64>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    #[ts(optional = nullable, as = "Option<Uuid>")]
+    pub parent_issue_id: Patch<Uuid>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    #[ts(optional = nullable, as = "Option<f64>")]
+    pub parent_issue_sort_order: Patch<f64>,
     #[serde(
         default,
         deserialize_with = "some_if_present",
@@ -149,6 +132,36 @@ pub struct UpdateIssueRequest {
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ListIssuesQuery {
     pub project_id: Uuid,
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_id: Option<Uuid>,
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee_user_id: Option<Uuid>,
+    /// Full-text query over title and description.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_field: Option<IssueSortField>,
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort_direction: Option<SortDirection>,
+    /// Page by `(updated_at, id)` instead of the sort options: return issues
+    /// updated after this cursor, oldest first.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_updated_at: Option<DateTime<Utc>>,
+    /// Tie-breaker for `after_updated_at`: the `id` of the last issue seen.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<Uuid>,
+    /// Page size for cursor paging. Setting it without a cursor starts from
+    /// the first page.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -169,6 +182,11 @@ pub struct SearchIssuesRequest {
     #[ts(optional)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search: Option<String>,
+    /// Full-text query over title and description, in web search syntax
+    /// (`"exact phrase"`, `or`, `-excluded`).
+    #[ts(optional)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
     #[ts(optional)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub simple_id: Option<String>,
@@ -202,3 +220,29 @@ pub struct ListIssuesResponse {
     pub limit: usize,
     pub offset: usize,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct CreateSubIssueRequest {
+    /// Optional client-generated ID. If not provided, server generates one.
+    #[ts(optional)]
+    pub id: Option<Uuid>,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Option<IssuePriority>,
+    /// Defaults to the project's first to-do status.
+    #[ts(optional)]
+    pub status_id: Option<Uuid>,
+}
+
+/// An issue with its sub-issues and rolled-up completion.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct IssueTreeNode {
+    pub issue: Issue,
+    /// The issue is in a done status and every sub-issue, at any depth, is
+    /// done. Cancelled sub-issues are ignored.
+    pub done: bool,
+    /// Direct sub-issues counted towards `done` (cancelled ones excluded).
+    pub total_children: i32,
+    pub completed_children: i32,
+    pub children: Vec<IssueTreeNode>,
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::{Issue, IssueAssignee, IssueComment, IssueTag};
+
+/// A single step of a batched issue edit.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IssueAction {
+    SetStatus { status_id: Uuid },
+    Assign { user_id: Uuid },
+    AddTag { tag_id: Uuid },
+    Comment { message: String },
+}
+
+/// Actions are applied in order inside one transaction: either all of them
+/// take effect or none do.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ApplyIssueActionsRequest {
+    pub actions: Vec<IssueAction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ApplyIssueActionsResponse {
+    pub issue: Issue,
+    pub assignees: Vec<IssueAssignee>,
+    pub tags: Vec<IssueTag>,
+    pub comments: Vec<IssueComment>,
+    pub txid: i64,
+}
@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum IssueActivityEventType {
+    Created,
+    StatusChanged,
+    Deleted,
+    /// The issue's target date is approaching. Recorded once per target date
+    /// by a background task, with no actor.
+    DueSoon,
+    /// A blocking relationship onto this issue was added.
+    Blocked,
+    /// A blocking relationship onto this issue was removed.
+    Unblocked,
+    /// Someone was `@mentioned` in the issue's description or a comment.
+    Mentioned,
+    /// The project was renamed or moved to another organization. Not tied
+    /// to an issue.
+    ProjectUpdated,
+    /// A type added after this build, read from a newer remote, so one new
+    /// kind of event doesn't fail the whole page. Never recorded.
+    #[serde(other)]
+    #[ts(skip)]
+    Unknown,
+}
+
+impl IssueActivityEventType {
+    /// The name stored in the database and used on the wire.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::StatusChanged => "status_changed",
+            Self::Deleted => "deleted",
+            Self::DueSoon => "due_soon",
+            Self::Blocked => "blocked",
+            Self::Unblocked => "unblocked",
+            Self::Mentioned => "mentioned",
+            Self::ProjectUpdated => "project_updated",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+/// A single entry in the append-only issue activity log.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct IssueActivity {
+    pub seq: i64,
+    pub project_id: Uuid,
+    /// `None` only for [`IssueActivityEventType::ProjectUpdated`].
+    pub issue_id: Option<Uuid>,
+    pub event_type: IssueActivityEventType,
+    pub from_status_id: Option<Uuid>,
+    pub to_status_id: Option<Uuid>,
+    pub actor_user_id: Option<Uuid>,
+    /// `X-Mutation-Id` of the request that caused the event, if it sent one.
+    pub mutation_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListIssueActivityQuery {
+    pub project_id: Uuid,
+    /// Only return events recorded after this activity sequence number.
+    /// A cursor below the project's purged history is rejected with 410;
+    /// start over from 0.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since_seq: Option<i64>,
+    /// Only return events of this type.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<IssueActivityEventType>,
+    /// Comma-separated event types to return, e.g. `created,status_changed`,
+    /// for clients that only react to a few kinds of change. Combined with
+    /// `event_type` if both are given.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_types: Option<String>,
+    /// Only return events created at or after this time.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+    /// Only return events created before this time.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<Utc>>,
+    /// Without `since_seq`, resume after the cursor this device last
+    /// acknowledged.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    /// Also return [`IssueActivityEventType::ProjectUpdated`] events. Off by
+    /// default because clients that predate them can't parse an event with
+    /// no `issue_id`.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include_project_events: Option<bool>,
+    /// Also return the project's replays with an id above this, so the
+    /// client can fetch their ranges again. Omitted by clients that don't
+    /// handle replays.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replays_after: Option<i64>,
+}
+
+/// A range of a project's activity an operator asked clients to fetch
+/// again, e.g. after a bug left them with cursors past events they never
+/// stored.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ActivityReplay {
+    /// Pass as `replays_after` once the range has been fetched again.
+    pub id: i64,
+    pub first_seq: i64,
+    pub last_seq: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListIssueActivityResponse {
+    /// Events in log order.
+    pub activity: Vec<IssueActivity>,
+    /// Cursor to pass as `since_seq` on the next request.
+    pub latest_seq: i64,
+    /// More events are available after `latest_seq`.
+    pub has_more: bool,
+    /// Replays after `replays_after`, oldest first; empty if it wasn't
+    /// given.
+    #[serde(default)]
+    pub replays: Vec<ActivityReplay>,
+}
+
+/// The last activity sequence number a user's device has processed for a
+/// project.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ActivityCursor {
+    pub project_id: Uuid,
+    pub device_id: String,
+    pub seq: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct GetActivityCursorQuery {
+    pub project_id: Uuid,
+    pub device_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct AckActivityRequest {
+    pub project_id: Uuid,
+    /// Stable identifier the client picks for itself.
+    pub device_id: String,
+    /// Highest sequence number processed. A lower value than the stored
+    /// one leaves the cursor where it is; a value above the commit-safe
+    /// watermark is stored as the watermark.
+    pub seq: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListDeletedIssuesQuery {
+    pub project_id: Uuid,
+    /// Only return deletions recorded after this activity sequence number.
+    /// A cursor below the project's purged history is rejected with 410;
+    /// start over from 0.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since_seq: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct IssueTombstone {
+    pub issue_id: Uuid,
+    pub seq: i64,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListDeletedIssuesResponse {
+    pub tombstones: Vec<IssueTombstone>,
+    /// Cursor to pass as `since_seq` on the next request. Advances even when
+    /// no deletions were returned so clients don't rescan old history.
+    pub latest_seq: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListDueSoonIssuesQuery {
+    pub project_id: Uuid,
+    /// Only return reminders recorded after this activity sequence number.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since_seq: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct IssueDueReminder {
+    pub issue_id: Uuid,
+    pub seq: i64,
+    pub target_date: DateTime<Utc>,
+    pub reminded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListDueSoonIssuesResponse {
+    /// Reminders for issues that are still open and still due on the
+    /// announced date.
+    pub reminders: Vec<IssueDueReminder>,
+    /// Cursor to pass as `since_seq` on the next request.
+    pub latest_seq: i64,
+}
@@ -10,19 +10,28 @@ use serde::{Deserialize, Deserializer};
 pub mod attachment;
 pub mod auth;
 pub mod blob;
+pub mod board_history;
+pub mod download;
 pub mod export;
 pub mod issue;
+pub mod issue_action;
+pub mod issue_activity;
 pub mod issue_assignee;
 pub mod issue_comment;
 pub mod issue_comment_reaction;
 pub mod issue_follower;
 pub mod issue_relationship;
 pub mod issue_tag;
+pub mod mutation_batch;
 pub mod notification;
 pub mod oauth;
 pub mod organization_member;
 pub mod organizations;
+pub mod patch;
+pub mod presence;
+pub mod product_update;
 pub mod project;
+pub mod project_report;
 pub mod project_status;
 pub mod pull_request;
 pub mod pull_requests_local;
@@ -35,19 +44,28 @@ pub mod workspaces;
 pub use attachment::*;
 pub use auth::*;
 pub use blob::*;
+pub use board_history::*;
+pub use download::*;
 pub use export::*;
 pub use issue::*;
+pub use issue_action::*;
+pub use issue_activity::*;
 pub use issue_assignee::*;
 pub use issue_comment::*;
 pub use issue_comment_reaction::*;
 pub use issue_follower::*;
 pub use issue_relationship::*;
 pub use issue_tag::*;
+pub use mutation_batch::*;
 pub use notification::*;
 pub use oauth::*;
 pub use organization_member::*;
 pub use organizations::*;
+pub use patch::*;
+pub use presence::*;
+pub use product_update::*;
 pub use project::*;
+pub use project_report::*;
 pub use project_status::*;
 pub use pull_request::*;
 pub use pull_requests_local::*;
@@ -57,6 +75,9 @@ pub use user::*;
 pub use workspace::*;
 pub use workspaces::*;
 
+/// Deserialize a present field as `Some`, including an explicit `null`
+/// into `Some(None)` for `Option<Option<T>>` fields. Prefer [`Patch`] for
+/// new nullable update fields.
 pub fn some_if_present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
 where
     D: Deserializer<'de>,
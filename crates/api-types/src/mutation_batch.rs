@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+use crate::{
+    CreateIssueAssigneeRequest, CreateIssueRelationshipRequest, CreateIssueRequest,
+    CreateIssueTagRequest, Issue, IssueAssignee, IssueRelationship, IssueTag,
+};
+
+/// One step of a batch. Later steps may refer to issues created by earlier
+/// ones through their client-generated ids.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchMutation {
+    CreateIssue(CreateIssueRequest),
+    AddIssueTag(CreateIssueTagRequest),
+    AddIssueAssignee(CreateIssueAssigneeRequest),
+    AddIssueRelationship(CreateIssueRelationshipRequest),
+}
+
+/// Mutations are applied in order inside one transaction: either all of them
+/// take effect or none do.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BatchMutationsRequest {
+    pub mutations: Vec<BatchMutation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum BatchMutationResult {
+    Issue(Issue),
+    IssueTag(IssueTag),
+    IssueAssignee(IssueAssignee),
+    IssueRelationship(IssueRelationship),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BatchMutationsResponse {
+    /// One result per mutation, in request order.
+    pub results: Vec<BatchMutationResult>,
+    pub txid: i64,
+}
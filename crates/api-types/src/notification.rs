@@ -19,6 +19,10 @@ pub enum NotificationType {
     IssueDeleted,
     IssueTitleChanged,
     IssueDescriptionChanged,
+    IssueDueSoon,
+    IssueBlocked,
+    IssueUnblocked,
+    IssueMentioned,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
@@ -85,3 +89,15 @@ pub struct UpdateNotificationRequest {
     #[serde(default, deserialize_with = "some_if_present")]
     pub seen: Option<bool>,
 }
+
+/// When the current user's daily email digest is sent.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct DigestSettings {
+    /// Hour of the day, in UTC (0-23). Unset uses the server's default.
+    pub digest_hour_utc: Option<i16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UpdateDigestSettingsRequest {
+    pub digest_hour_utc: Option<i16>,
+}
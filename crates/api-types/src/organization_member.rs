@@ -12,6 +12,8 @@ use uuid::Uuid;
 pub enum MemberRole {
     Admin,
     Member,
+    /// Can read the organization's projects and issues but not change them.
+    Viewer,
 }
 
 /// Organization member as stored in the database / streamed via Electric.
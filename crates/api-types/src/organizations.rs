@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sqlx::Type;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -157,3 +160,60 @@ pub struct UpdateMemberRoleResponse {
     pub user_id: Uuid,
     pub role: MemberRole,
 }
+
+/// Per-organization network restrictions. Empty lists leave that dimension
+/// unrestricted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct OrganizationAccessPolicy {
+    /// CIDR ranges (e.g. `203.0.113.0/24`) member requests must come from.
+    pub ip_allowlist: Vec<String>,
+    /// Auth session IDs (see `/v1/identity`) of the signed-in devices member
+    /// requests must come from.
+    pub device_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UpdateOrganizationAccessPolicyRequest {
+    pub ip_allowlist: Vec<String>,
+    pub device_ids: Vec<String>,
+}
+
+/// Per-organization retention for synced data that loses value faster than
+/// issues do.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct OrganizationRetentionPolicy {
+    /// Days after a workspace's last update to keep its diff summary (files
+    /// changed, lines added/removed). `None` keeps summaries indefinitely.
+    pub workspace_summary_days: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UpdateOrganizationRetentionPolicyRequest {
+    pub workspace_summary_days: Option<i32>,
+}
+
+/// Per-organization board automations driven by GitHub webhooks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct OrganizationAutomationPolicy {
+    /// Move Done issues back to In progress, and notify their assignees,
+    /// when a linked pull request is reverted or CI fails on the default
+    /// branch at its merge commit.
+    pub reopen_on_failure: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UpdateOrganizationAutomationPolicyRequest {
+    pub reopen_on_failure: bool,
+}
+
+/// Organization-wide client defaults applied by every member's app unless
+/// the member has chosen otherwise. Admins replace the whole document.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+pub struct OrganizationClientConfig {
+    /// Board filter state, in the frontend's own format.
+    #[serde(default)]
+    pub default_board_filters: Option<Value>,
+    /// Status name to CSS hex color (`#rrggbb`).
+    #[serde(default)]
+    pub status_colors: BTreeMap<String, String>,
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A JSON Merge Patch (RFC 7396) field: absent leaves the stored value
+/// alone, `null` clears it, and a value replaces it.
+///
+/// Use with `#[serde(default, skip_serializing_if = "Patch::is_absent")]` so
+/// a missing key deserializes to [`Patch::Absent`] and round-trips as missing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Patch<T> {
+    #[default]
+    Absent,
+    Null,
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    /// Overwrite the stored value with `value`, clearing it on `None`.
+    pub fn set(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Self::Value(value),
+            None => Self::Null,
+        }
+    }
+
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent)
+    }
+
+    /// The value being set, if any.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Self::Value(value) => Some(value),
+            Self::Absent | Self::Null => None,
+        }
+    }
+
+    /// The `Option<Option<T>>` form used by repository update methods:
+    /// `None` = leave unchanged, `Some(None)` = clear.
+    pub fn into_option(self) -> Option<Option<T>> {
+        match self {
+            Self::Absent => None,
+            Self::Null => Some(None),
+            Self::Value(value) => Some(Some(value)),
+        }
+    }
+}
+
+impl<T> From<Option<Option<T>>> for Patch<T> {
+    fn from(value: Option<Option<T>>) -> Self {
+        match value {
+            None => Self::Absent,
+            Some(None) => Self::Null,
+            Some(Some(value)) => Self::Value(value),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Absent | Self::Null => serializer.serialize_none(),
+            Self::Value(value) => serializer.serialize_some(value),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            None => Self::Null,
+            Some(value) => Self::Value(value),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::Patch;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Update {
+        #[serde(default, skip_serializing_if = "Patch::is_absent")]
+        description: Patch<String>,
+    }
+
+    #[test]
+    fn distinguishes_absent_null_and_value() {
+        let absent: Update = serde_json::from_str("{}").unwrap();
+        let null: Update = serde_json::from_str(r#"{"description":null}"#).unwrap();
+        let value: Update = serde_json::from_str(r#"{"description":"hi"}"#).unwrap();
+
+        assert_eq!(absent.description, Patch::Absent);
+        assert_eq!(null.description, Patch::Null);
+        assert_eq!(value.description, Patch::Value("hi".to_string()));
+        assert_eq!(null.description.into_option(), Some(None));
+    }
+
+    #[test]
+    fn serializes_back_to_merge_patch() {
+        let json = |description| serde_json::to_string(&Update { description }).unwrap();
+
+        assert_eq!(json(Patch::Absent), "{}");
+        assert_eq!(json(Patch::Null), r#"{"description":null}"#);
+        assert_eq!(json(Patch::Value("hi".into())), r#"{"description":"hi"}"#);
+    }
+}
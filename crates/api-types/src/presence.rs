@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::UserData;
+
+/// How long an editing indicator is shown without being repeated. Clients
+/// send [`ClientMessage::Editing`] more often than this while editing.
+pub const EDITING_INDICATOR_TTL_SECS: u64 = 15;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct PresenceQuery {
+    pub organization_id: Uuid,
+}
+
+/// A short-lived, single-use link for opening the organization's WebSocket,
+/// issued by `POST /v1/ws/tickets`. Browsers can't set an `Authorization`
+/// header on a WebSocket, so the ticket in the link stands in for it.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct PresenceTicket {
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Users with the organization open right now.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct PresenceResponse {
+    pub organization_id: Uuid,
+    pub users: Vec<UserData>,
+}
+
+/// Sent over the organization's WebSocket when someone comes online or
+/// closes their last connection.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type")]
+pub enum PresenceMessage {
+    /// Everyone online, replacing what the client had. Sent on connecting
+    /// and after messages to the client had to be dropped.
+    #[serde(rename = "presence.snapshot")]
+    Snapshot {
+        organization_id: Uuid,
+        users: Vec<UserData>,
+    },
+    #[serde(rename = "presence.joined")]
+    Joined {
+        organization_id: Uuid,
+        user: UserData,
+    },
+    #[serde(rename = "presence.left")]
+    Left {
+        organization_id: Uuid,
+        user_id: Uuid,
+    },
+    /// Shown until [`EDITING_INDICATOR_TTL_SECS`] pass without a repeat or
+    /// the user leaves.
+    #[serde(rename = "presence.editing")]
+    Editing {
+        organization_id: Uuid,
+        user_id: Uuid,
+        task_id: Uuid,
+    },
+}
+
+impl PresenceMessage {
+    pub fn organization_id(&self) -> Uuid {
+        match self {
+            Self::Snapshot {
+                organization_id, ..
+            }
+            | Self::Joined {
+                organization_id, ..
+            }
+            | Self::Left {
+                organization_id, ..
+            }
+            | Self::Editing {
+                organization_id, ..
+            } => *organization_id,
+        }
+    }
+}
+
+/// Sent by clients over the organization's WebSocket. These are relayed to
+/// other members and never stored.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// The sender is editing the issue `task_id`.
+    Editing { task_id: Uuid },
+}
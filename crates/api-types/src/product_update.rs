@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// An entry in the in-app "what's new" feed.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProductUpdate {
+    pub id: Uuid,
+    pub title: String,
+    /// Markdown.
+    pub body: String,
+    pub url: Option<String>,
+    pub published_at: DateTime<Utc>,
+    /// Whether the requesting user has read this update.
+    pub read: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListProductUpdatesResponse {
+    pub updates: Vec<ProductUpdate>,
+    pub unread_count: i64,
+}
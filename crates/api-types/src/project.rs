@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use uuid::Uuid;
 
-use crate::some_if_present;
+use crate::{Issue, OrganizationMemberWithProfile, ProjectStatus, Tag, some_if_present};
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct Project {
@@ -37,6 +37,53 @@ pub struct UpdateProjectRequest {
     pub sort_order: Option<i32>,
 }
 
+/// Commit message convention for commits made in a project's workspaces,
+/// shared by everyone working on the project.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct ProjectCommitSettings {
+    /// Rewrite commit messages into Conventional Commits with the task key.
+    pub conventional_commits: bool,
+    /// Template for the header, e.g. `{type}({task_key}): {summary}`. Unset
+    /// uses the default.
+    pub commit_template: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UpdateProjectCommitSettingsRequest {
+    pub conventional_commits: bool,
+    pub commit_template: Option<String>,
+}
+
+/// How pull requests are opened from a project's workspaces, shared by
+/// everyone working on the project.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectPrSettings {
+    /// Open pull requests as drafts unless the request says otherwise.
+    pub draft: bool,
+    /// Start a body left empty from the repository's pull request template.
+    pub use_template: bool,
+    /// Add the linked issue's title and description to a body left empty,
+    /// with a link back to the issue.
+    pub link_issue: bool,
+}
+
+impl Default for ProjectPrSettings {
+    fn default() -> Self {
+        Self {
+            draft: false,
+            use_template: true,
+            link_issue: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UpdateProjectPrSettingsRequest {
+    pub draft: bool,
+    pub use_template: bool,
+    pub link_issue: bool,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ListProjectsQuery {
     pub organization_id: Uuid,
@@ -64,3 +111,17 @@ pub struct BulkUpdateProjectsResponse {
     pub data: Vec<Project>,
     pub txid: i64,
 }
+
+/// Everything a client needs to render a project's board, read from a single
+/// consistent snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectSnapshotResponse {
+    pub project: Project,
+    pub issues: Vec<Issue>,
+    pub statuses: Vec<ProjectStatus>,
+    pub tags: Vec<Tag>,
+    pub members: Vec<OrganizationMemberWithProfile>,
+    /// Every activity event up to this seq is reflected in the snapshot;
+    /// follow up with activity after it.
+    pub latest_seq: i64,
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A stored point-in-time snapshot of a project board.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectReport {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub created_by_user_id: Option<Uuid>,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub snapshot: ProjectReportSnapshot,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct ProjectReportSnapshot {
+    pub status_counts: Vec<ReportStatusCount>,
+    /// Issues whose status changed during the report period, latest move first.
+    pub moved_issues: Vec<ReportMovedIssue>,
+    /// Issues completed during the report period.
+    pub completed_issues: Vec<ReportIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ReportStatusCount {
+    pub status_id: Uuid,
+    pub status_name: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ReportIssue {
+    pub issue_id: Uuid,
+    pub simple_id: String,
+    pub title: String,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ReportMovedIssue {
+    pub issue_id: Uuid,
+    pub simple_id: String,
+    pub title: String,
+    pub from_status_id: Option<Uuid>,
+    pub to_status_id: Uuid,
+    pub moved_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct CreateProjectReportRequest {
+    /// Start of the report period. Defaults to seven days before now.
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListProjectReportsResponse {
+    pub reports: Vec<ProjectReport>,
+}
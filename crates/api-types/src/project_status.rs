@@ -1,9 +1,25 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::Type;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use crate::some_if_present;
+use crate::{Patch, some_if_present};
+
+/// What a status means for the work in it, independent of its name.
+/// Automations that move issues look statuses up by category, so renaming a
+/// column doesn't break them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "project_status_category", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectStatusCategory {
+    Backlog,
+    Todo,
+    InProgress,
+    InReview,
+    Done,
+    Cancelled,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ProjectStatus {
@@ -13,6 +29,7 @@ pub struct ProjectStatus {
     pub color: String,
     pub sort_order: i32,
     pub hidden: bool,
+    pub category: Option<ProjectStatusCategory>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -27,6 +44,8 @@ pub struct CreateProjectStatusRequest {
     pub color: String,
     pub sort_order: i32,
     pub hidden: bool,
+    #[ts(optional)]
+    pub category: Option<ProjectStatusCategory>,
 }
 
 #[derive(Debug, Clone, Deserialize, TS)]
@@ -39,6 +58,9 @@ pub struct UpdateProjectStatusRequest {
     pub sort_order: Option<i32>,
     #[serde(default, deserialize_with = "some_if_present")]
     pub hidden: Option<bool>,
+    #[serde(default)]
+    #[ts(optional = nullable, as = "Option<ProjectStatusCategory>")]
+    pub category: Patch<ProjectStatusCategory>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::Patch;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct DeleteWorkspaceRequest {
     pub local_workspace_id: Uuid,
@@ -26,14 +28,59 @@ pub struct CreateWorkspaceRequest {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct UpdateWorkspaceRequest {
     pub local_workspace_id: Uuid,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<Option<String>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub name: Patch<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub archived: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub files_changed: Option<Option<i32>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub lines_added: Option<Option<i32>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub lines_removed: Option<Option<i32>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub files_changed: Patch<i32>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub lines_added: Patch<i32>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub lines_removed: Patch<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::UpdateWorkspaceRequest;
+    use crate::Patch;
+
+    fn parse(fields: serde_json::Value) -> UpdateWorkspaceRequest {
+        let mut body = json!({ "local_workspace_id": Uuid::nil() });
+        body.as_object_mut()
+            .unwrap()
+            .extend(fields.as_object().unwrap().clone());
+        serde_json::from_value(body).unwrap()
+    }
+
+    #[test]
+    fn absent_fields_are_left_unchanged() {
+        let request = parse(json!({}));
+        assert_eq!(request.name, Patch::Absent);
+        assert_eq!(request.files_changed, Patch::Absent);
+        assert_eq!(request.lines_added, Patch::Absent);
+        assert_eq!(request.lines_removed, Patch::Absent);
+    }
+
+    #[test]
+    fn null_clears_each_nullable_field() {
+        let request = parse(json!({
+            "name": null,
+            "files_changed": null,
+            "lines_added": null,
+            "lines_removed": null,
+        }));
+        assert_eq!(request.name, Patch::Null);
+        assert_eq!(request.files_changed, Patch::Null);
+        assert_eq!(request.lines_added, Patch::Null);
+        assert_eq!(request.lines_removed, Patch::Null);
+
+        // Clears must survive the client's serialization, not be dropped.
+        let body = serde_json::to_value(&request).unwrap();
+        assert_eq!(body["name"], serde_json::Value::Null);
+        assert!(body.as_object().unwrap().contains_key("lines_removed"));
+    }
 }
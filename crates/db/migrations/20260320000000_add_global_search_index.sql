@@ -0,0 +1,112 @@
+-- Full-text index behind global search. One FTS5 table covers every
+-- searchable entity. FTS5 can't index which row an entry came from, so
+-- `search_entities` maps each source row (`kind`, `entity_id`) to the rowid
+-- of its index row through a unique index, and the triggers that keep the
+-- index in sync with the source tables look entries up by rowid.
+CREATE TABLE search_entities (
+    id        INTEGER PRIMARY KEY,
+    kind      TEXT NOT NULL,
+    entity_id BLOB NOT NULL,
+    UNIQUE (kind, entity_id)
+);
+
+CREATE VIRTUAL TABLE search_index USING fts5(
+    title,
+    body,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Projects: name
+CREATE TRIGGER search_index_projects_insert AFTER INSERT ON projects BEGIN
+    INSERT INTO search_entities (kind, entity_id) VALUES ('project', NEW.id);
+    INSERT INTO search_index (rowid, title, body)
+    VALUES (last_insert_rowid(), NEW.name, '');
+END;
+
+CREATE TRIGGER search_index_projects_update AFTER UPDATE OF name ON projects BEGIN
+    UPDATE search_index SET title = NEW.name
+    WHERE rowid = (SELECT id FROM search_entities WHERE kind = 'project' AND entity_id = OLD.id);
+END;
+
+CREATE TRIGGER search_index_projects_delete AFTER DELETE ON projects BEGIN
+    DELETE FROM search_index
+    WHERE rowid = (SELECT id FROM search_entities WHERE kind = 'project' AND entity_id = OLD.id);
+    DELETE FROM search_entities WHERE kind = 'project' AND entity_id = OLD.id;
+END;
+
+-- Tasks: title, description
+CREATE TRIGGER search_index_tasks_insert AFTER INSERT ON tasks BEGIN
+    INSERT INTO search_entities (kind, entity_id) VALUES ('task', NEW.id);
+    INSERT INTO search_index (rowid, title, body)
+    VALUES (last_insert_rowid(), NEW.title, COALESCE(NEW.description, ''));
+END;
+
+CREATE TRIGGER search_index_tasks_update AFTER UPDATE OF title, description ON tasks BEGIN
+    UPDATE search_index SET title = NEW.title, body = COALESCE(NEW.description, '')
+    WHERE rowid = (SELECT id FROM search_entities WHERE kind = 'task' AND entity_id = OLD.id);
+END;
+
+CREATE TRIGGER search_index_tasks_delete AFTER DELETE ON tasks BEGIN
+    DELETE FROM search_index
+    WHERE rowid = (SELECT id FROM search_entities WHERE kind = 'task' AND entity_id = OLD.id);
+    DELETE FROM search_entities WHERE kind = 'task' AND entity_id = OLD.id;
+END;
+
+-- Workspaces: name, branch
+CREATE TRIGGER search_index_workspaces_insert AFTER INSERT ON workspaces BEGIN
+    INSERT INTO search_entities (kind, entity_id) VALUES ('workspace', NEW.id);
+    INSERT INTO search_index (rowid, title, body)
+    VALUES (last_insert_rowid(), COALESCE(NEW.name, NEW.branch), NEW.branch);
+END;
+
+CREATE TRIGGER search_index_workspaces_update AFTER UPDATE OF name, branch ON workspaces BEGIN
+    UPDATE search_index SET title = COALESCE(NEW.name, NEW.branch), body = NEW.branch
+    WHERE rowid = (SELECT id FROM search_entities WHERE kind = 'workspace' AND entity_id = OLD.id);
+END;
+
+CREATE TRIGGER search_index_workspaces_delete AFTER DELETE ON workspaces BEGIN
+    DELETE FROM search_index
+    WHERE rowid = (SELECT id FROM search_entities WHERE kind = 'workspace' AND entity_id = OLD.id);
+    DELETE FROM search_entities WHERE kind = 'workspace' AND entity_id = OLD.id;
+END;
+
+-- Agent turns (attempt logs): prompt, final summary
+CREATE TRIGGER search_index_agent_turns_insert AFTER INSERT ON coding_agent_turns BEGIN
+    INSERT INTO search_entities (kind, entity_id) VALUES ('agent_turn', NEW.id);
+    INSERT INTO search_index (rowid, title, body)
+    VALUES (last_insert_rowid(), COALESCE(NEW.prompt, ''), COALESCE(NEW.summary, ''));
+END;
+
+CREATE TRIGGER search_index_agent_turns_update AFTER UPDATE OF prompt, summary ON coding_agent_turns BEGIN
+    UPDATE search_index
+    SET title = COALESCE(NEW.prompt, ''), body = COALESCE(NEW.summary, '')
+    WHERE rowid = (SELECT id FROM search_entities WHERE kind = 'agent_turn' AND entity_id = OLD.id);
+END;
+
+CREATE TRIGGER search_index_agent_turns_delete AFTER DELETE ON coding_agent_turns BEGIN
+    DELETE FROM search_index
+    WHERE rowid = (SELECT id FROM search_entities WHERE kind = 'agent_turn' AND entity_id = OLD.id);
+    DELETE FROM search_entities WHERE kind = 'agent_turn' AND entity_id = OLD.id;
+END;
+
+-- Backfill existing rows.
+INSERT INTO search_entities (kind, entity_id) SELECT 'project', id FROM projects;
+INSERT INTO search_entities (kind, entity_id) SELECT 'task', id FROM tasks;
+INSERT INTO search_entities (kind, entity_id) SELECT 'workspace', id FROM workspaces;
+INSERT INTO search_entities (kind, entity_id) SELECT 'agent_turn', id FROM coding_agent_turns;
+
+INSERT INTO search_index (rowid, title, body)
+SELECT e.id, p.name, ''
+FROM projects p JOIN search_entities e ON e.kind = 'project' AND e.entity_id = p.id;
+
+INSERT INTO search_index (rowid, title, body)
+SELECT e.id, t.title, COALESCE(t.description, '')
+FROM tasks t JOIN search_entities e ON e.kind = 'task' AND e.entity_id = t.id;
+
+INSERT INTO search_index (rowid, title, body)
+SELECT e.id, COALESCE(w.name, w.branch), w.branch
+FROM workspaces w JOIN search_entities e ON e.kind = 'workspace' AND e.entity_id = w.id;
+
+INSERT INTO search_index (rowid, title, body)
+SELECT e.id, COALESCE(c.prompt, ''), COALESCE(c.summary, '')
+FROM coding_agent_turns c JOIN search_entities e ON e.kind = 'agent_turn' AND e.entity_id = c.id;
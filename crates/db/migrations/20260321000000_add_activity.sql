@@ -0,0 +1,19 @@
+-- Local mirror of remote issue activity for the offline activity timeline.
+-- Issue and status names are resolved when an event is mirrored so entries
+-- stay readable without the remote.
+CREATE TABLE activity (
+    project_id      BLOB NOT NULL,
+    seq             INTEGER NOT NULL,
+    issue_id        BLOB NOT NULL,
+    issue_simple_id TEXT,
+    issue_title     TEXT,
+    event_type      TEXT NOT NULL,
+    from_status     TEXT,
+    to_status       TEXT,
+    actor_user_id   BLOB,
+    created_at      TEXT NOT NULL,
+    PRIMARY KEY (project_id, seq)
+);
+
+CREATE INDEX idx_activity_project_created_at ON activity(project_id, created_at);
+CREATE INDEX idx_activity_issue_id ON activity(issue_id);
@@ -0,0 +1,3 @@
+-- Mutation ID the remote recorded for the request that caused each event,
+-- so the UI can skip re-rendering its own optimistic writes.
+ALTER TABLE activity ADD COLUMN mutation_id TEXT;
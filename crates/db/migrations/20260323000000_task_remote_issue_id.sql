@@ -0,0 +1,7 @@
+-- Remote issue a legacy local task was shared as, so sharing a project's
+-- tasks in bulk can be resumed without creating duplicates.
+ALTER TABLE tasks ADD COLUMN remote_issue_id BLOB;
+
+CREATE UNIQUE INDEX idx_tasks_remote_issue_id
+    ON tasks(remote_issue_id)
+    WHERE remote_issue_id IS NOT NULL;
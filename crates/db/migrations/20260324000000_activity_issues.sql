@@ -0,0 +1,14 @@
+-- Names of a remote project's issues, used to label mirrored activity. Kept
+-- across syncs so each sync only fetches issues updated since the newest one
+-- stored here; deleted issues are pruned from the remote tombstone feed.
+CREATE TABLE activity_issues (
+    project_id  BLOB NOT NULL,
+    issue_id    BLOB NOT NULL,
+    simple_id   TEXT NOT NULL,
+    title       TEXT NOT NULL,
+    updated_at  TEXT NOT NULL,
+    PRIMARY KEY (project_id, issue_id)
+);
+
+CREATE INDEX idx_activity_issues_project_updated_at
+    ON activity_issues(project_id, updated_at, issue_id);
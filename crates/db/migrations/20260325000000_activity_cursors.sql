@@ -0,0 +1,10 @@
+-- Sync progress per remote project, checkpointed as activity is mirrored so
+-- an interrupted sync resumes where it stopped. `seq` is the last event
+-- processed; `pruned_seq` is how far the deleted-issue feed has been applied,
+-- which lags `seq` until the end of a sync.
+CREATE TABLE activity_cursors (
+    project_id  BLOB PRIMARY KEY NOT NULL,
+    seq         INTEGER NOT NULL,
+    pruned_seq  INTEGER NOT NULL,
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
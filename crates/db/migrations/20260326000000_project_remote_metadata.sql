@@ -0,0 +1,4 @@
+-- Organization of the linked remote project, kept current from its
+-- `project_updated` activity so requests follow the project when it's
+-- transferred.
+ALTER TABLE projects ADD COLUMN remote_organization_id BLOB;
@@ -0,0 +1,10 @@
+-- Remote projects with shared issues but no local project linked to them,
+-- so the UI can offer to link one instead of the issues going unseen.
+-- Refreshed on every activity catch-up; linking a project hides its row.
+CREATE TABLE pending_project_links (
+    remote_project_id  BLOB PRIMARY KEY NOT NULL,
+    organization_id    BLOB NOT NULL,
+    name               TEXT NOT NULL,
+    issue_count        INTEGER NOT NULL,
+    updated_at         TEXT NOT NULL DEFAULT (datetime('now', 'subsec'))
+);
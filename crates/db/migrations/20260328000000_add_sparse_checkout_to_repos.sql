@@ -0,0 +1,2 @@
+-- Sparse checkout patterns for workspaces of large repositories
+ALTER TABLE repos ADD COLUMN sparse_checkout TEXT;
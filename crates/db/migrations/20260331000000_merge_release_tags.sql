@@ -0,0 +1,4 @@
+-- First release tag found to contain each merge, e.g. `v1.2.3`. NULL until
+-- a tag containing the merge commit exists in the repository.
+ALTER TABLE merges ADD COLUMN release_tag TEXT;
+ALTER TABLE pull_requests ADD COLUMN release_tag TEXT;
@@ -0,0 +1,3 @@
+-- Newest operator-requested replay applied to the mirror, so each replay
+-- moves the cursor back only once.
+ALTER TABLE activity_cursors ADD COLUMN replay_id INTEGER NOT NULL DEFAULT 0;
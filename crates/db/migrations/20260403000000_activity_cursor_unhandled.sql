@@ -0,0 +1,5 @@
+-- Earliest event fetched that no handler stored, e.g. a type added on the
+-- remote after this build. `seq` still moves past it so syncing continues;
+-- the startup audit moves `seq` back before it, so a build that knows the
+-- type stores it.
+ALTER TABLE activity_cursors ADD COLUMN unhandled_seq INTEGER;
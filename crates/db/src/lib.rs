@@ -3,10 +3,14 @@ use std::{str::FromStr, sync::Arc};
 use sqlx::{
     ConnectOptions, Error, Pool, Sqlite, SqlitePool,
     migrate::MigrateError,
-    sqlite::{SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePoolOptions},
+    sqlite::{
+        SqliteAutoVacuum, SqliteConnectOptions, SqliteConnection, SqliteJournalMode,
+        SqlitePoolOptions,
+    },
 };
 use utils::assets::asset_dir;
 
+pub mod maintenance;
 pub mod models;
 
 async fn run_migrations(pool: &Pool<Sqlite>) -> Result<(), Error> {
@@ -80,7 +84,8 @@ impl DBService {
         );
         let options = SqliteConnectOptions::from_str(&database_url)?
             .create_if_missing(true)
-            .journal_mode(SqliteJournalMode::Delete);
+            .journal_mode(SqliteJournalMode::Delete)
+            .auto_vacuum(SqliteAutoVacuum::Incremental);
         let pool = SqlitePool::connect_with(options).await?;
         run_migrations(&pool).await?;
         Ok(DBService { pool })
@@ -94,6 +99,7 @@ impl DBService {
         let options = SqliteConnectOptions::from_str(&database_url)?
             .create_if_missing(true)
             .journal_mode(SqliteJournalMode::Delete)
+            .auto_vacuum(SqliteAutoVacuum::Incremental)
             .disable_statement_logging();
         SqlitePoolOptions::new()
             .max_connections(64)
@@ -131,7 +137,8 @@ impl DBService {
         );
         let options = SqliteConnectOptions::from_str(&database_url)?
             .create_if_missing(true)
-            .journal_mode(SqliteJournalMode::Delete);
+            .journal_mode(SqliteJournalMode::Delete)
+            .auto_vacuum(SqliteAutoVacuum::Incremental);
 
         let pool = if let Some(hook) = after_connect {
             SqlitePoolOptions::new()
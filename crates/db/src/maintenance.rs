@@ -0,0 +1,91 @@
+//! Housekeeping for the local SQLite database.
+//!
+//! Execution logs make up most of the file for long-time users. Pools are
+//! opened with `auto_vacuum = INCREMENTAL` so deleted pages can be returned
+//! to the OS without a full `VACUUM`; databases created before that need one
+//! full [`vacuum`] to switch modes.
+
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+
+/// `PRAGMA auto_vacuum` value for incremental mode.
+const AUTO_VACUUM_INCREMENTAL: i64 = 2;
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct TableSize {
+    /// Table name; indexes are folded into their table.
+    pub name: String,
+    pub bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct DatabaseSize {
+    pub total_bytes: i64,
+    /// Space held by free pages, reclaimable by vacuuming.
+    pub free_bytes: i64,
+    /// Whether free pages are reclaimed incrementally, without a full vacuum.
+    pub incremental_vacuum: bool,
+    /// Largest first.
+    pub tables: Vec<TableSize>,
+}
+
+pub async fn size_report(pool: &SqlitePool) -> Result<DatabaseSize, sqlx::Error> {
+    let page_size: i64 = sqlx::query_scalar("PRAGMA page_size")
+        .fetch_one(pool)
+        .await?;
+    let page_count: i64 = sqlx::query_scalar("PRAGMA page_count")
+        .fetch_one(pool)
+        .await?;
+    let freelist_count: i64 = sqlx::query_scalar("PRAGMA freelist_count")
+        .fetch_one(pool)
+        .await?;
+    let auto_vacuum: i64 = sqlx::query_scalar("PRAGMA auto_vacuum")
+        .fetch_one(pool)
+        .await?;
+
+    let tables = sqlx::query_as::<_, (String, i64)>(
+        r#"SELECT COALESCE(m.tbl_name, s.name) AS name, SUM(s.pgsize) AS bytes
+           FROM dbstat s
+           LEFT JOIN sqlite_master m ON m.name = s.name
+           GROUP BY 1
+           ORDER BY bytes DESC"#,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|(name, bytes)| TableSize { name, bytes })
+    .collect();
+
+    Ok(DatabaseSize {
+        total_bytes: page_size * page_count,
+        free_bytes: page_size * freelist_count,
+        incremental_vacuum: auto_vacuum == AUTO_VACUUM_INCREMENTAL,
+        tables,
+    })
+}
+
+/// Routine upkeep: refresh planner statistics and release free pages when the
+/// database is in incremental mode. Cheap enough to run periodically.
+pub async fn optimize(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::query("PRAGMA optimize").execute(pool).await?;
+
+    let auto_vacuum: i64 = sqlx::query_scalar("PRAGMA auto_vacuum")
+        .fetch_one(pool)
+        .await?;
+    if auto_vacuum == AUTO_VACUUM_INCREMENTAL {
+        sqlx::query("PRAGMA incremental_vacuum")
+            .execute(pool)
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Rebuild the whole file. Reclaims all free space and applies the pool's
+/// `auto_vacuum` mode to databases created before it was set. Blocks writers
+/// for the duration, so only run on explicit request.
+pub async fn vacuum(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::query("VACUUM").execute(pool).await?;
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityEventType {
+    Created,
+    StatusChanged,
+    Deleted,
+    DueSoon,
+    Blocked,
+    Unblocked,
+    Mentioned,
+}
+
+/// A mirrored remote issue activity event.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Activity {
+    /// Remote project the event belongs to.
+    pub project_id: Uuid,
+    /// Position in the remote project's activity log.
+    pub seq: i64,
+    pub issue_id: Uuid,
+    pub issue_simple_id: Option<String>,
+    pub issue_title: Option<String>,
+    pub event_type: ActivityEventType,
+    pub from_status: Option<String>,
+    pub to_status: Option<String>,
+    pub actor_user_id: Option<Uuid>,
+    /// Client mutation ID of the request that caused the event.
+    pub mutation_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Activity {
+    /// Store `activity` unless it's already mirrored. Issue names missing
+    /// from `activity` (e.g. for a deleted issue) are taken from earlier
+    /// events for the same issue.
+    pub async fn insert<'e, E>(executor: E, activity: &Activity) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO activity (
+                   project_id, seq, issue_id, issue_simple_id, issue_title, event_type,
+                   from_status, to_status, actor_user_id, mutation_id, created_at
+               )
+               VALUES (
+                   $1, $2, $3,
+                   COALESCE($4, (SELECT issue_simple_id FROM activity
+                                 WHERE issue_id = $3 AND issue_simple_id IS NOT NULL
+                                 ORDER BY seq DESC LIMIT 1)),
+                   COALESCE($5, (SELECT issue_title FROM activity
+                                 WHERE issue_id = $3 AND issue_title IS NOT NULL
+                                 ORDER BY seq DESC LIMIT 1)),
+                   $6, $7, $8, $9, $10, $11
+               )
+               ON CONFLICT(project_id, seq) DO NOTHING"#,
+            activity.project_id,
+            activity.seq,
+            activity.issue_id,
+            activity.issue_simple_id,
+            activity.issue_title,
+            activity.event_type,
+            activity.from_status,
+            activity.to_status,
+            activity.actor_user_id,
+            activity.mutation_id,
+            activity.created_at,
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Whether an event like `activity`, caused by the same mutation, is
+    /// already mirrored: same issue, type, actor and statuses.
+    pub async fn exists_for_mutation<'e, E>(
+        executor: E,
+        activity: &Activity,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_scalar!(
+            r#"SELECT EXISTS(
+                   SELECT 1 FROM activity
+                   WHERE project_id = $1 AND issue_id = $2 AND event_type = $3
+                     AND mutation_id = $4
+                     AND actor_user_id IS $5
+                     AND from_status IS $6
+                     AND to_status IS $7
+               ) AS "exists!: bool""#,
+            activity.project_id,
+            activity.issue_id,
+            activity.event_type,
+            activity.mutation_id,
+            activity.actor_user_id,
+            activity.from_status,
+            activity.to_status
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Highest mirrored `seq` for `project_id`, or 0 if nothing is mirrored.
+    pub async fn latest_seq(pool: &SqlitePool, project_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COALESCE(MAX(seq), 0) AS "seq!: i64" FROM activity WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Projects with at least one mirrored event.
+    pub async fn project_ids(pool: &SqlitePool) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT DISTINCT project_id AS "project_id!: Uuid" FROM activity"#)
+            .fetch_all(pool)
+            .await
+    }
+
+    /// Events for `project_id` recorded at or after `since`, newest first.
+    pub async fn find_since(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Activity,
+            r#"SELECT project_id AS "project_id!: Uuid",
+                      seq AS "seq!: i64",
+                      issue_id AS "issue_id!: Uuid",
+                      issue_simple_id,
+                      issue_title,
+                      event_type AS "event_type!: ActivityEventType",
+                      from_status,
+                      to_status,
+                      actor_user_id AS "actor_user_id: Uuid",
+                      mutation_id,
+                      created_at AS "created_at!: DateTime<Utc>"
+               FROM activity
+               WHERE project_id = $1 AND created_at >= $2
+               ORDER BY seq DESC"#,
+            project_id,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// One-line human-readable description, e.g. "Moved VK-12 Fix login
+    /// from To do to In progress".
+    pub fn summary(&self) -> String {
+        let issue = match (&self.issue_simple_id, &self.issue_title) {
+            (Some(simple_id), Some(title)) => format!("{simple_id} {title}"),
+            (Some(label), None) | (None, Some(label)) => label.clone(),
+            (None, None) => "an issue".to_string(),
+        };
+        match self.event_type {
+            ActivityEventType::Created => format!("Created {issue}"),
+            ActivityEventType::StatusChanged => match (&self.from_status, &self.to_status) {
+                (Some(from), Some(to)) => format!("Moved {issue} from {from} to {to}"),
+                (None, Some(to)) => format!("Moved {issue} to {to}"),
+                _ => format!("Changed the status of {issue}"),
+            },
+            ActivityEventType::Deleted => format!("Deleted {issue}"),
+            ActivityEventType::DueSoon => format!("{issue} is due soon"),
+            ActivityEventType::Blocked => format!("{issue} was blocked"),
+            ActivityEventType::Unblocked => format!("{issue} was unblocked"),
+            ActivityEventType::Mentioned => format!("Mentioned someone in {issue}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::{Activity, ActivityEventType};
+
+    fn activity(event_type: ActivityEventType) -> Activity {
+        Activity {
+            project_id: Uuid::nil(),
+            seq: 1,
+            issue_id: Uuid::nil(),
+            issue_simple_id: Some("VK-12".to_string()),
+            issue_title: Some("Fix login".to_string()),
+            event_type,
+            from_status: Some("To do".to_string()),
+            to_status: Some("In progress".to_string()),
+            actor_user_id: None,
+            mutation_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn summarizes_events_with_whatever_names_are_known() {
+        assert_eq!(
+            activity(ActivityEventType::StatusChanged).summary(),
+            "Moved VK-12 Fix login from To do to In progress"
+        );
+
+        let mut deleted = activity(ActivityEventType::Deleted);
+        deleted.issue_simple_id = None;
+        deleted.issue_title = None;
+        assert_eq!(deleted.summary(), "Deleted an issue");
+    }
+}
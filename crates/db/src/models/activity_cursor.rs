@@ -0,0 +1,155 @@
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use uuid::Uuid;
+
+/// How far a remote project's activity has been mirrored.
+#[derive(Debug, Clone, Copy, FromRow)]
+pub struct ActivityCursor {
+    pub project_id: Uuid,
+    /// Last activity event processed.
+    pub seq: i64,
+    /// Deleted issues up to this event have been pruned from the name cache.
+    pub pruned_seq: i64,
+    /// Newest replay requested on the remote that has been applied.
+    pub replay_id: i64,
+    /// Earliest event fetched that no handler stored; `seq` is moved back
+    /// before it by [`Self::retry_unhandled`].
+    pub unhandled_seq: Option<i64>,
+}
+
+impl ActivityCursor {
+    pub async fn find(pool: &SqlitePool, project_id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ActivityCursor,
+            r#"SELECT project_id AS "project_id!: Uuid", seq, pruned_seq, replay_id, unhandled_seq
+               FROM activity_cursors
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ActivityCursor,
+            r#"SELECT project_id AS "project_id!: Uuid", seq, pruned_seq, replay_id, unhandled_seq
+               FROM activity_cursors"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record that events through `seq` were processed, and that the one at
+    /// `unhandled_seq`, if any, had no handler. Never moves the cursor
+    /// backwards nor `unhandled_seq` forwards; `pruned_seq` only applies
+    /// when the row is new.
+    pub async fn checkpoint<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        seq: i64,
+        pruned_seq: i64,
+        unhandled_seq: Option<i64>,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            r#"INSERT INTO activity_cursors (project_id, seq, pruned_seq, unhandled_seq)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   seq = MAX(activity_cursors.seq, excluded.seq),
+                   unhandled_seq = COALESCE(
+                       MIN(activity_cursors.unhandled_seq, excluded.unhandled_seq),
+                       activity_cursors.unhandled_seq,
+                       excluded.unhandled_seq
+                   ),
+                   updated_at = datetime('now', 'subsec')"#,
+            project_id,
+            seq,
+            pruned_seq,
+            unhandled_seq
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Move the cursor back to `seq` for replay `replay_id`, unless it's
+    /// already there. `pruned_seq` only applies when the row is new.
+    pub async fn rewind(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        seq: i64,
+        pruned_seq: i64,
+        replay_id: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO activity_cursors (project_id, seq, pruned_seq, replay_id)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id) DO UPDATE SET
+                   seq = MIN(activity_cursors.seq, excluded.seq),
+                   replay_id = MAX(activity_cursors.replay_id, excluded.replay_id),
+                   updated_at = datetime('now', 'subsec')"#,
+            project_id,
+            seq,
+            pruned_seq,
+            replay_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Move the cursor back before its earliest unhandled event, so the
+    /// events from there on are fetched again by a build that may handle
+    /// them. Already stored events are skipped when they arrive again.
+    pub async fn retry_unhandled(pool: &SqlitePool, project_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE activity_cursors
+               SET seq = MIN(seq, unhandled_seq - 1),
+                   unhandled_seq = NULL,
+                   updated_at = datetime('now', 'subsec')
+               WHERE project_id = $1 AND unhandled_seq IS NOT NULL"#,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Start `project_id` over from the beginning of its activity, keeping
+    /// the replays already seen.
+    pub async fn reset(pool: &SqlitePool, project_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE activity_cursors
+               SET seq = 0,
+                   pruned_seq = 0,
+                   unhandled_seq = NULL,
+                   updated_at = datetime('now', 'subsec')
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record that deleted issues through the current `seq` are pruned.
+    pub async fn mark_pruned(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        pruned_seq: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE activity_cursors
+               SET pruned_seq = MAX(pruned_seq, $2),
+                   updated_at = datetime('now', 'subsec')
+               WHERE project_id = $1"#,
+            project_id,
+            pruned_seq
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
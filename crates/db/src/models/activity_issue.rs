@@ -0,0 +1,112 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// Cached name of a remote issue, used to label mirrored activity.
+#[derive(Debug, Clone, FromRow)]
+pub struct ActivityIssue {
+    pub project_id: Uuid,
+    pub issue_id: Uuid,
+    pub simple_id: String,
+    pub title: String,
+    /// Remote `updated_at`; the newest one is the cursor for the next fetch.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ActivityIssue {
+    pub async fn upsert(pool: &SqlitePool, issue: &ActivityIssue) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO activity_issues (project_id, issue_id, simple_id, title, updated_at)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT(project_id, issue_id) DO UPDATE SET
+                   simple_id = excluded.simple_id,
+                   title = excluded.title,
+                   updated_at = excluded.updated_at"#,
+            issue.project_id,
+            issue.issue_id,
+            issue.simple_id,
+            issue.title,
+            issue.updated_at,
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ActivityIssue,
+            r#"SELECT project_id AS "project_id!: Uuid",
+                      issue_id AS "issue_id!: Uuid",
+                      simple_id,
+                      title,
+                      updated_at AS "updated_at!: DateTime<Utc>"
+               FROM activity_issues
+               WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn count_by_project(pool: &SqlitePool, project_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!: i64" FROM activity_issues WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// `(updated_at, issue_id)` of the most recently updated cached issue,
+    /// matching the remote's issue paging order. The remote only pages up to
+    /// updates that can no longer be joined by an earlier one committing
+    /// late, so resuming from here doesn't miss any.
+    pub async fn latest_cursor(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Option<(DateTime<Utc>, Uuid)>, sqlx::Error> {
+        let row = sqlx::query!(
+            r#"SELECT updated_at AS "updated_at!: DateTime<Utc>",
+                      issue_id AS "issue_id!: Uuid"
+               FROM activity_issues
+               WHERE project_id = $1
+               ORDER BY updated_at DESC, issue_id DESC
+               LIMIT 1"#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|row| (row.updated_at, row.issue_id)))
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        issue_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM activity_issues WHERE project_id = $1 AND issue_id = $2",
+            project_id,
+            issue_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Forget every cached issue of `project_id`; the next sync fetches the
+    /// ones that still exist.
+    pub async fn delete_by_project(pool: &SqlitePool, project_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM activity_issues WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -77,6 +77,16 @@ pub struct ExecutionProcess {
     pub updated_at: DateTime<Utc>,
 }
 
+/// A finished process whose log file may be pruned, with the project it
+/// belongs to (if its workspace is linked to a task).
+#[derive(Debug, Clone, FromRow)]
+pub struct ExecutionLogRef {
+    pub execution_id: Uuid,
+    pub session_id: Uuid,
+    pub project_id: Option<Uuid>,
+    pub completed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Deserialize, TS)]
 pub struct CreateExecutionProcess {
     pub session_id: Uuid,
@@ -249,6 +259,28 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Finished processes, newest first, for log retention.
+    pub async fn find_finished_log_refs(
+        pool: &SqlitePool,
+    ) -> Result<Vec<ExecutionLogRef>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionLogRef,
+            r#"SELECT
+                    ep.id as "execution_id!: Uuid",
+                    ep.session_id as "session_id!: Uuid",
+                    t.project_id as "project_id?: Uuid",
+                    COALESCE(ep.completed_at, ep.updated_at) as "completed_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               LEFT JOIN tasks t ON t.id = w.task_id
+               WHERE ep.status != 'running'
+               ORDER BY COALESCE(ep.completed_at, ep.updated_at) DESC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find running execution processes
     pub async fn find_running(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
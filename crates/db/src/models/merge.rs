@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{SqlitePool, Type};
@@ -16,6 +18,33 @@ pub enum MergeStatus {
     Unknown,
 }
 
+impl MergeStatus {
+    /// Combined status of several PRs for the same work: open while any PR
+    /// is, and merged only once every PR is merged, matching the rule the
+    /// remote server uses to mark an issue done. A closed PR keeps the work
+    /// from counting as merged. `None` without any PRs.
+    pub fn aggregate<'a>(
+        statuses: impl IntoIterator<Item = &'a MergeStatus>,
+    ) -> Option<MergeStatus> {
+        let statuses: Vec<&MergeStatus> = statuses.into_iter().collect();
+        if statuses.is_empty() {
+            return None;
+        }
+
+        let status = if statuses.iter().any(|s| matches!(s, MergeStatus::Open)) {
+            MergeStatus::Open
+        } else if statuses.iter().any(|s| matches!(s, MergeStatus::Unknown)) {
+            // Can't be counted as done until we know more.
+            MergeStatus::Unknown
+        } else if statuses.iter().all(|s| matches!(s, MergeStatus::Merged)) {
+            MergeStatus::Merged
+        } else {
+            MergeStatus::Closed
+        };
+        Some(status)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Merge {
@@ -30,6 +59,8 @@ pub struct DirectMerge {
     pub repo_id: Uuid,
     pub merge_commit: String,
     pub target_branch_name: String,
+    /// First release tag containing `merge_commit`, once one does
+    pub release_tag: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -41,6 +72,8 @@ pub struct PrMerge {
     pub repo_id: Uuid,
     pub created_at: DateTime<Utc>,
     pub target_branch_name: String,
+    /// First release tag containing the PR's merge commit, once one does
+    pub release_tag: Option<String>,
     pub pr_info: PullRequestInfo,
 }
 
@@ -60,9 +93,35 @@ struct DirectMergeRow {
     repo_id: Uuid,
     merge_commit: Option<String>,
     target_branch_name: String,
+    release_tag: Option<String>,
     created_at: DateTime<Utc>,
 }
 
+/// Which table a merge is recorded in.
+#[derive(Debug, Clone)]
+pub enum MergeRecord {
+    Direct(Uuid),
+    Pr(String),
+}
+
+/// A merge whose commit isn't known to be in a release tag yet.
+#[derive(Debug, Clone)]
+pub struct UnreleasedMerge {
+    pub record: MergeRecord,
+    pub repo_path: String,
+    pub merge_commit: String,
+}
+
+/// A merge of a completed task's work, with the parent task it belongs to.
+#[derive(Debug, Clone)]
+pub struct CompletedTaskMerge {
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub parent_task_title: Option<String>,
+    pub repo_path: String,
+    pub merge_commit: String,
+}
+
 impl Merge {
     pub fn merge_commit(&self) -> Option<String> {
         match self {
@@ -71,6 +130,13 @@ impl Merge {
         }
     }
 
+    pub fn release_tag(&self) -> Option<&str> {
+        match self {
+            Merge::Direct(direct) => direct.release_tag.as_deref(),
+            Merge::Pr(pr) => pr.release_tag.as_deref(),
+        }
+    }
+
     /// Create a direct merge record
     pub async fn create_direct(
         pool: &SqlitePool,
@@ -101,6 +167,7 @@ impl Merge {
             repo_id,
             merge_commit: merge_commit.to_string(),
             target_branch_name: target_branch_name.to_string(),
+            release_tag: None,
             created_at: now,
         })
     }
@@ -119,6 +186,7 @@ impl Merge {
                 repo_id AS "repo_id!: Uuid",
                 merge_commit,
                 target_branch_name,
+                release_tag,
                 created_at AS "created_at!: DateTime<Utc>"
             FROM merges
             WHERE workspace_id = ? AND merge_type = 'direct'
@@ -163,6 +231,7 @@ impl Merge {
                 repo_id AS "repo_id!: Uuid",
                 merge_commit,
                 target_branch_name,
+                release_tag,
                 created_at AS "created_at!: DateTime<Utc>"
             FROM merges
             WHERE workspace_id = ? AND repo_id = ? AND merge_type = 'direct'
@@ -193,6 +262,155 @@ impl Merge {
 
         Ok(merges)
     }
+
+    /// Merges made since `since`, direct or through a PR, whose commit
+    /// hasn't been found in a release tag yet.
+    pub async fn find_unreleased(
+        pool: &SqlitePool,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<UnreleasedMerge>, sqlx::Error> {
+        let direct = sqlx::query!(
+            r#"SELECT
+                m.id AS "id!: Uuid",
+                r.path AS "repo_path!",
+                m.merge_commit AS "merge_commit!"
+            FROM merges m
+            JOIN repos r ON r.id = m.repo_id
+            WHERE m.merge_type = 'direct'
+              AND m.merge_commit IS NOT NULL
+              AND m.release_tag IS NULL
+              AND m.created_at > ?"#,
+            since,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let prs = sqlx::query!(
+            r#"SELECT
+                p.id AS "id!",
+                r.path AS "repo_path!",
+                p.merge_commit_sha AS "merge_commit!"
+            FROM pull_requests p
+            JOIN repos r ON r.id = p.repo_id
+            WHERE p.pr_status = 'merged'
+              AND p.merge_commit_sha IS NOT NULL
+              AND p.release_tag IS NULL
+              AND COALESCE(p.merged_at, p.created_at) > ?"#,
+            since,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(direct
+            .into_iter()
+            .map(|row| UnreleasedMerge {
+                record: MergeRecord::Direct(row.id),
+                repo_path: row.repo_path,
+                merge_commit: row.merge_commit,
+            })
+            .chain(prs.into_iter().map(|row| UnreleasedMerge {
+                record: MergeRecord::Pr(row.id),
+                repo_path: row.repo_path,
+                merge_commit: row.merge_commit,
+            }))
+            .collect())
+    }
+
+    pub async fn set_release_tag(
+        pool: &SqlitePool,
+        record: &MergeRecord,
+        release_tag: &str,
+    ) -> Result<(), sqlx::Error> {
+        match record {
+            MergeRecord::Direct(id) => {
+                sqlx::query!(
+                    "UPDATE merges SET release_tag = ? WHERE id = ?",
+                    release_tag,
+                    id,
+                )
+                .execute(pool)
+                .await?;
+                Ok(())
+            }
+            MergeRecord::Pr(id) => PullRequest::set_release_tag(pool, id, release_tag).await,
+        }
+    }
+
+    /// Merges, direct or through a PR, of the project's done tasks, oldest
+    /// task first.
+    pub async fn find_completed_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<CompletedTaskMerge>, sqlx::Error> {
+        sqlx::query_as!(
+            CompletedTaskMerge,
+            r#"SELECT
+                t.id AS "task_id!: Uuid",
+                t.title AS "task_title!",
+                pt.title AS "parent_task_title?",
+                r.path AS "repo_path!",
+                mc.merge_commit AS "merge_commit!"
+            FROM (
+                SELECT workspace_id, repo_id, merge_commit
+                FROM merges
+                WHERE merge_type = 'direct' AND merge_commit IS NOT NULL
+                UNION ALL
+                SELECT workspace_id, repo_id, merge_commit_sha AS merge_commit
+                FROM pull_requests
+                WHERE pr_status = 'merged'
+                  AND merge_commit_sha IS NOT NULL
+                  AND workspace_id IS NOT NULL
+            ) mc
+            JOIN workspaces w ON w.id = mc.workspace_id
+            JOIN tasks t ON t.id = w.task_id
+            JOIN repos r ON r.id = mc.repo_id
+            LEFT JOIN workspaces pw ON pw.id = t.parent_workspace_id
+            LEFT JOIN tasks pt ON pt.id = pw.task_id
+            WHERE t.project_id = ? AND t.status = 'done'
+            ORDER BY t.created_at ASC"#,
+            project_id,
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The release each workspace with this archived status shipped in: the
+    /// tag of its most recent merge, which is `None` until that merge is
+    /// released.
+    pub async fn latest_release_tags(
+        pool: &SqlitePool,
+        archived: bool,
+    ) -> Result<HashMap<Uuid, Option<String>>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT workspace_id AS "workspace_id!: Uuid", release_tag
+            FROM (
+                SELECT m.workspace_id, m.release_tag,
+                       ROW_NUMBER() OVER (
+                           PARTITION BY m.workspace_id ORDER BY m.merged_at DESC
+                       ) AS rn
+                FROM (
+                    SELECT workspace_id, release_tag, created_at AS merged_at
+                    FROM merges
+                    WHERE merge_type = 'direct'
+                    UNION ALL
+                    SELECT workspace_id, release_tag, COALESCE(merged_at, created_at) AS merged_at
+                    FROM pull_requests
+                    WHERE workspace_id IS NOT NULL AND pr_status = 'merged'
+                ) m
+                JOIN workspaces w ON w.id = m.workspace_id
+                WHERE w.archived = ?
+            )
+            WHERE rn = 1"#,
+            archived,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.workspace_id, row.release_tag))
+            .collect())
+    }
 }
 
 impl From<DirectMergeRow> for DirectMerge {
@@ -205,6 +423,7 @@ impl From<DirectMergeRow> for DirectMerge {
                 .merge_commit
                 .expect("direct merge must have merge_commit"),
             target_branch_name: row.target_branch_name,
+            release_tag: row.release_tag,
             created_at: row.created_at,
         }
     }
@@ -215,3 +434,35 @@ impl From<DirectMergeRow> for Merge {
         Merge::Direct(DirectMerge::from(row))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_status() {
+        use MergeStatus::*;
+
+        assert!(MergeStatus::aggregate(&[]).is_none());
+        assert!(matches!(
+            MergeStatus::aggregate(&[Merged, Open, Merged]),
+            Some(Open)
+        ));
+        assert!(matches!(
+            MergeStatus::aggregate(&[Merged, Closed]),
+            Some(Closed)
+        ));
+        assert!(matches!(
+            MergeStatus::aggregate(&[Merged, Merged]),
+            Some(Merged)
+        ));
+        assert!(matches!(
+            MergeStatus::aggregate(&[Closed, Closed]),
+            Some(Closed)
+        ));
+        assert!(matches!(
+            MergeStatus::aggregate(&[Merged, Unknown]),
+            Some(Unknown)
+        ));
+    }
+}
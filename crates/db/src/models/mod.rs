@@ -1,14 +1,19 @@
+pub mod activity;
+pub mod activity_cursor;
+pub mod activity_issue;
 pub mod coding_agent_turn;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
 pub mod file;
 pub mod merge;
+pub mod pending_project_link;
 pub mod project;
 pub mod pull_request;
 pub mod repo;
 pub mod requests;
 pub mod scratch;
+pub mod search;
 pub mod session;
 pub mod tag;
 pub mod task;
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A remote project with shared issues that no local project is linked to.
+#[derive(Debug, Clone, FromRow, Serialize, TS)]
+pub struct PendingProjectLink {
+    pub remote_project_id: Uuid,
+    pub organization_id: Uuid,
+    /// Remote project name as of the last catch-up.
+    pub name: String,
+    /// Shared issues that would show up once the project is linked.
+    pub issue_count: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PendingProjectLink {
+    /// Pending links, most issues first. Projects linked since the last
+    /// prune are left out.
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PendingProjectLink,
+            r#"SELECT l.remote_project_id AS "remote_project_id!: Uuid",
+                      l.organization_id AS "organization_id!: Uuid",
+                      l.name,
+                      l.issue_count,
+                      l.updated_at AS "updated_at!: DateTime<Utc>"
+               FROM pending_project_links l
+               WHERE NOT EXISTS (
+                   SELECT 1 FROM projects p WHERE p.remote_project_id = l.remote_project_id
+               )
+               ORDER BY l.issue_count DESC, l.name ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        remote_project_id: Uuid,
+        organization_id: Uuid,
+        name: &str,
+        issue_count: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO pending_project_links
+                   (remote_project_id, organization_id, name, issue_count)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(remote_project_id) DO UPDATE SET
+                   organization_id = excluded.organization_id,
+                   name = excluded.name,
+                   issue_count = excluded.issue_count,
+                   updated_at = datetime('now', 'subsec')"#,
+            remote_project_id,
+            organization_id,
+            name,
+            issue_count
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Refresh issue counts from the mirror, then drop links that no longer
+    /// apply: a local project was linked since, or no mirrored issues remain.
+    pub async fn prune(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            r#"UPDATE pending_project_links
+               SET issue_count = (
+                   SELECT COUNT(*) FROM activity_issues i
+                   WHERE i.project_id = pending_project_links.remote_project_id
+               )"#
+        )
+        .execute(&mut *tx)
+        .await?;
+        let result = sqlx::query!(
+            r#"DELETE FROM pending_project_links
+               WHERE issue_count = 0
+                  OR EXISTS (
+                      SELECT 1 FROM projects p
+                      WHERE p.remote_project_id = pending_project_links.remote_project_id
+                  )"#
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete(pool: &SqlitePool, remote_project_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM pending_project_links WHERE remote_project_id = $1",
+            remote_project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
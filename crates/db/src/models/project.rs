@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -10,6 +10,9 @@ pub struct Project {
     pub name: String,
     pub default_agent_working_dir: Option<String>,
     pub remote_project_id: Option<Uuid>,
+    /// Organization owning the linked remote project when last seen, which
+    /// decides the remote that requests for the project go to.
+    pub remote_organization_id: Option<Uuid>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -24,6 +27,7 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      remote_organization_id as "remote_organization_id: Uuid",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -33,6 +37,90 @@ impl Project {
         .await
     }
 
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      default_agent_working_dir,
+                      remote_project_id as "remote_project_id: Uuid",
+                      remote_organization_id as "remote_organization_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_remote_project_id(
+        pool: &SqlitePool,
+        remote_project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Project,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      default_agent_working_dir,
+                      remote_project_id as "remote_project_id: Uuid",
+                      remote_organization_id as "remote_organization_id: Uuid",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM projects
+               WHERE remote_project_id = $1"#,
+            remote_project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Create a project containing `repo_id`, linked to `remote_project_id`.
+    pub async fn create_linked(
+        pool: &SqlitePool,
+        name: &str,
+        repo_id: Uuid,
+        remote_project_id: Uuid,
+        remote_organization_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let project_repo_id = Uuid::new_v4();
+        let mut tx = pool.begin().await?;
+
+        let project = sqlx::query_as!(
+            Project,
+            r#"INSERT INTO projects (id, name, remote_project_id, remote_organization_id)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         name,
+                         default_agent_working_dir,
+                         remote_project_id as "remote_project_id: Uuid",
+                            remote_organization_id as "remote_organization_id: Uuid",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            remote_project_id,
+            remote_organization_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO project_repos (id, project_id, repo_id)
+               VALUES ($1, $2, $3)"#,
+            project_repo_id,
+            id,
+            repo_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(project)
+    }
+
     pub async fn set_remote_project_id(
         pool: &SqlitePool,
         id: Uuid,
@@ -50,4 +138,27 @@ impl Project {
 
         Ok(())
     }
+
+    /// Record the organization currently owning `remote_project_id` on the
+    /// project linked to it, if any.
+    pub async fn set_remote_organization<'e, E>(
+        executor: E,
+        remote_project_id: Uuid,
+        organization_id: Uuid,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query!(
+            r#"UPDATE projects
+               SET remote_organization_id = $2
+               WHERE remote_project_id = $1"#,
+            remote_project_id,
+            organization_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
 }
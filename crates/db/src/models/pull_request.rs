@@ -17,6 +17,8 @@ pub struct PullRequest {
     pub target_branch_name: String,
     pub merged_at: Option<DateTime<Utc>>,
     pub merge_commit_sha: Option<String>,
+    /// First release tag containing the merge commit, once one does
+    pub release_tag: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub synced_at: Option<DateTime<Utc>>,
@@ -89,6 +91,7 @@ impl PullRequest {
                 target_branch_name,
                 merged_at AS "merged_at: DateTime<Utc>",
                 merge_commit_sha,
+                release_tag,
                 created_at AS "created_at!: DateTime<Utc>",
                 updated_at AS "updated_at!: DateTime<Utc>",
                 synced_at AS "synced_at: DateTime<Utc>"
@@ -142,6 +145,7 @@ impl PullRequest {
                 target_branch_name,
                 merged_at AS "merged_at: DateTime<Utc>",
                 merge_commit_sha,
+                release_tag,
                 created_at AS "created_at!: DateTime<Utc>",
                 updated_at AS "updated_at!: DateTime<Utc>",
                 synced_at AS "synced_at: DateTime<Utc>"
@@ -169,6 +173,7 @@ impl PullRequest {
                 target_branch_name,
                 merged_at AS "merged_at: DateTime<Utc>",
                 merge_commit_sha,
+                release_tag,
                 created_at AS "created_at!: DateTime<Utc>",
                 updated_at AS "updated_at!: DateTime<Utc>",
                 synced_at AS "synced_at: DateTime<Utc>"
@@ -198,6 +203,7 @@ impl PullRequest {
                 target_branch_name,
                 merged_at AS "merged_at: DateTime<Utc>",
                 merge_commit_sha,
+                release_tag,
                 created_at AS "created_at!: DateTime<Utc>",
                 updated_at AS "updated_at!: DateTime<Utc>",
                 synced_at AS "synced_at: DateTime<Utc>"
@@ -211,17 +217,14 @@ impl PullRequest {
         .await
     }
 
-    pub async fn count_open_for_workspace(
+    /// Combined status of every PR opened from a workspace, see
+    /// [`MergeStatus::aggregate`].
+    pub async fn aggregate_status_for_workspace(
         pool: &SqlitePool,
         workspace_id: Uuid,
-    ) -> Result<i64, sqlx::Error> {
-        let row = sqlx::query!(
-            r#"SELECT COUNT(1) AS "count!: i64" FROM pull_requests WHERE workspace_id = ? AND pr_status = 'open'"#,
-            workspace_id,
-        )
-        .fetch_one(pool)
-        .await?;
-        Ok(row.count)
+    ) -> Result<Option<MergeStatus>, sqlx::Error> {
+        let prs = Self::find_by_workspace_id(pool, workspace_id).await?;
+        Ok(MergeStatus::aggregate(prs.iter().map(|pr| &pr.pr_status)))
     }
 
     pub async fn get_latest_for_workspaces(
@@ -240,6 +243,7 @@ impl PullRequest {
                 t.target_branch_name,
                 t.merged_at AS "merged_at: DateTime<Utc>",
                 t.merge_commit_sha,
+                t.release_tag,
                 t.created_at AS "created_at!: DateTime<Utc>",
                 t.updated_at AS "updated_at!: DateTime<Utc>",
                 t.synced_at AS "synced_at: DateTime<Utc>"
@@ -263,6 +267,35 @@ impl PullRequest {
             .collect())
     }
 
+    /// Status of every pull request of each workspace with this archived
+    /// status, oldest first.
+    pub async fn statuses_for_workspaces(
+        pool: &SqlitePool,
+        archived: bool,
+    ) -> Result<HashMap<Uuid, Vec<MergeStatus>>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT
+                t.workspace_id AS "workspace_id!: Uuid",
+                t.pr_status AS "pr_status: MergeStatus"
+            FROM pull_requests t
+            INNER JOIN workspaces w ON t.workspace_id = w.id
+            WHERE w.archived = $1
+            ORDER BY t.created_at ASC"#,
+            archived,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut statuses: HashMap<Uuid, Vec<MergeStatus>> = HashMap::new();
+        for row in rows {
+            statuses
+                .entry(row.workspace_id)
+                .or_default()
+                .push(row.pr_status);
+        }
+        Ok(statuses)
+    }
+
     pub async fn find_all_with_workspace(
         pool: &SqlitePool,
     ) -> Result<Vec<PullRequest>, sqlx::Error> {
@@ -278,6 +311,7 @@ impl PullRequest {
                 target_branch_name,
                 merged_at AS "merged_at: DateTime<Utc>",
                 merge_commit_sha,
+                release_tag,
                 created_at AS "created_at!: DateTime<Utc>",
                 updated_at AS "updated_at!: DateTime<Utc>",
                 synced_at AS "synced_at: DateTime<Utc>"
@@ -302,6 +336,7 @@ impl PullRequest {
                 target_branch_name,
                 merged_at AS "merged_at: DateTime<Utc>",
                 merge_commit_sha,
+                release_tag,
                 created_at AS "created_at!: DateTime<Utc>",
                 updated_at AS "updated_at!: DateTime<Utc>",
                 synced_at AS "synced_at: DateTime<Utc>"
@@ -331,6 +366,7 @@ impl PullRequest {
             repo_id: self.repo_id.unwrap_or_else(Uuid::nil),
             created_at: self.created_at,
             target_branch_name: self.target_branch_name.clone(),
+            release_tag: self.release_tag.clone(),
             pr_info: PullRequestInfo {
                 number: self.pr_number,
                 url: self.pr_url.clone(),
@@ -341,6 +377,21 @@ impl PullRequest {
         }
     }
 
+    pub async fn set_release_tag(
+        pool: &SqlitePool,
+        id: &str,
+        release_tag: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE pull_requests SET release_tag = ? WHERE id = ?",
+            release_tag,
+            id,
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn delete(pool: &SqlitePool, id: &str) -> Result<(), sqlx::Error> {
         sqlx::query!("DELETE FROM pull_requests WHERE id = ?", id)
             .execute(pool)
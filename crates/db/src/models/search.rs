@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Type, Serialize, Deserialize, TS)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum GlobalSearchKind {
+    Project,
+    Task,
+    Workspace,
+    /// A coding agent turn: its prompt and final summary.
+    AgentTurn,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct GlobalSearchResult {
+    pub kind: GlobalSearchKind,
+    pub id: Uuid,
+    pub title: String,
+    /// Matching excerpt with hits wrapped in `<mark>` tags.
+    pub snippet: String,
+    /// Workspace to open for workspace and agent turn results.
+    pub workspace_id: Option<Uuid>,
+    /// BM25 score; lower is a better match.
+    pub rank: f64,
+}
+
+impl GlobalSearchResult {
+    /// Ranked matches for `query` across the full-text index. Each
+    /// whitespace-separated term must match, as a prefix.
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &str,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let Some(fts_query) = fts_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        sqlx::query_as!(
+            GlobalSearchResult,
+            r#"SELECT search_entities.kind AS "kind!: GlobalSearchKind",
+                      search_entities.entity_id AS "id!: Uuid",
+                      search_index.title AS "title!: String",
+                      snippet(search_index, -1, '<mark>', '</mark>', '…', 12) AS "snippet!: String",
+                      CASE search_entities.kind
+                          WHEN 'workspace' THEN search_entities.entity_id
+                          ELSE sessions.workspace_id
+                      END AS "workspace_id: Uuid",
+                      bm25(search_index, 5.0, 1.0) AS "rank!: f64"
+               FROM search_index
+               JOIN search_entities ON search_entities.id = search_index.rowid
+               LEFT JOIN coding_agent_turns
+                   ON search_entities.kind = 'agent_turn'
+                  AND coding_agent_turns.id = search_entities.entity_id
+               LEFT JOIN execution_processes
+                   ON execution_processes.id = coding_agent_turns.execution_process_id
+               LEFT JOIN sessions ON sessions.id = execution_processes.session_id
+               WHERE search_index MATCH $1
+               ORDER BY bm25(search_index, 5.0, 1.0)
+               LIMIT $2"#,
+            fts_query,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+/// Turn free text into an FTS5 query: every term quoted (so operators and
+/// punctuation are literal) and prefix-matched. `None` if nothing is left.
+fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{term}\"*"))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fts_query;
+
+    #[test]
+    fn quotes_terms_and_drops_embedded_quotes() {
+        assert_eq!(
+            fts_query("  fix \"login\" OR bug* "),
+            Some(r#""fix"* "login"* "OR"* "bug*"*"#.to_string())
+        );
+        assert_eq!(fts_query(" \"\" "), None);
+    }
+}
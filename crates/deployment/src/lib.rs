@@ -24,6 +24,7 @@ use services::services::{
     file_search::FileSearchCache,
     filesystem::{FilesystemError, FilesystemService},
     filesystem_watcher::FilesystemWatcherError,
+    organization_remotes::OrganizationRemoteClients,
     queued_message::QueuedMessageService,
     remote_client::RemoteClient,
     repo::RepoService,
@@ -34,6 +35,7 @@ use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 use trusted_key_auth::runtime::TrustedKeyAuthRuntime;
 use utils::sentry as sentry_utils;
+use uuid::Uuid;
 use worktree_manager::WorktreeError;
 
 #[derive(Debug, Clone, Copy, Error)]
@@ -128,6 +130,61 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         Err(RemoteClientNotConfigured)
     }
 
+    /// Clients for organizations on remotes other than the default one.
+    fn organization_remote_clients(&self) -> Option<&OrganizationRemoteClients> {
+        None
+    }
+
+    /// Client for the remote hosting `organization_id`: its entry in
+    /// `organization_remotes` if any, otherwise the default remote.
+    async fn remote_client_for_org(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<RemoteClient, RemoteClientNotConfigured> {
+        let api_base = self
+            .config()
+            .read()
+            .await
+            .organization_remotes
+            .get(&organization_id)
+            .map(|remote| remote.api_base.clone());
+        let Some(api_base) = api_base else {
+            return self.remote_client();
+        };
+        let clients = self
+            .organization_remote_clients()
+            .ok_or(RemoteClientNotConfigured)?;
+        clients
+            .client(&api_base)
+            .await
+            .map_err(|_| RemoteClientNotConfigured)
+    }
+
+    /// Clients for the default remote and every distinct organization
+    /// remote, for calls that span all of the user's organizations.
+    async fn all_remote_clients(&self) -> Vec<RemoteClient> {
+        let mut api_bases: Vec<String> = self
+            .config()
+            .read()
+            .await
+            .organization_remotes
+            .values()
+            .map(|remote| remote.api_base.clone())
+            .collect();
+        api_bases.sort();
+        api_bases.dedup();
+
+        let mut clients: Vec<RemoteClient> = self.remote_client().into_iter().collect();
+        if let Some(organization_clients) = self.organization_remote_clients() {
+            for api_base in &api_bases {
+                if let Ok(client) = organization_clients.client(api_base).await {
+                    clients.push(client);
+                }
+            }
+        }
+        clients
+    }
+
     async fn update_sentry_scope(&self) -> Result<(), DeploymentError> {
         let user_id = self.user_id();
         let config = self.config().read().await;
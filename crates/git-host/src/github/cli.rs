@@ -19,7 +19,7 @@ use url::Url;
 use utils::{command_ext::NoWindowExt, shell::resolve_executable_path_blocking};
 
 use crate::types::{
-    CreatePrRequest, PrComment, PrCommentAuthor, PrReviewComment, PullRequestDetail,
+    CreatePrRequest, GitHubIssue, PrComment, PrCommentAuthor, PrReviewComment, PullRequestDetail,
     ReviewCommentUser,
 };
 
@@ -119,6 +119,22 @@ struct GhPrResponse {
     updated_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Deserialize)]
+struct GhIssueResponse {
+    number: i64,
+    title: String,
+    #[serde(default)]
+    body: String,
+    url: String,
+    #[serde(default)]
+    labels: Vec<GhLabel>,
+}
+
+#[derive(Deserialize)]
+struct GhLabel {
+    name: String,
+}
+
 #[derive(Debug, Error)]
 pub enum GhCliError {
     #[error("GitHub CLI (`gh`) executable not found or not runnable")]
@@ -400,6 +416,33 @@ impl GhCli {
         Self::parse_pr_review_comments(&raw)
     }
 
+    /// List every open issue, pull requests excluded. Pages through the REST
+    /// API, since `gh issue list` stops at its `--limit`.
+    pub fn list_open_issues(
+        &self,
+        repo_info: &GitHubRepoInfo,
+    ) -> Result<Vec<GitHubIssue>, GhCliError> {
+        let mut args = vec![
+            "api".to_string(),
+            "--paginate".to_string(),
+            format!(
+                "repos/{}/{}/issues?state=open&per_page=100",
+                repo_info.owner, repo_info.repo_name
+            ),
+            "--jq".to_string(),
+            // One JSON object per issue, in the shape `gh issue list --json` uses.
+            ".[] | select(.pull_request == null) \
+             | {number, title, body: (.body // \"\"), url: .html_url, labels: [.labels[] | {name}]}"
+                .to_string(),
+        ];
+        if let Some(ref host) = repo_info.hostname {
+            args.push("--hostname".to_string());
+            args.push(host.clone());
+        }
+        let raw = self.run(args, None)?;
+        Self::parse_issue_list(&raw)
+    }
+
     pub fn pr_checkout(
         &self,
         repo_path: &Path,
@@ -510,6 +553,26 @@ impl GhCli {
         }
     }
 
+    fn parse_issue_list(raw: &str) -> Result<Vec<GitHubIssue>, GhCliError> {
+        serde_json::Deserializer::from_str(raw)
+            .into_iter::<GhIssueResponse>()
+            .map(|issue| {
+                let issue = issue.map_err(|err| {
+                    GhCliError::UnexpectedOutput(format!(
+                        "Failed to parse gh api issues response: {err}; raw: {raw}"
+                    ))
+                })?;
+                Ok(GitHubIssue {
+                    number: issue.number,
+                    title: issue.title,
+                    body: issue.body,
+                    url: issue.url,
+                    labels: issue.labels.into_iter().map(|label| label.name).collect(),
+                })
+            })
+            .collect()
+    }
+
     fn parse_pr_comments(raw: &str) -> Result<Vec<PrComment>, GhCliError> {
         let wrapper: GhCommentsWrapper = serde_json::from_str(raw.trim()).map_err(|err| {
             GhCliError::UnexpectedOutput(format!(
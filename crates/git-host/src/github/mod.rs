@@ -14,8 +14,8 @@ use tracing::info;
 use crate::{
     GitHostProvider,
     types::{
-        CreatePrRequest, GitHostError, PrComment, PrReviewComment, ProviderKind, PullRequestDetail,
-        UnifiedPrComment,
+        CreatePrRequest, GitHostError, GitHubIssue, PrComment, PrReviewComment, ProviderKind,
+        PullRequestDetail, UnifiedPrComment,
     },
 };
 
@@ -47,6 +47,46 @@ impl GitHubProvider {
             .map_err(Into::into)
     }
 
+    /// Open issues of the repository at `remote_url`.
+    pub async fn list_open_issues(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+    ) -> Result<Vec<GitHubIssue>, GitHostError> {
+        let repo_info = self.get_repo_info(remote_url, repo_path).await?;
+        let cli = self.gh_cli.clone();
+
+        (|| async {
+            let cli = cli.clone();
+            let repo_info = repo_info.clone();
+
+            let issues = task::spawn_blocking(move || cli.list_open_issues(&repo_info))
+                .await
+                .map_err(|err| {
+                    GitHostError::Repository(format!(
+                        "Failed to execute GitHub CLI for listing issues: {err}"
+                    ))
+                })?;
+            issues.map_err(GitHostError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHostError| e.should_retry())
+        .notify(|err: &GitHostError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
     async fn fetch_general_comments(
         &self,
         cli: &GhCli,
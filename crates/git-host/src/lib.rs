@@ -1,4 +1,5 @@
 mod detection;
+mod pr_template;
 mod types;
 
 pub mod azure;
@@ -9,9 +10,10 @@ use std::path::Path;
 use async_trait::async_trait;
 use detection::detect_provider_from_url;
 use enum_dispatch::enum_dispatch;
+pub use pr_template::find_pr_template;
 pub use types::{
-    CreatePrRequest, GitHostError, PrComment, PrCommentAuthor, PrReviewComment, ProviderKind,
-    PullRequestDetail, ReviewCommentUser, UnifiedPrComment,
+    CreatePrRequest, GitHostError, GitHubIssue, PrComment, PrCommentAuthor, PrReviewComment,
+    ProviderKind, PullRequestDetail, ReviewCommentUser, UnifiedPrComment,
 };
 
 use self::{azure::AzureDevOpsProvider, github::GitHubProvider};
@@ -0,0 +1,26 @@
+//! Pull request templates checked into a repository.
+
+use std::path::Path;
+
+/// Where GitHub and Azure DevOps look for a default template, in the order
+/// they prefer them.
+const TEMPLATE_PATHS: &[&str] = &[
+    ".github/pull_request_template.md",
+    ".github/PULL_REQUEST_TEMPLATE.md",
+    "pull_request_template.md",
+    "PULL_REQUEST_TEMPLATE.md",
+    "docs/pull_request_template.md",
+    "docs/PULL_REQUEST_TEMPLATE.md",
+    ".azuredevops/pull_request_template.md",
+];
+
+/// The repository's default pull request template, if it has a non-empty
+/// one. Templates meant to be picked from a directory of several aren't
+/// used.
+pub fn find_pr_template(repo_path: &Path) -> Option<String> {
+    TEMPLATE_PATHS.iter().find_map(|path| {
+        let template = std::fs::read_to_string(repo_path.join(path)).ok()?;
+        let template = template.trim();
+        (!template.is_empty()).then(|| template.to_string())
+    })
+}
@@ -139,6 +139,16 @@ impl UnifiedPrComment {
     }
 }
 
+/// An open issue in a GitHub repository.
+#[derive(Debug, Clone)]
+pub struct GitHubIssue {
+    pub number: i64,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+    pub labels: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct PullRequestDetail {
     pub number: i64,
@@ -0,0 +1,189 @@
+//! Branch names generated from a user-configured template such as
+//! `{prefix}/{task_key}-{slug}`, so workspace branches can follow an
+//! organization's naming rules.
+
+use crate::validation::is_valid_branch_name;
+
+/// Template matching the names generated before templates existed.
+pub const DEFAULT_BRANCH_TEMPLATE: &str = "{prefix}/{id}-{slug}";
+
+const PLACEHOLDERS: &[&str] = &["prefix", "id", "task_key", "slug", "username"];
+
+/// Values substituted into a branch template. Missing ones render empty.
+#[derive(Debug, Clone, Default)]
+pub struct BranchNameVars<'a> {
+    pub prefix: &'a str,
+    /// Short workspace ID, which keeps names unique.
+    pub id: &'a str,
+    /// Key of the linked issue, e.g. `VK-42`.
+    pub task_key: Option<&'a str>,
+    pub slug: &'a str,
+    pub username: Option<&'a str>,
+}
+
+impl BranchNameVars<'_> {
+    fn get(&self, placeholder: &str) -> Option<&str> {
+        match placeholder {
+            "prefix" => Some(self.prefix),
+            "id" => Some(self.id),
+            "task_key" => self.task_key,
+            "slug" => Some(self.slug),
+            "username" => self.username,
+            _ => None,
+        }
+    }
+}
+
+/// Whether `template` refers to `placeholder`, e.g. to skip looking up a
+/// value it doesn't use.
+pub fn branch_template_uses(template: &str, placeholder: &str) -> bool {
+    template.contains(&format!("{{{placeholder}}}"))
+}
+
+/// Check that `template` only uses known placeholders, includes `{id}` so
+/// names stay unique, and renders to a valid branch name.
+pub fn validate_branch_template(template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            return Err("Unclosed `{` in branch template".to_string());
+        };
+        let placeholder = &rest[start + 1..start + end];
+        if !PLACEHOLDERS.contains(&placeholder) {
+            return Err(format!(
+                "Unknown placeholder `{{{placeholder}}}` in branch template; use one of {}",
+                PLACEHOLDERS
+                    .iter()
+                    .map(|p| format!("{{{p}}}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    if !branch_template_uses(template, "id") {
+        return Err("Branch template must include {id}".to_string());
+    }
+
+    let sample = render_branch_name(
+        template,
+        &BranchNameVars {
+            prefix: "vk",
+            id: "a1b2",
+            task_key: Some("VK-42"),
+            slug: "fix-login",
+            username: Some("octocat"),
+        },
+    );
+    if is_valid_branch_name(&sample) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Branch template renders an invalid branch name, e.g. `{sample}`"
+        ))
+    }
+}
+
+/// Render `template`, dropping separators left dangling by empty values so
+/// an unset prefix gives `a1b2-fix-login` rather than `/a1b2-fix-login`.
+pub fn render_branch_name(template: &str, vars: &BranchNameVars) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        if let Some(value) = vars.get(&rest[start + 1..start + end]) {
+            rendered.push_str(&sanitize(value));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    rendered.push_str(rest);
+
+    rendered
+        .split('/')
+        .map(|segment| segment.trim_matches(['-', '_', '.']))
+        .filter(|segment| !segment.is_empty())
+        .map(collapse_hyphens)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn collapse_hyphens(segment: &str) -> String {
+    let mut collapsed = String::with_capacity(segment.len());
+    for c in segment.chars() {
+        if !(c == '-' && collapsed.ends_with('-')) {
+            collapsed.push(c);
+        }
+    }
+    collapsed
+}
+
+/// Replace characters that can't appear in a ref, and slashes, with hyphens.
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars<'a>(prefix: &'a str, task_key: Option<&'a str>) -> BranchNameVars<'a> {
+        BranchNameVars {
+            prefix,
+            id: "a1b2",
+            task_key,
+            slug: "fix-login",
+            username: Some("octocat"),
+        }
+    }
+
+    #[test]
+    fn test_default_template_matches_legacy_names() {
+        assert_eq!(
+            render_branch_name(DEFAULT_BRANCH_TEMPLATE, &vars("vk", None)),
+            "vk/a1b2-fix-login"
+        );
+        assert_eq!(
+            render_branch_name(DEFAULT_BRANCH_TEMPLATE, &vars("", None)),
+            "a1b2-fix-login"
+        );
+    }
+
+    #[test]
+    fn test_missing_values_drop_separators() {
+        let template = "{username}/{task_key}-{id}-{slug}";
+        assert_eq!(
+            render_branch_name(template, &vars("vk", Some("VK-42"))),
+            "octocat/VK-42-a1b2-fix-login"
+        );
+        assert_eq!(
+            render_branch_name(template, &vars("vk", None)),
+            "octocat/a1b2-fix-login"
+        );
+        assert_eq!(
+            render_branch_name("{id}-{task_key}-{slug}", &vars("vk", None)),
+            "a1b2-fix-login"
+        );
+    }
+
+    #[test]
+    fn test_validate_branch_template() {
+        assert!(validate_branch_template(DEFAULT_BRANCH_TEMPLATE).is_ok());
+        assert!(validate_branch_template("feature/{task_key}-{id}").is_ok());
+        assert!(validate_branch_template("{prefix}/{slug}").is_err());
+        assert!(validate_branch_template("{prefix}/{id}-{title}").is_err());
+        assert!(validate_branch_template("{prefix}/{id").is_err());
+        assert!(validate_branch_template("{id}..{slug}").is_err());
+    }
+}
@@ -25,7 +25,7 @@ use std::{
 use thiserror::Error;
 use utils::{path::ALWAYS_SKIP_DIRS, shell::resolve_executable_path_blocking};
 
-use super::Commit;
+use super::{Commit, credentials};
 
 #[derive(Debug, Error)]
 pub enum GitCliError {
@@ -107,6 +107,109 @@ impl GitCli {
         Ok(())
     }
 
+    /// Add a worktree for an existing branch that only materializes
+    /// `directories`: the worktree is created without a checkout, restricted
+    /// with cone-mode sparse-checkout, then populated, so files outside are
+    /// never written.
+    pub fn worktree_add_sparse(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch: &str,
+        directories: &[String],
+    ) -> Result<(), GitCliError> {
+        self.ensure_available()?;
+
+        let args: Vec<OsString> = vec![
+            "worktree".into(),
+            "add".into(),
+            "--no-checkout".into(),
+            worktree_path.as_os_str().into(),
+            OsString::from(branch),
+        ];
+        self.git(repo_path, args)?;
+        self.sparse_checkout_set(worktree_path, directories)?;
+        self.git(worktree_path, ["read-tree", "-mu", "HEAD"])?;
+        Ok(())
+    }
+
+    /// Run `git sparse-checkout set --cone -- <directories>`
+    pub fn sparse_checkout_set(
+        &self,
+        worktree_path: &Path,
+        directories: &[String],
+    ) -> Result<(), GitCliError> {
+        let mut args: Vec<OsString> = vec![
+            "sparse-checkout".into(),
+            "set".into(),
+            "--cone".into(),
+            "--".into(),
+        ];
+        args.extend(directories.iter().map(OsString::from));
+        self.git(worktree_path, args)?;
+        Ok(())
+    }
+
+    /// Whether the worktree has sparse-checkout enabled
+    pub fn is_sparse_checkout(&self, worktree_path: &Path) -> bool {
+        // `git config` exits non-zero when the key is unset.
+        self.git(worktree_path, ["config", "--bool", "core.sparseCheckout"])
+            .is_ok_and(|out| out.trim() == "true")
+    }
+
+    /// Run `git clone --filter=<filter_spec> <url> <target>` from the target's
+    /// parent directory
+    pub fn clone_filtered(
+        &self,
+        url: &str,
+        target_path: &Path,
+        filter_spec: &str,
+    ) -> Result<(), GitCliError> {
+        let parent = target_path
+            .parent()
+            .ok_or_else(|| GitCliError::CommandFailed("Invalid clone target path".to_string()))?;
+        let args: Vec<OsString> = vec![
+            "clone".into(),
+            format!("--filter={filter_spec}").into(),
+            OsString::from(url),
+            target_path.as_os_str().into(),
+        ];
+        let envs = self.remote_envs(parent);
+        match self.git_with_env(parent, args, &envs) {
+            Ok(_) => Ok(()),
+            Err(GitCliError::CommandFailed(msg)) => {
+                Err(self.classify_remote_error(parent, url, msg))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Whether the repository was cloned with `--filter` and may be missing
+    /// objects locally.
+    pub fn is_partial_clone(&self, repo_path: &Path) -> bool {
+        self.config_value(repo_path, "extensions.partialClone")
+            .is_some()
+    }
+
+    /// Fetch the blobs that differ between `from` and `to` (or the worktree
+    /// when `to` is `None`) from the promisor remote in one batch, so libgit2
+    /// can read them afterwards. `git diff` does the batching for us.
+    pub fn prefetch_diff_blobs(
+        &self,
+        repo_path: &Path,
+        from: &str,
+        to: Option<&str>,
+    ) -> Result<(), GitCliError> {
+        let mut args: Vec<OsString> = vec!["diff".into(), "--numstat".into(), from.into()];
+        if let Some(to) = to {
+            args.push(to.into());
+        }
+        args.push("--".into());
+        let envs = self.remote_envs(repo_path);
+        self.git_with_env(repo_path, args, &envs)?;
+        Ok(())
+    }
+
     /// Run `git -C <repo> worktree remove <path>`
     pub fn worktree_remove(
         &self,
@@ -349,7 +452,7 @@ impl GitCli {
         remote_url: &str,
         refspec: &str,
     ) -> Result<(), GitCliError> {
-        let envs = vec![(OsString::from("GIT_TERMINAL_PROMPT"), OsString::from("0"))];
+        let envs = self.remote_envs(repo_path);
 
         let args = [
             OsString::from("fetch"),
@@ -359,7 +462,9 @@ impl GitCli {
 
         match self.git_with_env(repo_path, args, &envs) {
             Ok(_) => Ok(()),
-            Err(GitCliError::CommandFailed(msg)) => Err(self.classify_cli_error(msg)),
+            Err(GitCliError::CommandFailed(msg)) => {
+                Err(self.classify_remote_error(repo_path, remote_url, msg))
+            }
             Err(err) => Err(err),
         }
     }
@@ -377,7 +482,7 @@ impl GitCli {
         } else {
             format!("refs/heads/{branch}:refs/heads/{branch}")
         };
-        let envs = vec![(OsString::from("GIT_TERMINAL_PROMPT"), OsString::from("0"))];
+        let envs = self.remote_envs(repo_path);
 
         let args = [
             OsString::from("push"),
@@ -387,7 +492,9 @@ impl GitCli {
 
         match self.git_with_env(repo_path, args, &envs) {
             Ok(_) => Ok(()),
-            Err(GitCliError::CommandFailed(msg)) => Err(self.classify_cli_error(msg)),
+            Err(GitCliError::CommandFailed(msg)) => {
+                Err(self.classify_remote_error(repo_path, remote_url, msg))
+            }
             Err(err) => Err(err),
         }
     }
@@ -399,7 +506,7 @@ impl GitCli {
         remote_url: &str,
         branch_name: &str,
     ) -> Result<bool, GitCliError> {
-        let envs = vec![(OsString::from("GIT_TERMINAL_PROMPT"), OsString::from("0"))];
+        let envs = self.remote_envs(repo_path);
 
         let args = [
             OsString::from("ls-remote"),
@@ -410,7 +517,9 @@ impl GitCli {
 
         match self.git_with_env(repo_path, args, &envs) {
             Ok(output) => Ok(!output.trim().is_empty()),
-            Err(GitCliError::CommandFailed(msg)) => Err(self.classify_cli_error(msg)),
+            Err(GitCliError::CommandFailed(msg)) => {
+                Err(self.classify_remote_error(repo_path, remote_url, msg))
+            }
             Err(err) => Err(err),
         }
     }
@@ -716,6 +825,8 @@ impl GitCli {
         if lower.contains("authentication failed")
             || lower.contains("could not read username")
             || lower.contains("invalid username or password")
+            || lower.contains("permission denied (publickey")
+            || lower.contains("host key verification failed")
         {
             GitCliError::AuthFailed(msg)
         } else if lower.contains("non-fast-forward")
@@ -729,6 +840,51 @@ impl GitCli {
         }
     }
 
+    /// Like [`Self::classify_cli_error`], adding what's likely missing when
+    /// authentication to `remote_url` failed.
+    fn classify_remote_error(
+        &self,
+        repo_path: &Path,
+        remote_url: &str,
+        msg: String,
+    ) -> GitCliError {
+        match self.classify_cli_error(msg) {
+            GitCliError::AuthFailed(msg) => {
+                let helper_configured = self.config_value(repo_path, "credential.helper").is_some();
+                match credentials::auth_hint(remote_url, helper_configured) {
+                    Some(hint) => GitCliError::AuthFailed(format!("{msg}\n{hint}")),
+                    None => GitCliError::AuthFailed(msg),
+                }
+            }
+            err => err,
+        }
+    }
+
+    /// Environment for commands that talk to a remote. Nothing may prompt,
+    /// so a missing credential fails the operation instead of hanging it:
+    /// HTTPS remotes get credentials from the user's helpers only, and SSH
+    /// runs in batch mode unless the user configured their own command.
+    fn remote_envs(&self, repo_path: &Path) -> Vec<(OsString, OsString)> {
+        let mut envs = vec![(OsString::from("GIT_TERMINAL_PROMPT"), OsString::from("0"))];
+        let ssh_configured = std::env::var_os("GIT_SSH_COMMAND").is_some()
+            || std::env::var_os("GIT_SSH").is_some()
+            || self.config_value(repo_path, "core.sshCommand").is_some();
+        if !ssh_configured {
+            envs.push((
+                OsString::from("GIT_SSH_COMMAND"),
+                OsString::from("ssh -o BatchMode=yes"),
+            ));
+        }
+        envs
+    }
+
+    /// Effective value of a config key in `repo_path`, if set and non-empty.
+    fn config_value(&self, repo_path: &Path, key: &str) -> Option<String> {
+        let value = self.git(repo_path, ["config", "--get", key]).ok()?;
+        let value = value.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
     /// Ensure `git` is available on PATH
     fn ensure_available(&self) -> Result<(), GitCliError> {
         use utils::command_ext::NoWindowExt;
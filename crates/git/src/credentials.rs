@@ -0,0 +1,126 @@
+//! Authentication for remotes on any host, not just GitHub. HTTPS remotes use
+//! the user's git credential helpers and SSH remotes their agent or default
+//! keys, so nothing beyond an optional token is stored by us.
+
+use git2::{Cred, CredentialType, RemoteCallbacks};
+
+/// Keys under `~/.ssh` tried in order when the agent has none that work.
+const DEFAULT_SSH_KEYS: &[&str] = &["id_ed25519", "id_ecdsa", "id_rsa"];
+
+/// Whether an SSH agent is reachable. Windows' OpenSSH agent listens on a
+/// fixed named pipe, so it's assumed there.
+pub(crate) fn ssh_agent_available() -> bool {
+    cfg!(windows) || std::env::var_os("SSH_AUTH_SOCK").is_some()
+}
+
+/// Whether `url` is reached over SSH, including scp-like `user@host:path`.
+pub(crate) fn is_ssh_url(url: &str) -> bool {
+    if let Some((scheme, _)) = url.split_once("://") {
+        return scheme == "ssh" || scheme == "git+ssh";
+    }
+    // A single letter before the colon is a Windows drive, not a host.
+    url.split_once(':')
+        .is_some_and(|(host, _)| host.len() > 1 && !host.contains(['/', '\\']))
+}
+
+/// Which credentials have been offered so far. libgit2 calls back again
+/// after a rejected credential, so each call offers the next one.
+#[derive(Default)]
+struct Offered {
+    token: bool,
+    helper: bool,
+    agent: bool,
+    key_files: usize,
+    username: bool,
+}
+
+/// Callbacks for libgit2 network operations offering, in order: `token`,
+/// the user's credential helpers, their SSH agent, then their default keys.
+pub(crate) fn remote_callbacks(token: Option<&str>) -> RemoteCallbacks<'_> {
+    let mut offered = Offered::default();
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username_from_url, allowed| {
+        let username = username_from_url.unwrap_or("git");
+
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if let Some(token) = token
+                && !offered.token
+            {
+                offered.token = true;
+                return Cred::userpass_plaintext(username, token);
+            }
+            if !offered.helper {
+                offered.helper = true;
+                if let Ok(config) = git2::Config::open_default()
+                    && let Ok(cred) = Cred::credential_helper(&config, url, username_from_url)
+                {
+                    return Ok(cred);
+                }
+            }
+        }
+
+        if allowed.contains(CredentialType::SSH_KEY) {
+            if !offered.agent {
+                offered.agent = true;
+                if ssh_agent_available()
+                    && let Ok(cred) = Cred::ssh_key_from_agent(username)
+                {
+                    return Ok(cred);
+                }
+            }
+            if let Some(ssh_dir) = dirs::home_dir().map(|home| home.join(".ssh")) {
+                while let Some(key) = DEFAULT_SSH_KEYS.get(offered.key_files) {
+                    offered.key_files += 1;
+                    let key_path = ssh_dir.join(key);
+                    if key_path.exists() {
+                        return Cred::ssh_key(username, None, &key_path, None);
+                    }
+                }
+            }
+        }
+
+        if allowed.contains(CredentialType::USERNAME) && !offered.username {
+            offered.username = true;
+            return Cred::username(username);
+        }
+
+        Err(git2::Error::from_str(&format!(
+            "no credentials accepted for {url}; configure a git credential helper or SSH agent"
+        )))
+    });
+    callbacks
+}
+
+/// What to tell the user when authenticating to `remote_url` failed.
+pub(crate) fn auth_hint(
+    remote_url: &str,
+    credential_helper_configured: bool,
+) -> Option<&'static str> {
+    if is_ssh_url(remote_url) {
+        (!ssh_agent_available())
+            .then_some("No SSH agent is running; start one and add your key to it")
+    } else {
+        (!credential_helper_configured)
+            .then_some("No git credential helper is configured; set `credential.helper` to sign in to this remote")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssh_urls() {
+        assert!(is_ssh_url("git@gitlab.com:group/repo.git"));
+        assert!(is_ssh_url("ssh://git@git.example.com:2222/repo.git"));
+        assert!(is_ssh_url("git+ssh://git@example.com/repo.git"));
+    }
+
+    #[test]
+    fn test_non_ssh_urls() {
+        assert!(!is_ssh_url("https://gitlab.com/group/repo.git"));
+        assert!(!is_ssh_url("file:///srv/git/repo.git"));
+        assert!(!is_ssh_url("/srv/git/repo.git"));
+        assert!(!is_ssh_url("C:\\git\\repo.git"));
+    }
+}
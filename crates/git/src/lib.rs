@@ -10,13 +10,19 @@ use thiserror::Error;
 use ts_rs::TS;
 use utils::diff::{Diff, DiffChangeKind};
 
+mod branch_name;
 mod cli;
+mod credentials;
 mod validation;
 
+pub use branch_name::{
+    BranchNameVars, DEFAULT_BRANCH_TEMPLATE, branch_template_uses, render_branch_name,
+    validate_branch_template,
+};
 use cli::{ChangeType, StatusDiffEntry, StatusDiffOptions};
 pub use cli::{GitCli, GitCliError, StatusEntry, WorktreeStatus};
 pub use utils::path::ALWAYS_SKIP_DIRS;
-pub use validation::is_valid_branch_prefix;
+pub use validation::{is_valid_branch_name, is_valid_branch_prefix, is_valid_sparse_checkout_dir};
 
 /// Statistics for a single file based on git history
 #[derive(Clone, Debug)]
@@ -72,6 +78,30 @@ pub enum ConflictOp {
     Revert,
 }
 
+/// Objects a partial clone leaves on the remote until they're needed.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum CloneFilter {
+    /// Full clone.
+    #[default]
+    None,
+    /// File contents are fetched when checked out. Treeless clones aren't
+    /// offered because libgit2 can't fetch missing trees on demand; saved
+    /// `treeless` settings load as blobless.
+    #[serde(alias = "treeless")]
+    Blobless,
+}
+
+impl CloneFilter {
+    fn filter_spec(self) -> Option<&'static str> {
+        match self {
+            CloneFilter::None => None,
+            CloneFilter::Blobless => Some("blob:none"),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, TS)]
 pub struct GitBranch {
     pub name: String,
@@ -323,6 +353,67 @@ impl GitService {
         Ok(true)
     }
 
+    /// Replace the messages of commits on the checked-out branch after
+    /// `since` with what `reword` returns for them, keeping authors, dates
+    /// and trees. Commits already on a remote-tracking branch, and history
+    /// containing merges, are left alone. Returns how many were reworded.
+    pub fn reword_unpushed_commits(
+        &self,
+        worktree_path: &Path,
+        since: &str,
+        reword: impl Fn(&str) -> Option<String>,
+    ) -> Result<usize, GitServiceError> {
+        let repo = Repository::open(worktree_path)?;
+        let mut head = repo.head()?;
+        if !head.is_branch() {
+            return Ok(0);
+        }
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+        revwalk.push(head.peel_to_commit()?.id())?;
+        revwalk.hide(git2::Oid::from_str(since)?)?;
+        revwalk.hide_glob("refs/remotes/*")?;
+        let commits = revwalk
+            .map(|oid| Ok(repo.find_commit(oid?)?))
+            .collect::<Result<Vec<_>, GitServiceError>>()?;
+        if commits.iter().any(|commit| commit.parent_count() != 1) {
+            return Ok(0);
+        }
+        let Some(first) = commits
+            .iter()
+            .position(|commit| reword(commit.message().unwrap_or_default()).is_some())
+        else {
+            return Ok(0);
+        };
+
+        // Everything after the first reworded commit gets a new parent, so
+        // it's recreated too, with its message reworded only if needed.
+        let mut parent = commits[first].parent(0)?;
+        let mut reworded = 0;
+        for commit in &commits[first..] {
+            let original = commit.message().unwrap_or_default();
+            let message = match reword(original) {
+                Some(message) => {
+                    reworded += 1;
+                    message
+                }
+                None => original.to_string(),
+            };
+            let oid = repo.commit(
+                None,
+                &commit.author(),
+                &commit.committer(),
+                &message,
+                &commit.tree()?,
+                &[&parent],
+            )?;
+            parent = repo.find_commit(oid)?;
+        }
+        head.set_target(parent.id(), "Reword commits to the commit convention")?;
+        Ok(reworded)
+    }
+
     /// Get worktree diffs against a base commit
     pub fn get_diffs(
         &self,
@@ -340,6 +431,14 @@ impl GitService {
             })?;
 
         let git = GitCli::new();
+        // Base-side contents are read through libgit2, which can't fetch
+        // blobs a partial clone left on the remote.
+        if git.is_partial_clone(worktree_path) {
+            git.prefetch_diff_blobs(worktree_path, &base_commit.to_string(), None)
+                .map_err(|e| {
+                    GitServiceError::InvalidRepository(format!("git diff prefetch failed: {e}"))
+                })?;
+        }
         let cli_opts = StatusDiffOptions {
             path_filter: path_filter.map(|fs| fs.iter().map(|s| s.to_string()).collect()),
         };
@@ -858,6 +957,111 @@ impl GitService {
         Ok(oid)
     }
 
+    /// Fetch the default remote's tags, and with them the commits they point
+    /// at, so merges made on the host can be found locally.
+    pub fn fetch_tags(&self, repo_path: &Path) -> Result<(), GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let remote = self.default_remote(&repo, repo_path)?;
+        GitCli::new().fetch_with_refspec(repo_path, &remote.url, "+refs/tags/*:refs/tags/*")?;
+        Ok(())
+    }
+
+    /// For each of `commit_shas`, the first release it shipped in: the
+    /// oldest [release tag](is_release_tag) on a commit that contains it.
+    /// The tags are resolved once for all the commits. Commits that aren't
+    /// in this repository or aren't released yet are left out.
+    pub fn first_releases_containing(
+        &self,
+        repo_path: &Path,
+        commit_shas: &[String],
+    ) -> Result<HashMap<String, String>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let mut releases = Vec::new();
+        for name in repo.tag_names(None)?.iter().flatten() {
+            if !is_release_tag(name) {
+                continue;
+            }
+            let Ok(tagged) = repo
+                .revparse_single(&format!("refs/tags/{name}"))
+                .and_then(|object| object.peel_to_commit())
+            else {
+                continue;
+            };
+            releases.push((tagged.time().seconds(), tagged.id(), name.to_string()));
+        }
+        releases.sort();
+
+        let mut first = HashMap::new();
+        for sha in commit_shas {
+            let Ok(commit) = git2::Oid::from_str(sha).and_then(|oid| repo.find_commit(oid)) else {
+                continue;
+            };
+            let committed = commit.time().seconds();
+            for (time, tagged, name) in &releases {
+                // A release cut before the commit was made can't contain it,
+                // which keeps unreleased commits from walking every tag.
+                if *time < committed {
+                    continue;
+                }
+                if *tagged == commit.id() || repo.graph_descendant_of(*tagged, commit.id())? {
+                    first.insert(sha.clone(), name.clone());
+                    break;
+                }
+            }
+        }
+        Ok(first)
+    }
+
+    /// Which of `commit_shas` are reachable from `to_ref` but not from
+    /// `from_ref`, like `git log from_ref..to_ref`. `None` if either ref
+    /// doesn't exist in this repository.
+    pub fn commits_in_range(
+        &self,
+        repo_path: &Path,
+        from_ref: Option<&str>,
+        to_ref: &str,
+        commit_shas: &[String],
+    ) -> Result<Option<HashSet<String>>, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let resolve = |name: &str| {
+            repo.revparse_single(name)
+                .and_then(|object| object.peel_to_commit())
+                .map(|commit| commit.id())
+                .ok()
+        };
+        let Some(to) = resolve(to_ref) else {
+            return Ok(None);
+        };
+        let from = match from_ref {
+            Some(name) => match resolve(name) {
+                Some(oid) => Some(oid),
+                None => return Ok(None),
+            },
+            None => None,
+        };
+
+        let wanted: HashSet<git2::Oid> = commit_shas
+            .iter()
+            .filter_map(|sha| git2::Oid::from_str(sha).ok())
+            .collect();
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(to)?;
+        if let Some(from) = from {
+            revwalk.hide(from)?;
+        }
+        let mut found = HashSet::new();
+        for oid in revwalk {
+            let oid = oid?;
+            if wanted.contains(&oid) {
+                found.insert(oid.to_string());
+                if found.len() == wanted.len() {
+                    break;
+                }
+            }
+        }
+        Ok(Some(found))
+    }
+
     pub fn get_fork_point(
         &self,
         worktree_path: &Path,
@@ -956,6 +1160,61 @@ impl GitService {
         Ok(())
     }
 
+    /// Discard all changes in a worktree and detach it from its branch so it
+    /// can be handed to another branch later. Ignored files are kept.
+    pub fn park_worktree(&self, worktree_path: &Path) -> Result<(), GitServiceError> {
+        let cli = GitCli::new();
+        if cli.is_rebase_in_progress(worktree_path).unwrap_or(false) {
+            let _ = cli.abort_rebase(worktree_path);
+        }
+        if cli.is_merge_in_progress(worktree_path).unwrap_or(false) {
+            let _ = cli.abort_merge(worktree_path);
+        }
+        for args in [
+            &["reset", "--hard"][..],
+            &["clean", "-fd"][..],
+            &["checkout", "--detach"][..],
+        ] {
+            cli.git(worktree_path, args).map_err(|e| {
+                GitServiceError::InvalidRepository(format!("git {} failed: {e}", args[0]))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Check out `branch` in an existing worktree, discarding local changes.
+    pub fn checkout_worktree_branch(
+        &self,
+        worktree_path: &Path,
+        branch: &str,
+    ) -> Result<(), GitServiceError> {
+        let cli = GitCli::new();
+        cli.git(worktree_path, ["checkout", "--force", branch])
+            .map_err(|e| GitServiceError::InvalidRepository(format!("git checkout failed: {e}")))?;
+        // Reapply sparse-checkout if configured (non-fatal)
+        let _ = cli.git(worktree_path, ["sparse-checkout", "reapply"]);
+        Ok(())
+    }
+
+    /// Add a worktree for an existing branch with only `directories`
+    /// checked out
+    pub fn add_sparse_worktree(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        branch: &str,
+        directories: &[String],
+    ) -> Result<(), GitServiceError> {
+        let git = GitCli::new();
+        git.worktree_add_sparse(repo_path, worktree_path, branch, directories)
+            .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
+        Ok(())
+    }
+
+    pub fn is_sparse_worktree(&self, worktree_path: &Path) -> bool {
+        GitCli::new().is_sparse_checkout(worktree_path)
+    }
+
     /// Add a worktree for a branch, optionally creating the branch
     pub fn add_worktree(
         &self,
@@ -1088,6 +1347,22 @@ impl GitService {
         commit_message: &str,
         base_branch_name: &str,
     ) -> Result<git2::Oid, GitServiceError> {
+        // libgit2 reads every blob the merge touches, so a partial clone has
+        // to fetch both sides' changes since the merge base first.
+        let repo_path = repo.workdir().unwrap_or(repo.path());
+        let git = GitCli::new();
+        if git.is_partial_clone(repo_path) {
+            let merge_base = repo
+                .merge_base(base_commit.id(), task_commit.id())?
+                .to_string();
+            for side in [base_commit.id(), task_commit.id()] {
+                git.prefetch_diff_blobs(repo_path, &merge_base, Some(&side.to_string()))
+                    .map_err(|e| {
+                        GitServiceError::InvalidRepository(format!("git diff prefetch failed: {e}"))
+                    })?;
+            }
+        }
+
         // In-memory merge to detect conflicts without touching the working tree
         let mut merge_opts = git2::MergeOptions::new();
         // Safety and correctness options
@@ -1525,7 +1800,7 @@ impl GitService {
 
         let git_cli = GitCli::new();
         if let Err(e) = git_cli.fetch_with_refspec(repo.path(), remote_url, refspec) {
-            tracing::error!("Fetch from GitHub failed: {}", e);
+            tracing::error!("Fetch from remote failed: {}", e);
             return Err(e.into());
         }
         Ok(())
@@ -1561,46 +1836,36 @@ impl GitService {
         self.fetch_from_remote(repo, remote, &refspec)
     }
 
-    /// Clone a repository to the specified directory
-    #[cfg(feature = "cloud")]
+    /// Clone a repository to the specified directory, authenticating with
+    /// `token` if given, else the user's credential helpers or SSH agent.
+    /// Partial clones go through the git CLI, which libgit2 can't do, and
+    /// never use `token`.
     pub fn clone_repository(
         clone_url: &str,
         target_path: &Path,
         token: Option<&str>,
+        filter: CloneFilter,
     ) -> Result<Repository, GitServiceError> {
-        use git2::{Cred, FetchOptions, RemoteCallbacks};
+        use git2::FetchOptions;
 
         if let Some(parent) = target_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        // Set up callbacks for authentication if token is provided
-        let mut callbacks = RemoteCallbacks::new();
-        if let Some(token) = token {
-            callbacks.credentials(|_url, username_from_url, _allowed_types| {
-                Cred::userpass_plaintext(username_from_url.unwrap_or("git"), token)
-            });
-        } else {
-            // Fallback to SSH agent and key file authentication
-            callbacks.credentials(|_url, username_from_url, _| {
-                // Try SSH agent first
-                if let Some(username) = username_from_url
-                    && let Ok(cred) = Cred::ssh_key_from_agent(username)
-                {
-                    return Ok(cred);
-                }
-
-                // Fallback to key file (~/.ssh/id_rsa)
-                let home = dirs::home_dir()
-                    .ok_or_else(|| git2::Error::from_str("Could not find home directory"))?;
-                let key_path = home.join(".ssh").join("id_rsa");
-                Cred::ssh_key(username_from_url.unwrap_or("git"), None, &key_path, None)
-            });
+        if let Some(filter_spec) = filter.filter_spec() {
+            GitCli::new().clone_filtered(clone_url, target_path, filter_spec)?;
+            tracing::info!(
+                "Successfully cloned repository from {} to {} (filter {})",
+                clone_url,
+                target_path.display(),
+                filter_spec
+            );
+            return Ok(Repository::open(target_path)?);
         }
 
         // Set up fetch options with our callbacks
         let mut fetch_opts = FetchOptions::new();
-        fetch_opts.remote_callbacks(callbacks);
+        fetch_opts.remote_callbacks(credentials::remote_callbacks(token));
 
         // Create a repository builder with fetch options
         let mut builder = git2::build::RepoBuilder::new();
@@ -1720,3 +1985,17 @@ pub fn compute_line_change_counts(old: &str, new: &str) -> (usize, usize) {
         }
     }
 }
+
+/// Whether `name` looks like a release: a version such as `v1.2.3`, `1.2`
+/// or `v2.0.0-rc.1`, optionally after a package prefix (`app@1.2.3`,
+/// `app/v1.2.3`, `app-v1.2.3`). Other tags, like deploy markers, are ignored.
+pub fn is_release_tag(name: &str) -> bool {
+    let last = name.rsplit(['/', '@']).next().unwrap_or(name);
+    let version = last.rsplit_once("-v").map_or(last, |(_, version)| version);
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let core = version.split(['-', '+']).next().unwrap_or(version);
+    (2..=3).contains(&core.split('.').count())
+        && core
+            .split('.')
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
@@ -10,6 +10,24 @@ pub fn is_valid_branch_prefix(prefix: &str) -> bool {
     git2::Branch::name_is_valid(&format!("{prefix}/x")).unwrap_or_default()
 }
 
+pub fn is_valid_branch_name(name: &str) -> bool {
+    git2::Branch::name_is_valid(name).unwrap_or_default()
+}
+
+/// A cone-mode sparse-checkout directory: relative, forward slashes, no
+/// `.`/`..` components, and nothing git could read as an option.
+pub fn is_valid_sparse_checkout_dir(dir: &str) -> bool {
+    if dir.is_empty() || dir.starts_with('-') || dir.starts_with('/') {
+        return false;
+    }
+    if dir.contains('\\') || dir.chars().any(char::is_control) {
+        return false;
+    }
+    dir.trim_end_matches('/')
+        .split('/')
+        .all(|part| !part.is_empty() && part != "." && part != "..")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,4 +62,20 @@ mod tests {
         assert!(!is_valid_branch_prefix("foo/"));
         assert!(!is_valid_branch_prefix(".foo"));
     }
+
+    #[test]
+    fn test_sparse_checkout_dirs() {
+        assert!(is_valid_sparse_checkout_dir("frontend"));
+        assert!(is_valid_sparse_checkout_dir("crates/git/"));
+        assert!(is_valid_sparse_checkout_dir(".github"));
+
+        assert!(!is_valid_sparse_checkout_dir(""));
+        assert!(!is_valid_sparse_checkout_dir("--no-cone"));
+        assert!(!is_valid_sparse_checkout_dir("/etc"));
+        assert!(!is_valid_sparse_checkout_dir("../outside"));
+        assert!(!is_valid_sparse_checkout_dir("a/./b"));
+        assert!(!is_valid_sparse_checkout_dir("a//b"));
+        assert!(!is_valid_sparse_checkout_dir("a\\b"));
+        assert!(!is_valid_sparse_checkout_dir("a\nb"));
+    }
 }
@@ -4,7 +4,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use git::{GitCli, GitService};
+use git::{GitCli, GitService, is_release_tag};
 use git2::{Repository, build::CheckoutBuilder};
 use tempfile::TempDir;
 use utils::diff::DiffChangeKind;
@@ -206,6 +206,95 @@ fn commit_and_is_worktree_clean() {
     assert!(s.is_worktree_clean(&repo_path).unwrap());
 }
 
+#[test]
+fn first_releases_containing_finds_earliest_release() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let git = GitCli::new();
+
+    write_file(&repo_path, "a.txt", "a\n");
+    s.commit(&repo_path, "a").unwrap();
+    git.git(&repo_path, ["tag", "v1.0"]).unwrap();
+    let first = s.get_head_info(&repo_path).unwrap().oid;
+
+    write_file(&repo_path, "b.txt", "b\n");
+    s.commit(&repo_path, "b").unwrap();
+    let merged = s.get_head_info(&repo_path).unwrap().oid;
+    git.git(&repo_path, ["tag", "deploy-staging"]).unwrap();
+    let commits = [merged.clone(), first.clone()];
+    let released = s.first_releases_containing(&repo_path, &commits).unwrap();
+    assert_eq!(released.get(&merged), None);
+
+    write_file(&repo_path, "c.txt", "c\n");
+    s.commit(&repo_path, "c").unwrap();
+    git.git(&repo_path, ["tag", "-a", "v1.1", "-m", "v1.1"])
+        .unwrap();
+    let released = s.first_releases_containing(&repo_path, &commits).unwrap();
+    assert_eq!(released.get(&merged).map(String::as_str), Some("v1.1"));
+    assert_eq!(released.get(&first).map(String::as_str), Some("v1.0"));
+}
+
+#[test]
+fn release_tags_look_like_versions() {
+    for tag in [
+        "v1.2.3",
+        "1.2",
+        "v2.0.0-rc.1",
+        "app@1.2.3",
+        "app/v1.2.3",
+        "app-v1.2.3",
+    ] {
+        assert!(is_release_tag(tag), "{tag}");
+    }
+    for tag in [
+        "deploy-staging",
+        "v1",
+        "latest",
+        "v1.2.3.4",
+        "nightly-2024.01",
+    ] {
+        assert!(!is_release_tag(tag), "{tag}");
+    }
+}
+
+#[test]
+fn commits_in_range_matches_git_log_range() {
+    let td = TempDir::new().unwrap();
+    let repo_path = init_repo_main(&td);
+    let s = GitService::new();
+    let git = GitCli::new();
+
+    let mut commits = Vec::new();
+    for name in ["a", "b", "c"] {
+        write_file(&repo_path, &format!("{name}.txt"), "x\n");
+        s.commit(&repo_path, name).unwrap();
+        commits.push(s.get_head_info(&repo_path).unwrap().oid);
+        git.git(&repo_path, ["tag", &format!("t-{name}")]).unwrap();
+    }
+
+    let in_range = s
+        .commits_in_range(&repo_path, Some("t-a"), "t-c", &commits)
+        .unwrap()
+        .unwrap();
+    assert!(!in_range.contains(&commits[0]));
+    assert!(in_range.contains(&commits[1]));
+    assert!(in_range.contains(&commits[2]));
+
+    let up_to_b = s
+        .commits_in_range(&repo_path, None, "t-b", &commits)
+        .unwrap()
+        .unwrap();
+    assert_eq!(up_to_b.len(), 2);
+    assert!(!up_to_b.contains(&commits[2]));
+
+    assert!(
+        s.commits_in_range(&repo_path, Some("missing"), "t-c", &commits)
+            .unwrap()
+            .is_none()
+    );
+}
+
 #[test]
 fn commit_in_detached_head_succeeds_via_service() {
     let td = TempDir::new().unwrap();
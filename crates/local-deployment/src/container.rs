@@ -7,6 +7,7 @@ use std::{
 };
 
 use anyhow::anyhow;
+use api_types::Patch;
 use async_trait::async_trait;
 use command_group::AsyncGroupChild;
 use db::{
@@ -46,6 +47,7 @@ use services::services::{
     container::{ContainerError, ContainerRef, ContainerService},
     diff_stream::{self, DiffStreamHandle},
     file::FileService,
+    log_retention,
     notification::NotificationService,
     queued_message::QueuedMessageService,
     remote_client::RemoteClient,
@@ -54,17 +56,50 @@ use services::services::{
 use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
 use utils::{
+    commit_message::{DEFAULT_COMMIT_TEMPLATE, apply_commit_template, follows_commit_template},
     log_msg::LogMsg,
     msg_store::MsgStore,
+    task_trailer::with_task_trailer,
     text::{git_branch_id, short_uuid, truncate_to_char_boundary},
 };
 use uuid::Uuid;
 use workspace_manager::{RepoWorkspaceInput, WorkspaceError, WorkspaceManager};
+use worktree_manager::WorktreePool;
 
 use crate::{command, copy};
 
 const WORKSPACE_TOUCH_DEBOUNCE: Duration = Duration::from_mins(2);
 
+/// How commits in a shared workspace are worded. The default leaves
+/// messages as written.
+#[derive(Default)]
+struct SharedCommitConventions {
+    /// The project's commit template, when it enforces one.
+    template: Option<String>,
+    task_key: Option<String>,
+    issue_id: Option<Uuid>,
+}
+
+impl SharedCommitConventions {
+    fn apply(&self, message: &str) -> String {
+        let mut message = match &self.template {
+            Some(template) => apply_commit_template(template, message, self.task_key.as_deref()),
+            None => message.to_string(),
+        };
+        if let Some(issue_id) = self.issue_id {
+            message = with_task_trailer(&message, issue_id);
+        }
+        message
+    }
+
+    /// `message` reworded, if it doesn't already follow the template.
+    fn reword(&self, message: &str) -> Option<String> {
+        let template = self.template.as_deref()?;
+        (!follows_commit_template(template, message, self.task_key.as_deref()))
+            .then(|| self.apply(message))
+    }
+}
+
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
@@ -128,6 +163,9 @@ impl LocalContainerService {
         };
 
         container.spawn_workspace_cleanup();
+        container.spawn_db_maintenance();
+        container.spawn_log_retention();
+        container.spawn_worktree_pool_pruning();
 
         container
     }
@@ -319,6 +357,64 @@ impl LocalContainerService {
         });
     }
 
+    fn spawn_db_maintenance(&self) {
+        let pool = self.db.pool.clone();
+        tokio::spawn(async move {
+            let mut maintenance_interval =
+                tokio::time::interval(tokio::time::Duration::from_secs(6 * 3600)); // 6 hours
+            // Skip the immediate first tick so startup isn't slowed down.
+            maintenance_interval.tick().await;
+            loop {
+                maintenance_interval.tick().await;
+                tracing::info!("Starting periodic database maintenance...");
+                db::maintenance::optimize(&pool)
+                    .await
+                    .unwrap_or_else(|e| tracing::error!("Database maintenance failed: {}", e));
+            }
+        });
+    }
+
+    fn spawn_log_retention(&self) {
+        let pool = self.db.pool.clone();
+        let config = self.config.clone();
+        tokio::spawn(async move {
+            let mut retention_interval =
+                tokio::time::interval(tokio::time::Duration::from_secs(24 * 3600)); // daily
+            loop {
+                retention_interval.tick().await;
+                let retention = config.read().await.log_retention.clone();
+                match log_retention::prune_logs(&pool, &retention, false).await {
+                    Ok(report) if report.process_count > 0 => tracing::info!(
+                        "Pruned {} execution logs ({} bytes)",
+                        report.process_count,
+                        report.bytes
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to prune execution logs: {}", e),
+                }
+            }
+        });
+    }
+
+    fn spawn_worktree_pool_pruning(&self) {
+        tokio::spawn(async move {
+            let mut prune_interval =
+                tokio::time::interval(tokio::time::Duration::from_secs(6 * 3600)); // 6 hours
+            loop {
+                prune_interval.tick().await;
+                match WorktreePool::prune(false).await {
+                    Ok(report) if !report.evicted.is_empty() => tracing::info!(
+                        "Evicted {} pooled worktrees ({} bytes)",
+                        report.evicted.len(),
+                        report.evicted.iter().map(|w| w.bytes).sum::<u64>()
+                    ),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Failed to prune worktree pool: {}", e),
+                }
+            }
+        });
+    }
+
     /// Record the current HEAD commit for each repository as the "after" state.
     /// Errors are silently ignored since this runs after the main execution completes
     /// and failure should not block process finalization.
@@ -340,6 +436,106 @@ impl LocalContainerService {
         }
     }
 
+    /// How commits in `workspace_id` are worded: the commit convention of
+    /// the remote project it's shared to, and a trailer naming its shared
+    /// issue. None for workspaces that aren't shared or when the remote
+    /// can't be reached.
+    async fn shared_commit_conventions(&self, workspace_id: Uuid) -> SharedCommitConventions {
+        let Some(client) = self.remote_client.as_ref() else {
+            return SharedCommitConventions::default();
+        };
+        let remote_workspace = match client.get_workspace_by_local_id(workspace_id).await {
+            Ok(remote_workspace) => remote_workspace,
+            Err(e) => {
+                tracing::debug!(
+                    "Committing workspace {} without shared conventions: {}",
+                    workspace_id,
+                    e
+                );
+                return SharedCommitConventions::default();
+            }
+        };
+
+        let mut conventions = SharedCommitConventions {
+            issue_id: remote_workspace.issue_id,
+            ..Default::default()
+        };
+        match client
+            .get_project_commit_settings(remote_workspace.project_id)
+            .await
+        {
+            Ok(settings) if settings.conventional_commits => {
+                conventions.task_key = match remote_workspace.issue_id {
+                    Some(issue_id) => client
+                        .get_issue(issue_id)
+                        .await
+                        .ok()
+                        .map(|issue| issue.simple_id),
+                    None => None,
+                };
+                conventions.template = Some(
+                    settings
+                        .commit_template
+                        .unwrap_or_else(|| DEFAULT_COMMIT_TEMPLATE.to_string()),
+                );
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Failed to load commit settings: {}", e),
+        }
+        conventions
+    }
+
+    /// Reword the commits the coding agent made itself that don't follow
+    /// the project's commit convention, as the auto-commit would have been.
+    async fn reword_agent_commits(
+        &self,
+        ctx: &ExecutionContext,
+        workspace_root: &Path,
+        conventions: &SharedCommitConventions,
+    ) {
+        if conventions.template.is_none() {
+            return;
+        }
+        let repo_states = match ExecutionProcessRepoState::find_by_execution_process_id(
+            &self.db.pool,
+            ctx.execution_process.id,
+        )
+        .await
+        {
+            Ok(repo_states) => repo_states,
+            Err(e) => {
+                tracing::warn!("Failed to load repo states for rewording commits: {}", e);
+                return;
+            }
+        };
+
+        for repo in &ctx.repos {
+            let Some(before_head) = repo_states
+                .iter()
+                .find(|s| s.repo_id == repo.id)
+                .and_then(|s| s.before_head_commit.as_deref())
+            else {
+                continue;
+            };
+            let worktree_path = workspace_root.join(&repo.name);
+            match self
+                .git()
+                .reword_unpushed_commits(&worktree_path, before_head, |message| {
+                    conventions.reword(message)
+                }) {
+                Ok(0) => {}
+                Ok(reworded) => tracing::info!(
+                    "Reworded {} agent commits in repo '{}' to the commit convention",
+                    reworded,
+                    repo.name
+                ),
+                Err(e) => {
+                    tracing::warn!("Failed to reword commits in repo '{}': {}", repo.name, e)
+                }
+            }
+        }
+    }
+
     /// Get the commit message based on the execution run reason.
     async fn get_commit_message(&self, ctx: &ExecutionContext) -> String {
         match ctx.execution_process.run_reason {
@@ -779,7 +975,7 @@ impl LocalContainerService {
                         remote_sync::sync_workspace_to_remote(
                             &client,
                             workspace_id,
-                            workspace_name.map(Some),
+                            Patch::set(workspace_name),
                             Some(archived),
                             stats.as_ref(),
                         )
@@ -1194,6 +1390,10 @@ impl ContainerService for LocalContainerService {
         self.config.read().await.git_branch_prefix.clone()
     }
 
+    async fn git_branch_template(&self) -> String {
+        self.config.read().await.git_branch_template.clone()
+    }
+
     fn workspace_to_current_dir(&self, workspace: &Workspace) -> PathBuf {
         PathBuf::from(workspace.container_ref.clone().unwrap_or_default())
     }
@@ -1559,8 +1759,6 @@ impl ContainerService for LocalContainerService {
             return Ok(false);
         }
 
-        let message = self.get_commit_message(ctx).await;
-
         let container_ref = ctx
             .workspace
             .container_ref
@@ -1569,11 +1767,25 @@ impl ContainerService for LocalContainerService {
         let workspace_root = PathBuf::from(container_ref);
 
         let repos_with_changes = self.check_repos_for_changes(&workspace_root, &ctx.repos)?;
-        if repos_with_changes.is_empty() {
+        let agent_committed = matches!(
+            ctx.execution_process.run_reason,
+            ExecutionProcessRunReason::CodingAgent
+        ) && self.has_commits_from_execution(ctx).await?;
+        if repos_with_changes.is_empty() && !agent_committed {
             tracing::debug!("No changes to commit in any repository");
             return Ok(false);
         }
 
+        let conventions = self.shared_commit_conventions(ctx.workspace.id).await;
+        if agent_committed {
+            self.reword_agent_commits(ctx, &workspace_root, &conventions)
+                .await;
+        }
+        if repos_with_changes.is_empty() {
+            return Ok(false);
+        }
+
+        let message = conventions.apply(&self.get_commit_message(ctx).await);
         Ok(self.commit_repos(repos_with_changes, &message))
     }
 
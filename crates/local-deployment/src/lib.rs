@@ -16,8 +16,10 @@ use relay_hosts::RelayHosts;
 use relay_webrtc::WebRtcHost;
 use remote_info::RemoteInfo;
 use services::services::{
+    activity::ActivityProcessor,
     analytics::{AnalyticsConfig, AnalyticsContext, AnalyticsService, generate_user_id},
     approvals::Approvals,
+    assignment_notifier::AssignmentNotifier,
     auth::AuthContext,
     config::{Config, load_config_from_file, save_config_to_file},
     container::ContainerService,
@@ -25,7 +27,9 @@ use services::services::{
     file::FileService,
     file_search::FileSearchCache,
     filesystem::FilesystemService,
+    network_watcher::{NetworkChanges, NetworkWatcher},
     oauth_credentials::OAuthCredentials,
+    organization_remotes::OrganizationRemoteClients,
     pr_monitor::PrMonitorService,
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
@@ -40,7 +44,7 @@ use utils::{
 };
 use uuid::Uuid;
 use workspace_manager::WorkspaceManager;
-use worktree_manager::WorktreeManager;
+use worktree_manager::{WorktreeManager, WorktreePool};
 
 use crate::{container::LocalContainerService, pty::PtyService};
 mod command;
@@ -65,6 +69,7 @@ pub struct LocalDeployment {
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
     remote_client: Result<RemoteClient, RemoteClientNotConfigured>,
+    organization_remote_clients: OrganizationRemoteClients,
     auth_context: AuthContext,
     oauth_handoffs: Arc<RwLock<HashMap<Uuid, PendingHandoff>>>,
     trusted_key_auth: TrustedKeyAuthRuntime,
@@ -74,6 +79,7 @@ pub struct LocalDeployment {
     remote_info: RemoteInfo,
     preview_proxy: PreviewProxyService,
     relay_hosts: Option<Arc<RelayHosts>>,
+    network_watcher: NetworkWatcher,
     shutdown: CancellationToken,
     webrtc_host: OnceLock<Arc<WebRtcHost>>,
     ssh_config: Arc<russh::server::Config>,
@@ -85,6 +91,8 @@ pub struct LocalDeployment {
 struct PendingHandoff {
     provider: String,
     app_verifier: String,
+    /// Organization whose remote the sign-in is for; `None` for the default.
+    organization_id: Option<Uuid>,
 }
 
 #[async_trait]
@@ -123,6 +131,7 @@ impl Deployment for LocalDeployment {
             let path = utils::path::expand_tilde(workspace_dir);
             WorktreeManager::set_workspace_dir_override(path);
         }
+        WorktreePool::set_policy(raw_config.worktree_pool.policy());
 
         let config = Arc::new(RwLock::new(raw_config));
         let user_id = generate_user_id();
@@ -186,10 +195,17 @@ impl Deployment for LocalDeployment {
                 .expect("relay_api_base already set");
         }
 
+        let network_watcher = NetworkWatcher::spawn(shutdown.child_token());
+
         let remote_client = match remote_info.get_api_base() {
             Some(url) => match RemoteClient::new(&url, auth_context.clone()) {
                 Ok(client) => {
                     tracing::info!("Remote client initialized with URL: {}", url);
+                    client.spawn_token_refresher(shutdown.child_token());
+                    client.spawn_network_change_handler(
+                        network_watcher.subscribe(),
+                        shutdown.child_token(),
+                    );
                     Ok(client)
                 }
                 Err(e) => {
@@ -203,6 +219,17 @@ impl Deployment for LocalDeployment {
             }
         };
 
+        let organization_remote_clients = OrganizationRemoteClients::new(
+            remote_info.get_api_base().as_deref(),
+            auth_context.clone(),
+            shutdown.child_token(),
+        );
+
+        if let Ok(client) = &remote_client {
+            ActivityProcessor::new(db.pool.clone(), client.clone())
+                .spawn_background_sync(shutdown.child_token());
+        }
+
         let oauth_handoffs = Arc::new(RwLock::new(HashMap::new()));
         let trusted_key_auth = TrustedKeyAuthRuntime::new(trusted_keys_path());
         let relay_signing = RelaySigningService::load_or_generate(&server_signing_key_path())
@@ -262,6 +289,14 @@ impl Deployment for LocalDeployment {
             let rc = remote_client.clone().ok();
             PrMonitorService::spawn(db, analytics, container, rc, pr_sync_notify.clone()).await;
         }
+        if let Ok(client) = remote_client.clone() {
+            AssignmentNotifier::spawn(
+                client,
+                container.notification_service().clone(),
+                config.clone(),
+                shutdown.child_token(),
+            );
+        }
 
         let deployment = Self {
             config,
@@ -279,6 +314,7 @@ impl Deployment for LocalDeployment {
             approvals,
             queued_message_service,
             remote_client,
+            organization_remote_clients,
             auth_context,
             oauth_handoffs,
             trusted_key_auth,
@@ -288,6 +324,7 @@ impl Deployment for LocalDeployment {
             remote_info,
             preview_proxy,
             relay_hosts,
+            network_watcher,
             shutdown,
             webrtc_host: OnceLock::new(),
             ssh_config,
@@ -378,6 +415,14 @@ impl Deployment for LocalDeployment {
         self.relay_hosts.as_ref().ok_or(RelayHostsNotConfigured)
     }
 
+    fn remote_client(&self) -> Result<RemoteClient, RemoteClientNotConfigured> {
+        self.remote_client.clone()
+    }
+
+    fn organization_remote_clients(&self) -> Option<&OrganizationRemoteClients> {
+        Some(&self.organization_remote_clients)
+    }
+
     fn trusted_key_auth(&self) -> &TrustedKeyAuthRuntime {
         &self.trusted_key_auth
     }
@@ -456,22 +501,38 @@ impl LocalDeployment {
         handoff_id: Uuid,
         provider: String,
         app_verifier: String,
+        organization_id: Option<Uuid>,
     ) {
         self.oauth_handoffs.write().await.insert(
             handoff_id,
             PendingHandoff {
                 provider,
                 app_verifier,
+                organization_id,
             },
         );
     }
 
-    pub async fn take_oauth_handoff(&self, handoff_id: &Uuid) -> Option<(String, String)> {
+    pub async fn take_oauth_handoff(
+        &self,
+        handoff_id: &Uuid,
+    ) -> Option<(String, String, Option<Uuid>)> {
         self.oauth_handoffs
             .write()
             .await
             .remove(handoff_id)
-            .map(|state| (state.provider, state.app_verifier))
+            .map(|state| (state.provider, state.app_verifier, state.organization_id))
+    }
+
+    /// Notified when the machine's network route changes, so long-lived
+    /// connections can be re-established on the new one.
+    pub fn network_changes(&self) -> NetworkChanges {
+        self.network_watcher.subscribe()
+    }
+
+    /// Cancelled when the server starts shutting down.
+    pub fn shutdown_token(&self) -> CancellationToken {
+        self.shutdown.clone()
     }
 
     pub fn pty(&self) -> &PtyService {
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use api_types::{
     CreateIssueRequest, Issue, IssuePriority, IssueRelationshipType, IssueSortField,
     ListIssueRelationshipsResponse, ListIssueTagsResponse, ListIssuesResponse,
-    ListPullRequestsResponse, ListTagsResponse, MutationResponse, PullRequestStatus,
+    ListPullRequestsResponse, ListTagsResponse, MutationResponse, Patch, PullRequestStatus,
     SearchIssuesRequest, SortDirection, UpdateIssueRequest,
 };
 use rmcp::{
@@ -67,7 +67,7 @@ struct McpListIssuesRequest {
     #[schemars(description = "Filter to issues having a tag with this name (case-insensitive)")]
     tag_name: Option<String>,
     #[schemars(
-        description = "Field to sort by. Allowed values: 'sort_order', 'priority', 'created_at', 'updated_at', 'title'. Default: 'sort_order'."
+        description = "Field to sort by. Allowed values: 'sort_order' (status column, then manual order), 'sort_key' (manual order alone), 'priority', 'target_date', 'created_at', 'updated_at', 'title'. Default: 'sort_order'."
     )]
     sort_field: Option<String>,
     #[schemars(description = "Sort direction. Allowed values: 'asc', 'desc'. Default: 'asc'.")]
@@ -213,9 +213,11 @@ struct McpUpdateIssueRequest {
     )]
     priority: Option<String>,
     #[schemars(
-        description = "Parent issue ID to set this as a subissue. Pass null to un-nest from parent."
+        description = "Parent issue ID to set this as a subissue. Pass null to un-nest from parent.",
+        with = "Option<Uuid>"
     )]
-    parent_issue_id: Option<Option<Uuid>>,
+    #[serde(default)]
+    parent_issue_id: Patch<Uuid>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -426,6 +428,7 @@ impl McpServer {
                 priority,
                 parent_issue_id,
                 search,
+                q: None,
                 simple_id,
                 assignee_user_id,
                 tag_id,
@@ -516,17 +519,17 @@ impl McpServer {
 
         // Expand @tagname references in description
         let expanded_description = match description {
-            Some(desc) => Some(Some(self.expand_tags(&desc).await)),
-            None => None,
+            Some(desc) => Patch::Value(self.expand_tags(&desc).await),
+            None => Patch::Absent,
         };
 
         let priority = if let Some(priority) = priority {
             match Self::parse_issue_priority(&priority) {
-                Ok(parsed) => Some(Some(parsed)),
+                Ok(parsed) => Patch::Value(parsed),
                 Err(e) => return Ok(McpServer::tool_error(e)),
             }
         } else {
-            None
+            Patch::Absent
         };
 
         let payload = UpdateIssueRequest {
@@ -534,12 +537,12 @@ impl McpServer {
             title,
             description: expanded_description,
             priority,
-            start_date: None,
-            target_date: None,
-            completed_at: None,
+            start_date: Patch::Absent,
+            target_date: Patch::Absent,
+            completed_at: Patch::Absent,
             sort_order: None,
             parent_issue_id,
-            parent_issue_sort_order: None,
+            parent_issue_sort_order: Patch::Absent,
             extension_metadata: None,
         };
 
@@ -590,12 +593,14 @@ impl McpServer {
             .as_str()
         {
             "sort_order" => Ok(IssueSortField::SortOrder),
+            "sort_key" => Ok(IssueSortField::SortKey),
             "priority" => Ok(IssueSortField::Priority),
+            "target_date" | "due_date" => Ok(IssueSortField::TargetDate),
             "created_at" => Ok(IssueSortField::CreatedAt),
             "updated_at" => Ok(IssueSortField::UpdatedAt),
             "title" => Ok(IssueSortField::Title),
             other => Err(ToolError::message(format!(
-                "Unknown sort_field '{}'. Allowed values: ['sort_order', 'priority', 'created_at', 'updated_at', 'title']",
+                "Unknown sort_field '{}'. Allowed values: ['sort_order', 'sort_key', 'priority', 'target_date', 'created_at', 'updated_at', 'title']",
                 other
             ))),
         }
@@ -15,7 +15,7 @@ use tokio_util::sync::CancellationToken;
 use tokio_yamux::Session;
 use ws_bridge::tungstenite_ws_stream_io;
 
-use crate::{tls::ws_connector, yamux_config};
+use crate::{tls::ws_connector, wake::WakeDetector, yamux_config};
 
 pub struct RelayClientConfig {
     pub ws_url: String,
@@ -27,6 +27,9 @@ pub struct RelayClientConfig {
 /// Connects the relay client control channel and starts handling inbound streams.
 ///
 /// Returns when shutdown is requested or when the control channel disconnects/errors.
+/// If the machine sleeps while connected, returns a [`crate::wake::SystemResumed`]
+/// error right after it wakes so the caller can reconnect without waiting for
+/// the dead connection to time out.
 pub async fn start_relay_client(config: RelayClientConfig) -> anyhow::Result<()> {
     let mut request = config
         .ws_url
@@ -53,6 +56,10 @@ pub async fn start_relay_client(config: RelayClientConfig) -> anyhow::Result<()>
 
     let shutdown = config.shutdown;
     let local_addr = config.local_addr;
+    // Polled across iterations so frequent inbound streams can't keep
+    // restarting its check interval.
+    let resumed = WakeDetector::new().resumed();
+    tokio::pin!(resumed);
 
     loop {
         tokio::select! {
@@ -60,6 +67,9 @@ pub async fn start_relay_client(config: RelayClientConfig) -> anyhow::Result<()>
                 control.close().await;
                 return Ok(());
             }
+            resumed = &mut resumed => {
+                return Err(resumed.into());
+            }
             inbound = session.next() => {
                 let stream = inbound
                     .ok_or_else(|| anyhow::anyhow!("Relay control channel closed"))?
@@ -5,6 +5,7 @@ use tokio_yamux::Config as YamuxConfig;
 pub mod client;
 pub mod server;
 pub mod tls;
+pub mod wake;
 
 /// Shared yamux configuration for both client and server sides of the relay tunnel.
 ///
@@ -0,0 +1,95 @@
+//! Detects the machine waking from sleep.
+//!
+//! The system's awake time stops while the OS is suspended but the wall clock
+//! keeps going, so after a resume the wall clock has moved much further than
+//! the awake time. A connection opened before the suspend is almost
+//! certainly dead by then, and waiting for keepalives to notice can take
+//! minutes.
+
+use std::time::{Duration, SystemTime};
+#[cfg(not(windows))]
+use std::{sync::OnceLock, time::Instant};
+
+use thiserror::Error;
+
+/// How often the clocks are compared.
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Wall-clock time unaccounted for by the awake time before we treat a
+/// gap as a suspend rather than scheduling jitter or an NTP correction.
+const SUSPEND_THRESHOLD: Duration = Duration::from_secs(15);
+
+/// The system resumed from sleep while a connection was open.
+#[derive(Debug, Error)]
+#[error("system resumed after ~{}s asleep", .0.as_secs())]
+pub struct SystemResumed(pub Duration);
+
+pub struct WakeDetector {
+    wall: SystemTime,
+    awake: Duration,
+}
+
+impl WakeDetector {
+    pub fn new() -> Self {
+        Self {
+            wall: SystemTime::now(),
+            awake: awake_time(),
+        }
+    }
+
+    /// Resolves once a suspend/resume is observed, with how long the system
+    /// was asleep.
+    pub async fn resumed(mut self) -> SystemResumed {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Some(asleep) = self.check() {
+                return SystemResumed(asleep);
+            }
+        }
+    }
+
+    fn check(&mut self) -> Option<Duration> {
+        let (wall, awake) = (SystemTime::now(), awake_time());
+        // A wall clock stepped backwards reports no elapsed time.
+        let wall_elapsed = wall.duration_since(self.wall).unwrap_or_default();
+        let awake_elapsed = awake.saturating_sub(self.awake);
+        self.wall = wall;
+        self.awake = awake;
+
+        let asleep = wall_elapsed.saturating_sub(awake_elapsed);
+        (asleep >= SUSPEND_THRESHOLD).then_some(asleep)
+    }
+}
+
+impl Default for WakeDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Time the system has spent awake, from an arbitrary starting point.
+#[cfg(not(windows))]
+fn awake_time() -> Duration {
+    // `Instant` stops while suspended on Linux and macOS.
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed()
+}
+
+/// Time the system has spent awake, from an arbitrary starting point.
+#[cfg(windows)]
+fn awake_time() -> Duration {
+    // `Instant` keeps counting through sleep on Windows; the unbiased
+    // interrupt time leaves it out.
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn QueryUnbiasedInterruptTime(unbiased_time: *mut u64) -> i32;
+    }
+
+    let mut ticks = 0u64;
+    // SAFETY: the pointer is to a live, writable u64.
+    unsafe { QueryUnbiasedInterruptTime(&mut ticks) };
+    // Counted in 100ns units.
+    Duration::from_nanos(ticks.saturating_mul(100))
+}
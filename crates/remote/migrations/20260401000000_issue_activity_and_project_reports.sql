@@ -0,0 +1,75 @@
+-- Append-only log of issue lifecycle events (creation, status moves, deletion).
+-- Rows are written by triggers on `issues` so every write path is covered,
+-- including bulk updates and direct SQL. `seq` gives a total order for replay.
+CREATE TABLE issue_activity (
+    seq BIGSERIAL PRIMARY KEY,
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    issue_id UUID NOT NULL,
+    event_type TEXT NOT NULL CHECK (event_type IN ('created', 'status_changed', 'deleted')),
+    from_status_id UUID,
+    to_status_id UUID,
+    actor_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_activity_project_created ON issue_activity(project_id, created_at);
+CREATE INDEX idx_issue_activity_issue ON issue_activity(issue_id);
+
+-- The acting user is propagated from the request via `begin_tx`
+-- (`vk.actor_user_id`); background writes leave it NULL.
+CREATE OR REPLACE FUNCTION record_issue_activity()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_actor UUID := NULLIF(current_setting('vk.actor_user_id', true), '')::uuid;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO issue_activity (project_id, issue_id, event_type, to_status_id, actor_user_id)
+        VALUES (NEW.project_id, NEW.id, 'created', NEW.status_id, v_actor);
+        RETURN NEW;
+    ELSIF TG_OP = 'UPDATE' THEN
+        IF NEW.status_id IS DISTINCT FROM OLD.status_id THEN
+            INSERT INTO issue_activity (
+                project_id, issue_id, event_type, from_status_id, to_status_id, actor_user_id
+            )
+            VALUES (NEW.project_id, NEW.id, 'status_changed', OLD.status_id, NEW.status_id, v_actor);
+        END IF;
+        RETURN NEW;
+    ELSE
+        -- Skip when the whole project is being deleted (cascade); its
+        -- activity rows are removed alongside it.
+        IF EXISTS (SELECT 1 FROM projects WHERE id = OLD.project_id) THEN
+            INSERT INTO issue_activity (
+                project_id, issue_id, event_type, from_status_id, actor_user_id
+            )
+            VALUES (OLD.project_id, OLD.id, 'deleted', OLD.status_id, v_actor);
+        END IF;
+        RETURN OLD;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_issues_activity
+    AFTER INSERT OR UPDATE OF status_id OR DELETE ON issues
+    FOR EACH ROW
+    EXECUTE FUNCTION record_issue_activity();
+
+-- Seed a creation event for existing issues so the log has a starting point.
+-- History before this migration is unknown, so the current status is used.
+INSERT INTO issue_activity (project_id, issue_id, event_type, to_status_id, actor_user_id, created_at)
+SELECT project_id, id, 'created', status_id, creator_user_id, created_at
+FROM issues
+ORDER BY created_at, id;
+
+-- Point-in-time board reports. The snapshot is computed once at creation and
+-- stored verbatim so later reads are stable even as the board changes.
+CREATE TABLE project_reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    created_by_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    period_start TIMESTAMPTZ NOT NULL,
+    period_end TIMESTAMPTZ NOT NULL,
+    snapshot JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_project_reports_project_created ON project_reports(project_id, created_at DESC);
@@ -0,0 +1,15 @@
+-- Cached replay checkpoints for `issue_activity`. Each row holds the
+-- issue -> status map after applying every event with seq <= `seq`, so
+-- historical board reconstruction only replays events after the checkpoint.
+-- `taken_at` is the latest event timestamp folded into the snapshot.
+CREATE TABLE issue_activity_snapshots (
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    seq BIGINT NOT NULL,
+    taken_at TIMESTAMPTZ NOT NULL,
+    issue_statuses JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (project_id, seq)
+);
+
+CREATE INDEX idx_issue_activity_snapshots_taken_at
+    ON issue_activity_snapshots(project_id, taken_at DESC);
@@ -0,0 +1,5 @@
+-- Deletion events in `issue_activity` double as tombstones for the
+-- "deleted since seq N" feed; index them so the feed doesn't scan the log.
+CREATE INDEX idx_issue_activity_tombstones
+    ON issue_activity(project_id, seq)
+    WHERE event_type = 'deleted';
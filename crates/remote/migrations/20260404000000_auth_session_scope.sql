@@ -0,0 +1,5 @@
+-- Capability scope for an auth session. Access tokens carry the scope as a
+-- claim; `read_only` sessions may read and stream data but not mutate it.
+ALTER TABLE auth_sessions
+    ADD COLUMN scope TEXT NOT NULL DEFAULT 'full'
+        CHECK (scope IN ('full', 'read_only'));
@@ -0,0 +1,19 @@
+-- Optional per-organization network access policy. When an organization has
+-- any allowlisted CIDR ranges, member access is only granted to requests
+-- whose client IP falls inside one of them; likewise for trusted devices,
+-- identified by the auth session signed in on the device rather than by
+-- anything the client sends. Organizations without entries are
+-- unrestricted.
+CREATE TABLE organization_ip_allowlist (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    cidr CIDR NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, cidr)
+);
+
+CREATE TABLE organization_trusted_devices (
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    session_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (organization_id, session_id)
+);
@@ -0,0 +1,27 @@
+-- Background organization exports. The finished archive is kept in `content`
+-- until `expires_at` and handed out through signed download URLs.
+--
+-- Export jobs run in the server process that accepted them. A running job
+-- touches `heartbeat_at` periodically, so a job whose process went away (a
+-- restart or a crash) can be told apart from one still running on another
+-- replica and marked failed instead of staying "running" forever.
+CREATE TABLE export_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    requested_by_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    request JSONB NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'running', 'completed', 'failed')),
+    filename TEXT,
+    content BYTEA,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ NOT NULL DEFAULT NOW() + INTERVAL '1 day',
+    heartbeat_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_export_jobs_expires_at ON export_jobs(expires_at);
+
+CREATE INDEX idx_export_jobs_unfinished ON export_jobs(created_at)
+    WHERE status IN ('pending', 'running');
@@ -0,0 +1,7 @@
+-- Per-project indexes for the sortable issue listing columns, so board-sized
+-- projects can be ordered in the database instead of by clients.
+CREATE INDEX idx_issues_project_sort_order ON issues(project_id, sort_order);
+CREATE INDEX idx_issues_project_priority ON issues(project_id, priority);
+CREATE INDEX idx_issues_project_target_date ON issues(project_id, target_date);
+CREATE INDEX idx_issues_project_created_at ON issues(project_id, created_at);
+CREATE INDEX idx_issues_project_updated_at ON issues(project_id, updated_at);
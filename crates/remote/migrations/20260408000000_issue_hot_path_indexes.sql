@@ -0,0 +1,4 @@
+-- Indexes for bulk fetch hot paths on large organizations: board columns are
+-- loaded per status, and activity feeds replay `issue_activity` by seq range.
+CREATE INDEX idx_issues_project_status ON issues(project_id, status_id);
+CREATE INDEX idx_issue_activity_project_seq ON issue_activity(project_id, seq);
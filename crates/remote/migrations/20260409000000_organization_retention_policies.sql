@@ -0,0 +1,14 @@
+-- Optional per-organization retention for workspace diff summaries, which are
+-- only useful while the work is fresh. A background task clears summaries
+-- of workspaces not updated within the configured window.
+CREATE TABLE organization_retention_policies (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    workspace_summary_days INTEGER CHECK (workspace_summary_days > 0),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_workspaces_updated_at_with_summary
+    ON workspaces(updated_at)
+    WHERE files_changed IS NOT NULL
+       OR lines_added IS NOT NULL
+       OR lines_removed IS NOT NULL;
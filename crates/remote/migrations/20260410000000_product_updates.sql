@@ -0,0 +1,19 @@
+-- Product announcements shown in the app's "what's new" feed. Rows are
+-- inserted by operators; a future `published_at` schedules an update.
+CREATE TABLE product_updates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    url TEXT,
+    published_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_product_updates_published_at ON product_updates(published_at DESC);
+
+CREATE TABLE product_update_reads (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    product_update_id UUID NOT NULL REFERENCES product_updates(id) ON DELETE CASCADE,
+    read_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, product_update_id)
+);
@@ -0,0 +1,17 @@
+-- Reminder events for issues approaching their target date. A background
+-- task appends `due_soon` rows to `issue_activity`; `issue_due_reminders`
+-- remembers which target date was announced so each date is reminded once,
+-- and moving the date re-arms the reminder.
+ALTER TABLE issue_activity DROP CONSTRAINT issue_activity_event_type_check;
+ALTER TABLE issue_activity ADD CONSTRAINT issue_activity_event_type_check
+    CHECK (event_type IN ('created', 'status_changed', 'deleted', 'due_soon'));
+
+CREATE TABLE issue_due_reminders (
+    issue_id UUID PRIMARY KEY REFERENCES issues(id) ON DELETE CASCADE,
+    target_date TIMESTAMPTZ NOT NULL,
+    reminded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issues_open_target_date
+    ON issues(target_date)
+    WHERE target_date IS NOT NULL AND completed_at IS NULL;
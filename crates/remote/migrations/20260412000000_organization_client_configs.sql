@@ -0,0 +1,7 @@
+-- Client defaults (board filters, status colors) admins push to every
+-- member's app. Stored as one JSON document per organization.
+CREATE TABLE organization_client_configs (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    config JSONB NOT NULL DEFAULT '{}'::jsonb,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
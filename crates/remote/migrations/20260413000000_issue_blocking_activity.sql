@@ -0,0 +1,41 @@
+-- `blocked` / `unblocked` activity for the blocked side of a blocking
+-- relationship (`issue_id` blocks `related_issue_id`). Recorded by trigger so
+-- cascaded deletes (e.g. the blocker being deleted) also unblock.
+ALTER TABLE issue_activity DROP CONSTRAINT issue_activity_event_type_check;
+ALTER TABLE issue_activity ADD CONSTRAINT issue_activity_event_type_check
+    CHECK (event_type IN (
+        'created', 'status_changed', 'deleted', 'due_soon', 'blocked', 'unblocked'
+    ));
+
+CREATE OR REPLACE FUNCTION record_issue_blocking_activity()
+RETURNS TRIGGER AS $$
+DECLARE
+    v_actor UUID := NULLIF(current_setting('vk.actor_user_id', true), '')::uuid;
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        IF NEW.relationship_type = 'blocking' THEN
+            INSERT INTO issue_activity (project_id, issue_id, event_type, actor_user_id)
+            SELECT i.project_id, i.id, 'blocked', v_actor
+            FROM issues i
+            WHERE i.id = NEW.related_issue_id;
+        END IF;
+        RETURN NEW;
+    ELSE
+        -- The blocked issue (or its project) may be going away in the same
+        -- cascade; only record against issues that still exist.
+        IF OLD.relationship_type = 'blocking' THEN
+            INSERT INTO issue_activity (project_id, issue_id, event_type, actor_user_id)
+            SELECT i.project_id, i.id, 'unblocked', v_actor
+            FROM issues i
+            WHERE i.id = OLD.related_issue_id
+              AND EXISTS (SELECT 1 FROM projects p WHERE p.id = i.project_id);
+        END IF;
+        RETURN OLD;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_issue_relationships_activity
+    AFTER INSERT OR DELETE ON issue_relationships
+    FOR EACH ROW
+    EXECUTE FUNCTION record_issue_blocking_activity();
@@ -0,0 +1,14 @@
+-- Full-text search over issue titles and descriptions. The vector is built
+-- by an immutable function so queries can match the expression index
+-- without adding a column to the synced issues table.
+CREATE OR REPLACE FUNCTION issue_search_vector(title TEXT, description TEXT)
+RETURNS tsvector
+LANGUAGE SQL
+IMMUTABLE
+PARALLEL SAFE
+AS $$
+    SELECT to_tsvector('simple'::regconfig, COALESCE(title, '') || ' ' || COALESCE(description, ''))
+$$;
+
+CREATE INDEX IF NOT EXISTS idx_issues_search_vector
+    ON issues USING GIN (issue_search_vector(title, description));
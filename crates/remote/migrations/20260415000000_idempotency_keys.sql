@@ -0,0 +1,18 @@
+-- Stored responses for requests sent with an `Idempotency-Key` header, so a
+-- retried mutation replays the original result instead of running twice.
+-- `status_code` is NULL while the first request is still in flight.
+-- `claim_token` names the request currently holding the key, so a request
+-- whose claim lapsed and was taken over can't complete or release it.
+CREATE TABLE idempotency_keys (
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    key             TEXT NOT NULL,
+    request_hash    TEXT NOT NULL,
+    claim_token     UUID NOT NULL,
+    status_code     INTEGER,
+    content_type    TEXT,
+    response_body   BYTEA,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, key)
+);
+
+CREATE INDEX idx_idempotency_keys_created_at ON idempotency_keys(created_at);
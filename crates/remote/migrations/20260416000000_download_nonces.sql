@@ -0,0 +1,12 @@
+-- Redemptions of signed download URLs for sensitive resources (exports,
+-- attachments). Each signed token carries a random nonce; a token stops
+-- working once its nonce has been redeemed the allowed number of times.
+-- Rows are pruned by the maintenance task after `expires_at`.
+CREATE TABLE download_nonces (
+    nonce       TEXT PRIMARY KEY,
+    uses        INTEGER NOT NULL DEFAULT 1,
+    expires_at  TIMESTAMPTZ NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_download_nonces_expires_at ON download_nonces(expires_at);
@@ -0,0 +1,20 @@
+-- Client-supplied mutation ID (`X-Mutation-Id`) of the request that caused an
+-- activity event, so clients can recognise echoes of their own writes. The
+-- request sets `vk.mutation_id` on its transaction; every activity trigger
+-- picks it up through this BEFORE INSERT hook instead of each passing it on.
+ALTER TABLE issue_activity ADD COLUMN mutation_id TEXT;
+
+CREATE OR REPLACE FUNCTION set_issue_activity_mutation_id()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.mutation_id IS NULL THEN
+        NEW.mutation_id := NULLIF(current_setting('vk.mutation_id', true), '');
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_issue_activity_mutation_id
+    BEFORE INSERT ON issue_activity
+    FOR EACH ROW
+    EXECUTE FUNCTION set_issue_activity_mutation_id();
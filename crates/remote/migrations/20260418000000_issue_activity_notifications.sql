@@ -0,0 +1,45 @@
+-- Fan `due_soon`, `blocked` and `unblocked` activity out to the issue's
+-- followers and assignees as notifications, so the notifications shape
+-- surfaces them. The other event types are already notified by the routes
+-- that cause them.
+ALTER TYPE notification_type ADD VALUE 'issue_due_soon';
+ALTER TYPE notification_type ADD VALUE 'issue_blocked';
+ALTER TYPE notification_type ADD VALUE 'issue_unblocked';
+
+CREATE OR REPLACE FUNCTION notify_issue_activity_subscribers()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO notifications (organization_id, user_id, notification_type, payload, issue_id)
+    SELECT p.organization_id,
+           r.user_id,
+           ('issue_' || NEW.event_type)::notification_type,
+           jsonb_strip_nulls(jsonb_build_object(
+               'deeplink_path', '/projects/' || i.project_id || '/issues/' || i.id,
+               'issue_id', i.id,
+               'issue_simple_id', i.simple_id,
+               'issue_title', i.title,
+               'actor_user_id', NEW.actor_user_id
+           )),
+           i.id
+    FROM issues i
+    JOIN projects p ON p.id = i.project_id
+    JOIN (
+        SELECT user_id FROM issue_followers WHERE issue_id = NEW.issue_id
+        UNION
+        SELECT user_id FROM issue_assignees WHERE issue_id = NEW.issue_id
+    ) r ON TRUE
+    WHERE i.id = NEW.issue_id
+      AND r.user_id IS DISTINCT FROM NEW.actor_user_id
+      AND EXISTS (
+          SELECT 1 FROM organization_member_metadata m
+          WHERE m.organization_id = p.organization_id AND m.user_id = r.user_id
+      );
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_issue_activity_notifications
+    AFTER INSERT ON issue_activity
+    FOR EACH ROW
+    WHEN (NEW.event_type IN ('due_soon', 'blocked', 'unblocked'))
+    EXECUTE FUNCTION notify_issue_activity_subscribers();
@@ -0,0 +1,42 @@
+-- `@username` mentions in issue descriptions (`comment_id` NULL) and
+-- comments. A user is recorded once per description or comment, so edits
+-- only notify people who are newly mentioned.
+CREATE TABLE issue_mentions (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    issue_id     UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    comment_id   UUID REFERENCES issue_comments(id) ON DELETE CASCADE,
+    user_id      UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    mentioned_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_issue_mentions_description
+    ON issue_mentions (issue_id, user_id) WHERE comment_id IS NULL;
+CREATE UNIQUE INDEX idx_issue_mentions_comment
+    ON issue_mentions (comment_id, user_id) WHERE comment_id IS NOT NULL;
+CREATE INDEX idx_issue_mentions_user_id ON issue_mentions (user_id);
+
+ALTER TYPE notification_type ADD VALUE 'issue_mentioned';
+
+ALTER TABLE issue_activity DROP CONSTRAINT issue_activity_event_type_check;
+ALTER TABLE issue_activity ADD CONSTRAINT issue_activity_event_type_check
+    CHECK (event_type IN (
+        'created', 'status_changed', 'deleted', 'due_soon', 'blocked', 'unblocked',
+        'mentioned'
+    ));
+
+CREATE OR REPLACE FUNCTION record_issue_mention_activity()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO issue_activity (project_id, issue_id, event_type, actor_user_id)
+    SELECT i.project_id, i.id, 'mentioned', NEW.mentioned_by
+    FROM issues i
+    WHERE i.id = NEW.issue_id;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_issue_mentions_activity
+    AFTER INSERT ON issue_mentions
+    FOR EACH ROW
+    EXECUTE FUNCTION record_issue_mention_activity();
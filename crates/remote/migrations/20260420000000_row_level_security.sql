@@ -0,0 +1,50 @@
+-- Row-level security on organization data, as a backstop for queries that
+-- forget to filter by organization. The policies fail closed: a session
+-- that hasn't set `app.current_org` sees no projects, issues or issue
+-- activity. Request handlers scope their transactions with
+-- `begin_tenant_tx`, which sets it. Code that legitimately works across
+-- organizations (background jobs, and the access guards that find which
+-- organization a row belongs to) switches to `vk_rls_bypass` with
+-- `begin_trusted_tx`.
+--
+-- Requirements: the role running migrations, which is also the role the
+-- server connects as, must be able to create roles and to grant
+-- `vk_rls_bypass` to itself, and the `electric_sync` role must already
+-- exist. Electric replicates whole tables and filters them per shape, and
+-- the proxy authorizes each shape before it reaches Electric, so
+-- `electric_sync` bypasses the policies.
+
+CREATE ROLE vk_rls_bypass NOLOGIN BYPASSRLS;
+GRANT vk_rls_bypass TO CURRENT_USER;
+
+GRANT USAGE ON SCHEMA public TO vk_rls_bypass;
+GRANT SELECT, INSERT, UPDATE, DELETE ON ALL TABLES IN SCHEMA public TO vk_rls_bypass;
+GRANT USAGE, SELECT ON ALL SEQUENCES IN SCHEMA public TO vk_rls_bypass;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    GRANT SELECT, INSERT, UPDATE, DELETE ON TABLES TO vk_rls_bypass;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    GRANT USAGE, SELECT ON SEQUENCES TO vk_rls_bypass;
+
+ALTER ROLE electric_sync BYPASSRLS;
+
+CREATE OR REPLACE FUNCTION app_current_org()
+RETURNS UUID AS $$
+    SELECT NULLIF(current_setting('app.current_org', true), '')::uuid
+$$ LANGUAGE sql STABLE;
+
+-- FORCE so the policies also apply to the table owner, which is the role
+-- the server connects as.
+ALTER TABLE projects ENABLE ROW LEVEL SECURITY;
+ALTER TABLE projects FORCE ROW LEVEL SECURITY;
+CREATE POLICY projects_tenant_isolation ON projects
+    USING (organization_id = app_current_org());
+
+ALTER TABLE issues ENABLE ROW LEVEL SECURITY;
+ALTER TABLE issues FORCE ROW LEVEL SECURITY;
+CREATE POLICY issues_tenant_isolation ON issues
+    USING (project_id IN (SELECT id FROM projects WHERE organization_id = app_current_org()));
+
+ALTER TABLE issue_activity ENABLE ROW LEVEL SECURITY;
+ALTER TABLE issue_activity FORCE ROW LEVEL SECURITY;
+CREATE POLICY issue_activity_tenant_isolation ON issue_activity
+    USING (project_id IN (SELECT id FROM projects WHERE organization_id = app_current_org()));
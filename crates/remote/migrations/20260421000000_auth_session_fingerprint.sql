@@ -0,0 +1,5 @@
+-- SHA-256 of the User-Agent product names (versions ignored) of the client
+-- that first used a session's tokens. Refreshes from a different client are
+-- rejected so a leaked refresh token can't be replayed from elsewhere. NULL
+-- until the session is bound; older sessions bind on their next refresh.
+ALTER TABLE auth_sessions ADD COLUMN client_fingerprint TEXT;
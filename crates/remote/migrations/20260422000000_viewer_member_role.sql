@@ -0,0 +1,3 @@
+-- Read-only organization members: they can see projects and issues but
+-- every issue mutation is rejected with 403.
+ALTER TYPE member_role ADD VALUE IF NOT EXISTS 'viewer';
@@ -0,0 +1,36 @@
+-- Renaming a project or moving it to another organization is recorded in the
+-- project's activity log, so clients that mirror the log refresh what they
+-- cached about the project. These are the only events not about an issue.
+ALTER TABLE issue_activity ALTER COLUMN issue_id DROP NOT NULL;
+
+ALTER TABLE issue_activity DROP CONSTRAINT issue_activity_event_type_check;
+ALTER TABLE issue_activity ADD CONSTRAINT issue_activity_event_type_check
+    CHECK (event_type IN (
+        'created', 'status_changed', 'deleted', 'due_soon', 'blocked', 'unblocked',
+        'mentioned', 'project_updated'
+    ));
+
+ALTER TABLE issue_activity ADD CONSTRAINT issue_activity_issue_id_check
+    CHECK ((issue_id IS NULL) = (event_type = 'project_updated'));
+
+CREATE OR REPLACE FUNCTION record_project_activity()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.name IS DISTINCT FROM OLD.name
+        OR NEW.organization_id IS DISTINCT FROM OLD.organization_id
+    THEN
+        INSERT INTO issue_activity (project_id, event_type, actor_user_id)
+        VALUES (
+            NEW.id,
+            'project_updated',
+            NULLIF(current_setting('vk.actor_user_id', true), '')::uuid
+        );
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_projects_activity
+    AFTER UPDATE OF name, organization_id ON projects
+    FOR EACH ROW
+    EXECUTE FUNCTION record_project_activity();
@@ -0,0 +1,13 @@
+-- The last activity sequence number each of a user's devices acknowledged
+-- per project, so a device can resume the feed without keeping the cursor
+-- itself.
+CREATE TABLE activity_cursors (
+    user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    device_id  TEXT NOT NULL,
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    seq        BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, device_id, project_id)
+);
+
+CREATE INDEX idx_activity_cursors_project_id ON activity_cursors(project_id);
@@ -0,0 +1,15 @@
+-- Top-level pull request comments copied into the comment threads of the
+-- issues the pull request is linked to, one row per comment and issue so a
+-- redelivered webhook doesn't copy a comment twice. Kept when the copy is
+-- deleted, so it isn't copied again either.
+CREATE TABLE pr_comment_mirrors (
+    -- 'issue_comment' or 'review'; GitHub numbers them separately.
+    source            TEXT NOT NULL,
+    github_comment_id BIGINT NOT NULL,
+    issue_id          UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    issue_comment_id  UUID REFERENCES issue_comments(id) ON DELETE SET NULL,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (source, github_comment_id, issue_id)
+);
+
+CREATE INDEX idx_pr_comment_mirrors_issue_id ON pr_comment_mirrors(issue_id);
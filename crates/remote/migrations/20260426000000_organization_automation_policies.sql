@@ -0,0 +1,12 @@
+-- Opt-in board automations driven by GitHub webhooks. Reopening is off by
+-- default since it moves issues without anyone on the board asking.
+CREATE TABLE organization_automation_policies (
+    organization_id UUID PRIMARY KEY REFERENCES organizations(id) ON DELETE CASCADE,
+    reopen_on_failure BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Finding the pull requests merged as a commit CI failed on.
+CREATE INDEX idx_pull_requests_merge_commit_sha
+    ON pull_requests(merge_commit_sha)
+    WHERE merge_commit_sha IS NOT NULL;
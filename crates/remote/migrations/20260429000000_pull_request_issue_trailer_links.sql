@@ -0,0 +1,6 @@
+-- Links made from a pull request's `Vibe-Kanban-Task` trailers, so editing
+-- the trailer out of the body can remove them without touching links made
+-- from a workspace or by hand. Links made from trailers before this can't be
+-- told apart and are kept.
+ALTER TABLE pull_request_issues
+    ADD COLUMN from_trailer BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,14 @@
+-- Ranges of a project's activity an operator asked clients to fetch again,
+-- e.g. after a bug left them with cursors past events they never stored.
+-- Clients find them through the activity feed and move their cursor back
+-- before `first_seq`; the events themselves keep their original `seq`.
+CREATE TABLE activity_replays (
+    id              BIGSERIAL PRIMARY KEY,
+    project_id      UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    first_seq       BIGINT NOT NULL,
+    last_seq        BIGINT NOT NULL,
+    requested_by    UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_activity_replays_project_id ON activity_replays(project_id, id);
@@ -0,0 +1,29 @@
+-- `issue_activity.seq` is taken from a sequence at insert, not at commit, so
+-- a transaction that commits late can add an event below a `seq` readers
+-- already moved past. Readers stop at a watermark instead: a `seq` at or
+-- below which every event is committed.
+--
+-- Take the transaction id before `seq`, so every allocated `seq` belongs to
+-- a transaction that is already visible as running to any later snapshot.
+-- The value the column default drew before this trigger ran is discarded.
+CREATE FUNCTION issue_activity_assign_seq() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_current_xact_id();
+    NEW.seq := nextval(pg_get_serial_sequence('issue_activity', 'seq'));
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_issue_activity_assign_seq
+    BEFORE INSERT ON issue_activity
+    FOR EACH ROW EXECUTE FUNCTION issue_activity_assign_seq();
+
+-- Sequence positions paired with the id of the transaction that recorded
+-- them, which was assigned after `seq` was read. Once no transaction below
+-- `horizon` is running, every event up to `seq` is committed (see
+-- `IssueActivityRepository::record_watermark`).
+CREATE TABLE issue_activity_watermarks (
+    horizon     BIGINT PRIMARY KEY,
+    seq         BIGINT NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
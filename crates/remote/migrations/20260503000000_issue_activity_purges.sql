@@ -0,0 +1,9 @@
+-- Purging the history of long-deleted issues also drops their tombstones, so
+-- a client resuming from a cursor below the purged events would never learn
+-- of those deletions. Record how far each project's history was purged so
+-- such cursors can be told to start over.
+CREATE TABLE issue_activity_purges (
+    project_id UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    purged_seq BIGINT NOT NULL,
+    purged_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
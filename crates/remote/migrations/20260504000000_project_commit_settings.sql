@@ -0,0 +1,9 @@
+-- Commit message convention for commits made in a project's workspaces,
+-- shared by everyone working on the project. Projects without a row leave
+-- messages as written.
+CREATE TABLE project_commit_settings (
+    project_id           UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    conventional_commits BOOLEAN NOT NULL DEFAULT FALSE,
+    commit_template      TEXT,
+    updated_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
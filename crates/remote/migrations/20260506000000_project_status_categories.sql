@@ -0,0 +1,22 @@
+-- What a status means for the work in it, so automations that move issues
+-- (reopening on failed work, completion rollups) find the right column even
+-- after it's renamed. Existing statuses named like the defaults get the
+-- matching category; anything else is left for the project to set.
+CREATE TYPE project_status_category AS ENUM (
+    'backlog', 'todo', 'in_progress', 'in_review', 'done', 'cancelled'
+);
+
+ALTER TABLE project_statuses ADD COLUMN category project_status_category;
+
+UPDATE project_statuses
+SET category = CASE LOWER(name)
+    WHEN 'backlog' THEN 'backlog'
+    WHEN 'to do' THEN 'todo'
+    WHEN 'in progress' THEN 'in_progress'
+    WHEN 'in review' THEN 'in_review'
+    WHEN 'done' THEN 'done'
+    WHEN 'cancelled' THEN 'cancelled'
+END::project_status_category;
+
+CREATE INDEX idx_project_statuses_category ON project_statuses(project_id, category)
+    WHERE category IS NOT NULL;
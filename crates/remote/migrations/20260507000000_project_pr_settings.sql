@@ -0,0 +1,9 @@
+-- How pull requests are opened from a project's workspaces, shared by
+-- everyone working on the project. Projects without a row use the defaults.
+CREATE TABLE project_pr_settings (
+    project_id   UUID PRIMARY KEY REFERENCES projects(id) ON DELETE CASCADE,
+    draft        BOOLEAN NOT NULL DEFAULT FALSE,
+    use_template BOOLEAN NOT NULL DEFAULT TRUE,
+    link_issue   BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
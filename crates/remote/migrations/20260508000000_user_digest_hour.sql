@@ -0,0 +1,5 @@
+-- Hour of the day (UTC) each user's email digest is sent. NULL uses the
+-- server's default.
+ALTER TABLE users
+    ADD COLUMN digest_hour_utc SMALLINT
+    CHECK (digest_hour_utc BETWEEN 0 AND 23);
@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::db::issue_activity::IssueActivityRepository;
+
+const INTERVAL: Duration = Duration::from_secs(1);
+/// How long superseded watermarks are kept.
+const KEEP_MINUTES: i64 = 60;
+
+/// Spawns a background task that records activity watermarks; activity feeds
+/// serve events only up to the newest settled one, so this sets how soon
+/// new events show up. Call once during server startup.
+pub(crate) fn spawn_watermark_task(pool: PgPool) -> JoinHandle<()> {
+    info!(
+        interval_secs = INTERVAL.as_secs(),
+        "Starting activity watermark background task"
+    );
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = IssueActivityRepository::record_watermark(
+                &pool,
+                chrono::Duration::minutes(KEEP_MINUTES),
+            )
+            .await
+            {
+                warn!(error = %e, "Failed to record activity watermark");
+            }
+        }
+    })
+}
@@ -1,11 +1,11 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 use anyhow::{Context, bail};
 use secrecy::ExposeSecret;
 use tracing::instrument;
 
 use crate::{
-    AppState,
+    AppState, activity_watermarks,
     analytics::{AnalyticsConfig, AnalyticsService},
     attachments::cleanup::spawn_cleanup_task,
     auth::{
@@ -15,11 +15,15 @@ use crate::{
     azure_blob::AzureBlobService,
     billing::BillingService,
     config::RemoteServerConfig,
-    db, digest,
+    db, digest, due_reminders,
+    electric_cache::ElectricCache,
+    export_jobs,
     github_app::GitHubAppService,
+    invalidation::InvalidationBus,
     mail::{LoopsMailer, Mailer, NoopMailer},
+    maintenance, prometheus,
     r2::R2Service,
-    routes,
+    retention, routes,
 };
 
 pub struct Server;
@@ -31,6 +35,8 @@ impl Server {
         fields(listen_addr = %config.listen_addr)
     )]
     pub async fn run(config: RemoteServerConfig, billing: BillingService) -> anyhow::Result<()> {
+        crate::spawn_log_filter_reloader();
+
         let pool = db::create_pool(&config.database_url)
             .await
             .context("failed to create postgres pool")?;
@@ -39,6 +45,10 @@ impl Server {
             .await
             .context("failed to run database migrations")?;
 
+        if let Err(error) = db::index_check::warn_on_missing_indexes(&pool).await {
+            tracing::warn!(?error, "failed to check expected database indexes");
+        }
+
         if let Some(password) = config.electric_role_password.as_ref() {
             db::ensure_electric_role_password(&pool, password.expose_secret())
                 .await
@@ -185,6 +195,21 @@ impl Server {
             spawn_cleanup_task(pool.clone(), azure_blob_service.clone());
         }
 
+        retention::spawn_retention_task(pool.clone(), config.tombstone_retention_days);
+        due_reminders::spawn_due_reminder_task(pool.clone());
+        activity_watermarks::spawn_watermark_task(pool.clone());
+        export_jobs::spawn_abandoned_job_task(pool.clone());
+
+        let maintenance_enabled = std::env::var("DB_MAINTENANCE_ENABLED")
+            .map(|v| matches!(v.as_str(), "true" | "1"))
+            .unwrap_or(false);
+
+        if maintenance_enabled {
+            maintenance::spawn_maintenance_task(pool.clone());
+        } else {
+            tracing::info!("Database maintenance disabled (feature flag)");
+        }
+
         let digest_enabled = std::env::var("DIGEST_ENABLED")
             .map(|v| matches!(v.as_str(), "true" | "1"))
             .unwrap_or(false);
@@ -201,6 +226,11 @@ impl Server {
             tracing::info!("Notification digest disabled (no email provider configured)");
         }
 
+        let invalidations = InvalidationBus::from_config(&config.invalidation_broker)
+            .context("failed to configure invalidation broker")?;
+        let electric_cache = ElectricCache::from_config(&config.electric_cache)
+            .context("failed to configure Electric cache")?;
+
         let state = AppState::new(
             pool.clone(),
             config.clone(),
@@ -215,9 +245,19 @@ impl Server {
             github_app,
             billing,
             analytics,
+            invalidations,
+            electric_cache,
         );
 
-        let router = routes::router(state);
+        let shutdown = state.shutdown().clone();
+        let mut router = routes::router(state);
+        if config.metrics.is_enabled()
+            && let Some(metrics) = prometheus::start(&config.metrics, pool.clone())
+                .await
+                .context("failed to start metrics")?
+        {
+            router = router.merge(metrics);
+        }
         let addr: SocketAddr = config
             .listen_addr
             .parse()
@@ -228,12 +268,48 @@ impl Server {
 
         tracing::info!(%addr, "shared sync server listening");
 
-        let make_service = router.into_make_service();
+        // Peer addresses decide whether forwarding headers are trusted.
+        let make_service = router.into_make_service_with_connect_info::<SocketAddr>();
 
-        axum::serve(tcp_listener, make_service)
-            .await
-            .context("shared sync server failure")?;
+        let signalled = shutdown.clone();
+        tokio::spawn(async move {
+            crate::shutdown::signal().await;
+            tracing::info!("shutdown requested; no longer accepting connections");
+            signalled.trigger();
+        });
+
+        let draining = shutdown.clone();
+        let serve = async {
+            axum::serve(tcp_listener, make_service)
+                .with_graceful_shutdown(async move { draining.requested().await })
+                .await
+                .context("shared sync server failure")?;
+            // Upgraded WebSockets aren't tracked by the server, so wait for
+            // their sessions to be told to reconnect separately.
+            shutdown.drained().await;
+            // Activity is recorded in the transaction of the request that
+            // caused it, so once requests are done this only waits for the
+            // last of those to commit and hand back their connections.
+            pool.close().await;
+            anyhow::Ok(())
+        };
+        let deadline = async {
+            shutdown.requested().await;
+            tokio::time::sleep(Duration::from_secs(config.shutdown_timeout_secs)).await;
+        };
+
+        tokio::select! {
+            result = serve => result?,
+            _ = deadline => {
+                tracing::warn!(
+                    timeout_secs = config.shutdown_timeout_secs,
+                    "shutdown deadline passed; exiting with connections still open"
+                );
+                return Ok(());
+            }
+        }
 
+        tracing::info!("shutdown complete");
         Ok(())
     }
 }
@@ -8,13 +8,21 @@ pub enum AuditAction {
     AuthLogout,
     AuthTokenRefresh,
     AuthTokenReuseDetected,
+    AuthTokenFingerprintMismatch,
     AuthSessionRevoked,
+    AuthReadOnlySessionCreated,
 
     MemberInvite,
     MemberAcceptInvite,
     MemberRevokeInvite,
     MemberRemove,
     MemberRoleChange,
+
+    OrgAccessPolicyUpdate,
+    OrgAccessPolicyDenied,
+    OrgRetentionPolicyUpdate,
+    OrgAutomationPolicyUpdate,
+    OrgClientConfigUpdate,
 }
 
 impl AuditAction {
@@ -24,12 +32,19 @@ impl AuditAction {
             Self::AuthLogout => "auth.logout",
             Self::AuthTokenRefresh => "auth.token_refresh",
             Self::AuthTokenReuseDetected => "auth.token_reuse_detected",
+            Self::AuthTokenFingerprintMismatch => "auth.token_fingerprint_mismatch",
             Self::AuthSessionRevoked => "auth.session_revoked",
+            Self::AuthReadOnlySessionCreated => "auth.read_only_session_created",
             Self::MemberInvite => "member.invite",
             Self::MemberAcceptInvite => "member.accept_invite",
             Self::MemberRevokeInvite => "member.revoke_invite",
             Self::MemberRemove => "member.remove",
             Self::MemberRoleChange => "member.role_change",
+            Self::OrgAccessPolicyUpdate => "org.access_policy_update",
+            Self::OrgAccessPolicyDenied => "org.access_policy_denied",
+            Self::OrgRetentionPolicyUpdate => "org.retention_policy_update",
+            Self::OrgAutomationPolicyUpdate => "org.automation_policy_update",
+            Self::OrgClientConfigUpdate => "org.client_config_update",
         }
     }
 }
@@ -19,7 +19,10 @@ use super::{
 use crate::{
     configure_user_scope,
     db::{
-        auth::{AuthSessionError, AuthSessionRepository, MAX_SESSION_INACTIVITY_DURATION},
+        auth::{
+            AuthSessionError, AuthSessionRepository, MAX_SESSION_INACTIVITY_DURATION,
+            client_fingerprint,
+        },
         identity_errors::IdentityError,
         oauth::{
             AuthorizationStatus, CreateOAuthHandoff, OAuthHandoff, OAuthHandoffError,
@@ -318,6 +321,7 @@ impl OAuthHandoffService {
         handoff_id: Uuid,
         app_code: &str,
         app_verifier: &str,
+        user_agent: Option<&str>,
     ) -> Result<RedeemResponse, HandoffError> {
         let repo = OAuthHandoffRepository::new(&self.pool);
         repo.ensure_redeemable(handoff_id).await?;
@@ -372,6 +376,15 @@ impl OAuthHandoffService {
 
         let tokens = self.jwt.generate_tokens(&session, &user, &provider)?;
 
+        // The session was created by the browser callback; bind it to the
+        // app that redeems it, which is what will refresh its tokens.
+        if !session_repo
+            .bind_client_fingerprint(session.id, &client_fingerprint(user_agent))
+            .await?
+        {
+            return Err(HandoffError::Denied);
+        }
+
         session_repo
             .set_current_refresh_token(session.id, tokens.refresh_token_id)
             .await?;
@@ -4,7 +4,7 @@ use aes_gcm::{
     Aes256Gcm, Key, Nonce,
     aead::{Aead, AeadCore, KeyInit, OsRng},
 };
-use api_types::User;
+use api_types::{SessionScope, User};
 use base64::{
     Engine as _,
     engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
@@ -52,6 +52,9 @@ pub(super) struct AccessTokenClaims {
     pub iat: i64,
     pub exp: i64,
     pub aud: String,
+    /// Tokens issued before scopes existed carry no claim and are full-scope.
+    #[serde(default)]
+    pub scope: SessionScope,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -72,6 +75,7 @@ pub struct AccessTokenDetails {
     pub user_id: Uuid,
     pub session_id: Uuid,
     pub expires_at: DateTime<Utc>,
+    pub scope: SessionScope,
 }
 
 #[derive(Debug, Clone)]
@@ -132,6 +136,7 @@ impl JwtService {
             iat: now.timestamp(),
             exp: access_exp.timestamp(),
             aud: "access".to_string(),
+            scope: session.scope,
         };
 
         // Refresh token, long-lived (~1 year)
@@ -198,6 +203,7 @@ impl JwtService {
             user_id: claims.sub,
             session_id: claims.session_id,
             expires_at,
+            scope: claims.scope,
         })
     }
 
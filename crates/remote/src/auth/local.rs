@@ -10,7 +10,7 @@ use uuid::Uuid;
 use crate::{
     AppState,
     db::{
-        auth::AuthSessionRepository,
+        auth::{AuthSessionRepository, client_fingerprint},
         organizations::OrganizationRepository,
         users::{UpsertUser, UserRepository},
     },
@@ -42,6 +42,7 @@ pub(crate) fn is_local_provider(provider: &str) -> bool {
 pub(crate) async fn login(
     state: &AppState,
     payload: &LocalLoginRequest,
+    user_agent: Option<&str>,
 ) -> Result<LocalLoginResponse, LocalAuthError> {
     let Some(local_auth) = state.config().auth.local() else {
         return Err(LocalAuthError::Disabled);
@@ -121,6 +122,14 @@ pub(crate) async fn login(
             LocalAuthError::Internal
         })?;
 
+    session_repo
+        .bind_client_fingerprint(session.id, &client_fingerprint(user_agent))
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to bind local auth session fingerprint");
+            LocalAuthError::Internal
+        })?;
+
     if let Some(analytics) = state.analytics() {
         analytics.track(
             user.id,
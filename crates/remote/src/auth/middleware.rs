@@ -1,8 +1,8 @@
-use api_types::User;
+use api_types::{SessionScope, User};
 use axum::{
     body::Body,
     extract::State,
-    http::{Request, StatusCode},
+    http::{Method, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -22,6 +22,7 @@ use crate::{
         identity_errors::IdentityError,
         users::UserRepository,
     },
+    middleware::client_ip::client_ip_for_request,
 };
 
 #[derive(Clone)]
@@ -30,6 +31,19 @@ pub struct RequestContext {
     pub session_id: Uuid,
     #[allow(dead_code)]
     pub access_token_expires_at: DateTime<Utc>,
+    pub scope: SessionScope,
+}
+
+/// Non-GET routes that only read data and are therefore open to read-only
+/// sessions.
+const READ_ONLY_POST_PATHS: &[&str] = &["/issues/search", "/ws/tickets"];
+
+/// Longer `X-Mutation-Id` values are ignored.
+const MAX_MUTATION_ID_LEN: usize = 128;
+
+fn is_read_request(method: &Method, path: &str) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || (*method == Method::POST && READ_ONLY_POST_PATHS.contains(&path))
 }
 
 pub(crate) async fn require_session(
@@ -49,6 +63,16 @@ pub(crate) async fn require_session(
 
     Span::current().record("user_id", tracing::field::display(ctx.user.id));
 
+    if ctx.scope == SessionScope::ReadOnly && !is_read_request(req.method(), req.uri().path()) {
+        warn!(
+            session_id = %ctx.session_id,
+            method = %req.method(),
+            path = req.uri().path(),
+            "read-only session attempted a write"
+        );
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
     let request_id = req
         .extensions()
         .get::<RequestId>()
@@ -59,6 +83,19 @@ pub(crate) async fn require_session(
     let tx_ctx = db::TxContext {
         user_id: ctx.user.id,
         request_id,
+        session_id: ctx.session_id,
+        client_ip: client_ip_for_request(
+            req.extensions(),
+            req.headers(),
+            &state.config().trusted_proxies,
+        ),
+        mutation_id: req
+            .headers()
+            .get("X-Mutation-Id")
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && value.len() <= MAX_MUTATION_ID_LEN)
+            .map(str::to_owned),
     };
 
     req.extensions_mut().insert(ctx);
@@ -90,10 +127,14 @@ pub(super) async fn request_context_from_access_token(
     }
 
     ctx.access_token_expires_at = identity.expires_at;
+    // The session row is authoritative, but never widen what the token says.
+    if identity.scope == SessionScope::ReadOnly {
+        ctx.scope = SessionScope::ReadOnly;
+    }
     Ok(ctx)
 }
 
-pub(super) async fn request_context_from_auth_session_id(
+pub(crate) async fn request_context_from_auth_session_id(
     state: &AppState,
     session_id: Uuid,
 ) -> Result<RequestContext, Response> {
@@ -161,6 +202,7 @@ pub(super) async fn request_context_from_auth_session_id(
         user,
         session_id: session.id,
         access_token_expires_at: Utc::now(),
+        scope: session.scope,
     };
 
     match session_repo.touch(session.id).await {
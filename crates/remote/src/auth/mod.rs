@@ -8,7 +8,9 @@ mod provider;
 pub(crate) use handoff::{CallbackResult, HandoffError, OAuthHandoffService};
 pub(crate) use jwt::{JwtError, JwtService};
 pub(crate) use local::{LocalAuthError, auth_methods_response, is_local_provider, login};
-pub(crate) use middleware::{RequestContext, require_session};
+pub(crate) use middleware::{
+    RequestContext, request_context_from_auth_session_id, require_session,
+};
 pub(crate) use oauth_token_validator::{OAuthTokenValidationError, OAuthTokenValidator};
 pub(crate) use provider::{
     GitHubOAuthProvider, GoogleOAuthProvider, ProviderRegistry, ProviderTokenDetails,
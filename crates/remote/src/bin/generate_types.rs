@@ -1,27 +1,37 @@
 use std::{env, fs, path::Path};
 
 use api_types::{
-    Attachment, AttachmentUrlResponse, AttachmentWithBlob, Blob, CreateIssueAssigneeRequest,
-    CreateIssueCommentReactionRequest, CreateIssueCommentRequest, CreateIssueFollowerRequest,
-    CreateIssueRelationshipRequest, CreateIssueRequest, CreateIssueTagRequest,
-    CreateProjectRequest, CreateProjectStatusRequest, CreatePullRequestIssueRequest,
-    CreateTagRequest, ExportRequest, Issue, IssueAssignee, IssueComment, IssueCommentReaction,
-    IssueFollower, IssuePriority, IssueRelationship, IssueRelationshipType, IssueSortField,
-    IssueTag, ListIssuesQuery, ListIssuesResponse, MemberRole, Notification, NotificationGroupKind,
-    NotificationPayload, NotificationType, OrganizationMember, Project, ProjectStatus, PullRequest,
-    PullRequestIssue, PullRequestStatus, SearchIssuesRequest, SortDirection, Tag,
+    AckActivityRequest, ActivityCursor, ActivityReplay, ApplyIssueActionsRequest,
+    ApplyIssueActionsResponse, Attachment, AttachmentUrlResponse, AttachmentWithBlob,
+    BatchMutation, BatchMutationResult, BatchMutationsRequest, BatchMutationsResponse, Blob,
+    BoardAtQuery, BoardAtResponse, BoardColumnAt, BoardIssueAt, ClientMessage,
+    ConfirmUploadRequest, CreateIssueAssigneeRequest, CreateIssueCommentReactionRequest,
+    CreateIssueCommentRequest, CreateIssueFollowerRequest, CreateIssueRelationshipRequest,
+    CreateIssueRequest, CreateIssueTagRequest, CreateProjectReportRequest, CreateProjectRequest,
+    CreateProjectStatusRequest, CreatePullRequestIssueRequest, CreateSubIssueRequest,
+    CreateTagRequest, DigestSettings, ExportJob, ExportJobResponse, ExportJobStatus, ExportRequest,
+    GetActivityCursorQuery, InitUploadRequest, InitUploadResponse, Issue, IssueAction,
+    IssueActivity, IssueActivityEventType, IssueAssignee, IssueComment, IssueCommentReaction,
+    IssueDueReminder, IssueFollower, IssuePriority, IssueRelationship, IssueRelationshipType,
+    IssueSortField, IssueTag, IssueTombstone, IssueTreeNode, ListDeletedIssuesQuery,
+    ListDeletedIssuesResponse, ListDueSoonIssuesQuery, ListDueSoonIssuesResponse,
+    ListIssueActivityQuery, ListIssueActivityResponse, ListIssuesQuery, ListIssuesResponse,
+    ListProjectReportsResponse, MemberRole, Notification, NotificationGroupKind,
+    NotificationPayload, NotificationType, OrganizationMember, OrganizationMemberWithProfile,
+    PresenceMessage, PresenceQuery, PresenceResponse, PresenceTicket, Project,
+    ProjectCommitSettings, ProjectPrSettings, ProjectReport, ProjectReportSnapshot,
+    ProjectSnapshotResponse, ProjectStatus, ProjectStatusCategory, PullRequest, PullRequestIssue,
+    PullRequestStatus, ReportIssue, ReportMovedIssue, ReportStatusCount, SearchIssuesRequest,
+    SignedDownloadUrl, SortDirection, Tag, UpdateDigestSettingsRequest,
     UpdateIssueCommentReactionRequest, UpdateIssueCommentRequest, UpdateIssueRequest,
-    UpdateNotificationRequest, UpdateProjectRequest, UpdateProjectStatusRequest, UpdateTagRequest,
-    User, UserData, Workspace,
+    UpdateNotificationRequest, UpdateProjectCommitSettingsRequest, UpdateProjectPrSettingsRequest,
+    UpdateProjectRequest, UpdateProjectStatusRequest, UpdateTagRequest, User, UserData, Workspace,
 };
 use relay_types::{CreateRemoteSessionResponse, ListRelayHostsResponse, RelayHost};
 use remote::{
     routes::{
         all_mutation_definitions,
-        attachments::{
-            CommitAttachmentsRequest, CommitAttachmentsResponse, ConfirmUploadRequest,
-            InitUploadRequest, InitUploadResponse,
-        },
+        attachments::{CommitAttachmentsRequest, CommitAttachmentsResponse},
     },
     shape_routes::all_shape_routes,
 };
@@ -80,6 +90,7 @@ fn export_shapes() -> String {
         NotificationType::decl(),
         Workspace::decl(),
         ProjectStatus::decl(),
+        ProjectStatusCategory::decl(),
         Tag::decl(),
         Issue::decl(),
         IssueAssignee::decl(),
@@ -97,6 +108,13 @@ fn export_shapes() -> String {
         ListIssuesQuery::decl(),
         SearchIssuesRequest::decl(),
         ListIssuesResponse::decl(),
+        IssueAction::decl(),
+        ApplyIssueActionsRequest::decl(),
+        ApplyIssueActionsResponse::decl(),
+        BatchMutation::decl(),
+        BatchMutationsRequest::decl(),
+        BatchMutationResult::decl(),
+        BatchMutationsResponse::decl(),
         PullRequestStatus::decl(),
         PullRequest::decl(),
         PullRequestIssue::decl(),
@@ -119,6 +137,8 @@ fn export_shapes() -> String {
         UpdateProjectStatusRequest::decl(),
         CreateIssueRequest::decl(),
         UpdateIssueRequest::decl(),
+        CreateSubIssueRequest::decl(),
+        IssueTreeNode::decl(),
         CreateIssueAssigneeRequest::decl(),
         CreateIssueFollowerRequest::decl(),
         CreateIssueTagRequest::decl(),
@@ -136,6 +156,55 @@ fn export_shapes() -> String {
         AttachmentUrlResponse::decl(),
         // Export API types
         ExportRequest::decl(),
+        ExportJobStatus::decl(),
+        ExportJob::decl(),
+        ExportJobResponse::decl(),
+        SignedDownloadUrl::decl(),
+        // Project report API types
+        ProjectReport::decl(),
+        ProjectReportSnapshot::decl(),
+        ReportStatusCount::decl(),
+        ReportIssue::decl(),
+        ReportMovedIssue::decl(),
+        CreateProjectReportRequest::decl(),
+        ListProjectReportsResponse::decl(),
+        // Board history API types
+        IssueActivityEventType::decl(),
+        IssueActivity::decl(),
+        BoardAtQuery::decl(),
+        BoardAtResponse::decl(),
+        BoardColumnAt::decl(),
+        BoardIssueAt::decl(),
+        ListIssueActivityQuery::decl(),
+        ActivityReplay::decl(),
+        ActivityCursor::decl(),
+        GetActivityCursorQuery::decl(),
+        AckActivityRequest::decl(),
+        ListIssueActivityResponse::decl(),
+        ListDeletedIssuesQuery::decl(),
+        IssueTombstone::decl(),
+        ListDeletedIssuesResponse::decl(),
+        ListDueSoonIssuesQuery::decl(),
+        IssueDueReminder::decl(),
+        ListDueSoonIssuesResponse::decl(),
+        // Project snapshot API types
+        OrganizationMemberWithProfile::decl(),
+        ProjectSnapshotResponse::decl(),
+        // Project commit settings API types
+        ProjectCommitSettings::decl(),
+        UpdateProjectCommitSettingsRequest::decl(),
+        // Project PR settings API types
+        ProjectPrSettings::decl(),
+        UpdateProjectPrSettingsRequest::decl(),
+        // Digest settings API types
+        DigestSettings::decl(),
+        UpdateDigestSettingsRequest::decl(),
+        // Presence API types
+        PresenceQuery::decl(),
+        PresenceTicket::decl(),
+        PresenceResponse::decl(),
+        PresenceMessage::decl(),
+        ClientMessage::decl(),
     ];
 
     for decl in type_decls {
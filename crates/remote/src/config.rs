@@ -1,9 +1,22 @@
 use std::env;
 
 use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
+use ipnetwork::IpNetwork;
 use secrecy::SecretString;
 use thiserror::Error;
 
+const DEFAULT_TOMBSTONE_RETENTION_DAYS: i64 = 90;
+const DEFAULT_USER_REQUESTS_PER_MINUTE: u32 = 600;
+const DEFAULT_ORGANIZATION_REQUESTS_PER_MINUTE: u32 = 3000;
+const DEFAULT_MAX_REQUEST_BODY_BYTES: usize = 2 * 1024 * 1024;
+const DEFAULT_MAX_TEXT_BYTES: usize = 64 * 1024;
+const DEFAULT_INVALIDATION_REDIS_CHANNEL: &str = "vk:invalidations";
+const DEFAULT_INVALIDATION_NATS_STREAM: &str = "VK_INVALIDATIONS";
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_ELECTRIC_CACHE_MAX_ENTRIES: usize = 10_000;
+const DEFAULT_ELECTRIC_CACHE_MAX_BODY_BYTES: usize = 1024 * 1024;
+const DEFAULT_ELECTRIC_CACHE_MAX_TTL_SECS: u64 = 60 * 60;
+
 #[derive(Debug, Clone)]
 pub struct RemoteServerConfig {
     pub database_url: String,
@@ -20,6 +33,293 @@ pub struct RemoteServerConfig {
     pub review_worker_base_url: Option<String>,
     pub review_disabled: bool,
     pub github_app: Option<GitHubAppConfig>,
+    /// Days to keep the activity history of deleted issues; `None` keeps it
+    /// forever.
+    pub tombstone_retention_days: Option<i64>,
+    pub rate_limit: RateLimitConfig,
+    pub payload_limits: PayloadLimits,
+    /// Emails of users allowed to call the `/admin` endpoints.
+    pub operator_emails: Vec<String>,
+    /// Peers whose forwarding headers (`CF-Connecting-IP`, `X-Forwarded-For`,
+    /// `X-Real-IP`) are trusted to report the client IP.
+    pub trusted_proxies: Vec<IpNetwork>,
+    pub invalidation_broker: InvalidationBrokerConfig,
+    pub electric_cache: ElectricCacheConfig,
+    pub metrics: MetricsConfig,
+    /// Seconds after SIGTERM for requests and WebSocket sessions to wind
+    /// down before the server exits anyway.
+    pub shutdown_timeout_secs: u64,
+}
+
+/// Where traces are exported over OTLP/HTTP, using the standard
+/// `OTEL_EXPORTER_OTLP_*` variables.
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// Full URL spans are posted to.
+    pub traces_endpoint: String,
+    /// Sent with every export, typically for authentication.
+    pub headers: Vec<(String, SecretString)>,
+}
+
+impl OtlpConfig {
+    pub fn from_env() -> Result<Option<Self>, ConfigError> {
+        let traces_endpoint = match env::var("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT") {
+            Ok(endpoint) if !endpoint.is_empty() => endpoint,
+            _ => match env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+                Ok(endpoint) if !endpoint.is_empty() => {
+                    format!("{}/v1/traces", endpoint.trim_end_matches('/'))
+                }
+                _ => return Ok(None),
+            },
+        };
+
+        let headers = match env::var("OTEL_EXPORTER_OTLP_HEADERS") {
+            Ok(value) => value
+                .split(',')
+                .filter(|pair| !pair.trim().is_empty())
+                .map(|pair| {
+                    let (name, value) = pair
+                        .split_once('=')
+                        .ok_or(ConfigError::InvalidVar("OTEL_EXPORTER_OTLP_HEADERS"))?;
+                    let value = urlencoding::decode(value.trim())
+                        .map_err(|_| ConfigError::InvalidVar("OTEL_EXPORTER_OTLP_HEADERS"))?;
+                    Ok((
+                        name.trim().to_string(),
+                        SecretString::new(value.into_owned().into()),
+                    ))
+                })
+                .collect::<Result<_, _>>()?,
+            Err(_) => Vec::new(),
+        };
+
+        Ok(Some(Self {
+            traces_endpoint,
+            headers,
+        }))
+    }
+}
+
+/// Where Prometheus metrics are served. With neither set, none are recorded.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsConfig {
+    /// Separate address serving only `/metrics`, without authentication;
+    /// keep it off the public network.
+    pub listen_addr: Option<String>,
+    /// Bearer token for `/metrics` on the main listener. Ignored when
+    /// `listen_addr` is set.
+    pub token: Option<SecretString>,
+}
+
+impl MetricsConfig {
+    fn from_env() -> Self {
+        Self {
+            listen_addr: env::var("METRICS_LISTEN_ADDR")
+                .ok()
+                .filter(|addr| !addr.is_empty()),
+            token: env::var("METRICS_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty())
+                .map(|token| SecretString::new(token.into())),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.listen_addr.is_some() || self.token.is_some()
+    }
+}
+
+/// Where responses to non-live Electric shape requests are cached, so
+/// popular shapes don't all go to the Electric origin.
+#[derive(Debug, Clone)]
+pub struct ElectricCacheConfig {
+    pub backend: ElectricCacheBackend,
+    /// Upper bound on how long a response is kept, whatever Electric's
+    /// cache headers allow.
+    pub max_ttl_secs: u64,
+    /// Larger responses are streamed through without being cached.
+    pub max_body_bytes: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum ElectricCacheBackend {
+    Disabled,
+    /// Per replica, holding at most `max_entries` responses.
+    Memory {
+        max_entries: usize,
+    },
+    /// Shared by all replicas.
+    Redis {
+        url: SecretString,
+    },
+}
+
+impl ElectricCacheConfig {
+    fn from_env() -> Result<Self, ConfigError> {
+        let backend = match env::var("ELECTRIC_CACHE").as_deref() {
+            Err(_) | Ok("") | Ok("disabled") => ElectricCacheBackend::Disabled,
+            Ok("memory") => ElectricCacheBackend::Memory {
+                max_entries: match env::var("ELECTRIC_CACHE_MAX_ENTRIES") {
+                    Ok(value) => value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|max| *max > 0)
+                        .ok_or(ConfigError::InvalidVar("ELECTRIC_CACHE_MAX_ENTRIES"))?,
+                    Err(_) => DEFAULT_ELECTRIC_CACHE_MAX_ENTRIES,
+                },
+            },
+            Ok("redis") => {
+                let url =
+                    env::var("REDIS_URL").map_err(|_| ConfigError::MissingVar("REDIS_URL"))?;
+                ElectricCacheBackend::Redis {
+                    url: SecretString::new(url.into()),
+                }
+            }
+            Ok(_) => return Err(ConfigError::InvalidVar("ELECTRIC_CACHE")),
+        };
+
+        let max_ttl_secs = match env::var("ELECTRIC_CACHE_MAX_TTL_SECS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .map_err(|_| ConfigError::InvalidVar("ELECTRIC_CACHE_MAX_TTL_SECS"))?,
+            Err(_) => DEFAULT_ELECTRIC_CACHE_MAX_TTL_SECS,
+        };
+
+        Ok(Self {
+            backend,
+            max_ttl_secs,
+            max_body_bytes: byte_limit(
+                "ELECTRIC_CACHE_MAX_BODY_BYTES",
+                DEFAULT_ELECTRIC_CACHE_MAX_BODY_BYTES,
+            )?,
+        })
+    }
+}
+
+/// How invalidations reach the server's replicas, and through them the
+/// organization WebSockets open on each.
+#[derive(Debug, Clone)]
+pub enum InvalidationBrokerConfig {
+    /// Single replica: invalidations stay in this process.
+    InProcess,
+    /// Several replicas: invalidations are fanned out over Redis pub/sub.
+    /// Ones sent while a replica is disconnected are lost, so it has its
+    /// sockets recheck everything once it's back.
+    Redis { url: SecretString, channel: String },
+    /// Several replicas: invalidations go through a NATS JetStream stream
+    /// that each replica reads with its own durable consumer, so ones sent
+    /// while a replica was disconnected are replayed instead of lost.
+    /// `consumer` must be unique per replica.
+    Nats {
+        url: SecretString,
+        stream: String,
+        consumer: String,
+    },
+}
+
+impl InvalidationBrokerConfig {
+    fn from_env() -> Result<Self, ConfigError> {
+        match env::var("INVALIDATION_BROKER").as_deref() {
+            Err(_) | Ok("") | Ok("in-process") => Ok(Self::InProcess),
+            Ok("redis") => {
+                let url =
+                    env::var("REDIS_URL").map_err(|_| ConfigError::MissingVar("REDIS_URL"))?;
+                let channel = env::var("INVALIDATION_REDIS_CHANNEL")
+                    .ok()
+                    .filter(|channel| !channel.is_empty())
+                    .unwrap_or_else(|| DEFAULT_INVALIDATION_REDIS_CHANNEL.to_string());
+                Ok(Self::Redis {
+                    url: SecretString::new(url.into()),
+                    channel,
+                })
+            }
+            Ok("nats") => {
+                let url = env::var("NATS_URL").map_err(|_| ConfigError::MissingVar("NATS_URL"))?;
+                let stream = env::var("INVALIDATION_NATS_STREAM")
+                    .ok()
+                    .filter(|stream| !stream.is_empty())
+                    .unwrap_or_else(|| DEFAULT_INVALIDATION_NATS_STREAM.to_string());
+                if !is_nats_name(&stream) {
+                    return Err(ConfigError::InvalidVar("INVALIDATION_NATS_STREAM"));
+                }
+                // Pod or container names are unique per replica, which is
+                // what a consumer name has to be.
+                let consumer = env::var("INVALIDATION_NATS_CONSUMER")
+                    .or_else(|_| env::var("HOSTNAME"))
+                    .map_err(|_| ConfigError::MissingVar("INVALIDATION_NATS_CONSUMER"))?;
+                if !is_nats_name(&consumer) {
+                    return Err(ConfigError::InvalidVar("INVALIDATION_NATS_CONSUMER"));
+                }
+                Ok(Self::Nats {
+                    url: SecretString::new(url.into()),
+                    stream,
+                    consumer,
+                })
+            }
+            Ok(_) => Err(ConfigError::InvalidVar("INVALIDATION_BROKER")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PayloadLimits {
+    /// Largest request body accepted on any route.
+    pub max_body_bytes: usize,
+    /// Largest issue title, issue description or comment.
+    pub max_text_bytes: usize,
+}
+
+impl PayloadLimits {
+    fn from_env() -> Result<Self, ConfigError> {
+        Ok(Self {
+            max_body_bytes: byte_limit("MAX_REQUEST_BODY_BYTES", DEFAULT_MAX_REQUEST_BODY_BYTES)?,
+            max_text_bytes: byte_limit("MAX_TEXT_BYTES", DEFAULT_MAX_TEXT_BYTES)?,
+        })
+    }
+}
+
+fn byte_limit(var: &'static str, default: usize) -> Result<usize, ConfigError> {
+    match env::var(var) {
+        Ok(value) => value
+            .parse::<usize>()
+            .ok()
+            .filter(|limit| *limit > 0)
+            .ok_or(ConfigError::InvalidVar(var)),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Authenticated requests allowed per minute; `None` disables that limit.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitConfig {
+    pub user_per_minute: Option<u32>,
+    pub organization_per_minute: Option<u32>,
+}
+
+impl RateLimitConfig {
+    fn from_env() -> Result<Self, ConfigError> {
+        Ok(Self {
+            user_per_minute: requests_per_minute(
+                "RATE_LIMIT_USER_PER_MINUTE",
+                DEFAULT_USER_REQUESTS_PER_MINUTE,
+            )?,
+            organization_per_minute: requests_per_minute(
+                "RATE_LIMIT_ORG_PER_MINUTE",
+                DEFAULT_ORGANIZATION_REQUESTS_PER_MINUTE,
+            )?,
+        })
+    }
+}
+
+/// `0` disables the limit.
+fn requests_per_minute(var: &'static str, default: u32) -> Result<Option<u32>, ConfigError> {
+    match env::var(var) {
+        Ok(value) => match value.parse::<u32>() {
+            Ok(0) => Ok(None),
+            Ok(limit) => Ok(Some(limit)),
+            Err(_) => Err(ConfigError::InvalidVar(var)),
+        },
+        Err(_) => Ok(Some(default)),
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -257,6 +557,44 @@ impl RemoteServerConfig {
 
         let github_app = GitHubAppConfig::from_env()?;
 
+        let tombstone_retention_days = match env::var("ISSUE_TOMBSTONE_RETENTION_DAYS") {
+            Ok(value) => match value.parse::<i64>() {
+                Ok(0) => None,
+                Ok(days) if days > 0 => Some(days),
+                _ => return Err(ConfigError::InvalidVar("ISSUE_TOMBSTONE_RETENTION_DAYS")),
+            },
+            Err(_) => Some(DEFAULT_TOMBSTONE_RETENTION_DAYS),
+        };
+
+        let rate_limit = RateLimitConfig::from_env()?;
+        let payload_limits = PayloadLimits::from_env()?;
+
+        let operator_emails = env::var("OPERATOR_EMAILS")
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|email| !email.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let trusted_proxies = match env::var("TRUSTED_PROXIES") {
+            Ok(value) => parse_trusted_proxies(&value)?,
+            Err(_) => Vec::new(),
+        };
+
+        let invalidation_broker = InvalidationBrokerConfig::from_env()?;
+        let electric_cache = ElectricCacheConfig::from_env()?;
+        let metrics = MetricsConfig::from_env();
+        let shutdown_timeout_secs = match env::var("SHUTDOWN_TIMEOUT_SECS") {
+            Ok(value) => value
+                .parse::<u64>()
+                .map_err(|_| ConfigError::InvalidVar("SHUTDOWN_TIMEOUT_SECS"))?,
+            Err(_) => DEFAULT_SHUTDOWN_TIMEOUT_SECS,
+        };
+
         Ok(Self {
             database_url,
             listen_addr,
@@ -272,6 +610,15 @@ impl RemoteServerConfig {
             review_worker_base_url,
             review_disabled,
             github_app,
+            tombstone_retention_days,
+            rate_limit,
+            payload_limits,
+            operator_emails,
+            trusted_proxies,
+            invalidation_broker,
+            electric_cache,
+            metrics,
+            shutdown_timeout_secs,
         })
     }
 }
@@ -293,6 +640,28 @@ fn parse_publication_names(value: &str) -> Result<Vec<String>, ConfigError> {
     Ok(names)
 }
 
+fn parse_trusted_proxies(value: &str) -> Result<Vec<IpNetwork>, ConfigError> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            entry
+                .parse()
+                .map_err(|_| ConfigError::InvalidVar("TRUSTED_PROXIES"))
+        })
+        .collect()
+}
+
+/// JetStream stream and consumer names can't be empty or contain
+/// whitespace, `.`, `*`, `>`, or path separators.
+fn is_nats_name(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
 fn is_valid_identifier(value: &str) -> bool {
     let mut chars = value.chars();
     let Some(first) = chars.next() else {
@@ -0,0 +1,71 @@
+use api_types::ActivityCursor;
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, Postgres};
+use uuid::Uuid;
+
+pub struct ActivityCursorRepository;
+
+impl ActivityCursorRepository {
+    pub async fn find<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        device_id: &str,
+        project_id: Uuid,
+    ) -> Result<Option<ActivityCursor>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as!(
+            ActivityCursor,
+            r#"
+            SELECT
+                project_id AS "project_id!: Uuid",
+                device_id  AS "device_id!",
+                seq        AS "seq!",
+                updated_at AS "updated_at!: DateTime<Utc>"
+            FROM activity_cursors
+            WHERE user_id = $1 AND device_id = $2 AND project_id = $3
+            "#,
+            user_id,
+            device_id,
+            project_id
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Record that the device processed the feed up to `seq`. Never moves
+    /// the cursor backwards, so acks arriving out of order are harmless.
+    pub async fn ack<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        device_id: &str,
+        project_id: Uuid,
+        seq: i64,
+    ) -> Result<ActivityCursor, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as!(
+            ActivityCursor,
+            r#"
+            INSERT INTO activity_cursors (user_id, device_id, project_id, seq)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id, device_id, project_id) DO UPDATE SET
+                seq        = GREATEST(activity_cursors.seq, EXCLUDED.seq),
+                updated_at = NOW()
+            RETURNING
+                project_id AS "project_id!: Uuid",
+                device_id  AS "device_id!",
+                seq        AS "seq!",
+                updated_at AS "updated_at!: DateTime<Utc>"
+            "#,
+            user_id,
+            device_id,
+            project_id,
+            seq
+        )
+        .fetch_one(executor)
+        .await
+    }
+}
@@ -1,5 +1,6 @@
-pub use api_types::AuthSession;
+pub use api_types::{AuthSession, SessionScope};
 use chrono::Duration;
+use sha2::{Digest, Sha256};
 use sqlx::{PgPool, query_as};
 use thiserror::Error;
 use uuid::Uuid;
@@ -22,6 +23,29 @@ pub enum AuthSessionError {
 
 pub const MAX_SESSION_INACTIVITY_DURATION: Duration = Duration::days(365);
 
+/// Fingerprint of the client presenting a session's tokens, stored as
+/// `auth_sessions.client_fingerprint`. Only the User-Agent's product names
+/// count, not versions or comments, so client upgrades keep their sessions.
+pub fn client_fingerprint(user_agent: Option<&str>) -> String {
+    hex::encode(Sha256::digest(user_agent_products(
+        user_agent.unwrap_or_default(),
+    )))
+}
+
+fn user_agent_products(user_agent: &str) -> String {
+    let mut products = Vec::new();
+    let mut comment_depth = 0usize;
+    for token in user_agent.split_whitespace() {
+        let product_depth = comment_depth;
+        comment_depth += token.matches('(').count();
+        comment_depth = comment_depth.saturating_sub(token.matches(')').count());
+        if product_depth == 0 && !token.starts_with('(') {
+            products.push(token.split('/').next().unwrap_or(token));
+        }
+    }
+    products.join(" ")
+}
+
 pub struct AuthSessionRepository<'a> {
     pool: &'a PgPool,
 }
@@ -35,12 +59,22 @@ impl<'a> AuthSessionRepository<'a> {
         &self,
         user_id: Uuid,
         refresh_token_id: Option<Uuid>,
+    ) -> Result<AuthSession, AuthSessionError> {
+        self.create_with_scope(user_id, refresh_token_id, SessionScope::Full)
+            .await
+    }
+
+    pub async fn create_with_scope(
+        &self,
+        user_id: Uuid,
+        refresh_token_id: Option<Uuid>,
+        scope: SessionScope,
     ) -> Result<AuthSession, AuthSessionError> {
         query_as!(
             AuthSession,
             r#"
-            INSERT INTO auth_sessions (user_id, refresh_token_id)
-            VALUES ($1, $2)
+            INSERT INTO auth_sessions (user_id, refresh_token_id, scope)
+            VALUES ($1, $2, $3)
             RETURNING
                 id                          AS "id!",
                 user_id                     AS "user_id!: Uuid",
@@ -50,10 +84,12 @@ impl<'a> AuthSessionRepository<'a> {
                 refresh_token_id           AS "refresh_token_id?",
                 refresh_token_issued_at     AS "refresh_token_issued_at?",
                 previous_refresh_token_id   AS "previous_refresh_token_id?",
-                previous_refresh_token_grace_expires_at AS "previous_refresh_token_grace_expires_at?"
+                previous_refresh_token_grace_expires_at AS "previous_refresh_token_grace_expires_at?",
+                scope                       AS "scope!: SessionScope"
             "#,
             user_id,
-            refresh_token_id
+            refresh_token_id,
+            scope as SessionScope
         )
         .fetch_one(self.pool)
         .await
@@ -73,7 +109,8 @@ impl<'a> AuthSessionRepository<'a> {
                 refresh_token_id           AS "refresh_token_id?",
                 refresh_token_issued_at     AS "refresh_token_issued_at?",
                 previous_refresh_token_id   AS "previous_refresh_token_id?",
-                previous_refresh_token_grace_expires_at AS "previous_refresh_token_grace_expires_at?"
+                previous_refresh_token_grace_expires_at AS "previous_refresh_token_grace_expires_at?",
+                scope                       AS "scope!: SessionScope"
             FROM auth_sessions
             WHERE id = $1
             "#,
@@ -178,6 +215,28 @@ impl<'a> AuthSessionRepository<'a> {
         Ok(())
     }
 
+    /// Bind the session to `fingerprint` if it isn't bound yet, and report
+    /// whether its fingerprint now matches.
+    pub async fn bind_client_fingerprint(
+        &self,
+        session_id: Uuid,
+        fingerprint: &str,
+    ) -> Result<bool, AuthSessionError> {
+        sqlx::query_scalar!(
+            r#"
+            UPDATE auth_sessions
+            SET client_fingerprint = COALESCE(client_fingerprint, $2)
+            WHERE id = $1
+            RETURNING client_fingerprint = $2 AS "matches!"
+            "#,
+            session_id,
+            fingerprint
+        )
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or(AuthSessionError::NotFound)
+    }
+
     pub async fn revoke_auth_session(&self, session_id: Uuid) -> Result<i64, AuthSessionError> {
         let mut tx = self.pool.begin().await.map_err(AuthSessionError::from)?;
 
@@ -309,3 +368,27 @@ impl<'a> AuthSessionRepository<'a> {
                 .is_some_and(|expires_at| expires_at > chrono::Utc::now())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::user_agent_products;
+
+    #[test]
+    fn user_agent_products_ignore_versions_and_comments() {
+        assert_eq!(
+            user_agent_products(
+                "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 \
+                 (KHTML, like Gecko) Chrome/126.0 Safari/537.36"
+            ),
+            "Mozilla AppleWebKit Chrome Safari"
+        );
+        assert_eq!(
+            user_agent_products("remote-client/0.1.4"),
+            user_agent_products("remote-client/0.2.0")
+        );
+        assert_ne!(
+            user_agent_products("remote-client/0.1.4"),
+            user_agent_products("curl/8.5.0")
+        );
+    }
+}
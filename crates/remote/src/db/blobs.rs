@@ -166,6 +166,7 @@ impl BlobRepository {
 
     /// Get the organization_id for a blob via its project.
     pub async fn organization_id(pool: &PgPool, blob_id: Uuid) -> Result<Option<Uuid>, BlobError> {
+        let mut tx = super::begin_trusted_tx(pool).await?;
         let record = sqlx::query_scalar!(
             r#"
             SELECT p.organization_id
@@ -175,8 +176,9 @@ impl BlobRepository {
             "#,
             blob_id
         )
-        .fetch_optional(pool)
+        .fetch_optional(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         Ok(record)
     }
@@ -1,4 +1,4 @@
-use api_types::{NotificationPayload, NotificationType};
+use api_types::{DigestSettings, NotificationPayload, NotificationType};
 use chrono::{DateTime, Utc};
 use sqlx::{PgPool, Postgres, pool::PoolConnection};
 use uuid::Uuid;
@@ -38,10 +38,14 @@ impl DigestRepository {
         }
     }
 
+    /// Users with notifications to digest. With `due_hour`, only users whose
+    /// digest hour, or `default_hour` if they haven't picked one, is that hour.
     pub async fn fetch_users_with_pending_notifications(
         pool: &PgPool,
         window_start: DateTime<Utc>,
         window_end: DateTime<Utc>,
+        due_hour: Option<i16>,
+        default_hour: i16,
     ) -> Result<Vec<DigestUser>, sqlx::Error> {
         sqlx::query_as!(
             DigestUser,
@@ -63,10 +67,13 @@ impl DigestRepository {
                   FROM notification_digest_deliveries d
                   WHERE d.notification_id = n.id
               )
+              AND ($3::smallint IS NULL OR COALESCE(u.digest_hour_utc, $4) = $3)
             ORDER BY u.id
             "#,
             window_start,
-            window_end
+            window_end,
+            due_hour,
+            default_hour
         )
         .fetch_all(pool)
         .await
@@ -111,6 +118,38 @@ impl DigestRepository {
         .await
     }
 
+    pub async fn settings(pool: &PgPool, user_id: Uuid) -> Result<DigestSettings, sqlx::Error> {
+        let settings = sqlx::query_as!(
+            DigestSettings,
+            r#"SELECT digest_hour_utc FROM users WHERE id = $1"#,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(settings.unwrap_or_default())
+    }
+
+    pub async fn update_settings(
+        pool: &PgPool,
+        user_id: Uuid,
+        digest_hour_utc: Option<i16>,
+    ) -> Result<DigestSettings, sqlx::Error> {
+        sqlx::query_as!(
+            DigestSettings,
+            r#"
+            UPDATE users
+            SET digest_hour_utc = $2
+            WHERE id = $1
+            RETURNING digest_hour_utc
+            "#,
+            user_id,
+            digest_hour_utc
+        )
+        .fetch_one(pool)
+        .await
+    }
+
     pub async fn record_notifications_delivered(
         pool: &PgPool,
         notification_ids: &[Uuid],
@@ -0,0 +1,46 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, Postgres};
+
+pub struct DownloadNonceRepository;
+
+impl DownloadNonceRepository {
+    /// Count one use of `nonce`. Returns `false` if it has already been used
+    /// `max_uses` times.
+    pub async fn redeem<'e, E>(
+        executor: E,
+        nonce: &str,
+        expires_at: DateTime<Utc>,
+        max_uses: i32,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let redeemed = sqlx::query_scalar!(
+            r#"
+            INSERT INTO download_nonces (nonce, expires_at)
+            VALUES ($1, $2)
+            ON CONFLICT (nonce) DO UPDATE
+            SET uses = download_nonces.uses + 1
+            WHERE download_nonces.uses < $3
+            RETURNING TRUE AS "redeemed!"
+            "#,
+            nonce,
+            expires_at,
+            max_uses
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(redeemed.is_some())
+    }
+
+    pub async fn delete_expired<'e, E>(executor: E) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!("DELETE FROM download_nonces WHERE expires_at < NOW()")
+            .execute(executor)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -1,5 +1,6 @@
 use api_types::{
-    AttachmentWithBlob, Issue, IssueAssignee, IssuePriority, Project, ProjectStatus, User,
+    AttachmentWithBlob, Issue, IssueActivity, IssueActivityEventType, IssueAssignee, IssueComment,
+    IssuePriority, Project, ProjectStatus, ProjectStatusCategory, User,
 };
 use chrono::{DateTime, Utc};
 use serde_json::Value;
@@ -7,6 +8,8 @@ use sqlx::PgPool;
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::tenant::TenantId;
+
 #[derive(Debug, Error)]
 pub enum ExportError {
     #[error("database error: {0}")]
@@ -19,9 +22,10 @@ impl ExportRepository {
     /// Fetch all projects the user can export from the organization.
     pub async fn list_accessible_projects_by_organization(
         pool: &PgPool,
-        organization_id: Uuid,
+        tenant: TenantId,
         user_id: Uuid,
     ) -> Result<Vec<Project>, ExportError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let records = sqlx::query_as!(
             Project,
             r#"
@@ -40,11 +44,12 @@ impl ExportRepository {
             WHERE p.organization_id = $1
             ORDER BY p.sort_order ASC, p.created_at DESC
             "#,
-            organization_id,
+            tenant.id(),
             user_id
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         Ok(records)
     }
@@ -52,10 +57,11 @@ impl ExportRepository {
     /// Fetch selected projects the user can export from the organization.
     pub async fn list_accessible_projects_by_ids(
         pool: &PgPool,
-        organization_id: Uuid,
+        tenant: TenantId,
         user_id: Uuid,
         project_ids: &[Uuid],
     ) -> Result<Vec<Project>, ExportError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let records = sqlx::query_as!(
             Project,
             r#"
@@ -75,12 +81,13 @@ impl ExportRepository {
               AND p.id = ANY($2)
             ORDER BY p.sort_order ASC, p.created_at DESC
             "#,
-            organization_id,
+            tenant.id(),
             project_ids,
             user_id
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         Ok(records)
     }
@@ -88,8 +95,10 @@ impl ExportRepository {
     /// Fetch all issues for the given project IDs (no pagination).
     pub async fn list_all_issues_by_projects(
         pool: &PgPool,
+        tenant: TenantId,
         project_ids: &[Uuid],
     ) -> Result<Vec<Issue>, ExportError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let issues = sqlx::query_as!(
             Issue,
             r#"
@@ -118,8 +127,9 @@ impl ExportRepository {
             "#,
             project_ids
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         Ok(issues)
     }
@@ -139,6 +149,7 @@ impl ExportRepository {
                 color           AS "color!",
                 sort_order      AS "sort_order!",
                 hidden          AS "hidden!",
+                category        AS "category: ProjectStatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             FROM project_statuses
             WHERE project_id = ANY($1)
@@ -155,8 +166,10 @@ impl ExportRepository {
     /// Fetch all assignees for issues in the given project IDs.
     pub async fn list_assignees_by_projects(
         pool: &PgPool,
+        tenant: TenantId,
         project_ids: &[Uuid],
     ) -> Result<Vec<IssueAssignee>, ExportError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let records = sqlx::query_as!(
             IssueAssignee,
             r#"
@@ -172,8 +185,75 @@ impl ExportRepository {
             "#,
             project_ids
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(records)
+    }
+
+    /// Fetch all comments on issues in the given project IDs.
+    pub async fn list_comments_by_projects(
+        pool: &PgPool,
+        tenant: TenantId,
+        project_ids: &[Uuid],
+    ) -> Result<Vec<IssueComment>, ExportError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let records = sqlx::query_as!(
+            IssueComment,
+            r#"
+            SELECT
+                c.id          AS "id!: Uuid",
+                c.issue_id    AS "issue_id!: Uuid",
+                c.author_id   AS "author_id: Uuid",
+                c.parent_id   AS "parent_id: Uuid",
+                c.message     AS "message!",
+                c.created_at  AS "created_at!: DateTime<Utc>",
+                c.updated_at  AS "updated_at!: DateTime<Utc>"
+            FROM issue_comments c
+            INNER JOIN issues i ON i.id = c.issue_id
+            WHERE i.project_id = ANY($1)
+            ORDER BY c.created_at ASC
+            "#,
+            project_ids
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(records)
+    }
+
+    /// Fetch the activity log for the given project IDs, including events
+    /// for issues that have since been deleted.
+    pub async fn list_activity_by_projects(
+        pool: &PgPool,
+        tenant: TenantId,
+        project_ids: &[Uuid],
+    ) -> Result<Vec<IssueActivity>, ExportError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let records = sqlx::query_as!(
+            IssueActivity,
+            r#"
+            SELECT
+                seq             AS "seq!",
+                project_id      AS "project_id!: Uuid",
+                issue_id        AS "issue_id?: Uuid",
+                event_type      AS "event_type!: IssueActivityEventType",
+                from_status_id  AS "from_status_id?: Uuid",
+                to_status_id    AS "to_status_id?: Uuid",
+                actor_user_id   AS "actor_user_id?: Uuid",
+                mutation_id     AS "mutation_id?",
+                created_at      AS "created_at!: DateTime<Utc>"
+            FROM issue_activity
+            WHERE project_id = ANY($1)
+            ORDER BY project_id, seq ASC
+            "#,
+            project_ids
+        )
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         Ok(records)
     }
@@ -181,8 +261,10 @@ impl ExportRepository {
     /// Fetch all attachments (with blob metadata) for issues in the given project IDs.
     pub async fn list_attachments_by_projects(
         pool: &PgPool,
+        tenant: TenantId,
         project_ids: &[Uuid],
     ) -> Result<Vec<AttachmentWithBlob>, ExportError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let attachments = sqlx::query_as!(
             AttachmentWithBlob,
             r#"
@@ -210,8 +292,9 @@ impl ExportRepository {
             "#,
             project_ids
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         Ok(attachments)
     }
@@ -219,7 +302,7 @@ impl ExportRepository {
     /// Fetch all users who are members of the given organization.
     pub async fn list_users_by_organization(
         pool: &PgPool,
-        organization_id: Uuid,
+        tenant: TenantId,
     ) -> Result<Vec<User>, ExportError> {
         let users = sqlx::query_as!(
             User,
@@ -236,7 +319,7 @@ impl ExportRepository {
             INNER JOIN organization_member_metadata omm ON omm.user_id = u.id
             WHERE omm.organization_id = $1
             "#,
-            organization_id
+            tenant.id()
         )
         .fetch_all(pool)
         .await?;
@@ -0,0 +1,228 @@
+use api_types::{ExportJob, ExportJobStatus, ExportRequest};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, Postgres};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::tenant::TenantId;
+
+#[derive(Debug, Error)]
+pub enum ExportJobError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// A job's finished archive, for serving downloads.
+pub struct ExportJobArchive {
+    pub filename: String,
+    pub content: Vec<u8>,
+}
+
+pub struct ExportJobRepository;
+
+impl ExportJobRepository {
+    pub async fn create<'e, E>(
+        executor: E,
+        tenant: TenantId,
+        requested_by_user_id: Uuid,
+        request: &ExportRequest,
+    ) -> Result<ExportJob, ExportJobError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let request = sqlx::types::Json(request);
+        let record = sqlx::query_as!(
+            ExportJob,
+            r#"
+            INSERT INTO export_jobs (organization_id, requested_by_user_id, request)
+            VALUES ($1, $2, $3)
+            RETURNING
+                id              AS "id!: Uuid",
+                organization_id AS "organization_id!: Uuid",
+                status          AS "status!: ExportJobStatus",
+                filename,
+                error,
+                created_at      AS "created_at!: DateTime<Utc>",
+                completed_at    AS "completed_at?: DateTime<Utc>",
+                expires_at      AS "expires_at!: DateTime<Utc>"
+            "#,
+            tenant.id(),
+            requested_by_user_id,
+            request as sqlx::types::Json<&ExportRequest>
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// A job owned by `user_id`, if it exists and has not expired.
+    pub async fn find_for_user<'e, E>(
+        executor: E,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<ExportJob>, ExportJobError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            ExportJob,
+            r#"
+            SELECT
+                id              AS "id!: Uuid",
+                organization_id AS "organization_id!: Uuid",
+                status          AS "status!: ExportJobStatus",
+                filename,
+                error,
+                created_at      AS "created_at!: DateTime<Utc>",
+                completed_at    AS "completed_at?: DateTime<Utc>",
+                expires_at      AS "expires_at!: DateTime<Utc>"
+            FROM export_jobs
+            WHERE id = $1 AND requested_by_user_id = $2 AND expires_at > NOW()
+            "#,
+            id,
+            user_id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn mark_running<'e, E>(executor: E, id: Uuid) -> Result<(), ExportJobError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "UPDATE export_jobs SET status = 'running', heartbeat_at = NOW() WHERE id = $1",
+            id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records that the job's process is still working on it.
+    pub async fn heartbeat<'e, E>(executor: E, id: Uuid) -> Result<(), ExportJobError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "UPDATE export_jobs SET heartbeat_at = NOW() WHERE id = $1 AND status = 'running'",
+            id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Fails pending or running jobs not heard from for `stale_after`, whose
+    /// process is gone. Returns how many were failed.
+    pub async fn fail_abandoned<'e, E>(
+        executor: E,
+        stale_after: chrono::Duration,
+    ) -> Result<u64, ExportJobError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            r#"
+            UPDATE export_jobs
+            SET status = 'failed',
+                error = 'export was interrupted; start it again',
+                completed_at = NOW()
+            WHERE status IN ('pending', 'running')
+              AND COALESCE(heartbeat_at, created_at) < NOW() - make_interval(secs => $1)
+            "#,
+            stale_after.num_seconds() as f64
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn complete<'e, E>(
+        executor: E,
+        id: Uuid,
+        filename: &str,
+        content: &[u8],
+    ) -> Result<(), ExportJobError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            UPDATE export_jobs
+            SET status = 'completed', filename = $2, content = $3, completed_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            filename,
+            content
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn fail<'e, E>(executor: E, id: Uuid, error: &str) -> Result<(), ExportJobError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            UPDATE export_jobs
+            SET status = 'failed', error = $2, completed_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            error
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// The finished archive of a completed, unexpired job.
+    pub async fn find_archive<'e, E>(
+        executor: E,
+        id: Uuid,
+    ) -> Result<Option<ExportJobArchive>, ExportJobError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            ExportJobArchive,
+            r#"
+            SELECT
+                filename AS "filename!",
+                content  AS "content!"
+            FROM export_jobs
+            WHERE id = $1
+              AND status = 'completed'
+              AND expires_at > NOW()
+            "#,
+            id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn delete_expired<'e, E>(executor: E) -> Result<u64, ExportJobError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!("DELETE FROM export_jobs WHERE expires_at <= NOW()")
+            .execute(executor)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
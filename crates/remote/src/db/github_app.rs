@@ -3,6 +3,8 @@ use sqlx::{FromRow, PgPool};
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::tenant::TenantId;
+
 #[derive(Debug, Error)]
 pub enum GitHubAppDbError {
     #[error("database error: {0}")]
@@ -63,7 +65,7 @@ impl<'a> GitHubAppRepository2<'a> {
 
     pub async fn create_installation(
         &self,
-        organization_id: Uuid,
+        tenant: TenantId,
         github_installation_id: i64,
         github_account_login: &str,
         github_account_type: &str,
@@ -102,7 +104,7 @@ impl<'a> GitHubAppRepository2<'a> {
                 created_at,
                 updated_at
             "#,
-            organization_id,
+            tenant.id(),
             github_installation_id,
             github_account_login,
             github_account_type,
@@ -176,7 +178,7 @@ impl<'a> GitHubAppRepository2<'a> {
 
     pub async fn get_by_organization(
         &self,
-        organization_id: Uuid,
+        tenant: TenantId,
     ) -> Result<Option<GitHubAppInstallation>, GitHubAppDbError> {
         let installation = sqlx::query_as!(
             GitHubAppInstallation,
@@ -195,7 +197,7 @@ impl<'a> GitHubAppRepository2<'a> {
             FROM github_app_installations
             WHERE organization_id = $1
             "#,
-            organization_id
+            tenant.id()
         )
         .fetch_optional(self.pool)
         .await?;
@@ -220,16 +222,13 @@ impl<'a> GitHubAppRepository2<'a> {
         Ok(())
     }
 
-    pub async fn delete_by_organization(
-        &self,
-        organization_id: Uuid,
-    ) -> Result<(), GitHubAppDbError> {
+    pub async fn delete_by_organization(&self, tenant: TenantId) -> Result<(), GitHubAppDbError> {
         sqlx::query!(
             r#"
             DELETE FROM github_app_installations
             WHERE organization_id = $1
             "#,
-            organization_id
+            tenant.id()
         )
         .execute(self.pool)
         .await?;
@@ -477,7 +476,7 @@ impl<'a> GitHubAppRepository2<'a> {
 
     pub async fn create_pending(
         &self,
-        organization_id: Uuid,
+        tenant: TenantId,
         user_id: Uuid,
         state_token: &str,
         expires_at: DateTime<Utc>,
@@ -488,7 +487,7 @@ impl<'a> GitHubAppRepository2<'a> {
             DELETE FROM github_app_pending_installations
             WHERE organization_id = $1
             "#,
-            organization_id
+            tenant.id()
         )
         .execute(self.pool)
         .await?;
@@ -506,7 +505,7 @@ impl<'a> GitHubAppRepository2<'a> {
                 expires_at,
                 created_at
             "#,
-            organization_id,
+            tenant.id(),
             user_id,
             state_token,
             expires_at
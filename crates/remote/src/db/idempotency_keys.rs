@@ -0,0 +1,156 @@
+use sqlx::{Executor, Postgres};
+use uuid::Uuid;
+
+/// How long a key is remembered. Older keys are treated as unused.
+pub const IDEMPOTENCY_KEY_TTL_HOURS: i32 = 24;
+/// How long an unfinished claim blocks retries. A request that died without
+/// completing or releasing its key (e.g. the server crashed) stops blocking
+/// the key after this, rather than for the whole TTL.
+pub const IDEMPOTENCY_CLAIM_LEASE_SECS: f64 = 60.0;
+
+#[derive(Debug, Clone)]
+pub struct IdempotencyRecord {
+    pub request_hash: String,
+    pub status_code: Option<i32>,
+    pub content_type: Option<String>,
+    pub response_body: Option<Vec<u8>>,
+}
+
+pub struct IdempotencyKeyRepository;
+
+impl IdempotencyKeyRepository {
+    /// Claim `key` for a new request. Returns the claim's token if this
+    /// request owns the key (it was unused, had expired, or its unfinished
+    /// claim's lease ran out), `None` if another request holds it. Only the
+    /// token's holder can [`complete`](Self::complete) or
+    /// [`release`](Self::release) the key.
+    pub async fn claim<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        key: &str,
+        request_hash: &str,
+    ) -> Result<Option<Uuid>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let claim_token = Uuid::new_v4();
+        let claimed = sqlx::query_scalar!(
+            r#"
+            INSERT INTO idempotency_keys (user_id, key, request_hash, claim_token)
+            VALUES ($1, $2, $3, $6)
+            ON CONFLICT (user_id, key) DO UPDATE
+            SET request_hash = EXCLUDED.request_hash,
+                claim_token = EXCLUDED.claim_token,
+                status_code = NULL,
+                content_type = NULL,
+                response_body = NULL,
+                created_at = NOW()
+            WHERE idempotency_keys.created_at < NOW() - make_interval(hours => $4)
+               OR (idempotency_keys.status_code IS NULL
+                   AND idempotency_keys.created_at < NOW() - make_interval(secs => $5))
+            RETURNING TRUE AS "claimed!"
+            "#,
+            user_id,
+            key,
+            request_hash,
+            IDEMPOTENCY_KEY_TTL_HOURS,
+            IDEMPOTENCY_CLAIM_LEASE_SECS,
+            claim_token
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(claimed.map(|_| claim_token))
+    }
+
+    pub async fn find<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        key: &str,
+    ) -> Result<Option<IdempotencyRecord>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as!(
+            IdempotencyRecord,
+            r#"
+            SELECT request_hash, status_code, content_type, response_body
+            FROM idempotency_keys
+            WHERE user_id = $1 AND key = $2
+            "#,
+            user_id,
+            key
+        )
+        .fetch_optional(executor)
+        .await
+    }
+
+    /// Store the response for `key` if `claim_token` still holds it.
+    /// `response_body` is `None` when the body was too large to keep, in
+    /// which case only the status is replayed.
+    pub async fn complete<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        key: &str,
+        claim_token: Uuid,
+        status_code: i32,
+        content_type: Option<&str>,
+        response_body: Option<&[u8]>,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            UPDATE idempotency_keys
+            SET status_code = $4, content_type = $5, response_body = $6
+            WHERE user_id = $1 AND key = $2 AND claim_token = $3
+            "#,
+            user_id,
+            key,
+            claim_token,
+            status_code,
+            content_type,
+            response_body
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Give up a claim so the request can be retried (e.g. after a 5xx).
+    /// Does nothing if another request has taken the key over since.
+    pub async fn release<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        key: &str,
+        claim_token: Uuid,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            "DELETE FROM idempotency_keys WHERE user_id = $1 AND key = $2 AND claim_token = $3",
+            user_id,
+            key,
+            claim_token
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Delete expired keys. Returns the number removed.
+    pub async fn delete_expired<'e, E>(executor: E) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            "DELETE FROM idempotency_keys WHERE created_at < NOW() - make_interval(hours => $1)",
+            IDEMPOTENCY_KEY_TTL_HOURS
+        )
+        .execute(executor)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
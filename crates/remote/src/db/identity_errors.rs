@@ -10,6 +10,8 @@ pub enum IdentityError {
     InvitationError(String),
     #[error("cannot delete organization: {0}")]
     CannotDeleteOrganization(String),
+    #[error("access denied by organization access policy")]
+    AccessPolicyDenied,
     #[error("organization conflict: {0}")]
     OrganizationConflict(String),
     #[error(transparent)]
@@ -0,0 +1,70 @@
+//! Startup sanity check for indexes the hot query paths depend on.
+//!
+//! Migrations create these, but indexes dropped by hand or skipped during a
+//! manual restore only show up later as slow bulk fetches. Missing ones are
+//! logged so they are visible at boot; the server still starts.
+
+use std::collections::HashSet;
+
+use sqlx::PgPool;
+
+/// `(table, index)` pairs expected in the `public` schema.
+const EXPECTED_INDEXES: &[(&str, &str)] = &[
+    ("issues", "idx_issues_project_id"),
+    ("issues", "idx_issues_project_status"),
+    ("issues", "idx_issues_project_sort_order"),
+    ("issues", "idx_issues_project_updated_at"),
+    ("issue_activity", "idx_issue_activity_project_seq"),
+    ("issue_activity", "idx_issue_activity_tombstones"),
+    ("issue_assignees", "idx_issue_assignees_issue_id"),
+    ("issue_tags", "idx_issue_tags_issue_id"),
+];
+
+pub(crate) async fn warn_on_missing_indexes(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let present = sqlx::query_scalar!(
+        r#"SELECT indexname AS "indexname!" FROM pg_indexes WHERE schemaname = 'public'"#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let missing = missing_indexes(&present);
+    if missing.is_empty() {
+        tracing::debug!(checked = EXPECTED_INDEXES.len(), "expected indexes present");
+    } else {
+        tracing::warn!(
+            missing = ?missing,
+            "expected database indexes are missing; bulk issue queries may be slow"
+        );
+    }
+
+    Ok(())
+}
+
+fn missing_indexes(present: &[String]) -> Vec<String> {
+    let present: HashSet<&str> = present.iter().map(String::as_str).collect();
+    EXPECTED_INDEXES
+        .iter()
+        .filter(|(_, index)| !present.contains(index))
+        .map(|(table, index)| format!("{table}.{index}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EXPECTED_INDEXES, missing_indexes};
+
+    #[test]
+    fn reports_only_absent_indexes() {
+        let present: Vec<String> = EXPECTED_INDEXES
+            .iter()
+            .skip(1)
+            .map(|(_, index)| index.to_string())
+            .collect();
+
+        assert_eq!(
+            missing_indexes(&present),
+            vec!["issues.idx_issues_project_id"]
+        );
+        assert_eq!(missing_indexes(&[]).len(), EXPECTED_INDEXES.len());
+    }
+}
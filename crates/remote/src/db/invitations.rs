@@ -9,6 +9,7 @@ use super::{
     identity_errors::IdentityError,
     organization_members::{add_member, assert_admin},
     organizations::{Organization, OrganizationRepository, is_personal_org},
+    tenant::TenantId,
 };
 use crate::db::organization_members::is_member;
 
@@ -37,17 +38,17 @@ impl<'a> InvitationRepository<'a> {
 
     pub async fn create_invitation(
         &self,
-        organization_id: Uuid,
+        tenant: TenantId,
         invited_by_user_id: Uuid,
         email: &str,
         role: MemberRole,
         expires_at: DateTime<Utc>,
         token: &str,
     ) -> Result<Invitation, IdentityError> {
-        assert_admin(self.pool, organization_id, invited_by_user_id).await?;
+        assert_admin(self.pool, tenant.id(), invited_by_user_id).await?;
 
         if OrganizationRepository::new(self.pool)
-            .is_personal(organization_id)
+            .is_personal(tenant.id())
             .await?
         {
             return Err(IdentityError::InvitationError(
@@ -74,7 +75,7 @@ impl<'a> InvitationRepository<'a> {
                 created_at AS "created_at!",
                 updated_at AS "updated_at!"
             "#,
-            organization_id,
+            tenant.id(),
             invited_by_user_id,
             email,
             role as MemberRole,
@@ -99,13 +100,13 @@ impl<'a> InvitationRepository<'a> {
 
     pub async fn list_invitations(
         &self,
-        organization_id: Uuid,
+        tenant: TenantId,
         requesting_user_id: Uuid,
     ) -> Result<Vec<Invitation>, IdentityError> {
-        assert_admin(self.pool, organization_id, requesting_user_id).await?;
+        assert_admin(self.pool, tenant.id(), requesting_user_id).await?;
 
         if OrganizationRepository::new(self.pool)
-            .is_personal(organization_id)
+            .is_personal(tenant.id())
             .await?
         {
             return Err(IdentityError::InvitationError(
@@ -131,7 +132,7 @@ impl<'a> InvitationRepository<'a> {
             WHERE organization_id = $1
             ORDER BY created_at DESC
             "#,
-            organization_id
+            tenant.id()
         )
         .fetch_all(self.pool)
         .await?;
@@ -166,11 +167,11 @@ impl<'a> InvitationRepository<'a> {
 
     pub async fn revoke_invitation(
         &self,
-        organization_id: Uuid,
+        tenant: TenantId,
         invitation_id: Uuid,
         requesting_user_id: Uuid,
     ) -> Result<(), IdentityError> {
-        assert_admin(self.pool, organization_id, requesting_user_id).await?;
+        assert_admin(self.pool, tenant.id(), requesting_user_id).await?;
 
         let result = sqlx::query!(
             r#"
@@ -178,7 +179,7 @@ impl<'a> InvitationRepository<'a> {
             WHERE id = $1 AND organization_id = $2
             "#,
             invitation_id,
-            organization_id
+            tenant.id()
         )
         .execute(self.pool)
         .await?;
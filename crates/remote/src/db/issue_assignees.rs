@@ -1,10 +1,10 @@
-use api_types::{DeleteResponse, IssueAssignee, MutationResponse};
+use api_types::{IssueAssignee, MutationResponse};
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 
-use super::get_txid;
+use super::{get_txid, tenant::TenantId};
 
 #[derive(Debug, Error)]
 pub enum IssueAssigneeError {
@@ -63,8 +63,10 @@ impl IssueAssigneeRepository {
 
     pub async fn list_by_project(
         pool: &PgPool,
+        tenant: TenantId,
         project_id: Uuid,
     ) -> Result<Vec<IssueAssignee>, IssueAssigneeError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let records = sqlx::query_as!(
             IssueAssignee,
             r#"
@@ -78,8 +80,9 @@ impl IssueAssigneeRepository {
             "#,
             project_id
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(records)
     }
 
@@ -89,8 +92,26 @@ impl IssueAssigneeRepository {
         issue_id: Uuid,
         user_id: Uuid,
     ) -> Result<MutationResponse<IssueAssignee>, IssueAssigneeError> {
-        let id = id.unwrap_or_else(Uuid::new_v4);
         let mut tx = super::begin_tx(pool).await?;
+        let data = Self::insert(&mut *tx, id, issue_id, user_id).await?;
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
+    /// Insert an assignee using the caller's executor, for composing with
+    /// other writes in one transaction.
+    pub async fn insert<'e, E>(
+        executor: E,
+        id: Option<Uuid>,
+        issue_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<IssueAssignee, IssueAssigneeError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let id = id.unwrap_or_else(Uuid::new_v4);
         let data = sqlx::query_as!(
             IssueAssignee,
             r#"
@@ -106,21 +127,19 @@ impl IssueAssigneeRepository {
             issue_id,
             user_id
         )
-        .fetch_one(&mut *tx)
+        .fetch_one(executor)
         .await?;
-        let txid = get_txid(&mut *tx).await?;
-        tx.commit().await?;
 
-        Ok(MutationResponse { data, txid })
+        Ok(data)
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueAssigneeError> {
-        let mut tx = super::begin_tx(pool).await?;
+    pub async fn delete<'e, E>(executor: E, id: Uuid) -> Result<(), IssueAssigneeError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         sqlx::query!("DELETE FROM issue_assignees WHERE id = $1", id)
-            .execute(&mut *tx)
+            .execute(executor)
             .await?;
-        let txid = get_txid(&mut *tx).await?;
-        tx.commit().await?;
-        Ok(DeleteResponse { txid })
+        Ok(())
     }
 }
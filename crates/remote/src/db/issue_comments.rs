@@ -1,6 +1,6 @@
 use api_types::{DeleteResponse, IssueComment, MutationResponse};
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -49,9 +49,29 @@ impl IssueCommentRepository {
         parent_id: Option<Uuid>,
         message: String,
     ) -> Result<MutationResponse<IssueComment>, IssueCommentError> {
+        let mut tx = super::begin_tx(pool).await?;
+        let data = Self::insert(&mut *tx, id, issue_id, author_id, parent_id, message).await?;
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
+    /// Insert a comment using the caller's executor, for composing with
+    /// other writes in one transaction.
+    pub async fn insert<'e, E>(
+        executor: E,
+        id: Option<Uuid>,
+        issue_id: Uuid,
+        author_id: Uuid,
+        parent_id: Option<Uuid>,
+        message: String,
+    ) -> Result<IssueComment, IssueCommentError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         let id = id.unwrap_or_else(Uuid::new_v4);
         let now = Utc::now();
-        let mut tx = super::begin_tx(pool).await?;
         let data = sqlx::query_as!(
             IssueComment,
             r#"
@@ -74,12 +94,10 @@ impl IssueCommentRepository {
             now,
             now
         )
-        .fetch_one(&mut *tx)
+        .fetch_one(executor)
         .await?;
-        let txid = get_txid(&mut *tx).await?;
-        tx.commit().await?;
 
-        Ok(MutationResponse { data, txid })
+        Ok(data)
     }
 
     /// Update an issue comment with partial fields. Uses COALESCE to preserve existing values
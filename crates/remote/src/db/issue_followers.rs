@@ -3,7 +3,7 @@ use sqlx::PgPool;
 use thiserror::Error;
 use uuid::Uuid;
 
-use super::get_txid;
+use super::{get_txid, tenant::TenantId};
 
 #[derive(Debug, Error)]
 pub enum IssueFollowerError {
@@ -60,8 +60,10 @@ impl IssueFollowerRepository {
 
     pub async fn list_by_project(
         pool: &PgPool,
+        tenant: TenantId,
         project_id: Uuid,
     ) -> Result<Vec<IssueFollower>, IssueFollowerError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let records = sqlx::query_as!(
             IssueFollower,
             r#"
@@ -74,8 +76,9 @@ impl IssueFollowerRepository {
             "#,
             project_id
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(records)
     }
 
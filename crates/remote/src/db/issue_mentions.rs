@@ -0,0 +1,54 @@
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::tenant::TenantId;
+
+#[derive(Debug, Error)]
+pub enum IssueMentionError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct IssueMentionRepository;
+
+impl IssueMentionRepository {
+    /// Record mentions of `usernames` (lowercase) in an issue's description,
+    /// or in `comment_id` when given. Only members of `tenant` other
+    /// than `mentioned_by` count. Returns the users who weren't already
+    /// mentioned there.
+    pub async fn record(
+        pool: &PgPool,
+        tenant: TenantId,
+        issue_id: Uuid,
+        comment_id: Option<Uuid>,
+        mentioned_by: Uuid,
+        usernames: &[String],
+    ) -> Result<Vec<Uuid>, IssueMentionError> {
+        // Scoped so the trigger recording `mentioned` activity can see the issue.
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let user_ids = sqlx::query_scalar!(
+            r#"
+            INSERT INTO issue_mentions (issue_id, comment_id, user_id, mentioned_by)
+            SELECT $2, $3, u.id, $4
+            FROM users u
+            JOIN organization_member_metadata m
+                ON m.user_id = u.id AND m.organization_id = $1
+            WHERE LOWER(u.username) = ANY($5::text[])
+              AND u.id <> $4
+            ON CONFLICT DO NOTHING
+            RETURNING user_id AS "user_id!: Uuid"
+            "#,
+            tenant.id(),
+            issue_id,
+            comment_id,
+            mentioned_by,
+            usernames
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(user_ids)
+    }
+}
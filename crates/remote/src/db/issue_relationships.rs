@@ -1,17 +1,23 @@
 use api_types::{DeleteResponse, IssueRelationship, IssueRelationshipType, MutationResponse};
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{Executor, PgConnection, PgPool, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 
-use super::get_txid;
+use super::{get_txid, tenant::TenantId};
 
 #[derive(Debug, Error)]
 pub enum IssueRelationshipError {
     #[error("database error: {0}")]
     Database(#[from] sqlx::Error),
+    #[error("blocking relationship would create a cycle")]
+    BlockingCycle,
 }
 
+/// Advisory lock serializing blocking-edge inserts so concurrent requests
+/// can't each pass the cycle check and together form a cycle.
+const BLOCKING_GRAPH_LOCK_ID: i64 = 3_447_201_003;
+
 pub struct IssueRelationshipRepository;
 
 impl IssueRelationshipRepository {
@@ -65,8 +71,10 @@ impl IssueRelationshipRepository {
 
     pub async fn list_by_project(
         pool: &PgPool,
+        tenant: TenantId,
         project_id: Uuid,
     ) -> Result<Vec<IssueRelationship>, IssueRelationshipError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let records = sqlx::query_as!(
             IssueRelationship,
             r#"
@@ -81,20 +89,47 @@ impl IssueRelationshipRepository {
             "#,
             project_id
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(records)
     }
 
     pub async fn create(
         pool: &PgPool,
+        tenant: TenantId,
         id: Option<Uuid>,
         issue_id: Uuid,
         related_issue_id: Uuid,
         relationship_type: IssueRelationshipType,
     ) -> Result<MutationResponse<IssueRelationship>, IssueRelationshipError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let data = Self::insert(&mut tx, id, issue_id, related_issue_id, relationship_type).await?;
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+        Ok(MutationResponse { data, txid })
+    }
+
+    /// Insert a relationship on the caller's connection, for composing with
+    /// other writes in one transaction. Blocking edges are rejected if they
+    /// would close a cycle.
+    pub async fn insert(
+        conn: &mut PgConnection,
+        id: Option<Uuid>,
+        issue_id: Uuid,
+        related_issue_id: Uuid,
+        relationship_type: IssueRelationshipType,
+    ) -> Result<IssueRelationship, IssueRelationshipError> {
         let id = id.unwrap_or_else(Uuid::new_v4);
-        let mut tx = super::begin_tx(pool).await?;
+        if relationship_type == IssueRelationshipType::Blocking {
+            sqlx::query("SELECT pg_advisory_xact_lock($1)")
+                .bind(BLOCKING_GRAPH_LOCK_ID)
+                .execute(&mut *conn)
+                .await?;
+            if Self::blocks_transitively(&mut *conn, related_issue_id, issue_id).await? {
+                return Err(IssueRelationshipError::BlockingCycle);
+            }
+        }
         let data = sqlx::query_as!(
             IssueRelationship,
             r#"
@@ -112,15 +147,50 @@ impl IssueRelationshipRepository {
             related_issue_id,
             relationship_type as IssueRelationshipType
         )
-        .fetch_one(&mut *tx)
+        .fetch_one(&mut *conn)
         .await?;
-        let txid = get_txid(&mut *tx).await?;
-        tx.commit().await?;
-        Ok(MutationResponse { data, txid })
+        Ok(data)
+    }
+
+    /// Whether `blocker` blocks `blocked` directly or through a chain of
+    /// blocking relationships.
+    async fn blocks_transitively<'e, E>(
+        executor: E,
+        blocker: Uuid,
+        blocked: Uuid,
+    ) -> Result<bool, IssueRelationshipError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let exists = sqlx::query_scalar!(
+            r#"
+            WITH RECURSIVE downstream AS (
+                SELECT related_issue_id AS id
+                FROM issue_relationships
+                WHERE issue_id = $1 AND relationship_type = 'blocking'
+                UNION
+                SELECT r.related_issue_id
+                FROM issue_relationships r
+                JOIN downstream d ON r.issue_id = d.id
+                WHERE r.relationship_type = 'blocking'
+            )
+            SELECT EXISTS (SELECT 1 FROM downstream WHERE id = $2) AS "exists!"
+            "#,
+            blocker,
+            blocked
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(exists)
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueRelationshipError> {
-        let mut tx = super::begin_tx(pool).await?;
+    pub async fn delete(
+        pool: &PgPool,
+        tenant: TenantId,
+        id: Uuid,
+    ) -> Result<DeleteResponse, IssueRelationshipError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         sqlx::query!("DELETE FROM issue_relationships WHERE id = $1", id)
             .execute(&mut *tx)
             .await?;
@@ -1,9 +1,9 @@
 use api_types::{DeleteResponse, IssueTag, MutationResponse};
-use sqlx::PgPool;
+use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 
-use super::get_txid;
+use super::{get_txid, tenant::TenantId};
 
 #[derive(Debug, Error)]
 pub enum IssueTagError {
@@ -57,8 +57,10 @@ impl IssueTagRepository {
 
     pub async fn list_by_project(
         pool: &PgPool,
+        tenant: TenantId,
         project_id: Uuid,
     ) -> Result<Vec<IssueTag>, IssueTagError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let records = sqlx::query_as!(
             IssueTag,
             r#"
@@ -71,8 +73,9 @@ impl IssueTagRepository {
             "#,
             project_id
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(records)
     }
 
@@ -82,8 +85,25 @@ impl IssueTagRepository {
         issue_id: Uuid,
         tag_id: Uuid,
     ) -> Result<MutationResponse<IssueTag>, IssueTagError> {
-        let id = id.unwrap_or_else(Uuid::new_v4);
         let mut tx = super::begin_tx(pool).await?;
+        let data = Self::insert(&mut *tx, id, issue_id, tag_id).await?;
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+        Ok(MutationResponse { data, txid })
+    }
+
+    /// Insert an issue tag using the caller's executor, for composing with
+    /// other writes in one transaction.
+    pub async fn insert<'e, E>(
+        executor: E,
+        id: Option<Uuid>,
+        issue_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<IssueTag, IssueTagError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let id = id.unwrap_or_else(Uuid::new_v4);
         let data = sqlx::query_as!(
             IssueTag,
             r#"
@@ -98,11 +118,9 @@ impl IssueTagRepository {
             issue_id,
             tag_id
         )
-        .fetch_one(&mut *tx)
+        .fetch_one(executor)
         .await?;
-        let txid = get_txid(&mut *tx).await?;
-        tx.commit().await?;
-        Ok(MutationResponse { data, txid })
+        Ok(data)
     }
 
     pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueTagError> {
@@ -1,6 +1,6 @@
 use api_types::{
-    DeleteResponse, Issue, IssuePriority, IssueSortField, ListIssuesResponse, MutationResponse,
-    PullRequestStatus, SearchIssuesRequest, SortDirection,
+    Issue, IssuePriority, IssueSortField, ListIssuesResponse, MutationResponse, PullRequestStatus,
+    SearchIssuesRequest, SortDirection,
 };
 use chrono::{DateTime, Utc};
 use serde_json::Value;
@@ -10,7 +10,7 @@ use uuid::Uuid;
 
 use super::{
     get_txid, issue_assignees::IssueAssigneeRepository, project_statuses::ProjectStatusRepository,
-    pull_requests::PullRequestRepository, workspaces::WorkspaceRepository,
+    pull_requests::PullRequestRepository, tenant::TenantId, workspaces::WorkspaceRepository,
 };
 
 #[derive(Debug, Error)]
@@ -39,7 +39,9 @@ impl IssueRepository {
     fn sort_field_key(sort_field: IssueSortField) -> &'static str {
         match sort_field {
             IssueSortField::SortOrder => "sort_order",
+            IssueSortField::SortKey => "sort_key",
             IssueSortField::Priority => "priority",
+            IssueSortField::TargetDate => "target_date",
             IssueSortField::CreatedAt => "created_at",
             IssueSortField::UpdatedAt => "updated_at",
             IssueSortField::Title => "title",
@@ -62,6 +64,7 @@ impl IssueRepository {
 
     pub async fn search(
         pool: &PgPool,
+        tenant: TenantId,
         query: &SearchIssuesRequest,
     ) -> Result<ListIssuesResponse, IssueError> {
         let status_ids = query.status_ids.as_deref();
@@ -72,6 +75,7 @@ impl IssueRepository {
             .map(|search| format!("%{search}%"));
         let simple_id = query.simple_id.as_deref().map(Self::escape_like_pattern);
         let tag_ids = query.tag_ids.as_deref();
+        let q = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty());
         let sort_field =
             Self::sort_field_key(query.sort_field.unwrap_or(IssueSortField::SortOrder));
         let sort_direction =
@@ -82,6 +86,7 @@ impl IssueRepository {
             .map(|value| value.max(0) as i64)
             .unwrap_or(i64::MAX);
 
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let total_count = sqlx::query_scalar!(
             r#"
             SELECT COUNT(*)::BIGINT
@@ -97,6 +102,11 @@ impl IssueRepository {
                   OR COALESCE(i.description, '') ILIKE $6 ESCAPE '\'
               )
               AND ($7::text IS NULL OR i.simple_id ILIKE $7 ESCAPE '\')
+              AND (
+                  $11::text IS NULL
+                  OR issue_search_vector(i.title, i.description)
+                      @@ websearch_to_tsquery('simple', $11)
+              )
               AND (
                   $8::uuid IS NULL
                   OR EXISTS (
@@ -132,8 +142,9 @@ impl IssueRepository {
             query.assignee_user_id,
             query.tag_id,
             tag_ids,
+            q,
         )
-        .fetch_one(pool)
+        .fetch_one(&mut *tx)
         .await?
         .unwrap_or(0) as usize;
 
@@ -172,6 +183,11 @@ impl IssueRepository {
                   OR COALESCE(i.description, '') ILIKE $6 ESCAPE '\'
               )
               AND ($7::text IS NULL OR i.simple_id ILIKE $7 ESCAPE '\')
+              AND (
+                  $15::text IS NULL
+                  OR issue_search_vector(i.title, i.description)
+                      @@ websearch_to_tsquery('simple', $15)
+              )
               AND (
                   $8::uuid IS NULL
                   OR EXISTS (
@@ -204,10 +220,10 @@ impl IssueRepository {
                     WHEN $11 = 'sort_order' AND $12 = 'desc' THEN ps.sort_order
                 END DESC NULLS LAST,
                 CASE
-                    WHEN $11 = 'sort_order' AND $12 = 'asc' THEN i.sort_order
+                    WHEN $11 IN ('sort_order', 'sort_key') AND $12 = 'asc' THEN i.sort_order
                 END ASC NULLS LAST,
                 CASE
-                    WHEN $11 = 'sort_order' AND $12 = 'desc' THEN i.sort_order
+                    WHEN $11 IN ('sort_order', 'sort_key') AND $12 = 'desc' THEN i.sort_order
                 END DESC NULLS LAST,
                 CASE
                     WHEN $11 = 'priority' AND $12 = 'asc' THEN i.priority
@@ -215,6 +231,12 @@ impl IssueRepository {
                 CASE
                     WHEN $11 = 'priority' AND $12 = 'desc' THEN i.priority
                 END DESC NULLS FIRST,
+                CASE
+                    WHEN $11 = 'target_date' AND $12 = 'asc' THEN i.target_date
+                END ASC NULLS LAST,
+                CASE
+                    WHEN $11 = 'target_date' AND $12 = 'desc' THEN i.target_date
+                END DESC NULLS LAST,
                 CASE
                     WHEN $11 = 'created_at' AND $12 = 'asc' THEN i.created_at
                 END ASC NULLS LAST,
@@ -251,9 +273,11 @@ impl IssueRepository {
             sort_direction,
             query_limit,
             offset as i64,
+            q,
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         let limit = query.limit.unwrap_or(issues.len() as i32).max(0) as usize;
 
@@ -265,6 +289,170 @@ impl IssueRepository {
         })
     }
 
+    /// Every issue in `project_id`, in board order.
+    pub async fn list_by_project<'e, E>(
+        executor: E,
+        project_id: Uuid,
+    ) -> Result<Vec<Issue>, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let issues = sqlx::query_as!(
+            Issue,
+            r#"
+            SELECT
+                i.id                  AS "id!: Uuid",
+                i.project_id          AS "project_id!: Uuid",
+                i.issue_number        AS "issue_number!",
+                i.simple_id           AS "simple_id!",
+                i.status_id           AS "status_id!: Uuid",
+                i.title               AS "title!",
+                i.description         AS "description?",
+                i.priority            AS "priority: IssuePriority",
+                i.start_date          AS "start_date?: DateTime<Utc>",
+                i.target_date         AS "target_date?: DateTime<Utc>",
+                i.completed_at        AS "completed_at?: DateTime<Utc>",
+                i.sort_order          AS "sort_order!",
+                i.parent_issue_id     AS "parent_issue_id?: Uuid",
+                i.parent_issue_sort_order AS "parent_issue_sort_order?",
+                i.extension_metadata  AS "extension_metadata!: Value",
+                i.creator_user_id     AS "creator_user_id?: Uuid",
+                i.created_at          AS "created_at!: DateTime<Utc>",
+                i.updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM issues i
+            WHERE i.project_id = $1
+            ORDER BY i.sort_order ASC, i.id ASC
+            "#,
+            project_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(issues)
+    }
+
+    /// Issues in every project of the organization.
+    pub async fn list_by_organization<'e, E>(
+        executor: E,
+        tenant: TenantId,
+    ) -> Result<Vec<Issue>, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let issues = sqlx::query_as!(
+            Issue,
+            r#"
+            SELECT
+                i.id                  AS "id!: Uuid",
+                i.project_id          AS "project_id!: Uuid",
+                i.issue_number        AS "issue_number!",
+                i.simple_id           AS "simple_id!",
+                i.status_id           AS "status_id!: Uuid",
+                i.title               AS "title!",
+                i.description         AS "description?",
+                i.priority            AS "priority: IssuePriority",
+                i.start_date          AS "start_date?: DateTime<Utc>",
+                i.target_date         AS "target_date?: DateTime<Utc>",
+                i.completed_at        AS "completed_at?: DateTime<Utc>",
+                i.sort_order          AS "sort_order!",
+                i.parent_issue_id     AS "parent_issue_id?: Uuid",
+                i.parent_issue_sort_order AS "parent_issue_sort_order?",
+                i.extension_metadata  AS "extension_metadata!: Value",
+                i.creator_user_id     AS "creator_user_id?: Uuid",
+                i.created_at          AS "created_at!: DateTime<Utc>",
+                i.updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM issues i
+            JOIN projects p ON p.id = i.project_id
+            WHERE p.organization_id = $1
+            ORDER BY i.project_id ASC, i.sort_order ASC, i.id ASC
+            "#,
+            tenant.id()
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(issues)
+    }
+
+    /// Up to `limit` issues in `project_id` ordered by `(updated_at, id)`,
+    /// starting after `after` when given.
+    ///
+    /// `updated_at` is the writing transaction's start time, so a transaction
+    /// still open can commit rows below the newest one visible now. Issues
+    /// updated at or after the start of the oldest open transaction are left
+    /// for a later page, which keeps `(updated_at, id)` safe to resume from.
+    /// Only sessions of this role are seen, which covers the server's own.
+    pub async fn list_page(
+        pool: &PgPool,
+        tenant: TenantId,
+        project_id: Uuid,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i32,
+    ) -> Result<ListIssuesResponse, IssueError> {
+        let (after_updated_at, after_id) = after.unzip();
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+
+        let total_count = sqlx::query_scalar!(
+            r#"SELECT COUNT(*)::BIGINT AS "count!" FROM issues WHERE project_id = $1"#,
+            project_id
+        )
+        .fetch_one(&mut *tx)
+        .await? as usize;
+
+        let issues = sqlx::query_as!(
+            Issue,
+            r#"
+            SELECT
+                i.id                  AS "id!: Uuid",
+                i.project_id          AS "project_id!: Uuid",
+                i.issue_number        AS "issue_number!",
+                i.simple_id           AS "simple_id!",
+                i.status_id           AS "status_id!: Uuid",
+                i.title               AS "title!",
+                i.description         AS "description?",
+                i.priority            AS "priority: IssuePriority",
+                i.start_date          AS "start_date?: DateTime<Utc>",
+                i.target_date         AS "target_date?: DateTime<Utc>",
+                i.completed_at        AS "completed_at?: DateTime<Utc>",
+                i.sort_order          AS "sort_order!",
+                i.parent_issue_id     AS "parent_issue_id?: Uuid",
+                i.parent_issue_sort_order AS "parent_issue_sort_order?",
+                i.extension_metadata  AS "extension_metadata!: Value",
+                i.creator_user_id     AS "creator_user_id?: Uuid",
+                i.created_at          AS "created_at!: DateTime<Utc>",
+                i.updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM issues i
+            WHERE i.project_id = $1
+              AND (
+                  $2::timestamptz IS NULL
+                  OR (i.updated_at, i.id) > ($2, $3::uuid)
+              )
+              AND i.updated_at < (
+                  SELECT MIN(a.xact_start)
+                  FROM pg_stat_activity a
+                  WHERE a.datname = current_database()
+                    AND a.backend_type = 'client backend'
+              )
+            ORDER BY i.updated_at ASC, i.id ASC
+            LIMIT $4
+            "#,
+            project_id,
+            after_updated_at,
+            after_id,
+            limit as i64
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(ListIssuesResponse {
+            issues,
+            total_count,
+            limit: limit as usize,
+            offset: 0,
+        })
+    }
+
     pub async fn find_by_id<'e, E>(executor: E, id: Uuid) -> Result<Option<Issue>, IssueError>
     where
         E: Executor<'e, Database = Postgres>,
@@ -302,10 +490,88 @@ impl IssueRepository {
         Ok(record)
     }
 
+    /// [`Self::find_by_id`] in its own transaction scoped to `tenant`.
+    pub async fn find_in_tenant(
+        pool: &PgPool,
+        tenant: TenantId,
+        id: Uuid,
+    ) -> Result<Option<Issue>, IssueError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let record = Self::find_by_id(&mut *tx, id).await?;
+        tx.commit().await?;
+
+        Ok(record)
+    }
+
+    /// All sub-issues below `id`, at any depth, in sibling order.
+    pub async fn find_descendants<'e, E>(executor: E, id: Uuid) -> Result<Vec<Issue>, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        // UNION (not UNION ALL) stops at already-visited rows, so a
+        // malformed parent cycle can't recurse forever.
+        let records = sqlx::query_as!(
+            Issue,
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT id FROM issues WHERE parent_issue_id = $1
+                UNION
+                SELECT i.id FROM issues i JOIN tree t ON i.parent_issue_id = t.id
+            )
+            SELECT
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                issue_number        AS "issue_number!",
+                simple_id           AS "simple_id!",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                parent_issue_sort_order AS "parent_issue_sort_order?",
+                extension_metadata  AS "extension_metadata!: Value",
+                creator_user_id     AS "creator_user_id?: Uuid",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM issues
+            WHERE id IN (SELECT id FROM tree) AND id <> $1
+            ORDER BY parent_issue_sort_order ASC NULLS LAST, sort_order ASC, created_at ASC
+            "#,
+            id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Lock an issue row for the rest of the transaction and return its
+    /// current `updated_at`, for precondition checks.
+    pub async fn lock_for_update(
+        conn: &mut PgConnection,
+        id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>, IssueError> {
+        let updated_at = sqlx::query_scalar!(
+            r#"SELECT updated_at AS "updated_at!: DateTime<Utc>" FROM issues WHERE id = $1 FOR UPDATE"#,
+            id
+        )
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(updated_at)
+    }
+
+    /// The organization an issue belongs to. Runs outside any tenant, since
+    /// the access guards use it to find the tenant to check.
     pub async fn organization_id(
         pool: &PgPool,
         issue_id: Uuid,
     ) -> Result<Option<Uuid>, IssueError> {
+        let mut tx = super::begin_trusted_tx(pool).await?;
         let record = sqlx::query_scalar!(
             r#"
             SELECT p.organization_id
@@ -315,7 +581,42 @@ impl IssueRepository {
             "#,
             issue_id
         )
-        .fetch_optional(pool)
+        .fetch_optional(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(record)
+    }
+
+    /// Whether `user_id` created the issue and nobody but `except_assignee`
+    /// is assigned to it, or `None` if there is no such issue. Locks the
+    /// issue, so no assignee can be added before the caller's transaction
+    /// ends.
+    pub async fn is_unassigned_creator(
+        conn: &mut PgConnection,
+        issue_id: Uuid,
+        user_id: Uuid,
+        except_assignee: Option<Uuid>,
+    ) -> Result<Option<bool>, IssueError> {
+        let record = sqlx::query_scalar!(
+            r#"
+            SELECT
+                COALESCE(i.creator_user_id = $2, FALSE)
+                AND NOT EXISTS (
+                    SELECT 1
+                    FROM issue_assignees a
+                    WHERE a.issue_id = i.id
+                      AND a.id IS DISTINCT FROM $3
+                ) AS "is_unassigned_creator!"
+            FROM issues i
+            WHERE i.id = $1
+            FOR UPDATE OF i
+            "#,
+            issue_id,
+            user_id,
+            except_assignee
+        )
+        .fetch_optional(conn)
         .await?;
 
         Ok(record)
@@ -324,6 +625,7 @@ impl IssueRepository {
     #[allow(clippy::too_many_arguments)]
     pub async fn create(
         pool: &PgPool,
+        tenant: TenantId,
         id: Option<Uuid>,
         project_id: Uuid,
         status_id: Uuid,
@@ -339,8 +641,54 @@ impl IssueRepository {
         extension_metadata: Value,
         creator_user_id: Uuid,
     ) -> Result<MutationResponse<Issue>, IssueError> {
-        let mut tx = super::begin_tx(pool).await?;
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let data = Self::insert(
+            &mut *tx,
+            id,
+            project_id,
+            status_id,
+            title,
+            description,
+            priority,
+            start_date,
+            target_date,
+            completed_at,
+            sort_order,
+            parent_issue_id,
+            parent_issue_sort_order,
+            extension_metadata,
+            creator_user_id,
+        )
+        .await?;
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
 
+    /// Insert an issue using the caller's executor, for composing with other
+    /// writes in one transaction.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn insert<'e, E>(
+        executor: E,
+        id: Option<Uuid>,
+        project_id: Uuid,
+        status_id: Uuid,
+        title: String,
+        description: Option<String>,
+        priority: Option<IssuePriority>,
+        start_date: Option<DateTime<Utc>>,
+        target_date: Option<DateTime<Utc>>,
+        completed_at: Option<DateTime<Utc>>,
+        sort_order: f64,
+        parent_issue_id: Option<Uuid>,
+        parent_issue_sort_order: Option<f64>,
+        extension_metadata: Value,
+        creator_user_id: Uuid,
+    ) -> Result<Issue, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         let id = id.unwrap_or_else(Uuid::new_v4);
         // Note: issue_number and simple_id are auto-generated by the DB trigger
         let data = sqlx::query_as!(
@@ -388,13 +736,10 @@ impl IssueRepository {
             extension_metadata,
             creator_user_id
         )
-        .fetch_one(&mut *tx)
+        .fetch_one(executor)
         .await?;
 
-        let txid = get_txid(&mut *tx).await?;
-        tx.commit().await?;
-
-        Ok(MutationResponse { data, txid })
+        Ok(data)
     }
 
     /// Update an issue with partial fields.
@@ -504,17 +849,14 @@ impl IssueRepository {
         Ok(data)
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueError> {
-        let mut tx = super::begin_tx(pool).await?;
-
+    pub async fn delete<'e, E>(executor: E, id: Uuid) -> Result<(), IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         sqlx::query!("DELETE FROM issues WHERE id = $1", id)
-            .execute(&mut *tx)
+            .execute(executor)
             .await?;
-
-        let txid = get_txid(&mut *tx).await?;
-        tx.commit().await?;
-
-        Ok(DeleteResponse { txid })
+        Ok(())
     }
 
     /// Syncs issue status based on a workflow signal.
@@ -575,7 +917,8 @@ impl IssueRepository {
 
     /// Syncs issue status based on the current pull-request status.
     /// - Open PR => move issue to "In review"
-    /// - Merged/closed PR => if all linked PRs are merged, move issue to "Done"
+    /// - Merged/closed PR => if the linked PRs taken together are merged, move
+    ///   issue to "Done"
     pub async fn sync_status_from_pull_request(
         conn: &mut PgConnection,
         issue_id: Uuid,
@@ -640,27 +983,27 @@ impl IssueRepository {
     /// - If the issue has no assignees, adds the workspace creator as an assignee
     pub async fn sync_issue_from_workspace_created(
         pool: &PgPool,
+        tenant: TenantId,
         issue_id: Uuid,
         user_id: Uuid,
     ) -> Result<(), IssueError> {
         // Status sync: only on first workspace
         let workspace_count = WorkspaceRepository::count_by_issue_id(pool, issue_id).await?;
         if workspace_count == 1 {
-            let Some(issue) = Self::find_by_id(pool, issue_id).await? else {
+            let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+            let Some(issue) = Self::find_by_id(&mut *tx, issue_id).await? else {
                 return Ok(());
             };
 
             let Some(in_progress_status) =
-                ProjectStatusRepository::find_by_name(pool, issue.project_id, "In progress")
+                ProjectStatusRepository::find_by_name(&mut *tx, issue.project_id, "In progress")
                     .await?
             else {
                 return Ok(());
             };
 
-            let mut conn = pool.acquire().await?;
-
             Self::move_to_status_if_pending(
-                &mut conn,
+                &mut tx,
                 issue_id,
                 issue.status_id,
                 in_progress_status.id,
@@ -669,16 +1012,17 @@ impl IssueRepository {
 
             // If sub-issue, also move parent issue to "In progress"
             if let Some(parent_issue_id) = issue.parent_issue_id
-                && let Some(parent_issue) = Self::find_by_id(pool, parent_issue_id).await?
+                && let Some(parent_issue) = Self::find_by_id(&mut *tx, parent_issue_id).await?
             {
                 Self::move_to_status_if_pending(
-                    &mut conn,
+                    &mut tx,
                     parent_issue_id,
                     parent_issue.status_id,
                     in_progress_status.id,
                 )
                 .await?;
             }
+            tx.commit().await?;
         }
 
         // Assignee sync: add creator if no assignees exist
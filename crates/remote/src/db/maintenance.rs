@@ -0,0 +1,74 @@
+use sqlx::{PgPool, Postgres, pool::PoolConnection};
+
+const MAINTENANCE_ADVISORY_LOCK_ID: i64 = 3_447_201_002;
+
+/// Tables with enough churn that planner statistics go stale between
+/// autovacuum runs.
+pub const ANALYZE_TABLES: &[&str] = &[
+    "issues",
+    "issue_activity",
+    "issue_assignees",
+    "issue_tags",
+    "issue_comments",
+    "notifications",
+    "workspaces",
+];
+
+/// Append-only tables whose indexes bloat from range deletes and are rebuilt
+/// in place.
+pub const REINDEX_TABLES: &[&str] = &["issue_activity"];
+
+pub struct MaintenanceRepository;
+
+pub struct MaintenanceRunLock {
+    connection: PoolConnection<Postgres>,
+}
+
+impl MaintenanceRepository {
+    pub async fn try_acquire_run_lock(
+        pool: &PgPool,
+    ) -> Result<Option<MaintenanceRunLock>, sqlx::Error> {
+        let mut connection = pool.acquire().await?;
+        let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(MAINTENANCE_ADVISORY_LOCK_ID)
+            .fetch_one(&mut *connection)
+            .await?;
+
+        if acquired {
+            Ok(Some(MaintenanceRunLock { connection }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// `table` must come from [`ANALYZE_TABLES`]; it is interpolated as-is.
+    pub async fn analyze(pool: &PgPool, table: &'static str) -> Result<(), sqlx::Error> {
+        sqlx::query(&format!("ANALYZE {table}"))
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Rebuild a table's indexes without blocking writes. `table` must come
+    /// from [`REINDEX_TABLES`]; it is interpolated as-is.
+    pub async fn reindex_concurrently(
+        pool: &PgPool,
+        table: &'static str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(&format!("REINDEX TABLE CONCURRENTLY {table}"))
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
+
+impl MaintenanceRunLock {
+    pub async fn release(mut self) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT pg_advisory_unlock($1)")
+            .bind(MAINTENANCE_ADVISORY_LOCK_ID)
+            .execute(&mut *self.connection)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,73 +1,158 @@
+pub mod activity_cursors;
 pub mod attachments;
 pub mod auth;
 pub mod blobs;
 pub mod digest;
+pub mod download_nonces;
 pub mod electric_publications;
 pub mod export;
+pub mod export_jobs;
 pub mod github_app;
 pub mod hosts;
+pub mod idempotency_keys;
 pub mod identity_errors;
+pub(crate) mod index_check;
 pub mod invitations;
+pub mod issue_activity;
 pub mod issue_assignees;
 pub mod issue_comment_reactions;
 pub mod issue_comments;
 pub mod issue_followers;
+pub mod issue_mentions;
 pub mod issue_relationships;
 pub mod issue_tags;
 pub mod issues;
+pub mod maintenance;
 pub mod notifications;
 pub mod oauth;
 pub mod oauth_accounts;
+pub mod organization_access_policies;
+pub mod organization_automation_policies;
+pub mod organization_client_configs;
+pub mod organization_health;
 pub mod organization_members;
+pub mod organization_retention_policies;
 pub mod organizations;
 pub mod pending_uploads;
+pub mod pr_comment_mirrors;
+pub mod product_updates;
+pub mod project_commit_settings;
 pub mod project_notification_preferences;
+pub mod project_pr_settings;
+pub mod project_reports;
 pub mod project_statuses;
 pub mod projects;
 pub mod pull_request_issues;
 pub mod pull_requests;
 pub mod reviews;
 pub mod tags;
+pub mod tenant;
 pub mod types;
 pub mod users;
 pub mod workspaces;
 
+use std::net::IpAddr;
+
 use sqlx::{
-    Executor, PgPool, Postgres, Transaction,
+    Executor, PgConnection, PgPool, Postgres, Transaction,
     migrate::MigrateError,
     postgres::{PgConnectOptions, PgPoolOptions},
 };
+use tenant::TenantId;
 use uuid::Uuid;
 
 pub(crate) type Tx<'a> = Transaction<'a, Postgres>;
 
-/// Per-request context propagated to database transactions via a tokio task-local.
-/// The auth middleware initialises the scope; `begin_tx` reads it.
+/// Per-request context propagated to the database layer via a tokio task-local.
+/// The auth middleware initialises the scope; `begin_tx` and organization
+/// access-policy checks read it.
 #[derive(Clone)]
 pub struct TxContext {
     pub user_id: Uuid,
     pub request_id: String,
+    pub session_id: Uuid,
+    pub client_ip: Option<IpAddr>,
+    /// Client-supplied `X-Mutation-Id`, recorded on the activity the request
+    /// causes so clients can recognise echoes of their own writes.
+    pub mutation_id: Option<String>,
 }
 
 tokio::task_local! {
     pub static TX_CONTEXT: Option<TxContext>;
 }
 
-/// Begin a transaction and tag it with the current request's request ID.
+/// Begin a transaction and tag it with the current request's request ID,
+/// acting user and mutation ID (read by the `issue_activity` triggers).
 /// If no context is set (e.g. background jobs), the transaction is untagged.
 pub async fn begin_tx(pool: &PgPool) -> Result<Tx<'_>, sqlx::Error> {
-    let mut tx = pool.begin().await?;
+    tag_tx(pool.begin().await?).await
+}
+
+async fn tag_tx(mut tx: Tx<'_>) -> Result<Tx<'_>, sqlx::Error> {
     let ctx = TX_CONTEXT.try_with(|c| c.clone()).ok().flatten();
     if let Some(ctx) = ctx {
         let name = format!("vk r:{}", ctx.request_id.replace('-', ""));
-        sqlx::query("SELECT set_config('application_name', $1, true)")
-            .bind(&name)
-            .execute(&mut *tx)
-            .await?;
+        sqlx::query(
+            "SELECT set_config('application_name', $1, true), set_config('vk.actor_user_id', $2, true), set_config('vk.mutation_id', $3, true)",
+        )
+        .bind(&name)
+        .bind(ctx.user_id.to_string())
+        .bind(ctx.mutation_id.unwrap_or_default())
+        .execute(&mut *tx)
+        .await?;
     }
     Ok(tx)
 }
 
+/// Like [`begin_tx`], but also scopes the transaction to `tenant` through
+/// the `app.current_org` setting. Row-level security then hides projects,
+/// issues and issue activity of other organizations, so a query that misses
+/// an organization filter can't leak or modify them.
+pub async fn begin_tenant_tx(pool: &PgPool, tenant: TenantId) -> Result<Tx<'_>, sqlx::Error> {
+    let mut tx = begin_tx(pool).await?;
+    scope_to_tenant(&mut tx, tenant).await?;
+    Ok(tx)
+}
+
+/// Like [`begin_tenant_tx`], but read-only and REPEATABLE READ, so every
+/// query in it sees the same snapshot.
+pub async fn begin_tenant_snapshot_tx(
+    pool: &PgPool,
+    tenant: TenantId,
+) -> Result<Tx<'_>, sqlx::Error> {
+    let tx = pool
+        .begin_with("BEGIN ISOLATION LEVEL REPEATABLE READ READ ONLY")
+        .await?;
+    let mut tx = tag_tx(tx).await?;
+    scope_to_tenant(&mut tx, tenant).await?;
+    Ok(tx)
+}
+
+/// Scope an already open transaction to `tenant`, for transactions that
+/// only learn their organization part way through (e.g. creating it).
+pub(crate) async fn scope_to_tenant(
+    conn: &mut PgConnection,
+    tenant: TenantId,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT set_config('app.current_org', $1, true)")
+        .bind(tenant.id().to_string())
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+/// Like [`begin_tx`], but the transaction runs as `vk_rls_bypass` and sees
+/// every organization's rows. Only for code that has to work across
+/// organizations: background jobs, and the access guards that look up which
+/// organization a row belongs to before there is a tenant to scope to.
+pub(crate) async fn begin_trusted_tx(pool: &PgPool) -> Result<Tx<'_>, sqlx::Error> {
+    let mut tx = begin_tx(pool).await?;
+    sqlx::query("SET LOCAL ROLE vk_rls_bypass")
+        .execute(&mut *tx)
+        .await?;
+    Ok(tx)
+}
+
 /// Get the current transaction ID from Postgres.
 /// Must be called within an active transaction.
 /// Uses text conversion to avoid xid8->bigint cast issues in some PG versions.
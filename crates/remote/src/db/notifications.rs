@@ -4,6 +4,8 @@ use sqlx::{Executor, FromRow, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::tenant::TenantId;
+
 #[derive(Debug, Error)]
 pub enum NotificationError {
     #[error(transparent)]
@@ -78,7 +80,7 @@ impl NotificationRepository {
 
     pub async fn create<'e, E>(
         executor: E,
-        organization_id: Uuid,
+        tenant: TenantId,
         user_id: Uuid,
         notification_type: NotificationType,
         payload: NotificationPayload,
@@ -109,7 +111,7 @@ impl NotificationRepository {
                 created_at
             "#,
             id,
-            organization_id,
+            tenant.id(),
             user_id,
             notification_type as NotificationType,
             payload as sqlx::types::Json<NotificationPayload>,
@@ -223,7 +225,7 @@ impl NotificationRepository {
 
     pub async fn upsert_recent<'e, E>(
         executor: E,
-        organization_id: Uuid,
+        tenant: TenantId,
         user_id: Uuid,
         notification_type: NotificationType,
         payload: NotificationPayload,
@@ -311,7 +313,7 @@ impl NotificationRepository {
             FROM inserted
             "#,
             id,
-            organization_id,
+            tenant.id(),
             user_id,
             notification_type as NotificationType,
             payload as sqlx::types::Json<NotificationPayload>,
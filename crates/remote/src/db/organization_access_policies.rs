@@ -0,0 +1,297 @@
+use std::net::IpAddr;
+
+use api_types::OrganizationAccessPolicy;
+use ipnetwork::IpNetwork;
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{TX_CONTEXT, identity_errors::IdentityError, tenant::TenantId};
+use crate::audit::{self, AuditAction, AuditEvent};
+
+#[derive(Debug, Error)]
+pub enum OrganizationAccessPolicyError {
+    #[error("invalid CIDR range: {0}")]
+    InvalidCidr(String),
+    #[error("invalid device session id: {0}")]
+    InvalidDeviceId(String),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// Why a request was rejected by an organization's access policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessPolicyViolation {
+    IpNotAllowed,
+    UnknownDevice,
+}
+
+impl AccessPolicyViolation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::IpNotAllowed => "client IP not in organization allowlist",
+            Self::UnknownDevice => "device not trusted by organization",
+        }
+    }
+}
+
+pub struct OrganizationAccessPolicyRepository;
+
+impl OrganizationAccessPolicyRepository {
+    pub async fn get(
+        pool: &PgPool,
+        tenant: TenantId,
+    ) -> Result<OrganizationAccessPolicy, OrganizationAccessPolicyError> {
+        let ip_allowlist = sqlx::query_scalar!(
+            r#"
+            SELECT cidr AS "cidr!: IpNetwork"
+            FROM organization_ip_allowlist
+            WHERE organization_id = $1
+            ORDER BY created_at, cidr
+            "#,
+            tenant.id()
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|cidr| cidr.to_string())
+        .collect();
+
+        let device_ids = sqlx::query_scalar!(
+            r#"
+            SELECT session_id AS "session_id!"
+            FROM organization_trusted_devices
+            WHERE organization_id = $1
+            ORDER BY created_at, session_id
+            "#,
+            tenant.id()
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(OrganizationAccessPolicy {
+            ip_allowlist,
+            device_ids,
+        })
+    }
+
+    /// Replace the organization's allowlists wholesale.
+    pub async fn replace(
+        pool: &PgPool,
+        tenant: TenantId,
+        ip_allowlist: &[IpNetwork],
+        device_ids: &[String],
+    ) -> Result<OrganizationAccessPolicy, OrganizationAccessPolicyError> {
+        let mut tx = super::begin_tx(pool).await?;
+
+        sqlx::query!(
+            "DELETE FROM organization_ip_allowlist WHERE organization_id = $1",
+            tenant.id()
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM organization_trusted_devices WHERE organization_id = $1",
+            tenant.id()
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO organization_ip_allowlist (organization_id, cidr)
+            SELECT $1, cidr FROM UNNEST($2::cidr[]) AS cidr
+            ON CONFLICT DO NOTHING
+            "#,
+            tenant.id(),
+            ip_allowlist as &[IpNetwork]
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO organization_trusted_devices (organization_id, session_id)
+            SELECT $1, session_id FROM UNNEST($2::text[]) AS session_id
+            ON CONFLICT DO NOTHING
+            "#,
+            tenant.id(),
+            device_ids
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Self::get(pool, tenant).await
+    }
+
+    /// Check a client against the organization's policy. Dimensions without
+    /// any entries are unrestricted. Devices are identified by the auth
+    /// session signed in on them.
+    pub async fn evaluate(
+        pool: &PgPool,
+        organization_id: Uuid,
+        client_ip: Option<IpAddr>,
+        session_id: Uuid,
+    ) -> Result<Option<AccessPolicyViolation>, OrganizationAccessPolicyError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                (
+                    NOT EXISTS (
+                        SELECT 1 FROM organization_ip_allowlist WHERE organization_id = $1
+                    )
+                    OR EXISTS (
+                        SELECT 1 FROM organization_ip_allowlist
+                        WHERE organization_id = $1 AND $2::inet <<= cidr
+                    )
+                ) AS "ip_allowed!",
+                (
+                    NOT EXISTS (
+                        SELECT 1 FROM organization_trusted_devices WHERE organization_id = $1
+                    )
+                    OR EXISTS (
+                        SELECT 1 FROM organization_trusted_devices
+                        WHERE organization_id = $1 AND session_id = $3
+                    )
+                ) AS "device_allowed!"
+            "#,
+            organization_id,
+            client_ip.map(IpNetwork::from) as Option<IpNetwork>,
+            session_id.to_string()
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(if !row.ip_allowed {
+            Some(AccessPolicyViolation::IpNotAllowed)
+        } else if !row.device_allowed {
+            Some(AccessPolicyViolation::UnknownDevice)
+        } else {
+            None
+        })
+    }
+}
+
+/// Parse and validate a policy update into the forms stored in the database.
+pub fn parse_policy(
+    ip_allowlist: &[String],
+    device_ids: &[String],
+) -> Result<(Vec<IpNetwork>, Vec<String>), OrganizationAccessPolicyError> {
+    let cidrs = ip_allowlist
+        .iter()
+        .map(|raw| {
+            let network = raw
+                .trim()
+                .parse::<IpNetwork>()
+                .map_err(|_| OrganizationAccessPolicyError::InvalidCidr(raw.clone()))?;
+            // `cidr` columns reject host bits, so store the network address.
+            IpNetwork::new(network.network(), network.prefix())
+                .map_err(|_| OrganizationAccessPolicyError::InvalidCidr(raw.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let device_ids = device_ids
+        .iter()
+        .map(|raw| {
+            raw.trim()
+                .parse::<Uuid>()
+                .map(|session_id| session_id.to_string())
+                .map_err(|_| OrganizationAccessPolicyError::InvalidDeviceId(raw.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((cidrs, device_ids))
+}
+
+/// Enforce the organization's access policy for the request currently in
+/// scope (see [`TX_CONTEXT`]). Rejections are audited. Calls outside a
+/// request (background jobs) are not restricted.
+pub(crate) async fn enforce_for_current_request(
+    pool: &PgPool,
+    organization_id: Uuid,
+) -> Result<(), IdentityError> {
+    let Some(ctx) = TX_CONTEXT.try_with(|c| c.clone()).ok().flatten() else {
+        return Ok(());
+    };
+
+    let violation = OrganizationAccessPolicyRepository::evaluate(
+        pool,
+        organization_id,
+        ctx.client_ip,
+        ctx.session_id,
+    )
+    .await
+    .map_err(|error| match error {
+        OrganizationAccessPolicyError::Database(error) => IdentityError::Database(error),
+        other => {
+            tracing::error!(?other, "unexpected access policy error");
+            IdentityError::AccessPolicyDenied
+        }
+    })?;
+
+    let Some(violation) = violation else {
+        return Ok(());
+    };
+
+    tracing::warn!(
+        %organization_id,
+        user_id = %ctx.user_id,
+        client_ip = ?ctx.client_ip,
+        session_id = %ctx.session_id,
+        reason = violation.as_str(),
+        "request rejected by organization access policy"
+    );
+    audit::emit(
+        AuditEvent::system(AuditAction::OrgAccessPolicyDenied)
+            .user(ctx.user_id, Some(ctx.session_id))
+            .organization(organization_id)
+            .resource("organization", Some(organization_id))
+            .description(format!(
+                "{} (ip: {}, session: {})",
+                violation.as_str(),
+                ctx.client_ip
+                    .map(|ip| ip.to_string())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                ctx.session_id,
+            )),
+    );
+
+    Err(IdentityError::AccessPolicyDenied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrganizationAccessPolicyError, parse_policy};
+
+    #[test]
+    fn parse_policy_accepts_cidrs_and_bare_addresses() {
+        let (cidrs, devices) = parse_policy(
+            &["10.0.0.0/8".to_string(), " 2001:db8::1 ".to_string()],
+            &[" 6F9619FF-8B86-D011-B42D-00CF4FC964FF ".to_string()],
+        )
+        .unwrap();
+
+        assert_eq!(cidrs.len(), 2);
+        assert_eq!(cidrs[1].prefix(), 128);
+
+        let (cidrs, _) = parse_policy(&["192.168.1.17/24".to_string()], &[]).unwrap();
+        assert_eq!(cidrs[0].to_string(), "192.168.1.0/24");
+        assert_eq!(
+            devices,
+            vec!["6f9619ff-8b86-d011-b42d-00cf4fc964ff".to_string()]
+        );
+    }
+
+    #[test]
+    fn parse_policy_rejects_invalid_entries() {
+        assert!(matches!(
+            parse_policy(&["10.0.0.0/33".to_string()], &[]),
+            Err(OrganizationAccessPolicyError::InvalidCidr(_))
+        ));
+        assert!(matches!(
+            parse_policy(&[], &["laptop-1".to_string()]),
+            Err(OrganizationAccessPolicyError::InvalidDeviceId(_))
+        ));
+    }
+}
@@ -0,0 +1,55 @@
+use api_types::OrganizationAutomationPolicy;
+use sqlx::{Executor, Postgres};
+
+use super::tenant::TenantId;
+
+pub struct OrganizationAutomationPolicyRepository;
+
+impl OrganizationAutomationPolicyRepository {
+    pub async fn get<'e, E>(
+        executor: E,
+        tenant: TenantId,
+    ) -> Result<OrganizationAutomationPolicy, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let policy = sqlx::query_as!(
+            OrganizationAutomationPolicy,
+            r#"
+            SELECT reopen_on_failure
+            FROM organization_automation_policies
+            WHERE organization_id = $1
+            "#,
+            tenant.id()
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(policy.unwrap_or_default())
+    }
+
+    pub async fn upsert<'e, E>(
+        executor: E,
+        tenant: TenantId,
+        reopen_on_failure: bool,
+    ) -> Result<OrganizationAutomationPolicy, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as!(
+            OrganizationAutomationPolicy,
+            r#"
+            INSERT INTO organization_automation_policies (organization_id, reopen_on_failure)
+            VALUES ($1, $2)
+            ON CONFLICT (organization_id) DO UPDATE
+            SET reopen_on_failure = EXCLUDED.reopen_on_failure,
+                updated_at = NOW()
+            RETURNING reopen_on_failure
+            "#,
+            tenant.id(),
+            reopen_on_failure
+        )
+        .fetch_one(executor)
+        .await
+    }
+}
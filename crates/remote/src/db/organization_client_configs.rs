@@ -0,0 +1,55 @@
+use api_types::OrganizationClientConfig;
+use sqlx::{Executor, Postgres, types::Json};
+
+use super::tenant::TenantId;
+
+pub struct OrganizationClientConfigRepository;
+
+impl OrganizationClientConfigRepository {
+    pub async fn get<'e, E>(
+        executor: E,
+        tenant: TenantId,
+    ) -> Result<OrganizationClientConfig, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let config = sqlx::query_scalar!(
+            r#"
+            SELECT config AS "config!: Json<OrganizationClientConfig>"
+            FROM organization_client_configs
+            WHERE organization_id = $1
+            "#,
+            tenant.id()
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(config.map(|Json(config)| config).unwrap_or_default())
+    }
+
+    pub async fn upsert<'e, E>(
+        executor: E,
+        tenant: TenantId,
+        config: &OrganizationClientConfig,
+    ) -> Result<OrganizationClientConfig, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let Json(config) = sqlx::query_scalar!(
+            r#"
+            INSERT INTO organization_client_configs (organization_id, config)
+            VALUES ($1, $2)
+            ON CONFLICT (organization_id) DO UPDATE
+            SET config = EXCLUDED.config,
+                updated_at = NOW()
+            RETURNING config AS "config!: Json<OrganizationClientConfig>"
+            "#,
+            tenant.id(),
+            Json(config) as Json<&OrganizationClientConfig>
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(config)
+    }
+}
@@ -0,0 +1,262 @@
+//! Operator-facing health summary of one organization, for triaging
+//! reports like "sync is slow for us".
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::tenant::TenantId;
+
+const LARGEST_ISSUES_LIMIT: i64 = 10;
+const RECENT_ERRORS_LIMIT: i64 = 10;
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationHealth {
+    pub organization_id: Uuid,
+    pub generated_at: DateTime<Utc>,
+    pub event_rates: EventRates,
+    pub active_sessions: i64,
+    pub backlog: Backlog,
+    pub largest_issues: Vec<LargestIssue>,
+    pub recent_errors: Vec<RecentError>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventRates {
+    pub issue_events_last_hour: i64,
+    pub issue_events_last_day: i64,
+    pub comments_last_hour: i64,
+    pub comments_last_day: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Backlog {
+    /// Export jobs not yet finished.
+    pub export_jobs: i64,
+    /// Uploads started but not yet confirmed.
+    pub pending_uploads: i64,
+    pub unseen_notifications: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LargestIssue {
+    pub id: Uuid,
+    pub simple_id: String,
+    pub title: String,
+    pub comment_count: i64,
+    /// Description plus comment text.
+    pub size_bytes: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecentError {
+    pub source: &'static str,
+    pub id: Uuid,
+    pub message: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+pub struct OrganizationHealthRepository;
+
+impl OrganizationHealthRepository {
+    pub async fn summarize(
+        pool: &PgPool,
+        tenant: TenantId,
+    ) -> Result<OrganizationHealth, sqlx::Error> {
+        let (event_rates, active_sessions, backlog, largest_issues, recent_errors) = tokio::try_join!(
+            Self::event_rates(pool, tenant),
+            Self::active_sessions(pool, tenant),
+            Self::backlog(pool, tenant),
+            Self::largest_issues(pool, tenant),
+            Self::recent_errors(pool, tenant),
+        )?;
+
+        Ok(OrganizationHealth {
+            organization_id: tenant.id(),
+            generated_at: Utc::now(),
+            event_rates,
+            active_sessions,
+            backlog,
+            largest_issues,
+            recent_errors,
+        })
+    }
+
+    async fn event_rates(pool: &PgPool, tenant: TenantId) -> Result<EventRates, sqlx::Error> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let record = sqlx::query!(
+            r#"
+            SELECT
+                (
+                    SELECT COUNT(*) FILTER (WHERE a.created_at > NOW() - INTERVAL '1 hour')
+                    FROM issue_activity a
+                    INNER JOIN projects p ON p.id = a.project_id
+                    WHERE p.organization_id = $1
+                      AND a.created_at > NOW() - INTERVAL '1 day'
+                ) AS "issue_events_last_hour!",
+                (
+                    SELECT COUNT(*)
+                    FROM issue_activity a
+                    INNER JOIN projects p ON p.id = a.project_id
+                    WHERE p.organization_id = $1
+                      AND a.created_at > NOW() - INTERVAL '1 day'
+                ) AS "issue_events_last_day!",
+                (
+                    SELECT COUNT(*) FILTER (WHERE c.created_at > NOW() - INTERVAL '1 hour')
+                    FROM issue_comments c
+                    INNER JOIN issues i ON i.id = c.issue_id
+                    INNER JOIN projects p ON p.id = i.project_id
+                    WHERE p.organization_id = $1
+                      AND c.created_at > NOW() - INTERVAL '1 day'
+                ) AS "comments_last_hour!",
+                (
+                    SELECT COUNT(*)
+                    FROM issue_comments c
+                    INNER JOIN issues i ON i.id = c.issue_id
+                    INNER JOIN projects p ON p.id = i.project_id
+                    WHERE p.organization_id = $1
+                      AND c.created_at > NOW() - INTERVAL '1 day'
+                ) AS "comments_last_day!"
+            "#,
+            tenant.id()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(EventRates {
+            issue_events_last_hour: record.issue_events_last_hour,
+            issue_events_last_day: record.issue_events_last_day,
+            comments_last_hour: record.comments_last_hour,
+            comments_last_day: record.comments_last_day,
+        })
+    }
+
+    /// Unrevoked sessions of the organization's members used in the last 15
+    /// minutes.
+    async fn active_sessions(pool: &PgPool, tenant: TenantId) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM auth_sessions s
+            INNER JOIN organization_member_metadata omm ON omm.user_id = s.user_id
+            WHERE omm.organization_id = $1
+              AND s.revoked_at IS NULL
+              AND s.last_used_at > NOW() - INTERVAL '15 minutes'
+            "#,
+            tenant.id()
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    async fn backlog(pool: &PgPool, tenant: TenantId) -> Result<Backlog, sqlx::Error> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let record = sqlx::query!(
+            r#"
+            SELECT
+                (
+                    SELECT COUNT(*)
+                    FROM export_jobs
+                    WHERE organization_id = $1
+                      AND status IN ('pending', 'running')
+                ) AS "export_jobs!",
+                (
+                    SELECT COUNT(*)
+                    FROM pending_uploads u
+                    INNER JOIN projects p ON p.id = u.project_id
+                    WHERE p.organization_id = $1
+                      AND u.expires_at > NOW()
+                ) AS "pending_uploads!",
+                (
+                    SELECT COUNT(*)
+                    FROM notifications
+                    WHERE organization_id = $1
+                      AND NOT seen
+                      AND dismissed_at IS NULL
+                ) AS "unseen_notifications!"
+            "#,
+            tenant.id()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(Backlog {
+            export_jobs: record.export_jobs,
+            pending_uploads: record.pending_uploads,
+            unseen_notifications: record.unseen_notifications,
+        })
+    }
+
+    async fn largest_issues(
+        pool: &PgPool,
+        tenant: TenantId,
+    ) -> Result<Vec<LargestIssue>, sqlx::Error> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let records = sqlx::query_as!(
+            LargestIssue,
+            r#"
+            SELECT
+                i.id            AS "id!: Uuid",
+                i.simple_id     AS "simple_id!",
+                i.title         AS "title!",
+                COUNT(c.id)     AS "comment_count!",
+                (
+                    COALESCE(OCTET_LENGTH(i.description), 0)
+                    + COALESCE(SUM(OCTET_LENGTH(c.message)), 0)
+                )::BIGINT       AS "size_bytes!"
+            FROM issues i
+            INNER JOIN projects p ON p.id = i.project_id
+            LEFT JOIN issue_comments c ON c.issue_id = i.id
+            WHERE p.organization_id = $1
+            GROUP BY i.id
+            ORDER BY 5 DESC
+            LIMIT $2
+            "#,
+            tenant.id(),
+            LARGEST_ISSUES_LIMIT
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(records)
+    }
+
+    /// Failed export jobs are the only errors persisted per organization;
+    /// request errors live in the logs.
+    async fn recent_errors(
+        pool: &PgPool,
+        tenant: TenantId,
+    ) -> Result<Vec<RecentError>, sqlx::Error> {
+        let records = sqlx::query!(
+            r#"
+            SELECT
+                id                                  AS "id!: Uuid",
+                COALESCE(error, '')                 AS "message!",
+                COALESCE(completed_at, created_at)  AS "occurred_at!: DateTime<Utc>"
+            FROM export_jobs
+            WHERE organization_id = $1
+              AND status = 'failed'
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+            tenant.id(),
+            RECENT_ERRORS_LIMIT
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|record| RecentError {
+                source: "export_job",
+                id: record.id,
+                message: record.message,
+                occurred_at: record.occurred_at,
+            })
+            .collect())
+    }
+}
@@ -2,7 +2,10 @@ use api_types::MemberRole;
 use sqlx::{Executor, PgPool, Postgres};
 use uuid::Uuid;
 
-use super::identity_errors::IdentityError;
+use super::{
+    identity_errors::IdentityError, organization_access_policies::enforce_for_current_request,
+    tenant::TenantId,
+};
 
 pub(super) async fn add_member<'a, E>(
     executor: E,
@@ -50,6 +53,23 @@ pub(crate) async fn check_user_role(
     Ok(result.map(|r| r.role))
 }
 
+/// Ids of every organization `user_id` belongs to.
+pub async fn list_organization_ids_for_user(
+    pool: &PgPool,
+    user_id: Uuid,
+) -> Result<Vec<Uuid>, sqlx::Error> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT organization_id AS "organization_id!: Uuid"
+        FROM organization_member_metadata
+        WHERE user_id = $1
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await
+}
+
 pub async fn is_member<'a, E>(
     executor: E,
     organization_id: Uuid,
@@ -82,11 +102,11 @@ pub(crate) async fn assert_membership(
 ) -> Result<(), IdentityError> {
     let exists = is_member(pool, organization_id, user_id).await?;
 
-    if exists {
-        Ok(())
-    } else {
-        Err(IdentityError::NotFound)
+    if !exists {
+        return Err(IdentityError::NotFound);
     }
+
+    enforce_for_current_request(pool, organization_id).await
 }
 
 pub(crate) async fn assert_issue_access(
@@ -94,6 +114,9 @@ pub(crate) async fn assert_issue_access(
     issue_id: Uuid,
     user_id: Uuid,
 ) -> Result<(), IdentityError> {
+    // Finding the organization can't be tenant scoped; membership of it is
+    // what gets checked.
+    let mut tx = super::begin_trusted_tx(pool).await?;
     let org_id = sqlx::query_scalar!(
         r#"
         SELECT p.organization_id
@@ -103,9 +126,10 @@ pub(crate) async fn assert_issue_access(
         "#,
         issue_id
     )
-    .fetch_optional(pool)
-    .await?
-    .ok_or(IdentityError::NotFound)?;
+    .fetch_optional(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    let org_id = org_id.ok_or(IdentityError::NotFound)?;
 
     assert_membership(pool, org_id, user_id).await
 }
@@ -115,20 +139,24 @@ pub(crate) async fn assert_project_access(
     project_id: Uuid,
     user_id: Uuid,
 ) -> Result<(), IdentityError> {
+    // Finding the organization can't be tenant scoped; membership of it is
+    // what gets checked.
+    let mut tx = super::begin_trusted_tx(pool).await?;
     let org_id = sqlx::query_scalar!(
         r#"SELECT organization_id FROM projects WHERE id = $1"#,
         project_id
     )
-    .fetch_optional(pool)
-    .await?
-    .ok_or(IdentityError::NotFound)?;
+    .fetch_optional(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    let org_id = org_id.ok_or(IdentityError::NotFound)?;
 
     assert_membership(pool, org_id, user_id).await
 }
 
 pub(crate) async fn list_by_organization(
     pool: &PgPool,
-    organization_id: Uuid,
+    tenant: TenantId,
 ) -> Result<Vec<api_types::OrganizationMember>, sqlx::Error> {
     sqlx::query_as!(
         api_types::OrganizationMember,
@@ -142,15 +170,53 @@ pub(crate) async fn list_by_organization(
         FROM organization_member_metadata
         WHERE organization_id = $1
         "#,
-        organization_id
+        tenant.id()
     )
     .fetch_all(pool)
     .await
 }
 
+/// Members of `tenant` with their profile, oldest first.
+pub(crate) async fn list_with_profiles<'e, E>(
+    executor: E,
+    tenant: TenantId,
+) -> Result<Vec<api_types::OrganizationMemberWithProfile>, sqlx::Error>
+where
+    E: Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as!(
+        api_types::OrganizationMemberWithProfile,
+        r#"
+        SELECT
+            omm.user_id AS "user_id!: Uuid",
+            omm.role AS "role!: MemberRole",
+            omm.joined_at AS "joined_at!",
+            u.first_name AS "first_name?",
+            u.last_name AS "last_name?",
+            u.username AS "username?",
+            u.email AS "email?",
+            oa.avatar_url AS "avatar_url?"
+        FROM organization_member_metadata omm
+        INNER JOIN users u ON omm.user_id = u.id
+        LEFT JOIN LATERAL (
+            SELECT avatar_url
+            FROM oauth_accounts
+            WHERE user_id = omm.user_id
+            ORDER BY created_at ASC
+            LIMIT 1
+        ) oa ON true
+        WHERE omm.organization_id = $1
+        ORDER BY omm.joined_at ASC
+        "#,
+        tenant.id()
+    )
+    .fetch_all(executor)
+    .await
+}
+
 pub(crate) async fn list_users_by_organization(
     pool: &PgPool,
-    organization_id: Uuid,
+    tenant: TenantId,
 ) -> Result<Vec<api_types::User>, sqlx::Error> {
     sqlx::query_as!(
         api_types::User,
@@ -166,7 +232,7 @@ pub(crate) async fn list_users_by_organization(
         FROM users
         WHERE id IN (SELECT user_id FROM organization_member_metadata WHERE organization_id = $1)
         "#,
-        organization_id
+        tenant.id()
     )
     .fetch_all(pool)
     .await
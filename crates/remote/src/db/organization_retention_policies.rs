@@ -0,0 +1,86 @@
+use api_types::OrganizationRetentionPolicy;
+use sqlx::{Executor, Postgres};
+
+use super::tenant::TenantId;
+
+pub struct OrganizationRetentionPolicyRepository;
+
+impl OrganizationRetentionPolicyRepository {
+    pub async fn get<'e, E>(
+        executor: E,
+        tenant: TenantId,
+    ) -> Result<OrganizationRetentionPolicy, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let policy = sqlx::query_as!(
+            OrganizationRetentionPolicy,
+            r#"
+            SELECT workspace_summary_days
+            FROM organization_retention_policies
+            WHERE organization_id = $1
+            "#,
+            tenant.id()
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(policy.unwrap_or_default())
+    }
+
+    pub async fn upsert<'e, E>(
+        executor: E,
+        tenant: TenantId,
+        workspace_summary_days: Option<i32>,
+    ) -> Result<OrganizationRetentionPolicy, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as!(
+            OrganizationRetentionPolicy,
+            r#"
+            INSERT INTO organization_retention_policies (organization_id, workspace_summary_days)
+            VALUES ($1, $2)
+            ON CONFLICT (organization_id) DO UPDATE
+            SET workspace_summary_days = EXCLUDED.workspace_summary_days,
+                updated_at = NOW()
+            RETURNING workspace_summary_days
+            "#,
+            tenant.id(),
+            workspace_summary_days
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Clear diff summaries of workspaces whose organization's retention
+    /// window has passed. Returns the number of workspaces cleared.
+    pub async fn purge_expired_workspace_summaries<'e, E>(executor: E) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            r#"
+            UPDATE workspaces w
+            SET files_changed = NULL,
+                lines_added = NULL,
+                lines_removed = NULL
+            FROM projects p
+            JOIN organization_retention_policies orp
+              ON orp.organization_id = p.organization_id
+            WHERE w.project_id = p.id
+              AND orp.workspace_summary_days IS NOT NULL
+              AND w.updated_at < NOW() - make_interval(days => orp.workspace_summary_days)
+              AND (
+                  w.files_changed IS NOT NULL
+                  OR w.lines_added IS NOT NULL
+                  OR w.lines_removed IS NOT NULL
+              )
+            "#
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
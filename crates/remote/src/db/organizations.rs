@@ -9,6 +9,7 @@ use super::{
         check_user_role as get_user_role,
     },
     projects::ProjectRepository,
+    tenant::{TenantId, TenantSource},
 };
 
 pub struct OrganizationRepository<'a> {
@@ -77,13 +78,16 @@ impl<'a> OrganizationRepository<'a> {
                 let org = create_personal_org_tx(&mut *tx, &name, &slug).await?;
 
                 // Create initial project with default tags and statuses
-                ProjectRepository::create_initial_project_tx(&mut tx, org.id)
-                    .await
-                    .map_err(|e| {
-                        IdentityError::Database(sqlx::Error::Protocol(format!(
-                            "Failed to create initial project: {e}"
-                        )))
-                    })?;
+                ProjectRepository::create_initial_project_tx(
+                    &mut tx,
+                    TenantId::trusted(org.id, TenantSource::NewOrganization),
+                )
+                .await
+                .map_err(|e| {
+                    IdentityError::Database(sqlx::Error::Protocol(format!(
+                        "Failed to create initial project: {e}"
+                    )))
+                })?;
 
                 tx.commit().await?;
                 org
@@ -151,13 +155,16 @@ impl<'a> OrganizationRepository<'a> {
         })?;
 
         // Create initial project with default tags and statuses
-        ProjectRepository::create_initial_project_tx(&mut tx, org.id)
-            .await
-            .map_err(|e| {
-                IdentityError::Database(sqlx::Error::Protocol(format!(
-                    "Failed to create initial project: {e}"
-                )))
-            })?;
+        ProjectRepository::create_initial_project_tx(
+            &mut tx,
+            TenantId::trusted(org.id, TenantSource::NewOrganization),
+        )
+        .await
+        .map_err(|e| {
+            IdentityError::Database(sqlx::Error::Protocol(format!(
+                "Failed to create initial project: {e}"
+            )))
+        })?;
 
         add_member(&mut *tx, org.id, creator_user_id, MemberRole::Admin).await?;
 
@@ -0,0 +1,55 @@
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+pub struct PrCommentMirrorRepository;
+
+impl PrCommentMirrorRepository {
+    /// Claim copying GitHub comment `github_comment_id` from `source` into
+    /// the issue's thread. Returns `false` if it was copied before.
+    pub async fn claim(
+        conn: &mut PgConnection,
+        source: &str,
+        github_comment_id: i64,
+        issue_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let claimed = sqlx::query!(
+            r#"
+            INSERT INTO pr_comment_mirrors (source, github_comment_id, issue_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+            source,
+            github_comment_id,
+            issue_id
+        )
+        .execute(&mut *conn)
+        .await?
+        .rows_affected()
+            > 0;
+        Ok(claimed)
+    }
+
+    /// Record the issue comment a claimed GitHub comment was copied to.
+    pub async fn set_issue_comment(
+        conn: &mut PgConnection,
+        source: &str,
+        github_comment_id: i64,
+        issue_id: Uuid,
+        issue_comment_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+            UPDATE pr_comment_mirrors
+            SET issue_comment_id = $4
+            WHERE source = $1 AND github_comment_id = $2 AND issue_id = $3
+            "#,
+            source,
+            github_comment_id,
+            issue_id,
+            issue_comment_id
+        )
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use api_types::ProductUpdate;
+use sqlx::{Executor, Postgres};
+use uuid::Uuid;
+
+pub struct ProductUpdateRepository;
+
+impl ProductUpdateRepository {
+    /// Published updates, newest first, with the user's read state.
+    pub async fn list_for_user<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<ProductUpdate>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as!(
+            ProductUpdate,
+            r#"
+            SELECT
+                pu.id,
+                pu.title,
+                pu.body,
+                pu.url,
+                pu.published_at,
+                (pur.user_id IS NOT NULL) AS "read!"
+            FROM product_updates pu
+            LEFT JOIN product_update_reads pur
+                ON pur.product_update_id = pu.id AND pur.user_id = $1
+            WHERE pu.published_at <= NOW()
+            ORDER BY pu.published_at DESC
+            LIMIT $2
+            "#,
+            user_id,
+            limit
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    pub async fn unread_count<'e, E>(executor: E, user_id: Uuid) -> Result<i64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM product_updates pu
+            WHERE pu.published_at <= NOW()
+              AND NOT EXISTS (
+                  SELECT 1 FROM product_update_reads pur
+                  WHERE pur.product_update_id = pu.id AND pur.user_id = $1
+              )
+            "#,
+            user_id
+        )
+        .fetch_one(executor)
+        .await
+    }
+
+    /// Idempotent. Returns false if the update does not exist or is not yet
+    /// published.
+    pub async fn mark_read<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        product_update_id: Uuid,
+    ) -> Result<bool, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO product_update_reads (user_id, product_update_id)
+            SELECT $1, id
+            FROM product_updates
+            WHERE id = $2 AND published_at <= NOW()
+            ON CONFLICT (user_id, product_update_id)
+                DO UPDATE SET read_at = product_update_reads.read_at
+            "#,
+            user_id,
+            product_update_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn mark_all_read<'e, E>(executor: E, user_id: Uuid) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO product_update_reads (user_id, product_update_id)
+            SELECT $1, id
+            FROM product_updates
+            WHERE published_at <= NOW()
+            ON CONFLICT (user_id, product_update_id) DO NOTHING
+            "#,
+            user_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,58 @@
+use api_types::ProjectCommitSettings;
+use sqlx::{Executor, Postgres};
+use uuid::Uuid;
+
+pub struct ProjectCommitSettingsRepository;
+
+impl ProjectCommitSettingsRepository {
+    /// Stored settings, or ones leaving messages as written.
+    pub async fn get<'e, E>(
+        executor: E,
+        project_id: Uuid,
+    ) -> Result<ProjectCommitSettings, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let settings = sqlx::query_as!(
+            ProjectCommitSettings,
+            r#"
+            SELECT conventional_commits, commit_template
+            FROM project_commit_settings
+            WHERE project_id = $1
+            "#,
+            project_id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(settings.unwrap_or_default())
+    }
+
+    pub async fn upsert<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        conventional_commits: bool,
+        commit_template: Option<&str>,
+    ) -> Result<ProjectCommitSettings, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as!(
+            ProjectCommitSettings,
+            r#"
+            INSERT INTO project_commit_settings (project_id, conventional_commits, commit_template)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (project_id) DO UPDATE
+            SET conventional_commits = EXCLUDED.conventional_commits,
+                commit_template = EXCLUDED.commit_template,
+                updated_at = NOW()
+            RETURNING conventional_commits, commit_template
+            "#,
+            project_id,
+            conventional_commits,
+            commit_template
+        )
+        .fetch_one(executor)
+        .await
+    }
+}
@@ -0,0 +1,58 @@
+use api_types::ProjectPrSettings;
+use sqlx::{Executor, Postgres};
+use uuid::Uuid;
+
+pub struct ProjectPrSettingsRepository;
+
+impl ProjectPrSettingsRepository {
+    /// Stored settings, or the defaults.
+    pub async fn get<'e, E>(executor: E, project_id: Uuid) -> Result<ProjectPrSettings, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let settings = sqlx::query_as!(
+            ProjectPrSettings,
+            r#"
+            SELECT draft, use_template, link_issue
+            FROM project_pr_settings
+            WHERE project_id = $1
+            "#,
+            project_id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(settings.unwrap_or_default())
+    }
+
+    pub async fn upsert<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        draft: bool,
+        use_template: bool,
+        link_issue: bool,
+    ) -> Result<ProjectPrSettings, sqlx::Error>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as!(
+            ProjectPrSettings,
+            r#"
+            INSERT INTO project_pr_settings (project_id, draft, use_template, link_issue)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (project_id) DO UPDATE
+            SET draft = EXCLUDED.draft,
+                use_template = EXCLUDED.use_template,
+                link_issue = EXCLUDED.link_issue,
+                updated_at = NOW()
+            RETURNING draft, use_template, link_issue
+            "#,
+            project_id,
+            draft,
+            use_template,
+            link_issue
+        )
+        .fetch_one(executor)
+        .await
+    }
+}
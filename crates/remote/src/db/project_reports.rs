@@ -0,0 +1,258 @@
+use api_types::{
+    ProjectReport, ProjectReportSnapshot, ReportIssue, ReportMovedIssue, ReportStatusCount,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::tenant::TenantId;
+
+#[derive(Debug, Error)]
+pub enum ProjectReportError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+#[derive(Debug, FromRow)]
+struct ProjectReportRow {
+    id: Uuid,
+    project_id: Uuid,
+    created_by_user_id: Option<Uuid>,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    snapshot: sqlx::types::Json<ProjectReportSnapshot>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<ProjectReportRow> for ProjectReport {
+    fn from(row: ProjectReportRow) -> Self {
+        Self {
+            id: row.id,
+            project_id: row.project_id,
+            created_by_user_id: row.created_by_user_id,
+            period_start: row.period_start,
+            period_end: row.period_end,
+            snapshot: row.snapshot.0,
+            created_at: row.created_at,
+        }
+    }
+}
+
+pub struct ProjectReportRepository;
+
+impl ProjectReportRepository {
+    /// Compute the board snapshot for `project_id` over `[period_start, period_end)`.
+    /// All of it is read from one snapshot, so concurrent writes can't make
+    /// the counts and lists disagree.
+    pub async fn build_snapshot(
+        pool: &PgPool,
+        tenant: TenantId,
+        project_id: Uuid,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<ProjectReportSnapshot, ProjectReportError> {
+        let mut tx = super::begin_tenant_snapshot_tx(pool, tenant).await?;
+        let status_counts = sqlx::query_as!(
+            ReportStatusCount,
+            r#"
+            SELECT
+                ps.id                     AS "status_id!: Uuid",
+                ps.name                   AS "status_name!",
+                COUNT(i.id)::BIGINT       AS "count!"
+            FROM project_statuses ps
+            LEFT JOIN issues i ON i.status_id = ps.id
+            WHERE ps.project_id = $1
+            GROUP BY ps.id, ps.name, ps.sort_order
+            ORDER BY ps.sort_order ASC
+            "#,
+            project_id
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let moved_issues = sqlx::query_as!(
+            ReportMovedIssue,
+            r#"
+            SELECT
+                i.id                AS "issue_id!: Uuid",
+                i.simple_id         AS "simple_id!",
+                i.title             AS "title!",
+                a.from_status_id    AS "from_status_id?: Uuid",
+                a.to_status_id      AS "to_status_id!: Uuid",
+                a.created_at        AS "moved_at!: DateTime<Utc>"
+            FROM issue_activity a
+            JOIN issues i ON i.id = a.issue_id
+            WHERE a.project_id = $1
+              AND a.event_type = 'status_changed'
+              AND a.created_at >= $2
+              AND a.created_at < $3
+            ORDER BY a.seq DESC
+            "#,
+            project_id,
+            period_start,
+            period_end
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let completed_issues = sqlx::query_as!(
+            ReportIssue,
+            r#"
+            SELECT
+                id              AS "issue_id!: Uuid",
+                simple_id       AS "simple_id!",
+                title           AS "title!",
+                completed_at    AS "completed_at?: DateTime<Utc>"
+            FROM issues
+            WHERE project_id = $1
+              AND completed_at >= $2
+              AND completed_at < $3
+            ORDER BY completed_at DESC
+            "#,
+            project_id,
+            period_start,
+            period_end
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(ProjectReportSnapshot {
+            status_counts,
+            moved_issues,
+            completed_issues,
+        })
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        tenant: TenantId,
+        project_id: Uuid,
+        created_by_user_id: Uuid,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        snapshot: ProjectReportSnapshot,
+    ) -> Result<ProjectReport, ProjectReportError> {
+        let snapshot = sqlx::types::Json(snapshot);
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let row = sqlx::query_as!(
+            ProjectReportRow,
+            r#"
+            INSERT INTO project_reports (
+                project_id, created_by_user_id, period_start, period_end, snapshot
+            )
+            SELECT p.id, $2, $3, $4, $5
+            FROM projects p
+            WHERE p.id = $1 AND p.organization_id = $6
+            RETURNING
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                created_by_user_id  AS "created_by_user_id?: Uuid",
+                period_start        AS "period_start!: DateTime<Utc>",
+                period_end          AS "period_end!: DateTime<Utc>",
+                snapshot            AS "snapshot!: sqlx::types::Json<ProjectReportSnapshot>",
+                created_at          AS "created_at!: DateTime<Utc>"
+            "#,
+            project_id,
+            created_by_user_id,
+            period_start,
+            period_end,
+            snapshot as sqlx::types::Json<ProjectReportSnapshot>,
+            tenant.id()
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(row.into())
+    }
+
+    pub async fn find_by_id(
+        pool: &PgPool,
+        tenant: TenantId,
+        project_id: Uuid,
+        id: Uuid,
+    ) -> Result<Option<ProjectReport>, ProjectReportError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let row = sqlx::query_as!(
+            ProjectReportRow,
+            r#"
+            SELECT
+                r.id                  AS "id!: Uuid",
+                r.project_id          AS "project_id!: Uuid",
+                r.created_by_user_id  AS "created_by_user_id?: Uuid",
+                r.period_start        AS "period_start!: DateTime<Utc>",
+                r.period_end          AS "period_end!: DateTime<Utc>",
+                r.snapshot            AS "snapshot!: sqlx::types::Json<ProjectReportSnapshot>",
+                r.created_at          AS "created_at!: DateTime<Utc>"
+            FROM project_reports r
+            JOIN projects p ON p.id = r.project_id
+            WHERE r.id = $1 AND r.project_id = $2 AND p.organization_id = $3
+            "#,
+            id,
+            project_id,
+            tenant.id()
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(row.map(Into::into))
+    }
+
+    pub async fn list_by_project(
+        pool: &PgPool,
+        tenant: TenantId,
+        project_id: Uuid,
+    ) -> Result<Vec<ProjectReport>, ProjectReportError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
+        let rows = sqlx::query_as!(
+            ProjectReportRow,
+            r#"
+            SELECT
+                r.id                  AS "id!: Uuid",
+                r.project_id          AS "project_id!: Uuid",
+                r.created_by_user_id  AS "created_by_user_id?: Uuid",
+                r.period_start        AS "period_start!: DateTime<Utc>",
+                r.period_end          AS "period_end!: DateTime<Utc>",
+                r.snapshot            AS "snapshot!: sqlx::types::Json<ProjectReportSnapshot>",
+                r.created_at          AS "created_at!: DateTime<Utc>"
+            FROM project_reports r
+            JOIN projects p ON p.id = r.project_id
+            WHERE r.project_id = $1 AND p.organization_id = $2
+            ORDER BY r.created_at DESC
+            "#,
+            project_id,
+            tenant.id()
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// The project and organization a report belongs to, as
+    /// `(project_id, organization_id)`. Runs outside any tenant, since a
+    /// signed download URL names only the report.
+    pub async fn owner(
+        pool: &PgPool,
+        report_id: Uuid,
+    ) -> Result<Option<(Uuid, Uuid)>, ProjectReportError> {
+        let mut tx = super::begin_trusted_tx(pool).await?;
+        let owner = sqlx::query!(
+            r#"
+            SELECT r.project_id, p.organization_id
+            FROM project_reports r
+            JOIN projects p ON p.id = r.project_id
+            WHERE r.id = $1
+            "#,
+            report_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(owner.map(|owner| (owner.project_id, owner.organization_id)))
+    }
+}
@@ -1,4 +1,4 @@
-use api_types::{DeleteResponse, MutationResponse, ProjectStatus};
+use api_types::{DeleteResponse, MutationResponse, ProjectStatus, ProjectStatusCategory};
 use chrono::{DateTime, Utc};
 use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
@@ -6,15 +6,46 @@ use uuid::Uuid;
 
 use super::get_txid;
 
-/// Default statuses that are created for each new project (name, color, sort_order, hidden)
+/// Default statuses that are created for each new project (name, color, sort_order, hidden,
+/// category)
 /// Colors are in HSL format: "H S% L%"
-pub const DEFAULT_STATUSES: &[(&str, &str, i32, bool)] = &[
-    ("Backlog", "220 9% 46%", 0, true),
-    ("To do", "217 91% 60%", 1, false),
-    ("In progress", "38 92% 50%", 2, false),
-    ("In review", "258 90% 66%", 3, false),
-    ("Done", "142 71% 45%", 4, false),
-    ("Cancelled", "0 84% 60%", 5, true),
+pub const DEFAULT_STATUSES: &[(&str, &str, i32, bool, ProjectStatusCategory)] = &[
+    (
+        "Backlog",
+        "220 9% 46%",
+        0,
+        true,
+        ProjectStatusCategory::Backlog,
+    ),
+    (
+        "To do",
+        "217 91% 60%",
+        1,
+        false,
+        ProjectStatusCategory::Todo,
+    ),
+    (
+        "In progress",
+        "38 92% 50%",
+        2,
+        false,
+        ProjectStatusCategory::InProgress,
+    ),
+    (
+        "In review",
+        "258 90% 66%",
+        3,
+        false,
+        ProjectStatusCategory::InReview,
+    ),
+    ("Done", "142 71% 45%", 4, false, ProjectStatusCategory::Done),
+    (
+        "Cancelled",
+        "0 84% 60%",
+        5,
+        true,
+        ProjectStatusCategory::Cancelled,
+    ),
 ];
 
 #[derive(Debug, Error)]
@@ -43,6 +74,7 @@ impl ProjectStatusRepository {
                 color           AS "color!",
                 sort_order      AS "sort_order!",
                 hidden          AS "hidden!",
+                category        AS "category: ProjectStatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             FROM project_statuses
             WHERE id = $1
@@ -73,6 +105,7 @@ impl ProjectStatusRepository {
                 color           AS "color!",
                 sort_order      AS "sort_order!",
                 hidden          AS "hidden!",
+                category        AS "category: ProjectStatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             FROM project_statuses
             WHERE project_id = $1 AND LOWER(name) = LOWER($2)
@@ -86,6 +119,43 @@ impl ProjectStatusRepository {
         Ok(record)
     }
 
+    /// The first status in board order with `category`, if the project has
+    /// one.
+    pub async fn find_by_category<'e, E>(
+        executor: E,
+        project_id: Uuid,
+        category: ProjectStatusCategory,
+    ) -> Result<Option<ProjectStatus>, ProjectStatusError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            ProjectStatus,
+            r#"
+            SELECT
+                id              AS "id!: Uuid",
+                project_id      AS "project_id!: Uuid",
+                name            AS "name!",
+                color           AS "color!",
+                sort_order      AS "sort_order!",
+                hidden          AS "hidden!",
+                category        AS "category: ProjectStatusCategory",
+                created_at      AS "created_at!: DateTime<Utc>"
+            FROM project_statuses
+            WHERE project_id = $1 AND category = $2
+            ORDER BY sort_order ASC
+            LIMIT 1
+            "#,
+            project_id,
+            category as ProjectStatusCategory
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         pool: &PgPool,
         id: Option<Uuid>,
@@ -94,6 +164,7 @@ impl ProjectStatusRepository {
         color: String,
         sort_order: i32,
         hidden: bool,
+        category: Option<ProjectStatusCategory>,
     ) -> Result<MutationResponse<ProjectStatus>, ProjectStatusError> {
         let mut tx = super::begin_tx(pool).await?;
         let id = id.unwrap_or_else(Uuid::new_v4);
@@ -101,8 +172,8 @@ impl ProjectStatusRepository {
         let data = sqlx::query_as!(
             ProjectStatus,
             r#"
-            INSERT INTO project_statuses (id, project_id, name, color, sort_order, hidden, created_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO project_statuses (id, project_id, name, color, sort_order, hidden, category, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING
                 id              AS "id!: Uuid",
                 project_id      AS "project_id!: Uuid",
@@ -110,6 +181,7 @@ impl ProjectStatusRepository {
                 color           AS "color!",
                 sort_order      AS "sort_order!",
                 hidden          AS "hidden!",
+                category        AS "category: ProjectStatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             "#,
             id,
@@ -118,6 +190,7 @@ impl ProjectStatusRepository {
             color,
             sort_order,
             hidden,
+            category as Option<ProjectStatusCategory>,
             created_at
         )
         .fetch_one(&mut *tx)
@@ -129,7 +202,7 @@ impl ProjectStatusRepository {
     }
 
     /// Update a project status with partial fields. Uses COALESCE to preserve existing values
-    /// when None is provided.
+    /// when None is provided; `category` is `Some(None)` to clear it.
     pub async fn update(
         pool: &PgPool,
         id: Uuid,
@@ -137,8 +210,11 @@ impl ProjectStatusRepository {
         color: Option<String>,
         sort_order: Option<i32>,
         hidden: Option<bool>,
+        category: Option<Option<ProjectStatusCategory>>,
     ) -> Result<MutationResponse<ProjectStatus>, ProjectStatusError> {
         let mut tx = super::begin_tx(pool).await?;
+        let update_category = category.is_some();
+        let category_value = category.flatten();
         let data = sqlx::query_as!(
             ProjectStatus,
             r#"
@@ -147,8 +223,9 @@ impl ProjectStatusRepository {
                 name = COALESCE($1, name),
                 color = COALESCE($2, color),
                 sort_order = COALESCE($3, sort_order),
-                hidden = COALESCE($4, hidden)
-            WHERE id = $5
+                hidden = COALESCE($4, hidden),
+                category = CASE WHEN $5 THEN $6 ELSE category END
+            WHERE id = $7
             RETURNING
                 id              AS "id!: Uuid",
                 project_id      AS "project_id!: Uuid",
@@ -156,12 +233,15 @@ impl ProjectStatusRepository {
                 color           AS "color!",
                 sort_order      AS "sort_order!",
                 hidden          AS "hidden!",
+                category        AS "category: ProjectStatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             "#,
             name,
             color,
             sort_order,
             hidden,
+            update_category,
+            category_value as Option<ProjectStatusCategory>,
             id
         )
         .fetch_one(&mut *tx)
@@ -199,6 +279,7 @@ impl ProjectStatusRepository {
                 color           AS "color!",
                 sort_order      AS "sort_order!",
                 hidden          AS "hidden!",
+                category        AS "category: ProjectStatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             FROM project_statuses
             WHERE project_id = $1
@@ -220,21 +301,24 @@ impl ProjectStatusRepository {
     {
         let names: Vec<String> = DEFAULT_STATUSES
             .iter()
-            .map(|(n, _, _, _)| (*n).to_string())
+            .map(|(n, _, _, _, _)| (*n).to_string())
             .collect();
         let colors: Vec<String> = DEFAULT_STATUSES
             .iter()
-            .map(|(_, c, _, _)| (*c).to_string())
+            .map(|(_, c, _, _, _)| (*c).to_string())
             .collect();
-        let sort_orders: Vec<i32> = DEFAULT_STATUSES.iter().map(|(_, _, s, _)| *s).collect();
-        let hiddens: Vec<bool> = DEFAULT_STATUSES.iter().map(|(_, _, _, h)| *h).collect();
+        let sort_orders: Vec<i32> = DEFAULT_STATUSES.iter().map(|(_, _, s, _, _)| *s).collect();
+        let hiddens: Vec<bool> = DEFAULT_STATUSES.iter().map(|(_, _, _, h, _)| *h).collect();
+        let categories: Vec<ProjectStatusCategory> =
+            DEFAULT_STATUSES.iter().map(|(_, _, _, _, c)| *c).collect();
 
         let statuses = sqlx::query_as!(
             ProjectStatus,
             r#"
-            INSERT INTO project_statuses (id, project_id, name, color, sort_order, hidden, created_at)
-            SELECT gen_random_uuid(), $1, name, color, sort_order, hidden, NOW()
-            FROM UNNEST($2::text[], $3::text[], $4::int[], $5::bool[]) AS t(name, color, sort_order, hidden)
+            INSERT INTO project_statuses (id, project_id, name, color, sort_order, hidden, category, created_at)
+            SELECT gen_random_uuid(), $1, name, color, sort_order, hidden, category, NOW()
+            FROM UNNEST($2::text[], $3::text[], $4::int[], $5::bool[], $6::project_status_category[])
+                AS t(name, color, sort_order, hidden, category)
             RETURNING
                 id              AS "id!: Uuid",
                 project_id      AS "project_id!: Uuid",
@@ -242,13 +326,15 @@ impl ProjectStatusRepository {
                 color           AS "color!",
                 sort_order      AS "sort_order!",
                 hidden          AS "hidden!",
+                category        AS "category: ProjectStatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             "#,
             project_id,
             &names,
             &colors,
             &sort_orders,
-            &hiddens
+            &hiddens,
+            &categories as &[ProjectStatusCategory]
         )
         .fetch_all(executor)
         .await?;
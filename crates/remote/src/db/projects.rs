@@ -4,7 +4,9 @@ use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 
-use super::{get_txid, project_statuses::ProjectStatusRepository, tags::TagRepository};
+use super::{
+    get_txid, project_statuses::ProjectStatusRepository, tags::TagRepository, tenant::TenantId,
+};
 
 /// Default color for the initial project created with personal organizations
 /// HSL format: "H S% L%" (blue - matches "To do" status)
@@ -57,7 +59,7 @@ impl ProjectRepository {
     pub async fn create<'e, E>(
         executor: E,
         id: Option<Uuid>,
-        organization_id: Uuid,
+        tenant: TenantId,
         name: String,
         color: String,
     ) -> Result<Project, ProjectError>
@@ -95,7 +97,7 @@ impl ProjectRepository {
                 updated_at       AS "updated_at!: DateTime<Utc>"
             "#,
             id,
-            organization_id,
+            tenant.id(),
             name,
             color,
             now,
@@ -109,7 +111,7 @@ impl ProjectRepository {
 
     pub async fn list_by_organization<'e, E>(
         executor: E,
-        organization_id: Uuid,
+        tenant: TenantId,
     ) -> Result<Vec<Project>, ProjectError>
     where
         E: Executor<'e, Database = Postgres>,
@@ -129,7 +131,7 @@ impl ProjectRepository {
             WHERE organization_id = $1
             ORDER BY sort_order ASC, created_at DESC
             "#,
-            organization_id
+            tenant.id()
         )
         .fetch_all(executor)
         .await?;
@@ -141,12 +143,13 @@ impl ProjectRepository {
     /// when None is provided.
     pub async fn update(
         pool: &PgPool,
+        tenant: TenantId,
         id: Uuid,
         name: Option<String>,
         color: Option<String>,
         sort_order: Option<i32>,
     ) -> Result<MutationResponse<Project>, ProjectError> {
-        let mut tx = super::begin_tx(pool).await?;
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let data = Self::update_partial(&mut *tx, id, name, color, sort_order).await?;
 
         let txid = get_txid(&mut *tx).await?;
@@ -197,8 +200,12 @@ impl ProjectRepository {
         Ok(record)
     }
 
-    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, ProjectError> {
-        let mut tx = super::begin_tx(pool).await?;
+    pub async fn delete(
+        pool: &PgPool,
+        tenant: TenantId,
+        id: Uuid,
+    ) -> Result<DeleteResponse, ProjectError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         sqlx::query!("DELETE FROM projects WHERE id = $1", id)
             .execute(&mut *tx)
             .await?;
@@ -207,14 +214,14 @@ impl ProjectRepository {
         Ok(DeleteResponse { txid })
     }
 
-    pub async fn organization_id<'e, E>(
-        executor: E,
+    /// The organization a project belongs to. Runs outside any tenant, since
+    /// the access guards use it to find the tenant to check.
+    pub async fn organization_id(
+        pool: &PgPool,
         project_id: Uuid,
-    ) -> Result<Option<Uuid>, ProjectError>
-    where
-        E: Executor<'e, Database = Postgres>,
-    {
-        sqlx::query_scalar!(
+    ) -> Result<Option<Uuid>, ProjectError> {
+        let mut tx = super::begin_trusted_tx(pool).await?;
+        let organization_id = sqlx::query_scalar!(
             r#"
             SELECT organization_id
             FROM projects
@@ -222,21 +229,23 @@ impl ProjectRepository {
             "#,
             project_id
         )
-        .fetch_optional(executor)
-        .await
-        .map_err(ProjectError::from)
+        .fetch_optional(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(organization_id)
     }
 
     /// Creates the initial project for a newly created personal organization.
     /// Includes default tags and statuses. Designed for use within transactions.
     pub async fn create_initial_project_tx(
         tx: &mut sqlx::Transaction<'_, Postgres>,
-        organization_id: Uuid,
+        tenant: TenantId,
     ) -> Result<Project, ProjectError> {
+        super::scope_to_tenant(tx, tenant).await?;
         let project = Self::create(
             &mut **tx,
             None,
-            organization_id,
+            tenant,
             INITIAL_PROJECT_NAME.to_string(),
             INITIAL_PROJECT_COLOR.to_string(),
         )
@@ -257,13 +266,13 @@ impl ProjectRepository {
     pub async fn create_with_defaults(
         pool: &PgPool,
         id: Option<Uuid>,
-        organization_id: Uuid,
+        tenant: TenantId,
         name: String,
         color: String,
     ) -> Result<MutationResponse<Project>, ProjectError> {
-        let mut tx = super::begin_tx(pool).await?;
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
 
-        let project = Self::create(&mut *tx, id, organization_id, name, color).await?;
+        let project = Self::create(&mut *tx, id, tenant, name, color).await?;
 
         TagRepository::create_default_tags(&mut *tx, project.id)
             .await
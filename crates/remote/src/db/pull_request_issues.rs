@@ -3,7 +3,7 @@ use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 
-use super::pull_requests::PullRequestRepository;
+use super::{pull_requests::PullRequestRepository, tenant::TenantId};
 
 #[derive(Debug, Error)]
 pub enum PullRequestIssueError {
@@ -60,8 +60,10 @@ impl PullRequestIssueRepository {
 
     pub async fn list_by_project(
         pool: &PgPool,
+        tenant: TenantId,
         project_id: Uuid,
     ) -> Result<Vec<PullRequestIssue>, PullRequestIssueError> {
+        let mut tx = super::begin_tenant_tx(pool, tenant).await?;
         let records = sqlx::query_as!(
             PullRequestIssue,
             r#"
@@ -75,8 +77,9 @@ impl PullRequestIssueRepository {
             "#,
             project_id
         )
-        .fetch_all(pool)
+        .fetch_all(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(records)
     }
 
@@ -111,6 +114,60 @@ impl PullRequestIssueRepository {
         Ok(record)
     }
 
+    /// Link `issue_id` to the pull request because its body names the issue
+    /// in a trailer. An existing link is left as it is, so one made another
+    /// way isn't removed with the trailer.
+    pub async fn create_from_trailer<'e, E>(
+        executor: E,
+        pull_request_id: Uuid,
+        issue_id: Uuid,
+    ) -> Result<(), PullRequestIssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            INSERT INTO pull_request_issues (pull_request_id, issue_id, from_trailer)
+            VALUES ($1, $2, TRUE)
+            ON CONFLICT (pull_request_id, issue_id) DO NOTHING
+            "#,
+            pull_request_id,
+            issue_id
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
+    /// Links made from trailers of the pull request at `url` in
+    /// `organization_id`, as `(pull_request_id, issue_id)` pairs.
+    pub async fn trailer_links_for_pr_url<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        url: &str,
+    ) -> Result<Vec<(Uuid, Uuid)>, PullRequestIssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let rows = sqlx::query!(
+            r#"
+            SELECT pri.pull_request_id, pri.issue_id
+            FROM pull_requests pr
+            JOIN pull_request_issues pri ON pri.pull_request_id = pr.id
+            JOIN projects p ON p.id = pr.project_id
+            WHERE pr.url = $1 AND p.organization_id = $2 AND pri.from_trailer
+            "#,
+            url,
+            organization_id
+        )
+        .fetch_all(executor)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.pull_request_id, row.issue_id))
+            .collect())
+    }
+
     pub async fn delete(
         pool: &PgPool,
         pull_request_id: Uuid,
@@ -172,4 +229,55 @@ impl PullRequestIssueRepository {
         .await?;
         Ok(ids)
     }
+
+    /// Issues in `organization_id` linked to the pull request at `url`.
+    pub async fn issue_ids_for_pr_url<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        url: &str,
+    ) -> Result<Vec<Uuid>, PullRequestIssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let ids = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT pri.issue_id AS "issue_id!: Uuid"
+            FROM pull_requests pr
+            JOIN pull_request_issues pri ON pri.pull_request_id = pr.id
+            JOIN projects p ON p.id = pr.project_id
+            WHERE pr.url = $1 AND p.organization_id = $2
+            "#,
+            url,
+            organization_id
+        )
+        .fetch_all(executor)
+        .await?;
+        Ok(ids)
+    }
+
+    /// Issues in `organization_id` linked to pull requests merged as the
+    /// commit `sha`.
+    pub async fn issue_ids_for_merge_commit<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        sha: &str,
+    ) -> Result<Vec<Uuid>, PullRequestIssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let ids = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT pri.issue_id AS "issue_id!: Uuid"
+            FROM pull_requests pr
+            JOIN pull_request_issues pri ON pri.pull_request_id = pr.id
+            JOIN projects p ON p.id = pr.project_id
+            WHERE pr.merge_commit_sha = $1 AND p.organization_id = $2
+            "#,
+            sha,
+            organization_id
+        )
+        .fetch_all(executor)
+        .await?;
+        Ok(ids)
+    }
 }
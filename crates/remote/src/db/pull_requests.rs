@@ -1,6 +1,6 @@
 use api_types::{PullRequest, PullRequestStatus};
 use chrono::{DateTime, Utc};
-use sqlx::{Executor, Postgres};
+use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -83,14 +83,14 @@ impl PullRequestRepository {
     }
 
     /// Returns all PR rows matching a URL that belong to projects the user is a member of.
-    pub async fn list_by_url_for_user<'e, E>(
-        executor: E,
+    /// Those can span organizations, so the lookup isn't tenant scoped; the
+    /// membership join is what limits it.
+    pub async fn list_by_url_for_user(
+        pool: &PgPool,
         url: &str,
         user_id: Uuid,
-    ) -> Result<Vec<PullRequest>, PullRequestError>
-    where
-        E: Executor<'e, Database = Postgres>,
-    {
+    ) -> Result<Vec<PullRequest>, PullRequestError> {
+        let mut tx = super::begin_trusted_tx(pool).await?;
         let records = sqlx::query_as!(
             PullRequest,
             r#"
@@ -117,9 +117,10 @@ impl PullRequestRepository {
             url,
             user_id
         )
-        .fetch_all(executor)
+        .fetch_all(&mut *tx)
         .await?;
 
+        tx.commit().await?;
         Ok(records)
     }
 
@@ -128,7 +128,10 @@ impl TagRepository {
         Ok(DeleteResponse { txid })
     }
 
-    pub async fn list_by_project(pool: &PgPool, project_id: Uuid) -> Result<Vec<Tag>, TagError> {
+    pub async fn list_by_project<'e, E>(executor: E, project_id: Uuid) -> Result<Vec<Tag>, TagError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         let records = sqlx::query_as!(
             Tag,
             r#"
@@ -142,7 +145,7 @@ impl TagRepository {
             "#,
             project_id
         )
-        .fetch_all(pool)
+        .fetch_all(executor)
         .await?;
 
         Ok(records)
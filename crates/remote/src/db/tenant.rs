@@ -0,0 +1,62 @@
+//! Tenant scoping for organization-owned data.
+//!
+//! Repository methods that read or write one organization's data take a
+//! [`TenantId`] rather than a raw organization id. Request handlers get one
+//! from the access guards in `routes::organization_members`, so a handler
+//! can't query an organization it hasn't checked the caller belongs to.
+
+use std::fmt;
+
+use uuid::Uuid;
+
+/// An organization the current code path is authorized to act in.
+///
+/// The inner id is private: the only way to make one is [`TenantId::trusted`],
+/// which takes the [`TenantSource`] that vouches for the id and records it.
+///
+/// ```compile_fail
+/// let tenant = remote::db::tenant::TenantId(uuid::Uuid::nil());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TenantId(Uuid);
+
+impl TenantId {
+    /// Scope to an organization id that `source` vouches for. Never pass a
+    /// request path or body id here without the check `source` names.
+    pub(crate) fn trusted(organization_id: Uuid, source: TenantSource) -> Self {
+        tracing::trace!(%organization_id, ?source, "scoped to tenant");
+        Self(organization_id)
+    }
+
+    pub fn id(self) -> Uuid {
+        self.0
+    }
+}
+
+/// Why a code path may act in an organization. Adding a variant is the only
+/// way to introduce a new kind of tenant scope, so each one is reviewed here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TenantSource {
+    /// An access guard in `routes::organization_members` checked the caller
+    /// belongs to the organization.
+    MembershipCheck,
+    /// The caller is a server operator acting on an organization they named.
+    OperatorAccess,
+    /// The organization owns the installation a verified GitHub webhook came
+    /// from.
+    Webhook,
+    /// The organization was recorded when one of its admins requested a
+    /// GitHub App install URL.
+    PendingInstallation,
+    /// The organization was created in the current transaction.
+    NewOrganization,
+    /// The organization owns a resource named by a download URL the server
+    /// signed after an access check.
+    SignedDownload,
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -192,6 +192,27 @@ fn build_digest_copy(row: &NotificationDigestRow) -> DigestCopy {
             format!("{actor_name} changed the description on {issue_label}"),
             issue_context(payload).map(|issue| format!("Updated the description on {issue}.")),
         ),
+        NotificationType::IssueDueSoon => {
+            (format!("{issue_label} is due soon"), issue_context(payload))
+        }
+        NotificationType::IssueBlocked => (
+            format!("{issue_label} was blocked by another issue"),
+            issue_context(payload),
+        ),
+        NotificationType::IssueUnblocked => (
+            format!("{issue_label} is no longer blocked"),
+            issue_context(payload),
+        ),
+        NotificationType::IssueMentioned => (
+            format!("{actor_name} mentioned you in {issue_label}"),
+            payload
+                .comment_preview
+                .as_deref()
+                .map(clean_preview_text)
+                .filter(|value| !value.is_empty())
+                .map(|value| format!("\"{}\"", truncate_text(&value, 177)))
+                .or_else(|| issue_context(payload)),
+        ),
     };
 
     DigestCopy {
@@ -44,6 +44,10 @@ pub enum DigestError {
     InvalidWindowDuration,
 }
 
+/// Send digests to the users whose digest hour is `due_hour`, where users
+/// who haven't picked one get theirs at `default_hour`. With no `due_hour`,
+/// everyone with pending notifications gets one.
+#[allow(clippy::too_many_arguments)]
 pub async fn run_email_digest(
     pool: &PgPool,
     mailer: &dyn Mailer,
@@ -51,13 +55,20 @@ pub async fn run_email_digest(
     now: DateTime<Utc>,
     window: Duration,
     send_delay: Duration,
+    due_hour: Option<u32>,
+    default_hour: u32,
 ) -> Result<DigestStats, DigestError> {
     let (window_start, window_end) = digest_window(now, window)?;
     let mut stats = DigestStats::default();
 
-    let users =
-        DigestRepository::fetch_users_with_pending_notifications(pool, window_start, window_end)
-            .await?;
+    let users = DigestRepository::fetch_users_with_pending_notifications(
+        pool,
+        window_start,
+        window_end,
+        due_hour.map(|hour| hour as i16),
+        default_hour as i16,
+    )
+    .await?;
 
     info!(
         window_start = %window_start,
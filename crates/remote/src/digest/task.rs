@@ -1,6 +1,6 @@
 use std::{panic::AssertUnwindSafe, sync::Arc, time::Duration};
 
-use chrono::{DateTime, Days, Timelike, Utc};
+use chrono::{DateTime, Timelike, Utc};
 use futures::FutureExt;
 use sqlx::PgPool;
 use tokio::task::JoinHandle;
@@ -13,7 +13,9 @@ use crate::{
 };
 
 const DEFAULT_WINDOW: Duration = Duration::from_secs(86400);
-const DEFAULT_RUN_HOUR_UTC: u32 = 8;
+/// Digest hour for users who haven't picked one. `DIGEST_RUN_HOUR_UTC`
+/// overrides it.
+const DEFAULT_HOUR_UTC: u32 = 8;
 const DEFAULT_SEND_DELAY: Duration = Duration::from_millis(100);
 
 pub fn spawn_digest_task(
@@ -25,11 +27,11 @@ pub fn spawn_digest_task(
         .ok()
         .and_then(|v| v.parse::<u64>().ok())
         .map(Duration::from_secs);
-    let run_hour_utc = std::env::var("DIGEST_RUN_HOUR_UTC")
+    let default_hour_utc = std::env::var("DIGEST_RUN_HOUR_UTC")
         .ok()
         .and_then(|v| v.parse::<u32>().ok())
         .filter(|hour| *hour < 24)
-        .unwrap_or(DEFAULT_RUN_HOUR_UTC);
+        .unwrap_or(DEFAULT_HOUR_UTC);
     let window = std::env::var("DIGEST_WINDOW_SECS_OVERRIDE")
         .ok()
         .and_then(|v| v.parse::<u64>().ok())
@@ -48,7 +50,7 @@ pub fn spawn_digest_task(
             "Starting notification digest background task with interval override"
         ),
         None => info!(
-            run_hour_utc,
+            default_hour_utc,
             window_secs = window.as_secs(),
             "Starting notification digest background task"
         ),
@@ -60,7 +62,7 @@ pub fn spawn_digest_task(
             mailer.as_ref(),
             &base_url,
             interval_override,
-            run_hour_utc,
+            default_hour_utc,
             window,
             send_delay,
         ));
@@ -81,7 +83,7 @@ async fn digest_loop(
     mailer: &dyn Mailer,
     base_url: &str,
     interval_override: Option<Duration>,
-    run_hour_utc: u32,
+    default_hour_utc: u32,
     window: Duration,
     send_delay: Duration,
 ) {
@@ -90,7 +92,7 @@ async fn digest_loop(
             tokio::time::sleep(interval).await;
         } else {
             let now = Utc::now();
-            let next_run = next_run_at(now, run_hour_utc);
+            let next_run = next_run_at(now);
             let sleep_duration = (next_run - now)
                 .to_std()
                 .unwrap_or_else(|_| Duration::from_secs(0));
@@ -103,7 +105,22 @@ async fn digest_loop(
             continue;
         };
 
-        match run_email_digest(pool, mailer, base_url, Utc::now(), window, send_delay).await {
+        let now = Utc::now();
+        // Users each pick their digest hour, so every hourly run serves the
+        // ones whose hour it is.
+        let due_hour = interval_override.is_none().then(|| now.hour());
+        match run_email_digest(
+            pool,
+            mailer,
+            base_url,
+            now,
+            window,
+            send_delay,
+            due_hour,
+            default_hour_utc,
+        )
+        .await
+        {
             Ok(stats) => {
                 info!(
                     users_processed = stats.users_processed,
@@ -137,21 +154,12 @@ async fn acquire_run_lock(pool: &PgPool) -> Option<DigestRunLock> {
     }
 }
 
-fn next_run_at(now: DateTime<Utc>, run_hour_utc: u32) -> DateTime<Utc> {
-    let today = now.date_naive();
-    let today_run = today
-        .and_hms_opt(run_hour_utc, 0, 0)
-        .expect("validated digest hour");
-
-    let next_naive = if now.hour() < run_hour_utc {
-        today_run
-    } else {
-        today
-            .checked_add_days(Days::new(1))
-            .expect("date overflow for digest schedule")
-            .and_hms_opt(run_hour_utc, 0, 0)
-            .expect("validated digest hour")
-    };
-
-    DateTime::from_naive_utc_and_offset(next_naive, Utc)
+/// The start of the next hour.
+fn next_run_at(now: DateTime<Utc>) -> DateTime<Utc> {
+    let this_hour = now
+        .with_minute(0)
+        .and_then(|time| time.with_second(0))
+        .and_then(|time| time.with_nanosecond(0))
+        .expect("valid time");
+    this_hour + chrono::Duration::hours(1)
 }
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tracing::{info, instrument, warn};
+
+use crate::db::{
+    begin_trusted_tx,
+    issue_activity::{IssueActivityError, IssueActivityRepository},
+};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(900);
+const DEFAULT_WINDOW_HOURS: i64 = 24;
+
+/// Spawns a background task that records `due_soon` activity for issues whose
+/// target date is within the reminder window. Call once during server startup.
+pub(crate) fn spawn_due_reminder_task(pool: PgPool) -> JoinHandle<()> {
+    let interval = std::env::var("DUE_REMINDER_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_INTERVAL);
+    let window = std::env::var("DUE_REMINDER_WINDOW_HOURS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|hours| *hours > 0)
+        .map(chrono::Duration::hours)
+        .unwrap_or_else(|| chrono::Duration::hours(DEFAULT_WINDOW_HOURS));
+
+    info!(
+        interval_secs = interval.as_secs(),
+        window_hours = window.num_hours(),
+        "Starting due reminder background task"
+    );
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // Skip the immediate first tick so the server can finish starting up.
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            run_sweep(&pool, window).await;
+        }
+    })
+}
+
+#[instrument(name = "due_reminders.sweep", skip_all)]
+async fn run_sweep(pool: &PgPool, window: chrono::Duration) {
+    match record_due_soon(pool, window).await {
+        Ok(count) => info!(reminded = count, "Due reminder sweep complete"),
+        Err(e) => warn!(error = %e, "Due reminder sweep failed"),
+    }
+}
+
+/// Runs as the row-level security bypass role, since the sweep covers every
+/// organization.
+async fn record_due_soon(
+    pool: &PgPool,
+    window: chrono::Duration,
+) -> Result<u64, IssueActivityError> {
+    let mut tx = begin_trusted_tx(pool).await?;
+    let count = IssueActivityRepository::record_due_soon(&mut *tx, Utc::now() + window).await?;
+    tx.commit().await?;
+    Ok(count)
+}
@@ -0,0 +1,448 @@
+//! Cache for responses to non-live Electric shape requests, so clients
+//! loading the same popular shape don't each go to the Electric origin.
+//!
+//! Responses are kept for as long as Electric's `cache-control` allows, up
+//! to [`ElectricCacheConfig::max_ttl_secs`]. Entries are keyed by what
+//! selects the data (table, where clause and its params, columns) and the
+//! position in the shape log (offset and handle), so a cached response is
+//! only ever served for a request the shape route already authorized.
+//! Requests without a handle are answered with whichever handle the Electric
+//! instance behind the sticky upstream serves, so those are also keyed by
+//! that upstream.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::{Body, Bytes},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
+use redis::{AsyncCommands, aio::MultiplexedConnection};
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::{ElectricCacheBackend, ElectricCacheConfig};
+
+const REDIS_KEY_PREFIX: &str = "vk:electric:";
+
+/// A request's place in the cache, for requests that can be cached at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeCacheKey {
+    shape: String,
+    offset: String,
+    handle: Option<String>,
+    /// Sticky upstream the request is sent with; only part of the key
+    /// while `handle` is unset.
+    upstream: String,
+}
+
+impl ShapeCacheKey {
+    /// `None` for live requests, which wait for changes and so can't be
+    /// answered from a cache.
+    pub fn new(
+        table: &str,
+        where_clause: &str,
+        params: &[String],
+        client_params: &HashMap<String, String>,
+        upstream: &str,
+    ) -> Option<Self> {
+        if client_params.get("live").is_some_and(|live| live == "true") {
+            return None;
+        }
+        let mut shape = format!("{table}\0{where_clause}");
+        for param in params {
+            shape.push('\0');
+            shape.push_str(param);
+        }
+        shape.push('\0');
+        shape.push_str(client_params.get("columns").map_or("", String::as_str));
+
+        Some(Self {
+            shape,
+            offset: client_params
+                .get("offset")
+                .cloned()
+                .unwrap_or_else(|| "-1".to_string()),
+            handle: client_params.get("handle").cloned(),
+            upstream: upstream.to_string(),
+        })
+    }
+
+    /// The request a client starts the shape with, which is answered with
+    /// whatever handle is current.
+    pub fn initial(&self) -> Self {
+        Self {
+            shape: self.shape.clone(),
+            offset: "-1".to_string(),
+            handle: None,
+            upstream: self.upstream.clone(),
+        }
+    }
+
+    fn storage_key(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(&self.shape);
+        hasher.update([0]);
+        hasher.update(&self.offset);
+        match &self.handle {
+            Some(handle) => {
+                hasher.update([0]);
+                hasher.update(handle);
+            }
+            None => {
+                hasher.update([1]);
+                hasher.update(&self.upstream);
+            }
+        }
+        hex::encode(hasher.finalize())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl IntoResponse for CachedResponse {
+    fn into_response(self) -> Response {
+        (self.status, self.headers, Body::from(self.body)).into_response()
+    }
+}
+
+/// How a response is stored in Redis.
+#[derive(Serialize, Deserialize)]
+struct StoredResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl From<&CachedResponse> for StoredResponse {
+    fn from(response: &CachedResponse) -> Self {
+        Self {
+            status: response.status.as_u16(),
+            headers: response
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: BASE64_STANDARD.encode(&response.body),
+        }
+    }
+}
+
+impl TryFrom<StoredResponse> for CachedResponse {
+    type Error = ();
+
+    fn try_from(stored: StoredResponse) -> Result<Self, ()> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in stored.headers {
+            headers.append(
+                HeaderName::try_from(name).map_err(|_| ())?,
+                HeaderValue::try_from(value).map_err(|_| ())?,
+            );
+        }
+        Ok(Self {
+            status: StatusCode::from_u16(stored.status).map_err(|_| ())?,
+            headers,
+            body: BASE64_STANDARD.decode(stored.body).map_err(|_| ())?.into(),
+        })
+    }
+}
+
+pub struct ElectricCache {
+    store: Store,
+    max_ttl: Duration,
+    max_body_bytes: usize,
+}
+
+enum Store {
+    Memory(MemoryStore),
+    Redis(RedisStore),
+}
+
+impl ElectricCache {
+    /// `None` when caching is disabled.
+    pub fn from_config(config: &ElectricCacheConfig) -> Result<Option<Self>, redis::RedisError> {
+        let store = match &config.backend {
+            ElectricCacheBackend::Disabled => return Ok(None),
+            ElectricCacheBackend::Memory { max_entries } => Store::Memory(MemoryStore {
+                entries: Mutex::default(),
+                max_entries: *max_entries,
+            }),
+            ElectricCacheBackend::Redis { url } => Store::Redis(RedisStore {
+                client: redis::Client::open(url.expose_secret())?,
+                connection: tokio::sync::Mutex::new(None),
+            }),
+        };
+        Ok(Some(Self {
+            store,
+            max_ttl: Duration::from_secs(config.max_ttl_secs),
+            max_body_bytes: config.max_body_bytes,
+        }))
+    }
+
+    pub fn max_body_bytes(&self) -> usize {
+        self.max_body_bytes
+    }
+
+    /// How long a response with this `cache-control` may be kept, if at all.
+    pub fn ttl(&self, cache_control: &str) -> Option<Duration> {
+        ttl_from_cache_control(cache_control).map(|ttl| ttl.min(self.max_ttl))
+    }
+
+    pub async fn get(&self, key: &ShapeCacheKey) -> Option<CachedResponse> {
+        match &self.store {
+            Store::Memory(store) => store.get(&key.storage_key()),
+            Store::Redis(store) => store.get(&key.storage_key()).await,
+        }
+    }
+
+    pub async fn put(&self, key: &ShapeCacheKey, response: CachedResponse, ttl: Duration) {
+        if ttl.is_zero() {
+            return;
+        }
+        match &self.store {
+            Store::Memory(store) => store.put(key.storage_key(), response, ttl),
+            Store::Redis(store) => store.put(&key.storage_key(), &response, ttl).await,
+        }
+    }
+
+    pub async fn remove(&self, key: &ShapeCacheKey) {
+        match &self.store {
+            Store::Memory(store) => store.remove(&key.storage_key()),
+            Store::Redis(store) => store.remove(&key.storage_key()).await,
+        }
+    }
+}
+
+/// Shared caches may keep a response for `s-maxage`, or else `max-age`.
+fn ttl_from_cache_control(cache_control: &str) -> Option<Duration> {
+    let mut max_age = None;
+    let mut s_maxage = None;
+    for directive in cache_control.split(',').map(str::trim) {
+        let (name, value) = directive.split_once('=').unwrap_or((directive, ""));
+        match name.to_ascii_lowercase().as_str() {
+            "no-store" | "no-cache" | "private" => return None,
+            "max-age" => max_age = value.trim_matches('"').parse::<u64>().ok(),
+            "s-maxage" => s_maxage = value.trim_matches('"').parse::<u64>().ok(),
+            _ => {}
+        }
+    }
+    s_maxage
+        .or(max_age)
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs)
+}
+
+struct MemoryStore {
+    entries: Mutex<MemoryEntries>,
+    max_entries: usize,
+}
+
+#[derive(Default)]
+struct MemoryEntries {
+    by_key: HashMap<String, (Instant, CachedResponse)>,
+    /// The keys of `by_key` in expiry order, so eviction needs no scan.
+    by_expiry: BTreeSet<(Instant, String)>,
+}
+
+impl MemoryEntries {
+    fn remove(&mut self, key: &str) {
+        if let Some((expires_at, _)) = self.by_key.remove(key) {
+            self.by_expiry.remove(&(expires_at, key.to_string()));
+        }
+    }
+}
+
+impl MemoryStore {
+    fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.by_key.get(key) {
+            Some((expires_at, response)) if *expires_at > Instant::now() => Some(response.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// When full, evicts the entry expiring soonest, which is an expired
+    /// one if there are any.
+    fn put(&self, key: String, response: CachedResponse, ttl: Duration) {
+        let expires_at = Instant::now() + ttl;
+        let mut entries = self.entries.lock().unwrap();
+        entries.remove(&key);
+        if entries.by_key.len() >= self.max_entries
+            && let Some((_, soonest)) = entries.by_expiry.pop_first()
+        {
+            entries.by_key.remove(&soonest);
+        }
+        entries.by_expiry.insert((expires_at, key.clone()));
+        entries.by_key.insert(key, (expires_at, response));
+    }
+
+    fn remove(&self, key: &str) {
+        self.entries.lock().unwrap().remove(key);
+    }
+}
+
+/// Redis failures are logged and treated as misses; the origin still
+/// answers.
+struct RedisStore {
+    client: redis::Client,
+    connection: tokio::sync::Mutex<Option<MultiplexedConnection>>,
+}
+
+impl RedisStore {
+    async fn connection(&self) -> Option<MultiplexedConnection> {
+        let mut connection = self.connection.lock().await;
+        if connection.is_none() {
+            match self.client.get_multiplexed_async_connection().await {
+                Ok(connected) => *connection = Some(connected),
+                Err(error) => tracing::warn!(?error, "failed to connect to Electric cache"),
+            }
+        }
+        connection.clone()
+    }
+
+    async fn failed(&self, error: redis::RedisError) {
+        tracing::warn!(?error, "Electric cache request failed");
+        *self.connection.lock().await = None;
+    }
+
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut conn = self.connection().await?;
+        let stored: Option<String> = match conn.get(format!("{REDIS_KEY_PREFIX}{key}")).await {
+            Ok(stored) => stored,
+            Err(error) => {
+                self.failed(error).await;
+                return None;
+            }
+        };
+        serde_json::from_str::<StoredResponse>(&stored?)
+            .ok()
+            .and_then(|stored| stored.try_into().ok())
+    }
+
+    async fn put(&self, key: &str, response: &CachedResponse, ttl: Duration) {
+        let Some(mut conn) = self.connection().await else {
+            return;
+        };
+        let Ok(stored) = serde_json::to_string(&StoredResponse::from(response)) else {
+            return;
+        };
+        if let Err(error) = conn
+            .set_ex::<_, _, ()>(format!("{REDIS_KEY_PREFIX}{key}"), stored, ttl.as_secs())
+            .await
+        {
+            self.failed(error).await;
+        }
+    }
+
+    async fn remove(&self, key: &str) {
+        let Some(mut conn) = self.connection().await else {
+            return;
+        };
+        if let Err(error) = conn.del::<_, ()>(format!("{REDIS_KEY_PREFIX}{key}")).await {
+            self.failed(error).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ttl_from_cache_control() {
+        assert_eq!(
+            ttl_from_cache_control("public, max-age=604800, s-maxage=3600"),
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(
+            ttl_from_cache_control("public, max-age=60, stale-while-revalidate=300"),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(ttl_from_cache_control("no-store"), None);
+        assert_eq!(ttl_from_cache_control("public, max-age=0"), None);
+    }
+
+    #[test]
+    fn test_live_requests_are_not_cached() {
+        let params = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>()
+        };
+        let key = ShapeCacheKey::new(
+            "issues",
+            "project_id = $1",
+            &["p".to_string()],
+            &params(&[("offset", "0_0"), ("handle", "h")]),
+            "s",
+        )
+        .unwrap();
+        assert_eq!(key.initial().offset, "-1");
+        assert!(
+            ShapeCacheKey::new(
+                "issues",
+                "project_id = $1",
+                &["p".to_string()],
+                &params(&[("offset", "0_0"), ("live", "true")]),
+                "s",
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_only_requests_without_handle_are_keyed_by_upstream() {
+        let key = |pairs: &[(&str, &str)], upstream: &str| {
+            let params = pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>();
+            ShapeCacheKey::new("issues", "project_id = $1", &[], &params, upstream)
+                .unwrap()
+                .storage_key()
+        };
+        assert_ne!(key(&[], "a"), key(&[], "b"));
+        assert_eq!(
+            key(&[("offset", "0_0"), ("handle", "h")], "a"),
+            key(&[("offset", "0_0"), ("handle", "h")], "b")
+        );
+    }
+
+    #[test]
+    fn test_memory_store_evicts_soonest_expiry() {
+        let store = MemoryStore {
+            entries: Mutex::default(),
+            max_entries: 2,
+        };
+        let response = CachedResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        };
+        store.put("a".to_string(), response.clone(), Duration::from_secs(60));
+        store.put("b".to_string(), response.clone(), Duration::from_secs(10));
+        store.put("a".to_string(), response.clone(), Duration::from_secs(30));
+        store.put("c".to_string(), response, Duration::from_secs(60));
+        assert!(store.get("a").is_some());
+        assert!(store.get("b").is_none());
+        assert!(store.get("c").is_some());
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::db::export_jobs::ExportJobRepository;
+
+/// How often a running export job records that it is still alive.
+pub(crate) const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+/// Silence after which a pending or running job is taken to have lost its
+/// process. Several heartbeats, so a slow database write isn't mistaken for
+/// a dead job.
+const STALE_AFTER_SECS: i64 = 180;
+const INTERVAL: Duration = Duration::from_secs(60);
+
+/// Spawns a background task that fails export jobs whose process went away,
+/// e.g. in a restart, which would otherwise stay "running" forever. The first
+/// sweep runs right away. Call once during server startup.
+pub(crate) fn spawn_abandoned_job_task(pool: PgPool) -> JoinHandle<()> {
+    info!(
+        interval_secs = INTERVAL.as_secs(),
+        stale_after_secs = STALE_AFTER_SECS,
+        "Starting abandoned export job background task"
+    );
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(INTERVAL);
+        loop {
+            ticker.tick().await;
+            match ExportJobRepository::fail_abandoned(
+                &pool,
+                chrono::Duration::seconds(STALE_AFTER_SECS),
+            )
+            .await
+            {
+                Ok(0) => {}
+                Ok(failed) => info!(failed, "Marked abandoned export jobs failed"),
+                Err(e) => warn!(error = %e, "Failed to mark abandoned export jobs"),
+            }
+        }
+    })
+}
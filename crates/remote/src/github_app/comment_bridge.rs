@@ -0,0 +1,253 @@
+//! Copies top-level comments on pull requests, and the summaries of their
+//! reviews, into the comment threads of the issues they're linked to, so
+//! discussion isn't split between GitHub and the board. Inline review
+//! comments stay on GitHub, since they only make sense next to the diff.
+//!
+//! Only comments by members of the organization are copied. On public
+//! repositories anyone can comment, and the issue threads are private.
+
+use api_types::{NotificationPayload, NotificationType};
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    db::{
+        begin_tenant_tx,
+        identity_errors::IdentityError,
+        issue_comments::{IssueCommentError, IssueCommentRepository},
+        issues::IssueRepository,
+        oauth_accounts::{OAuthAccountError, OAuthAccountRepository},
+        organization_members::is_member,
+        pr_comment_mirrors::PrCommentMirrorRepository,
+        pull_request_issues::{PullRequestIssueError, PullRequestIssueRepository},
+        tenant::{TenantId, TenantSource},
+    },
+    mentions::notify_mentions,
+    notifications::notify_issue_subscribers,
+};
+
+#[derive(Debug, Error)]
+pub enum CommentBridgeError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    OAuthAccount(#[from] OAuthAccountError),
+    #[error(transparent)]
+    Identity(#[from] IdentityError),
+    #[error(transparent)]
+    IssueComment(#[from] IssueCommentError),
+    #[error(transparent)]
+    PullRequestIssue(#[from] PullRequestIssueError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrCommentKind {
+    Comment,
+    /// A submitted review, with GitHub's `state` of it.
+    Review(ReviewState),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+}
+
+impl ReviewState {
+    pub fn parse(state: &str) -> Self {
+        match state.to_ascii_lowercase().as_str() {
+            "approved" => Self::Approved,
+            "changes_requested" => Self::ChangesRequested,
+            _ => Self::Commented,
+        }
+    }
+}
+
+/// A pull request comment or review as reported by a webhook.
+#[derive(Debug, Clone)]
+pub struct WebhookPrComment {
+    pub kind: PrCommentKind,
+    pub github_id: i64,
+    pub html_url: String,
+    pub author_github_id: i64,
+    pub author_login: String,
+    pub body: String,
+    pub pr_url: String,
+    pub pr_number: i64,
+}
+
+impl WebhookPrComment {
+    fn source(&self) -> &'static str {
+        match self.kind {
+            PrCommentKind::Comment => "issue_comment",
+            PrCommentKind::Review(_) => "review",
+        }
+    }
+
+    /// The comment as posted on the board: who said it with a link back,
+    /// then what they said, cut to fit `max_bytes`.
+    fn message(&self, max_bytes: usize) -> String {
+        let verb = match self.kind {
+            PrCommentKind::Comment => "commented on",
+            PrCommentKind::Review(ReviewState::Approved) => "approved",
+            PrCommentKind::Review(ReviewState::ChangesRequested) => "requested changes on",
+            PrCommentKind::Review(ReviewState::Commented) => "reviewed",
+        };
+        let header = format!(
+            "**@{}** {verb} [#{}]({}):\n\n",
+            self.author_login, self.pr_number, self.html_url
+        );
+
+        let body = self.body.trim();
+        let budget = max_bytes.saturating_sub(header.len());
+        if body.len() <= budget {
+            return header + body;
+        }
+        const ELLIPSIS: &str = "…";
+        let mut end = budget.saturating_sub(ELLIPSIS.len());
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        header + &body[..end] + ELLIPSIS
+    }
+}
+
+/// Copy `comment` into the thread of every issue in `organization_id`, the
+/// organization the webhook came from, that its pull request is linked to,
+/// and notify the issues' subscribers as if the commenter had posted it.
+/// Comments are only copied when the commenter signed in with GitHub and
+/// belongs to the organization; the copy is attributed to them. Returns how
+/// many issues got a copy; issues that already had one are skipped.
+pub async fn mirror_pr_comment(
+    pool: &PgPool,
+    organization_id: Uuid,
+    comment: &WebhookPrComment,
+    max_bytes: usize,
+) -> Result<usize, CommentBridgeError> {
+    let author_id = match OAuthAccountRepository::new(pool)
+        .get_by_provider_user("github", &comment.author_github_id.to_string())
+        .await?
+    {
+        Some(account) if is_member(pool, organization_id, account.user_id).await? => {
+            account.user_id
+        }
+        _ => {
+            info!(
+                url = %comment.html_url,
+                author = %comment.author_login,
+                "not mirroring pull request comment from outside the organization"
+            );
+            return Ok(0);
+        }
+    };
+
+    let tenant = TenantId::trusted(organization_id, TenantSource::Webhook);
+    let mut tx = begin_tenant_tx(pool, tenant).await?;
+    let issue_ids = PullRequestIssueRepository::issue_ids_for_pr_url(
+        &mut *tx,
+        organization_id,
+        &comment.pr_url,
+    )
+    .await?;
+
+    let message = comment.message(max_bytes);
+    let mut mirrored = Vec::new();
+    for issue_id in issue_ids {
+        if !PrCommentMirrorRepository::claim(&mut tx, comment.source(), comment.github_id, issue_id)
+            .await?
+        {
+            continue;
+        }
+        let issue_comment = IssueCommentRepository::insert(
+            &mut *tx,
+            None,
+            issue_id,
+            author_id,
+            None,
+            message.clone(),
+        )
+        .await?;
+        PrCommentMirrorRepository::set_issue_comment(
+            &mut tx,
+            comment.source(),
+            comment.github_id,
+            issue_id,
+            issue_comment.id,
+        )
+        .await?;
+        mirrored.push(issue_comment);
+    }
+    tx.commit().await?;
+
+    for issue_comment in &mirrored {
+        let Ok(Some(issue)) =
+            IssueRepository::find_in_tenant(pool, tenant, issue_comment.issue_id).await
+        else {
+            continue;
+        };
+        let comment_preview = issue_comment.message.chars().take(100).collect::<String>();
+        notify_issue_subscribers(
+            pool,
+            tenant,
+            author_id,
+            &issue,
+            NotificationType::IssueCommentAdded,
+            NotificationPayload {
+                comment_preview: Some(comment_preview),
+                ..Default::default()
+            },
+            Some(issue_comment.id),
+        )
+        .await;
+        notify_mentions(
+            pool,
+            tenant,
+            author_id,
+            &issue,
+            &issue_comment.message,
+            Some(issue_comment.id),
+        )
+        .await;
+    }
+
+    if !mirrored.is_empty() {
+        info!(url = %comment.html_url, issues = mirrored.len(), "mirrored pull request comment");
+    }
+    Ok(mirrored.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(kind: PrCommentKind, body: &str) -> WebhookPrComment {
+        WebhookPrComment {
+            kind,
+            github_id: 1,
+            html_url: "https://github.com/o/r/pull/7#issuecomment-1".to_string(),
+            author_github_id: 2,
+            author_login: "octocat".to_string(),
+            body: body.to_string(),
+            pr_url: "https://github.com/o/r/pull/7".to_string(),
+            pr_number: 7,
+        }
+    }
+
+    #[test]
+    fn test_message_links_back_to_github() {
+        assert_eq!(
+            comment(PrCommentKind::Review(ReviewState::Approved), " Ship it \n").message(1024),
+            "**@octocat** approved [#7](https://github.com/o/r/pull/7#issuecomment-1):\n\nShip it"
+        );
+    }
+
+    #[test]
+    fn test_message_fits_limit() {
+        let message = comment(PrCommentKind::Comment, &"é".repeat(100)).message(120);
+        assert!(message.len() <= 120);
+        assert!(message.ends_with('…'));
+    }
+}
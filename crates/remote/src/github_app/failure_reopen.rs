@@ -0,0 +1,227 @@
+//! Moves Done issues back to In progress when the work that finished them
+//! turns out to be broken: a linked pull request is reverted, or CI fails
+//! on the default branch at the commit it was merged as. Organizations opt
+//! in through their automation policy.
+
+use api_types::{
+    Issue, NotificationPayload, NotificationType, ProjectStatus, ProjectStatusCategory,
+};
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::db::{
+    begin_tenant_tx,
+    issue_assignees::{IssueAssigneeError, IssueAssigneeRepository},
+    issues::{IssueError, IssueRepository},
+    notifications::NotificationRepository,
+    organization_automation_policies::OrganizationAutomationPolicyRepository,
+    organization_members::is_member,
+    project_statuses::{ProjectStatusError, ProjectStatusRepository},
+    pull_request_issues::{PullRequestIssueError, PullRequestIssueRepository},
+    tenant::{TenantId, TenantSource},
+};
+
+#[derive(Debug, Error)]
+pub enum FailureReopenError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Issue(#[from] IssueError),
+    #[error(transparent)]
+    IssueAssignee(#[from] IssueAssigneeError),
+    #[error(transparent)]
+    ProjectStatus(#[from] ProjectStatusError),
+    #[error(transparent)]
+    PullRequestIssue(#[from] PullRequestIssueError),
+}
+
+/// Broken work reported by a webhook.
+#[derive(Debug, Clone)]
+pub enum WorkFailure {
+    /// The pull request at `pr_url` was undone by a merged revert.
+    Reverted { pr_url: String },
+    /// CI failed on the default branch at commit `sha`.
+    FailedChecks { sha: String },
+}
+
+/// The pull request a revert opened from GitHub's Revert button undoes,
+/// from the `Reverts owner/repo#123` line it puts in the body. Reverts are
+/// always opened in the same repository.
+pub fn reverted_pull_request_url(
+    repository_html_url: &str,
+    repository_full_name: &str,
+    body: &str,
+) -> Option<String> {
+    body.lines().find_map(|line| {
+        let (repo, number) = line.trim().strip_prefix("Reverts ")?.split_once('#')?;
+        let number: u64 = number.trim().parse().ok()?;
+        repo.eq_ignore_ascii_case(repository_full_name).then(|| {
+            format!(
+                "{}/pull/{number}",
+                repository_html_url.trim_end_matches('/')
+            )
+        })
+    })
+}
+
+/// Reopen the Done issues in `organization_id` linked to the failed work,
+/// if the organization enabled it, and notify their assignees. Returns the
+/// reopened issues.
+pub async fn reopen_failed_work(
+    pool: &PgPool,
+    organization_id: Uuid,
+    failure: &WorkFailure,
+) -> Result<Vec<Uuid>, FailureReopenError> {
+    let tenant = TenantId::trusted(organization_id, TenantSource::Webhook);
+    if !OrganizationAutomationPolicyRepository::get(pool, tenant)
+        .await?
+        .reopen_on_failure
+    {
+        return Ok(Vec::new());
+    }
+
+    let mut tx = begin_tenant_tx(pool, tenant).await?;
+    let issue_ids = match failure {
+        WorkFailure::Reverted { pr_url } => {
+            PullRequestIssueRepository::issue_ids_for_pr_url(&mut *tx, organization_id, pr_url)
+                .await?
+        }
+        WorkFailure::FailedChecks { sha } => {
+            PullRequestIssueRepository::issue_ids_for_merge_commit(&mut *tx, organization_id, sha)
+                .await?
+        }
+    };
+    tx.commit().await?;
+
+    let mut reopened = Vec::new();
+    for issue_id in issue_ids {
+        let Some((issue, status)) = reopen(pool, tenant, issue_id).await? else {
+            continue;
+        };
+        info!(%issue_id, ?failure, "reopened issue after failed work");
+        notify_assignees(pool, tenant, &issue, &status).await?;
+        reopened.push(issue_id);
+    }
+    Ok(reopened)
+}
+
+/// Move the issue from a done status to the project's first in-progress
+/// one, going by the statuses' categories rather than their names. Returns
+/// the updated issue and its new status, or `None` if it wasn't done or its
+/// project has no in-progress status.
+async fn reopen(
+    pool: &PgPool,
+    tenant: TenantId,
+    issue_id: Uuid,
+) -> Result<Option<(Issue, ProjectStatus)>, FailureReopenError> {
+    let mut tx = begin_tenant_tx(pool, tenant).await?;
+    let Some(issue) = IssueRepository::find_by_id(&mut *tx, issue_id).await? else {
+        return Ok(None);
+    };
+    let is_done = ProjectStatusRepository::find_by_id(&mut *tx, issue.status_id)
+        .await?
+        .is_some_and(|status| status.category == Some(ProjectStatusCategory::Done));
+    if !is_done {
+        return Ok(None);
+    }
+    let Some(in_progress) = ProjectStatusRepository::find_by_category(
+        &mut *tx,
+        issue.project_id,
+        ProjectStatusCategory::InProgress,
+    )
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let issue = IssueRepository::update(
+        &mut *tx,
+        issue_id,
+        Some(in_progress.id),
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(None),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(Some((issue, in_progress)))
+}
+
+/// Nobody on the board made the change, so unlike status notifications
+/// from the app there's no actor.
+async fn notify_assignees(
+    pool: &PgPool,
+    tenant: TenantId,
+    issue: &Issue,
+    status: &ProjectStatus,
+) -> Result<(), FailureReopenError> {
+    let payload = NotificationPayload {
+        deeplink_path: Some(format!(
+            "/projects/{}/issues/{}",
+            issue.project_id, issue.id
+        )),
+        issue_id: Some(issue.id),
+        issue_simple_id: Some(issue.simple_id.clone()),
+        issue_title: Some(issue.title.clone()),
+        new_status_id: Some(issue.status_id),
+        new_status_name: Some(status.name.clone()),
+        ..Default::default()
+    };
+    for assignee in IssueAssigneeRepository::list_by_issue(pool, issue.id).await? {
+        if !is_member(pool, tenant.id(), assignee.user_id)
+            .await
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        if let Err(error) = NotificationRepository::create(
+            pool,
+            tenant,
+            assignee.user_id,
+            NotificationType::IssueStatusChanged,
+            payload.clone(),
+            Some(issue.id),
+            None,
+        )
+        .await
+        {
+            warn!(?error, user_id = %assignee.user_id, issue_id = %issue.id, "failed to notify assignee of reopened issue");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverted_pull_request_url() {
+        let body = "Reverts acme/widgets#42\n\nBroke the build.";
+        assert_eq!(
+            reverted_pull_request_url("https://github.com/acme/widgets", "acme/widgets", body),
+            Some("https://github.com/acme/widgets/pull/42".to_string())
+        );
+        assert_eq!(
+            reverted_pull_request_url("https://github.com/acme/widgets", "acme/other", body),
+            None
+        );
+        assert_eq!(
+            reverted_pull_request_url(
+                "https://github.com/acme/widgets",
+                "acme/widgets",
+                "Fixes acme/widgets#42"
+            ),
+            None
+        );
+    }
+}
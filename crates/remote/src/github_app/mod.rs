@@ -1,9 +1,17 @@
+mod comment_bridge;
+mod failure_reopen;
 mod jwt;
 mod pr_review;
 mod service;
-mod webhook;
+mod task_links;
 
+pub use comment_bridge::{
+    CommentBridgeError, PrCommentKind, ReviewState, WebhookPrComment, mirror_pr_comment,
+};
+pub use failure_reopen::{
+    FailureReopenError, WorkFailure, reopen_failed_work, reverted_pull_request_url,
+};
 pub use jwt::GitHubAppJwt;
 pub use pr_review::{PrReviewError, PrReviewParams, PrReviewService};
 pub use service::{GitHubAppService, InstallationInfo, PrDetails, PrRef, Repository};
-pub use webhook::verify_webhook_signature;
+pub use task_links::{TaskLinkError, WebhookPullRequest, link_trailer_issues};
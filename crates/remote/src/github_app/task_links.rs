@@ -0,0 +1,146 @@
+//! Links pull requests opened outside the app to the issues named in their
+//! `Vibe-Kanban-Task` trailers, so they move those issues along like pull
+//! requests opened from a workspace do.
+//!
+//! Anyone can open a pull request on a public repository, so trailers are
+//! only honoured from authors GitHub reports as belonging to the repository.
+
+use api_types::PullRequestStatus;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use thiserror::Error;
+use tracing::{info, warn};
+use utils::task_trailer::parse_task_trailers;
+use uuid::Uuid;
+
+use crate::db::{
+    begin_tenant_tx,
+    issues::{IssueError, IssueRepository},
+    projects::{ProjectError, ProjectRepository},
+    pull_request_issues::{PullRequestIssueError, PullRequestIssueRepository},
+    pull_requests::{PullRequestError, PullRequestRepository},
+    tenant::{TenantId, TenantSource},
+};
+
+#[derive(Debug, Error)]
+pub enum TaskLinkError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Issue(#[from] IssueError),
+    #[error(transparent)]
+    Project(#[from] ProjectError),
+    #[error(transparent)]
+    PullRequest(#[from] PullRequestError),
+    #[error(transparent)]
+    PullRequestIssue(#[from] PullRequestIssueError),
+}
+
+/// `author_association` values of authors whose trailers are honoured.
+const TRUSTED_AUTHOR_ASSOCIATIONS: &[&str] = &["OWNER", "MEMBER", "COLLABORATOR"];
+
+/// A pull request as reported by a webhook.
+#[derive(Debug, Clone)]
+pub struct WebhookPullRequest {
+    pub url: String,
+    pub number: i32,
+    pub body: String,
+    /// How the author is related to the repository, e.g. `MEMBER`.
+    pub author_association: String,
+    pub status: PullRequestStatus,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub merge_commit_sha: Option<String>,
+    pub target_branch_name: String,
+}
+
+/// Link `pr` to every issue its body names, record its current status and
+/// sync the issues' status from it, then unlink issues an earlier body named
+/// but this one doesn't. Issues outside `organization_id`, the organization
+/// the webhook came from, are ignored so a trailer can't touch another
+/// organization's board. Returns the linked issues.
+pub async fn link_trailer_issues(
+    pool: &PgPool,
+    organization_id: Uuid,
+    pr: &WebhookPullRequest,
+) -> Result<Vec<Uuid>, TaskLinkError> {
+    let tenant = TenantId::trusted(organization_id, TenantSource::Webhook);
+    let issue_ids = if TRUSTED_AUTHOR_ASSOCIATIONS.contains(&pr.author_association.as_str()) {
+        parse_task_trailers(&pr.body)
+    } else {
+        info!(
+            url = %pr.url,
+            author_association = %pr.author_association,
+            "ignoring task trailers from an author outside the repository"
+        );
+        Vec::new()
+    };
+
+    let mut linked = Vec::new();
+    for &issue_id in &issue_ids {
+        let mut tx = begin_tenant_tx(pool, tenant).await?;
+        let Some(issue) = IssueRepository::find_by_id(&mut *tx, issue_id).await? else {
+            continue;
+        };
+        let in_organization = ProjectRepository::find_by_id(&mut *tx, issue.project_id)
+            .await?
+            .is_some_and(|project| project.organization_id == organization_id);
+        if !in_organization {
+            warn!(%issue_id, url = %pr.url, "ignoring task trailer for an issue in another organization");
+            continue;
+        }
+
+        let existing =
+            PullRequestRepository::find_by_url_and_project(&mut *tx, &pr.url, issue.project_id)
+                .await?;
+        let pull_request = match existing {
+            Some(existing) => {
+                PullRequestRepository::update(
+                    &mut *tx,
+                    existing.id,
+                    Some(pr.status),
+                    Some(pr.merged_at),
+                    Some(pr.merge_commit_sha.clone()),
+                )
+                .await?
+            }
+            None => {
+                PullRequestRepository::create(
+                    &mut *tx,
+                    pr.url.clone(),
+                    pr.number,
+                    pr.status,
+                    pr.merged_at,
+                    pr.merge_commit_sha.clone(),
+                    pr.target_branch_name.clone(),
+                    issue.project_id,
+                    issue_id,
+                )
+                .await?
+            }
+        };
+        PullRequestIssueRepository::create_from_trailer(&mut *tx, pull_request.id, issue_id)
+            .await?;
+        IssueRepository::sync_status_from_pull_request(&mut tx, issue_id, pull_request.status)
+            .await?;
+        tx.commit().await?;
+
+        info!(%issue_id, url = %pr.url, status = ?pr.status, "linked pull request from task trailer");
+        linked.push(issue_id);
+    }
+
+    let mut tx = begin_tenant_tx(pool, tenant).await?;
+    let links =
+        PullRequestIssueRepository::trailer_links_for_pr_url(&mut *tx, organization_id, &pr.url)
+            .await?;
+    for (pull_request_id, issue_id) in links {
+        if issue_ids.contains(&issue_id) {
+            continue;
+        }
+        PullRequestIssueRepository::delete_and_cleanup_orphan(&mut tx, pull_request_id, issue_id)
+            .await?;
+        info!(%issue_id, url = %pr.url, "unlinked pull request whose task trailer was removed");
+    }
+    tx.commit().await?;
+
+    Ok(linked)
+}
@@ -0,0 +1,184 @@
+//! NATS JetStream backend. Each replica reads the stream through its own
+//! durable consumer, so invalidations published while it was disconnected
+//! are replayed from the stream when it reconnects instead of being lost.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Context as _, anyhow};
+use async_nats::jetstream::{
+    self,
+    consumer::{AckPolicy, DeliverPolicy, PullConsumer, pull},
+    stream,
+};
+use futures::StreamExt;
+use secrecy::{ExposeSecret, SecretString};
+use tokio::sync::{broadcast, mpsc};
+use uuid::Uuid;
+
+use super::{Envelope, InProcessBroker, Invalidation, InvalidationBroker};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+/// Invalidations older than this are dropped from the stream. A replica
+/// disconnected for longer may have missed some, so its sockets recheck
+/// everything when it reconnects.
+const STREAM_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+/// Consumers of replicas that went away for good are removed after this.
+const CONSUMER_INACTIVE_THRESHOLD: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub struct JetStreamBroker {
+    local: InProcessBroker,
+    outbound: mpsc::UnboundedSender<Invalidation>,
+}
+
+impl JetStreamBroker {
+    /// Connects in the background; invalidations published before the
+    /// connection is up are sent once it is.
+    pub fn connect(url: SecretString, stream: String, consumer: String) -> Self {
+        let local = InProcessBroker::new();
+        let (outbound, outbound_rx) = mpsc::unbounded_channel();
+        let session = Session {
+            url,
+            subject: format!("{stream}.events"),
+            stream,
+            consumer,
+            origin: Uuid::new_v4(),
+            local: local.sender.clone(),
+        };
+        tokio::spawn(session.run(outbound_rx));
+        Self { local, outbound }
+    }
+}
+
+impl InvalidationBroker for JetStreamBroker {
+    fn publish(&self, invalidation: Invalidation) {
+        self.local.publish(invalidation);
+        // Only fails once the session task is gone, i.e. at shutdown.
+        let _ = self.outbound.send(invalidation);
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<Invalidation> {
+        self.local.subscribe()
+    }
+}
+
+struct Session {
+    url: SecretString,
+    stream: String,
+    subject: String,
+    consumer: String,
+    origin: Uuid,
+    local: broadcast::Sender<Invalidation>,
+}
+
+impl Session {
+    async fn run(self, mut outbound: mpsc::UnboundedReceiver<Invalidation>) {
+        let mut disconnected_at = None;
+        loop {
+            match self.connected(&mut outbound, &mut disconnected_at).await {
+                Ok(()) => return,
+                Err(error) => {
+                    tracing::warn!(
+                        ?error,
+                        stream = %self.stream,
+                        "invalidation stream disconnected; reconnecting"
+                    );
+                    disconnected_at.get_or_insert_with(Instant::now);
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        }
+    }
+
+    /// Relay invalidations both ways until the connection fails, or until
+    /// `outbound` closes at shutdown. `disconnected_at` is when the last
+    /// working connection was lost, and is cleared once reading resumes.
+    async fn connected(
+        &self,
+        outbound: &mut mpsc::UnboundedReceiver<Invalidation>,
+        disconnected_at: &mut Option<Instant>,
+    ) -> anyhow::Result<()> {
+        let client = async_nats::connect(self.url.expose_secret())
+            .await
+            .context("failed to connect to NATS")?;
+        let jetstream = jetstream::new(client);
+        let stream = jetstream
+            .get_or_create_stream(stream::Config {
+                name: self.stream.clone(),
+                subjects: vec![self.subject.clone()],
+                max_age: STREAM_MAX_AGE,
+                ..Default::default()
+            })
+            .await
+            .context("failed to open invalidation stream")?;
+        let consumer: PullConsumer = stream
+            .get_or_create_consumer(
+                &self.consumer,
+                pull::Config {
+                    durable_name: Some(self.consumer.clone()),
+                    ack_policy: AckPolicy::Explicit,
+                    // A new replica has no sockets yet, so there's nothing
+                    // to replay for it.
+                    deliver_policy: DeliverPolicy::New,
+                    inactive_threshold: CONSUMER_INACTIVE_THRESHOLD,
+                    ..Default::default()
+                },
+            )
+            .await
+            .context("failed to open invalidation consumer")?;
+        let mut messages = consumer
+            .messages()
+            .await
+            .context("failed to read invalidation consumer")?;
+        if disconnected_at
+            .take()
+            .is_some_and(|at| at.elapsed() >= STREAM_MAX_AGE)
+        {
+            tracing::info!(
+                stream = %self.stream,
+                "invalidations may have expired unread; rechecking everything"
+            );
+            let _ = self.local.send(Invalidation::All);
+        }
+
+        loop {
+            tokio::select! {
+                invalidation = outbound.recv() => {
+                    let Some(invalidation) = invalidation else {
+                        return Ok(());
+                    };
+                    if let Err(error) = self.publish(&jetstream, invalidation).await {
+                        tracing::warn!(?invalidation, "invalidation not sent to other replicas");
+                        return Err(error);
+                    }
+                }
+                message = messages.next() => {
+                    let message = message
+                        .ok_or_else(|| anyhow!("invalidation consumer ended"))?
+                        .context("failed to read invalidation")?;
+                    match serde_json::from_slice::<Envelope>(&message.payload) {
+                        Ok(envelope) if envelope.origin == self.origin => {}
+                        Ok(envelope) => {
+                            envelope.deliver(&self.local);
+                        }
+                        Err(error) => tracing::warn!(%error, "ignoring malformed invalidation"),
+                    }
+                    message.ack().await.map_err(|error| anyhow!(error))?;
+                }
+            }
+        }
+    }
+
+    /// Publish and wait for the stream to store it, so a failure is noticed.
+    async fn publish(
+        &self,
+        jetstream: &jetstream::Context,
+        invalidation: Invalidation,
+    ) -> anyhow::Result<()> {
+        let payload = serde_json::to_vec(&Envelope::new(self.origin, invalidation))?;
+        jetstream
+            .publish(self.subject.clone(), payload.into())
+            .await?
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! Cache invalidation bus.
+//!
+//! Anything that changes state a long-lived holder may have cached publishes
+//! an [`Invalidation`], instead of each holder relying on its own TTL. Open
+//! organization WebSockets subscribe so a removed member's socket closes as
+//! soon as the change arrives, rather than at its next periodic check.
+//! Delivery is best-effort: a subscriber that lags behind is told so and
+//! should assume anything changed, as it should on [`Invalidation::All`].
+//!
+//! With several server replicas, each one holds its own sockets, so the bus
+//! delegates to an [`InvalidationBroker`] chosen by
+//! [`InvalidationBrokerConfig`]: in-process for a single replica, Redis
+//! pub/sub or NATS JetStream to reach every replica.
+
+mod jetstream;
+mod redis_pubsub;
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+pub use jetstream::JetStreamBroker;
+pub use redis_pubsub::RedisBroker;
+use secrecy::ExposeSecret;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::config::InvalidationBrokerConfig;
+
+const CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Invalidation {
+    /// A user joined or left an organization, or their role changed.
+    Membership {
+        organization_id: Uuid,
+        user_id: Uuid,
+    },
+    /// Invalidations from other replicas may have been missed; recheck
+    /// everything.
+    All,
+}
+
+/// Delivers invalidations to subscribers, possibly across processes.
+pub trait InvalidationBroker: Send + Sync {
+    /// Deliver `invalidation` to every subscriber, including this
+    /// process's own.
+    fn publish(&self, invalidation: Invalidation);
+
+    fn subscribe(&self) -> broadcast::Receiver<Invalidation>;
+}
+
+#[derive(Clone)]
+pub struct InvalidationBus {
+    broker: Arc<dyn InvalidationBroker>,
+}
+
+impl InvalidationBus {
+    pub fn new() -> Self {
+        Self::with_broker(InProcessBroker::new())
+    }
+
+    pub fn with_broker(broker: impl InvalidationBroker + 'static) -> Self {
+        Self {
+            broker: Arc::new(broker),
+        }
+    }
+
+    /// The bus for `config`. Must be called inside a Tokio runtime.
+    pub fn from_config(config: &InvalidationBrokerConfig) -> Result<Self, redis::RedisError> {
+        Ok(match config {
+            InvalidationBrokerConfig::InProcess => Self::new(),
+            InvalidationBrokerConfig::Redis { url, channel } => {
+                Self::with_broker(RedisBroker::connect(url.expose_secret(), channel.clone())?)
+            }
+            InvalidationBrokerConfig::Nats {
+                url,
+                stream,
+                consumer,
+            } => Self::with_broker(JetStreamBroker::connect(
+                url.clone(),
+                stream.clone(),
+                consumer.clone(),
+            )),
+        })
+    }
+
+    pub fn publish(&self, invalidation: Invalidation) {
+        self.broker.publish(invalidation);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Invalidation> {
+        self.broker.subscribe()
+    }
+}
+
+impl Default for InvalidationBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Invalidations stay inside this process. Enough for a single replica.
+pub struct InProcessBroker {
+    sender: broadcast::Sender<Invalidation>,
+}
+
+impl InProcessBroker {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CAPACITY);
+        Self { sender }
+    }
+}
+
+impl Default for InProcessBroker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InvalidationBroker for InProcessBroker {
+    fn publish(&self, invalidation: Invalidation) {
+        // No subscribers just means nothing is cached yet.
+        let _ = self.sender.send(invalidation);
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<Invalidation> {
+        self.sender.subscribe()
+    }
+}
+
+/// What goes over the wire between replicas. `origin` lets a replica skip
+/// its own messages, which it already delivered locally.
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    origin: Uuid,
+    invalidation: Invalidation,
+    /// Missing from replicas that predate it.
+    #[serde(default)]
+    sent_at: Option<DateTime<Utc>>,
+}
+
+impl Envelope {
+    fn new(origin: Uuid, invalidation: Invalidation) -> Self {
+        Self {
+            origin,
+            invalidation,
+            sent_at: Some(Utc::now()),
+        }
+    }
+
+    /// Hand the invalidation to this replica's subscribers, recording how
+    /// long it took to reach them from the replica that sent it. Nothing is
+    /// recorded when no socket is subscribed, since nothing acted on it.
+    fn deliver(self, local: &broadcast::Sender<Invalidation>) {
+        if local.send(self.invalidation).is_err() {
+            return;
+        }
+        if let Some(sent_at) = self.sent_at {
+            let lag = (Utc::now() - sent_at).to_std().unwrap_or_default();
+            metrics::histogram!(crate::prometheus::INVALIDATION_LAG_SECONDS).record(lag);
+        }
+    }
+}
@@ -0,0 +1,161 @@
+//! Redis pub/sub backend: reaches every replica, but anything published
+//! while a replica is disconnected is lost.
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use redis::AsyncCommands;
+use tokio::sync::{broadcast, mpsc};
+use uuid::Uuid;
+
+use super::{Envelope, InProcessBroker, Invalidation, InvalidationBroker};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+/// Fans invalidations out to every replica over a Redis pub/sub channel.
+///
+/// Local subscribers are notified immediately; Redis only carries them to
+/// the other replicas. If the subscription drops, subscribers get
+/// [`Invalidation::All`] once it's back, since anything published in between
+/// is lost.
+pub struct RedisBroker {
+    local: InProcessBroker,
+    outbound: mpsc::UnboundedSender<Invalidation>,
+}
+
+impl RedisBroker {
+    pub fn connect(url: &str, channel: String) -> Result<Self, redis::RedisError> {
+        let client = redis::Client::open(url)?;
+        let origin = Uuid::new_v4();
+        let local = InProcessBroker::new();
+        let (outbound, outbound_rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(run_publisher(
+            client.clone(),
+            channel.clone(),
+            origin,
+            outbound_rx,
+        ));
+        tokio::spawn(run_subscriber(
+            client,
+            channel,
+            origin,
+            local.sender.clone(),
+        ));
+
+        Ok(Self { local, outbound })
+    }
+}
+
+impl InvalidationBroker for RedisBroker {
+    fn publish(&self, invalidation: Invalidation) {
+        self.local.publish(invalidation);
+        // Only fails once the publisher task is gone, i.e. at shutdown.
+        let _ = self.outbound.send(invalidation);
+    }
+
+    fn subscribe(&self) -> broadcast::Receiver<Invalidation> {
+        self.local.subscribe()
+    }
+}
+
+async fn run_publisher(
+    client: redis::Client,
+    channel: String,
+    origin: Uuid,
+    mut outbound: mpsc::UnboundedReceiver<Invalidation>,
+) {
+    let mut connection = None;
+    while let Some(invalidation) = outbound.recv().await {
+        let payload = match serde_json::to_string(&Envelope::new(origin, invalidation)) {
+            Ok(payload) => payload,
+            Err(error) => {
+                tracing::error!(?error, "failed to encode invalidation");
+                continue;
+            }
+        };
+
+        // A dropped connection is usually only noticed when publishing on
+        // it, so try once more on a fresh one before giving up. Otherwise a
+        // removed member keeps their sockets on other replicas until those
+        // next recheck on their own.
+        for attempt in 0..2 {
+            let conn = match connection.as_mut() {
+                Some(conn) => conn,
+                None => match client.get_multiplexed_async_connection().await {
+                    Ok(conn) => connection.insert(conn),
+                    Err(error) => {
+                        tracing::warn!(
+                            ?error,
+                            ?invalidation,
+                            "invalidation not sent to other replicas"
+                        );
+                        break;
+                    }
+                },
+            };
+            match conn.publish::<_, _, ()>(&channel, &payload).await {
+                Ok(()) => break,
+                Err(error) => {
+                    connection = None;
+                    if attempt > 0 {
+                        tracing::warn!(
+                            ?error,
+                            ?invalidation,
+                            "invalidation not sent to other replicas"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn run_subscriber(
+    client: redis::Client,
+    channel: String,
+    origin: Uuid,
+    local: broadcast::Sender<Invalidation>,
+) {
+    let mut connected_before = false;
+    loop {
+        let mut pubsub = match client.get_async_pubsub().await {
+            Ok(pubsub) => pubsub,
+            Err(error) => {
+                tracing::warn!(?error, "failed to connect to Redis for invalidations");
+                tokio::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+        if let Err(error) = pubsub.subscribe(&channel).await {
+            tracing::warn!(?error, %channel, "failed to subscribe to invalidations");
+            tokio::time::sleep(RECONNECT_DELAY).await;
+            continue;
+        }
+        if connected_before {
+            tracing::info!(%channel, "resubscribed to invalidations; clearing caches");
+            let _ = local.send(Invalidation::All);
+        }
+        connected_before = true;
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            let envelope = message
+                .get_payload::<String>()
+                .map_err(|error| error.to_string())
+                .and_then(|payload| {
+                    serde_json::from_str::<Envelope>(&payload).map_err(|error| error.to_string())
+                });
+            match envelope {
+                Ok(envelope) if envelope.origin == origin => {}
+                Ok(envelope) => {
+                    envelope.deliver(&local);
+                }
+                Err(error) => tracing::warn!(%error, "ignoring malformed invalidation"),
+            }
+        }
+
+        tracing::warn!(%channel, "invalidation subscription dropped; reconnecting");
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
@@ -1,3 +1,4 @@
+mod activity_watermarks;
 mod analytics;
 mod app;
 pub mod attachments;
@@ -8,25 +9,39 @@ mod billing;
 pub mod config;
 pub mod db;
 pub mod digest;
+mod due_reminders;
+mod electric_cache;
+mod export_jobs;
 pub mod github_app;
+pub mod invalidation;
 pub mod mail;
+mod maintenance;
+pub mod mentions;
 mod middleware;
 pub mod mutation_definition;
 pub mod notifications;
+mod prometheus;
 pub mod r2;
+mod retention;
 pub mod routes;
 pub mod shape_definition;
 pub mod shape_route;
 pub mod shape_routes;
 pub mod shapes;
 mod shared_key_auth;
+mod shutdown;
+pub mod signed_urls;
 mod state;
+mod trace_context;
+mod ws;
 
 use std::env;
 
 pub use app::Server;
 pub use billing::BillingService;
+use config::OtlpConfig;
 use opentelemetry::trace::TracerProvider as _;
+use secrecy::ExposeSecret;
 pub use state::AppState;
 use tracing_error::ErrorLayer;
 use tracing_subscriber::{
@@ -37,13 +52,51 @@ use tracing_subscriber::{
 };
 pub use utils::sentry::{SentrySource, init_once as sentry_init_once};
 
-fn init_otel_layer<S>() -> Option<Box<dyn Layer<S> + Send + Sync>>
+fn init_otel_layer<S>(otlp: Option<&OtlpConfig>) -> Option<Box<dyn Layer<S> + Send + Sync>>
 where
     S: tracing::Subscriber
         + for<'span> tracing_subscriber::registry::LookupSpan<'span>
         + Send
         + Sync,
 {
+    let app_insights = app_insights_exporter();
+    let otlp = otlp.and_then(otlp_exporter);
+    if app_insights.is_none() && otlp.is_none() {
+        return None;
+    }
+
+    let service_name =
+        env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| "vibe-kanban-remote".to_string());
+
+    let mut builder = opentelemetry_sdk::trace::SdkTracerProvider::builder().with_resource(
+        opentelemetry_sdk::Resource::builder()
+            .with_service_name(service_name)
+            .build(),
+    );
+    if let Some(exporter) = app_insights {
+        builder = builder.with_batch_exporter(exporter);
+    }
+    if let Some(exporter) = otlp {
+        builder = builder.with_batch_exporter(exporter);
+    }
+    let provider = builder.build();
+
+    // Register globally so the provider outlives this function.
+    // Without this, Drop shuts down the batch exporter and no spans export.
+    opentelemetry::global::set_tracer_provider(provider.clone());
+    // W3C `traceparent`, read from incoming requests and sent on outgoing
+    // ones (see `trace_context`).
+    opentelemetry::global::set_text_map_propagator(
+        opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+    );
+
+    let tracer = provider.tracer("vibe-kanban-remote");
+    let layer = tracing_opentelemetry::OpenTelemetryLayer::new(tracer);
+    Some(layer.boxed())
+}
+
+fn app_insights_exporter()
+-> Option<opentelemetry_application_insights::Exporter<otel_reqwest::blocking::Client>> {
     let connection_string = env::var("APPLICATIONINSIGHTS_CONNECTION_STRING").ok()?;
     if connection_string.is_empty() {
         return None;
@@ -55,34 +108,40 @@ where
         .join()
         .ok()?;
 
-    let exporter = opentelemetry_application_insights::Exporter::new_from_connection_string(
+    opentelemetry_application_insights::Exporter::new_from_connection_string(
         &connection_string,
         http_client,
     )
-    .ok()?;
-
-    let service_name =
-        env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| "vibe-kanban-remote".to_string());
-
-    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
-        .with_resource(
-            opentelemetry_sdk::Resource::builder()
-                .with_service_name(service_name)
-                .build(),
-        )
-        .with_batch_exporter(exporter)
-        .build();
+    .ok()
+}
 
-    // Register globally so the provider outlives this function.
-    // Without this, Drop shuts down the batch exporter and no spans export.
-    opentelemetry::global::set_tracer_provider(provider.clone());
+fn otlp_exporter(config: &OtlpConfig) -> Option<opentelemetry_otlp::SpanExporter> {
+    use opentelemetry_otlp::{WithExportConfig, WithHttpConfig};
 
-    let tracer = provider.tracer("vibe-kanban-remote");
-    let layer = tracing_opentelemetry::OpenTelemetryLayer::new(tracer);
-    Some(layer.boxed())
+    let headers = config
+        .headers
+        .iter()
+        .map(|(name, value)| (name.clone(), value.expose_secret().to_string()))
+        .collect();
+    let endpoint = config.traces_endpoint.clone();
+    // The default HTTP client is reqwest's blocking one, which panics if
+    // built on a runtime thread; build it on its own thread, as above.
+    std::thread::spawn(move || {
+        opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(endpoint)
+            .with_headers(headers)
+            .build()
+            .ok()
+    })
+    .join()
+    .ok()
+    .flatten()
 }
 
-pub fn init_tracing() {
+/// Set up logging, plus trace export to Application Insights and/or `otlp`
+/// when configured.
+pub fn init_tracing(otlp: Option<&OtlpConfig>) {
     if tracing::dispatcher::has_been_set() {
         return;
     }
@@ -94,11 +153,18 @@ pub fn init_tracing() {
         .with_span_events(FmtSpan::CLOSE)
         .boxed();
 
-    let otel_layer = init_otel_layer();
+    let otlp_enabled = otlp.is_some();
+    let otel_layer = init_otel_layer(otlp);
     let otel_enabled = otel_layer.is_some();
 
+    // Reloadable so `RUST_LOG_FILE` can change it on SIGHUP (see
+    // `spawn_log_filter_reloader`). Invalid directives fall back to `info`.
+    let filter_layer = utils::log_filter::reloadable(&env_filter)
+        .or_else(|_| utils::log_filter::reloadable("info"))
+        .expect("static filter is valid");
+
     tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(env_filter))
+        .with(filter_layer)
         .with(ErrorLayer::default())
         .with(fmt_layer)
         .with(otel_layer)
@@ -109,13 +175,55 @@ pub fn init_tracing() {
         otel_enabled,
         "Tracing initialized ({})",
         if otel_enabled {
-            "stdout + Application Insights"
+            "stdout + OpenTelemetry"
         } else {
             "stdout only"
         }
     );
+    if otlp_enabled && !otel_enabled {
+        tracing::warn!("OTLP trace export is configured but its exporter could not be created");
+    }
+}
+
+/// On SIGHUP, re-read filter directives from the file named by
+/// `RUST_LOG_FILE` and apply them without restarting. An empty or missing
+/// file restores the startup filter.
+#[cfg(unix)]
+pub fn spawn_log_filter_reloader() {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let Some(path) = env::var_os("RUST_LOG_FILE") else {
+        return;
+    };
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(error) => {
+            tracing::warn!(?error, "failed to install SIGHUP handler for log filter");
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        while hangups.recv().await.is_some() {
+            let directives = tokio::fs::read_to_string(&path)
+                .await
+                .map(|contents| contents.trim().to_string())
+                .unwrap_or_default();
+            let result = if directives.is_empty() {
+                utils::log_filter::reset()
+            } else {
+                utils::log_filter::set(&directives)
+            };
+            if let Err(error) = result {
+                tracing::warn!(%error, "failed to reload log filter");
+            }
+        }
+    });
 }
 
+#[cfg(not(unix))]
+pub fn spawn_log_filter_reloader() {}
+
 pub fn configure_user_scope(user_id: uuid::Uuid, username: Option<&str>, email: Option<&str>) {
     utils::sentry::configure_user_scope(&user_id.to_string(), username, email);
 }
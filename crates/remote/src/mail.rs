@@ -159,6 +159,7 @@ impl Mailer for LoopsMailer {
         let role_str = match role {
             MemberRole::Admin => "admin",
             MemberRole::Member => "member",
+            MemberRole::Viewer => "viewer",
         };
         let inviter = invited_by.unwrap_or("someone");
 
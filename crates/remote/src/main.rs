@@ -1,6 +1,7 @@
 use remote::{
-    BillingService, SentrySource, Server, config::RemoteServerConfig, init_tracing,
-    sentry_init_once,
+    BillingService, SentrySource, Server,
+    config::{OtlpConfig, RemoteServerConfig},
+    init_tracing, sentry_init_once,
 };
 
 #[tokio::main]
@@ -11,8 +12,9 @@ async fn main() -> anyhow::Result<()> {
         .expect("Failed to install rustls crypto provider");
 
     sentry_init_once(SentrySource::Remote);
-    init_tracing();
 
+    // Before the rest of the config, which logs what it picked.
+    init_tracing(OtlpConfig::from_env()?.as_ref());
     let config = RemoteServerConfig::from_env()?;
 
     #[cfg(feature = "vk-billing")]
@@ -0,0 +1,210 @@
+//! Scheduled database maintenance.
+//!
+//! Autovacuum keeps up with dead tuples, but after months of churn planner
+//! statistics on the hot issue tables drift and the append-only activity log's
+//! indexes bloat. This task runs `ANALYZE` on those tables and rebuilds the
+//! activity indexes once a day, logging per-step timings so regressions show
+//! up in traces rather than as slow boards.
+
+use std::{
+    panic::AssertUnwindSafe,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Days, Timelike, Utc};
+use futures::FutureExt;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tracing::{error, info, instrument, warn};
+
+use crate::db::{
+    download_nonces::DownloadNonceRepository,
+    idempotency_keys::IdempotencyKeyRepository,
+    maintenance::{ANALYZE_TABLES, MaintenanceRepository, MaintenanceRunLock, REINDEX_TABLES},
+};
+
+const DEFAULT_RUN_HOUR_UTC: u32 = 3;
+
+#[derive(Debug, Default)]
+struct MaintenanceStats {
+    analyzed: usize,
+    reindexed: usize,
+    failures: usize,
+}
+
+pub(crate) fn spawn_maintenance_task(pool: PgPool) -> JoinHandle<()> {
+    let interval_override = std::env::var("DB_MAINTENANCE_INTERVAL_SECS_OVERRIDE")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(Duration::from_secs);
+    let run_hour_utc = std::env::var("DB_MAINTENANCE_RUN_HOUR_UTC")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok())
+        .filter(|hour| *hour < 24)
+        .unwrap_or(DEFAULT_RUN_HOUR_UTC);
+    let reindex_enabled = std::env::var("DB_MAINTENANCE_REINDEX")
+        .map(|v| !matches!(v.as_str(), "false" | "0"))
+        .unwrap_or(true);
+
+    match interval_override {
+        Some(interval) => info!(
+            interval_secs = interval.as_secs(),
+            reindex_enabled, "Starting database maintenance task with interval override"
+        ),
+        None => info!(
+            run_hour_utc,
+            reindex_enabled, "Starting database maintenance task"
+        ),
+    }
+
+    tokio::spawn(async move {
+        let result = AssertUnwindSafe(maintenance_loop(
+            &pool,
+            interval_override,
+            run_hour_utc,
+            reindex_enabled,
+        ));
+
+        if let Err(panic) = result.catch_unwind().await {
+            let msg = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            error!(panic = %msg, "Database maintenance task died — maintenance will not run until next deploy");
+        }
+    })
+}
+
+async fn maintenance_loop(
+    pool: &PgPool,
+    interval_override: Option<Duration>,
+    run_hour_utc: u32,
+    reindex_enabled: bool,
+) {
+    loop {
+        if let Some(interval) = interval_override {
+            tokio::time::sleep(interval).await;
+        } else {
+            let now = Utc::now();
+            let next_run = next_run_at(now, run_hour_utc);
+            let sleep_duration = (next_run - now)
+                .to_std()
+                .unwrap_or_else(|_| Duration::from_secs(0));
+
+            info!(next_run = %next_run, sleep_secs = sleep_duration.as_secs(), "Next database maintenance scheduled");
+            tokio::time::sleep(sleep_duration).await;
+        }
+
+        let Some(lock) = acquire_run_lock(pool).await else {
+            continue;
+        };
+
+        run_maintenance(pool, reindex_enabled).await;
+
+        if let Err(error) = lock.release().await {
+            warn!(error = %error, "Failed to release database maintenance lock");
+        }
+    }
+}
+
+#[instrument(name = "db_maintenance.run", skip(pool))]
+async fn run_maintenance(pool: &PgPool, reindex_enabled: bool) {
+    let started = Instant::now();
+    let mut stats = MaintenanceStats::default();
+
+    for table in ANALYZE_TABLES {
+        let step = Instant::now();
+        match MaintenanceRepository::analyze(pool, table).await {
+            Ok(()) => {
+                stats.analyzed += 1;
+                info!(
+                    table,
+                    duration_ms = step.elapsed().as_millis() as u64,
+                    "Analyzed table"
+                );
+            }
+            Err(error) => {
+                stats.failures += 1;
+                warn!(table, error = %error, "Failed to analyze table");
+            }
+        }
+    }
+
+    if reindex_enabled {
+        for table in REINDEX_TABLES {
+            let step = Instant::now();
+            match MaintenanceRepository::reindex_concurrently(pool, table).await {
+                Ok(()) => {
+                    stats.reindexed += 1;
+                    info!(
+                        table,
+                        duration_ms = step.elapsed().as_millis() as u64,
+                        "Reindexed table"
+                    );
+                }
+                Err(error) => {
+                    stats.failures += 1;
+                    warn!(table, error = %error, "Failed to reindex table");
+                }
+            }
+        }
+    }
+
+    match IdempotencyKeyRepository::delete_expired(pool).await {
+        Ok(deleted) => info!(deleted, "Pruned expired idempotency keys"),
+        Err(error) => {
+            stats.failures += 1;
+            warn!(error = %error, "Failed to prune expired idempotency keys");
+        }
+    }
+
+    match DownloadNonceRepository::delete_expired(pool).await {
+        Ok(deleted) => info!(deleted, "Pruned expired download nonces"),
+        Err(error) => {
+            stats.failures += 1;
+            warn!(error = %error, "Failed to prune expired download nonces");
+        }
+    }
+
+    info!(
+        analyzed = stats.analyzed,
+        reindexed = stats.reindexed,
+        failures = stats.failures,
+        duration_ms = started.elapsed().as_millis() as u64,
+        "Database maintenance cycle complete"
+    );
+}
+
+async fn acquire_run_lock(pool: &PgPool) -> Option<MaintenanceRunLock> {
+    match MaintenanceRepository::try_acquire_run_lock(pool).await {
+        Ok(Some(lock)) => Some(lock),
+        Ok(None) => {
+            info!("Skipping database maintenance cycle because another instance is running it");
+            None
+        }
+        Err(error) => {
+            error!(error = %error, "Failed to acquire database maintenance lock");
+            None
+        }
+    }
+}
+
+fn next_run_at(now: DateTime<Utc>, run_hour_utc: u32) -> DateTime<Utc> {
+    let today = now.date_naive();
+    let today_run = today
+        .and_hms_opt(run_hour_utc, 0, 0)
+        .expect("validated maintenance hour");
+
+    let next_naive = if now.hour() < run_hour_utc {
+        today_run
+    } else {
+        today
+            .checked_add_days(Days::new(1))
+            .expect("date overflow for maintenance schedule")
+            .and_hms_opt(run_hour_utc, 0, 0)
+            .expect("validated maintenance hour")
+    };
+
+    DateTime::from_naive_utc_and_offset(next_naive, Utc)
+}
@@ -0,0 +1,98 @@
+//! `@username` mentions in issue descriptions and comments.
+
+use api_types::{Issue, NotificationPayload, NotificationType};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    db::{issue_mentions::IssueMentionRepository, tenant::TenantId},
+    notifications::send_issue_notifications,
+};
+
+/// Record the mentions in `text` and notify users mentioned for the first
+/// time. `comment_id` is the comment `text` came from, or `None` for the
+/// issue description.
+pub async fn notify_mentions(
+    pool: &PgPool,
+    tenant: TenantId,
+    actor_user_id: Uuid,
+    issue: &Issue,
+    text: &str,
+    comment_id: Option<Uuid>,
+) {
+    let usernames = parse_mentions(text);
+    if usernames.is_empty() {
+        return;
+    }
+
+    let recipients = match IssueMentionRepository::record(
+        pool,
+        tenant,
+        issue.id,
+        comment_id,
+        actor_user_id,
+        &usernames,
+    )
+    .await
+    {
+        Ok(recipients) => recipients,
+        Err(error) => {
+            tracing::warn!(?error, issue_id = %issue.id, "failed to record mentions");
+            return;
+        }
+    };
+
+    send_issue_notifications(
+        pool,
+        tenant,
+        actor_user_id,
+        &recipients,
+        issue,
+        NotificationType::IssueMentioned,
+        NotificationPayload {
+            comment_preview: Some(text.chars().take(100).collect()),
+            ..Default::default()
+        },
+        comment_id,
+        Some(issue.id),
+    )
+    .await;
+}
+
+/// Distinct lowercase usernames mentioned in `text`. A mention is `@`
+/// at the start of a word followed by letters, digits, `_`, `-` or `.`, so
+/// email addresses don't count.
+fn parse_mentions(text: &str) -> Vec<String> {
+    let mut usernames = Vec::new();
+    let mut previous = None;
+    for (index, c) in text.char_indices() {
+        let at_word_start = previous.is_none_or(|p: char| !p.is_alphanumeric() && p != '_');
+        previous = Some(c);
+        if c != '@' || !at_word_start {
+            continue;
+        }
+        let username: String = text[index + 1..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+            .collect();
+        let username = username.trim_end_matches(['.', '-']).to_ascii_lowercase();
+        if !username.is_empty() && !usernames.contains(&username) {
+            usernames.push(username);
+        }
+    }
+    usernames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_mentions;
+
+    #[test]
+    fn finds_distinct_mentions_and_ignores_emails() {
+        assert_eq!(
+            parse_mentions("@Alice can you pair with @bob.smith? cc @alice, not me@example.com."),
+            vec!["alice".to_string(), "bob.smith".to_string()]
+        );
+        assert!(parse_mentions("no mentions @ all").is_empty());
+    }
+}
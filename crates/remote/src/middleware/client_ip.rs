@@ -0,0 +1,154 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{Extensions, HeaderMap, request::Parts},
+};
+use ipnetwork::IpNetwork;
+
+use crate::AppState;
+
+/// Client IP of a request. Forwarding headers are only believed when the
+/// connecting peer is one of `trusted_proxies`, since anyone else can set
+/// them to any address; otherwise the peer itself is the client.
+pub(crate) fn client_ip(
+    peer: Option<IpAddr>,
+    headers: &HeaderMap,
+    trusted_proxies: &[IpNetwork],
+) -> Option<IpAddr> {
+    let peer = peer?;
+    let is_trusted = |ip: IpAddr| trusted_proxies.iter().any(|proxy| proxy.contains(ip));
+    if !is_trusted(peer) {
+        return Some(peer);
+    }
+
+    // Set by Cloudflare (production), which overwrites any client value
+    if let Some(ip) = header_ip(headers, "CF-Connecting-IP") {
+        return Some(ip);
+    }
+
+    // Each proxy appends the address it received the request from, so walk
+    // back from the nearest hop until one isn't a proxy we trust. Hops
+    // further left than that were supplied by the client.
+    if let Some(forwarded) = headers.get("X-Forwarded-For").and_then(|v| v.to_str().ok()) {
+        let mut client = None;
+        for hop in forwarded.rsplit(',') {
+            let Ok(ip) = hop.trim().parse::<IpAddr>() else {
+                break;
+            };
+            client = Some(ip);
+            if !is_trusted(ip) {
+                break;
+            }
+        }
+        if client.is_some() {
+            return client;
+        }
+    }
+
+    header_ip(headers, "X-Real-IP").or(Some(peer))
+}
+
+/// Client IP for a request whose connection info was recorded by the server.
+pub(crate) fn client_ip_for_request(
+    extensions: &Extensions,
+    headers: &HeaderMap,
+    trusted_proxies: &[IpNetwork],
+) -> Option<IpAddr> {
+    let peer = extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    client_ip(peer, headers, trusted_proxies)
+}
+
+fn header_ip(headers: &HeaderMap, name: &str) -> Option<IpAddr> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.trim().parse().ok())
+}
+
+/// Extracts the request's client IP, see [`client_ip`].
+pub(crate) struct ClientIp(pub Option<IpAddr>);
+
+impl FromRequestParts<AppState> for ClientIp {
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self(client_ip_for_request(
+            &parts.extensions,
+            &parts.headers,
+            &state.config().trusted_proxies,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use axum::http::HeaderMap;
+    use ipnetwork::IpNetwork;
+
+    use super::client_ip;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn ignores_forwarding_headers_from_untrusted_peers() {
+        let proxies: Vec<IpNetwork> = vec!["10.0.0.0/8".parse().unwrap()];
+        let spoofed = headers(&[
+            ("CF-Connecting-IP", "192.0.2.1"),
+            ("X-Forwarded-For", "192.0.2.1"),
+            ("X-Real-IP", "192.0.2.1"),
+        ]);
+
+        assert_eq!(
+            client_ip(Some(ip("203.0.113.9")), &spoofed, &proxies),
+            Some(ip("203.0.113.9"))
+        );
+        assert_eq!(
+            client_ip(Some(ip("203.0.113.9")), &spoofed, &[]),
+            Some(ip("203.0.113.9"))
+        );
+    }
+
+    #[test]
+    fn reads_forwarding_headers_from_trusted_proxies() {
+        let proxies: Vec<IpNetwork> = vec!["10.0.0.0/8".parse().unwrap()];
+        let peer = Some(ip("10.0.0.2"));
+
+        assert_eq!(
+            client_ip(
+                peer,
+                &headers(&[("CF-Connecting-IP", "198.51.100.7")]),
+                &proxies
+            ),
+            Some(ip("198.51.100.7"))
+        );
+        // The client prepended a fake hop; the first untrusted hop from the
+        // right is the address our proxy actually saw.
+        assert_eq!(
+            client_ip(
+                peer,
+                &headers(&[("X-Forwarded-For", "192.0.2.1, 198.51.100.7, 10.0.0.3")]),
+                &proxies
+            ),
+            Some(ip("198.51.100.7"))
+        );
+        assert_eq!(client_ip(peer, &HeaderMap::new(), &proxies), peer);
+    }
+}
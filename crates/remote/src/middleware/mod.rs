@@ -1 +1,2 @@
+pub(crate) mod client_ip;
 pub(crate) mod version;
@@ -6,21 +6,19 @@ use uuid::Uuid;
 
 use crate::db::{
     issue_assignees::IssueAssigneeRepository, issue_followers::IssueFollowerRepository,
-    notifications::NotificationRepository, organization_members::is_member,
+    notifications::NotificationRepository, organization_members::is_member, tenant::TenantId,
 };
 
 pub async fn notify_issue_subscribers(
     pool: &PgPool,
-    organization_id: Uuid,
+    tenant: TenantId,
     actor_user_id: Uuid,
     issue: &Issue,
     notification_type: NotificationType,
     extra_payload: NotificationPayload,
     comment_id: Option<Uuid>,
 ) {
-    let recipients = match collect_issue_recipients(pool, organization_id, issue.id, actor_user_id)
-        .await
-    {
+    let recipients = match collect_issue_recipients(pool, tenant, issue.id, actor_user_id).await {
         Ok(r) => r,
         Err(e) => {
             tracing::warn!(?e, issue_id = %issue.id, "failed to collect notification recipients");
@@ -30,7 +28,7 @@ pub async fn notify_issue_subscribers(
 
     send_issue_notifications(
         pool,
-        organization_id,
+        tenant,
         actor_user_id,
         &recipients,
         issue,
@@ -48,7 +46,7 @@ pub async fn notify_issue_subscribers(
 #[allow(clippy::too_many_arguments)]
 pub async fn send_issue_notifications(
     pool: &PgPool,
-    organization_id: Uuid,
+    tenant: TenantId,
     actor_user_id: Uuid,
     recipients: &[Uuid],
     issue: &Issue,
@@ -66,7 +64,7 @@ pub async fn send_issue_notifications(
     for &recipient_id in recipients {
         if let Err(e) = NotificationRepository::create(
             pool,
-            organization_id,
+            tenant,
             recipient_id,
             notification_type,
             payload.clone(),
@@ -83,7 +81,7 @@ pub async fn send_issue_notifications(
 #[allow(clippy::too_many_arguments)]
 pub async fn send_debounced_issue_notifications(
     pool: &PgPool,
-    organization_id: Uuid,
+    tenant: TenantId,
     actor_user_id: Uuid,
     recipients: &[Uuid],
     issue: &Issue,
@@ -101,7 +99,7 @@ pub async fn send_debounced_issue_notifications(
     for &recipient_id in recipients {
         if let Err(e) = NotificationRepository::upsert_recent(
             pool,
-            organization_id,
+            tenant,
             recipient_id,
             notification_type,
             payload.clone(),
@@ -117,14 +115,14 @@ pub async fn send_debounced_issue_notifications(
 
 pub async fn notify_user(
     pool: &PgPool,
-    organization_id: Uuid,
+    tenant: TenantId,
     actor_user_id: Uuid,
     recipient_user_id: Uuid,
     issue: &Issue,
     notification_type: NotificationType,
     extra_payload: NotificationPayload,
 ) {
-    if !is_member(pool, organization_id, recipient_user_id)
+    if !is_member(pool, tenant.id(), recipient_user_id)
         .await
         .unwrap_or(false)
     {
@@ -133,7 +131,7 @@ pub async fn notify_user(
 
     send_issue_notifications(
         pool,
-        organization_id,
+        tenant,
         actor_user_id,
         &[recipient_user_id],
         issue,
@@ -147,7 +145,7 @@ pub async fn notify_user(
 
 pub async fn collect_issue_recipients(
     pool: &PgPool,
-    organization_id: Uuid,
+    tenant: TenantId,
     issue_id: Uuid,
     exclude_user_id: Uuid,
 ) -> Result<Vec<Uuid>, Box<dyn std::error::Error + Send + Sync>> {
@@ -160,10 +158,7 @@ pub async fn collect_issue_recipients(
 
     let mut recipients = Vec::with_capacity(user_ids.len());
     for user_id in user_ids {
-        if is_member(pool, organization_id, user_id)
-            .await
-            .unwrap_or(false)
-        {
+        if is_member(pool, tenant.id(), user_id).await.unwrap_or(false) {
             recipients.push(user_id);
         }
     }
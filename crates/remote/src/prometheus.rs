@@ -0,0 +1,178 @@
+//! Prometheus metrics, served at `/metrics` when [`MetricsConfig`] enables
+//! them. Until the recorder is installed the `metrics` macros used across
+//! the crate record nothing, so call sites don't check whether it is.
+
+use std::{net::SocketAddr, time::Duration};
+
+use anyhow::Context as _;
+use axum::{
+    Router,
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{Request, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use axum_extra::headers::{Authorization, HeaderMapExt, authorization::Bearer};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use secrecy::{ExposeSecret, SecretString};
+use sqlx::PgPool;
+use subtle::ConstantTimeEq;
+use tokio::time::Instant;
+
+use crate::config::MetricsConfig;
+
+pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+/// Live Electric shape requests waiting on the upstream, i.e. clients
+/// currently subscribed to changes.
+pub const ELECTRIC_LIVE_REQUESTS: &str = "electric_live_requests";
+/// Non-live Electric shape requests, by whether they were answered from the
+/// cache.
+pub const ELECTRIC_CACHE_REQUESTS_TOTAL: &str = "electric_cache_requests_total";
+/// Time from an invalidation being published on one replica to the
+/// organization WebSockets subscribed on another receiving it, e.g. how long
+/// a removed member's sockets elsewhere stay open.
+pub const INVALIDATION_LAG_SECONDS: &str = "invalidation_broker_lag_seconds";
+pub const ACTIVITY_FEED_EVENTS_TOTAL: &str = "activity_feed_events_total";
+/// Activity feed pages that were full, i.e. a client still catching up.
+pub const ACTIVITY_CATCH_UP_PAGES_TOTAL: &str = "activity_catch_up_pages_total";
+/// Messages queued for WebSocket clients across all connections.
+pub const WS_OUTBOUND_QUEUE_DEPTH: &str = "ws_outbound_queue_depth";
+/// Times a WebSocket client fell behind and had its queued changes
+/// replaced by a snapshot.
+pub const WS_OUTBOUND_OVERFLOWS_TOTAL: &str = "ws_outbound_overflows_total";
+pub const DB_POOL_CONNECTIONS: &str = "db_pool_connections";
+pub const DB_POOL_MAX_CONNECTIONS: &str = "db_pool_max_connections";
+
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+const POOL_SAMPLE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Install the global recorder, start sampling `pool`, and return the routes
+/// serving `/metrics` for the main listener, if it's the one serving them.
+pub async fn start(config: &MetricsConfig, pool: PgPool) -> anyhow::Result<Option<Router>> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), LATENCY_BUCKETS)
+        .context("invalid metric buckets")?
+        .install_recorder()
+        .context("failed to install metrics recorder")?;
+
+    let upkeep = handle.clone();
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(UPKEEP_INTERVAL).await;
+            upkeep.run_upkeep();
+        }
+    });
+    spawn_pool_sampler(pool);
+
+    let routes = Router::new()
+        .route("/metrics", get(render))
+        .with_state(handle);
+
+    if let Some(listen_addr) = &config.listen_addr {
+        let addr: SocketAddr = listen_addr
+            .parse()
+            .context("metrics listen address is invalid")?;
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .context("failed to bind metrics listener")?;
+        tracing::info!(%addr, "metrics listening");
+        tokio::spawn(async move {
+            if let Err(error) = axum::serve(listener, routes).await {
+                tracing::error!(?error, "metrics server failure");
+            }
+        });
+        return Ok(None);
+    }
+
+    Ok(config
+        .token
+        .clone()
+        .map(|token| routes.layer(middleware::from_fn_with_state(token, require_token))))
+}
+
+async fn render(State(handle): State<PrometheusHandle>) -> String {
+    handle.render()
+}
+
+async fn require_token(
+    State(token): State<SecretString>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let authorized = request
+        .headers()
+        .typed_get::<Authorization<Bearer>>()
+        .is_some_and(|bearer| {
+            bool::from(
+                bearer
+                    .token()
+                    .as_bytes()
+                    .ct_eq(token.expose_secret().as_bytes()),
+            )
+        });
+    if !authorized {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    next.run(request).await
+}
+
+/// Count and time every request by route template, so IDs in paths don't
+/// multiply the series.
+pub(crate) async fn track_requests(request: Request<Body>, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| "unmatched".to_string(), |path| path.as_str().to_string());
+    let started = Instant::now();
+
+    let response = next.run(request).await;
+
+    let status = response.status().as_u16().to_string();
+    metrics::counter!(
+        HTTP_REQUESTS_TOTAL,
+        "method" => method.clone(),
+        "route" => route.clone(),
+        "status" => status
+    )
+    .increment(1);
+    metrics::histogram!(HTTP_REQUEST_DURATION_SECONDS, "method" => method, "route" => route)
+        .record(started.elapsed());
+    response
+}
+
+/// Holds a gauge one higher until dropped.
+pub(crate) struct GaugeGuard(&'static str);
+
+impl GaugeGuard {
+    pub(crate) fn increment(name: &'static str) -> Self {
+        metrics::gauge!(name).increment(1);
+        Self(name)
+    }
+}
+
+impl Drop for GaugeGuard {
+    fn drop(&mut self) {
+        metrics::gauge!(self.0).decrement(1);
+    }
+}
+
+fn spawn_pool_sampler(pool: PgPool) {
+    tokio::spawn(async move {
+        metrics::gauge!(DB_POOL_MAX_CONNECTIONS).set(pool.options().get_max_connections());
+        loop {
+            let size = pool.size();
+            let idle = pool.num_idle() as u32;
+            metrics::gauge!(DB_POOL_CONNECTIONS, "state" => "idle").set(idle);
+            metrics::gauge!(DB_POOL_CONNECTIONS, "state" => "in_use")
+                .set(size.saturating_sub(idle));
+            tokio::time::sleep(POOL_SAMPLE_INTERVAL).await;
+        }
+    });
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tracing::{info, instrument, warn};
+
+use crate::db::{
+    begin_trusted_tx,
+    issue_activity::{IssueActivityError, IssueActivityRepository},
+    organization_retention_policies::OrganizationRetentionPolicyRepository,
+};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Spawns a background task that periodically applies organization retention
+/// policies and, when `tombstone_retention_days` is set, purges the history of
+/// issues deleted longer ago than that. Call once during server startup.
+pub(crate) fn spawn_retention_task(
+    pool: PgPool,
+    tombstone_retention_days: Option<i64>,
+) -> JoinHandle<()> {
+    let interval = std::env::var("RETENTION_SWEEP_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_INTERVAL);
+
+    info!(
+        interval_secs = interval.as_secs(),
+        ?tombstone_retention_days,
+        "Starting retention background task"
+    );
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        // Skip the immediate first tick so the server can finish starting up.
+        ticker.tick().await;
+
+        loop {
+            ticker.tick().await;
+            run_sweep(&pool, tombstone_retention_days).await;
+        }
+    })
+}
+
+#[instrument(name = "retention.sweep", skip_all)]
+async fn run_sweep(pool: &PgPool, tombstone_retention_days: Option<i64>) {
+    match purge_expired_workspace_summaries(pool).await {
+        Ok(count) => info!(
+            cleared = count,
+            "Workspace summary retention sweep complete"
+        ),
+        Err(e) => warn!(error = %e, "Workspace summary retention sweep failed"),
+    }
+
+    let Some(days) = tombstone_retention_days else {
+        return;
+    };
+    let cutoff = Utc::now() - chrono::Duration::days(days);
+    match purge_deleted_issue_history(pool, cutoff).await {
+        Ok(count) => info!(
+            purged = count,
+            "Deleted issue history retention sweep complete"
+        ),
+        Err(e) => warn!(error = %e, "Deleted issue history retention sweep failed"),
+    }
+}
+
+// The sweeps cover every organization, so they run as the row-level security
+// bypass role.
+
+async fn purge_expired_workspace_summaries(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let mut tx = begin_trusted_tx(pool).await?;
+    let count =
+        OrganizationRetentionPolicyRepository::purge_expired_workspace_summaries(&mut *tx).await?;
+    tx.commit().await?;
+    Ok(count)
+}
+
+async fn purge_deleted_issue_history(
+    pool: &PgPool,
+    cutoff: DateTime<Utc>,
+) -> Result<u64, IssueActivityError> {
+    let mut tx = begin_trusted_tx(pool).await?;
+    let count = IssueActivityRepository::purge_deleted_issue_history(&mut *tx, cutoff).await?;
+    tx.commit().await?;
+    Ok(count)
+}
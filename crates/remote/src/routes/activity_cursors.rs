@@ -0,0 +1,118 @@
+use api_types::{AckActivityRequest, ActivityCursor, GetActivityCursorQuery};
+use axum::{
+    Json, Router,
+    extract::{Extension, Query, State},
+    http::StatusCode,
+    routing::get,
+};
+use tracing::instrument;
+
+use super::{error::ErrorResponse, organization_members::ensure_project_access};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{activity_cursors::ActivityCursorRepository, issue_activity::IssueActivityRepository},
+};
+
+/// Longer device IDs are rejected; clients pick their own, so this only
+/// bounds what they can make us store.
+const MAX_DEVICE_ID_LEN: usize = 128;
+
+pub(super) fn router() -> Router<AppState> {
+    Router::new().route(
+        "/activity/cursor",
+        get(get_activity_cursor).put(ack_activity),
+    )
+}
+
+pub(super) fn validate_device_id(device_id: &str) -> Result<(), ErrorResponse> {
+    if device_id.is_empty() || device_id.len() > MAX_DEVICE_ID_LEN {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            format!("device_id must be 1 to {MAX_DEVICE_ID_LEN} bytes"),
+        ));
+    }
+    Ok(())
+}
+
+#[instrument(
+    name = "activity_cursors.get",
+    skip(state, ctx),
+    fields(project_id = %query.project_id, user_id = %ctx.user.id)
+)]
+async fn get_activity_cursor(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<GetActivityCursorQuery>,
+) -> Result<Json<ActivityCursor>, ErrorResponse> {
+    validate_device_id(&query.device_id)?;
+    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+
+    ActivityCursorRepository::find(
+        state.pool(),
+        ctx.user.id,
+        &query.device_id,
+        query.project_id,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to load activity cursor");
+        ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to load activity cursor",
+        )
+    })?
+    .map(Json)
+    .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "no cursor stored for this device"))
+}
+
+/// Store how far the device has processed the project's activity feed, up
+/// to the commit-safe watermark.
+#[instrument(
+    name = "activity_cursors.ack",
+    skip(state, ctx, payload),
+    fields(project_id = %payload.project_id, seq = payload.seq, user_id = %ctx.user.id)
+)]
+async fn ack_activity(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<AckActivityRequest>,
+) -> Result<Json<ActivityCursor>, ErrorResponse> {
+    validate_device_id(&payload.device_id)?;
+    if payload.seq < 0 {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "seq must not be negative",
+        ));
+    }
+    ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
+
+    // A seq above the commit-safe watermark could still gain a lower
+    // neighbour that commits later; storing it would skip that event.
+    let safe_seq = IssueActivityRepository::safe_seq(state.pool())
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to load activity watermark");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to store activity cursor",
+            )
+        })?;
+    let cursor = ActivityCursorRepository::ack(
+        state.pool(),
+        ctx.user.id,
+        &payload.device_id,
+        payload.project_id,
+        payload.seq.min(safe_seq),
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to store activity cursor");
+        ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to store activity cursor",
+        )
+    })?;
+
+    Ok(Json(cursor))
+}
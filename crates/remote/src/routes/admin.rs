@@ -0,0 +1,224 @@
+//! Operator-only endpoints. Operators are the users whose email is listed in
+//! `OPERATOR_EMAILS`; they don't need to be members of the organizations
+//! they inspect.
+
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{error::ErrorResponse, organization_members::tenant_tx};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        identity_errors::IdentityError,
+        issue_activity::{IssueActivityRepository, ProjectActivityRange},
+        organization_health::{OrganizationHealth, OrganizationHealthRepository},
+        organizations::OrganizationRepository,
+        tenant::{TenantId, TenantSource},
+    },
+    ws::WsSession,
+};
+
+pub(super) fn router() -> Router<AppState> {
+    Router::new()
+        .route("/admin/orgs/{org_id}/health", get(get_organization_health))
+        .route("/admin/activity/replay", post(replay_activity))
+        .route("/admin/ws/sessions", get(list_ws_sessions))
+        .route(
+            "/admin/ws/sessions/{session_id}",
+            delete(disconnect_ws_session),
+        )
+}
+
+fn ensure_operator(state: &AppState, ctx: &RequestContext) -> Result<(), ErrorResponse> {
+    let is_operator = state
+        .config()
+        .operator_emails
+        .iter()
+        .any(|email| email.eq_ignore_ascii_case(&ctx.user.email));
+    if !is_operator {
+        tracing::warn!(user_id = %ctx.user.id, "non-operator requested an admin endpoint");
+        return Err(ErrorResponse::new(
+            StatusCode::FORBIDDEN,
+            "operator access required",
+        ));
+    }
+    Ok(())
+}
+
+async fn find_organization(state: &AppState, org_id: Uuid) -> Result<Uuid, ErrorResponse> {
+    let organization = OrganizationRepository::new(state.pool())
+        .fetch_organization(org_id)
+        .await
+        .map_err(|error| match error {
+            IdentityError::NotFound => {
+                ErrorResponse::new(StatusCode::NOT_FOUND, "organization not found")
+            }
+            error => {
+                tracing::error!(?error, "failed to load organization");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        })?;
+    Ok(organization.id)
+}
+
+#[instrument(
+    name = "admin.organization_health",
+    skip(state, ctx),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn get_organization_health(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<OrganizationHealth>, ErrorResponse> {
+    ensure_operator(&state, &ctx)?;
+
+    let organization_id = find_organization(&state, org_id).await?;
+
+    let health = OrganizationHealthRepository::summarize(
+        state.pool(),
+        TenantId::trusted(organization_id, TenantSource::OperatorAccess),
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to summarize organization health");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    Ok(Json(health))
+}
+
+#[derive(Debug, Deserialize)]
+struct ReplayActivityRequest {
+    organization_id: Uuid,
+    /// First activity `seq` to replay, inclusive.
+    from_seq: i64,
+    /// Last activity `seq` to replay, inclusive.
+    to_seq: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct ReplayActivityResponse {
+    /// Every project that had events in the range; a replay was recorded
+    /// for each.
+    projects: Vec<ProjectActivityRange>,
+    events: i64,
+}
+
+/// Have clients fetch an organization's stored activity in a `seq` range
+/// again, to recover ones whose cursor state was corrupted. A replay is
+/// recorded per project, which clients find on their next activity poll;
+/// the events themselves keep their original `seq`.
+#[instrument(
+    name = "admin.replay_activity",
+    skip(state, ctx, payload),
+    fields(
+        org_id = %payload.organization_id,
+        from_seq = payload.from_seq,
+        to_seq = payload.to_seq,
+        user_id = %ctx.user.id
+    )
+)]
+async fn replay_activity(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<ReplayActivityRequest>,
+) -> Result<Json<ReplayActivityResponse>, ErrorResponse> {
+    ensure_operator(&state, &ctx)?;
+
+    if payload.from_seq < 0 || payload.from_seq > payload.to_seq {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "`from_seq` must be non-negative and at most `to_seq`",
+        ));
+    }
+    let organization_id = find_organization(&state, payload.organization_id).await?;
+
+    let mut tx = tenant_tx(
+        state.pool(),
+        TenantId::trusted(organization_id, TenantSource::OperatorAccess),
+    )
+    .await?;
+    let projects = IssueActivityRepository::ranges_for_organization(
+        &mut *tx,
+        organization_id,
+        payload.from_seq,
+        payload.to_seq,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to load activity ranges");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+    for range in &projects {
+        IssueActivityRepository::record_replay(&mut *tx, range, ctx.user.id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to record activity replay");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
+    }
+    tx.commit().await.map_err(|error| {
+        tracing::error!(?error, "failed to commit activity replays");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    let events = projects.iter().map(|range| range.events).sum();
+    tracing::info!(
+        projects = projects.len(),
+        events,
+        "replayed organization activity"
+    );
+    Ok(Json(ReplayActivityResponse { projects, events }))
+}
+
+#[derive(Debug, Serialize)]
+struct WsSessionsResponse {
+    sessions: Vec<WsSession>,
+}
+
+/// WebSocket sessions connected to this replica; each replica only knows
+/// its own.
+#[instrument(name = "admin.list_ws_sessions", skip(state, ctx), fields(user_id = %ctx.user.id))]
+async fn list_ws_sessions(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+) -> Result<Json<WsSessionsResponse>, ErrorResponse> {
+    ensure_operator(&state, &ctx)?;
+
+    Ok(Json(WsSessionsResponse {
+        sessions: state.ws_sessions().list(),
+    }))
+}
+
+/// Close a WebSocket session on this replica. The client is sent a close
+/// frame once what's already queued for it has been written.
+#[instrument(
+    name = "admin.disconnect_ws_session",
+    skip(state, ctx),
+    fields(session_id = %session_id, user_id = %ctx.user.id)
+)]
+async fn disconnect_ws_session(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(session_id): Path<Uuid>,
+) -> Result<StatusCode, ErrorResponse> {
+    ensure_operator(&state, &ctx)?;
+
+    if !state.ws_sessions().disconnect(session_id) {
+        return Err(ErrorResponse::new(
+            StatusCode::NOT_FOUND,
+            "session not found on this replica",
+        ));
+    }
+    tracing::info!("disconnected websocket session");
+    Ok(StatusCode::NO_CONTENT)
+}
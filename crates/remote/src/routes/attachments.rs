@@ -1,5 +1,6 @@
 use api_types::{
-    AttachmentUrlResponse, AttachmentWithBlob, AttachmentWithUrl, ListAttachmentsResponse,
+    AttachmentUrlResponse, AttachmentWithBlob, AttachmentWithUrl, ConfirmUploadRequest,
+    InitUploadRequest, InitUploadResponse, ListAttachmentsResponse, SignedDownloadUrl,
 };
 use axum::{
     Json, Router,
@@ -8,14 +9,18 @@ use axum::{
     response::{IntoResponse, Response},
     routing::{delete, get, post},
 };
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::organization_members::{
-    ensure_comment_access, ensure_issue_access, ensure_project_access,
+use super::{
+    downloads::signed_download_url,
+    organization_members::{
+        ensure_comment_access, ensure_comment_write_access, ensure_issue_access,
+        ensure_issue_write_access, ensure_project_access, ensure_project_write_access,
+    },
 };
 use crate::{
     AppState,
@@ -27,6 +32,7 @@ use crate::{
         blobs::{BlobError, BlobRepository},
         pending_uploads::{PendingUploadError, PendingUploadRepository},
     },
+    signed_urls::DownloadResource,
 };
 
 pub fn router() -> Router<AppState> {
@@ -35,6 +41,10 @@ pub fn router() -> Router<AppState> {
         .route("/attachments/confirm", post(confirm_upload))
         .route("/attachments/{id}/file", get(get_attachment_file))
         .route("/attachments/{id}/thumbnail", get(get_attachment_thumbnail))
+        .route(
+            "/attachments/{id}/download-url",
+            get(get_attachment_download_url),
+        )
         .route("/attachments/{id}", delete(delete_attachment))
         .route(
             "/issues/{issue_id}/attachments",
@@ -54,40 +64,6 @@ pub fn router() -> Router<AppState> {
         )
 }
 
-#[derive(Debug, Serialize, Deserialize, TS)]
-pub struct InitUploadRequest {
-    pub project_id: Uuid,
-    pub filename: String,
-    #[ts(type = "number")]
-    pub size_bytes: i64,
-    pub hash: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, TS)]
-pub struct InitUploadResponse {
-    pub upload_url: String,
-    pub upload_id: Uuid,
-    pub expires_at: DateTime<Utc>,
-    pub skip_upload: bool,
-    pub existing_blob_id: Option<Uuid>,
-}
-
-#[derive(Debug, Serialize, Deserialize, TS)]
-pub struct ConfirmUploadRequest {
-    pub project_id: Uuid,
-    pub upload_id: Uuid,
-    pub filename: String,
-    #[ts(optional)]
-    pub content_type: Option<String>,
-    #[ts(type = "number")]
-    pub size_bytes: i64,
-    pub hash: String,
-    #[ts(optional)]
-    pub issue_id: Option<Uuid>,
-    #[ts(optional)]
-    pub comment_id: Option<Uuid>,
-}
-
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CommitAttachmentsRequest {
     pub attachment_ids: Vec<Uuid>,
@@ -176,7 +152,7 @@ async fn init_upload(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<InitUploadRequest>,
 ) -> Result<Json<InitUploadResponse>, RouteError> {
-    ensure_project_access(state.pool(), ctx.user.id, payload.project_id)
+    ensure_project_write_access(state.pool(), ctx.user.id, payload.project_id)
         .await
         .map_err(|_| RouteError::AccessDenied)?;
 
@@ -233,17 +209,17 @@ async fn confirm_upload(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<ConfirmUploadRequest>,
 ) -> Result<Json<AttachmentWithBlob>, RouteError> {
-    ensure_project_access(state.pool(), ctx.user.id, payload.project_id)
+    ensure_project_write_access(state.pool(), ctx.user.id, payload.project_id)
         .await
         .map_err(|_| RouteError::AccessDenied)?;
 
     if let Some(issue_id) = payload.issue_id {
-        ensure_issue_access(state.pool(), ctx.user.id, issue_id)
+        ensure_issue_write_access(state.pool(), ctx.user.id, issue_id)
             .await
             .map_err(|_| RouteError::AccessDenied)?;
     }
     if let Some(comment_id) = payload.comment_id {
-        ensure_comment_access(state.pool(), ctx.user.id, comment_id)
+        ensure_comment_write_access(state.pool(), ctx.user.id, comment_id)
             .await
             .map_err(|_| RouteError::AccessDenied)?;
     }
@@ -335,7 +311,7 @@ async fn commit_issue_attachments(
     Path(issue_id): Path<Uuid>,
     Json(payload): Json<CommitAttachmentsRequest>,
 ) -> Result<Json<CommitAttachmentsResponse>, RouteError> {
-    ensure_issue_access(state.pool(), ctx.user.id, issue_id)
+    ensure_issue_write_access(state.pool(), ctx.user.id, issue_id)
         .await
         .map_err(|_| RouteError::AccessDenied)?;
 
@@ -352,7 +328,7 @@ async fn commit_comment_attachments(
     Path(comment_id): Path<Uuid>,
     Json(payload): Json<CommitAttachmentsRequest>,
 ) -> Result<Json<CommitAttachmentsResponse>, RouteError> {
-    ensure_comment_access(state.pool(), ctx.user.id, comment_id)
+    ensure_comment_write_access(state.pool(), ctx.user.id, comment_id)
         .await
         .map_err(|_| RouteError::AccessDenied)?;
 
@@ -449,6 +425,29 @@ async fn get_attachment_thumbnail(
     Ok(Json(AttachmentUrlResponse { url }))
 }
 
+/// Like `/file`, but returns a link to the server's signed download route so
+/// attachments are handed out the same way as exports and reports.
+#[instrument(name = "attachments.get_download_url", skip(state, ctx), fields(attachment_id = %id, user_id = %ctx.user.id))]
+async fn get_attachment_download_url(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<SignedDownloadUrl>, RouteError> {
+    let attachment = AttachmentRepository::find_by_id_with_blob(state.pool(), id)
+        .await?
+        .ok_or(RouteError::NotFound)?;
+
+    ensure_attachment_access(&state, ctx.user.id, &attachment).await?;
+
+    if state.azure_blob().is_none() {
+        return Err(RouteError::NotConfigured);
+    }
+    Ok(Json(signed_download_url(
+        &state,
+        DownloadResource::Attachment(attachment.id),
+    )))
+}
+
 #[instrument(name = "attachments.delete", skip(state, ctx), fields(attachment_id = %id, user_id = %ctx.user.id))]
 async fn delete_attachment(
     State(state): State<AppState>,
@@ -459,7 +458,7 @@ async fn delete_attachment(
         .await?
         .ok_or(RouteError::NotFound)?;
 
-    ensure_attachment_access(&state, ctx.user.id, &attachment).await?;
+    ensure_attachment_write_access(&state, ctx.user.id, &attachment).await?;
 
     let blob_id = attachment.blob_id;
     AttachmentRepository::delete(state.pool(), id).await?;
@@ -507,6 +506,32 @@ async fn ensure_attachment_access(
     Ok(())
 }
 
+/// [`ensure_attachment_access`] for deleting the attachment.
+async fn ensure_attachment_write_access(
+    state: &AppState,
+    user_id: Uuid,
+    attachment: &AttachmentWithBlob,
+) -> Result<(), RouteError> {
+    if let Some(issue_id) = attachment.issue_id {
+        ensure_issue_write_access(state.pool(), user_id, issue_id)
+            .await
+            .map_err(|_| RouteError::AccessDenied)?;
+    } else if let Some(comment_id) = attachment.comment_id {
+        ensure_comment_write_access(state.pool(), user_id, comment_id)
+            .await
+            .map_err(|_| RouteError::AccessDenied)?;
+    } else if let Some(project_id) =
+        AttachmentRepository::project_id(state.pool(), attachment.id).await?
+    {
+        ensure_project_write_access(state.pool(), user_id, project_id)
+            .await
+            .map_err(|_| RouteError::AccessDenied)?;
+    } else {
+        return Err(RouteError::AccessDenied);
+    }
+    Ok(())
+}
+
 fn sanitize_filename(filename: &str) -> String {
     filename
         .chars()
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use api_types::{BoardAtQuery, BoardAtResponse, BoardColumnAt, BoardIssueAt};
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    routing::get,
+};
+use chrono::{Duration, Utc};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_project_access, finish_read, tenant_tx},
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        issue_activity::{self, ActivitySnapshot, IssueActivityRepository, IssueStatusMap},
+        issues::IssueRepository,
+        project_statuses::ProjectStatusRepository,
+        tenant::TenantId,
+    },
+};
+
+/// Persist a replay checkpoint once a reconstruction has to fold in at least
+/// this many events past the previous one.
+const SNAPSHOT_INTERVAL: usize = 500;
+
+/// Only events at least this old are checkpointed. `seq` is assigned at
+/// insert rather than commit, so a newer event may still be joined by one
+/// with a lower `seq` from a transaction that hasn't committed yet. That
+/// can't happen here as long as transactions recording activity finish
+/// within this long.
+const SNAPSHOT_SETTLE_TIME: Duration = Duration::minutes(5);
+
+pub(super) fn router() -> Router<AppState> {
+    Router::new().route("/projects/{project_id}/board-at", get(get_board_at))
+}
+
+#[instrument(
+    name = "board_history.get_board_at",
+    skip(state, ctx),
+    fields(project_id = %project_id, timestamp = %query.timestamp, user_id = %ctx.user.id)
+)]
+async fn get_board_at(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<BoardAtQuery>,
+) -> Result<Json<BoardAtResponse>, ErrorResponse> {
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    if query.timestamp > Utc::now() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "timestamp must not be in the future",
+        ));
+    }
+
+    let statuses = reconstruct_statuses(&state, tenant, project_id, query.timestamp).await?;
+
+    let project_statuses: HashMap<Uuid, (i32, String)> =
+        ProjectStatusRepository::list_by_project(state.pool(), project_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %project_id, "failed to list project statuses");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load statuses")
+            })?
+            .into_iter()
+            .map(|status| (status.id, (status.sort_order, status.name)))
+            .collect();
+
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let current_issues: HashMap<Uuid, (String, String)> =
+        IssueRepository::list_by_project(&mut *tx, project_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %project_id, "failed to list issues");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load issues")
+            })?
+            .into_iter()
+            .map(|issue| (issue.id, (issue.simple_id, issue.title)))
+            .collect();
+    finish_read(tx).await?;
+
+    let mut columns: HashMap<Uuid, Vec<BoardIssueAt>> = HashMap::new();
+    for (issue_id, status_id) in statuses {
+        let (simple_id, title) = current_issues
+            .get(&issue_id)
+            .cloned()
+            .map_or((None, None), |(simple_id, title)| {
+                (Some(simple_id), Some(title))
+            });
+        columns.entry(status_id).or_default().push(BoardIssueAt {
+            issue_id,
+            simple_id,
+            title,
+        });
+    }
+
+    let mut columns: Vec<BoardColumnAt> = columns
+        .into_iter()
+        .map(|(status_id, mut issues)| {
+            issues.sort_by(|a, b| a.simple_id.cmp(&b.simple_id));
+            BoardColumnAt {
+                status_id,
+                status_name: project_statuses
+                    .get(&status_id)
+                    .map(|(_, name)| name.clone()),
+                issues,
+            }
+        })
+        .collect();
+    // Board order; statuses deleted since then go last.
+    columns.sort_by_key(|column| {
+        project_statuses
+            .get(&column.status_id)
+            .map_or(i32::MAX, |(sort_order, _)| *sort_order)
+    });
+
+    Ok(Json(BoardAtResponse {
+        project_id,
+        timestamp: query.timestamp,
+        columns,
+    }))
+}
+
+/// Replay the activity log up to `at`, starting from the nearest cached
+/// checkpoint and caching a new one when the replay was long.
+async fn reconstruct_statuses(
+    state: &AppState,
+    tenant: TenantId,
+    project_id: Uuid,
+    at: chrono::DateTime<Utc>,
+) -> Result<IssueStatusMap, ErrorResponse> {
+    let snapshot = IssueActivityRepository::latest_snapshot_before(state.pool(), project_id, at)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load activity snapshot");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load history")
+        })?;
+
+    let (after_seq, mut taken_at, mut statuses) = match snapshot {
+        Some(snapshot) => (
+            snapshot.seq,
+            Some(snapshot.taken_at),
+            snapshot.issue_statuses,
+        ),
+        None => (0, None, IssueStatusMap::new()),
+    };
+
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let events = IssueActivityRepository::list_for_replay(&mut *tx, project_id, after_seq, at)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load issue activity");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load history")
+        })?;
+    finish_read(tx).await?;
+
+    let settled_before = Utc::now() - SNAPSHOT_SETTLE_TIME;
+    let (settled, recent) = events.split_at(
+        events
+            .iter()
+            .take_while(|event| event.created_at <= settled_before)
+            .count(),
+    );
+
+    issue_activity::replay(&mut statuses, settled);
+
+    if settled.len() >= SNAPSHOT_INTERVAL
+        && let Some(last) = settled.last()
+    {
+        for event in settled {
+            taken_at = Some(taken_at.map_or(event.created_at, |t| t.max(event.created_at)));
+        }
+        // `settled` is a contiguous prefix of the log that no transaction
+        // can still add to, so the folded state is valid for any later
+        // reconstruction.
+        let checkpoint = ActivitySnapshot {
+            seq: last.seq,
+            taken_at: taken_at.unwrap_or(last.created_at),
+            issue_statuses: statuses,
+        };
+        if let Err(error) =
+            IssueActivityRepository::store_snapshot(state.pool(), project_id, &checkpoint).await
+        {
+            tracing::warn!(?error, %project_id, "failed to cache activity snapshot");
+        }
+        statuses = checkpoint.issue_statuses;
+    }
+
+    issue_activity::replay(&mut statuses, recent);
+
+    Ok(statuses)
+}
@@ -0,0 +1,174 @@
+use api_types::SignedDownloadUrl;
+use axum::{
+    Router,
+    body::Body,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Redirect, Response},
+    routing::get,
+};
+use chrono::Utc;
+use tracing::instrument;
+
+use super::error::ErrorResponse;
+use crate::{
+    AppState,
+    db::{
+        attachments::AttachmentRepository,
+        download_nonces::DownloadNonceRepository,
+        export_jobs::ExportJobRepository,
+        project_reports::ProjectReportRepository,
+        tenant::{TenantId, TenantSource},
+    },
+    signed_urls::{DownloadResource, SignedUrlError},
+};
+
+pub(super) fn public_router() -> Router<AppState> {
+    Router::new().route("/downloads/{token}", get(download))
+}
+
+/// Sign `resource` and build its absolute download URL. Callers must have
+/// authorized access to the resource already.
+pub(super) fn signed_download_url(
+    state: &AppState,
+    resource: DownloadResource,
+) -> SignedDownloadUrl {
+    let (token, expires_at) = state.download_signer().sign(resource, Utc::now());
+    SignedDownloadUrl {
+        url: format!(
+            "{}/v1/downloads/{token}",
+            state.server_public_base_url.trim_end_matches('/')
+        ),
+        expires_at,
+    }
+}
+
+#[instrument(name = "downloads.download", skip(state, token))]
+async fn download(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<Response, ErrorResponse> {
+    let signed =
+        state
+            .download_signer()
+            .verify(&token, Utc::now())
+            .map_err(|error| match error {
+                SignedUrlError::Expired => ErrorResponse::new(StatusCode::GONE, error.to_string()),
+                _ => ErrorResponse::new(StatusCode::FORBIDDEN, error.to_string()),
+            })?;
+
+    if let Some(max_uses) = signed.resource.max_uses() {
+        let redeemed = DownloadNonceRepository::redeem(
+            state.pool(),
+            &signed.nonce,
+            signed.expires_at,
+            max_uses,
+        )
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to redeem download nonce");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+        if !redeemed {
+            return Err(ErrorResponse::new(
+                StatusCode::GONE,
+                "download link has already been used",
+            ));
+        }
+    }
+
+    match signed.resource {
+        DownloadResource::ExportJob(job_id) => {
+            let archive = ExportJobRepository::find_archive(state.pool(), job_id)
+                .await
+                .map_err(|error| {
+                    tracing::error!(?error, %job_id, "failed to load export archive");
+                    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load export")
+                })?
+                .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "export not found"))?;
+
+            Ok(attachment_response(
+                "application/zip",
+                &archive.filename,
+                archive.content,
+            ))
+        }
+        DownloadResource::ProjectReport(report_id) => {
+            let load_failed = |error| {
+                tracing::error!(?error, %report_id, "failed to load project report");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load report")
+            };
+            let not_found = || ErrorResponse::new(StatusCode::NOT_FOUND, "report not found");
+            let (project_id, organization_id) =
+                ProjectReportRepository::owner(state.pool(), report_id)
+                    .await
+                    .map_err(load_failed)?
+                    .ok_or_else(not_found)?;
+            let tenant = TenantId::trusted(organization_id, TenantSource::SignedDownload);
+            let report =
+                ProjectReportRepository::find_by_id(state.pool(), tenant, project_id, report_id)
+                    .await
+                    .map_err(load_failed)?
+                    .ok_or_else(not_found)?;
+
+            let body = serde_json::to_vec_pretty(&report).map_err(|error| {
+                tracing::error!(?error, %report_id, "failed to serialize project report");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to render report")
+            })?;
+            let filename = format!(
+                "project-report-{}.json",
+                report.period_end.format("%Y-%m-%d")
+            );
+
+            Ok(attachment_response("application/json", &filename, body))
+        }
+        DownloadResource::Attachment(attachment_id) => {
+            let attachment =
+                AttachmentRepository::find_by_id_with_blob(state.pool(), attachment_id)
+                    .await
+                    .map_err(|error| {
+                        tracing::error!(?error, %attachment_id, "failed to load attachment");
+                        ErrorResponse::new(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            "failed to load attachment",
+                        )
+                    })?
+                    .ok_or_else(|| {
+                        ErrorResponse::new(StatusCode::NOT_FOUND, "attachment not found")
+                    })?;
+
+            let azure = state.azure_blob().ok_or_else(|| {
+                ErrorResponse::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "Attachment storage not available",
+                )
+            })?;
+            let url = azure
+                .create_read_url(&attachment.blob_path)
+                .map_err(|error| {
+                    tracing::error!(?error, %attachment_id, "failed to sign attachment url");
+                    ErrorResponse::new(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "failed to load attachment",
+                    )
+                })?;
+
+            Ok(Redirect::temporary(&url).into_response())
+        }
+    }
+}
+
+fn attachment_response(content_type: &'static str, filename: &str, body: Vec<u8>) -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        Body::from(body),
+    )
+        .into_response()
+}
@@ -0,0 +1,40 @@
+//! `?dry_run=true` support for mutation routes.
+//!
+//! A dry run performs the same access checks and writes as a real request,
+//! then rolls the transaction back, so constraint violations surface exactly
+//! as they would on commit.
+
+use axum::http::StatusCode;
+use serde::Deserialize;
+
+use super::error::ErrorResponse;
+use crate::db::{Tx, get_txid};
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct DryRunQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Commit `tx` and return its txid, or roll it back when `dry_run` is set.
+/// Dry runs report a txid of 0 since nothing was written.
+pub(crate) async fn finish_tx(mut tx: Tx<'_>, dry_run: bool) -> Result<i64, ErrorResponse> {
+    if dry_run {
+        tx.rollback().await.map_err(|error| {
+            tracing::error!(?error, "failed to roll back dry run");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+        return Ok(0);
+    }
+
+    let txid = get_txid(&mut *tx).await.map_err(|error| {
+        tracing::error!(?error, "failed to get txid");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+    tx.commit().await.map_err(|error| {
+        tracing::error!(?error, "failed to commit transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    Ok(txid)
+}
@@ -2,17 +2,23 @@ use std::collections::HashMap;
 
 use axum::{
     Router,
-    body::Body,
+    body::{Body, Bytes},
     http::{HeaderMap, HeaderValue, StatusCode, header},
     response::{IntoResponse, Response},
 };
-use futures::TryStreamExt;
+use futures::{Stream, StreamExt, TryStreamExt};
 use secrecy::ExposeSecret;
 use serde::Deserialize;
 use tracing::error;
 use uuid::Uuid;
 
-use crate::{AppState, shape_definition::ShapeExport};
+use crate::{
+    AppState,
+    electric_cache::{CachedResponse, ShapeCacheKey},
+    prometheus::{ELECTRIC_CACHE_REQUESTS_TOTAL, ELECTRIC_LIVE_REQUESTS, GaugeGuard},
+    shape_definition::ShapeExport,
+    trace_context::current_trace_headers,
+};
 
 #[derive(Deserialize)]
 pub(crate) struct OrgShapeQuery {
@@ -85,10 +91,39 @@ pub(crate) async fn proxy_table(
             .append_pair("secret", secret.expose_secret());
     }
 
+    let sticky = session_id.to_string();
+    let cached = state.electric_cache().and_then(|cache| {
+        ShapeCacheKey::new(
+            shape.table(),
+            shape.where_clause(),
+            electric_params,
+            client_params,
+            &sticky,
+        )
+        .map(|key| (cache, key))
+    });
+    if let Some((cache, key)) = &cached {
+        let hit = cache.get(key).await;
+        metrics::counter!(
+            ELECTRIC_CACHE_REQUESTS_TOTAL,
+            "result" => if hit.is_some() { "hit" } else { "miss" }
+        )
+        .increment(1);
+        if let Some(response) = hit {
+            return Ok(response.into_response());
+        }
+    }
+
+    // Live requests are held upstream until something changes.
+    let _live = client_params
+        .get("live")
+        .is_some_and(|live| live == "true")
+        .then(|| GaugeGuard::increment(ELECTRIC_LIVE_REQUESTS));
     let response = state
         .http_client
         .get(origin_url.as_str())
-        .header(ELECTRIC_STICKY_HEADER, session_id.to_string())
+        .headers(current_trace_headers())
+        .header(ELECTRIC_STICKY_HEADER, &sticky)
         .send()
         .await
         .map_err(ProxyError::Connection)?;
@@ -108,16 +143,72 @@ pub(crate) async fn proxy_table(
     // Add Vary header for proper caching with auth
     headers.insert(header::VARY, HeaderValue::from_static("Authorization"));
 
-    // Stream the response body directly without buffering
+    let Some((cache, key)) = cached else {
+        // Stream the response body directly without buffering
+        let body_stream = response.bytes_stream().map_err(std::io::Error::other);
+        return Ok((status, headers, Body::from_stream(body_stream)).into_response());
+    };
+
+    // The shape's handle changed, so the cached start of the shape points
+    // clients at a handle Electric no longer serves.
+    if status == StatusCode::CONFLICT {
+        cache.remove(&key.initial()).await;
+    }
+    let ttl = headers
+        .get(header::CACHE_CONTROL)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| cache.ttl(value))
+        .filter(|_| status == StatusCode::OK);
     let body_stream = response.bytes_stream().map_err(std::io::Error::other);
-    let body = Body::from_stream(body_stream);
+    let Some(ttl) = ttl else {
+        return Ok((status, headers, Body::from_stream(body_stream)).into_response());
+    };
+
+    let body = match buffer_body(body_stream, cache.max_body_bytes()).await? {
+        BufferedBody::Complete(body) => {
+            let response = CachedResponse {
+                status,
+                headers: headers.clone(),
+                body: body.clone(),
+            };
+            cache.put(&key, response, ttl).await;
+            Body::from(body)
+        }
+        BufferedBody::TooLarge(body) => body,
+    };
 
     Ok((status, headers, body).into_response())
 }
 
+enum BufferedBody {
+    Complete(Bytes),
+    /// Over the cache's size limit; what was read is sent ahead of the rest.
+    TooLarge(Body),
+}
+
+async fn buffer_body(
+    stream: impl Stream<Item = Result<Bytes, std::io::Error>> + Send + 'static,
+    max_bytes: usize,
+) -> Result<BufferedBody, ProxyError> {
+    let mut stream = Box::pin(stream);
+    let mut buffered = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(ProxyError::Body)?;
+        buffered.extend_from_slice(&chunk);
+        if buffered.len() > max_bytes {
+            let prefix = futures::stream::once(async move { Ok(Bytes::from(buffered)) });
+            return Ok(BufferedBody::TooLarge(Body::from_stream(
+                prefix.chain(stream),
+            )));
+        }
+    }
+    Ok(BufferedBody::Complete(buffered.into()))
+}
+
 #[derive(Debug)]
 pub(crate) enum ProxyError {
     Connection(reqwest::Error),
+    Body(std::io::Error),
     InvalidConfig(String),
     Authorization(String),
 }
@@ -133,6 +224,10 @@ impl IntoResponse for ProxyError {
                 )
                     .into_response()
             }
+            ProxyError::Body(err) => {
+                error!(?err, "failed to read Electric response");
+                (StatusCode::BAD_GATEWAY, "failed to read Electric response").into_response()
+            }
             ProxyError::InvalidConfig(msg) => {
                 error!(%msg, "invalid Electric proxy configuration");
                 (StatusCode::INTERNAL_SERVER_ERROR, "internal server error").into_response()
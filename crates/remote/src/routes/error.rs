@@ -1,11 +1,14 @@
 use axum::{
     Json,
-    http::StatusCode,
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode, header},
+    middleware::Next,
     response::{IntoResponse, Response},
 };
 use serde_json::json;
 
-use crate::db::identity_errors::IdentityError;
+use crate::{AppState, db::identity_errors::IdentityError};
 
 #[derive(Debug)]
 pub struct ErrorResponse {
@@ -20,6 +23,21 @@ impl ErrorResponse {
             message: message.into(),
         }
     }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn payload_too_large(what: &str, limit_bytes: usize) -> Self {
+        Self::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("{what} exceeds the {limit_bytes} byte limit"),
+        )
+    }
 }
 
 impl IntoResponse for ErrorResponse {
@@ -58,6 +76,10 @@ pub(crate) fn membership_error(error: IdentityError, forbidden_message: &str) ->
         IdentityError::NotFound | IdentityError::PermissionDenied => {
             ErrorResponse::new(StatusCode::FORBIDDEN, forbidden_message)
         }
+        IdentityError::AccessPolicyDenied => ErrorResponse::new(
+            StatusCode::FORBIDDEN,
+            "Request blocked by organization access policy",
+        ),
         IdentityError::Database(_) => {
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
         }
@@ -67,3 +89,36 @@ pub(crate) fn membership_error(error: IdentityError, forbidden_message: &str) ->
         }
     }
 }
+
+/// Reject `text` with 413 when it is longer than `limit_bytes`.
+pub(crate) fn check_text_size(
+    field: &str,
+    text: Option<&str>,
+    limit_bytes: usize,
+) -> Result<(), ErrorResponse> {
+    match text {
+        Some(text) if text.len() > limit_bytes => {
+            Err(ErrorResponse::payload_too_large(field, limit_bytes))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Give 413s from the body limit layer and body extractors, which are plain
+/// text, the usual JSON error body.
+pub(crate) async fn json_payload_too_large(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE || is_json {
+        return response;
+    }
+    ErrorResponse::payload_too_large("request body", state.config().payload_limits.max_body_bytes)
+        .into_response()
+}
@@ -1,56 +1,411 @@
 use std::{
     collections::{HashMap, HashSet},
     io::{Cursor, Write},
+    sync::Arc,
 };
 
-use api_types::ExportRequest;
+use api_types::{
+    ExportJobResponse, ExportJobStatus, ExportRequest, Issue, IssueActivity, IssueComment, Project,
+};
 use axum::{
     Json, Router,
     body::Body,
-    extract::{Extension, State},
+    extract::{Extension, Path, Query, State},
     http::{StatusCode, header},
     response::{IntoResponse, Response},
-    routing::post,
+    routing::{get, post},
 };
 use chrono::Utc;
+use futures::{StreamExt, stream};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
 use tracing::instrument;
 use uuid::Uuid;
 use zip::{ZipWriter, write::SimpleFileOptions};
 
-use super::{error::ErrorResponse, organization_members::ensure_member_access};
-use crate::{AppState, auth::RequestContext, db::export::ExportRepository};
+use super::{
+    downloads::signed_download_url, error::ErrorResponse,
+    organization_members::ensure_member_access,
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{export::ExportRepository, export_jobs::ExportJobRepository, tenant::TenantId},
+    export_jobs,
+    signed_urls::DownloadResource,
+};
 
 pub(super) fn router() -> Router<AppState> {
-    Router::new().route("/export", post(export_data))
+    Router::new()
+        .route("/export", post(export_data))
+        .route("/export/jobs", post(create_export_job))
+        .route("/export/jobs/{job_id}", get(get_export_job))
+        .route("/export/tasks", get(export_tasks))
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TaskExportFormat {
+    #[default]
+    Ndjson,
+    Csv,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskExportQuery {
+    organization_id: Uuid,
+    #[serde(default)]
+    format: TaskExportFormat,
+}
+
+/// One NDJSON line of a task export.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TaskExportRecord<'a> {
+    Project(&'a Project),
+    Task(&'a Issue),
+    Comment(&'a IssueComment),
+    Activity(&'a IssueActivity),
 }
 
+const TASK_CSV_HEADER: [&str; 11] = [
+    "Type",
+    "Project",
+    "Issue ID",
+    "ID",
+    "Created",
+    "User",
+    "Title",
+    "Status",
+    "From Status",
+    "Event",
+    "Body",
+];
+
 #[instrument(name = "export.data", skip(state, ctx, payload))]
 async fn export_data(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<ExportRequest>,
 ) -> Result<Response, ErrorResponse> {
+    let tenant = ensure_member_access(state.pool(), payload.organization_id, ctx.user.id).await?;
+
+    let (filename, zip_bytes) = build_export_archive(&state, tenant, ctx.user.id, &payload).await?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        Body::from(zip_bytes),
+    )
+        .into_response())
+}
+
+#[instrument(name = "export.create_job", skip(state, ctx, payload))]
+async fn create_export_job(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<ExportRequest>,
+) -> Result<(StatusCode, Json<ExportJobResponse>), ErrorResponse> {
+    let tenant = ensure_member_access(state.pool(), payload.organization_id, ctx.user.id).await?;
+
+    if let Err(error) = ExportJobRepository::delete_expired(state.pool()).await {
+        tracing::warn!(?error, "failed to delete expired export jobs");
+    }
+
+    let job = ExportJobRepository::create(state.pool(), tenant, ctx.user.id, &payload)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to create export job");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to create export")
+        })?;
+
+    let job_id = job.id;
+    let user_id = ctx.user.id;
+    let task_state = state.clone();
+    tokio::spawn(async move {
+        run_export_job(task_state, job_id, tenant, user_id, payload).await;
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ExportJobResponse {
+            job,
+            download: None,
+        }),
+    ))
+}
+
+#[instrument(name = "export.get_job", skip(state, ctx), fields(job_id = %job_id))]
+async fn get_export_job(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<ExportJobResponse>, ErrorResponse> {
+    let job = ExportJobRepository::find_for_user(state.pool(), job_id, ctx.user.id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %job_id, "failed to load export job");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load export")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "export not found"))?;
+
+    ensure_member_access(state.pool(), job.organization_id, ctx.user.id).await?;
+
+    let download = (job.status == ExportJobStatus::Completed)
+        .then(|| signed_download_url(&state, DownloadResource::ExportJob(job.id)));
+
+    Ok(Json(ExportJobResponse { job, download }))
+}
+
+/// Stream every task, comment and activity event in the organization's
+/// projects, one project at a time, so large organizations don't have to fit
+/// in memory.
+#[instrument(
+    name = "export.tasks",
+    skip(state, ctx, query),
+    fields(organization_id = %query.organization_id, user_id = %ctx.user.id)
+)]
+async fn export_tasks(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<TaskExportQuery>,
+) -> Result<Response, ErrorResponse> {
+    let tenant = ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
+    let pool = state.pool().clone();
+
+    let (projects, users) = tokio::try_join!(
+        ExportRepository::list_accessible_projects_by_organization(&pool, tenant, ctx.user.id),
+        ExportRepository::list_users_by_organization(&pool, tenant),
+    )
+    .map_err(|error| {
+        tracing::error!(?error, "failed to load organization for task export");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to fetch data")
+    })?;
+    let user_map: Arc<HashMap<Uuid, String>> = Arc::new(
+        users
+            .iter()
+            .map(|u| {
+                let name = format_user_name(
+                    u.first_name.as_deref(),
+                    u.last_name.as_deref(),
+                    u.username.as_deref(),
+                    &u.email,
+                );
+                (u.id, name)
+            })
+            .collect(),
+    );
+
+    let format = query.format;
+    let csv_header = match format {
+        TaskExportFormat::Ndjson => Vec::new(),
+        TaskExportFormat::Csv => {
+            let mut wtr = csv::Writer::from_writer(Vec::new());
+            wtr.write_record(TASK_CSV_HEADER)
+                .map_err(|e| csv_error(&e))?;
+            wtr.into_inner()
+                .map_err(|e| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        }
+    };
+    let chunks = stream::iter(projects).then(move |project| {
+        let pool = pool.clone();
+        let user_map = user_map.clone();
+        async move { encode_project_tasks(&pool, tenant, &project, format, &user_map).await }
+    });
+    let body_stream = stream::once(async { Ok(csv_header) }).chain(chunks);
+
+    let (content_type, extension) = match format {
+        TaskExportFormat::Ndjson => ("application/x-ndjson", "ndjson"),
+        TaskExportFormat::Csv => ("text/csv; charset=utf-8", "csv"),
+    };
+    let date = Utc::now().format("%Y-%m-%d");
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"vibe-kanban-tasks-{date}.{extension}\""),
+            ),
+        ],
+        Body::from_stream(body_stream),
+    )
+        .into_response())
+}
+
+/// Encode one project and its tasks, comments and activity. Errors end the
+/// stream, which the client sees as a truncated download.
+async fn encode_project_tasks(
+    pool: &PgPool,
+    tenant: TenantId,
+    project: &Project,
+    format: TaskExportFormat,
+    user_map: &HashMap<Uuid, String>,
+) -> Result<Vec<u8>, std::io::Error> {
+    let project_ids = [project.id];
+    let (issues, comments, activity, statuses) = tokio::try_join!(
+        ExportRepository::list_all_issues_by_projects(pool, tenant, &project_ids),
+        ExportRepository::list_comments_by_projects(pool, tenant, &project_ids),
+        ExportRepository::list_activity_by_projects(pool, tenant, &project_ids),
+        ExportRepository::list_statuses_by_projects(pool, &project_ids),
+    )
+    .map_err(|error| {
+        tracing::error!(?error, project_id = %project.id, "failed to fetch tasks for export");
+        std::io::Error::other(error)
+    })?;
+
+    let mut buf = Vec::new();
+    match format {
+        TaskExportFormat::Ndjson => {
+            let records = std::iter::once(TaskExportRecord::Project(project))
+                .chain(issues.iter().map(TaskExportRecord::Task))
+                .chain(comments.iter().map(TaskExportRecord::Comment))
+                .chain(activity.iter().map(TaskExportRecord::Activity));
+            for record in records {
+                serde_json::to_writer(&mut buf, &record)?;
+                buf.push(b'\n');
+            }
+        }
+        TaskExportFormat::Csv => {
+            let status_map: HashMap<Uuid, &str> =
+                statuses.iter().map(|s| (s.id, s.name.as_str())).collect();
+            let issue_simple_id_map: HashMap<Uuid, &str> = issues
+                .iter()
+                .map(|i| (i.id, i.simple_id.as_str()))
+                .collect();
+            let status_name =
+                |id: Option<Uuid>| id.and_then(|id| status_map.get(&id)).copied().unwrap_or("");
+            let user_name = |id: Option<Uuid>| {
+                id.and_then(|id| user_map.get(&id))
+                    .map(|s| s.as_str())
+                    .unwrap_or("")
+            };
+            let simple_id = |id: Uuid| issue_simple_id_map.get(&id).copied().unwrap_or("");
+
+            let mut wtr = csv::Writer::from_writer(&mut buf);
+            for issue in &issues {
+                wtr.write_record([
+                    "task",
+                    &project.name,
+                    &issue.simple_id,
+                    &issue.id.to_string(),
+                    &issue.created_at.to_rfc3339(),
+                    user_name(issue.creator_user_id),
+                    &issue.title,
+                    status_name(Some(issue.status_id)),
+                    "",
+                    "",
+                    issue.description.as_deref().unwrap_or(""),
+                ])?;
+            }
+            for comment in &comments {
+                wtr.write_record([
+                    "comment",
+                    &project.name,
+                    simple_id(comment.issue_id),
+                    &comment.id.to_string(),
+                    &comment.created_at.to_rfc3339(),
+                    user_name(comment.author_id),
+                    "",
+                    "",
+                    "",
+                    "",
+                    &comment.message,
+                ])?;
+            }
+            for event in &activity {
+                wtr.write_record([
+                    "activity",
+                    &project.name,
+                    event.issue_id.map_or("", simple_id),
+                    &event.seq.to_string(),
+                    &event.created_at.to_rfc3339(),
+                    user_name(event.actor_user_id),
+                    "",
+                    status_name(event.to_status_id),
+                    status_name(event.from_status_id),
+                    event.event_type.as_str(),
+                    "",
+                ])?;
+            }
+            wtr.flush()?;
+        }
+    }
+
+    Ok(buf)
+}
+
+async fn run_export_job(
+    state: AppState,
+    job_id: Uuid,
+    tenant: TenantId,
+    user_id: Uuid,
+    payload: ExportRequest,
+) {
     let pool = state.pool();
+    if let Err(error) = ExportJobRepository::mark_running(pool, job_id).await {
+        tracing::error!(?error, %job_id, "failed to mark export job running");
+        return;
+    }
+
+    // Heartbeats keep the job from being failed as abandoned while it runs.
+    let build = build_export_archive(&state, tenant, user_id, &payload);
+    tokio::pin!(build);
+    let mut heartbeat = tokio::time::interval(export_jobs::HEARTBEAT_INTERVAL);
+    heartbeat.tick().await;
+    let built = loop {
+        tokio::select! {
+            built = &mut build => break built,
+            _ = heartbeat.tick() => {
+                if let Err(error) = ExportJobRepository::heartbeat(pool, job_id).await {
+                    tracing::warn!(?error, %job_id, "failed to record export job heartbeat");
+                }
+            }
+        }
+    };
+
+    let result = match built {
+        Ok((filename, zip_bytes)) => {
+            ExportJobRepository::complete(pool, job_id, &filename, &zip_bytes).await
+        }
+        Err(error) => {
+            tracing::warn!(?error, %job_id, "export job failed");
+            ExportJobRepository::fail(pool, job_id, error.message()).await
+        }
+    };
+    if let Err(error) = result {
+        tracing::error!(?error, %job_id, "failed to record export job result");
+    }
+}
 
-    // Verify org membership
-    ensure_member_access(pool, payload.organization_id, ctx.user.id).await?;
+/// Build the export ZIP for `payload`. Returns the suggested filename and the
+/// archive bytes.
+async fn build_export_archive(
+    state: &AppState,
+    tenant: TenantId,
+    user_id: Uuid,
+    payload: &ExportRequest,
+) -> Result<(String, Vec<u8>), ErrorResponse> {
+    let pool = state.pool();
 
     let requested_project_ids: HashSet<_> = payload.project_ids.iter().copied().collect();
 
     // Determine which projects to export. The export repository applies the
     // organization membership filter directly to the project query.
     let projects = if payload.project_ids.is_empty() {
-        ExportRepository::list_accessible_projects_by_organization(
-            pool,
-            payload.organization_id,
-            ctx.user.id,
-        )
-        .await
+        ExportRepository::list_accessible_projects_by_organization(pool, tenant, user_id).await
     } else {
         ExportRepository::list_accessible_projects_by_ids(
             pool,
-            payload.organization_id,
-            ctx.user.id,
+            tenant,
+            user_id,
             &payload.project_ids,
         )
         .await
@@ -79,12 +434,12 @@ async fn export_data(
     // Fetch all data in parallel
     let (issues, users, all_statuses, assignees, attachments) = tokio::try_join!(
         async {
-            ExportRepository::list_all_issues_by_projects(pool, &project_ids)
+            ExportRepository::list_all_issues_by_projects(pool, tenant, &project_ids)
                 .await
                 .map_err(|e| e.to_string())
         },
         async {
-            ExportRepository::list_users_by_organization(pool, payload.organization_id)
+            ExportRepository::list_users_by_organization(pool, tenant)
                 .await
                 .map_err(|e| e.to_string())
         },
@@ -94,13 +449,13 @@ async fn export_data(
                 .map_err(|e| e.to_string())
         },
         async {
-            ExportRepository::list_assignees_by_projects(pool, &project_ids)
+            ExportRepository::list_assignees_by_projects(pool, tenant, &project_ids)
                 .await
                 .map_err(|e| e.to_string())
         },
         async {
             if payload.include_attachments {
-                ExportRepository::list_attachments_by_projects(pool, &project_ids)
+                ExportRepository::list_attachments_by_projects(pool, tenant, &project_ids)
                     .await
                     .map_err(|e| e.to_string())
             } else {
@@ -357,18 +712,7 @@ async fn export_data(
     let date = Utc::now().format("%Y-%m-%d");
     let filename = format!("vibe-kanban-export-{date}.zip");
 
-    Ok((
-        StatusCode::OK,
-        [
-            (header::CONTENT_TYPE, "application/zip".to_string()),
-            (
-                header::CONTENT_DISPOSITION,
-                format!("attachment; filename=\"{filename}\""),
-            ),
-        ],
-        Body::from(zip_bytes),
-    )
-        .into_response())
+    Ok((filename, zip_bytes))
 }
 
 fn format_user_name(
@@ -1,3 +1,4 @@
+use api_types::PullRequestStatus;
 use axum::{
     Json, Router,
     body::Bytes,
@@ -10,17 +11,27 @@ use chrono::{Duration, Utc};
 use secrecy::ExposeSecret;
 use serde::{Deserialize, Serialize};
 use tracing::{error, info, warn};
+use utils::{task_trailer::parse_task_trailers, webhooks::verify_hmac_sha256_hex};
 use uuid::Uuid;
 
-use super::error::ErrorResponse;
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_admin_access, ensure_member_access},
+};
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
-        github_app::GitHubAppRepository2, identity_errors::IdentityError,
-        organizations::OrganizationRepository, reviews::ReviewRepository,
+        github_app::GitHubAppRepository2,
+        organizations::OrganizationRepository,
+        reviews::ReviewRepository,
+        tenant::{TenantId, TenantSource},
+    },
+    github_app::{
+        PrCommentKind, PrReviewParams, PrReviewService, ReviewState, WebhookPrComment,
+        WebhookPullRequest, WorkFailure, link_trailer_issues, mirror_pr_comment,
+        reopen_failed_work, reverted_pull_request_url,
     },
-    github_app::{PrReviewParams, PrReviewService, verify_webhook_signature},
 };
 
 // ========== Public Routes ==========
@@ -131,23 +142,10 @@ async fn get_install_url(
         ErrorResponse::new(StatusCode::NOT_IMPLEMENTED, "GitHub App not configured")
     })?;
 
-    // Check user is admin of organization
-    let org_repo = OrganizationRepository::new(state.pool());
-    org_repo
-        .assert_admin(org_id, ctx.user.id)
-        .await
-        .map_err(|e| match e {
-            IdentityError::PermissionDenied => {
-                ErrorResponse::new(StatusCode::FORBIDDEN, "Admin access required")
-            }
-            IdentityError::NotFound => {
-                ErrorResponse::new(StatusCode::NOT_FOUND, "Organization not found")
-            }
-            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
-        })?;
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
 
     // Check not a personal org
-    let is_personal = org_repo
+    let is_personal = OrganizationRepository::new(state.pool())
         .is_personal(org_id)
         .await
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
@@ -167,7 +165,7 @@ async fn get_install_url(
     // Store pending installation
     let gh_repo = GitHubAppRepository2::new(state.pool());
     gh_repo
-        .create_pending(org_id, ctx.user.id, &state_token, expires_at)
+        .create_pending(tenant, ctx.user.id, &state_token, expires_at)
         .await
         .map_err(|e| {
             error!(?e, "Failed to create pending installation");
@@ -191,21 +189,11 @@ async fn get_status(
     axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
     Path(org_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    // Check user is member of organization
-    let org_repo = OrganizationRepository::new(state.pool());
-    org_repo
-        .assert_membership(org_id, ctx.user.id)
-        .await
-        .map_err(|e| match e {
-            IdentityError::PermissionDenied | IdentityError::NotFound => {
-                ErrorResponse::new(StatusCode::FORBIDDEN, "Access denied")
-            }
-            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
-        })?;
+    let tenant = ensure_member_access(state.pool(), org_id, ctx.user.id).await?;
 
     let gh_repo = GitHubAppRepository2::new(state.pool());
 
-    let installation = gh_repo.get_by_organization(org_id).await.map_err(|e| {
+    let installation = gh_repo.get_by_organization(tenant).await.map_err(|e| {
         error!(?e, "Failed to get GitHub App installation");
         ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
     })?;
@@ -255,23 +243,10 @@ async fn uninstall(
     axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
     Path(org_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    // Check user is admin of organization
-    let org_repo = OrganizationRepository::new(state.pool());
-    org_repo
-        .assert_admin(org_id, ctx.user.id)
-        .await
-        .map_err(|e| match e {
-            IdentityError::PermissionDenied => {
-                ErrorResponse::new(StatusCode::FORBIDDEN, "Admin access required")
-            }
-            IdentityError::NotFound => {
-                ErrorResponse::new(StatusCode::NOT_FOUND, "Organization not found")
-            }
-            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
-        })?;
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
 
     let gh_repo = GitHubAppRepository2::new(state.pool());
-    gh_repo.delete_by_organization(org_id).await.map_err(|e| {
+    gh_repo.delete_by_organization(tenant).await.map_err(|e| {
         error!(?e, "Failed to delete GitHub App installation");
         ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
     })?;
@@ -288,25 +263,12 @@ async fn update_repo_review_enabled(
     Path((org_id, repo_id)): Path<(Uuid, Uuid)>,
     Json(payload): Json<UpdateRepoReviewEnabledRequest>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    // Check user is admin of organization
-    let org_repo = OrganizationRepository::new(state.pool());
-    org_repo
-        .assert_admin(org_id, ctx.user.id)
-        .await
-        .map_err(|e| match e {
-            IdentityError::PermissionDenied => {
-                ErrorResponse::new(StatusCode::FORBIDDEN, "Admin access required")
-            }
-            IdentityError::NotFound => {
-                ErrorResponse::new(StatusCode::NOT_FOUND, "Organization not found")
-            }
-            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
-        })?;
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
 
     // Get installation for this org
     let gh_repo = GitHubAppRepository2::new(state.pool());
     let installation = gh_repo
-        .get_by_organization(org_id)
+        .get_by_organization(tenant)
         .await
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "GitHub App not installed"))?;
@@ -347,22 +309,12 @@ async fn fetch_repositories(
     axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
     Path(org_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    // Check user is member of organization
-    let org_repo = OrganizationRepository::new(state.pool());
-    org_repo
-        .assert_membership(org_id, ctx.user.id)
-        .await
-        .map_err(|e| match e {
-            IdentityError::PermissionDenied | IdentityError::NotFound => {
-                ErrorResponse::new(StatusCode::FORBIDDEN, "Access denied")
-            }
-            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
-        })?;
+    let tenant = ensure_member_access(state.pool(), org_id, ctx.user.id).await?;
 
     let gh_repo = GitHubAppRepository2::new(state.pool());
 
     let installation = gh_repo
-        .get_by_organization(org_id)
+        .get_by_organization(tenant)
         .await
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "GitHub App not installed"))?;
@@ -419,24 +371,11 @@ async fn bulk_update_review_enabled(
     Path(org_id): Path<Uuid>,
     Json(payload): Json<UpdateRepoReviewEnabledRequest>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
-    // Check user is admin of organization
-    let org_repo = OrganizationRepository::new(state.pool());
-    org_repo
-        .assert_admin(org_id, ctx.user.id)
-        .await
-        .map_err(|e| match e {
-            IdentityError::PermissionDenied => {
-                ErrorResponse::new(StatusCode::FORBIDDEN, "Admin access required")
-            }
-            IdentityError::NotFound => {
-                ErrorResponse::new(StatusCode::NOT_FOUND, "Organization not found")
-            }
-            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
-        })?;
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
 
     let gh_repo = GitHubAppRepository2::new(state.pool());
     let installation = gh_repo
-        .get_by_organization(org_id)
+        .get_by_organization(tenant)
         .await
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "GitHub App not installed"))?;
@@ -553,7 +492,8 @@ async fn handle_callback(
     // Create installation record
     if let Err(e) = gh_repo
         .create_installation(
-            pending.organization_id,
+            // Recorded when an admin requested the install URL.
+            TenantId::trusted(pending.organization_id, TenantSource::PendingInstallation),
             installation_id,
             &installation_info.account.login,
             &installation_info.account.account_type,
@@ -623,15 +563,29 @@ async fn handle_webhook(
         .and_then(|v| v.to_str().ok())
         .unwrap_or("");
 
-    if !verify_webhook_signature(
+    if let Err(e) = verify_hmac_sha256_hex(
         github_app.webhook_secret().expose_secret().as_bytes(),
+        "sha256=",
         signature,
         &body,
     ) {
-        warn!("Invalid webhook signature");
+        warn!(error = %e, "Invalid webhook signature");
         return StatusCode::UNAUTHORIZED.into_response();
     }
 
+    // GitHub redelivers with the same id; only the first delivery is handled.
+    if let Some(delivery_id) = headers
+        .get("X-GitHub-Delivery")
+        .and_then(|v| v.to_str().ok())
+        && state
+            .webhook_deliveries()
+            .check_and_insert(delivery_id)
+            .is_err()
+    {
+        info!(delivery_id, "Ignoring replayed webhook delivery");
+        return StatusCode::OK.into_response();
+    }
+
     // Get event type
     let event_type = headers
         .get("X-GitHub-Event")
@@ -655,6 +609,8 @@ async fn handle_webhook(
         "installation_repositories" => handle_installation_repos_event(&state, &payload).await,
         "pull_request" => handle_pull_request_event(&state, github_app, &payload).await,
         "issue_comment" => handle_issue_comment_event(&state, github_app, &payload).await,
+        "pull_request_review" => handle_pull_request_review_event(&state, &payload).await,
+        "check_suite" => handle_check_suite_event(&state, &payload).await,
         _ => {
             info!(event_type, "Ignoring unhandled webhook event");
             StatusCode::OK.into_response()
@@ -943,6 +899,14 @@ async fn handle_pull_request_event(
 ) -> Response {
     let action = payload["action"].as_str().unwrap_or("");
 
+    if matches!(action, "opened" | "edited" | "reopened" | "closed") {
+        link_pull_request_to_tasks(state, payload).await;
+    }
+
+    if action == "closed" && payload["pull_request"]["merged"].as_bool() == Some(true) {
+        reopen_reverted_pull_request(state, payload).await;
+    }
+
     if action != "opened" {
         return StatusCode::OK.into_response();
     }
@@ -990,6 +954,131 @@ async fn handle_pull_request_event(
     StatusCode::OK.into_response()
 }
 
+/// The organization whose app installation sent the webhook, unless the
+/// installation is unknown or suspended.
+async fn installation_organization(state: &AppState, payload: &serde_json::Value) -> Option<Uuid> {
+    let installation_id = payload["installation"]["id"].as_i64().unwrap_or(0);
+    match GitHubAppRepository2::new(state.pool())
+        .get_by_github_id(installation_id)
+        .await
+    {
+        Ok(Some(installation)) if installation.suspended_at.is_none() => {
+            Some(installation.organization_id)
+        }
+        Ok(_) => {
+            info!(
+                installation_id,
+                "Ignoring webhook from unknown or suspended installation"
+            );
+            None
+        }
+        Err(e) => {
+            error!(?e, installation_id, "Failed to look up installation");
+            None
+        }
+    }
+}
+
+/// Link the pull request to issues named by `Vibe-Kanban-Task` trailers in
+/// its body, within the organization that installed the app. An edited body
+/// may have dropped trailers, so edits are reconciled even without any.
+async fn link_pull_request_to_tasks(state: &AppState, payload: &serde_json::Value) {
+    let pull_request = &payload["pull_request"];
+    let body = pull_request["body"].as_str().unwrap_or("");
+    if parse_task_trailers(body).is_empty() && payload["action"].as_str() != Some("edited") {
+        return;
+    }
+
+    let Some(organization_id) = installation_organization(state, payload).await else {
+        return;
+    };
+
+    let merged = pull_request["merged"].as_bool().unwrap_or(false);
+    let status = if merged {
+        PullRequestStatus::Merged
+    } else if pull_request["state"].as_str() == Some("closed") {
+        PullRequestStatus::Closed
+    } else {
+        PullRequestStatus::Open
+    };
+    let pr = WebhookPullRequest {
+        url: pull_request["html_url"].as_str().unwrap_or("").to_string(),
+        number: pull_request["number"].as_i64().unwrap_or(0) as i32,
+        body: body.to_string(),
+        author_association: pull_request["author_association"]
+            .as_str()
+            .unwrap_or("NONE")
+            .to_string(),
+        status,
+        merged_at: pull_request["merged_at"]
+            .as_str()
+            .and_then(|merged_at| merged_at.parse().ok()),
+        merge_commit_sha: merged
+            .then(|| pull_request["merge_commit_sha"].as_str())
+            .flatten()
+            .map(str::to_string),
+        target_branch_name: pull_request["base"]["ref"]
+            .as_str()
+            .unwrap_or("main")
+            .to_string(),
+    };
+    if pr.url.is_empty() {
+        return;
+    }
+
+    if let Err(e) = link_trailer_issues(state.pool(), organization_id, &pr).await {
+        error!(?e, url = %pr.url, "Failed to link pull request from task trailer");
+    }
+}
+
+/// Reopen the issues of the pull request a merged revert undid.
+async fn reopen_reverted_pull_request(state: &AppState, payload: &serde_json::Value) {
+    let Some(pr_url) = reverted_pull_request_url(
+        payload["repository"]["html_url"].as_str().unwrap_or(""),
+        payload["repository"]["full_name"].as_str().unwrap_or(""),
+        payload["pull_request"]["body"].as_str().unwrap_or(""),
+    ) else {
+        return;
+    };
+    reopen_failed(state, payload, WorkFailure::Reverted { pr_url }).await;
+}
+
+/// Reopen the issues of pull requests merged as a commit whose CI failed on
+/// the default branch.
+async fn handle_check_suite_event(state: &AppState, payload: &serde_json::Value) -> Response {
+    let check_suite = &payload["check_suite"];
+    let failed = matches!(
+        check_suite["conclusion"].as_str(),
+        Some("failure" | "timed_out")
+    );
+    let on_default_branch = check_suite["head_branch"].as_str().is_some()
+        && check_suite["head_branch"] == payload["repository"]["default_branch"];
+    let sha = check_suite["head_sha"].as_str().unwrap_or("");
+    if payload["action"].as_str() != Some("completed")
+        || !failed
+        || !on_default_branch
+        || sha.is_empty()
+    {
+        return StatusCode::OK.into_response();
+    }
+
+    let failure = WorkFailure::FailedChecks {
+        sha: sha.to_string(),
+    };
+    reopen_failed(state, payload, failure).await;
+
+    StatusCode::OK.into_response()
+}
+
+async fn reopen_failed(state: &AppState, payload: &serde_json::Value, failure: WorkFailure) {
+    let Some(organization_id) = installation_organization(state, payload).await else {
+        return;
+    };
+    if let Err(e) = reopen_failed_work(state.pool(), organization_id, &failure).await {
+        error!(?e, ?failure, "Failed to reopen issues after failed work");
+    }
+}
+
 async fn handle_issue_comment_event(
     state: &AppState,
     github_app: &crate::github_app::GitHubAppService,
@@ -1007,14 +1096,40 @@ async fn handle_issue_comment_event(
         return StatusCode::OK.into_response();
     }
 
-    // Check for exact "!reviewfast" trigger
     let comment_body = payload["comment"]["body"].as_str().unwrap_or("").trim();
+    let user_type = payload["comment"]["user"]["type"].as_str().unwrap_or("");
+
+    // Mirror teammates' comments into linked issues, but not commands
+    if comment_body != "!reviewfast" && user_type != "Bot" {
+        let comment = WebhookPrComment {
+            kind: PrCommentKind::Comment,
+            github_id: payload["comment"]["id"].as_i64().unwrap_or(0),
+            html_url: payload["comment"]["html_url"]
+                .as_str()
+                .unwrap_or("")
+                .to_string(),
+            author_github_id: payload["comment"]["user"]["id"].as_i64().unwrap_or(0),
+            author_login: payload["comment"]["user"]["login"]
+                .as_str()
+                .unwrap_or("")
+                .to_string(),
+            body: comment_body.to_string(),
+            pr_url: payload["issue"]["pull_request"]["html_url"]
+                .as_str()
+                .unwrap_or("")
+                .to_string(),
+            pr_number: payload["issue"]["number"].as_i64().unwrap_or(0),
+        };
+        mirror_comment(state, payload, comment).await;
+        return StatusCode::OK.into_response();
+    }
+
+    // Check for exact "!reviewfast" trigger
     if comment_body != "!reviewfast" {
         return StatusCode::OK.into_response();
     }
 
     // Ignore bot comments to prevent loops
-    let user_type = payload["comment"]["user"]["type"].as_str().unwrap_or("");
     if user_type == "Bot" {
         info!("Ignoring !reviewfast from bot user");
         return StatusCode::OK.into_response();
@@ -1047,6 +1162,60 @@ async fn handle_issue_comment_event(
     StatusCode::OK.into_response()
 }
 
+async fn handle_pull_request_review_event(
+    state: &AppState,
+    payload: &serde_json::Value,
+) -> Response {
+    let review = &payload["review"];
+    let body = review["body"].as_str().unwrap_or("").trim();
+    // Reviews without a summary are only inline comments, which stay on GitHub
+    if payload["action"].as_str() != Some("submitted")
+        || body.is_empty()
+        || review["user"]["type"].as_str() == Some("Bot")
+    {
+        return StatusCode::OK.into_response();
+    }
+
+    let comment = WebhookPrComment {
+        kind: PrCommentKind::Review(ReviewState::parse(review["state"].as_str().unwrap_or(""))),
+        github_id: review["id"].as_i64().unwrap_or(0),
+        html_url: review["html_url"].as_str().unwrap_or("").to_string(),
+        author_github_id: review["user"]["id"].as_i64().unwrap_or(0),
+        author_login: review["user"]["login"].as_str().unwrap_or("").to_string(),
+        body: body.to_string(),
+        pr_url: payload["pull_request"]["html_url"]
+            .as_str()
+            .unwrap_or("")
+            .to_string(),
+        pr_number: payload["pull_request"]["number"].as_i64().unwrap_or(0),
+    };
+    mirror_comment(state, payload, comment).await;
+
+    StatusCode::OK.into_response()
+}
+
+/// Copy a pull request comment into the threads of the issues the pull
+/// request is linked to in the installing organization.
+async fn mirror_comment(state: &AppState, payload: &serde_json::Value, comment: WebhookPrComment) {
+    if comment.github_id == 0 || comment.pr_url.is_empty() {
+        return;
+    }
+    let Some(organization_id) = installation_organization(state, payload).await else {
+        return;
+    };
+
+    if let Err(e) = mirror_pr_comment(
+        state.pool(),
+        organization_id,
+        &comment,
+        state.config().payload_limits.max_text_bytes,
+    )
+    .await
+    {
+        error!(?e, url = %comment.html_url, "Failed to mirror pull request comment");
+    }
+}
+
 // ========== Debug Endpoint ==========
 
 /// Parse a GitHub PR URL into (owner, repo, pr_number)
@@ -0,0 +1,266 @@
+//! `Idempotency-Key` support for mutation routes.
+//!
+//! The first `POST` with a given key runs normally and its response is
+//! stored; retries with the same key and body replay that response instead
+//! of running again. Reusing a key for a different request is rejected, and
+//! a retry that arrives while the original is still running gets 409 until
+//! the claim's lease runs out; once a retry has taken a lapsed claim over,
+//! the original request can no longer store or release the key. Server
+//! errors are not stored, so the client can retry them. Responses too large
+//! to keep are stored as their status only, and replayed with an empty body.
+//! Requests without the header are unaffected.
+
+use axum::{
+    body::{Body, Bytes, HttpBody, to_bytes},
+    extract::State,
+    http::{HeaderValue, Method, Request, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::error::ErrorResponse;
+use crate::{AppState, auth::RequestContext, db::idempotency_keys::IdempotencyKeyRepository};
+
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+const MAX_KEY_LEN: usize = 255;
+/// Larger responses are stored as their status only.
+const MAX_RESPONSE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Must run after `require_session` so the user is known.
+pub(crate) async fn idempotency(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let Some(key) = key
+        .to_str()
+        .ok()
+        .map(str::trim)
+        .filter(|key| !key.is_empty() && key.len() <= MAX_KEY_LEN)
+        .map(str::to_owned)
+    else {
+        return ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "Idempotency-Key must be 1-255 visible characters",
+        )
+        .into_response();
+    };
+    let Some(user_id) = request
+        .extensions()
+        .get::<RequestContext>()
+        .map(|ctx| ctx.user.id)
+    else {
+        return next.run(request).await;
+    };
+
+    let (parts, body) = request.into_parts();
+    let max_body_bytes = state.config().payload_limits.max_body_bytes;
+    let Ok(body) = to_bytes(body, max_body_bytes).await else {
+        return ErrorResponse::payload_too_large("request body", max_body_bytes).into_response();
+    };
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map_or(parts.uri.path(), |path_and_query| path_and_query.as_str());
+    let request_hash = request_hash(&parts.method, path_and_query, &body);
+
+    let token = match IdempotencyKeyRepository::claim(state.pool(), user_id, &key, &request_hash)
+        .await
+    {
+        Ok(Some(token)) => token,
+        Ok(None) => return replay(&state, user_id, &key, &request_hash).await,
+        Err(error) => {
+            tracing::error!(?error, "failed to claim idempotency key");
+            return ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+                .into_response();
+        }
+    };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    let claim = Claim {
+        user_id,
+        key: &key,
+        token,
+    };
+    store(&state, &claim, response).await
+}
+
+/// A key this request holds, as returned by [`IdempotencyKeyRepository::claim`].
+struct Claim<'a> {
+    user_id: Uuid,
+    key: &'a str,
+    token: Uuid,
+}
+
+/// Hex SHA-256 over the method, path, query and body, so a key reused for a
+/// different request is detected.
+fn request_hash(method: &Method, path_and_query: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str().as_bytes());
+    hasher.update(b" ");
+    hasher.update(path_and_query.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+async fn replay(state: &AppState, user_id: Uuid, key: &str, request_hash: &str) -> Response {
+    let record = match IdempotencyKeyRepository::find(state.pool(), user_id, key).await {
+        Ok(Some(record)) => record,
+        // Released between our claim attempt and now; let the client retry.
+        Ok(None) => {
+            return ErrorResponse::new(
+                StatusCode::CONFLICT,
+                "request with this Idempotency-Key is in progress",
+            )
+            .into_response();
+        }
+        Err(error) => {
+            tracing::error!(?error, "failed to load idempotency key");
+            return ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+                .into_response();
+        }
+    };
+
+    if record.request_hash != request_hash {
+        return ErrorResponse::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Idempotency-Key was already used for a different request",
+        )
+        .into_response();
+    }
+
+    let Some(status) = record
+        .status_code
+        .and_then(|code| u16::try_from(code).ok())
+        .and_then(|code| StatusCode::from_u16(code).ok())
+    else {
+        return ErrorResponse::new(
+            StatusCode::CONFLICT,
+            "request with this Idempotency-Key is in progress",
+        )
+        .into_response();
+    };
+
+    let mut response = Response::new(Body::from(record.response_body.unwrap_or_default()));
+    *response.status_mut() = status;
+    if let Some(content_type) = record
+        .content_type
+        .and_then(|value| HeaderValue::from_str(&value).ok())
+    {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, content_type);
+    }
+    response
+        .headers_mut()
+        .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+/// Record `response` against the key, or release the key for server errors.
+async fn store(state: &AppState, claim: &Claim<'_>, response: Response) -> Response {
+    let (parts, body) = response.into_parts();
+    if parts.status.is_server_error() {
+        release(state, claim).await;
+        return Response::from_parts(parts, body);
+    }
+
+    // The mutation has committed by now, so a body too large to keep still
+    // has to be recorded; otherwise a retry would run it a second time.
+    let fits = body
+        .size_hint()
+        .upper()
+        .is_some_and(|upper| upper <= MAX_RESPONSE_BYTES as u64);
+    if !fits {
+        tracing::warn!("response too large to store for idempotency; storing status only");
+        complete(state, claim, parts.status, None, None).await;
+        return Response::from_parts(parts, body);
+    }
+
+    let body: Bytes = match to_bytes(body, MAX_RESPONSE_BYTES).await {
+        Ok(body) => body,
+        Err(error) => {
+            tracing::warn!(?error, "failed to read response for idempotency");
+            release(state, claim).await;
+            return ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+                .into_response();
+        }
+    };
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    complete(state, claim, parts.status, content_type, Some(&body)).await;
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+async fn complete(
+    state: &AppState,
+    claim: &Claim<'_>,
+    status: StatusCode,
+    content_type: Option<&str>,
+    body: Option<&[u8]>,
+) {
+    if let Err(error) = IdempotencyKeyRepository::complete(
+        state.pool(),
+        claim.user_id,
+        claim.key,
+        claim.token,
+        i32::from(status.as_u16()),
+        content_type,
+        body,
+    )
+    .await
+    {
+        tracing::error!(?error, "failed to store idempotent response");
+        release(state, claim).await;
+    }
+}
+
+async fn release(state: &AppState, claim: &Claim<'_>) {
+    if let Err(error) =
+        IdempotencyKeyRepository::release(state.pool(), claim.user_id, claim.key, claim.token).await
+    {
+        tracing::error!(?error, "failed to release idempotency key");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+
+    use super::request_hash;
+
+    #[test]
+    fn hash_covers_method_path_query_and_body() {
+        let base = request_hash(&Method::POST, "/issues", b"{\"title\":\"a\"}");
+
+        assert_eq!(
+            base,
+            request_hash(&Method::POST, "/issues", b"{\"title\":\"a\"}")
+        );
+        assert_ne!(
+            base,
+            request_hash(&Method::POST, "/issues", b"{\"title\":\"b\"}")
+        );
+        assert_ne!(
+            base,
+            request_hash(&Method::POST, "/tags", b"{\"title\":\"a\"}")
+        );
+        assert_ne!(
+            base,
+            request_hash(&Method::POST, "/issues?dry_run=true", b"{\"title\":\"a\"}")
+        );
+    }
+}
@@ -10,6 +10,9 @@ struct IdentityResponse {
     pub user_id: Uuid,
     pub username: Option<String>,
     pub email: String,
+    /// Auth session of the calling device, as listed in organization
+    /// trusted-device policies.
+    pub session_id: Uuid,
 }
 
 pub(super) fn router() -> Router<AppState> {
@@ -23,5 +26,6 @@ async fn get_identity(Extension(ctx): Extension<RequestContext>) -> Json<Identit
         user_id: user.id,
         username: user.username,
         email: user.email,
+        session_id: ctx.session_id,
     })
 }
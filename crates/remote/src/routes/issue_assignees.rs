@@ -11,8 +11,9 @@ use tracing::instrument;
 use uuid::Uuid;
 
 use super::{
+    dry_run::{DryRunQuery, finish_tx},
     error::{ErrorResponse, db_error},
-    organization_members::ensure_issue_access,
+    organization_members::{ensure_issue_access, ensure_issue_creator_write_access, tenant_tx},
 };
 use crate::{
     AppState,
@@ -94,28 +95,35 @@ async fn get_issue_assignee(
 async fn create_issue_assignee(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
+    Query(DryRunQuery { dry_run }): Query<DryRunQuery>,
     Json(payload): Json<CreateIssueAssigneeRequest>,
 ) -> Result<Json<MutationResponse<IssueAssignee>>, ErrorResponse> {
-    let organization_id = ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
-
-    let response = IssueAssigneeRepository::create(
-        state.pool(),
-        payload.id,
-        payload.issue_id,
-        payload.user_id,
-    )
-    .await
-    .map_err(|error| {
-        tracing::error!(?error, "failed to create issue assignee");
-        db_error(error, "failed to create issue assignee")
-    })?;
-
-    if payload.user_id != ctx.user.id
-        && let Ok(Some(issue)) = IssueRepository::find_by_id(state.pool(), payload.issue_id).await
+    let access =
+        ensure_issue_creator_write_access(state.pool(), ctx.user.id, payload.issue_id).await?;
+    let tenant = access.tenant;
+
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    access.confirm(&mut tx, payload.issue_id, None).await?;
+
+    let data =
+        IssueAssigneeRepository::insert(&mut *tx, payload.id, payload.issue_id, payload.user_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to create issue assignee");
+                db_error(error, "failed to create issue assignee")
+            })?;
+
+    let txid = finish_tx(tx, dry_run).await?;
+    let response = MutationResponse { data, txid };
+
+    if !dry_run
+        && payload.user_id != ctx.user.id
+        && let Ok(Some(issue)) =
+            IssueRepository::find_in_tenant(state.pool(), tenant, payload.issue_id).await
     {
         notify_user(
             state.pool(),
-            organization_id,
+            tenant,
             ctx.user.id,
             payload.user_id,
             &issue,
@@ -152,21 +160,31 @@ async fn delete_issue_assignee(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue assignee not found"))?;
 
-    let organization_id = ensure_issue_access(state.pool(), ctx.user.id, assignee.issue_id).await?;
+    let access =
+        ensure_issue_creator_write_access(state.pool(), ctx.user.id, assignee.issue_id).await?;
+    let tenant = access.tenant;
 
-    let response = IssueAssigneeRepository::delete(state.pool(), issue_assignee_id)
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    access
+        .confirm(&mut tx, assignee.issue_id, Some(issue_assignee_id))
+        .await?;
+    IssueAssigneeRepository::delete(&mut *tx, issue_assignee_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete issue assignee");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
+    let response = DeleteResponse {
+        txid: finish_tx(tx, false).await?,
+    };
 
     if assignee.user_id != ctx.user.id
-        && let Ok(Some(issue)) = IssueRepository::find_by_id(state.pool(), assignee.issue_id).await
+        && let Ok(Some(issue)) =
+            IssueRepository::find_in_tenant(state.pool(), tenant, assignee.issue_id).await
     {
         notify_user(
             state.pool(),
-            organization_id,
+            tenant,
             ctx.user.id,
             assignee.user_id,
             &issue,
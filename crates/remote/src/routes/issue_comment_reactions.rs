@@ -13,7 +13,7 @@ use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
-    organization_members::ensure_issue_access,
+    organization_members::{ensure_issue_access, ensure_issue_write_access},
 };
 use crate::{
     AppState,
@@ -21,7 +21,7 @@ use crate::{
     db::{
         issue_comment_reactions::IssueCommentReactionRepository,
         issue_comments::IssueCommentRepository, issues::IssueRepository,
-        organization_members::is_member,
+        organization_members::is_member, tenant::TenantId,
     },
     mutation_definition::MutationBuilder,
     notifications::send_issue_notifications,
@@ -47,7 +47,7 @@ pub fn router() -> axum::Router<AppState> {
 
 async fn notify_comment_author_about_reaction(
     state: &AppState,
-    organization_id: Uuid,
+    tenant: TenantId,
     actor_user_id: Uuid,
     comment: &IssueComment,
     emoji: &str,
@@ -57,20 +57,22 @@ async fn notify_comment_author_about_reaction(
     };
 
     if comment_author_id == actor_user_id
-        || !is_member(state.pool(), organization_id, comment_author_id)
+        || !is_member(state.pool(), tenant.id(), comment_author_id)
             .await
             .unwrap_or(false)
     {
         return;
     }
 
-    let Ok(Some(issue)) = IssueRepository::find_by_id(state.pool(), comment.issue_id).await else {
+    let Ok(Some(issue)) =
+        IssueRepository::find_in_tenant(state.pool(), tenant, comment.issue_id).await
+    else {
         return;
     };
 
     send_issue_notifications(
         state.pool(),
-        organization_id,
+        tenant,
         actor_user_id,
         &[comment_author_id],
         &issue,
@@ -172,7 +174,7 @@ async fn create_issue_comment_reaction(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "comment not found"))?;
 
-    let organization_id = ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    let tenant = ensure_issue_write_access(state.pool(), ctx.user.id, comment.issue_id).await?;
 
     let response = IssueCommentReactionRepository::create(
         state.pool(),
@@ -189,7 +191,7 @@ async fn create_issue_comment_reaction(
 
     notify_comment_author_about_reaction(
         &state,
-        organization_id,
+        tenant,
         ctx.user.id,
         &comment,
         &response.data.emoji,
@@ -234,7 +236,7 @@ async fn update_issue_comment_reaction(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "comment not found"))?;
 
-    let organization_id = ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    let tenant = ensure_issue_write_access(state.pool(), ctx.user.id, comment.issue_id).await?;
 
     let response = IssueCommentReactionRepository::update(
         state.pool(),
@@ -249,7 +251,7 @@ async fn update_issue_comment_reaction(
 
     notify_comment_author_about_reaction(
         &state,
-        organization_id,
+        tenant,
         ctx.user.id,
         &comment,
         &response.data.emoji,
@@ -293,7 +295,7 @@ async fn delete_issue_comment_reaction(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "comment not found"))?;
 
-    ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    ensure_issue_write_access(state.pool(), ctx.user.id, comment.issue_id).await?;
 
     let response = IssueCommentReactionRepository::delete(state.pool(), issue_comment_reaction_id)
         .await
@@ -12,8 +12,8 @@ use tracing::instrument;
 use uuid::Uuid;
 
 use super::{
-    error::{ErrorResponse, db_error},
-    organization_members::ensure_issue_access,
+    error::{ErrorResponse, check_text_size, db_error},
+    organization_members::{ensure_issue_access, ensure_issue_write_access},
 };
 use crate::{
     AppState,
@@ -22,6 +22,7 @@ use crate::{
         issue_comments::IssueCommentRepository, issues::IssueRepository,
         organization_members::check_user_role,
     },
+    mentions::notify_mentions,
     mutation_definition::MutationBuilder,
     notifications::notify_issue_subscribers,
 };
@@ -102,7 +103,12 @@ async fn create_issue_comment(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreateIssueCommentRequest>,
 ) -> Result<Json<MutationResponse<IssueComment>>, ErrorResponse> {
-    let organization_id = ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
+    let tenant = ensure_issue_write_access(state.pool(), ctx.user.id, payload.issue_id).await?;
+    check_text_size(
+        "comment",
+        Some(&payload.message),
+        state.config().payload_limits.max_text_bytes,
+    )?;
 
     let is_reply = payload.parent_id.is_some();
 
@@ -127,18 +133,19 @@ async fn create_issue_comment(
             serde_json::json!({
                 "comment_id": response.data.id,
                 "issue_id": response.data.issue_id,
-                "organization_id": organization_id,
+                "organization_id": tenant.id(),
                 "is_reply": is_reply,
             }),
         );
     }
 
-    if let Ok(Some(issue)) = IssueRepository::find_by_id(state.pool(), response.data.issue_id).await
+    if let Ok(Some(issue)) =
+        IssueRepository::find_in_tenant(state.pool(), tenant, response.data.issue_id).await
     {
         let comment_preview = response.data.message.chars().take(100).collect::<String>();
         notify_issue_subscribers(
             state.pool(),
-            organization_id,
+            tenant,
             ctx.user.id,
             &issue,
             NotificationType::IssueCommentAdded,
@@ -149,6 +156,15 @@ async fn create_issue_comment(
             Some(response.data.id),
         )
         .await;
+        notify_mentions(
+            state.pool(),
+            tenant,
+            ctx.user.id,
+            &issue,
+            &response.data.message,
+            Some(response.data.id),
+        )
+        .await;
     }
 
     Ok(Json(response))
@@ -176,13 +192,18 @@ async fn update_issue_comment(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue comment not found"))?;
 
-    let organization_id = ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    let tenant = ensure_issue_write_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    check_text_size(
+        "comment",
+        payload.message.as_deref(),
+        state.config().payload_limits.max_text_bytes,
+    )?;
 
     let is_author = comment
         .author_id
         .map(|id| id == ctx.user.id)
         .unwrap_or(false);
-    let is_admin = check_user_role(state.pool(), organization_id, ctx.user.id)
+    let is_admin = check_user_role(state.pool(), tenant.id(), ctx.user.id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to check user role");
@@ -205,6 +226,20 @@ async fn update_issue_comment(
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
 
+    if let Ok(Some(issue)) =
+        IssueRepository::find_in_tenant(state.pool(), tenant, comment.issue_id).await
+    {
+        notify_mentions(
+            state.pool(),
+            tenant,
+            ctx.user.id,
+            &issue,
+            &response.data.message,
+            Some(response.data.id),
+        )
+        .await;
+    }
+
     Ok(Json(response))
 }
 
@@ -229,13 +264,13 @@ async fn delete_issue_comment(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue comment not found"))?;
 
-    let organization_id = ensure_issue_access(state.pool(), ctx.user.id, comment.issue_id).await?;
+    let tenant = ensure_issue_write_access(state.pool(), ctx.user.id, comment.issue_id).await?;
 
     let is_author = comment
         .author_id
         .map(|id| id == ctx.user.id)
         .unwrap_or(false);
-    let is_admin = check_user_role(state.pool(), organization_id, ctx.user.id)
+    let is_admin = check_user_role(state.pool(), tenant.id(), ctx.user.id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to check user role");
@@ -12,7 +12,7 @@ use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
-    organization_members::ensure_issue_access,
+    organization_members::{ensure_issue_access, ensure_issue_write_access},
 };
 use crate::{
     AppState,
@@ -95,7 +95,7 @@ async fn create_issue_follower(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreateIssueFollowerRequest>,
 ) -> Result<Json<MutationResponse<IssueFollower>>, ErrorResponse> {
-    ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
+    ensure_issue_write_access(state.pool(), ctx.user.id, payload.issue_id).await?;
 
     let response = IssueFollowerRepository::create(
         state.pool(),
@@ -133,7 +133,7 @@ async fn delete_issue_follower(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue follower not found"))?;
 
-    ensure_issue_access(state.pool(), ctx.user.id, follower.issue_id).await?;
+    ensure_issue_write_access(state.pool(), ctx.user.id, follower.issue_id).await?;
 
     let response = IssueFollowerRepository::delete(state.pool(), issue_follower_id)
         .await
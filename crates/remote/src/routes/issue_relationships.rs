@@ -12,12 +12,12 @@ use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
-    organization_members::ensure_issue_access,
+    organization_members::{ensure_issue_access, ensure_issue_write_access},
 };
 use crate::{
     AppState,
     auth::RequestContext,
-    db::issue_relationships::IssueRelationshipRepository,
+    db::issue_relationships::{IssueRelationshipError, IssueRelationshipRepository},
     mutation_definition::{MutationBuilder, NoUpdate},
 };
 
@@ -100,19 +100,25 @@ async fn create_issue_relationship(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreateIssueRelationshipRequest>,
 ) -> Result<Json<MutationResponse<IssueRelationship>>, ErrorResponse> {
-    ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
+    let tenant = ensure_issue_write_access(state.pool(), ctx.user.id, payload.issue_id).await?;
 
     let response = IssueRelationshipRepository::create(
         state.pool(),
+        tenant,
         payload.id,
         payload.issue_id,
         payload.related_issue_id,
         payload.relationship_type,
     )
     .await
-    .map_err(|error| {
-        tracing::error!(?error, "failed to create issue relationship");
-        db_error(error, "failed to create issue relationship")
+    .map_err(|error| match error {
+        IssueRelationshipError::BlockingCycle => {
+            ErrorResponse::new(StatusCode::CONFLICT, error.to_string())
+        }
+        IssueRelationshipError::Database(_) => {
+            tracing::error!(?error, "failed to create issue relationship");
+            db_error(error, "failed to create issue relationship")
+        }
     })?;
 
     Ok(Json(response))
@@ -139,9 +145,10 @@ async fn delete_issue_relationship(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue relationship not found"))?;
 
-    ensure_issue_access(state.pool(), ctx.user.id, relationship.issue_id).await?;
+    let tenant =
+        ensure_issue_write_access(state.pool(), ctx.user.id, relationship.issue_id).await?;
 
-    let response = IssueRelationshipRepository::delete(state.pool(), issue_relationship_id)
+    let response = IssueRelationshipRepository::delete(state.pool(), tenant, issue_relationship_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete issue relationship");
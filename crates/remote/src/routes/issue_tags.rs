@@ -12,7 +12,7 @@ use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
-    organization_members::ensure_issue_access,
+    organization_members::{ensure_issue_access, ensure_issue_write_access},
 };
 use crate::{
     AppState,
@@ -95,7 +95,7 @@ async fn create_issue_tag(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreateIssueTagRequest>,
 ) -> Result<Json<MutationResponse<IssueTag>>, ErrorResponse> {
-    ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
+    ensure_issue_write_access(state.pool(), ctx.user.id, payload.issue_id).await?;
 
     let response =
         IssueTagRepository::create(state.pool(), payload.id, payload.issue_id, payload.tag_id)
@@ -129,7 +129,7 @@ async fn delete_issue_tag(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue tag not found"))?;
 
-    ensure_issue_access(state.pool(), ctx.user.id, issue_tag.issue_id).await?;
+    ensure_issue_write_access(state.pool(), ctx.user.id, issue_tag.issue_id).await?;
 
     let response = IssueTagRepository::delete(state.pool(), issue_tag_id)
         .await
@@ -1,32 +1,56 @@
+use std::collections::HashMap;
+
 use api_types::{
-    CreateIssueRequest, DeleteResponse, Issue, ListIssuesQuery, ListIssuesResponse,
-    MutationResponse, NotificationPayload, NotificationType, SearchIssuesRequest,
-    UpdateIssueRequest,
+    ApplyIssueActionsRequest, ApplyIssueActionsResponse, CreateIssueRequest, CreateSubIssueRequest,
+    DeleteResponse, Issue, IssueAction, IssueActivityEventType, IssueTreeNode,
+    ListDeletedIssuesQuery, ListDeletedIssuesResponse, ListDueSoonIssuesQuery,
+    ListDueSoonIssuesResponse, ListIssueActivityQuery, ListIssueActivityResponse, ListIssuesQuery,
+    ListIssuesResponse, MutationResponse, NotificationPayload, NotificationType,
+    ProjectStatusCategory, SearchIssuesRequest, UpdateIssueRequest,
 };
 use axum::{
     Json,
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
-    routing::post,
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::IntoDeserializer};
+use sqlx::PgConnection;
 use tracing::instrument;
 use uuid::Uuid;
 
 use super::{
-    error::{ErrorResponse, db_error},
-    organization_members::ensure_project_access,
+    activity_cursors::validate_device_id,
+    dry_run::{DryRunQuery, finish_tx},
+    error::{ErrorResponse, check_text_size, db_error},
+    organization_members::{
+        ensure_issue_access, ensure_issue_creator_write_access, ensure_issue_write_access,
+        ensure_project_access, ensure_project_write_access, finish_read, tenant_tx,
+    },
+    preconditions::{check_if_match, etag_header},
 };
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
-        get_txid, issue_followers::IssueFollowerRepository, issues::IssueRepository,
+        activity_cursors::ActivityCursorRepository,
+        get_txid,
+        issue_activity::{IssueActivityFilter, IssueActivityRepository},
+        issue_assignees::IssueAssigneeRepository,
+        issue_comments::IssueCommentRepository,
+        issue_followers::IssueFollowerRepository,
+        issue_tags::IssueTagRepository,
+        issues::IssueRepository,
         project_statuses::ProjectStatusRepository,
+        tags::TagRepository,
+        tenant::TenantId,
     },
+    mentions::notify_mentions,
     mutation_definition::MutationBuilder,
     notifications::{
-        collect_issue_recipients, send_debounced_issue_notifications, send_issue_notifications,
+        collect_issue_recipients, notify_issue_subscribers, notify_user,
+        send_debounced_issue_notifications, send_issue_notifications,
     },
 };
 
@@ -46,11 +70,17 @@ pub fn router() -> axum::Router<AppState> {
         .router()
         .route("/issues/search", post(search_issues))
         .route("/issues/bulk", post(bulk_update_issues))
+        .route("/issues/{issue_id}/actions", post(apply_issue_actions))
+        .route("/issues/activity", get(list_issue_activity))
+        .route("/issues/deleted", get(list_deleted_issues))
+        .route("/issues/due-soon", get(list_due_soon_issues))
+        .route("/issues/{issue_id}/sub-issues", post(create_sub_issue))
+        .route("/issues/{issue_id}/tree", get(get_issue_tree))
 }
 
 async fn notify_issue_update_changes(
     state: &AppState,
-    organization_id: Uuid,
+    tenant: TenantId,
     actor_user_id: Uuid,
     old_issue: &Issue,
     new_issue: &Issue,
@@ -60,6 +90,18 @@ async fn notify_issue_update_changes(
     let description_changed = old_issue.description != new_issue.description;
     let priority_changed = old_issue.priority != new_issue.priority;
 
+    if description_changed && let Some(description) = &new_issue.description {
+        notify_mentions(
+            state.pool(),
+            tenant,
+            actor_user_id,
+            new_issue,
+            description,
+            None,
+        )
+        .await;
+    }
+
     let needs_notification =
         status_changed || title_changed || description_changed || priority_changed;
     if !needs_notification {
@@ -67,9 +109,7 @@ async fn notify_issue_update_changes(
     }
 
     let recipients =
-        match collect_issue_recipients(state.pool(), organization_id, new_issue.id, actor_user_id)
-            .await
-        {
+        match collect_issue_recipients(state.pool(), tenant, new_issue.id, actor_user_id).await {
             Ok(recipients) => recipients,
             Err(error) => {
                 tracing::warn!(
@@ -101,7 +141,7 @@ async fn notify_issue_update_changes(
 
         send_issue_notifications(
             state.pool(),
-            organization_id,
+            tenant,
             actor_user_id,
             &recipients,
             new_issue,
@@ -122,7 +162,7 @@ async fn notify_issue_update_changes(
     if title_changed {
         send_debounced_issue_notifications(
             state.pool(),
-            organization_id,
+            tenant,
             actor_user_id,
             &recipients,
             new_issue,
@@ -140,7 +180,7 @@ async fn notify_issue_update_changes(
     if description_changed {
         send_debounced_issue_notifications(
             state.pool(),
-            organization_id,
+            tenant,
             actor_user_id,
             &recipients,
             new_issue,
@@ -155,7 +195,7 @@ async fn notify_issue_update_changes(
     if priority_changed {
         send_debounced_issue_notifications(
             state.pool(),
-            organization_id,
+            tenant,
             actor_user_id,
             &recipients,
             new_issue,
@@ -183,25 +223,50 @@ async fn list_issues(
     Query(query): Query<ListIssuesQuery>,
 ) -> Result<Json<ListIssuesResponse>, ErrorResponse> {
     let project_id = query.project_id;
-    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    if query.after_updated_at.is_some() || query.limit.is_some() {
+        if query.status_id.is_some() || query.assignee_user_id.is_some() || query.q.is_some() {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "filters are not supported with cursor paging",
+            ));
+        }
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_ISSUE_PAGE_SIZE)
+            .clamp(1, MAX_ISSUE_PAGE_SIZE);
+        let after = query
+            .after_updated_at
+            .map(|updated_at| (updated_at, query.after_id.unwrap_or(Uuid::nil())));
+        let response = IssueRepository::list_page(state.pool(), tenant, project_id, after, limit)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, project_id = %project_id, "failed to list issues");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list issues")
+            })?;
+        return Ok(Json(response));
+    }
+
     let request = SearchIssuesRequest {
         project_id,
-        status_id: None,
+        status_id: query.status_id,
         status_ids: None,
         priority: None,
         parent_issue_id: None,
         search: None,
+        q: query.q,
         simple_id: None,
-        assignee_user_id: None,
+        assignee_user_id: query.assignee_user_id,
         tag_id: None,
         tag_ids: None,
-        sort_field: None,
-        sort_direction: None,
+        sort_field: query.sort_field,
+        sort_direction: query.sort_direction,
         limit: None,
         offset: None,
     };
 
-    let response = IssueRepository::search(state.pool(), &request)
+    let response = IssueRepository::search(state.pool(), tenant, &request)
         .await
         .map_err(|error| {
             tracing::error!(?error, project_id = %project_id, "failed to list issues");
@@ -221,9 +286,9 @@ async fn search_issues(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<SearchIssuesRequest>,
 ) -> Result<Json<ListIssuesResponse>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
 
-    let response = IssueRepository::search(state.pool(), &payload)
+    let response = IssueRepository::search(state.pool(), tenant, &payload)
         .await
         .map_err(|error| {
             tracing::error!(?error, project_id = %payload.project_id, "failed to search issues");
@@ -233,27 +298,512 @@ async fn search_issues(
     Ok(Json(response))
 }
 
+/// Page sizes for cursor-paged issue listing.
+const DEFAULT_ISSUE_PAGE_SIZE: i32 = 500;
+const MAX_ISSUE_PAGE_SIZE: i32 = 1000;
+
+/// Page size for the raw activity feed.
+const ACTIVITY_PAGE_SIZE: i64 = 500;
+
+/// Incremental feed of a project's `issue_activity` events, paged by
+/// `latest_seq` and optionally filtered by event types and creation time.
+/// Clients that acknowledge what they processed via `/activity/cursor` can
+/// pass their `device_id` instead of `since_seq` to resume from there.
+/// Events above the commit-safe watermark are held back until every event
+/// below them has committed, so the cursor never skips a late commit.
 #[instrument(
-    name = "issues.get_issue",
+    name = "issues.list_issue_activity",
+    skip(state, ctx),
+    fields(
+        project_id = %query.project_id,
+        since_seq = ?query.since_seq,
+        event_type = ?query.event_type,
+        event_types = ?query.event_types,
+        user_id = %ctx.user.id
+    )
+)]
+async fn list_issue_activity(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<ListIssueActivityQuery>,
+) -> Result<Json<ListIssueActivityResponse>, ErrorResponse> {
+    let project_id = query.project_id;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    if let (Some(since), Some(until)) = (query.since, query.until)
+        && since >= until
+    {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "`since` must be earlier than `until`",
+        ));
+    }
+
+    let since_seq = match (query.since_seq, &query.device_id) {
+        (Some(since_seq), _) => since_seq,
+        (None, Some(device_id)) => {
+            validate_device_id(device_id)?;
+            ActivityCursorRepository::find(state.pool(), ctx.user.id, device_id, project_id)
+                .await
+                .map_err(|error| {
+                    tracing::error!(?error, %project_id, "failed to load activity cursor");
+                    ErrorResponse::new(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "failed to list issue activity",
+                    )
+                })?
+                .map_or(0, |cursor| cursor.seq)
+        }
+        (None, None) => 0,
+    };
+    let mut event_types = parse_event_types(query.event_types.as_deref())?;
+    if query.event_type == Some(IssueActivityEventType::Unknown) {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "unknown event type",
+        ));
+    }
+    event_types.extend(query.event_type);
+    let filter = IssueActivityFilter {
+        event_types,
+        since: query.since,
+        until: query.until,
+        include_project_events: query.include_project_events.unwrap_or(false),
+    };
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let safe_seq = activity_safe_seq(&mut tx, project_id, "failed to list issue activity").await?;
+    ensure_cursor_not_purged(
+        &mut tx,
+        project_id,
+        since_seq,
+        "failed to list issue activity",
+    )
+    .await?;
+    let activity = IssueActivityRepository::list_since(
+        &mut *tx,
+        project_id,
+        since_seq,
+        safe_seq,
+        &filter,
+        ACTIVITY_PAGE_SIZE,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, %project_id, "failed to list issue activity");
+        ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to list issue activity",
+        )
+    })?;
+    let replays = match query.replays_after {
+        Some(after_id) => IssueActivityRepository::replays_after(
+            &mut *tx,
+            project_id,
+            after_id,
+            ACTIVITY_PAGE_SIZE,
+        )
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to list activity replays");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to list issue activity",
+            )
+        })?,
+        None => Vec::new(),
+    };
+    finish_read(tx).await?;
+
+    let has_more = activity.len() as i64 == ACTIVITY_PAGE_SIZE;
+    let latest_seq = activity.last().map_or(since_seq, |event| event.seq);
+    metrics::counter!(crate::prometheus::ACTIVITY_FEED_EVENTS_TOTAL)
+        .increment(activity.len() as u64);
+    if has_more {
+        metrics::counter!(crate::prometheus::ACTIVITY_CATCH_UP_PAGES_TOTAL).increment(1);
+    }
+
+    Ok(Json(ListIssueActivityResponse {
+        activity,
+        latest_seq,
+        has_more,
+        replays,
+    }))
+}
+
+/// [`IssueActivityRepository::safe_seq`], failing the request with `message`.
+async fn activity_safe_seq(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    message: &'static str,
+) -> Result<i64, ErrorResponse> {
+    IssueActivityRepository::safe_seq(conn)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load activity watermark");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+        })
+}
+
+/// Reject a cursor below the project's purge horizon with 410 Gone: the
+/// deletions recorded in between were purged, so a client resuming from it
+/// would keep issues that no longer exist. It has to drop what it mirrored
+/// and start over from seq 0, which is always accepted.
+async fn ensure_cursor_not_purged(
+    conn: &mut PgConnection,
+    project_id: Uuid,
+    since_seq: i64,
+    message: &'static str,
+) -> Result<(), ErrorResponse> {
+    let purged_seq = IssueActivityRepository::purged_seq(conn, project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load activity purge horizon");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+        })?;
+    if since_seq > 0 && since_seq < purged_seq {
+        return Err(ErrorResponse::new(
+            StatusCode::GONE,
+            format!("activity before seq {purged_seq} was purged; sync again from seq 0"),
+        ));
+    }
+    Ok(())
+}
+
+/// Parse a comma-separated list of event types, ignoring empty entries.
+fn parse_event_types(raw: Option<&str>) -> Result<Vec<IssueActivityEventType>, ErrorResponse> {
+    raw.unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            let event_type: Result<_, serde::de::value::Error> =
+                IssueActivityEventType::deserialize(name.into_deserializer());
+            match event_type {
+                Ok(event_type) if event_type != IssueActivityEventType::Unknown => Ok(event_type),
+                _ => Err(ErrorResponse::new(
+                    StatusCode::BAD_REQUEST,
+                    format!("unknown event type `{name}`"),
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Incremental deletion feed backed by `issue_activity` tombstones. Clients keep
+/// `latest_seq` as a cursor instead of diffing full issue ID sets.
+#[instrument(
+    name = "issues.list_deleted_issues",
+    skip(state, ctx),
+    fields(project_id = %query.project_id, since_seq = ?query.since_seq, user_id = %ctx.user.id)
+)]
+async fn list_deleted_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<ListDeletedIssuesQuery>,
+) -> Result<Json<ListDeletedIssuesResponse>, ErrorResponse> {
+    let project_id = query.project_id;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    // The cursor stops at the commit-safe watermark, so a deletion that
+    // commits after this request is returned next time rather than skipped.
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let safe_seq = activity_safe_seq(&mut tx, project_id, "failed to list deleted issues").await?;
+    let since_seq = query.since_seq.unwrap_or(0);
+    ensure_cursor_not_purged(
+        &mut tx,
+        project_id,
+        since_seq,
+        "failed to list deleted issues",
+    )
+    .await?;
+    let latest_seq = IssueActivityRepository::latest_seq(&mut *tx, project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load activity cursor");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to list deleted issues",
+            )
+        })?
+        .min(safe_seq);
+
+    let tombstones =
+        IssueActivityRepository::list_tombstones_since(&mut *tx, project_id, since_seq, safe_seq)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %project_id, "failed to list issue tombstones");
+                ErrorResponse::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to list deleted issues",
+                )
+            })?;
+    finish_read(tx).await?;
+
+    let latest_seq = tombstones
+        .last()
+        .map_or(latest_seq, |tombstone| tombstone.seq.max(latest_seq));
+
+    Ok(Json(ListDeletedIssuesResponse {
+        tombstones,
+        latest_seq,
+    }))
+}
+
+/// Incremental feed of `due_soon` reminders; `latest_seq` works like the
+/// deletion feed's cursor.
+#[instrument(
+    name = "issues.list_due_soon_issues",
+    skip(state, ctx),
+    fields(project_id = %query.project_id, since_seq = ?query.since_seq, user_id = %ctx.user.id)
+)]
+async fn list_due_soon_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<ListDueSoonIssuesQuery>,
+) -> Result<Json<ListDueSoonIssuesResponse>, ErrorResponse> {
+    let project_id = query.project_id;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let safe_seq = activity_safe_seq(&mut tx, project_id, "failed to list due issues").await?;
+    let latest_seq = IssueActivityRepository::latest_seq(&mut *tx, project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load activity cursor");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to list due issues",
+            )
+        })?
+        .min(safe_seq);
+
+    let reminders = IssueActivityRepository::list_due_soon_since(
+        &mut *tx,
+        project_id,
+        query.since_seq.unwrap_or(0),
+        safe_seq,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, %project_id, "failed to list due reminders");
+        ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to list due issues",
+        )
+    })?;
+    finish_read(tx).await?;
+
+    let latest_seq = reminders
+        .last()
+        .map_or(latest_seq, |reminder| reminder.seq.max(latest_seq));
+
+    Ok(Json(ListDueSoonIssuesResponse {
+        reminders,
+        latest_seq,
+    }))
+}
+
+/// Create an issue under `issue_id` in the same project.
+#[instrument(
+    name = "issues.create_sub_issue",
+    skip(state, ctx, payload),
+    fields(parent_issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn create_sub_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+    dry_run: Query<DryRunQuery>,
+    Json(payload): Json<CreateSubIssueRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let tenant = ensure_issue_write_access(state.pool(), ctx.user.id, issue_id).await?;
+    let max_text_bytes = state.config().payload_limits.max_text_bytes;
+    check_text_size("title", Some(&payload.title), max_text_bytes)?;
+    check_text_size(
+        "description",
+        payload.description.as_deref(),
+        max_text_bytes,
+    )?;
+    let parent = load_issue(&state, tenant, issue_id).await?;
+
+    let status_id = match payload.status_id {
+        Some(status_id) => {
+            let status = ProjectStatusRepository::find_by_id(state.pool(), status_id)
+                .await
+                .map_err(|error| {
+                    tracing::error!(?error, %status_id, "failed to load status");
+                    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load status")
+                })?;
+            if status.is_none_or(|status| status.project_id != parent.project_id) {
+                return Err(ErrorResponse::new(
+                    StatusCode::BAD_REQUEST,
+                    "status does not belong to the parent issue's project",
+                ));
+            }
+            status_id
+        }
+        None => ProjectStatusRepository::find_by_category(
+            state.pool(),
+            parent.project_id,
+            ProjectStatusCategory::Todo,
+        )
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, project_id = %parent.project_id, "failed to load status");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load status")
+        })?
+        .map_or(parent.status_id, |status| status.id),
+    };
+
+    create_issue(
+        State(state),
+        Extension(ctx),
+        dry_run,
+        Json(CreateIssueRequest {
+            id: payload.id,
+            project_id: parent.project_id,
+            status_id,
+            title: payload.title,
+            description: payload.description,
+            priority: payload.priority,
+            start_date: None,
+            target_date: None,
+            completed_at: None,
+            sort_order: parent.sort_order,
+            parent_issue_id: Some(parent.id),
+            parent_issue_sort_order: None,
+            extension_metadata: serde_json::json!({}),
+        }),
+    )
+    .await
+}
+
+/// The issue with all of its sub-issues and server-computed completion rollup.
+#[instrument(
+    name = "issues.get_issue_tree",
     skip(state, ctx),
     fields(issue_id = %issue_id, user_id = %ctx.user.id)
 )]
-async fn get_issue(
+async fn get_issue_tree(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
     Path(issue_id): Path<Uuid>,
-) -> Result<Json<Issue>, ErrorResponse> {
-    let issue = IssueRepository::find_by_id(state.pool(), issue_id)
+) -> Result<Json<IssueTreeNode>, ErrorResponse> {
+    let tenant = ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
+    let root = load_issue(&state, tenant, issue_id).await?;
+
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let descendants = IssueRepository::find_descendants(&mut *tx, issue_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %issue_id, "failed to load sub-issues");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load issue tree",
+            )
+        })?;
+    finish_read(tx).await?;
+
+    let statuses = ProjectStatusRepository::list_by_project(state.pool(), root.project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, project_id = %root.project_id, "failed to load statuses");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load issue tree",
+            )
+        })?;
+    let status_categories: HashMap<Uuid, ProjectStatusCategory> = statuses
+        .into_iter()
+        .filter_map(|status| Some((status.id, status.category?)))
+        .collect();
+
+    Ok(Json(build_issue_tree(
+        root,
+        descendants,
+        &status_categories,
+    )))
+}
+
+async fn load_issue(
+    state: &AppState,
+    tenant: TenantId,
+    issue_id: Uuid,
+) -> Result<Issue, ErrorResponse> {
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let issue = IssueRepository::find_by_id(&mut *tx, issue_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, %issue_id, "failed to load issue");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load issue")
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+    finish_read(tx).await?;
+    Ok(issue)
+}
+
+/// Assemble `root` and its `descendants` (in sibling order) into a tree,
+/// rolling completion up from the leaves. `status_categories` maps status IDs
+/// to their categories; statuses without one count as neither done nor
+/// cancelled.
+fn build_issue_tree(
+    root: Issue,
+    descendants: Vec<Issue>,
+    status_categories: &HashMap<Uuid, ProjectStatusCategory>,
+) -> IssueTreeNode {
+    let mut children_by_parent: HashMap<Uuid, Vec<Issue>> = HashMap::new();
+    for issue in descendants {
+        if let Some(parent_id) = issue.parent_issue_id {
+            children_by_parent.entry(parent_id).or_default().push(issue);
+        }
+    }
+    build_node(root, &mut children_by_parent, status_categories)
+}
+
+fn build_node(
+    issue: Issue,
+    children_by_parent: &mut HashMap<Uuid, Vec<Issue>>,
+    status_categories: &HashMap<Uuid, ProjectStatusCategory>,
+) -> IssueTreeNode {
+    let children: Vec<IssueTreeNode> = children_by_parent
+        .remove(&issue.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|child| build_node(child, children_by_parent, status_categories))
+        .collect();
 
-    ensure_project_access(state.pool(), ctx.user.id, issue.project_id).await?;
+    let category = |issue: &Issue| status_categories.get(&issue.status_id).copied();
+    let counted: Vec<&IssueTreeNode> = children
+        .iter()
+        .filter(|child| category(&child.issue) != Some(ProjectStatusCategory::Cancelled))
+        .collect();
+    let completed_children = counted.iter().filter(|child| child.done).count();
+    let done = category(&issue) == Some(ProjectStatusCategory::Done)
+        && completed_children == counted.len();
+
+    IssueTreeNode {
+        done,
+        total_children: counted.len() as i32,
+        completed_children: completed_children as i32,
+        issue,
+        children,
+    }
+}
+
+#[instrument(
+    name = "issues.get_issue",
+    skip(state, ctx),
+    fields(issue_id = %issue_id, user_id = %ctx.user.id)
+)]
+async fn get_issue(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let tenant = ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
+    let issue = load_issue(&state, tenant, issue_id).await?;
 
-    Ok(Json(issue))
+    Ok((etag_header(issue.updated_at), Json(issue)))
 }
 
 #[instrument(
@@ -264,18 +814,32 @@ async fn get_issue(
 async fn create_issue(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
+    Query(DryRunQuery { dry_run }): Query<DryRunQuery>,
     Json(payload): Json<CreateIssueRequest>,
-) -> Result<Json<MutationResponse<Issue>>, ErrorResponse> {
-    let organization_id =
-        ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let tenant = ensure_project_write_access(state.pool(), ctx.user.id, payload.project_id).await?;
+    let max_text_bytes = state.config().payload_limits.max_text_bytes;
+    check_text_size("title", Some(&payload.title), max_text_bytes)?;
+    check_text_size(
+        "description",
+        payload.description.as_deref(),
+        max_text_bytes,
+    )?;
 
     let has_parent = payload.parent_issue_id.is_some();
     let has_description = payload.description.is_some();
     let priority = payload.priority;
     let parent_issue_id = payload.parent_issue_id;
 
-    let response = IssueRepository::create(
-        state.pool(),
+    let mut tx = crate::db::begin_tenant_tx(state.pool(), tenant)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to begin transaction");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    let data = IssueRepository::insert(
+        &mut *tx,
         payload.id,
         payload.project_id,
         payload.status_id,
@@ -297,6 +861,13 @@ async fn create_issue(
         db_error(error, "failed to create issue")
     })?;
 
+    let txid = finish_tx(tx, dry_run).await?;
+    let response = MutationResponse { data, txid };
+    let etag = etag_header(response.data.updated_at);
+    if dry_run {
+        return Ok((etag, Json(response)));
+    }
+
     // Auto-follow: the creator should receive notifications for all activity on this issue.
     if let Err(e) =
         IssueFollowerRepository::create(state.pool(), None, response.data.id, ctx.user.id).await
@@ -304,6 +875,18 @@ async fn create_issue(
         tracing::warn!(?e, issue_id = %response.data.id, "failed to auto-follow issue for creator");
     }
 
+    if let Some(description) = &response.data.description {
+        notify_mentions(
+            state.pool(),
+            tenant,
+            ctx.user.id,
+            &response.data,
+            description,
+            None,
+        )
+        .await;
+    }
+
     if let Some(analytics) = state.analytics() {
         analytics.track(
             ctx.user.id,
@@ -311,7 +894,7 @@ async fn create_issue(
             serde_json::json!({
                 "issue_id": response.data.id,
                 "project_id": response.data.project_id,
-                "organization_id": organization_id,
+                "organization_id": tenant.id(),
                 "has_description": has_description,
                 "has_parent": has_parent,
                 "priority": format!("{:?}", priority),
@@ -326,13 +909,13 @@ async fn create_issue(
                     "issue_id": response.data.id,
                     "parent_issue_id": parent_id,
                     "project_id": response.data.project_id,
-                    "organization_id": organization_id,
+                    "organization_id": tenant.id(),
                 }),
             );
         }
     }
 
-    Ok(Json(response))
+    Ok((etag, Json(response)))
 }
 
 #[instrument(
@@ -344,58 +927,70 @@ async fn update_issue(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
     Path(issue_id): Path<Uuid>,
+    Query(DryRunQuery { dry_run }): Query<DryRunQuery>,
+    headers: HeaderMap,
     Json(payload): Json<UpdateIssueRequest>,
-) -> Result<Json<MutationResponse<Issue>>, ErrorResponse> {
-    let issue = IssueRepository::find_by_id(state.pool(), issue_id)
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let access = ensure_issue_creator_write_access(state.pool(), ctx.user.id, issue_id).await?;
+    let tenant = access.tenant;
+    let issue = load_issue(&state, tenant, issue_id).await?;
+    let max_text_bytes = state.config().payload_limits.max_text_bytes;
+    check_text_size("title", payload.title.as_deref(), max_text_bytes)?;
+    check_text_size(
+        "description",
+        payload.description.value().map(String::as_str),
+        max_text_bytes,
+    )?;
+
+    let mut tx = crate::db::begin_tenant_tx(state.pool(), tenant)
         .await
         .map_err(|error| {
-            tracing::error!(?error, %issue_id, "failed to load issue");
+            tracing::error!(?error, "failed to begin transaction");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    // Check the precondition against the locked row so a concurrent writer
+    // can't slip in between the check and the update.
+    let current_updated_at = IssueRepository::lock_for_update(&mut tx, issue_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %issue_id, "failed to lock issue");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load issue")
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
-
-    let organization_id =
-        ensure_project_access(state.pool(), ctx.user.id, issue.project_id).await?;
-
-    let mut tx = crate::db::begin_tx(state.pool()).await.map_err(|error| {
-        tracing::error!(?error, "failed to begin transaction");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-    })?;
+    check_if_match(&headers, current_updated_at)?;
+    access.confirm(&mut tx, issue_id, None).await?;
 
     let data = IssueRepository::update(
         &mut *tx,
         issue_id,
         payload.status_id,
         payload.title,
-        payload.description,
-        payload.priority,
-        payload.start_date,
-        payload.target_date,
-        payload.completed_at,
+        payload.description.into_option(),
+        payload.priority.into_option(),
+        payload.start_date.into_option(),
+        payload.target_date.into_option(),
+        payload.completed_at.into_option(),
         payload.sort_order,
-        payload.parent_issue_id,
-        payload.parent_issue_sort_order,
+        payload.parent_issue_id.into_option(),
+        payload.parent_issue_sort_order.into_option(),
         payload.extension_metadata,
     )
     .await
     .map_err(|error| {
         tracing::error!(?error, "failed to update issue");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        db_error(error, "failed to update issue")
     })?;
 
-    let txid = get_txid(&mut *tx).await.map_err(|error| {
-        tracing::error!(?error, "failed to get txid");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-    })?;
-
-    tx.commit().await.map_err(|error| {
-        tracing::error!(?error, "failed to commit transaction");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-    })?;
-
-    notify_issue_update_changes(&state, organization_id, ctx.user.id, &issue, &data).await;
+    let txid = finish_tx(tx, dry_run).await?;
+    if !dry_run {
+        notify_issue_update_changes(&state, tenant, ctx.user.id, &issue, &data).await;
+    }
 
-    Ok(Json(MutationResponse { data, txid }))
+    Ok((
+        etag_header(data.updated_at),
+        Json(MutationResponse { data, txid }),
+    ))
 }
 
 #[instrument(
@@ -407,47 +1002,56 @@ async fn delete_issue(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
     Path(issue_id): Path<Uuid>,
+    headers: HeaderMap,
 ) -> Result<Json<DeleteResponse>, ErrorResponse> {
-    let issue = IssueRepository::find_by_id(state.pool(), issue_id)
+    let access = ensure_issue_creator_write_access(state.pool(), ctx.user.id, issue_id).await?;
+    let tenant = access.tenant;
+    let issue = load_issue(&state, tenant, issue_id).await?;
+
+    let recipients =
+        match collect_issue_recipients(state.pool(), tenant, issue.id, ctx.user.id).await {
+            Ok(recipients) => recipients,
+            Err(error) => {
+                tracing::warn!(
+                    ?error,
+                    issue_id = %issue.id,
+                    "failed to collect notification recipients"
+                );
+                vec![]
+            }
+        };
+
+    let mut tx = crate::db::begin_tenant_tx(state.pool(), tenant)
         .await
         .map_err(|error| {
-            tracing::error!(?error, %issue_id, "failed to load issue");
+            tracing::error!(?error, "failed to begin transaction");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    // As in update: compare against the locked row, not the earlier read.
+    let current_updated_at = IssueRepository::lock_for_update(&mut tx, issue_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %issue_id, "failed to lock issue");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load issue")
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+    check_if_match(&headers, current_updated_at)?;
+    access.confirm(&mut tx, issue_id, None).await?;
 
-    let organization_id =
-        ensure_project_access(state.pool(), ctx.user.id, issue.project_id).await?;
-
-    let recipients = match collect_issue_recipients(
-        state.pool(),
-        organization_id,
-        issue.id,
-        ctx.user.id,
-    )
-    .await
-    {
-        Ok(recipients) => recipients,
-        Err(error) => {
-            tracing::warn!(
-                ?error,
-                issue_id = %issue.id,
-                "failed to collect notification recipients"
-            );
-            vec![]
-        }
-    };
-
-    let response = IssueRepository::delete(state.pool(), issue_id)
+    IssueRepository::delete(&mut *tx, issue_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete issue");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
+    let response = DeleteResponse {
+        txid: finish_tx(tx, false).await?,
+    };
 
     send_issue_notifications(
         state.pool(),
-        organization_id,
+        tenant,
         ctx.user.id,
         &recipients,
         &issue,
@@ -500,22 +1104,29 @@ async fn bulk_update_issues(
         }));
     }
 
-    // Get first issue to determine project_id for access check
-    let first_issue = IssueRepository::find_by_id(state.pool(), payload.updates[0].id)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, "failed to find first issue");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to find issue")
-        })?
-        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+    let max_text_bytes = state.config().payload_limits.max_text_bytes;
+    for item in &payload.updates {
+        check_text_size("title", item.changes.title.as_deref(), max_text_bytes)?;
+        check_text_size(
+            "description",
+            item.changes.description.value().map(String::as_str),
+            max_text_bytes,
+        )?;
+    }
 
-    let project_id = first_issue.project_id;
-    let organization_id = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+    // The first issue determines the project the whole batch must belong to
+    let first_issue_id = payload.updates[0].id;
+    let access =
+        ensure_issue_creator_write_access(state.pool(), ctx.user.id, first_issue_id).await?;
+    let tenant = access.tenant;
+    let project_id = load_issue(&state, tenant, first_issue_id).await?.project_id;
 
-    let mut tx = crate::db::begin_tx(state.pool()).await.map_err(|error| {
-        tracing::error!(?error, "failed to begin transaction");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-    })?;
+    let mut tx = crate::db::begin_tenant_tx(state.pool(), tenant)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to begin transaction");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
 
     let mut results = Vec::with_capacity(payload.updates.len());
     let mut notification_pairs = Vec::with_capacity(payload.updates.len());
@@ -536,6 +1147,7 @@ async fn bulk_update_issues(
                 "all issues must belong to the same project",
             ));
         }
+        access.confirm(&mut tx, item.id, None).await?;
 
         // Update the issue
         let updated = IssueRepository::update(
@@ -543,14 +1155,14 @@ async fn bulk_update_issues(
             item.id,
             item.changes.status_id,
             item.changes.title,
-            item.changes.description,
-            item.changes.priority,
-            item.changes.start_date,
-            item.changes.target_date,
-            item.changes.completed_at,
+            item.changes.description.into_option(),
+            item.changes.priority.into_option(),
+            item.changes.start_date.into_option(),
+            item.changes.target_date.into_option(),
+            item.changes.completed_at.into_option(),
             item.changes.sort_order,
-            item.changes.parent_issue_id,
-            item.changes.parent_issue_sort_order,
+            item.changes.parent_issue_id.into_option(),
+            item.changes.parent_issue_sort_order.into_option(),
             item.changes.extension_metadata,
         )
         .await
@@ -573,8 +1185,7 @@ async fn bulk_update_issues(
     })?;
 
     for (old_issue, new_issue) in &notification_pairs {
-        notify_issue_update_changes(&state, organization_id, ctx.user.id, old_issue, new_issue)
-            .await;
+        notify_issue_update_changes(&state, tenant, ctx.user.id, old_issue, new_issue).await;
     }
 
     Ok(Json(BulkUpdateIssuesResponse {
@@ -582,3 +1193,274 @@ async fn bulk_update_issues(
         txid,
     }))
 }
+
+// =============================================================================
+// Batched Actions
+// =============================================================================
+
+#[instrument(
+    name = "issues.apply_actions",
+    skip(state, ctx, payload),
+    fields(issue_id = %issue_id, user_id = %ctx.user.id, count = payload.actions.len())
+)]
+async fn apply_issue_actions(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_id): Path<Uuid>,
+    Json(payload): Json<ApplyIssueActionsRequest>,
+) -> Result<Json<ApplyIssueActionsResponse>, ErrorResponse> {
+    let tenant = ensure_issue_write_access(state.pool(), ctx.user.id, issue_id).await?;
+    let issue = load_issue(&state, tenant, issue_id).await?;
+
+    // Reject references to other projects before writing anything.
+    for action in &payload.actions {
+        match action {
+            IssueAction::SetStatus { status_id } => {
+                let status = ProjectStatusRepository::find_by_id(state.pool(), *status_id)
+                    .await
+                    .map_err(|error| {
+                        tracing::error!(?error, %status_id, "failed to load status");
+                        ErrorResponse::new(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            "failed to load status",
+                        )
+                    })?;
+                if status.is_none_or(|status| status.project_id != issue.project_id) {
+                    return Err(ErrorResponse::new(
+                        StatusCode::BAD_REQUEST,
+                        "status does not belong to the issue's project",
+                    ));
+                }
+            }
+            IssueAction::AddTag { tag_id } => {
+                let tag = TagRepository::find_by_id(state.pool(), *tag_id)
+                    .await
+                    .map_err(|error| {
+                        tracing::error!(?error, %tag_id, "failed to load tag");
+                        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load tag")
+                    })?;
+                if tag.is_none_or(|tag| tag.project_id != issue.project_id) {
+                    return Err(ErrorResponse::new(
+                        StatusCode::BAD_REQUEST,
+                        "tag does not belong to the issue's project",
+                    ));
+                }
+            }
+            IssueAction::Comment { message } if message.trim().is_empty() => {
+                return Err(ErrorResponse::new(
+                    StatusCode::BAD_REQUEST,
+                    "comment must not be empty",
+                ));
+            }
+            IssueAction::Comment { message } => {
+                check_text_size(
+                    "comment",
+                    Some(message),
+                    state.config().payload_limits.max_text_bytes,
+                )?;
+            }
+            IssueAction::Assign { .. } => {}
+        }
+    }
+
+    let mut tx = crate::db::begin_tenant_tx(state.pool(), tenant)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to begin transaction");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    let mut updated = issue.clone();
+    let mut assignees = Vec::new();
+    let mut tags = Vec::new();
+    let mut comments = Vec::new();
+
+    for action in payload.actions {
+        match action {
+            IssueAction::SetStatus { status_id } => {
+                updated = IssueRepository::update(
+                    &mut *tx,
+                    issue_id,
+                    Some(status_id),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await
+                .map_err(|error| {
+                    tracing::error!(?error, "failed to update issue status");
+                    db_error(error, "failed to update issue")
+                })?;
+            }
+            IssueAction::Assign { user_id } => {
+                let assignee = IssueAssigneeRepository::insert(&mut *tx, None, issue_id, user_id)
+                    .await
+                    .map_err(|error| {
+                        tracing::error!(?error, "failed to create issue assignee");
+                        db_error(error, "failed to create issue assignee")
+                    })?;
+                assignees.push(assignee);
+            }
+            IssueAction::AddTag { tag_id } => {
+                let tag = IssueTagRepository::insert(&mut *tx, None, issue_id, tag_id)
+                    .await
+                    .map_err(|error| {
+                        tracing::error!(?error, "failed to create issue tag");
+                        db_error(error, "failed to create issue tag")
+                    })?;
+                tags.push(tag);
+            }
+            IssueAction::Comment { message } => {
+                let comment = IssueCommentRepository::insert(
+                    &mut *tx,
+                    None,
+                    issue_id,
+                    ctx.user.id,
+                    None,
+                    message,
+                )
+                .await
+                .map_err(|error| {
+                    tracing::error!(?error, "failed to create issue comment");
+                    db_error(error, "failed to create issue comment")
+                })?;
+                comments.push(comment);
+            }
+        }
+    }
+
+    let txid = get_txid(&mut *tx).await.map_err(|error| {
+        tracing::error!(?error, "failed to get txid");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+    tx.commit().await.map_err(|error| {
+        tracing::error!(?error, "failed to commit transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    notify_issue_update_changes(&state, tenant, ctx.user.id, &issue, &updated).await;
+    for assignee in &assignees {
+        if assignee.user_id != ctx.user.id {
+            notify_user(
+                state.pool(),
+                tenant,
+                ctx.user.id,
+                assignee.user_id,
+                &updated,
+                NotificationType::IssueAssigneeChanged,
+                NotificationPayload {
+                    assignee_user_id: Some(assignee.user_id),
+                    ..Default::default()
+                },
+            )
+            .await;
+        }
+    }
+    for comment in &comments {
+        notify_issue_subscribers(
+            state.pool(),
+            tenant,
+            ctx.user.id,
+            &updated,
+            NotificationType::IssueCommentAdded,
+            NotificationPayload {
+                comment_preview: Some(comment.message.chars().take(100).collect()),
+                ..Default::default()
+            },
+            Some(comment.id),
+        )
+        .await;
+    }
+
+    Ok(Json(ApplyIssueActionsResponse {
+        issue: updated,
+        assignees,
+        tags,
+        comments,
+        txid,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api_types::{Issue, IssueActivityEventType, ProjectStatusCategory};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use super::{build_issue_tree, parse_event_types};
+
+    fn issue(status_id: Uuid, parent_issue_id: Option<Uuid>) -> Issue {
+        Issue {
+            id: Uuid::new_v4(),
+            project_id: Uuid::nil(),
+            issue_number: 1,
+            simple_id: "VK-1".to_string(),
+            status_id,
+            title: "issue".to_string(),
+            description: None,
+            priority: None,
+            start_date: None,
+            target_date: None,
+            completed_at: None,
+            sort_order: 0.0,
+            parent_issue_id,
+            parent_issue_sort_order: None,
+            extension_metadata: serde_json::json!({}),
+            creator_user_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn parent_is_done_only_when_all_active_children_are_done() {
+        let (todo, done, cancelled) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let status_categories = HashMap::from([
+            (todo, ProjectStatusCategory::Todo),
+            (done, ProjectStatusCategory::Done),
+            (cancelled, ProjectStatusCategory::Cancelled),
+        ]);
+
+        let root = issue(done, None);
+        let child = issue(done, Some(root.id));
+        let grandchild = issue(todo, Some(child.id));
+        let dropped = issue(cancelled, Some(root.id));
+
+        let tree = build_issue_tree(
+            root.clone(),
+            vec![child.clone(), dropped.clone(), grandchild.clone()],
+            &status_categories,
+        );
+        assert!(!tree.done);
+        assert_eq!((tree.completed_children, tree.total_children), (0, 1));
+        assert_eq!(tree.children[0].children[0].issue.id, grandchild.id);
+
+        let grandchild = issue(done, Some(child.id));
+        let tree = build_issue_tree(root, vec![child, dropped, grandchild], &status_categories);
+        assert!(tree.done);
+        assert_eq!((tree.completed_children, tree.total_children), (1, 1));
+        assert_eq!(tree.children.len(), 2);
+    }
+
+    #[test]
+    fn event_type_filter_rejects_unknown_names() {
+        assert_eq!(
+            parse_event_types(Some("created, due_soon,")).unwrap(),
+            vec![
+                IssueActivityEventType::Created,
+                IssueActivityEventType::DueSoon
+            ]
+        );
+        assert!(parse_event_types(Some("created,brand_new")).is_err());
+        assert!(parse_event_types(Some("unknown")).is_err());
+    }
+}
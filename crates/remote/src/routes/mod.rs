@@ -1,8 +1,11 @@
-use axum::{Json, Router, http::header::HeaderName, middleware, routing::get};
+use axum::{
+    Json, Router, extract::DefaultBodyLimit, http::header::HeaderName, middleware, routing::get,
+};
 use serde::Serialize;
 use tower_http::{
     compression::CompressionLayer,
     cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer},
+    limit::RequestBodyLimitLayer,
     request_id::{MakeRequestUuid, PropagateRequestIdLayer, RequestId, SetRequestIdLayer},
     services::{ServeDir, ServeFile},
     trace::{DefaultOnFailure, TraceLayer},
@@ -25,12 +28,18 @@ mod billing {
         Router::new()
     }
 }
+mod activity_cursors;
+mod admin;
 pub mod attachments;
+mod board_history;
+mod downloads;
+mod dry_run;
 pub(crate) mod electric_proxy;
 pub(crate) mod error;
 mod export;
 mod github_app;
 pub mod hosts;
+mod idempotency;
 mod identity;
 pub mod issue_assignees;
 pub mod issue_comment_reactions;
@@ -39,14 +48,23 @@ pub mod issue_followers;
 pub mod issue_relationships;
 pub mod issue_tags;
 pub mod issues;
+mod mutations;
 pub mod notifications;
 mod oauth;
+mod organization_access_policies;
+mod organization_automation_policies;
+mod organization_client_configs;
 pub(crate) mod organization_members;
+mod organization_retention_policies;
 mod organizations;
+mod preconditions;
+mod product_updates;
+mod project_reports;
 pub mod project_statuses;
 pub mod projects;
 pub mod pull_request_issues;
 mod pull_requests;
+pub(crate) mod rate_limit;
 mod review;
 pub mod tags;
 mod tokens;
@@ -80,6 +98,7 @@ pub fn router(state: AppState) -> Router {
             if let Some(request_id) = request_id {
                 span.record("request_id", field::display(request_id));
             }
+            crate::trace_context::continue_trace(&span, request.headers());
             span
         })
         .on_response(
@@ -105,16 +124,25 @@ pub fn router(state: AppState) -> Router {
         .merge(oauth::public_router())
         .merge(organization_members::public_router())
         .merge(tokens::public_router())
+        .merge(downloads::public_router())
+        .merge(crate::ws::public_router())
         .merge(review::public_router())
         .merge(github_app::public_router())
         .merge(billing::public_router());
 
     let v1_protected = Router::<AppState>::new()
+        .merge(admin::router())
         .merge(identity::router())
         .merge(hosts::router())
         .merge(projects::router())
+        .merge(project_reports::router())
+        .merge(board_history::router())
         .merge(organizations::router())
         .merge(organization_members::protected_router())
+        .merge(organization_access_policies::router())
+        .merge(organization_automation_policies::router())
+        .merge(organization_client_configs::router())
+        .merge(organization_retention_policies::router())
         .merge(oauth::protected_router())
         .merge(electric_proxy::router())
         .merge(github_app::protected_router())
@@ -123,17 +151,29 @@ pub fn router(state: AppState) -> Router {
         .merge(issue_comments::router())
         .merge(issue_comment_reactions::router())
         .merge(issues::router())
+        .merge(activity_cursors::router())
         .merge(issue_assignees::router())
         .merge(attachments::router())
         .merge(issue_followers::router())
         .merge(issue_tags::router())
         .merge(issue_relationships::router())
+        .merge(mutations::router())
         .merge(pull_request_issues::router())
         .merge(pull_requests::router())
         .merge(notifications::router())
+        .merge(product_updates::router())
         .merge(workspaces::router())
         .merge(billing::protected_router())
         .merge(export::router())
+        .merge(crate::ws::router())
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            idempotency::idempotency,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::rate_limit,
+        ))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             require_session,
@@ -147,6 +187,15 @@ pub fn router(state: AppState) -> Router {
         .nest("/v1", v1_public)
         .nest("/v1", v1_protected)
         .fallback_service(spa)
+        .layer(middleware::from_fn(crate::prometheus::track_requests))
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(
+            state.config().payload_limits.max_body_bytes,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            error::json_payload_too_large,
+        ))
         .layer(CompressionLayer::new())
         .layer(middleware::from_fn(
             crate::middleware::version::add_version_headers,
@@ -156,6 +205,10 @@ pub fn router(state: AppState) -> Router {
                 .allow_origin(AllowOrigin::mirror_request())
                 .allow_methods(AllowMethods::mirror_request())
                 .allow_headers(AllowHeaders::mirror_request())
+                .expose_headers([
+                    axum::http::header::ETAG,
+                    HeaderName::from_static("idempotent-replayed"),
+                ])
                 .allow_credentials(true),
         )
         .layer(trace_layer)
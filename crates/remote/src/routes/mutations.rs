@@ -0,0 +1,342 @@
+use std::collections::{HashMap, HashSet};
+
+use api_types::{
+    BatchMutation, BatchMutationResult, BatchMutationsRequest, BatchMutationsResponse,
+    NotificationPayload, NotificationType,
+};
+use axum::{
+    Json, Router,
+    extract::{Extension, State},
+    http::StatusCode,
+    routing::post,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{
+    dry_run::finish_tx,
+    error::{ErrorResponse, check_text_size, db_error},
+    organization_members::{
+        IssueWriteAccess, ensure_issue_creator_write_access, ensure_issue_write_access,
+        ensure_project_write_access, finish_read, tenant_tx,
+    },
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        issue_assignees::IssueAssigneeRepository,
+        issue_followers::IssueFollowerRepository,
+        issue_relationships::{IssueRelationshipError, IssueRelationshipRepository},
+        issue_tags::IssueTagRepository,
+        issues::IssueRepository,
+        tags::TagRepository,
+        tenant::TenantId,
+    },
+    notifications::notify_user,
+};
+
+const MAX_BATCH_MUTATIONS: usize = 50;
+
+pub(super) fn router() -> Router<AppState> {
+    Router::new().route("/mutations/batch", post(apply_batch))
+}
+
+/// The organization the batch writes to, the projects it touches, checked
+/// once each, and the project of every issue it references, including issues
+/// created earlier in the batch. Issues only assigned to keep the access
+/// from [`ensure_issue_creator_write_access`], confirmed when writing.
+#[derive(Default)]
+struct BatchScope {
+    tenant: Option<TenantId>,
+    accessible_projects: HashSet<Uuid>,
+    issue_projects: HashMap<Uuid, Uuid>,
+    assignable_issues: HashMap<Uuid, IssueWriteAccess>,
+}
+
+impl BatchScope {
+    async fn project(
+        &mut self,
+        state: &AppState,
+        user_id: Uuid,
+        project_id: Uuid,
+    ) -> Result<(), ErrorResponse> {
+        if self.accessible_projects.insert(project_id) {
+            let tenant = ensure_project_write_access(state.pool(), user_id, project_id).await?;
+            self.scope_to(tenant)?;
+        }
+        Ok(())
+    }
+
+    /// The batch runs in one tenant transaction, so every project it
+    /// touches has to belong to the same organization.
+    fn scope_to(&mut self, tenant: TenantId) -> Result<(), ErrorResponse> {
+        match self.tenant {
+            Some(current) if current != tenant => Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "all mutations must belong to the same organization",
+            )),
+            _ => {
+                self.tenant = Some(tenant);
+                Ok(())
+            }
+        }
+    }
+
+    async fn issue_project(
+        &mut self,
+        state: &AppState,
+        user_id: Uuid,
+        issue_id: Uuid,
+    ) -> Result<Uuid, ErrorResponse> {
+        if let Some(project_id) = self.issue_projects.get(&issue_id) {
+            return Ok(*project_id);
+        }
+
+        let tenant = ensure_issue_write_access(state.pool(), user_id, issue_id).await?;
+        self.scope_to(tenant)?;
+        let mut tx = tenant_tx(state.pool(), tenant).await?;
+        let project_id = IssueRepository::find_by_id(&mut *tx, issue_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %issue_id, "failed to load issue");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load issue")
+            })?
+            .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?
+            .project_id;
+        finish_read(tx).await?;
+        self.accessible_projects.insert(project_id);
+        self.issue_projects.insert(issue_id, project_id);
+        Ok(project_id)
+    }
+
+    /// Like [`Self::issue_project`], but lets a viewer assign an unassigned
+    /// issue they created, as the single-item route does.
+    async fn assignable_issue(
+        &mut self,
+        state: &AppState,
+        user_id: Uuid,
+        issue_id: Uuid,
+    ) -> Result<(), ErrorResponse> {
+        if self.issue_projects.contains_key(&issue_id)
+            || self.assignable_issues.contains_key(&issue_id)
+        {
+            return Ok(());
+        }
+
+        let access = ensure_issue_creator_write_access(state.pool(), user_id, issue_id).await?;
+        self.scope_to(access.tenant)?;
+        self.assignable_issues.insert(issue_id, access);
+        Ok(())
+    }
+}
+
+#[instrument(
+    name = "mutations.apply_batch",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id, count = payload.mutations.len())
+)]
+async fn apply_batch(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<BatchMutationsRequest>,
+) -> Result<Json<BatchMutationsResponse>, ErrorResponse> {
+    if payload.mutations.is_empty() {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "no mutations provided",
+        ));
+    }
+    if payload.mutations.len() > MAX_BATCH_MUTATIONS {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            format!("at most {MAX_BATCH_MUTATIONS} mutations per batch"),
+        ));
+    }
+
+    // Authorize and validate everything before writing anything.
+    let max_text_bytes = state.config().payload_limits.max_text_bytes;
+    let mut scope = BatchScope::default();
+    for mutation in &payload.mutations {
+        match mutation {
+            BatchMutation::CreateIssue(request) => {
+                scope
+                    .project(&state, ctx.user.id, request.project_id)
+                    .await?;
+                check_text_size("title", Some(&request.title), max_text_bytes)?;
+                check_text_size(
+                    "description",
+                    request.description.as_deref(),
+                    max_text_bytes,
+                )?;
+                if let Some(parent_issue_id) = request.parent_issue_id {
+                    scope
+                        .issue_project(&state, ctx.user.id, parent_issue_id)
+                        .await?;
+                }
+                if let Some(id) = request.id {
+                    scope.issue_projects.insert(id, request.project_id);
+                }
+            }
+            BatchMutation::AddIssueTag(request) => {
+                let project_id = scope
+                    .issue_project(&state, ctx.user.id, request.issue_id)
+                    .await?;
+                let tag = TagRepository::find_by_id(state.pool(), request.tag_id)
+                    .await
+                    .map_err(|error| {
+                        tracing::error!(?error, tag_id = %request.tag_id, "failed to load tag");
+                        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load tag")
+                    })?;
+                if tag.is_none_or(|tag| tag.project_id != project_id) {
+                    return Err(ErrorResponse::new(
+                        StatusCode::BAD_REQUEST,
+                        "tag does not belong to the issue's project",
+                    ));
+                }
+            }
+            BatchMutation::AddIssueAssignee(request) => {
+                scope
+                    .assignable_issue(&state, ctx.user.id, request.issue_id)
+                    .await?;
+            }
+            BatchMutation::AddIssueRelationship(request) => {
+                scope
+                    .issue_project(&state, ctx.user.id, request.issue_id)
+                    .await?;
+                scope
+                    .issue_project(&state, ctx.user.id, request.related_issue_id)
+                    .await?;
+            }
+        }
+    }
+
+    let Some(tenant) = scope.tenant else {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "no mutations provided",
+        ));
+    };
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+
+    let mut results = Vec::with_capacity(payload.mutations.len());
+    for mutation in payload.mutations {
+        let result = match mutation {
+            BatchMutation::CreateIssue(request) => {
+                let issue = IssueRepository::insert(
+                    &mut *tx,
+                    request.id,
+                    request.project_id,
+                    request.status_id,
+                    request.title,
+                    request.description,
+                    request.priority,
+                    request.start_date,
+                    request.target_date,
+                    request.completed_at,
+                    request.sort_order,
+                    request.parent_issue_id,
+                    request.parent_issue_sort_order,
+                    request.extension_metadata,
+                    ctx.user.id,
+                )
+                .await
+                .map_err(|error| {
+                    tracing::error!(?error, "failed to create issue");
+                    db_error(error, "failed to create issue")
+                })?;
+                BatchMutationResult::Issue(issue)
+            }
+            BatchMutation::AddIssueTag(request) => {
+                let tag = IssueTagRepository::insert(
+                    &mut *tx,
+                    request.id,
+                    request.issue_id,
+                    request.tag_id,
+                )
+                .await
+                .map_err(|error| {
+                    tracing::error!(?error, "failed to create issue tag");
+                    db_error(error, "failed to create issue tag")
+                })?;
+                BatchMutationResult::IssueTag(tag)
+            }
+            BatchMutation::AddIssueAssignee(request) => {
+                if let Some(access) = scope.assignable_issues.get(&request.issue_id) {
+                    access.confirm(&mut tx, request.issue_id, None).await?;
+                }
+                let assignee = IssueAssigneeRepository::insert(
+                    &mut *tx,
+                    request.id,
+                    request.issue_id,
+                    request.user_id,
+                )
+                .await
+                .map_err(|error| {
+                    tracing::error!(?error, "failed to create issue assignee");
+                    db_error(error, "failed to create issue assignee")
+                })?;
+                BatchMutationResult::IssueAssignee(assignee)
+            }
+            BatchMutation::AddIssueRelationship(request) => {
+                let relationship = IssueRelationshipRepository::insert(
+                    &mut tx,
+                    request.id,
+                    request.issue_id,
+                    request.related_issue_id,
+                    request.relationship_type,
+                )
+                .await
+                .map_err(|error| match error {
+                    IssueRelationshipError::BlockingCycle => {
+                        ErrorResponse::new(StatusCode::CONFLICT, error.to_string())
+                    }
+                    IssueRelationshipError::Database(_) => {
+                        tracing::error!(?error, "failed to create issue relationship");
+                        db_error(error, "failed to create issue relationship")
+                    }
+                })?;
+                BatchMutationResult::IssueRelationship(relationship)
+            }
+        };
+        results.push(result);
+    }
+
+    let txid = finish_tx(tx, false).await?;
+
+    // As with single creates, the creator follows every issue they made and
+    // anyone else assigned to an issue is notified.
+    for result in &results {
+        match result {
+            BatchMutationResult::Issue(issue) => {
+                if let Err(error) =
+                    IssueFollowerRepository::create(state.pool(), None, issue.id, ctx.user.id).await
+                {
+                    tracing::warn!(?error, issue_id = %issue.id, "failed to auto-follow issue for creator");
+                }
+            }
+            BatchMutationResult::IssueAssignee(assignee) if assignee.user_id != ctx.user.id => {
+                if let Ok(Some(issue)) =
+                    IssueRepository::find_in_tenant(state.pool(), tenant, assignee.issue_id).await
+                {
+                    notify_user(
+                        state.pool(),
+                        tenant,
+                        ctx.user.id,
+                        assignee.user_id,
+                        &issue,
+                        NotificationType::IssueAssigneeChanged,
+                        NotificationPayload {
+                            assignee_user_id: Some(assignee.user_id),
+                            ..Default::default()
+                        },
+                    )
+                    .await;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Json(BatchMutationsResponse { results, txid }))
+}
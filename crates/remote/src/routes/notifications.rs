@@ -1,9 +1,12 @@
-use api_types::{DeleteResponse, MutationResponse, Notification, UpdateNotificationRequest};
+use api_types::{
+    DeleteResponse, DigestSettings, MutationResponse, Notification, UpdateDigestSettingsRequest,
+    UpdateNotificationRequest,
+};
 use axum::{
     Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
-    routing::post,
+    routing::{get, post},
 };
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
@@ -13,7 +16,7 @@ use super::error::ErrorResponse;
 use crate::{
     AppState,
     auth::RequestContext,
-    db::{get_txid, notifications::NotificationRepository},
+    db::{digest::DigestRepository, get_txid, notifications::NotificationRepository},
     mutation_definition::{MutationBuilder, NoCreate},
 };
 
@@ -58,6 +61,10 @@ pub fn router() -> Router<AppState> {
     mutation()
         .router()
         .route("/notifications/bulk", post(bulk_update_notifications))
+        .route(
+            "/notifications/digest-settings",
+            get(get_digest_settings).put(update_digest_settings),
+        )
 }
 
 #[instrument(
@@ -309,3 +316,53 @@ async fn bulk_update_notifications(
         txid,
     }))
 }
+
+#[instrument(
+    name = "notifications.get_digest_settings",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id)
+)]
+async fn get_digest_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+) -> Result<Json<DigestSettings>, ErrorResponse> {
+    let settings = DigestRepository::settings(state.pool(), ctx.user.id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to load digest settings");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(settings))
+}
+
+#[instrument(
+    name = "notifications.update_digest_settings",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id)
+)]
+async fn update_digest_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<UpdateDigestSettingsRequest>,
+) -> Result<Json<DigestSettings>, ErrorResponse> {
+    if payload
+        .digest_hour_utc
+        .is_some_and(|hour| !(0..24).contains(&hour))
+    {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "`digest_hour_utc` must be between 0 and 23",
+        ));
+    }
+
+    let settings =
+        DigestRepository::update_settings(state.pool(), ctx.user.id, payload.digest_hour_utc)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to update digest settings");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
+
+    Ok(Json(settings))
+}
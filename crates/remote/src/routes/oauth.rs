@@ -7,7 +7,7 @@ use api_types::{
 use axum::{
     Json, Router,
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode, header::USER_AGENT},
     response::{IntoResponse, Redirect, Response},
     routing::{get, post},
 };
@@ -74,11 +74,18 @@ async fn web_init(
 
 async fn web_redeem(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<HandoffRedeemRequest>,
 ) -> Response {
     let handoff = state.handoff();
+    let user_agent = headers.get(USER_AGENT).and_then(|v| v.to_str().ok());
     match handoff
-        .redeem(payload.handoff_id, &payload.app_code, &payload.app_verifier)
+        .redeem(
+            payload.handoff_id,
+            &payload.app_code,
+            &payload.app_verifier,
+            user_agent,
+        )
         .await
     {
         Ok(result) => {
@@ -113,9 +120,11 @@ async fn web_redeem(
 
 async fn local_login(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<LocalLoginRequest>,
 ) -> Result<Json<LocalLoginResponse>, LocalAuthError> {
-    let response = local_login_flow(&state, &payload).await?;
+    let user_agent = headers.get(USER_AGENT).and_then(|v| v.to_str().ok());
+    let response = local_login_flow(&state, &payload, user_agent).await?;
     Ok(Json(response))
 }
 
@@ -0,0 +1,111 @@
+use api_types::{OrganizationAccessPolicy, UpdateOrganizationAccessPolicyRequest};
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::get,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{error::ErrorResponse, organization_members::ensure_admin_access};
+use crate::{
+    AppState,
+    audit::{self, AuditAction, AuditEvent},
+    auth::RequestContext,
+    db::organization_access_policies::{
+        OrganizationAccessPolicyError, OrganizationAccessPolicyRepository, parse_policy,
+    },
+    middleware::client_ip::ClientIp,
+};
+
+pub(super) fn router() -> Router<AppState> {
+    Router::new().route(
+        "/organizations/{org_id}/access-policy",
+        get(get_access_policy).put(update_access_policy),
+    )
+}
+
+#[instrument(
+    name = "organization_access_policies.get_access_policy",
+    skip(state, ctx),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn get_access_policy(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<OrganizationAccessPolicy>, ErrorResponse> {
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let policy = OrganizationAccessPolicyRepository::get(state.pool(), tenant)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %org_id, "failed to load access policy");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load policy")
+        })?;
+
+    Ok(Json(policy))
+}
+
+#[instrument(
+    name = "organization_access_policies.update_access_policy",
+    skip(state, ctx, client_ip, payload),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn update_access_policy(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    ClientIp(client_ip): ClientIp,
+    Json(payload): Json<UpdateOrganizationAccessPolicyRequest>,
+) -> Result<Json<OrganizationAccessPolicy>, ErrorResponse> {
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let (cidrs, device_ids) = parse_policy(&payload.ip_allowlist, &payload.device_ids)
+        .map_err(|error| ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string()))?;
+
+    // Refuse policies that would immediately lock out the admin applying them.
+    if !cidrs.is_empty() && !client_ip.is_some_and(|ip| cidrs.iter().any(|cidr| cidr.contains(ip)))
+    {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "IP allowlist must include your current address",
+        ));
+    }
+    if !device_ids.is_empty() && !device_ids.contains(&ctx.session_id.to_string()) {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "Trusted devices must include your current device",
+        ));
+    }
+
+    let policy =
+        OrganizationAccessPolicyRepository::replace(state.pool(), tenant, &cidrs, &device_ids)
+            .await
+            .map_err(|error| match error {
+                OrganizationAccessPolicyError::Database(error) => {
+                    tracing::error!(?error, %org_id, "failed to update access policy");
+                    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to update policy")
+                }
+                other => ErrorResponse::new(StatusCode::BAD_REQUEST, other.to_string()),
+            })?;
+
+    audit::emit(
+        AuditEvent::from_request(&ctx, AuditAction::OrgAccessPolicyUpdate)
+            .organization(org_id)
+            .resource("organization", Some(org_id))
+            .http(
+                "PUT",
+                format!("/v1/organizations/{org_id}/access-policy"),
+                200,
+            )
+            .description(format!(
+                "{} IP ranges, {} trusted devices",
+                policy.ip_allowlist.len(),
+                policy.device_ids.len()
+            )),
+    );
+
+    Ok(Json(policy))
+}
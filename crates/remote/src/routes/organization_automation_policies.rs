@@ -0,0 +1,89 @@
+use api_types::{OrganizationAutomationPolicy, UpdateOrganizationAutomationPolicyRequest};
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::get,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{error::ErrorResponse, organization_members::ensure_admin_access};
+use crate::{
+    AppState,
+    audit::{self, AuditAction, AuditEvent},
+    auth::RequestContext,
+    db::organization_automation_policies::OrganizationAutomationPolicyRepository,
+};
+
+pub(super) fn router() -> Router<AppState> {
+    Router::new().route(
+        "/organizations/{org_id}/automation-policy",
+        get(get_automation_policy).put(update_automation_policy),
+    )
+}
+
+#[instrument(
+    name = "organization_automation_policies.get_automation_policy",
+    skip(state, ctx),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn get_automation_policy(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<OrganizationAutomationPolicy>, ErrorResponse> {
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let policy = OrganizationAutomationPolicyRepository::get(state.pool(), tenant)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %org_id, "failed to load automation policy");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load policy")
+        })?;
+
+    Ok(Json(policy))
+}
+
+#[instrument(
+    name = "organization_automation_policies.update_automation_policy",
+    skip(state, ctx, payload),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn update_automation_policy(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<UpdateOrganizationAutomationPolicyRequest>,
+) -> Result<Json<OrganizationAutomationPolicy>, ErrorResponse> {
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let policy = OrganizationAutomationPolicyRepository::upsert(
+        state.pool(),
+        tenant,
+        payload.reopen_on_failure,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, %org_id, "failed to update automation policy");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to update policy")
+    })?;
+
+    audit::emit(
+        AuditEvent::from_request(&ctx, AuditAction::OrgAutomationPolicyUpdate)
+            .organization(org_id)
+            .resource("organization", Some(org_id))
+            .http(
+                "PUT",
+                format!("/v1/organizations/{org_id}/automation-policy"),
+                200,
+            )
+            .description(if policy.reopen_on_failure {
+                "reopen on failure enabled"
+            } else {
+                "reopen on failure disabled"
+            }),
+    );
+
+    Ok(Json(policy))
+}
@@ -0,0 +1,143 @@
+use api_types::OrganizationClientConfig;
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::get,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_admin_access, ensure_member_access},
+};
+use crate::{
+    AppState,
+    audit::{self, AuditAction, AuditEvent},
+    auth::RequestContext,
+    db::organization_client_configs::OrganizationClientConfigRepository,
+};
+
+/// Upper bound on status color overrides per organization.
+const MAX_STATUS_COLORS: usize = 200;
+
+pub(super) fn router() -> Router<AppState> {
+    Router::new().route(
+        "/organizations/{org_id}/client-config",
+        get(get_client_config).put(update_client_config),
+    )
+}
+
+/// Readable by every member, since each member's app applies it.
+#[instrument(
+    name = "organization_client_configs.get_client_config",
+    skip(state, ctx),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn get_client_config(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<OrganizationClientConfig>, ErrorResponse> {
+    let tenant = ensure_member_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let config = OrganizationClientConfigRepository::get(state.pool(), tenant)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %org_id, "failed to load client config");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load client config",
+            )
+        })?;
+
+    Ok(Json(config))
+}
+
+#[instrument(
+    name = "organization_client_configs.update_client_config",
+    skip(state, ctx, payload),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn update_client_config(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<OrganizationClientConfig>,
+) -> Result<Json<OrganizationClientConfig>, ErrorResponse> {
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    validate_client_config(&payload)
+        .map_err(|message| ErrorResponse::new(StatusCode::BAD_REQUEST, message))?;
+
+    let config = OrganizationClientConfigRepository::upsert(state.pool(), tenant, &payload)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %org_id, "failed to update client config");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to update client config",
+            )
+        })?;
+
+    audit::emit(
+        AuditEvent::from_request(&ctx, AuditAction::OrgClientConfigUpdate)
+            .organization(org_id)
+            .resource("organization", Some(org_id))
+            .http(
+                "PUT",
+                format!("/v1/organizations/{org_id}/client-config"),
+                200,
+            )
+            .description(format!(
+                "{} status colors, default board filters {}",
+                config.status_colors.len(),
+                if config.default_board_filters.is_some() {
+                    "set"
+                } else {
+                    "cleared"
+                }
+            )),
+    );
+
+    Ok(Json(config))
+}
+
+fn validate_client_config(config: &OrganizationClientConfig) -> Result<(), String> {
+    if config.status_colors.len() > MAX_STATUS_COLORS {
+        return Err(format!(
+            "at most {MAX_STATUS_COLORS} status colors may be configured"
+        ));
+    }
+    for (status, color) in &config.status_colors {
+        if status.trim().is_empty() {
+            return Err("status names must not be empty".to_string());
+        }
+        if !is_hex_color(color) {
+            return Err(format!(
+                "color for status '{status}' must be a #rrggbb hex value"
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn is_hex_color(value: &str) -> bool {
+    value
+        .strip_prefix('#')
+        .is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_hex_color;
+
+    #[test]
+    fn accepts_only_six_digit_hex_colors() {
+        assert!(is_hex_color("#1a2B3c"));
+        assert!(!is_hex_color("1a2b3c"));
+        assert!(!is_hex_color("#abc"));
+        assert!(!is_hex_color("#12345g"));
+    }
+}
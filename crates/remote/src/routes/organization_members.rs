@@ -1,6 +1,6 @@
 use api_types::{
-    ListMembersResponse, MemberRole, OrganizationMemberWithProfile, RevokeInvitationRequest,
-    UpdateMemberRoleRequest, UpdateMemberRoleResponse,
+    ListMembersResponse, MemberRole, RevokeInvitationRequest, UpdateMemberRoleRequest,
+    UpdateMemberRoleResponse,
 };
 use axum::{
     Json, Router,
@@ -11,7 +11,7 @@ use axum::{
 };
 use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use tracing::warn;
 use uuid::Uuid;
 
@@ -21,14 +21,18 @@ use crate::{
     audit::{self, AuditAction, AuditEvent},
     auth::RequestContext,
     db::{
+        self, Tx,
         identity_errors::IdentityError,
         invitations::{Invitation, InvitationRepository},
         issue_comments::IssueCommentRepository,
         issues::IssueRepository,
+        organization_access_policies::enforce_for_current_request,
         organization_members,
         organizations::OrganizationRepository,
         projects::ProjectRepository,
+        tenant::{TenantId, TenantSource},
     },
+    invalidation::Invalidation,
 };
 
 pub(super) fn public_router() -> Router<AppState> {
@@ -102,14 +106,14 @@ async fn create_invitation(
     let org_repo = OrganizationRepository::new(&state.pool);
     let invitation_repo = InvitationRepository::new(&state.pool);
 
-    ensure_admin_access(&state.pool, org_id, user.id).await?;
+    let tenant = ensure_admin_access(&state.pool, org_id, user.id).await?;
 
     let token = Uuid::new_v4().to_string();
     let expires_at = Utc::now() + Duration::days(7);
 
     let invitation = invitation_repo
         .create_invitation(
-            org_id,
+            tenant,
             user.id,
             &payload.email,
             payload.role,
@@ -186,10 +190,10 @@ async fn list_invitations(
     let user = ctx.user;
     let invitation_repo = InvitationRepository::new(&state.pool);
 
-    ensure_admin_access(&state.pool, org_id, user.id).await?;
+    let tenant = ensure_admin_access(&state.pool, org_id, user.id).await?;
 
     let invitations = invitation_repo
-        .list_invitations(org_id, user.id)
+        .list_invitations(tenant, user.id)
         .await
         .map_err(|e| match e {
             IdentityError::PermissionDenied => {
@@ -241,10 +245,10 @@ async fn revoke_invitation(
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let invitation_repo = InvitationRepository::new(&state.pool);
 
-    ensure_admin_access(&state.pool, org_id, ctx.user.id).await?;
+    let tenant = ensure_admin_access(&state.pool, org_id, ctx.user.id).await?;
 
     invitation_repo
-        .revoke_invitation(org_id, payload.invitation_id, ctx.user.id)
+        .revoke_invitation(tenant, payload.invitation_id, ctx.user.id)
         .await
         .map_err(|e| match e {
             IdentityError::PermissionDenied => {
@@ -292,6 +296,11 @@ async fn accept_invitation(
             _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
         })?;
 
+    state.invalidations().publish(Invalidation::Membership {
+        organization_id: org.id,
+        user_id: user.id,
+    });
+
     audit::emit(
         AuditEvent::system(AuditAction::MemberAcceptInvite)
             .user(user.id, Some(session_id))
@@ -325,37 +334,11 @@ async fn list_members(
     Path(org_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, ErrorResponse> {
     let user = ctx.user;
-    ensure_member_access(&state.pool, org_id, user.id).await?;
+    let tenant = ensure_member_access(&state.pool, org_id, user.id).await?;
 
-    let members = sqlx::query_as!(
-        OrganizationMemberWithProfile,
-        r#"
-        SELECT
-            omm.user_id AS "user_id!: Uuid",
-            omm.role AS "role!: MemberRole",
-            omm.joined_at AS "joined_at!",
-            u.first_name AS "first_name?",
-            u.last_name AS "last_name?",
-            u.username AS "username?",
-            u.email AS "email?",
-            oa.avatar_url AS "avatar_url?"
-        FROM organization_member_metadata omm
-        INNER JOIN users u ON omm.user_id = u.id
-        LEFT JOIN LATERAL (
-            SELECT avatar_url
-            FROM oauth_accounts
-            WHERE user_id = omm.user_id
-            ORDER BY created_at ASC
-            LIMIT 1
-        ) oa ON true
-        WHERE omm.organization_id = $1
-        ORDER BY omm.joined_at ASC
-        "#,
-        org_id
-    )
-    .fetch_all(&state.pool)
-    .await
-    .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
+    let members = organization_members::list_with_profiles(&state.pool, tenant)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
     Ok(Json(ListMembersResponse { members }))
 }
@@ -446,6 +429,11 @@ async fn remove_member(
         .await
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
+    state.invalidations().publish(Invalidation::Membership {
+        organization_id: org_id,
+        user_id,
+    });
+
     audit::emit(
         AuditEvent::system(AuditAction::MemberRemove)
             .user(user.id, Some(session_id))
@@ -471,7 +459,7 @@ async fn update_member_role(
     let session_id = ctx.session_id;
 
     let user = ctx.user;
-    if user.id == user_id && payload.role == MemberRole::Member {
+    if user.id == user_id && payload.role != MemberRole::Admin {
         return Err(ErrorResponse::new(
             StatusCode::BAD_REQUEST,
             "Cannot demote yourself",
@@ -518,7 +506,7 @@ async fn update_member_role(
         }));
     }
 
-    if target.role == MemberRole::Admin && payload.role == MemberRole::Member {
+    if target.role == MemberRole::Admin && payload.role != MemberRole::Admin {
         let admin_ids = sqlx::query_scalar!(
             r#"
             SELECT user_id
@@ -558,6 +546,11 @@ async fn update_member_role(
         .await
         .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?;
 
+    state.invalidations().publish(Invalidation::Membership {
+        organization_id: org_id,
+        user_id,
+    });
+
     audit::emit(
         AuditEvent::system(AuditAction::MemberRoleChange)
             .user(user.id, Some(session_id))
@@ -580,32 +573,48 @@ async fn update_member_role(
     }))
 }
 
+/// Check `user_id` belongs to the organization and scope to it.
 pub(crate) async fn ensure_member_access(
     pool: &PgPool,
     organization_id: Uuid,
     user_id: Uuid,
-) -> Result<(), ErrorResponse> {
+) -> Result<TenantId, ErrorResponse> {
     organization_members::assert_membership(pool, organization_id, user_id)
         .await
-        .map_err(|err| membership_error(err, "Not a member of organization"))
+        .map_err(|err| membership_error(err, "Not a member of organization"))?;
+
+    Ok(TenantId::trusted(
+        organization_id,
+        TenantSource::MembershipCheck,
+    ))
 }
 
+/// Check `user_id` administers the organization and scope to it.
 pub(crate) async fn ensure_admin_access(
     pool: &PgPool,
     organization_id: Uuid,
     user_id: Uuid,
-) -> Result<(), ErrorResponse> {
+) -> Result<TenantId, ErrorResponse> {
     OrganizationRepository::new(pool)
         .assert_admin(organization_id, user_id)
         .await
-        .map_err(|err| membership_error(err, "Admin access required"))
+        .map_err(|err| membership_error(err, "Admin access required"))?;
+
+    enforce_for_current_request(pool, organization_id)
+        .await
+        .map_err(|err| membership_error(err, "Admin access required"))?;
+
+    Ok(TenantId::trusted(
+        organization_id,
+        TenantSource::MembershipCheck,
+    ))
 }
 
 pub(crate) async fn ensure_project_access(
     pool: &PgPool,
     user_id: Uuid,
     project_id: Uuid,
-) -> Result<Uuid, ErrorResponse> {
+) -> Result<TenantId, ErrorResponse> {
     let organization_id = ProjectRepository::organization_id(pool, project_id)
         .await
         .map_err(|error| {
@@ -643,14 +652,17 @@ pub(crate) async fn ensure_project_access(
             membership_error(err, "project not accessible")
         })?;
 
-    Ok(organization_id)
+    Ok(TenantId::trusted(
+        organization_id,
+        TenantSource::MembershipCheck,
+    ))
 }
 
 pub(crate) async fn ensure_issue_access(
     pool: &PgPool,
     user_id: Uuid,
     issue_id: Uuid,
-) -> Result<Uuid, ErrorResponse> {
+) -> Result<TenantId, ErrorResponse> {
     let organization_id = IssueRepository::organization_id(pool, issue_id)
         .await
         .map_err(|error| {
@@ -688,14 +700,178 @@ pub(crate) async fn ensure_issue_access(
             membership_error(err, "issue not accessible")
         })?;
 
-    Ok(organization_id)
+    Ok(TenantId::trusted(
+        organization_id,
+        TenantSource::MembershipCheck,
+    ))
+}
+
+async fn member_role(
+    pool: &PgPool,
+    tenant: TenantId,
+    user_id: Uuid,
+) -> Result<Option<MemberRole>, ErrorResponse> {
+    OrganizationRepository::new(pool)
+        .check_user_role(tenant.id(), user_id)
+        .await
+        .map_err(|err| membership_error(err, "not accessible"))
+}
+
+async fn is_viewer(pool: &PgPool, tenant: TenantId, user_id: Uuid) -> Result<bool, ErrorResponse> {
+    Ok(member_role(pool, tenant, user_id).await? == Some(MemberRole::Viewer))
+}
+
+fn viewer_denied(tenant: TenantId, user_id: Uuid) -> ErrorResponse {
+    warn!(organization_id = %tenant, %user_id, "viewer attempted a mutation");
+    ErrorResponse::new(StatusCode::FORBIDDEN, "Viewers have read-only access")
+}
+
+/// Refuse viewers; every other role may make changes.
+fn require_editor(
+    role: Option<MemberRole>,
+    tenant: TenantId,
+    user_id: Uuid,
+) -> Result<TenantId, ErrorResponse> {
+    if role == Some(MemberRole::Viewer) {
+        return Err(viewer_denied(tenant, user_id));
+    }
+    Ok(tenant)
+}
+
+/// Check `user_id` may change things in the organization. Viewers can read
+/// its projects, issues and comments but every mutation is refused.
+async fn ensure_editor(
+    pool: &PgPool,
+    tenant: TenantId,
+    user_id: Uuid,
+) -> Result<TenantId, ErrorResponse> {
+    require_editor(member_role(pool, tenant, user_id).await?, tenant, user_id)
+}
+
+/// [`ensure_member_access`] for handlers that create projects.
+pub(crate) async fn ensure_member_write_access(
+    pool: &PgPool,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<TenantId, ErrorResponse> {
+    let tenant = ensure_member_access(pool, organization_id, user_id).await?;
+    ensure_editor(pool, tenant, user_id).await
+}
+
+/// [`ensure_project_access`] for handlers that change a project or anything
+/// in it: issues, statuses, tags and attachments.
+pub(crate) async fn ensure_project_write_access(
+    pool: &PgPool,
+    user_id: Uuid,
+    project_id: Uuid,
+) -> Result<TenantId, ErrorResponse> {
+    let tenant = ensure_project_access(pool, user_id, project_id).await?;
+    ensure_editor(pool, tenant, user_id).await
+}
+
+/// [`ensure_issue_access`] for handlers that change an issue or what hangs
+/// off it: followers, reactions, links and attachments.
+pub(crate) async fn ensure_issue_write_access(
+    pool: &PgPool,
+    user_id: Uuid,
+    issue_id: Uuid,
+) -> Result<TenantId, ErrorResponse> {
+    let tenant = ensure_issue_access(pool, user_id, issue_id).await?;
+    ensure_editor(pool, tenant, user_id).await
+}
+
+/// [`ensure_issue_write_access`] for updating, assigning or deleting an
+/// issue. A viewer may still do these to an unassigned issue they created,
+/// e.g. one filed before they were made a viewer; whether they did is only
+/// settled by [`IssueWriteAccess::confirm`] inside the mutation's
+/// transaction.
+pub(crate) async fn ensure_issue_creator_write_access(
+    pool: &PgPool,
+    user_id: Uuid,
+    issue_id: Uuid,
+) -> Result<IssueWriteAccess, ErrorResponse> {
+    let tenant = ensure_issue_access(pool, user_id, issue_id).await?;
+    Ok(IssueWriteAccess {
+        tenant,
+        user_id,
+        creator_only: is_viewer(pool, tenant, user_id).await?,
+    })
+}
+
+/// Returned by [`ensure_issue_creator_write_access`]. Valid for any issue in
+/// `tenant`, so a bulk change can confirm each issue it touches.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IssueWriteAccess {
+    pub(crate) tenant: TenantId,
+    user_id: Uuid,
+    creator_only: bool,
+}
+
+impl IssueWriteAccess {
+    /// Finish the check in `conn`, the mutation's transaction. For a viewer
+    /// this locks the issue and requires that they created it and that
+    /// nobody but `except_assignee`, the assignee being removed, is assigned.
+    pub(crate) async fn confirm(
+        &self,
+        conn: &mut PgConnection,
+        issue_id: Uuid,
+        except_assignee: Option<Uuid>,
+    ) -> Result<(), ErrorResponse> {
+        if !self.creator_only {
+            return Ok(());
+        }
+
+        let is_creator =
+            IssueRepository::is_unassigned_creator(conn, issue_id, self.user_id, except_assignee)
+                .await
+                .map_err(|error| {
+                    tracing::error!(?error, %issue_id, "failed to check issue creator");
+                    ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+                })?
+                .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
+        if !is_creator {
+            return Err(viewer_denied(self.tenant, self.user_id));
+        }
+        Ok(())
+    }
+}
+
+/// Begin a transaction scoped to `tenant`, as returned by one of the guards
+/// here. Projects, issues and issue activity are only visible through one.
+pub(crate) async fn tenant_tx(pool: &PgPool, tenant: TenantId) -> Result<Tx<'_>, ErrorResponse> {
+    db::begin_tenant_tx(pool, tenant).await.map_err(|error| {
+        tracing::error!(?error, "failed to begin transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })
+}
+
+/// [`db::begin_tenant_snapshot_tx`], for reads that must agree with each
+/// other.
+pub(crate) async fn tenant_snapshot_tx(
+    pool: &PgPool,
+    tenant: TenantId,
+) -> Result<Tx<'_>, ErrorResponse> {
+    db::begin_tenant_snapshot_tx(pool, tenant)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to begin transaction");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })
+}
+
+/// Commit a [`tenant_tx`] that only read.
+pub(crate) async fn finish_read(tx: Tx<'_>) -> Result<(), ErrorResponse> {
+    tx.commit().await.map_err(|error| {
+        tracing::error!(?error, "failed to commit transaction");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })
 }
 
 pub(crate) async fn ensure_comment_access(
     pool: &PgPool,
     user_id: Uuid,
     comment_id: Uuid,
-) -> Result<Uuid, ErrorResponse> {
+) -> Result<TenantId, ErrorResponse> {
     let comment = IssueCommentRepository::find_by_id(pool, comment_id)
         .await
         .map_err(|error| {
@@ -713,3 +889,36 @@ pub(crate) async fn ensure_comment_access(
 
     ensure_issue_access(pool, user_id, comment.issue_id).await
 }
+
+/// [`ensure_comment_access`] for handlers that change a comment.
+pub(crate) async fn ensure_comment_write_access(
+    pool: &PgPool,
+    user_id: Uuid,
+    comment_id: Uuid,
+) -> Result<TenantId, ErrorResponse> {
+    let tenant = ensure_comment_access(pool, user_id, comment_id).await?;
+    ensure_editor(pool, tenant, user_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewer_mutation_is_forbidden() {
+        let tenant = TenantId::trusted(Uuid::new_v4(), TenantSource::MembershipCheck);
+        let error = require_editor(Some(MemberRole::Viewer), tenant, Uuid::new_v4()).unwrap_err();
+        assert_eq!(error.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_members_and_admins_may_mutate() {
+        let tenant = TenantId::trusted(Uuid::new_v4(), TenantSource::MembershipCheck);
+        for role in [MemberRole::Member, MemberRole::Admin] {
+            assert_eq!(
+                require_editor(Some(role), tenant, Uuid::new_v4()).unwrap(),
+                tenant
+            );
+        }
+    }
+}
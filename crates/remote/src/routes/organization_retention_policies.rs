@@ -0,0 +1,100 @@
+use api_types::{OrganizationRetentionPolicy, UpdateOrganizationRetentionPolicyRequest};
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::get,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{error::ErrorResponse, organization_members::ensure_admin_access};
+use crate::{
+    AppState,
+    audit::{self, AuditAction, AuditEvent},
+    auth::RequestContext,
+    db::organization_retention_policies::OrganizationRetentionPolicyRepository,
+};
+
+/// Upper bound on configurable retention windows (ten years).
+const MAX_RETENTION_DAYS: i32 = 3650;
+
+pub(super) fn router() -> Router<AppState> {
+    Router::new().route(
+        "/organizations/{org_id}/retention-policy",
+        get(get_retention_policy).put(update_retention_policy),
+    )
+}
+
+#[instrument(
+    name = "organization_retention_policies.get_retention_policy",
+    skip(state, ctx),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn get_retention_policy(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+) -> Result<Json<OrganizationRetentionPolicy>, ErrorResponse> {
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let policy = OrganizationRetentionPolicyRepository::get(state.pool(), tenant)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %org_id, "failed to load retention policy");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load policy")
+        })?;
+
+    Ok(Json(policy))
+}
+
+#[instrument(
+    name = "organization_retention_policies.update_retention_policy",
+    skip(state, ctx, payload),
+    fields(org_id = %org_id, user_id = %ctx.user.id)
+)]
+async fn update_retention_policy(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(org_id): Path<Uuid>,
+    Json(payload): Json<UpdateOrganizationRetentionPolicyRequest>,
+) -> Result<Json<OrganizationRetentionPolicy>, ErrorResponse> {
+    let tenant = ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    if let Some(days) = payload.workspace_summary_days
+        && !(1..=MAX_RETENTION_DAYS).contains(&days)
+    {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            format!("workspace_summary_days must be between 1 and {MAX_RETENTION_DAYS}"),
+        ));
+    }
+
+    let policy = OrganizationRetentionPolicyRepository::upsert(
+        state.pool(),
+        tenant,
+        payload.workspace_summary_days,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, %org_id, "failed to update retention policy");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to update policy")
+    })?;
+
+    audit::emit(
+        AuditEvent::from_request(&ctx, AuditAction::OrgRetentionPolicyUpdate)
+            .organization(org_id)
+            .resource("organization", Some(org_id))
+            .http(
+                "PUT",
+                format!("/v1/organizations/{org_id}/retention-policy"),
+                200,
+            )
+            .description(match policy.workspace_summary_days {
+                Some(days) => format!("workspace summaries kept {days} days"),
+                None => "workspace summaries kept indefinitely".to_string(),
+            }),
+    );
+
+    Ok(Json(policy))
+}
@@ -0,0 +1,93 @@
+//! `ETag` / `If-Match` optimistic concurrency for mutation routes.
+//!
+//! Entity versions are derived from `updated_at`, so any write bumps the tag.
+//! Requests without `If-Match` are unconditional, as before.
+
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use chrono::{DateTime, Utc};
+
+use super::error::ErrorResponse;
+
+/// The strong entity tag for a row last written at `updated_at`.
+pub(crate) fn etag(updated_at: DateTime<Utc>) -> String {
+    format!("\"{}\"", updated_at.timestamp_micros())
+}
+
+pub(crate) fn etag_header(updated_at: DateTime<Utc>) -> [(header::HeaderName, HeaderValue); 1] {
+    let value =
+        HeaderValue::from_str(&etag(updated_at)).expect("entity tags are ASCII digits in quotes");
+    [(header::ETAG, value)]
+}
+
+/// Fail with 412 unless the request's `If-Match` (if any) names the current
+/// version. Weak tags never match: `If-Match` uses strong comparison
+/// (RFC 9110 §13.1.1).
+pub(crate) fn check_if_match(
+    headers: &HeaderMap,
+    updated_at: DateTime<Utc>,
+) -> Result<(), ErrorResponse> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(());
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| ErrorResponse::new(StatusCode::BAD_REQUEST, "If-Match header is not valid"))?;
+
+    let current = etag(updated_at);
+    let matches = value
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate == current);
+
+    if matches {
+        Ok(())
+    } else {
+        Err(ErrorResponse::new(
+            StatusCode::PRECONDITION_FAILED,
+            "resource was modified by another request",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+    use chrono::{Duration, Utc};
+
+    use super::{check_if_match, etag};
+
+    fn with_if_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn matches_current_version_wildcard_and_missing_header() {
+        let updated_at = Utc::now();
+        let tag = etag(updated_at);
+
+        assert!(check_if_match(&HeaderMap::new(), updated_at).is_ok());
+        assert!(check_if_match(&with_if_match("*"), updated_at).is_ok());
+        assert!(check_if_match(&with_if_match(&tag), updated_at).is_ok());
+        assert!(check_if_match(&with_if_match(&format!("\"1\", {tag}")), updated_at).is_ok());
+    }
+
+    #[test]
+    fn rejects_weak_tags() {
+        let updated_at = Utc::now();
+        let weak = format!("W/{}", etag(updated_at));
+
+        let error = check_if_match(&with_if_match(&weak), updated_at).unwrap_err();
+        assert_eq!(error.status(), StatusCode::PRECONDITION_FAILED);
+    }
+
+    #[test]
+    fn rejects_stale_versions() {
+        let updated_at = Utc::now();
+        let stale = etag(updated_at - Duration::seconds(1));
+
+        let error = check_if_match(&with_if_match(&stale), updated_at).unwrap_err();
+        assert_eq!(error.status(), StatusCode::PRECONDITION_FAILED);
+    }
+}
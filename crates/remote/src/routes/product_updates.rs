@@ -0,0 +1,109 @@
+use api_types::ListProductUpdatesResponse;
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::error::ErrorResponse;
+use crate::{AppState, auth::RequestContext, db::product_updates::ProductUpdateRepository};
+
+/// Number of updates returned to the feed.
+const FEED_LIMIT: i64 = 50;
+
+pub(super) fn router() -> Router<AppState> {
+    Router::new()
+        .route("/product-updates", get(list_product_updates))
+        .route("/product-updates/read-all", post(mark_all_read))
+        .route("/product-updates/{id}/read", post(mark_read))
+}
+
+#[instrument(
+    name = "product_updates.list",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id)
+)]
+async fn list_product_updates(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+) -> Result<Json<ListProductUpdatesResponse>, ErrorResponse> {
+    Ok(Json(feed(&state, ctx.user.id).await?))
+}
+
+#[instrument(
+    name = "product_updates.mark_read",
+    skip(state, ctx),
+    fields(product_update_id = %id, user_id = %ctx.user.id)
+)]
+async fn mark_read(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListProductUpdatesResponse>, ErrorResponse> {
+    let found = ProductUpdateRepository::mark_read(state.pool(), ctx.user.id, id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to mark product update read");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to mark product update read",
+            )
+        })?;
+    if !found {
+        return Err(ErrorResponse::new(
+            StatusCode::NOT_FOUND,
+            "product update not found",
+        ));
+    }
+
+    Ok(Json(feed(&state, ctx.user.id).await?))
+}
+
+#[instrument(
+    name = "product_updates.mark_all_read",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id)
+)]
+async fn mark_all_read(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+) -> Result<Json<ListProductUpdatesResponse>, ErrorResponse> {
+    ProductUpdateRepository::mark_all_read(state.pool(), ctx.user.id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to mark product updates read");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to mark product updates read",
+            )
+        })?;
+
+    Ok(Json(feed(&state, ctx.user.id).await?))
+}
+
+async fn feed(
+    state: &AppState,
+    user_id: Uuid,
+) -> Result<ListProductUpdatesResponse, ErrorResponse> {
+    let load_error = |error: sqlx::Error| {
+        tracing::error!(?error, "failed to load product updates");
+        ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to load product updates",
+        )
+    };
+    let updates = ProductUpdateRepository::list_for_user(state.pool(), user_id, FEED_LIMIT)
+        .await
+        .map_err(load_error)?;
+    let unread_count = ProductUpdateRepository::unread_count(state.pool(), user_id)
+        .await
+        .map_err(load_error)?;
+
+    Ok(ListProductUpdatesResponse {
+        updates,
+        unread_count,
+    })
+}
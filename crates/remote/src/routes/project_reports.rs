@@ -0,0 +1,173 @@
+use api_types::{
+    CreateProjectReportRequest, ListProjectReportsResponse, ProjectReport, SignedDownloadUrl,
+};
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    routing::get,
+};
+use chrono::{Duration, Utc};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{
+    downloads::signed_download_url, error::ErrorResponse,
+    organization_members::ensure_project_access,
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{project_reports::ProjectReportRepository, tenant::TenantId},
+    signed_urls::DownloadResource,
+};
+
+/// Default report window when the caller doesn't specify `since`.
+const DEFAULT_REPORT_PERIOD_DAYS: i64 = 7;
+
+pub(super) fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/projects/{project_id}/reports",
+            get(list_reports).post(create_report),
+        )
+        .route(
+            "/projects/{project_id}/reports/{report_id}",
+            get(get_report),
+        )
+        .route(
+            "/projects/{project_id}/reports/{report_id}/download-url",
+            get(get_report_download_url),
+        )
+}
+
+#[instrument(
+    name = "project_reports.create",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn create_report(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    payload: Option<Json<CreateProjectReportRequest>>,
+) -> Result<Json<ProjectReport>, ErrorResponse> {
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let period_end = Utc::now();
+    let period_start = payload
+        .since
+        .unwrap_or_else(|| period_end - Duration::days(DEFAULT_REPORT_PERIOD_DAYS));
+
+    if period_start >= period_end {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "`since` must be in the past",
+        ));
+    }
+
+    let snapshot = ProjectReportRepository::build_snapshot(
+        state.pool(),
+        tenant,
+        project_id,
+        period_start,
+        period_end,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, %project_id, "failed to build project report");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to build report")
+    })?;
+
+    let report = ProjectReportRepository::create(
+        state.pool(),
+        tenant,
+        project_id,
+        ctx.user.id,
+        period_start,
+        period_end,
+        snapshot,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, %project_id, "failed to store project report");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to store report")
+    })?;
+
+    Ok(Json(report))
+}
+
+#[instrument(
+    name = "project_reports.list",
+    skip(state, ctx),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn list_reports(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ListProjectReportsResponse>, ErrorResponse> {
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let reports = ProjectReportRepository::list_by_project(state.pool(), tenant, project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to list project reports");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list reports")
+        })?;
+
+    Ok(Json(ListProjectReportsResponse { reports }))
+}
+
+#[instrument(
+    name = "project_reports.get",
+    skip(state, ctx),
+    fields(project_id = %project_id, report_id = %report_id, user_id = %ctx.user.id)
+)]
+async fn get_report(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, report_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ProjectReport>, ErrorResponse> {
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    Ok(Json(
+        load_report(&state, tenant, project_id, report_id).await?,
+    ))
+}
+
+#[instrument(
+    name = "project_reports.get_download_url",
+    skip(state, ctx),
+    fields(project_id = %project_id, report_id = %report_id, user_id = %ctx.user.id)
+)]
+async fn get_report_download_url(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path((project_id, report_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<SignedDownloadUrl>, ErrorResponse> {
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let report = load_report(&state, tenant, project_id, report_id).await?;
+
+    Ok(Json(signed_download_url(
+        &state,
+        DownloadResource::ProjectReport(report.id),
+    )))
+}
+
+async fn load_report(
+    state: &AppState,
+    tenant: TenantId,
+    project_id: Uuid,
+    report_id: Uuid,
+) -> Result<ProjectReport, ErrorResponse> {
+    ProjectReportRepository::find_by_id(state.pool(), tenant, project_id, report_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %report_id, "failed to load project report");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load report")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "report not found"))
+}
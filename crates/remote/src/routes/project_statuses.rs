@@ -1,6 +1,7 @@
 use api_types::{
     CreateProjectStatusRequest, DeleteResponse, ListProjectStatusesQuery,
-    ListProjectStatusesResponse, MutationResponse, ProjectStatus, UpdateProjectStatusRequest,
+    ListProjectStatusesResponse, MutationResponse, ProjectStatus, ProjectStatusCategory,
+    UpdateProjectStatusRequest,
 };
 use axum::{
     Json,
@@ -15,7 +16,7 @@ use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
-    organization_members::ensure_project_access,
+    organization_members::{ensure_project_access, ensure_project_write_access},
 };
 use crate::{
     AppState,
@@ -103,7 +104,7 @@ async fn create_project_status(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreateProjectStatusRequest>,
 ) -> Result<Json<MutationResponse<ProjectStatus>>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, payload.project_id).await?;
 
     if !is_valid_hsl_color(&payload.color) {
         return Err(ErrorResponse::new(
@@ -120,6 +121,7 @@ async fn create_project_status(
         payload.color,
         payload.sort_order,
         payload.hidden,
+        payload.category,
     )
     .await
     .map_err(|error| {
@@ -152,7 +154,7 @@ async fn update_project_status(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project status not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, status.project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, status.project_id).await?;
 
     if let Some(ref color) = payload.color
         && !is_valid_hsl_color(color)
@@ -170,6 +172,7 @@ async fn update_project_status(
         payload.color,
         payload.sort_order,
         payload.hidden,
+        payload.category.into_option(),
     )
     .await
     .map_err(|error| {
@@ -201,7 +204,7 @@ async fn delete_project_status(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project status not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, status.project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, status.project_id).await?;
 
     let response = ProjectStatusRepository::delete(state.pool(), project_status_id)
         .await
@@ -258,7 +261,7 @@ async fn bulk_update_project_statuses(
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project status not found"))?;
 
     let project_id = first_status.project_id;
-    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
 
     let mut tx = crate::db::begin_tx(state.pool()).await.map_err(|error| {
         tracing::error!(?error, "failed to begin transaction");
@@ -295,6 +298,8 @@ async fn bulk_update_project_statuses(
         }
 
         // Update the status within the transaction
+        let update_category = !item.changes.category.is_absent();
+        let category = item.changes.category.into_option().flatten();
         let updated = sqlx::query_as!(
             ProjectStatus,
             r#"
@@ -303,8 +308,9 @@ async fn bulk_update_project_statuses(
                 name = COALESCE($1, name),
                 color = COALESCE($2, color),
                 sort_order = COALESCE($3, sort_order),
-                hidden = COALESCE($4, hidden)
-            WHERE id = $5
+                hidden = COALESCE($4, hidden),
+                category = CASE WHEN $5 THEN $6 ELSE category END
+            WHERE id = $7
             RETURNING
                 id              AS "id!: Uuid",
                 project_id      AS "project_id!: Uuid",
@@ -312,12 +318,15 @@ async fn bulk_update_project_statuses(
                 color           AS "color!",
                 sort_order      AS "sort_order!",
                 hidden          AS "hidden!",
+                category        AS "category: ProjectStatusCategory",
                 created_at      AS "created_at!: DateTime<Utc>"
             "#,
             item.changes.name,
             item.changes.color,
             item.changes.sort_order,
             item.changes.hidden,
+            update_category,
+            category as Option<ProjectStatusCategory>,
             item.id
         )
         .fetch_one(&mut *tx)
@@ -1,24 +1,36 @@
 use api_types::{
     BulkUpdateProjectsRequest, BulkUpdateProjectsResponse, CreateProjectRequest, DeleteResponse,
-    ListProjectsQuery, ListProjectsResponse, MutationResponse, Project, UpdateProjectRequest,
+    ListProjectsQuery, ListProjectsResponse, MutationResponse, Project, ProjectCommitSettings,
+    ProjectPrSettings, ProjectSnapshotResponse, UpdateProjectCommitSettingsRequest,
+    UpdateProjectPrSettingsRequest, UpdateProjectRequest,
 };
 use axum::{
     Json,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
-    routing::post,
+    routing::{get, post},
 };
 use tracing::instrument;
+use utils::commit_message::validate_commit_template;
 use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
-    organization_members::ensure_member_access,
+    organization_members::{
+        ensure_member_access, ensure_member_write_access, ensure_project_access,
+        ensure_project_write_access, finish_read, tenant_snapshot_tx, tenant_tx,
+    },
 };
 use crate::{
     AppState,
     auth::RequestContext,
-    db::{get_txid, projects::ProjectRepository, types::is_valid_hsl_color},
+    db::{
+        get_txid, issue_activity::IssueActivityRepository, issues::IssueRepository,
+        organization_members, project_commit_settings::ProjectCommitSettingsRepository,
+        project_pr_settings::ProjectPrSettingsRepository,
+        project_statuses::ProjectStatusRepository, projects::ProjectRepository,
+        tags::TagRepository, types::is_valid_hsl_color,
+    },
     mutation_definition::MutationBuilder,
 };
 
@@ -36,6 +48,15 @@ pub fn router() -> axum::Router<AppState> {
     mutation()
         .router()
         .route("/projects/bulk", post(bulk_update_projects))
+        .route("/projects/{project_id}/snapshot", get(get_project_snapshot))
+        .route(
+            "/projects/{project_id}/commit-settings",
+            get(get_commit_settings).put(update_commit_settings),
+        )
+        .route(
+            "/projects/{project_id}/pr-settings",
+            get(get_pr_settings).put(update_pr_settings),
+        )
 }
 
 #[instrument(
@@ -48,14 +69,16 @@ async fn list_projects(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<ListProjectsQuery>,
 ) -> Result<Json<ListProjectsResponse>, ErrorResponse> {
-    ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
+    let tenant = ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
 
-    let projects = ProjectRepository::list_by_organization(state.pool(), query.organization_id)
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let projects = ProjectRepository::list_by_organization(&mut *tx, tenant)
         .await
         .map_err(|error| {
             tracing::error!(?error, organization_id = %query.organization_id, "failed to list projects");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list projects")
         })?;
+    finish_read(tx).await?;
 
     Ok(Json(ListProjectsResponse { projects }))
 }
@@ -70,19 +93,81 @@ async fn get_project(
     Extension(ctx): Extension<RequestContext>,
     Path(project_id): Path<Uuid>,
 ) -> Result<Json<Project>, ErrorResponse> {
-    let project = ProjectRepository::find_by_id(state.pool(), project_id)
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let project = ProjectRepository::find_by_id(&mut *tx, project_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, %project_id, "failed to load project");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load project")
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
-
-    ensure_member_access(state.pool(), project.organization_id, ctx.user.id).await?;
+    finish_read(tx).await?;
 
     Ok(Json(project))
 }
 
+/// Issues, statuses, tags and members read in one REPEATABLE READ
+/// transaction, so clients can bootstrap from a consistent view and then
+/// follow activity after `latest_seq`.
+#[instrument(
+    name = "projects.get_project_snapshot",
+    skip(state, ctx),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn get_project_snapshot(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ProjectSnapshotResponse>, ErrorResponse> {
+    let internal = |error: &dyn std::fmt::Debug| {
+        tracing::error!(?error, %project_id, "failed to load project snapshot");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load project")
+    };
+
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let mut tx = tenant_snapshot_tx(state.pool(), tenant).await?;
+
+    let project = ProjectRepository::find_by_id(&mut *tx, project_id)
+        .await
+        .map_err(|e| internal(&e))?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
+
+    // `seq` is assigned at insert, not commit, so the newest event in this
+    // snapshot can sit above one that commits later. Hand out the
+    // commit-safe watermark instead: every event up to it is in the
+    // snapshot. Events above it that the snapshot already reflects are sent
+    // again by the feed, and applying them twice is harmless.
+    let latest_seq = IssueActivityRepository::safe_seq(&mut *tx)
+        .await
+        .map_err(|e| internal(&e))?;
+    let issues = IssueRepository::list_by_project(&mut *tx, project_id)
+        .await
+        .map_err(|e| internal(&e))?;
+    let statuses = ProjectStatusRepository::list_by_project(&mut *tx, project_id)
+        .await
+        .map_err(|e| internal(&e))?;
+    let tags = TagRepository::list_by_project(&mut *tx, project_id)
+        .await
+        .map_err(|e| internal(&e))?;
+    let members = organization_members::list_with_profiles(&mut *tx, tenant)
+        .await
+        .map_err(|e| internal(&e))?;
+
+    finish_read(tx).await?;
+
+    Ok(Json(ProjectSnapshotResponse {
+        project,
+        issues,
+        statuses,
+        tags,
+        members,
+        latest_seq,
+    }))
+}
+
 #[instrument(
     name = "projects.create_project",
     skip(state, ctx, payload),
@@ -93,7 +178,8 @@ async fn create_project(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreateProjectRequest>,
 ) -> Result<Json<MutationResponse<Project>>, ErrorResponse> {
-    ensure_member_access(state.pool(), payload.organization_id, ctx.user.id).await?;
+    let tenant =
+        ensure_member_write_access(state.pool(), payload.organization_id, ctx.user.id).await?;
 
     if !is_valid_hsl_color(&payload.color) {
         return Err(ErrorResponse::new(
@@ -105,7 +191,7 @@ async fn create_project(
     let response = ProjectRepository::create_with_defaults(
         state.pool(),
         payload.id,
-        payload.organization_id,
+        tenant,
         payload.name,
         payload.color,
     )
@@ -140,15 +226,7 @@ async fn update_project(
     Path(project_id): Path<Uuid>,
     Json(payload): Json<UpdateProjectRequest>,
 ) -> Result<Json<MutationResponse<Project>>, ErrorResponse> {
-    let existing = ProjectRepository::find_by_id(state.pool(), project_id)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, %project_id, "failed to load project");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load project")
-        })?
-        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
-
-    ensure_member_access(state.pool(), existing.organization_id, ctx.user.id).await?;
+    let tenant = ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
 
     if let Some(ref color) = payload.color
         && !is_valid_hsl_color(color)
@@ -161,6 +239,7 @@ async fn update_project(
 
     let response = ProjectRepository::update(
         state.pool(),
+        tenant,
         project_id,
         payload.name,
         payload.color,
@@ -175,6 +254,117 @@ async fn update_project(
     Ok(Json(response))
 }
 
+#[instrument(
+    name = "projects.get_commit_settings",
+    skip(state, ctx),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn get_commit_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ProjectCommitSettings>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let settings = ProjectCommitSettingsRepository::get(state.pool(), project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load commit settings");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(settings))
+}
+
+#[instrument(
+    name = "projects.update_commit_settings",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn update_commit_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpdateProjectCommitSettingsRequest>,
+) -> Result<Json<ProjectCommitSettings>, ErrorResponse> {
+    ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let commit_template = payload
+        .commit_template
+        .as_deref()
+        .map(str::trim)
+        .filter(|template| !template.is_empty());
+    if let Some(template) = commit_template {
+        validate_commit_template(template)
+            .map_err(|message| ErrorResponse::new(StatusCode::BAD_REQUEST, message))?;
+    }
+
+    let settings = ProjectCommitSettingsRepository::upsert(
+        state.pool(),
+        project_id,
+        payload.conventional_commits,
+        commit_template,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, %project_id, "failed to update commit settings");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    Ok(Json(settings))
+}
+
+#[instrument(
+    name = "projects.get_pr_settings",
+    skip(state, ctx),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn get_pr_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<ProjectPrSettings>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let settings = ProjectPrSettingsRepository::get(state.pool(), project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load pr settings");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(settings))
+}
+
+#[instrument(
+    name = "projects.update_pr_settings",
+    skip(state, ctx, payload),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn update_pr_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpdateProjectPrSettingsRequest>,
+) -> Result<Json<ProjectPrSettings>, ErrorResponse> {
+    ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let settings = ProjectPrSettingsRepository::upsert(
+        state.pool(),
+        project_id,
+        payload.draft,
+        payload.use_template,
+        payload.link_issue,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, %project_id, "failed to update pr settings");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    Ok(Json(settings))
+}
+
 #[instrument(
     name = "projects.bulk_update",
     skip(state, ctx, payload),
@@ -192,21 +382,11 @@ async fn bulk_update_projects(
         }));
     }
 
-    let first_project = ProjectRepository::find_by_id(state.pool(), payload.updates[0].id)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, "failed to find first project");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to find project")
-        })?
-        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
+    let tenant =
+        ensure_project_write_access(state.pool(), ctx.user.id, payload.updates[0].id).await?;
+    let organization_id = tenant.id();
 
-    let organization_id = first_project.organization_id;
-    ensure_member_access(state.pool(), organization_id, ctx.user.id).await?;
-
-    let mut tx = crate::db::begin_tx(state.pool()).await.map_err(|error| {
-        tracing::error!(?error, "failed to begin transaction");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-    })?;
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
 
     let mut results = Vec::with_capacity(payload.updates.len());
 
@@ -279,17 +459,9 @@ async fn delete_project(
     Extension(ctx): Extension<RequestContext>,
     Path(project_id): Path<Uuid>,
 ) -> Result<Json<DeleteResponse>, ErrorResponse> {
-    let project = ProjectRepository::find_by_id(state.pool(), project_id)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, %project_id, "failed to load project");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load project")
-        })?
-        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
-
-    ensure_member_access(state.pool(), project.organization_id, ctx.user.id).await?;
+    let tenant = ensure_project_write_access(state.pool(), ctx.user.id, project_id).await?;
 
-    let response = ProjectRepository::delete(state.pool(), project_id)
+    let response = ProjectRepository::delete(state.pool(), tenant, project_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to delete project");
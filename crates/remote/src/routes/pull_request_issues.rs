@@ -12,14 +12,14 @@ use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
-    organization_members::ensure_issue_access,
+    organization_members::{ensure_issue_access, ensure_issue_write_access, tenant_tx},
 };
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
-        begin_tx, get_txid, issues::IssueRepository,
-        pull_request_issues::PullRequestIssueRepository, pull_requests::PullRequestRepository,
+        get_txid, issues::IssueRepository, pull_request_issues::PullRequestIssueRepository,
+        pull_requests::PullRequestRepository,
     },
     mutation_definition::{MutationBuilder, NoUpdate},
 };
@@ -105,9 +105,11 @@ async fn create_pull_request_issue(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreatePullRequestIssueRequest>,
 ) -> Result<Json<MutationResponse<PullRequestIssue>>, ErrorResponse> {
-    ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
+    let tenant = ensure_issue_write_access(state.pool(), ctx.user.id, payload.issue_id).await?;
 
-    let issue = IssueRepository::find_by_id(state.pool(), payload.issue_id)
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+
+    let issue = IssueRepository::find_by_id(&mut *tx, payload.issue_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to find issue");
@@ -117,11 +119,6 @@ async fn create_pull_request_issue(
 
     let project_id = issue.project_id;
 
-    let mut tx = begin_tx(state.pool()).await.map_err(|error| {
-        tracing::error!(?error, "failed to begin transaction");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-    })?;
-
     let pr =
         match PullRequestRepository::find_by_url_and_project(&mut *tx, &payload.url, project_id)
             .await
@@ -207,12 +204,9 @@ async fn delete_pull_request_issue(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "pull request issue not found"))?;
 
-    ensure_issue_access(state.pool(), ctx.user.id, link.issue_id).await?;
+    let tenant = ensure_issue_write_access(state.pool(), ctx.user.id, link.issue_id).await?;
 
-    let mut tx = begin_tx(state.pool()).await.map_err(|error| {
-        tracing::error!(?error, "failed to begin transaction");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-    })?;
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
 
     PullRequestIssueRepository::delete_and_cleanup_orphan(
         &mut tx,
@@ -15,14 +15,14 @@ use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
-    organization_members::ensure_issue_access,
+    organization_members::{ensure_issue_access, ensure_project_access, tenant_tx},
 };
 use crate::{
     AppState,
     auth::RequestContext,
     db::{
         get_txid, issues::IssueRepository, pull_request_issues::PullRequestIssueRepository,
-        pull_requests::PullRequestRepository, workspaces::WorkspaceRepository,
+        pull_requests::PullRequestRepository, scope_to_tenant, workspaces::WorkspaceRepository,
     },
 };
 
@@ -99,9 +99,11 @@ async fn create_pull_request(
 ) -> Result<Json<MutationResponse<PullRequest>>, ErrorResponse> {
     let issue_id = payload.issue_id;
 
-    ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
+    let tenant = ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
 
-    let issue = IssueRepository::find_by_id(state.pool(), issue_id)
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+
+    let issue = IssueRepository::find_by_id(&mut *tx, issue_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to find issue");
@@ -111,11 +113,6 @@ async fn create_pull_request(
 
     let project_id = issue.project_id;
 
-    let mut tx = state.pool().begin().await.map_err(|error| {
-        tracing::error!(?error, "failed to begin transaction");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-    })?;
-
     let pr =
         match PullRequestRepository::find_by_url_and_project(&mut *tx, &payload.url, project_id)
             .await
@@ -197,13 +194,25 @@ async fn update_pull_request(
         ));
     }
 
+    // The pull request can be linked in several organizations' projects; the
+    // transaction is scoped to each one in turn.
+    let mut tenants = Vec::with_capacity(pull_requests.len());
+    for pull_request in &pull_requests {
+        tenants
+            .push(ensure_project_access(state.pool(), ctx.user.id, pull_request.project_id).await?);
+    }
+
     let mut tx = state.pool().begin().await.map_err(|error| {
         tracing::error!(?error, "failed to begin transaction");
         ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
     })?;
 
     let mut last_pr = None;
-    for pull_request in &pull_requests {
+    for (pull_request, tenant) in pull_requests.iter().zip(&tenants) {
+        scope_to_tenant(&mut tx, *tenant).await.map_err(|error| {
+            tracing::error!(?error, "failed to scope transaction");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
         let updated = PullRequestRepository::update(
             &mut *tx,
             pull_request.id,
@@ -226,7 +235,11 @@ async fn update_pull_request(
         )
     })?;
 
-    for pull_request in &pull_requests {
+    for (pull_request, tenant) in pull_requests.iter().zip(&tenants) {
+        scope_to_tenant(&mut tx, *tenant).await.map_err(|error| {
+            tracing::error!(?error, "failed to scope transaction");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
         let issue_ids = PullRequestIssueRepository::issue_ids_for_pr(&mut *tx, pull_request.id)
             .await
             .map_err(|error| {
@@ -284,9 +297,11 @@ async fn upsert_pull_request(
         .issue_id
         .ok_or_else(|| ErrorResponse::new(StatusCode::BAD_REQUEST, "workspace has no issue"))?;
 
-    ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
+    let tenant = ensure_issue_access(state.pool(), ctx.user.id, issue_id).await?;
+
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
 
-    let issue = IssueRepository::find_by_id(state.pool(), issue_id)
+    let issue = IssueRepository::find_by_id(&mut *tx, issue_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, "failed to find issue");
@@ -296,11 +311,6 @@ async fn upsert_pull_request(
 
     let project_id = issue.project_id;
 
-    let mut tx = state.pool().begin().await.map_err(|error| {
-        tracing::error!(?error, "failed to begin transaction");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-    })?;
-
     let existing_pr =
         PullRequestRepository::find_by_url_and_project(&mut *tx, &payload.url, project_id)
             .await
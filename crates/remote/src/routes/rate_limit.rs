@@ -0,0 +1,192 @@
+//! Per-user and per-organization request budgets.
+//!
+//! Every user, and every organization, has an in-memory token bucket that
+//! refills at the configured requests per minute and holds at most one
+//! minute's worth. A request takes a token from its user's bucket and from
+//! the bucket of each organization the user belongs to, so one noisy client
+//! runs out before it can starve the rest of its organization. Exhausted
+//! budgets get 429 with `Retry-After`. Budgets are per server instance.
+
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, RandomState},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::error::ErrorResponse;
+use crate::{
+    AppState, auth::RequestContext, config::RateLimitConfig,
+    db::organization_members::list_organization_ids_for_user,
+};
+
+const SHARDS: usize = 16;
+/// Full buckets are dropped from a shard once it grows past this.
+const MAX_BUCKETS_PER_SHARD: usize = 4096;
+/// How long a user's organization list is reused before reloading it.
+const MEMBERSHIP_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum BucketKey {
+    User(Uuid),
+    Organization(Uuid),
+}
+
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// A user's organization IDs and when they were loaded.
+type CachedMemberships = (Instant, Arc<[Uuid]>);
+
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    hasher: RandomState,
+    shards: Vec<Mutex<HashMap<BucketKey, Bucket>>>,
+    memberships: Mutex<HashMap<Uuid, CachedMemberships>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            hasher: RandomState::new(),
+            shards: (0..SHARDS).map(|_| Mutex::new(HashMap::new())).collect(),
+            memberships: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Charge one request to `user_id` and their organizations. On failure,
+    /// returns how long until the exhausted budget has a token again.
+    async fn check(&self, pool: &PgPool, user_id: Uuid) -> Result<(), Duration> {
+        let now = Instant::now();
+        if let Some(per_minute) = self.config.user_per_minute {
+            self.take_at(BucketKey::User(user_id), per_minute, now)?;
+        }
+        if let Some(per_minute) = self.config.organization_per_minute {
+            for organization_id in self.organization_ids(pool, user_id, now).await.iter() {
+                self.take_at(BucketKey::Organization(*organization_id), per_minute, now)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn organization_ids(&self, pool: &PgPool, user_id: Uuid, now: Instant) -> Arc<[Uuid]> {
+        {
+            let memberships = self.memberships.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some((loaded_at, ids)) = memberships.get(&user_id)
+                && now.duration_since(*loaded_at) < MEMBERSHIP_TTL
+            {
+                return ids.clone();
+            }
+        }
+
+        let ids: Arc<[Uuid]> = match list_organization_ids_for_user(pool, user_id).await {
+            Ok(ids) => ids.into(),
+            Err(error) => {
+                // Fail open: a lookup error shouldn't take the API down.
+                tracing::warn!(?error, %user_id, "failed to load organizations for rate limit");
+                return Arc::from([]);
+            }
+        };
+        let mut memberships = self.memberships.lock().unwrap_or_else(|e| e.into_inner());
+        memberships.retain(|_, (loaded_at, _)| now.duration_since(*loaded_at) < MEMBERSHIP_TTL);
+        memberships.insert(user_id, (now, ids.clone()));
+        ids
+    }
+
+    fn take_at(&self, key: BucketKey, per_minute: u32, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(per_minute);
+        let per_second = capacity / 60.0;
+        let shard = &self.shards[self.hasher.hash_one(key) as usize % SHARDS];
+        let mut buckets = shard.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() >= MAX_BUCKETS_PER_SHARD && !buckets.contains_key(&key) {
+            buckets.retain(|_, bucket| {
+                bucket.tokens + now.duration_since(bucket.refilled_at).as_secs_f64() * per_second
+                    < capacity
+            });
+        }
+
+        let bucket = buckets.entry(key).or_insert(Bucket {
+            tokens: capacity,
+            refilled_at: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * per_second).min(capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+/// Must run after `require_session` so the user is known.
+pub(crate) async fn rate_limit(
+    State(state): State<AppState>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(user_id) = request
+        .extensions()
+        .get::<RequestContext>()
+        .map(|ctx| ctx.user.id)
+    else {
+        return next.run(request).await;
+    };
+
+    if let Err(retry_after) = state.rate_limiter().check(state.pool(), user_id).await {
+        let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+        tracing::debug!(%user_id, seconds, "request rate limited");
+        return (
+            [(header::RETRY_AFTER, seconds.to_string())],
+            ErrorResponse::new(StatusCode::TOO_MANY_REQUESTS, "rate limit exceeded"),
+        )
+            .into_response();
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_a_minute_of_burst_then_refills() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            user_per_minute: Some(2),
+            organization_per_minute: None,
+        });
+        let key = BucketKey::User(Uuid::new_v4());
+        let start = Instant::now();
+
+        assert_eq!(limiter.take_at(key, 2, start), Ok(()));
+        assert_eq!(limiter.take_at(key, 2, start), Ok(()));
+        assert_eq!(limiter.take_at(key, 2, start), Err(Duration::from_secs(30)));
+        assert_eq!(
+            limiter.take_at(key, 2, start + Duration::from_secs(30)),
+            Ok(())
+        );
+        assert!(
+            limiter
+                .take_at(BucketKey::User(Uuid::new_v4()), 2, start)
+                .is_ok()
+        );
+    }
+}
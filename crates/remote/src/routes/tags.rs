@@ -12,7 +12,7 @@ use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
-    organization_members::ensure_project_access,
+    organization_members::{ensure_project_access, ensure_project_write_access},
 };
 use crate::{
     AppState,
@@ -90,7 +90,7 @@ async fn create_tag(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreateTagRequest>,
 ) -> Result<Json<MutationResponse<Tag>>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, payload.project_id).await?;
 
     if !is_valid_hsl_color(&payload.color) {
         return Err(ErrorResponse::new(
@@ -134,7 +134,7 @@ async fn update_tag(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "tag not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, tag.project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, tag.project_id).await?;
 
     if let Some(ref color) = payload.color
         && !is_valid_hsl_color(color)
@@ -174,7 +174,7 @@ async fn delete_tag(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "tag not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, tag.project_id).await?;
+    ensure_project_write_access(state.pool(), ctx.user.id, tag.project_id).await?;
 
     let response = TagRepository::delete(state.pool(), tag_id)
         .await
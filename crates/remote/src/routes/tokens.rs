@@ -2,7 +2,7 @@ use api_types::{TokenRefreshRequest, TokenRefreshResponse};
 use axum::{
     Json, Router,
     extract::State,
-    http::StatusCode,
+    http::{HeaderMap, StatusCode, header::USER_AGENT},
     response::{IntoResponse, Response},
     routing::post,
 };
@@ -13,7 +13,7 @@ use crate::{
     audit::{self, AuditAction, AuditEvent},
     auth::{JwtError, OAuthTokenValidationError, is_local_provider},
     db::{
-        auth::{AuthSessionError, AuthSessionRepository},
+        auth::{AuthSessionError, AuthSessionRepository, SessionScope, client_fingerprint},
         identity_errors::IdentityError,
         oauth_accounts::{OAuthAccountError, OAuthAccountRepository},
         users::UserRepository,
@@ -21,7 +21,9 @@ use crate::{
 };
 
 pub(super) fn public_router() -> Router<AppState> {
-    Router::new().route("/tokens/refresh", post(refresh_token))
+    Router::new()
+        .route("/tokens/refresh", post(refresh_token))
+        .route("/tokens/read-only", post(issue_read_only_tokens))
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -34,6 +36,8 @@ pub(super) enum TokenRefreshError {
     TokenExpired,
     #[error("refresh token reused - possible token theft")]
     TokenReuseDetected,
+    #[error("refresh token presented by a different client than its session")]
+    FingerprintMismatch,
     #[error("provider token has been revoked")]
     ProviderTokenRevoked,
     #[error("temporary failure validating provider token")]
@@ -100,8 +104,41 @@ fn current_session_tokens_response(
     .into_response())
 }
 
+/// Reject tokens presented by a client other than the one their session is
+/// bound to, binding sessions that predate fingerprints to the caller.
+async fn ensure_client_fingerprint(
+    session_repo: &AuthSessionRepository<'_>,
+    user_id: uuid::Uuid,
+    session_id: uuid::Uuid,
+    headers: &HeaderMap,
+    path: &str,
+) -> Result<(), TokenRefreshError> {
+    let user_agent = headers.get(USER_AGENT).and_then(|v| v.to_str().ok());
+    if session_repo
+        .bind_client_fingerprint(session_id, &client_fingerprint(user_agent))
+        .await?
+    {
+        return Ok(());
+    }
+
+    warn!(
+        user_id = %user_id,
+        session_id = %session_id,
+        user_agent = user_agent.unwrap_or_default(),
+        "Refresh token presented by a different client than its session"
+    );
+    audit::emit(
+        AuditEvent::system(AuditAction::AuthTokenFingerprintMismatch)
+            .user(user_id, Some(session_id))
+            .resource("auth_session", Some(session_id))
+            .http("POST", path, 401),
+    );
+    Err(TokenRefreshError::FingerprintMismatch)
+}
+
 async fn refresh_token(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(payload): Json<TokenRefreshRequest>,
 ) -> Result<Response, TokenRefreshError> {
     let jwt_service = &state.jwt();
@@ -154,6 +191,15 @@ async fn refresh_token(
         return Err(TokenRefreshError::TokenReuseDetected);
     }
 
+    ensure_client_fingerprint(
+        &session_repo,
+        token_details.user_id,
+        token_details.session_id,
+        &headers,
+        "/v1/tokens/refresh",
+    )
+    .await?;
+
     // Move encrypted_provider_tokens from legacy refresh token claim to the DB
     if let Some(legacy_provider_token_details) =
         token_details.legacy_provider_token_details.as_ref()
@@ -274,6 +320,91 @@ async fn refresh_token(
     .into_response())
 }
 
+/// Exchanges a full-scope refresh token for tokens on a new read-only session,
+/// for kiosk/dashboard clients that stream the board but must not mutate it.
+/// The caller's own session is left untouched.
+async fn issue_read_only_tokens(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(payload): Json<TokenRefreshRequest>,
+) -> Result<Response, TokenRefreshError> {
+    let jwt_service = &state.jwt();
+    let session_repo = AuthSessionRepository::new(state.pool());
+
+    let token_details = match jwt_service.decode_refresh_token(&payload.refresh_token) {
+        Ok(details) => details,
+        Err(JwtError::TokenExpired) => return Err(TokenRefreshError::TokenExpired),
+        Err(_) => return Err(TokenRefreshError::InvalidToken),
+    };
+
+    let session = match session_repo.get(token_details.session_id).await {
+        Ok(session) => session,
+        Err(AuthSessionError::NotFound) => return Err(TokenRefreshError::SessionRevoked),
+        Err(error) => return Err(TokenRefreshError::SessionError(error)),
+    };
+
+    if session.revoked_at.is_some() {
+        return Err(TokenRefreshError::SessionRevoked);
+    }
+
+    // Only the live token of a full session may mint new sessions; anything
+    // else is rejected without the reuse handling `/tokens/refresh` applies.
+    let is_live_token = session.refresh_token_id == Some(token_details.refresh_token_id)
+        || session_repo
+            .is_previous_refresh_token_within_grace(&session, token_details.refresh_token_id);
+    if !is_live_token || session.scope != SessionScope::Full {
+        return Err(TokenRefreshError::InvalidToken);
+    }
+
+    ensure_client_fingerprint(
+        &session_repo,
+        token_details.user_id,
+        token_details.session_id,
+        &headers,
+        "/v1/tokens/read-only",
+    )
+    .await?;
+
+    if !is_local_provider(&token_details.provider) {
+        state
+            .oauth_token_validator()
+            .validate(
+                &token_details.provider,
+                token_details.user_id,
+                token_details.session_id,
+            )
+            .await?;
+    }
+
+    let user_repo = UserRepository::new(state.pool());
+    let user = user_repo.fetch_user(token_details.user_id).await?;
+
+    let read_only_session = session_repo
+        .create_with_scope(user.id, None, SessionScope::ReadOnly)
+        .await?;
+    let tokens = jwt_service.generate_tokens(&read_only_session, &user, &token_details.provider)?;
+    session_repo
+        .set_current_refresh_token(read_only_session.id, tokens.refresh_token_id)
+        .await?;
+    let user_agent = headers.get(USER_AGENT).and_then(|v| v.to_str().ok());
+    session_repo
+        .bind_client_fingerprint(read_only_session.id, &client_fingerprint(user_agent))
+        .await?;
+
+    audit::emit(
+        AuditEvent::system(AuditAction::AuthReadOnlySessionCreated)
+            .user(user.id, Some(token_details.session_id))
+            .resource("auth_session", Some(read_only_session.id))
+            .http("POST", "/v1/tokens/read-only", 200),
+    );
+
+    Ok(Json(TokenRefreshResponse {
+        access_token: tokens.access_token,
+        refresh_token: tokens.refresh_token,
+    })
+    .into_response())
+}
+
 impl IntoResponse for TokenRefreshError {
     fn into_response(self) -> Response {
         let (status, error_code) = match self {
@@ -283,6 +414,9 @@ impl IntoResponse for TokenRefreshError {
             TokenRefreshError::TokenReuseDetected => {
                 (StatusCode::UNAUTHORIZED, "token_reuse_detected")
             }
+            TokenRefreshError::FingerprintMismatch => {
+                (StatusCode::UNAUTHORIZED, "session_fingerprint_mismatch")
+            }
             TokenRefreshError::ProviderTokenRevoked => {
                 (StatusCode::UNAUTHORIZED, "provider_token_revoked")
             }
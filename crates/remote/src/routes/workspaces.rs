@@ -10,8 +10,9 @@ use tracing::instrument;
 use uuid::Uuid;
 
 use super::{
+    dry_run::finish_tx,
     error::{ErrorResponse, db_error},
-    organization_members::ensure_project_access,
+    organization_members::{ensure_project_access, tenant_tx},
 };
 use crate::{
     AppState,
@@ -67,7 +68,7 @@ async fn create_workspace(
     Extension(ctx): Extension<RequestContext>,
     Json(payload): Json<CreateWorkspaceRequest>,
 ) -> Result<Json<Workspace>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
 
     let workspace = WorkspaceRepository::create(
         state.pool(),
@@ -90,9 +91,13 @@ async fn create_workspace(
     })?;
 
     if let Some(issue_id) = payload.issue_id {
-        if let Err(error) =
-            IssueRepository::sync_issue_from_workspace_created(state.pool(), issue_id, ctx.user.id)
-                .await
+        if let Err(error) = IssueRepository::sync_issue_from_workspace_created(
+            state.pool(),
+            tenant,
+            issue_id,
+            ctx.user.id,
+        )
+        .await
         {
             tracing::warn!(?error, "failed to sync issue from workspace creation");
         }
@@ -136,11 +141,11 @@ async fn update_workspace(
     let updated = WorkspaceRepository::update(
         state.pool(),
         workspace.id,
-        payload.name,
+        payload.name.into_option(),
         payload.archived,
-        payload.files_changed,
-        payload.lines_added,
-        payload.lines_removed,
+        payload.files_changed.into_option(),
+        payload.lines_added.into_option(),
+        payload.lines_removed.into_option(),
     )
     .await
     .map_err(|error| {
@@ -172,23 +177,20 @@ async fn sync_issue_status_from_local_merge(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "workspace not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, workspace.project_id).await?;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, workspace.project_id).await?;
 
     let Some(issue_id) = workspace.issue_id else {
         return Ok(StatusCode::NO_CONTENT);
     };
 
-    let mut conn = state.pool().acquire().await.map_err(|error| {
-        tracing::error!(?error, "failed to acquire connection");
-        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
-    })?;
-
-    IssueRepository::sync_status_from_local_workspace_merge(&mut conn, issue_id)
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    IssueRepository::sync_status_from_local_workspace_merge(&mut tx, issue_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, issue_id = %issue_id, "failed to sync issue status");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
+    finish_tx(tx, false).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -29,7 +29,10 @@ use crate::{
     },
     routes::{
         error::ErrorResponse,
-        organization_members::{ensure_issue_access, ensure_member_access, ensure_project_access},
+        organization_members::{
+            ensure_issue_access, ensure_member_access, ensure_project_access, finish_read,
+            tenant_tx,
+        },
     },
     shape_route::{
         IssueFallbackQuery, NoQueryParams, OrgFallbackQuery, ProjectFallbackQuery, ShapeRoute,
@@ -96,6 +99,12 @@ pub fn all_shape_routes() -> Vec<ShapeRoute> {
             "/fallback/users",
             fallback_list_users,
         ),
+        ShapeRoute::new(
+            &shapes::ORGANIZATION_ISSUES_SHAPE,
+            ShapeScope::Org,
+            "/fallback/organization_issues",
+            fallback_list_organization_issues,
+        ),
         // Project-scoped
         ShapeRoute::new(
             &shapes::PROJECT_TAGS_SHAPE,
@@ -189,14 +198,16 @@ async fn fallback_list_projects(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<OrgFallbackQuery>,
 ) -> Result<Json<ListProjectsResponse>, ErrorResponse> {
-    ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
+    let tenant = ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
 
-    let projects = ProjectRepository::list_by_organization(state.pool(), query.organization_id)
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let projects = ProjectRepository::list_by_organization(&mut *tx, tenant)
         .await
         .map_err(|error| {
             tracing::error!(?error, organization_id = %query.organization_id, "failed to list projects (fallback)");
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list projects")
         })?;
+    finish_read(tx).await?;
 
     Ok(Json(ListProjectsResponse { projects }))
 }
@@ -228,10 +239,10 @@ async fn fallback_list_organization_members(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<OrgFallbackQuery>,
 ) -> Result<Json<ListOrganizationMembersResponse>, ErrorResponse> {
-    ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
+    let tenant = ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
 
     let organization_member_metadata =
-        organization_members::list_by_organization(state.pool(), query.organization_id)
+        organization_members::list_by_organization(state.pool(), tenant)
             .await
             .map_err(|error| {
                 tracing::error!(?error, organization_id = %query.organization_id, "failed to list organization members (fallback)");
@@ -251,10 +262,9 @@ async fn fallback_list_users(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<OrgFallbackQuery>,
 ) -> Result<Json<ListUsersResponse>, ErrorResponse> {
-    ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
+    let tenant = ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
 
-    let users =
-        organization_members::list_users_by_organization(state.pool(), query.organization_id)
+    let users = organization_members::list_users_by_organization(state.pool(), tenant)
             .await
             .map_err(|error| {
                 tracing::error!(?error, organization_id = %query.organization_id, "failed to list users (fallback)");
@@ -264,6 +274,31 @@ async fn fallback_list_users(
     Ok(Json(ListUsersResponse { users }))
 }
 
+async fn fallback_list_organization_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<OrgFallbackQuery>,
+) -> Result<Json<ListIssuesResponse>, ErrorResponse> {
+    let tenant = ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
+
+    let mut tx = tenant_tx(state.pool(), tenant).await?;
+    let issues = IssueRepository::list_by_organization(&mut *tx, tenant)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, organization_id = %query.organization_id, "failed to list issues (fallback)");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list issues")
+        })?;
+    finish_read(tx).await?;
+
+    let total_count = issues.len();
+    Ok(Json(ListIssuesResponse {
+        issues,
+        total_count,
+        limit: total_count,
+        offset: 0,
+    }))
+}
+
 // =============================================================================
 // Project-scoped fallback handlers
 // =============================================================================
@@ -311,10 +346,11 @@ async fn fallback_list_issues(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<ProjectFallbackQuery>,
 ) -> Result<Json<ListIssuesResponse>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
 
     let response = IssueRepository::search(
         state.pool(),
+        tenant,
         &SearchIssuesRequest {
             project_id: query.project_id,
             status_id: None,
@@ -322,6 +358,7 @@ async fn fallback_list_issues(
             priority: None,
             parent_issue_id: None,
             search: None,
+            q: None,
             simple_id: None,
             assignee_user_id: None,
             tag_id: None,
@@ -363,10 +400,10 @@ async fn fallback_list_issue_assignees(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<ProjectFallbackQuery>,
 ) -> Result<Json<ListIssueAssigneesResponse>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
 
     let issue_assignees =
-        IssueAssigneeRepository::list_by_project(state.pool(), query.project_id)
+        IssueAssigneeRepository::list_by_project(state.pool(), tenant, query.project_id)
             .await
             .map_err(|error| {
                 tracing::error!(?error, project_id = %query.project_id, "failed to list issue assignees (fallback)");
@@ -384,10 +421,10 @@ async fn fallback_list_issue_followers(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<ProjectFallbackQuery>,
 ) -> Result<Json<ListIssueFollowersResponse>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
 
     let issue_followers =
-        IssueFollowerRepository::list_by_project(state.pool(), query.project_id)
+        IssueFollowerRepository::list_by_project(state.pool(), tenant, query.project_id)
             .await
             .map_err(|error| {
                 tracing::error!(?error, project_id = %query.project_id, "failed to list issue followers (fallback)");
@@ -405,9 +442,9 @@ async fn fallback_list_issue_tags(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<ProjectFallbackQuery>,
 ) -> Result<Json<ListIssueTagsResponse>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
 
-    let issue_tags = IssueTagRepository::list_by_project(state.pool(), query.project_id)
+    let issue_tags = IssueTagRepository::list_by_project(state.pool(), tenant, query.project_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, project_id = %query.project_id, "failed to list issue tags (fallback)");
@@ -425,10 +462,10 @@ async fn fallback_list_issue_relationships(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<ProjectFallbackQuery>,
 ) -> Result<Json<ListIssueRelationshipsResponse>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
 
     let issue_relationships =
-        IssueRelationshipRepository::list_by_project(state.pool(), query.project_id)
+        IssueRelationshipRepository::list_by_project(state.pool(), tenant, query.project_id)
             .await
             .map_err(|error| {
                 tracing::error!(?error, project_id = %query.project_id, "failed to list issue relationships (fallback)");
@@ -468,10 +505,10 @@ async fn fallback_list_pull_request_issues(
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<ProjectFallbackQuery>,
 ) -> Result<Json<ListPullRequestIssuesResponse>, ErrorResponse> {
-    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+    let tenant = ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
 
     let pull_request_issues =
-        PullRequestIssueRepository::list_by_project(state.pool(), query.project_id)
+        PullRequestIssueRepository::list_by_project(state.pool(), tenant, query.project_id)
             .await
             .map_err(|error| {
                 tracing::error!(?error, project_id = %query.project_id, "failed to list pull request issues (fallback)");
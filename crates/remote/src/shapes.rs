@@ -44,6 +44,16 @@ pub const USERS_SHAPE: ShapeDefinition<User> = crate::define_shape!(
     params: ["organization_id"],
 );
 
+/// Issues across all of an organization's projects, for views that follow
+/// every shared task rather than one board.
+pub const ORGANIZATION_ISSUES_SHAPE: ShapeDefinition<Issue> = crate::define_shape!(
+    name: "ORGANIZATION_ISSUES_SHAPE",
+    table: "issues",
+    where_clause: r#""project_id" IN (SELECT id FROM projects WHERE "organization_id" = $1)"#,
+    url: "/shape/issues",
+    params: ["organization_id"],
+);
+
 // =============================================================================
 // Project-scoped shapes
 // =============================================================================
@@ -0,0 +1,122 @@
+//! Graceful shutdown. On SIGTERM or Ctrl-C the server stops accepting
+//! connections, lets in-flight requests finish, and closes WebSocket
+//! sessions asking clients to reconnect, all within
+//! [`RemoteServerConfig::shutdown_timeout_secs`].
+//!
+//! [`RemoteServerConfig::shutdown_timeout_secs`]: crate::config::RemoteServerConfig::shutdown_timeout_secs
+
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+#[derive(Clone)]
+pub struct Shutdown {
+    requested: Arc<watch::Sender<bool>>,
+    /// Connections still being wound down, see [`Shutdown::hold`].
+    holds: Arc<watch::Sender<usize>>,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self {
+            requested: Arc::new(watch::Sender::new(false)),
+            holds: Arc::new(watch::Sender::new(0)),
+        }
+    }
+
+    pub fn trigger(&self) {
+        self.requested.send_replace(true);
+    }
+
+    pub fn is_requested(&self) -> bool {
+        *self.requested.borrow()
+    }
+
+    /// Resolves once shutdown is requested, immediately if it already was.
+    pub async fn requested(&self) {
+        let mut requested = self.requested.subscribe();
+        // The sender lives as long as `self`, so this can't fail.
+        let _ = requested.wait_for(|requested| *requested).await;
+    }
+
+    /// Keep [`Shutdown::drained`] waiting until the returned guard is
+    /// dropped, for connections the HTTP server no longer tracks, such as
+    /// upgraded WebSockets.
+    pub fn hold(&self) -> ShutdownHold {
+        self.holds.send_modify(|holds| *holds += 1);
+        ShutdownHold(Arc::clone(&self.holds))
+    }
+
+    /// Resolves once every [`ShutdownHold`] has been dropped.
+    pub async fn drained(&self) {
+        let mut holds = self.holds.subscribe();
+        let _ = holds.wait_for(|holds| *holds == 0).await;
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct ShutdownHold(Arc<watch::Sender<usize>>);
+
+impl Drop for ShutdownHold {
+    fn drop(&mut self) {
+        self.0.send_modify(|holds| *holds -= 1);
+    }
+}
+
+/// Resolves on SIGTERM, as sent by orchestrators, or Ctrl-C.
+pub async fn signal() {
+    let ctrl_c = async {
+        if let Err(error) = tokio::signal::ctrl_c().await {
+            tracing::warn!(?error, "failed to listen for Ctrl-C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(error) => {
+                tracing::warn!(?error, "failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drained_waits_for_holds() {
+        let shutdown = Shutdown::new();
+        let hold = shutdown.hold();
+        shutdown.trigger();
+        shutdown.requested().await;
+
+        let drained = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.drained().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!drained.is_finished());
+
+        drop(hold);
+        drained.await.unwrap();
+    }
+}
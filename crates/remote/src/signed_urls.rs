@@ -0,0 +1,220 @@
+//! Short-lived HMAC-signed download URLs.
+//!
+//! Large payloads (export archives, report snapshots, attachments) are handed
+//! out as `/v1/downloads/{token}` links instead of being streamed through an
+//! authenticated request. The token names the resource and an expiry, and is
+//! signed with a key derived from the server's JWT secret, so the download
+//! route needs no session. Each token also carries a random nonce so the
+//! download route can cap how often a leaked link is redeemed.
+
+use std::fmt;
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How long a signed download URL stays valid.
+pub const DOWNLOAD_URL_TTL: Duration = Duration::minutes(5);
+
+/// Redemptions allowed per token for sensitive resources. More than one so a
+/// dropped connection can be retried.
+pub const SENSITIVE_DOWNLOAD_MAX_USES: i32 = 3;
+
+/// A resource that can be fetched through a signed download URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadResource {
+    ExportJob(Uuid),
+    ProjectReport(Uuid),
+    Attachment(Uuid),
+}
+
+impl DownloadResource {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::ExportJob(_) => "export",
+            Self::ProjectReport(_) => "report",
+            Self::Attachment(_) => "attachment",
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        match self {
+            Self::ExportJob(id) | Self::ProjectReport(id) | Self::Attachment(id) => *id,
+        }
+    }
+
+    /// How many times one signed URL for this resource may be redeemed, or
+    /// `None` if it is only limited by expiry.
+    pub fn max_uses(&self) -> Option<i32> {
+        match self {
+            Self::ExportJob(_) | Self::Attachment(_) => Some(SENSITIVE_DOWNLOAD_MAX_USES),
+            Self::ProjectReport(_) => None,
+        }
+    }
+
+    fn from_parts(kind: &str, id: Uuid) -> Option<Self> {
+        match kind {
+            "export" => Some(Self::ExportJob(id)),
+            "report" => Some(Self::ProjectReport(id)),
+            "attachment" => Some(Self::Attachment(id)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SignedUrlError {
+    #[error("malformed download token")]
+    Malformed,
+    #[error("invalid download token signature")]
+    InvalidSignature,
+    #[error("download link has expired")]
+    Expired,
+}
+
+/// A verified download token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedDownload {
+    pub resource: DownloadResource,
+    pub nonce: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct DownloadSigner {
+    key: [u8; 32],
+}
+
+impl fmt::Debug for DownloadSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DownloadSigner").finish_non_exhaustive()
+    }
+}
+
+impl DownloadSigner {
+    /// Derive a signing key dedicated to download URLs from the JWT secret.
+    pub fn from_secret(secret: &SecretString) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"vk-download-url:");
+        hasher.update(secret.expose_secret().as_bytes());
+        Self {
+            key: hasher.finalize().into(),
+        }
+    }
+
+    /// Sign `resource` for [`DOWNLOAD_URL_TTL`] from `now`.
+    pub fn sign(&self, resource: DownloadResource, now: DateTime<Utc>) -> (String, DateTime<Utc>) {
+        let expires_at = now + DOWNLOAD_URL_TTL;
+        let nonce = Uuid::new_v4().simple().to_string();
+        let payload = Self::payload(resource, expires_at.timestamp(), &nonce);
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(&payload).finalize().into_bytes());
+        (format!("{payload}.{signature}"), expires_at)
+    }
+
+    pub fn verify(
+        &self,
+        token: &str,
+        now: DateTime<Utc>,
+    ) -> Result<SignedDownload, SignedUrlError> {
+        let (payload, signature) = token.rsplit_once('.').ok_or(SignedUrlError::Malformed)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| SignedUrlError::Malformed)?;
+        self.mac(payload)
+            .verify_slice(&signature)
+            .map_err(|_| SignedUrlError::InvalidSignature)?;
+
+        let mut parts = payload.split('.');
+        let (Some(kind), Some(id), Some(expires_at), Some(nonce), None) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return Err(SignedUrlError::Malformed);
+        };
+        let id = Uuid::parse_str(id).map_err(|_| SignedUrlError::Malformed)?;
+        let expires_at: i64 = expires_at.parse().map_err(|_| SignedUrlError::Malformed)?;
+        let resource = DownloadResource::from_parts(kind, id).ok_or(SignedUrlError::Malformed)?;
+
+        let expires_at =
+            DateTime::from_timestamp(expires_at, 0).ok_or(SignedUrlError::Malformed)?;
+
+        if now > expires_at {
+            return Err(SignedUrlError::Expired);
+        }
+
+        Ok(SignedDownload {
+            resource,
+            nonce: nonce.to_string(),
+            expires_at,
+        })
+    }
+
+    fn payload(resource: DownloadResource, expires_at: i64, nonce: &str) -> String {
+        format!("{}.{}.{expires_at}.{nonce}", resource.kind(), resource.id())
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts any key size");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use secrecy::SecretString;
+    use uuid::Uuid;
+
+    use super::{DOWNLOAD_URL_TTL, DownloadResource, DownloadSigner, SignedUrlError};
+
+    fn signer() -> DownloadSigner {
+        DownloadSigner::from_secret(&SecretString::from("dGVzdC1zZWNyZXQ="))
+    }
+
+    #[test]
+    fn round_trips_resource() {
+        let now = Utc::now();
+        let resource = DownloadResource::ProjectReport(Uuid::new_v4());
+        let (token, expires_at) = signer().sign(resource, now);
+
+        assert_eq!(expires_at, now + DOWNLOAD_URL_TTL);
+        assert_eq!(signer().verify(&token, now).unwrap().resource, resource);
+
+        let (second, _) = signer().sign(resource, now);
+        assert_ne!(
+            signer().verify(&token, now).unwrap().nonce,
+            signer().verify(&second, now).unwrap().nonce
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_and_expired_tokens() {
+        let now = Utc::now();
+        let (token, _) = signer().sign(DownloadResource::ExportJob(Uuid::new_v4()), now);
+
+        let tampered = token.replacen("export", "report", 1);
+        assert_eq!(
+            signer().verify(&tampered, now),
+            Err(SignedUrlError::InvalidSignature)
+        );
+
+        let later = now + DOWNLOAD_URL_TTL + Duration::seconds(1);
+        assert_eq!(signer().verify(&token, later), Err(SignedUrlError::Expired));
+
+        let other = DownloadSigner::from_secret(&SecretString::from("b3RoZXI="));
+        assert_eq!(
+            other.verify(&token, now),
+            Err(SignedUrlError::InvalidSignature)
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use sqlx::PgPool;
+use utils::webhooks::ReplayCache;
 
 use crate::{
     analytics::AnalyticsService,
@@ -8,9 +9,15 @@ use crate::{
     azure_blob::AzureBlobService,
     billing::BillingService,
     config::RemoteServerConfig,
+    electric_cache::ElectricCache,
     github_app::GitHubAppService,
+    invalidation::InvalidationBus,
     mail::Mailer,
     r2::R2Service,
+    routes::rate_limit::RateLimiter,
+    shutdown::Shutdown,
+    signed_urls::DownloadSigner,
+    ws::{PresenceTracker, SessionRegistry, TicketSigner},
 };
 
 #[derive(Clone)]
@@ -28,6 +35,13 @@ pub struct AppState {
     github_app: Option<Arc<GitHubAppService>>,
     billing: BillingService,
     analytics: Option<AnalyticsService>,
+    webhook_deliveries: Arc<ReplayCache>,
+    invalidations: InvalidationBus,
+    electric_cache: Option<Arc<ElectricCache>>,
+    rate_limiter: Arc<RateLimiter>,
+    presence: Arc<PresenceTracker>,
+    ws_sessions: Arc<SessionRegistry>,
+    shutdown: Shutdown,
 }
 
 impl AppState {
@@ -46,7 +60,10 @@ impl AppState {
         github_app: Option<Arc<GitHubAppService>>,
         billing: BillingService,
         analytics: Option<AnalyticsService>,
+        invalidations: InvalidationBus,
+        electric_cache: Option<ElectricCache>,
     ) -> Self {
+        let rate_limiter = Arc::new(RateLimiter::new(config.rate_limit));
         Self {
             pool,
             config,
@@ -61,6 +78,13 @@ impl AppState {
             github_app,
             billing,
             analytics,
+            webhook_deliveries: Arc::new(ReplayCache::default()),
+            invalidations,
+            electric_cache: electric_cache.map(Arc::new),
+            rate_limiter,
+            presence: Arc::new(PresenceTracker::new()),
+            ws_sessions: Arc::new(SessionRegistry::default()),
+            shutdown: Shutdown::new(),
         }
     }
 
@@ -76,6 +100,14 @@ impl AppState {
         Arc::clone(&self.jwt)
     }
 
+    pub fn download_signer(&self) -> DownloadSigner {
+        DownloadSigner::from_secret(&self.jwt.secret)
+    }
+
+    pub fn ws_ticket_signer(&self) -> TicketSigner {
+        TicketSigner::from_secret(&self.jwt.secret)
+    }
+
     pub fn handoff(&self) -> Arc<OAuthHandoffService> {
         Arc::clone(&self.handoff)
     }
@@ -107,4 +139,33 @@ impl AppState {
     pub fn analytics(&self) -> Option<&AnalyticsService> {
         self.analytics.as_ref()
     }
+
+    /// Webhook delivery ids already handled, shared by every receiver route.
+    pub fn webhook_deliveries(&self) -> &ReplayCache {
+        &self.webhook_deliveries
+    }
+
+    pub fn invalidations(&self) -> &InvalidationBus {
+        &self.invalidations
+    }
+
+    pub fn electric_cache(&self) -> Option<&ElectricCache> {
+        self.electric_cache.as_deref()
+    }
+
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
+    }
+
+    pub fn presence(&self) -> &Arc<PresenceTracker> {
+        &self.presence
+    }
+
+    pub fn ws_sessions(&self) -> &Arc<SessionRegistry> {
+        &self.ws_sessions
+    }
+
+    pub fn shutdown(&self) -> &Shutdown {
+        &self.shutdown
+    }
 }
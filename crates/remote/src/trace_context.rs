@@ -0,0 +1,30 @@
+//! W3C trace context propagation, so a request can be followed from the
+//! client through this server into the services it calls. Does nothing
+//! unless trace export is enabled (see `init_tracing`), since the default
+//! propagator is a no-op.
+
+use axum::http::HeaderMap;
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Make `span` a child of the trace named by `headers`' `traceparent`, if
+/// any.
+pub(crate) fn continue_trace(span: &Span, headers: &HeaderMap) {
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(headers))
+    });
+    // Fails only when the span isn't recorded by the OpenTelemetry layer.
+    let _ = span.set_parent(parent);
+}
+
+/// `traceparent` (and `tracestate`) for the current span, to send with an
+/// outgoing request.
+pub(crate) fn current_trace_headers() -> HeaderMap {
+    let context = Span::current().context();
+    let mut headers = HeaderMap::new();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(&mut headers));
+    });
+    headers
+}
@@ -0,0 +1,540 @@
+//! Per-organization WebSocket that clients keep open while they're looking
+//! at the organization. It carries presence: who else has it open and which
+//! issues they're editing.
+
+mod presence;
+mod sessions;
+mod tickets;
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use api_types::{
+    ClientMessage, PresenceMessage, PresenceQuery, PresenceResponse, PresenceTicket, UserData,
+};
+use axum::{
+    Json, Router,
+    extract::{
+        Extension, Query, State,
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade, close_code},
+    },
+    http::StatusCode,
+    response::Response,
+    routing::{get, post},
+};
+use chrono::Utc;
+use futures::{SinkExt, StreamExt, stream::SplitSink};
+pub use presence::PresenceTracker;
+use serde::Deserialize;
+use sessions::SessionHandle;
+pub use sessions::{SessionRegistry, WsSession};
+use sqlx::PgPool;
+pub use tickets::TicketSigner;
+use tokio::{
+    sync::{
+        broadcast::error::RecvError,
+        mpsc::{self, error::TrySendError},
+    },
+    time::{Instant, interval_at},
+};
+use tracing::{Span, field, instrument};
+use utils::ws::WsCloseCode;
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    auth::{RequestContext, request_context_from_auth_session_id},
+    db::{
+        TX_CONTEXT, TxContext, download_nonces::DownloadNonceRepository, issues::IssueRepository,
+    },
+    invalidation::Invalidation,
+    middleware::client_ip::ClientIp,
+    prometheus::{WS_OUTBOUND_OVERFLOWS_TOTAL, WS_OUTBOUND_QUEUE_DEPTH},
+    routes::{error::ErrorResponse, organization_members::ensure_member_access},
+    shutdown::Shutdown,
+};
+
+/// Repeats of the same editing message closer together than this are
+/// dropped, and each connection's [`EditingBudget`] refills at one message
+/// per interval, which bounds what one client can make everyone else
+/// receive however it varies the issues.
+const MIN_EDITING_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Editing messages a connection may send at once before it's held to
+/// [`MIN_EDITING_INTERVAL`].
+const EDITING_BURST: f64 = 5.0;
+
+/// Issues whose organization one connection remembers before starting over.
+const MAX_CACHED_ISSUES: usize = 256;
+
+/// Messages queued for one client before it's considered too slow.
+const OUTBOUND_CAPACITY: usize = 64;
+
+/// Close reason sent when the server is shutting down rather than the
+/// session ending, so clients know to connect again.
+const RECONNECT_REASON: &str = "reconnect";
+const DISCONNECTED_REASON: &str = "disconnected by operator";
+
+/// How often an open socket checks that the auth session it was opened with
+/// is still valid and its user still a member, so revoking either closes it.
+/// Membership changes published on the invalidation bus are checked right
+/// away; this catches the rest.
+const REVALIDATE_INTERVAL: Duration = Duration::from_secs(60);
+
+pub(crate) fn router() -> Router<AppState> {
+    Router::new()
+        .route("/ws/tickets", post(create_ticket))
+        .route("/presence", get(get_presence))
+}
+
+/// The upgrade authenticates with a ticket from [`create_ticket`] instead of
+/// a bearer token, which browsers can't send with a WebSocket.
+pub(crate) fn public_router() -> Router<AppState> {
+    Router::new().route("/ws", get(connect))
+}
+
+#[derive(Debug, Deserialize)]
+struct ConnectQuery {
+    ticket: String,
+}
+
+#[instrument(
+    name = "ws.get_presence",
+    skip(state, ctx),
+    fields(organization_id = %query.organization_id, user_id = %ctx.user.id)
+)]
+async fn get_presence(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<PresenceQuery>,
+) -> Result<Json<PresenceResponse>, ErrorResponse> {
+    ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
+
+    Ok(Json(PresenceResponse {
+        organization_id: query.organization_id,
+        users: state.presence().online(query.organization_id),
+    }))
+}
+
+/// Issue a ticket for opening the organization's WebSocket as the current
+/// auth session.
+#[instrument(
+    name = "ws.create_ticket",
+    skip(state, ctx),
+    fields(organization_id = %request.organization_id, user_id = %ctx.user.id)
+)]
+async fn create_ticket(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(request): Json<PresenceQuery>,
+) -> Result<Json<PresenceTicket>, ErrorResponse> {
+    ensure_member_access(state.pool(), request.organization_id, ctx.user.id).await?;
+
+    let (ticket, expires_at) =
+        state
+            .ws_ticket_signer()
+            .sign(ctx.session_id, request.organization_id, Utc::now());
+    let base = state.server_public_base_url.trim_end_matches('/');
+    let base = if let Some(host) = base.strip_prefix("https://") {
+        format!("wss://{host}")
+    } else if let Some(host) = base.strip_prefix("http://") {
+        format!("ws://{host}")
+    } else {
+        base.to_string()
+    };
+    Ok(Json(PresenceTicket {
+        url: format!("{base}/v1/ws?ticket={ticket}"),
+        expires_at,
+    }))
+}
+
+/// Redeem a ticket and upgrade. The session is checked again here, since
+/// it may have been revoked since the ticket was issued.
+#[instrument(
+    name = "ws.connect",
+    skip_all,
+    fields(organization_id = field::Empty, user_id = field::Empty)
+)]
+async fn connect(
+    State(state): State<AppState>,
+    ClientIp(client_ip): ClientIp,
+    Query(query): Query<ConnectQuery>,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, ErrorResponse> {
+    let ticket = state
+        .ws_ticket_signer()
+        .verify(&query.ticket, Utc::now())
+        .map_err(|error| ErrorResponse::new(StatusCode::UNAUTHORIZED, error.to_string()))?;
+    let redeemed =
+        DownloadNonceRepository::redeem(state.pool(), &ticket.nonce, ticket.expires_at, 1)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to redeem ws ticket");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
+    if !redeemed {
+        return Err(ErrorResponse::new(
+            StatusCode::UNAUTHORIZED,
+            "ticket has already been used",
+        ));
+    }
+
+    let ctx = request_context_from_auth_session_id(&state, ticket.session_id)
+        .await
+        .map_err(|response| ErrorResponse::new(response.status(), "session is no longer valid"))?;
+    let span = Span::current();
+    span.record("organization_id", field::display(ticket.organization_id));
+    span.record("user_id", field::display(ctx.user.id));
+
+    // Organization access policies read the client and session from here,
+    // both now and whenever the socket revalidates.
+    let tx_ctx = TxContext {
+        user_id: ctx.user.id,
+        request_id: String::new(),
+        session_id: ctx.session_id,
+        client_ip,
+        mutation_id: None,
+    };
+    TX_CONTEXT
+        .scope(
+            Some(tx_ctx.clone()),
+            ensure_member_access(state.pool(), ticket.organization_id, ctx.user.id),
+        )
+        .await?;
+
+    let user = UserData {
+        user_id: ctx.user.id,
+        first_name: ctx.user.first_name,
+        last_name: ctx.user.last_name,
+        username: ctx.user.username,
+    };
+    Ok(upgrade.on_upgrade(move |socket| {
+        TX_CONTEXT.scope(
+            Some(tx_ctx),
+            serve(socket, state, ticket.organization_id, ctx.session_id, user),
+        )
+    }))
+}
+
+/// Relay presence changes in the organization until the client goes away,
+/// and the client's own editing to everyone else. The client starts with a
+/// snapshot of everyone online.
+///
+/// Messages go through a bounded queue drained by [`write_outbound`], so a
+/// slow client can't hold up this loop and make it lag the broadcast. When
+/// the queue fills, or the loop lags anyway, further changes are dropped
+/// and a fresh snapshot is queued once there's room.
+///
+/// On shutdown the session ends and the client is asked to reconnect, which
+/// it will do against another replica or this one once it's back. An
+/// operator can also end it through the admin API, and it ends by itself
+/// once `auth_session_id` is revoked or the user leaves the organization;
+/// the latter is noticed as soon as the membership change reaches this
+/// replica's invalidation bus.
+async fn serve(
+    socket: WebSocket,
+    state: AppState,
+    organization_id: Uuid,
+    auth_session_id: Uuid,
+    user: UserData,
+) {
+    let (pool, presence, shutdown) = (state.pool(), state.presence(), state.shutdown());
+    let user_id = user.user_id;
+    let session = state.ws_sessions().register(organization_id, user_id);
+    // Subscribed before anything else, so a membership change made while
+    // the socket is being set up still gets it rechecked.
+    let mut invalidations = state.invalidations().subscribe();
+    let mut invalidations_open = true;
+    let (sink, mut incoming) = socket.split();
+    let (outbound, outbound_rx) = mpsc::channel(OUTBOUND_CAPACITY);
+    tokio::spawn(write_outbound(
+        sink,
+        outbound_rx,
+        shutdown.clone(),
+        Arc::clone(session.handle()),
+    ));
+
+    // Subscribed before the snapshot, so nothing after it is missed; changes
+    // it already reflects may arrive again, which is harmless.
+    let (_guard, mut events) = presence.connect(organization_id, user);
+    let mut needs_snapshot = true;
+    let mut last_editing: Option<(Uuid, Instant)> = None;
+    let mut editing_budget = EditingBudget::new(Instant::now());
+    // Whether each issue is in the organization, so repeats don't query.
+    let mut issues_in_organization: HashMap<Uuid, bool> = HashMap::new();
+    let mut revalidate = interval_at(Instant::now() + REVALIDATE_INTERVAL, REVALIDATE_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = shutdown.requested() => return,
+            _ = session.disconnected() => return,
+            _ = revalidate.tick() => {
+                if !still_authorized(&state, auth_session_id, organization_id, user_id).await {
+                    session.expire();
+                    return;
+                }
+                // Issues may have moved since they were checked.
+                issues_in_organization.clear();
+            }
+            invalidation = invalidations.recv(), if invalidations_open => {
+                let recheck = match invalidation {
+                    Ok(invalidation) => affects(invalidation, organization_id, user_id),
+                    // Can't tell whether a missed one was about this socket.
+                    Err(RecvError::Lagged(_)) => true,
+                    Err(RecvError::Closed) => {
+                        invalidations_open = false;
+                        false
+                    }
+                };
+                if recheck {
+                    if !still_authorized(&state, auth_session_id, organization_id, user_id).await {
+                        session.expire();
+                        return;
+                    }
+                    issues_in_organization.clear();
+                }
+            }
+            permit = outbound.reserve(), if needs_snapshot => {
+                let Ok(permit) = permit else {
+                    return;
+                };
+                metrics::gauge!(WS_OUTBOUND_QUEUE_DEPTH).increment(1);
+                session.queued();
+                permit.send(PresenceMessage::Snapshot {
+                    organization_id,
+                    users: presence.online(organization_id),
+                });
+                needs_snapshot = false;
+            }
+            event = events.recv() => match event {
+                Ok(PresenceMessage::Editing { user_id: editor, .. }) if editor == user_id => {}
+                // While a snapshot is pending, it covers anything dropped.
+                Ok(message) if !needs_snapshot => {
+                    // Counted before sending so the writer never sees it
+                    // dequeued first.
+                    match outbound.try_reserve() {
+                        Ok(permit) => {
+                            metrics::gauge!(WS_OUTBOUND_QUEUE_DEPTH).increment(1);
+                            session.queued();
+                            permit.send(message);
+                        }
+                        Err(TrySendError::Full(_)) => {
+                            metrics::counter!(WS_OUTBOUND_OVERFLOWS_TOTAL).increment(1);
+                            needs_snapshot = true;
+                        }
+                        Err(TrySendError::Closed(_)) => return,
+                    }
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(_)) => {
+                    metrics::counter!(WS_OUTBOUND_OVERFLOWS_TOTAL).increment(1);
+                    needs_snapshot = true;
+                }
+                Err(RecvError::Closed) => return,
+            },
+            message = incoming.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    match serde_json::from_str::<ClientMessage>(&text) {
+                        Ok(ClientMessage::Editing { task_id }) => {
+                            let now = Instant::now();
+                            let repeated = last_editing.is_some_and(|(last_task, at)| {
+                                last_task == task_id
+                                    && now.duration_since(at) < MIN_EDITING_INTERVAL
+                            });
+                            if repeated || !editing_budget.take(now) {
+                                continue;
+                            }
+                            let in_organization = match issues_in_organization.get(&task_id) {
+                                Some(&cached) => cached,
+                                None => {
+                                    let found =
+                                        issue_in_organization(pool, task_id, organization_id)
+                                            .await;
+                                    if issues_in_organization.len() >= MAX_CACHED_ISSUES {
+                                        issues_in_organization.clear();
+                                    }
+                                    issues_in_organization.insert(task_id, found);
+                                    found
+                                }
+                            };
+                            if in_organization {
+                                last_editing = Some((task_id, now));
+                                presence.editing(organization_id, user_id, task_id);
+                            }
+                        }
+                        Err(error) => tracing::debug!(%error, "ignoring malformed client message"),
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Send queued messages to the client until the queue closes with its
+/// session or the client stops accepting them. Holds up shutdown until the
+/// client has been sent everything and told to reconnect.
+async fn write_outbound(
+    mut sink: SplitSink<WebSocket, Message>,
+    mut outbound: mpsc::Receiver<PresenceMessage>,
+    shutdown: Shutdown,
+    session: Arc<SessionHandle>,
+) {
+    let _hold = shutdown.hold();
+    let mut client_gone = false;
+    while let Some(message) = outbound.recv().await {
+        metrics::gauge!(WS_OUTBOUND_QUEUE_DEPTH).decrement(1);
+        session.dequeued(1);
+        let text = serde_json::to_string(&message).expect("presence messages serialize");
+        if sink.send(Message::Text(text.into())).await.is_err() {
+            client_gone = true;
+            break;
+        }
+        session.sent();
+    }
+    let close = if shutdown.is_requested() {
+        Some((close_code::RESTART, RECONNECT_REASON))
+    } else if session.is_disconnected() {
+        Some((close_code::POLICY, DISCONNECTED_REASON))
+    } else if session.is_expired() {
+        // Tells clients not to reconnect with the same credentials.
+        let expired = WsCloseCode::AuthExpired;
+        Some((expired.code(), expired.reason()))
+    } else {
+        None
+    };
+    if !client_gone && let Some((code, reason)) = close {
+        let _ = sink
+            .send(Message::Close(Some(CloseFrame {
+                code,
+                reason: reason.into(),
+            })))
+            .await;
+    }
+    // Whatever is left will never be sent.
+    outbound.close();
+    let mut unsent = 0;
+    while outbound.try_recv().is_ok() {
+        unsent += 1;
+    }
+    metrics::gauge!(WS_OUTBOUND_QUEUE_DEPTH).decrement(unsent as f64);
+    session.dequeued(unsent);
+}
+
+/// A token bucket for one connection's editing messages, holding up to
+/// [`EDITING_BURST`] and refilling at one per [`MIN_EDITING_INTERVAL`].
+struct EditingBudget {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl EditingBudget {
+    fn new(now: Instant) -> Self {
+        Self {
+            tokens: EDITING_BURST,
+            refilled_at: now,
+        }
+    }
+
+    /// Spend a token, or return false if there's none left.
+    fn take(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() / MIN_EDITING_INTERVAL.as_secs_f64())
+            .min(EDITING_BURST);
+        self.refilled_at = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Whether `issue_id` is on one of the organization's boards, so members
+/// can't announce edits to issues they can't see.
+async fn issue_in_organization(pool: &PgPool, issue_id: Uuid, organization_id: Uuid) -> bool {
+    match IssueRepository::organization_id(pool, issue_id).await {
+        Ok(found) => found == Some(organization_id),
+        Err(error) => {
+            tracing::warn!(?error, %issue_id, "failed to look up issue organization");
+            false
+        }
+    }
+}
+
+/// Whether `invalidation` may have changed what `user_id` can do in the
+/// organization, so a socket of theirs should check again.
+fn affects(invalidation: Invalidation, organization_id: Uuid, user_id: Uuid) -> bool {
+    match invalidation {
+        Invalidation::Membership {
+            organization_id: changed_organization,
+            user_id: changed_user,
+        } => changed_organization == organization_id && changed_user == user_id,
+        Invalidation::All => true,
+    }
+}
+
+/// Whether the auth session a socket was opened with is still valid and its
+/// user still a member of the organization. A failed lookup keeps the
+/// socket open until the next check.
+async fn still_authorized(
+    state: &AppState,
+    auth_session_id: Uuid,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> bool {
+    match request_context_from_auth_session_id(state, auth_session_id).await {
+        Ok(ctx) if ctx.user.id == user_id => {}
+        Ok(_) => return false,
+        Err(response) => return response.status().is_server_error(),
+    }
+    match ensure_member_access(state.pool(), organization_id, user_id).await {
+        Ok(_) => true,
+        Err(error) => error.status().is_server_error(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editing_budget_allows_a_burst_then_refills() {
+        let start = Instant::now();
+        let mut budget = EditingBudget::new(start);
+        for _ in 0..EDITING_BURST as usize {
+            assert!(budget.take(start));
+        }
+        assert!(!budget.take(start));
+
+        assert!(budget.take(start + MIN_EDITING_INTERVAL));
+        assert!(!budget.take(start + MIN_EDITING_INTERVAL));
+    }
+
+    #[test]
+    fn test_only_own_membership_changes_affect_a_socket() {
+        let (organization_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let membership = |organization_id, user_id| Invalidation::Membership {
+            organization_id,
+            user_id,
+        };
+
+        assert!(affects(
+            membership(organization_id, user_id),
+            organization_id,
+            user_id
+        ));
+        assert!(!affects(
+            membership(organization_id, Uuid::new_v4()),
+            organization_id,
+            user_id
+        ));
+        assert!(!affects(
+            membership(Uuid::new_v4(), user_id),
+            organization_id,
+            user_id
+        ));
+        assert!(affects(Invalidation::All, organization_id, user_id));
+    }
+}
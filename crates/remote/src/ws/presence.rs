@@ -0,0 +1,212 @@
+//! Who has an organization open. A user is online while they hold at least
+//! one connection, so a second tab doesn't announce them twice and closing
+//! it doesn't announce them leaving.
+//!
+//! Each replica only knows its own connections; clients of one replica
+//! don't see users connected to another.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use api_types::{PresenceMessage, UserData};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Events buffered per organization for connections that fall behind.
+const CAPACITY: usize = 256;
+
+struct Online {
+    user: UserData,
+    connections: usize,
+}
+
+/// An organization with at least one connection. Each has its own channel,
+/// so one organization's traffic can't make another's connections lag.
+struct Organization {
+    users: HashMap<Uuid, Online>,
+    events: broadcast::Sender<PresenceMessage>,
+}
+
+pub struct PresenceTracker {
+    organizations: Mutex<HashMap<Uuid, Organization>>,
+}
+
+impl PresenceTracker {
+    pub fn new() -> Self {
+        Self {
+            organizations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn online(&self, organization_id: Uuid) -> Vec<UserData> {
+        self.organizations
+            .lock()
+            .unwrap()
+            .get(&organization_id)
+            .map(|organization| {
+                organization
+                    .users
+                    .values()
+                    .map(|online| online.user.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Count a connection of `user` to the organization until the returned
+    /// guard is dropped, and subscribe it to the organization's joins, leaves
+    /// and editing. The subscription starts before the join is announced, so
+    /// the connection sees its own.
+    pub fn connect(
+        self: &Arc<Self>,
+        organization_id: Uuid,
+        user: UserData,
+    ) -> (PresenceGuard, broadcast::Receiver<PresenceMessage>) {
+        let user_id = user.user_id;
+        let mut organizations = self.organizations.lock().unwrap();
+        let organization = organizations
+            .entry(organization_id)
+            .or_insert_with(|| Organization {
+                users: HashMap::new(),
+                events: broadcast::channel(CAPACITY).0,
+            });
+        let events = organization.events.subscribe();
+        let online = organization.users.entry(user_id).or_insert_with(|| Online {
+            user: user.clone(),
+            connections: 0,
+        });
+        online.connections += 1;
+        if online.connections == 1 {
+            let _ = organization.events.send(PresenceMessage::Joined {
+                organization_id,
+                user,
+            });
+        }
+
+        let guard = PresenceGuard {
+            tracker: Arc::clone(self),
+            organization_id,
+            user_id,
+        };
+        (guard, events)
+    }
+
+    /// Tell the organization `user_id` is editing `task_id`. Not recorded;
+    /// clients expire the indicator themselves.
+    pub fn editing(&self, organization_id: Uuid, user_id: Uuid, task_id: Uuid) {
+        let organizations = self.organizations.lock().unwrap();
+        if let Some(organization) = organizations.get(&organization_id) {
+            let _ = organization.events.send(PresenceMessage::Editing {
+                organization_id,
+                user_id,
+                task_id,
+            });
+        }
+    }
+
+    fn disconnect(&self, organization_id: Uuid, user_id: Uuid) {
+        let mut organizations = self.organizations.lock().unwrap();
+        let Some(organization) = organizations.get_mut(&organization_id) else {
+            return;
+        };
+        let Some(online) = organization.users.get_mut(&user_id) else {
+            return;
+        };
+        online.connections -= 1;
+        if online.connections > 0 {
+            return;
+        }
+
+        organization.users.remove(&user_id);
+        let _ = organization.events.send(PresenceMessage::Left {
+            organization_id,
+            user_id,
+        });
+        if organization.users.is_empty() {
+            organizations.remove(&organization_id);
+        }
+    }
+}
+
+impl Default for PresenceTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keeps a user online in an organization until dropped.
+pub struct PresenceGuard {
+    tracker: Arc<PresenceTracker>,
+    organization_id: Uuid,
+    user_id: Uuid,
+}
+
+impl Drop for PresenceGuard {
+    fn drop(&mut self) {
+        self.tracker.disconnect(self.organization_id, self.user_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user() -> UserData {
+        UserData {
+            user_id: Uuid::new_v4(),
+            first_name: None,
+            last_name: None,
+            username: Some("octocat".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_announces_first_join_and_last_leave() {
+        let tracker = Arc::new(PresenceTracker::new());
+        let organization_id = Uuid::new_v4();
+        let user = user();
+
+        let (first, mut events) = tracker.connect(organization_id, user.clone());
+        let (second, _) = tracker.connect(organization_id, user.clone());
+        assert!(matches!(
+            events.try_recv(),
+            Ok(PresenceMessage::Joined { user: joined, .. }) if joined.user_id == user.user_id
+        ));
+        assert!(events.try_recv().is_err());
+        assert_eq!(tracker.online(organization_id).len(), 1);
+
+        drop(first);
+        assert!(events.try_recv().is_err());
+        drop(second);
+        assert!(matches!(
+            events.try_recv(),
+            Ok(PresenceMessage::Left { user_id, .. }) if user_id == user.user_id
+        ));
+        assert!(tracker.online(organization_id).is_empty());
+    }
+
+    #[test]
+    fn test_organizations_are_separate() {
+        let tracker = Arc::new(PresenceTracker::new());
+        let (org_a, org_b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let (_a, mut events_a) = tracker.connect(org_a, user());
+        assert!(events_a.try_recv().is_ok());
+        let (_b, mut events_b) = tracker.connect(org_b, user());
+        assert_eq!(tracker.online(org_a).len(), 1);
+        assert_eq!(tracker.online(org_b).len(), 1);
+
+        tracker.editing(org_b, Uuid::new_v4(), Uuid::new_v4());
+        assert!(events_a.try_recv().is_err());
+        assert!(matches!(
+            events_b.try_recv(),
+            Ok(PresenceMessage::Joined { .. })
+        ));
+        assert!(matches!(
+            events_b.try_recv(),
+            Ok(PresenceMessage::Editing { organization_id, .. }) if organization_id == org_b
+        ));
+    }
+}
@@ -0,0 +1,198 @@
+//! Live WebSocket sessions on this replica, so operators can see who is
+//! connected and drop a session that misbehaves.
+
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WsSession {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub connected_at: DateTime<Utc>,
+    /// Sequence number of the last message written to the client, counting
+    /// from 1; 0 before the first.
+    pub last_sent_seq: u64,
+    /// Messages waiting to be written to the client.
+    pub queue_depth: usize,
+}
+
+pub struct SessionHandle {
+    id: Uuid,
+    organization_id: Uuid,
+    user_id: Uuid,
+    connected_at: DateTime<Utc>,
+    last_sent_seq: AtomicU64,
+    queue_depth: AtomicUsize,
+    disconnected: AtomicBool,
+    disconnect: Notify,
+    expired: AtomicBool,
+}
+
+impl SessionHandle {
+    pub fn queued(&self) {
+        self.queue_depth.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn dequeued(&self, count: usize) {
+        self.queue_depth.fetch_sub(count, Ordering::Relaxed);
+    }
+
+    pub fn sent(&self) {
+        self.last_sent_seq.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Whether an operator disconnected the session.
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected.load(Ordering::Relaxed)
+    }
+
+    /// Record that the user's auth session or membership ended while the
+    /// socket was open, so the client is told why it was closed.
+    pub fn expire(&self) {
+        self.expired.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expired.load(Ordering::Relaxed)
+    }
+
+    /// Resolves once an operator disconnects the session.
+    pub async fn disconnected(&self) {
+        if !self.is_disconnected() {
+            self.disconnect.notified().await;
+        }
+    }
+
+    fn snapshot(&self) -> WsSession {
+        WsSession {
+            id: self.id,
+            organization_id: self.organization_id,
+            user_id: self.user_id,
+            connected_at: self.connected_at,
+            last_sent_seq: self.last_sent_seq.load(Ordering::Relaxed),
+            queue_depth: self.queue_depth.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SessionRegistry {
+    sessions: Mutex<HashMap<Uuid, Arc<SessionHandle>>>,
+}
+
+impl SessionRegistry {
+    /// Track a session until the returned registration is dropped.
+    pub fn register(self: &Arc<Self>, organization_id: Uuid, user_id: Uuid) -> SessionRegistration {
+        let handle = Arc::new(SessionHandle {
+            id: Uuid::new_v4(),
+            organization_id,
+            user_id,
+            connected_at: Utc::now(),
+            last_sent_seq: AtomicU64::new(0),
+            queue_depth: AtomicUsize::new(0),
+            disconnected: AtomicBool::new(false),
+            disconnect: Notify::new(),
+            expired: AtomicBool::new(false),
+        });
+        self.sessions
+            .lock()
+            .unwrap()
+            .insert(handle.id, Arc::clone(&handle));
+        SessionRegistration {
+            registry: Arc::clone(self),
+            handle,
+        }
+    }
+
+    /// Sessions oldest first.
+    pub fn list(&self) -> Vec<WsSession> {
+        let mut sessions: Vec<_> = self
+            .sessions
+            .lock()
+            .unwrap()
+            .values()
+            .map(|handle| handle.snapshot())
+            .collect();
+        sessions.sort_by_key(|session| session.connected_at);
+        sessions
+    }
+
+    /// Ask a session to close. Returns false if there's no such session.
+    pub fn disconnect(&self, id: Uuid) -> bool {
+        let Some(handle) = self.sessions.lock().unwrap().get(&id).cloned() else {
+            return false;
+        };
+        handle.disconnected.store(true, Ordering::Relaxed);
+        // Stores a permit if the session isn't waiting right now.
+        handle.disconnect.notify_one();
+        true
+    }
+}
+
+/// Keeps a session listed until dropped.
+pub struct SessionRegistration {
+    registry: Arc<SessionRegistry>,
+    handle: Arc<SessionHandle>,
+}
+
+impl SessionRegistration {
+    pub fn handle(&self) -> &Arc<SessionHandle> {
+        &self.handle
+    }
+}
+
+impl Deref for SessionRegistration {
+    type Target = SessionHandle;
+
+    fn deref(&self) -> &SessionHandle {
+        &self.handle
+    }
+}
+
+impl Drop for SessionRegistration {
+    fn drop(&mut self) {
+        self.registry
+            .sessions
+            .lock()
+            .unwrap()
+            .remove(&self.handle.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sessions_listed_until_dropped() {
+        let registry = Arc::new(SessionRegistry::default());
+        let session = registry.register(Uuid::new_v4(), Uuid::new_v4());
+        session.queued();
+        session.queued();
+        session.dequeued(1);
+        session.sent();
+
+        let listed = registry.list();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].last_sent_seq, 1);
+        assert_eq!(listed[0].queue_depth, 1);
+
+        assert!(registry.disconnect(session.handle().id));
+        assert!(session.is_disconnected());
+
+        drop(session);
+        assert!(registry.list().is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! Short-lived tickets for opening the organization's WebSocket.
+//!
+//! Browsers can't set an `Authorization` header on a WebSocket, so clients
+//! first ask for a ticket over an authenticated request and then put it in
+//! the upgrade URL. A ticket names the session and organization it was
+//! issued for and is signed with a key derived from the server's JWT secret.
+//! It carries a random nonce so the upgrade can refuse a ticket that has
+//! already been used.
+
+use std::fmt;
+
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How long a ticket stays valid. Only needs to cover the client connecting
+/// right after asking for it.
+pub const TICKET_TTL: Duration = Duration::seconds(30);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TicketError {
+    #[error("malformed ticket")]
+    Malformed,
+    #[error("invalid ticket signature")]
+    InvalidSignature,
+    #[error("ticket has expired")]
+    Expired,
+}
+
+/// A verified ticket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ticket {
+    pub session_id: Uuid,
+    pub organization_id: Uuid,
+    pub nonce: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct TicketSigner {
+    key: [u8; 32],
+}
+
+impl fmt::Debug for TicketSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TicketSigner").finish_non_exhaustive()
+    }
+}
+
+impl TicketSigner {
+    /// Derive a signing key dedicated to tickets from the JWT secret.
+    pub fn from_secret(secret: &SecretString) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"vk-ws-ticket:");
+        hasher.update(secret.expose_secret().as_bytes());
+        Self {
+            key: hasher.finalize().into(),
+        }
+    }
+
+    /// Sign a ticket for [`TICKET_TTL`] from `now`.
+    pub fn sign(
+        &self,
+        session_id: Uuid,
+        organization_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> (String, DateTime<Utc>) {
+        let expires_at = now + TICKET_TTL;
+        let nonce = Uuid::new_v4().simple().to_string();
+        let payload = format!(
+            "{session_id}.{organization_id}.{}.{nonce}",
+            expires_at.timestamp()
+        );
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(&payload).finalize().into_bytes());
+        (format!("{payload}.{signature}"), expires_at)
+    }
+
+    pub fn verify(&self, ticket: &str, now: DateTime<Utc>) -> Result<Ticket, TicketError> {
+        let (payload, signature) = ticket.rsplit_once('.').ok_or(TicketError::Malformed)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| TicketError::Malformed)?;
+        self.mac(payload)
+            .verify_slice(&signature)
+            .map_err(|_| TicketError::InvalidSignature)?;
+
+        let mut parts = payload.split('.');
+        let (Some(session_id), Some(organization_id), Some(expires_at), Some(nonce), None) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return Err(TicketError::Malformed);
+        };
+        let session_id = Uuid::parse_str(session_id).map_err(|_| TicketError::Malformed)?;
+        let organization_id =
+            Uuid::parse_str(organization_id).map_err(|_| TicketError::Malformed)?;
+        let expires_at: i64 = expires_at.parse().map_err(|_| TicketError::Malformed)?;
+        let expires_at = DateTime::from_timestamp(expires_at, 0).ok_or(TicketError::Malformed)?;
+
+        if now > expires_at {
+            return Err(TicketError::Expired);
+        }
+
+        Ok(Ticket {
+            session_id,
+            organization_id,
+            nonce: nonce.to_string(),
+            expires_at,
+        })
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts any key size");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signer() -> TicketSigner {
+        TicketSigner::from_secret(&SecretString::from("dGVzdC1zZWNyZXQ="))
+    }
+
+    #[test]
+    fn test_round_trips_session_and_organization() {
+        let now = Utc::now();
+        let (session_id, organization_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (ticket, expires_at) = signer().sign(session_id, organization_id, now);
+
+        let verified = signer().verify(&ticket, now).unwrap();
+        assert_eq!(verified.session_id, session_id);
+        assert_eq!(verified.organization_id, organization_id);
+        assert_eq!(expires_at, now + TICKET_TTL);
+    }
+
+    #[test]
+    fn test_rejects_tampered_and_expired_tickets() {
+        let now = Utc::now();
+        let organization_id = Uuid::new_v4();
+        let (ticket, _) = signer().sign(Uuid::new_v4(), organization_id, now);
+
+        let tampered =
+            ticket.replacen(&organization_id.to_string(), &Uuid::new_v4().to_string(), 1);
+        assert_eq!(
+            signer().verify(&tampered, now),
+            Err(TicketError::InvalidSignature)
+        );
+
+        let later = now + TICKET_TTL + Duration::seconds(1);
+        assert_eq!(signer().verify(&ticket, later), Err(TicketError::Expired));
+    }
+}
@@ -16,12 +16,19 @@ fn generate_types_content() -> String {
         db::models::repo::UpdateRepo::decl(),
         db::models::repo::SearchResult::decl(),
         db::models::repo::SearchMatchType::decl(),
+        db::models::search::GlobalSearchKind::decl(),
+        db::models::search::GlobalSearchResult::decl(),
+        db::models::activity::ActivityEventType::decl(),
+        db::models::activity::Activity::decl(),
+        db::models::pending_project_link::PendingProjectLink::decl(),
         db::models::workspace_repo::WorkspaceRepo::decl(),
         db::models::workspace_repo::CreateWorkspaceRepo::decl(),
         db::models::workspace_repo::RepoWithTargetBranch::decl(),
         db::models::tag::Tag::decl(),
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
+        db::maintenance::TableSize::decl(),
+        db::maintenance::DatabaseSize::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
         db::models::scratch::DraftWorkspaceData::decl(),
         db::models::scratch::DraftWorkspaceAttachment::decl(),
@@ -89,10 +96,43 @@ fn generate_types_content() -> String {
         api_types::ListMembersResponse::decl(),
         api_types::UpdateMemberRoleRequest::decl(),
         api_types::UpdateMemberRoleResponse::decl(),
+        api_types::OrganizationAccessPolicy::decl(),
+        api_types::UpdateOrganizationAccessPolicyRequest::decl(),
+        api_types::OrganizationRetentionPolicy::decl(),
+        api_types::UpdateOrganizationRetentionPolicyRequest::decl(),
+        api_types::OrganizationAutomationPolicy::decl(),
+        api_types::UpdateOrganizationAutomationPolicyRequest::decl(),
+        api_types::OrganizationClientConfig::decl(),
+        api_types::ProductUpdate::decl(),
+        api_types::ListProductUpdatesResponse::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
         server::routes::oauth::TokenResponse::decl(),
+        services::services::auth::AuthEvent::decl(),
+        server::routes::admin::LogLevelResponse::decl(),
+        server::routes::admin::UpdateLogLevelRequest::decl(),
+        server::routes::onboarding::CreateSampleProjectRequest::decl(),
+        server::routes::onboarding::SampleProjectResponse::decl(),
+        server::routes::activity::ActivityQuery::decl(),
+        server::routes::activity::ActivityFeedEntry::decl(),
+        server::routes::activity::ActivitySyncStatus::decl(),
+        server::routes::activity::ActivityFeed::decl(),
+        server::routes::activity::ActivityCatchUpQuery::decl(),
+        services::services::activity::ActivityCatchUpSummary::decl(),
+        server::routes::projects::ShareAllEvent::decl(),
+        server::routes::projects::ShareAllPreview::decl(),
+        server::routes::projects::SharedTaskPreview::decl(),
+        server::routes::projects::LinkRemoteProjectRequest::decl(),
+        server::routes::projects::ImportGitHubIssuesRequest::decl(),
+        services::services::github_import::GitHubImportSummary::decl(),
+        server::routes::projects::ChangelogQuery::decl(),
+        services::services::changelog::Changelog::decl(),
+        services::services::changelog::ChangelogSection::decl(),
+        services::services::changelog::ChangelogEntry::decl(),
+        server::routes::open::OpenTargetQuery::decl(),
+        server::routes::open::OpenTargetKind::decl(),
+        server::routes::open::OpenTargetResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
         server::routes::config::Environment::decl(),
         server::routes::config::McpServerQuery::decl(),
@@ -138,6 +178,8 @@ fn generate_types_content() -> String {
         server::routes::workspaces::pr::CreatePrApiRequest::decl(),
         server::routes::attachments::AttachmentResponse::decl(),
         server::routes::attachments::AttachmentMetadata::decl(),
+        server::routes::attachments::PasteImageRequest::decl(),
+        server::routes::attachments::PasteImageResponse::decl(),
         db::models::requests::WorkspaceRepoInput::decl(),
         server::routes::workspaces::integration::RunAgentSetupRequest::decl(),
         server::routes::workspaces::integration::RunAgentSetupResponse::decl(),
@@ -175,6 +217,8 @@ fn generate_types_content() -> String {
         server::routes::workspaces::workspace_summary::WorkspaceSummary::decl(),
         server::routes::workspaces::workspace_summary::WorkspaceSummaryResponse::decl(),
         server::routes::workspaces::workspace_summary::DiffStats::decl(),
+        server::routes::workspaces::worktree_pool::PooledWorktreeInfo::decl(),
+        server::routes::workspaces::worktree_pool::WorktreePoolPruneReport::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
         services::services::file_search::SearchMode::decl(),
@@ -189,7 +233,15 @@ fn generate_types_content() -> String {
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
         services::services::config::SendMessageShortcut::decl(),
+        services::services::config::LogRetentionConfig::decl(),
+        services::services::config::DoNotDisturbConfig::decl(),
+        services::services::config::OrganizationRemote::decl(),
+        services::services::config::WorktreePoolConfig::decl(),
+        services::services::config::DoNotDisturbWindow::decl(),
+        git::CloneFilter::decl(),
         git::GitBranch::decl(),
+        services::services::log_retention::ProjectLogPrune::decl(),
+        services::services::log_retention::LogPruneReport::decl(),
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
         git::ConflictOp::decl(),
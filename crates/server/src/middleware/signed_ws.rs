@@ -1,6 +1,7 @@
 use std::{
     borrow::Cow,
     pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
     task::{Context, Poll},
 };
 
@@ -16,9 +17,51 @@ use deployment::Deployment;
 use futures_util::{Sink, SinkExt, Stream, StreamExt};
 use relay_control::signing::{RelaySigningService, RequestSignature};
 use relay_ws::{SignedAxumSocket, signed_axum_websocket};
+use serde::Serialize;
+use utils::ws::WsCloseCode;
 
 use crate::{DeploymentImpl, middleware::RelayRequestSignatureContext};
 
+static ACTIVE_SOCKETS: AtomicU64 = AtomicU64::new(0);
+static OPENED_SOCKETS: AtomicU64 = AtomicU64::new(0);
+static RELAYED_SOCKETS: AtomicU64 = AtomicU64::new(0);
+
+/// WebSocket connection counters since process start.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct WsStats {
+    pub active: u64,
+    pub opened: u64,
+    pub relayed: u64,
+}
+
+pub fn ws_stats() -> WsStats {
+    WsStats {
+        active: ACTIVE_SOCKETS.load(Ordering::Relaxed),
+        opened: OPENED_SOCKETS.load(Ordering::Relaxed),
+        relayed: RELAYED_SOCKETS.load(Ordering::Relaxed),
+    }
+}
+
+/// Keeps [`ACTIVE_SOCKETS`] accurate even if the callback panics.
+struct ActiveSocketGuard;
+
+impl ActiveSocketGuard {
+    fn new(relayed: bool) -> Self {
+        ACTIVE_SOCKETS.fetch_add(1, Ordering::Relaxed);
+        OPENED_SOCKETS.fetch_add(1, Ordering::Relaxed);
+        if relayed {
+            RELAYED_SOCKETS.fetch_add(1, Ordering::Relaxed);
+        }
+        Self
+    }
+}
+
+impl Drop for ActiveSocketGuard {
+    fn drop(&mut self) {
+        ACTIVE_SOCKETS.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
 struct RelaySigningContext {
     request_signature: RequestSignature,
     signing: RelaySigningService,
@@ -103,6 +146,7 @@ impl SignedWsUpgrade {
         };
 
         self.ws.on_upgrade(move |socket| async move {
+            let _guard = ActiveSocketGuard::new(relay_signing.is_some());
             let inner = match relay_signing {
                 Some(ctx) => {
                     match signed_axum_websocket(&ctx.signing, &ctx.request_signature, socket).await
@@ -156,6 +200,13 @@ impl MaybeSignedWebSocket {
             WebSocketInner::Signed(ws) => ws.close().await,
         }
     }
+
+    /// Close with a structured code so the client can tell how to recover.
+    /// Relayed sockets carry the close frame inside the signed channel.
+    pub async fn close_with(&mut self, code: WsCloseCode) -> anyhow::Result<()> {
+        self.send(code.close_message()).await?;
+        self.close().await
+    }
 }
 
 impl Stream for MaybeSignedWebSocket {
@@ -0,0 +1,141 @@
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use chrono::{DateTime, Duration, Utc};
+use db::models::activity::Activity;
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::activity::{
+    ActivityCatchUpSummary, ActivityProcessor, ActivityProcessorError, ProjectSync,
+};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::remote::hosting_remote};
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/activity", get(list_activity))
+        .route("/activity/catch-up", post(catch_up_activity))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ActivityQuery {
+    /// Remote project id.
+    pub project_id: Uuid,
+    /// Earliest event to include; defaults to the last 24 hours.
+    #[serde(default)]
+    #[ts(optional)]
+    pub since: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ActivityCatchUpQuery {
+    /// Remote organization whose projects should be mirrored.
+    pub organization_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ActivityFeedEntry {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub activity: Activity,
+    pub summary: String,
+}
+
+/// How fresh the entries of an [`ActivityFeed`] are.
+#[derive(Debug, Serialize, TS)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ActivitySyncStatus {
+    /// New events were mirrored before reading.
+    Synced,
+    /// The remote couldn't be reached; entries come from the local mirror.
+    Unavailable,
+    /// The backlog is too large to mirror within a request, so it's being
+    /// mirrored in the background, up to at least `latest_seq`. Entries come
+    /// from the local mirror until then; fetch again later for the rest.
+    BulkSyncRequired { latest_seq: i64 },
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ActivityFeed {
+    pub entries: Vec<ActivityFeedEntry>,
+    pub sync: ActivitySyncStatus,
+}
+
+/// Recent activity for a project, newest first. New events are mirrored from
+/// the remote first when it's reachable; otherwise, or while a large backlog
+/// is mirrored in the background, the local mirror is served.
+async fn list_activity(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ActivityQuery>,
+) -> Result<ResponseJson<ApiResponse<ActivityFeed>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let client = hosting_remote(&deployment, |client| async move {
+        client.get_remote_project(query.project_id).await
+    })
+    .await;
+    let sync = match client {
+        Ok(client) => {
+            let processor = ActivityProcessor::new(pool.clone(), client);
+            match processor.sync_project_or_defer(query.project_id).await {
+                Ok(ProjectSync::Synced(_)) => ActivitySyncStatus::Synced,
+                Ok(ProjectSync::BulkSyncRequired { latest_seq }) => {
+                    processor.spawn_bulk_sync(query.project_id);
+                    ActivitySyncStatus::BulkSyncRequired { latest_seq }
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        project_id = %query.project_id,
+                        error = %error,
+                        "Failed to sync activity; serving local copy"
+                    );
+                    ActivitySyncStatus::Unavailable
+                }
+            }
+        }
+        Err(_) => ActivitySyncStatus::Unavailable,
+    };
+
+    let since = query
+        .since
+        .unwrap_or_else(|| Utc::now() - Duration::hours(24));
+    let entries = Activity::find_since(pool, query.project_id, since)
+        .await?
+        .into_iter()
+        .map(|activity| ActivityFeedEntry {
+            summary: activity.summary(),
+            activity,
+        })
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(ActivityFeed {
+        entries,
+        sync,
+    })))
+}
+
+/// Mirror new activity for every project in an organization, e.g. for the
+/// initial sync after joining a large one.
+async fn catch_up_activity(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ActivityCatchUpQuery>,
+) -> Result<ResponseJson<ApiResponse<ActivityCatchUpSummary>>, ApiError> {
+    let client = deployment
+        .remote_client_for_org(query.organization_id)
+        .await?;
+    let processor = ActivityProcessor::new(deployment.db().pool.clone(), client);
+    let summary = processor
+        .catch_up(query.organization_id)
+        .await
+        .map_err(|error| match error {
+            ActivityProcessorError::Remote(error) => ApiError::RemoteClient(error),
+            ActivityProcessorError::Database(error) => ApiError::Database(error),
+        })?;
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
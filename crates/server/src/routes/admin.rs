@@ -0,0 +1,60 @@
+use axum::{Json, Router, response::Json as ResponseJson, routing::get};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::{
+    log_filter::{self, LogFilterError},
+    response::ApiResponse,
+};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/admin/log-level", get(get_log_level).put(update_log_level))
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct LogLevelResponse {
+    /// Active `RUST_LOG`-style directives.
+    pub filter: String,
+    /// Directives the server started with.
+    pub default_filter: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateLogLevelRequest {
+    /// New directives, e.g. `info,utils::ws=trace`. `null` restores the
+    /// startup filter.
+    pub filter: Option<String>,
+}
+
+async fn get_log_level() -> Result<ResponseJson<ApiResponse<LogLevelResponse>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(log_level_response()?)))
+}
+
+async fn update_log_level(
+    Json(payload): Json<UpdateLogLevelRequest>,
+) -> Result<ResponseJson<ApiResponse<LogLevelResponse>>, ApiError> {
+    match payload.filter.as_deref().map(str::trim) {
+        Some(filter) if !filter.is_empty() => log_filter::set(filter),
+        _ => log_filter::reset(),
+    }
+    .map_err(log_filter_error)?;
+
+    Ok(ResponseJson(ApiResponse::success(log_level_response()?)))
+}
+
+fn log_level_response() -> Result<LogLevelResponse, ApiError> {
+    Ok(LogLevelResponse {
+        filter: log_filter::current().map_err(log_filter_error)?,
+        default_filter: log_filter::default_directives()
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+fn log_filter_error(error: LogFilterError) -> ApiError {
+    match error {
+        LogFilterError::Invalid(_) => ApiError::BadRequest(error.to_string()),
+        other => ApiError::Io(std::io::Error::other(other)),
+    }
+}
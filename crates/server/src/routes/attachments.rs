@@ -6,6 +6,7 @@ use axum::{
     response::{Json as ResponseJson, Response},
     routing::{delete, get, post},
 };
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
 use chrono::{DateTime, Utc};
 use db::models::file::{File, WorkspaceAttachment};
 use deployment::Deployment;
@@ -29,7 +30,7 @@ pub(crate) fn content_type_and_disposition_for_attachment(
     }
 }
 
-fn is_safe_inline_attachment_mime_type(mime_type: &str) -> bool {
+pub(crate) fn is_safe_inline_attachment_mime_type(mime_type: &str) -> bool {
     matches!(
         mime_type,
         "image/png"
@@ -42,6 +43,43 @@ fn is_safe_inline_attachment_mime_type(mime_type: &str) -> bool {
     )
 }
 
+/// Decode pasted clipboard data into its image MIME type and bytes. Accepts a
+/// `data:` URL or bare base64, in which case the type comes from `filename`.
+pub(crate) fn decode_pasted_image(
+    data: &str,
+    filename: Option<&str>,
+) -> Result<(String, Vec<u8>), ApiError> {
+    let (mime_type, encoded) = match data.trim().strip_prefix("data:") {
+        Some(rest) => {
+            let (mime_type, encoded) = rest
+                .split_once(";base64,")
+                .ok_or_else(|| ApiError::BadRequest("Expected a base64 data URL".to_string()))?;
+            (mime_type.to_string(), encoded)
+        }
+        None => {
+            let mime_type = filename
+                .and_then(|name| mime_guess::from_path(name).first_raw())
+                .unwrap_or("image/png");
+            (mime_type.to_string(), data.trim())
+        }
+    };
+
+    if !is_safe_inline_attachment_mime_type(&mime_type) {
+        return Err(ApiError::BadRequest(format!(
+            "Unsupported image type: {mime_type}"
+        )));
+    }
+
+    let bytes = BASE64_STANDARD
+        .decode(encoded)
+        .map_err(|_| ApiError::BadRequest("Image data is not valid base64".to_string()))?;
+    if bytes.is_empty() {
+        return Err(ApiError::BadRequest("Image data is empty".to_string()));
+    }
+
+    Ok((mime_type, bytes))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct AttachmentResponse {
     pub id: Uuid,
@@ -70,6 +108,24 @@ impl AttachmentResponse {
     }
 }
 
+#[derive(Debug, Deserialize, TS)]
+pub struct PasteImageRequest {
+    /// Base64 image bytes, optionally as a `data:image/...;base64,` URL.
+    pub data: String,
+    pub filename: Option<String>,
+    /// Also upload the image to the remote attachment store for the issue.
+    #[serde(default)]
+    pub share: bool,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct PasteImageResponse {
+    pub attachment: AttachmentResponse,
+    pub remote_attachment_id: Option<Uuid>,
+    /// Markdown image reference to insert into the description.
+    pub markdown: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct AttachmentMetadata {
     pub exists: bool,
@@ -196,7 +252,7 @@ pub fn routes() -> Router<DeploymentImpl> {
 mod tests {
     use axum::http::header;
 
-    use super::content_type_and_disposition_for_attachment;
+    use super::{content_type_and_disposition_for_attachment, decode_pasted_image};
 
     #[test]
     fn allows_safe_images_inline() {
@@ -243,4 +299,22 @@ mod tests {
             "x-content-type-options"
         );
     }
+
+    #[test]
+    fn decodes_pasted_data_urls() {
+        let (mime_type, bytes) =
+            decode_pasted_image("data:image/jpeg;base64,aGVsbG8=", None).unwrap();
+        assert_eq!(mime_type, "image/jpeg");
+        assert_eq!(bytes, b"hello");
+
+        let (mime_type, _) = decode_pasted_image("aGVsbG8=", Some("shot.webp")).unwrap();
+        assert_eq!(mime_type, "image/webp");
+    }
+
+    #[test]
+    fn rejects_non_image_pastes() {
+        assert!(decode_pasted_image("data:text/html;base64,aGVsbG8=", None).is_err());
+        assert!(decode_pasted_image("data:image/png;base64,!!!", None).is_err());
+        assert!(decode_pasted_image("", None).is_err());
+    }
 }
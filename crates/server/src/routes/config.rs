@@ -32,6 +32,7 @@ use tokio::fs;
 use ts_rs::TS;
 use utils::{assets::config_path, log_msg::LogMsg, response::ApiResponse};
 use uuid::Uuid;
+use worktree_manager::WorktreePool;
 
 use crate::{
     DeploymentImpl,
@@ -189,6 +190,9 @@ async fn update_config(
             "Invalid git branch prefix. Must be a valid git branch name component without slashes.",
         ));
     }
+    if let Err(message) = git::validate_branch_template(&new_config.git_branch_template) {
+        return ResponseJson(ApiResponse::error(&message));
+    }
 
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
@@ -243,6 +247,10 @@ async fn track_config_events(deployment: &DeploymentImpl, old: &Config, new: &Co
 async fn handle_config_events(deployment: &DeploymentImpl, old: &Config, new: &Config) {
     track_config_events(deployment, old, new).await;
 
+    if old.worktree_pool != new.worktree_pool {
+        WorktreePool::set_policy(new.worktree_pool.policy());
+    }
+
     let old_host_nickname = relay_registration::clean_host_nickname(old, deployment.user_id());
     let new_host_nickname = relay_registration::clean_host_nickname(new, deployment.user_id());
 
@@ -0,0 +1,34 @@
+use axum::{
+    Router,
+    extract::State,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use db::maintenance::{self, DatabaseSize};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/db/size", get(get_db_size))
+        .route("/db/vacuum", post(vacuum_db))
+}
+
+async fn get_db_size(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<DatabaseSize>>, ApiError> {
+    let report = maintenance::size_report(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
+/// Full vacuum; returns the size breakdown afterwards.
+async fn vacuum_db(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<DatabaseSize>>, ApiError> {
+    let pool = &deployment.db().pool;
+    maintenance::vacuum(pool).await?;
+    let report = maintenance::size_report(pool).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
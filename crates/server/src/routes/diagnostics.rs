@@ -0,0 +1,197 @@
+//! Diagnostic bundle users can attach to bug reports.
+
+use std::io::{Cursor, Write};
+
+use axum::{
+    Router,
+    body::Body,
+    extract::State,
+    http::{StatusCode, header},
+    response::Response,
+    routing::post,
+};
+use chrono::Utc;
+use deployment::Deployment;
+use serde::Serialize;
+use serde_json::Value;
+use utils::{log_buffer, version::APP_VERSION};
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::signed_ws::{WsStats, ws_stats},
+};
+
+const REDACTED: &str = "[redacted]";
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/diagnostics/bundle", post(create_bundle))
+}
+
+#[derive(Debug, Serialize)]
+struct Manifest {
+    app_version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    generated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SyncStatus {
+    logged_in: bool,
+    remote_configured: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct SchemaStatus {
+    migration_count: i64,
+    latest_version: Option<i64>,
+    latest_description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Status {
+    sync: SyncStatus,
+    schema: SchemaStatus,
+    websockets: WsStats,
+}
+
+/// Zip of `manifest.json`, `config.json` (secrets redacted), `status.json`
+/// and the most recent log lines.
+async fn create_bundle(State(deployment): State<DeploymentImpl>) -> Result<Response, ApiError> {
+    let now = Utc::now();
+    let manifest = Manifest {
+        app_version: APP_VERSION,
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        generated_at: now.to_rfc3339(),
+    };
+
+    let mut config = serde_json::to_value(&*deployment.config().read().await)
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))?;
+    redact_secrets(&mut config);
+
+    let status = Status {
+        sync: SyncStatus {
+            logged_in: matches!(
+                deployment.get_login_status().await,
+                api_types::LoginStatus::LoggedIn { .. }
+            ),
+            remote_configured: deployment.remote_client().is_ok(),
+        },
+        schema: schema_status(&deployment.db().pool).await?,
+        websockets: ws_stats(),
+    };
+
+    let mut logs = log_buffer::recent_lines().join("\n");
+    logs.push('\n');
+
+    let bundle = build_zip(&[
+        ("manifest.json", to_json(&manifest)?),
+        ("config.json", to_json(&config)?),
+        ("status.json", to_json(&status)?),
+        ("logs.txt", logs.into_bytes()),
+    ])
+    .map_err(|e| ApiError::Io(std::io::Error::other(e)))?;
+
+    let filename = format!(
+        "vibe-kanban-diagnostics-{}.zip",
+        now.format("%Y%m%dT%H%M%SZ")
+    );
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/zip")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{filename}\""),
+        )
+        .header(header::CACHE_CONTROL, "no-store")
+        .body(Body::from(bundle))
+        .map_err(|e| ApiError::Io(std::io::Error::other(e)))
+}
+
+async fn schema_status(pool: &sqlx::SqlitePool) -> Result<SchemaStatus, ApiError> {
+    let migration_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations")
+        .fetch_one(pool)
+        .await?;
+    let latest: Option<(i64, String)> = sqlx::query_as(
+        "SELECT version, description FROM _sqlx_migrations ORDER BY version DESC LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(SchemaStatus {
+        migration_count,
+        latest_version: latest.as_ref().map(|(version, _)| *version),
+        latest_description: latest.map(|(_, description)| description),
+    })
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Vec<u8>, ApiError> {
+    serde_json::to_vec_pretty(value).map_err(|e| ApiError::Io(std::io::Error::other(e)))
+}
+
+fn build_zip(entries: &[(&str, Vec<u8>)]) -> zip::result::ZipResult<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in entries {
+        zip.start_file(*name, options)?;
+        zip.write_all(contents)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Replace credential and personal values anywhere in `value`.
+fn redact_secrets(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_sensitive_key(key) && !value.is_null() {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    key == "pat"
+        || ["token", "secret", "password", "api_key", "email"]
+            .iter()
+            .any(|needle| key.contains(needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::redact_secrets;
+
+    #[test]
+    fn redacts_nested_secrets_and_keeps_other_fields() {
+        let mut config = json!({
+            "theme": "DARK",
+            "github": { "pat": "ghp_x", "oauth_token": "gho_y", "username": "octo" },
+            "profiles": [{ "api_key": "sk-z" }],
+            "primary_email": null,
+        });
+
+        redact_secrets(&mut config);
+
+        assert_eq!(
+            config,
+            json!({
+                "theme": "DARK",
+                "github": { "pat": "[redacted]", "oauth_token": "[redacted]", "username": "octo" },
+                "profiles": [{ "api_key": "[redacted]" }],
+                "primary_email": null,
+            })
+        );
+    }
+}
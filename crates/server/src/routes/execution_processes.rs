@@ -13,8 +13,11 @@ use db::models::{
 use deployment::Deployment;
 use futures_util::{StreamExt, TryStreamExt};
 use serde::Deserialize;
-use services::services::container::ContainerService;
-use utils::{log_msg::LogMsg, response::ApiResponse};
+use services::services::{
+    container::ContainerService,
+    log_retention::{self, LogPruneReport},
+};
+use utils::{log_msg::LogMsg, response::ApiResponse, ws::WsCloseCode};
 use uuid::Uuid;
 
 use crate::{
@@ -244,8 +247,13 @@ async fn handle_execution_processes_by_session_ws(
         .await?
         .map_ok(|msg| msg.to_ws_message_unchecked());
 
+    let shutdown = deployment.shutdown_token();
     loop {
         tokio::select! {
+            _ = shutdown.cancelled() => {
+                let _ = socket.close_with(WsCloseCode::ServerRestarting).await;
+                break;
+            }
             item = stream.next() => {
                 match item {
                     Some(Ok(msg)) => {
@@ -254,7 +262,12 @@ async fn handle_execution_processes_by_session_ws(
                         }
                     }
                     Some(Err(e)) => {
-                        tracing::error!("stream error: {}", e);
+                        if let Some(code) = WsCloseCode::for_stream_error(&e) {
+                            tracing::warn!("closing lagged stream: {}", e);
+                            let _ = socket.close_with(code).await;
+                        } else {
+                            tracing::error!("stream error: {}", e);
+                        }
                         break;
                     }
                     None => break,
@@ -283,6 +296,24 @@ async fn get_execution_process_repo_states(
     Ok(ResponseJson(ApiResponse::success(repo_states)))
 }
 
+#[derive(Debug, Deserialize)]
+struct PruneLogsQuery {
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// Apply the configured log retention policy now. With `?dry_run=true`, only
+/// report what would be deleted.
+async fn prune_logs(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<PruneLogsQuery>,
+) -> Result<ResponseJson<ApiResponse<LogPruneReport>>, ApiError> {
+    let retention = deployment.config().read().await.log_retention.clone();
+    let report =
+        log_retention::prune_logs(&deployment.db().pool, &retention, query.dry_run).await?;
+    Ok(ResponseJson(ApiResponse::success(report)))
+}
+
 pub(super) fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let workspace_id_router = Router::new()
         .route("/", get(get_execution_process_by_id))
@@ -300,6 +331,7 @@ pub(super) fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/stream/session/ws",
             get(stream_execution_processes_by_session_ws),
         )
+        .route("/prune-logs", post(prune_logs))
         .nest("/{id}", workspace_id_router);
 
     Router::new().nest("/execution-processes", workspaces_router)
@@ -6,9 +6,13 @@ use tower_http::{compression::CompressionLayer, validate_request::ValidateReques
 
 use crate::{DeploymentImpl, middleware};
 
+pub mod activity;
+pub mod admin;
 pub mod approvals;
 pub mod config;
 pub mod containers;
+pub mod database;
+pub mod diagnostics;
 pub mod filesystem;
 // pub mod github;
 pub mod attachments;
@@ -18,8 +22,11 @@ pub mod frontend;
 pub mod health;
 pub mod host_relay;
 pub mod oauth;
+pub mod onboarding;
+pub mod open;
 pub mod organizations;
 pub mod preview;
+pub mod projects;
 pub mod relay_auth;
 pub mod releases;
 pub mod remote;
@@ -36,13 +43,20 @@ pub mod workspaces;
 pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
     let relay_signed_routes = Router::new()
         .route("/health", get(health::health_check))
+        .merge(activity::router())
+        .merge(admin::router())
         .merge(config::router())
         .merge(containers::router(&deployment))
+        .merge(database::router())
+        .merge(diagnostics::router())
         .merge(workspaces::router(&deployment))
         .merge(execution_processes::router(&deployment))
         .merge(tags::router(&deployment))
         .merge(oauth::router())
+        .merge(onboarding::router())
+        .merge(open::router())
         .merge(organizations::router())
+        .merge(projects::router())
         .merge(filesystem::router())
         .merge(repo::router())
         .merge(events::router(&deployment))
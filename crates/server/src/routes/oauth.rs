@@ -1,3 +1,5 @@
+use std::convert::Infallible;
+
 use api_types::{
     AuthMethodsResponse, HandoffInitRequest, HandoffRedeemRequest, LocalLoginRequest,
     ProfileResponse, StatusResponse,
@@ -6,17 +8,23 @@ use axum::{
     Router,
     extract::{Json, Query, State},
     http::{Response, StatusCode},
-    response::Json as ResponseJson,
+    response::{
+        Json as ResponseJson, Sse,
+        sse::{Event, KeepAlive},
+    },
     routing::{get, post},
 };
 use chrono::{DateTime, Utc};
 use deployment::Deployment;
+use futures_util::Stream;
 use rand::{Rng, distributions::Alphanumeric};
 use serde::{Deserialize, Serialize};
 use services::services::{
-    config::save_config_to_file, oauth_credentials::Credentials, remote_sync,
+    auth::AuthContext, config::save_config_to_file, oauth_credentials::Credentials,
+    remote_client::RemoteClient, remote_sync,
 };
 use sha2::{Digest, Sha256};
+use tokio::sync::broadcast::error::RecvError;
 use ts_rs::TS;
 use utils::{assets::config_path, jwt::extract_expiration, response::ApiResponse};
 use uuid::Uuid;
@@ -88,21 +96,33 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/auth/logout", post(logout))
         .route("/auth/status", get(status))
         .route("/auth/token", get(get_token))
+        .route("/auth/events", get(events))
         .route("/auth/user", get(get_current_user))
 }
 
 async fn auth_methods(
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OrganizationQuery>,
 ) -> Result<ResponseJson<ApiResponse<AuthMethodsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = auth_client(&deployment, query.organization_id).await?;
     let methods = client.auth_methods().await?;
     Ok(ResponseJson(ApiResponse::success(methods)))
 }
 
+/// Organization whose remote an auth route acts on. Absent for the default
+/// remote.
+#[derive(Debug, Deserialize)]
+struct OrganizationQuery {
+    #[serde(default)]
+    organization_id: Option<Uuid>,
+}
+
 #[derive(Debug, Deserialize)]
 struct HandoffInitPayload {
     provider: String,
     return_to: String,
+    #[serde(default)]
+    organization_id: Option<Uuid>,
 }
 
 #[derive(Debug, Serialize)]
@@ -115,7 +135,7 @@ async fn handoff_init(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<HandoffInitPayload>,
 ) -> Result<ResponseJson<ApiResponse<HandoffInitResponseBody>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = auth_client(&deployment, payload.organization_id).await?;
 
     let app_verifier = generate_secret();
     let app_challenge = hash_sha256_hex(&app_verifier);
@@ -129,7 +149,12 @@ async fn handoff_init(
     let response = client.handoff_init(&request).await?;
 
     deployment
-        .store_oauth_handoff(response.handoff_id, payload.provider, app_verifier)
+        .store_oauth_handoff(
+            response.handoff_id,
+            payload.provider,
+            app_verifier,
+            payload.organization_id,
+        )
         .await;
 
     Ok(ResponseJson(ApiResponse::success(
@@ -171,21 +196,24 @@ async fn handoff_complete(
         ));
     };
 
-    let (provider, app_verifier) = match deployment.take_oauth_handoff(&query.handoff_id).await {
-        Some(state) => state,
-        None => {
-            tracing::warn!(
-                handoff_id = %query.handoff_id,
-                "received callback for unknown handoff"
-            );
-            return Ok(simple_html_response(
-                StatusCode::BAD_REQUEST,
-                "OAuth handoff not found or already completed".to_string(),
-            ));
-        }
+    let Some((provider, app_verifier, organization_id)) =
+        deployment.take_oauth_handoff(&query.handoff_id).await
+    else {
+        tracing::warn!(
+            handoff_id = %query.handoff_id,
+            "received callback for unknown handoff"
+        );
+        return Ok(simple_html_response(
+            StatusCode::BAD_REQUEST,
+            "OAuth handoff not found or already completed".to_string(),
+        ));
     };
 
-    let client = deployment.remote_client()?;
+    let organization_client = organization_remote(&deployment, organization_id).await?;
+    let client = match &organization_client {
+        Some(client) => client.clone(),
+        None => deployment.remote_client()?,
+    };
 
     let redeem_request = HandoffRedeemRequest {
         handoff_id: query.handoff_id,
@@ -194,16 +222,18 @@ async fn handoff_complete(
     };
 
     let redeem = client.handoff_redeem(&redeem_request).await?;
+    let credentials = Credentials {
+        access_token: Some(redeem.access_token.clone()),
+        refresh_token: redeem.refresh_token.clone(),
+        expires_at: None,
+    };
 
-    finalize_login(
-        &deployment,
-        Credentials {
-            access_token: Some(redeem.access_token.clone()),
-            refresh_token: redeem.refresh_token.clone(),
-            expires_at: None,
-        },
-    )
-    .await?;
+    match organization_client {
+        Some(client) => save_credentials(client.auth_context(), credentials).await?,
+        None => {
+            finalize_login(&deployment, credentials).await?;
+        }
+    }
 
     let is_desktop = query.source.as_deref() == Some("desktop");
     Ok(close_window_response(
@@ -214,36 +244,49 @@ async fn handoff_complete(
 
 async fn local_login(
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OrganizationQuery>,
     Json(payload): Json<LocalLoginRequest>,
 ) -> Result<ResponseJson<ApiResponse<ProfileResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let organization_client = organization_remote(&deployment, query.organization_id).await?;
+    let client = match &organization_client {
+        Some(client) => client.clone(),
+        None => deployment.remote_client()?,
+    };
     let response = client.local_login(&payload).await?;
-    let profile = finalize_login(
-        &deployment,
-        Credentials {
-            access_token: Some(response.access_token),
-            refresh_token: response.refresh_token,
-            expires_at: None,
-        },
-    )
-    .await?;
+    let credentials = Credentials {
+        access_token: Some(response.access_token),
+        refresh_token: response.refresh_token,
+        expires_at: None,
+    };
+
+    let profile = match organization_client {
+        Some(client) => {
+            save_credentials(client.auth_context(), credentials).await?;
+            client.profile().await?
+        }
+        None => finalize_login(&deployment, credentials).await?,
+    };
 
     Ok(ResponseJson(ApiResponse::success(profile)))
 }
 
-async fn logout(State(deployment): State<DeploymentImpl>) -> Result<StatusCode, ApiError> {
+async fn logout(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OrganizationQuery>,
+) -> Result<StatusCode, ApiError> {
+    if let Some(client) = organization_remote(&deployment, query.organization_id).await? {
+        let _ = client.logout().await;
+        clear_credentials(client.auth_context()).await?;
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
     let auth_context = deployment.auth_context();
 
     if let Ok(client) = deployment.remote_client() {
         let _ = client.logout().await;
     }
 
-    auth_context.clear_credentials().await.map_err(|e| {
-        tracing::error!(?e, "failed to clear credentials");
-        ApiError::Io(e)
-    })?;
-
-    auth_context.clear_profile().await;
+    clear_credentials(auth_context).await?;
 
     relay_registration::stop_relay(&deployment).await;
 
@@ -252,9 +295,18 @@ async fn logout(State(deployment): State<DeploymentImpl>) -> Result<StatusCode,
 
 async fn status(
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OrganizationQuery>,
 ) -> Result<ResponseJson<ApiResponse<StatusResponse>>, ApiError> {
     use api_types::LoginStatus;
 
+    if let Some(client) = organization_remote(&deployment, query.organization_id).await? {
+        return Ok(ResponseJson(ApiResponse::success(StatusResponse {
+            logged_in: client.is_signed_in().await,
+            profile: None,
+            degraded: None,
+        })));
+    }
+
     let login_status = deployment.get_login_status().await;
     let degraded = deployment
         .auth_context()
@@ -281,13 +333,14 @@ async fn status(
 /// Returns the current access token (auto-refreshes if needed)
 async fn get_token(
     State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OrganizationQuery>,
 ) -> Result<ResponseJson<ApiResponse<TokenResponse>>, ApiError> {
-    let remote_client = deployment.remote_client()?;
+    let remote_client = auth_client(&deployment, query.organization_id).await?;
 
     // This will auto-refresh the token if expired
     let access_token = remote_client.access_token().await.map_err(ApiError::from)?;
 
-    let creds = deployment.auth_context().get_credentials().await;
+    let creds = remote_client.auth_context().get_credentials().await;
     let expires_at = creds.and_then(|c| c.expires_at);
 
     Ok(ResponseJson(ApiResponse::success(TokenResponse {
@@ -296,6 +349,31 @@ async fn get_token(
     })))
 }
 
+/// Streams the session's `AuthEvent`s, so the frontend renews its token
+/// before it lapses instead of after a request is rejected.
+async fn events(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OrganizationQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let client = auth_client(&deployment, query.organization_id).await?;
+    let rx = client.auth_context().subscribe();
+
+    let stream = futures_util::stream::unfold(rx, |mut rx| async move {
+        let event = loop {
+            match rx.recv().await {
+                Ok(event) => break event,
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        };
+        let event = Event::default()
+            .json_data(&event)
+            .unwrap_or_else(|_| Event::default());
+        Some((Ok(event), rx))
+    });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
 async fn get_current_user(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<CurrentUserResponse>>, ApiError> {
@@ -325,10 +403,45 @@ fn generate_secret() -> String {
         .collect()
 }
 
-async fn finalize_login(
+/// Client for the remote hosting `organization_id`, when that is not the
+/// default remote. Such a remote keeps its own sign-in, apart from the default
+/// one and everything built on it (relay, analytics, workspace sync).
+async fn organization_remote(
     deployment: &DeploymentImpl,
+    organization_id: Option<Uuid>,
+) -> Result<Option<RemoteClient>, ApiError> {
+    let Some(organization_id) = organization_id else {
+        return Ok(None);
+    };
+    if !deployment
+        .config()
+        .read()
+        .await
+        .organization_remotes
+        .contains_key(&organization_id)
+    {
+        return Ok(None);
+    }
+    Ok(Some(
+        deployment.remote_client_for_org(organization_id).await?,
+    ))
+}
+
+/// Client for the remote hosting `organization_id`, or the default remote.
+async fn auth_client(
+    deployment: &DeploymentImpl,
+    organization_id: Option<Uuid>,
+) -> Result<RemoteClient, ApiError> {
+    match organization_remote(deployment, organization_id).await? {
+        Some(client) => Ok(client),
+        None => Ok(deployment.remote_client()?),
+    }
+}
+
+async fn save_credentials(
+    auth_context: &AuthContext,
     mut credentials: Credentials,
-) -> Result<ProfileResponse, ApiError> {
+) -> Result<(), ApiError> {
     let access_token = credentials
         .access_token
         .as_ref()
@@ -337,14 +450,29 @@ async fn finalize_login(
         .map_err(|err| ApiError::BadRequest(format!("Invalid access token: {err}")))?;
     credentials.expires_at = Some(expires_at);
 
-    deployment
-        .auth_context()
+    auth_context
         .save_credentials(&credentials)
         .await
         .map_err(|e| {
             tracing::error!(?e, "failed to save credentials");
             ApiError::Io(e)
-        })?;
+        })
+}
+
+async fn clear_credentials(auth_context: &AuthContext) -> Result<(), ApiError> {
+    auth_context.clear_credentials().await.map_err(|e| {
+        tracing::error!(?e, "failed to clear credentials");
+        ApiError::Io(e)
+    })?;
+    auth_context.clear_profile().await;
+    Ok(())
+}
+
+async fn finalize_login(
+    deployment: &DeploymentImpl,
+    credentials: Credentials,
+) -> Result<ProfileResponse, ApiError> {
+    save_credentials(deployment.auth_context(), credentials).await?;
 
     let config_guard = deployment.config().read().await;
     if !config_guard.analytics_enabled {
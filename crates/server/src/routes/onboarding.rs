@@ -0,0 +1,183 @@
+use api_types::{CreateIssueRequest, CreateProjectRequest, IssuePriority};
+use axum::{Json, Router, extract::State, response::Json as ResponseJson, routing::post};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    config::save_config_to_file,
+    remote_client::{RemoteClient, RemoteClientError},
+};
+use ts_rs::TS;
+use utils::{assets::config_path, response::ApiResponse};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const SAMPLE_PROJECT_NAME: &str = "Sample project";
+const SAMPLE_PROJECT_COLOR: &str = "217 91% 60%";
+
+const CHECKLIST: &str =
+    "Welcome to Vibe Kanban! Work through this checklist to get to know the board.
+
+- [ ] Open this issue and tick off an item
+- [ ] Drag an issue into another column
+- [ ] Create a new issue
+- [ ] Start a workspace on an issue to run a coding agent
+- [ ] Review the agent's changes and open a pull request
+- [ ] Delete this sample project from onboarding when you're done";
+
+/// (title, description, priority) of the sample issues, in board order.
+const SAMPLE_ISSUES: &[(&str, &str, Option<IssuePriority>)] = &[
+    (
+        "Start here: getting started checklist",
+        CHECKLIST,
+        Some(IssuePriority::High),
+    ),
+    (
+        "Run a coding agent on an issue",
+        "Create a workspace from this issue, pick a repository and an agent, and describe what you want changed. The agent works in its own branch so your checkout stays untouched.",
+        Some(IssuePriority::Medium),
+    ),
+    (
+        "Organize work with priorities and target dates",
+        "Set a priority and a target date on this issue, then sort the board by target date to see what's due next.",
+        Some(IssuePriority::Low),
+    ),
+    (
+        "Invite a teammate",
+        "Issues are shared with everyone in the organization. Invite someone from the organization settings and assign this issue to them.",
+        None,
+    ),
+];
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/onboarding/sample-project",
+        post(create_sample_project).delete(delete_sample_project),
+    )
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateSampleProjectRequest {
+    pub organization_id: Uuid,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct SampleProjectResponse {
+    pub project_id: Uuid,
+    pub issue_ids: Vec<Uuid>,
+}
+
+/// Create a demo project with a few issues, including a guided checklist.
+/// Only one sample project exists at a time.
+async fn create_sample_project(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateSampleProjectRequest>,
+) -> Result<ResponseJson<ApiResponse<SampleProjectResponse>>, ApiError> {
+    if deployment.config().read().await.sample_project_id.is_some() {
+        return Err(ApiError::Conflict(
+            "A sample project already exists".to_string(),
+        ));
+    }
+
+    let client = deployment.remote_client()?;
+    let project = client
+        .create_project(&CreateProjectRequest {
+            id: None,
+            organization_id: payload.organization_id,
+            name: SAMPLE_PROJECT_NAME.to_string(),
+            color: SAMPLE_PROJECT_COLOR.to_string(),
+        })
+        .await?
+        .data;
+
+    let issue_ids = match create_sample_issues(&client, project.id).await {
+        Ok(issue_ids) => issue_ids,
+        Err(error) => {
+            // Don't leave a half-populated project behind.
+            if let Err(e) = client.delete_project(project.id).await {
+                tracing::warn!(
+                    project_id = %project.id,
+                    error = %e,
+                    "Failed to remove partial sample project"
+                );
+            }
+            return Err(error);
+        }
+    };
+
+    set_sample_project_id(&deployment, Some(project.id)).await?;
+
+    Ok(ResponseJson(ApiResponse::success(SampleProjectResponse {
+        project_id: project.id,
+        issue_ids,
+    })))
+}
+
+/// Delete the sample project and everything in it. Succeeds if there is none.
+async fn delete_sample_project(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let Some(project_id) = deployment.config().read().await.sample_project_id else {
+        return Ok(ResponseJson(ApiResponse::success(())));
+    };
+
+    let client = deployment.remote_client()?;
+    match client.delete_project(project_id).await {
+        Ok(()) | Err(RemoteClientError::Http { status: 404, .. }) => {}
+        Err(error) => return Err(error.into()),
+    }
+
+    set_sample_project_id(&deployment, None).await?;
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+async fn create_sample_issues(
+    client: &RemoteClient,
+    project_id: Uuid,
+) -> Result<Vec<Uuid>, ApiError> {
+    let statuses = client.list_project_statuses(project_id).await?;
+    let status_id = statuses
+        .project_statuses
+        .iter()
+        .filter(|status| !status.hidden)
+        .min_by_key(|status| status.sort_order)
+        .map(|status| status.id)
+        .ok_or_else(|| ApiError::Io(std::io::Error::other("sample project has no statuses")))?;
+
+    let mut issue_ids = Vec::with_capacity(SAMPLE_ISSUES.len());
+    for (index, (title, description, priority)) in SAMPLE_ISSUES.iter().enumerate() {
+        let issue = client
+            .create_issue(&CreateIssueRequest {
+                id: None,
+                project_id,
+                status_id,
+                title: title.to_string(),
+                description: Some(description.to_string()),
+                priority: *priority,
+                start_date: None,
+                target_date: None,
+                completed_at: None,
+                sort_order: (index + 1) as f64,
+                parent_issue_id: None,
+                parent_issue_sort_order: None,
+                extension_metadata: serde_json::json!({}),
+            })
+            .await?
+            .data;
+        issue_ids.push(issue.id);
+    }
+
+    Ok(issue_ids)
+}
+
+async fn set_sample_project_id(
+    deployment: &DeploymentImpl,
+    project_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let mut new_config = deployment.config().read().await.clone();
+    new_config.sample_project_id = project_id;
+    save_config_to_file(&new_config, &config_path()).await?;
+    *deployment.config().write().await = new_config;
+    Ok(())
+}
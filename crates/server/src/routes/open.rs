@@ -0,0 +1,137 @@
+//! Resolves deep link targets (`task:{id}`, `workspace:{id}`, ...) from
+//! emails and chat messages into in-app routes.
+
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::workspace::{Workspace, WorkspaceError};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, routes::remote::on_hosting_remote};
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/open", get(resolve_open_target))
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct OpenTargetQuery {
+    /// `task:{id}` (alias `issue:{id}`), `project:{id}` or `workspace:{id}`.
+    pub target: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenTargetKind {
+    Issue,
+    Project,
+    Workspace,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct OpenTargetResponse {
+    pub kind: OpenTargetKind,
+    /// Route to navigate to in the app.
+    pub path: String,
+    /// Project to focus, when the target belongs to one.
+    pub project_id: Option<Uuid>,
+    pub issue_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+}
+
+/// Check that the target exists and return where to navigate to.
+async fn resolve_open_target(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<OpenTargetQuery>,
+) -> Result<ResponseJson<ApiResponse<OpenTargetResponse>>, ApiError> {
+    let (kind, id) = parse_target(&query.target)
+        .ok_or_else(|| ApiError::BadRequest(format!("Invalid target: {}", query.target)))?;
+
+    let response = match kind {
+        OpenTargetKind::Issue => {
+            let issue =
+                on_hosting_remote(
+                    &deployment,
+                    |client| async move { client.get_issue(id).await },
+                )
+                .await?;
+            OpenTargetResponse {
+                kind,
+                path: format!("/projects/{}/issues/{}", issue.project_id, issue.id),
+                project_id: Some(issue.project_id),
+                issue_id: Some(issue.id),
+                workspace_id: None,
+            }
+        }
+        OpenTargetKind::Project => {
+            let project = on_hosting_remote(&deployment, |client| async move {
+                client.get_remote_project(id).await
+            })
+            .await?;
+            OpenTargetResponse {
+                kind,
+                path: format!("/projects/{}", project.id),
+                project_id: Some(project.id),
+                issue_id: None,
+                workspace_id: None,
+            }
+        }
+        OpenTargetKind::Workspace => {
+            let workspace = Workspace::find_by_id(&deployment.db().pool, id)
+                .await?
+                .ok_or(ApiError::Workspace(WorkspaceError::WorkspaceNotFound))?;
+            OpenTargetResponse {
+                kind,
+                path: format!("/workspaces/{}", workspace.id),
+                project_id: None,
+                issue_id: None,
+                workspace_id: Some(workspace.id),
+            }
+        }
+    };
+
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+/// Split `kind:id`. Shared tasks are remote issues, so `task` and `issue`
+/// resolve the same way.
+fn parse_target(target: &str) -> Option<(OpenTargetKind, Uuid)> {
+    let (kind, id) = target.trim().split_once(':')?;
+    let kind = match kind.to_ascii_lowercase().as_str() {
+        "task" | "issue" => OpenTargetKind::Issue,
+        "project" => OpenTargetKind::Project,
+        "workspace" | "attempt" => OpenTargetKind::Workspace,
+        _ => return None,
+    };
+    Some((kind, id.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::{OpenTargetKind, parse_target};
+
+    #[test]
+    fn parses_known_kinds_and_rejects_the_rest() {
+        let id = Uuid::new_v4();
+
+        assert_eq!(
+            parse_target(&format!("task:{id}")),
+            Some((OpenTargetKind::Issue, id))
+        );
+        assert_eq!(
+            parse_target(&format!("Workspace:{id}")),
+            Some((OpenTargetKind::Workspace, id))
+        );
+        assert_eq!(parse_target(&format!("repo:{id}")), None);
+        assert_eq!(parse_target("task:not-a-uuid"), None);
+        assert_eq!(parse_target(&id.to_string()), None);
+    }
+}
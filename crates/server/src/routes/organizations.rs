@@ -1,9 +1,15 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
 use api_types::{
     AcceptInvitationResponse, CreateInvitationRequest, CreateInvitationResponse,
     CreateOrganizationRequest, CreateOrganizationResponse, GetInvitationResponse,
     GetOrganizationResponse, ListInvitationsResponse, ListMembersResponse,
-    ListOrganizationsResponse, Organization, RevokeInvitationRequest, UpdateMemberRoleRequest,
-    UpdateMemberRoleResponse, UpdateOrganizationRequest,
+    ListOrganizationsResponse, Organization, OrganizationClientConfig, RevokeInvitationRequest,
+    UpdateMemberRoleRequest, UpdateMemberRoleResponse, UpdateOrganizationRequest,
 };
 use axum::{
     Router,
@@ -12,11 +18,21 @@ use axum::{
     response::Json as ResponseJson,
     routing::{delete, get, patch, post},
 };
-use deployment::Deployment;
+use deployment::{Deployment, RemoteClientNotConfigured};
+use services::services::remote_client::RemoteClientError;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{DeploymentImpl, error::ApiError, routes::remote::on_hosting_remote};
+
+/// Last client config fetched per organization and when, served while the
+/// remote is unreachable so organization defaults still apply offline.
+static CLIENT_CONFIG_CACHE: LazyLock<Mutex<HashMap<Uuid, (OrganizationClientConfig, Instant)>>> =
+    LazyLock::new(Default::default);
+
+/// How long a cached client config may be served. Another server may have
+/// saved a newer config meanwhile, and nothing here would hear about it.
+const CLIENT_CONFIG_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
 
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
@@ -45,23 +61,58 @@ pub fn router() -> Router<DeploymentImpl> {
             "/organizations/{org_id}/members/{user_id}/role",
             patch(update_member_role),
         )
+        .route(
+            "/organizations/{org_id}/client-config",
+            get(get_client_config).put(update_client_config),
+        )
 }
 
 async fn list_organizations(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<ListOrganizationsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-
-    let response = client.list_organizations().await?;
-
-    Ok(ResponseJson(ApiResponse::success(response)))
+    let clients = deployment.all_remote_clients().await;
+    if clients.is_empty() {
+        return Err(RemoteClientNotConfigured.into());
+    }
+
+    // Organizations can live on different remotes; the same organization
+    // may be visible from more than one, so keep the first copy. A remote
+    // that can't be reached, or isn't signed in to, only drops its own
+    // organizations from the list.
+    let mut organizations = Vec::new();
+    let mut seen = HashSet::new();
+    let mut last_error = None;
+    let mut any_succeeded = false;
+    for client in clients {
+        match client.list_organizations().await {
+            Ok(response) => {
+                any_succeeded = true;
+                for organization in response.organizations {
+                    if seen.insert(organization.id) {
+                        organizations.push(organization);
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!(?e, "failed to list organizations from a remote");
+                last_error = Some(e);
+            }
+        }
+    }
+    if !any_succeeded && let Some(e) = last_error {
+        return Err(e.into());
+    }
+
+    Ok(ResponseJson(ApiResponse::success(
+        ListOrganizationsResponse { organizations },
+    )))
 }
 
 async fn get_organization(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<GetOrganizationResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = deployment.remote_client_for_org(id).await?;
 
     let response = client.get_organization(id).await?;
 
@@ -93,7 +144,7 @@ async fn update_organization(
     Path(id): Path<Uuid>,
     Json(request): Json<UpdateOrganizationRequest>,
 ) -> Result<ResponseJson<ApiResponse<Organization>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = deployment.remote_client_for_org(id).await?;
 
     let response = client.update_organization(id, &request).await?;
 
@@ -104,7 +155,7 @@ async fn delete_organization(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = deployment.remote_client_for_org(id).await?;
 
     client.delete_organization(id).await?;
 
@@ -116,7 +167,7 @@ async fn create_invitation(
     Path(org_id): Path<Uuid>,
     Json(request): Json<CreateInvitationRequest>,
 ) -> Result<ResponseJson<ApiResponse<CreateInvitationResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = deployment.remote_client_for_org(org_id).await?;
 
     let response = client.create_invitation(org_id, &request).await?;
 
@@ -138,7 +189,7 @@ async fn list_invitations(
     State(deployment): State<DeploymentImpl>,
     Path(org_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<ListInvitationsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = deployment.remote_client_for_org(org_id).await?;
 
     let response = client.list_invitations(org_id).await?;
 
@@ -149,9 +200,11 @@ async fn get_invitation(
     State(deployment): State<DeploymentImpl>,
     Path(token): Path<String>,
 ) -> Result<ResponseJson<ApiResponse<GetInvitationResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-
-    let response = client.get_invitation(&token).await?;
+    let token = &token;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.get_invitation(token).await
+    })
+    .await?;
 
     Ok(ResponseJson(ApiResponse::success(response)))
 }
@@ -161,7 +214,7 @@ async fn revoke_invitation(
     Path(org_id): Path<Uuid>,
     Json(payload): Json<RevokeInvitationRequest>,
 ) -> Result<StatusCode, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = deployment.remote_client_for_org(org_id).await?;
 
     client
         .revoke_invitation(org_id, payload.invitation_id)
@@ -174,9 +227,11 @@ async fn accept_invitation(
     State(deployment): State<DeploymentImpl>,
     Path(invitation_token): Path<String>,
 ) -> Result<ResponseJson<ApiResponse<AcceptInvitationResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-
-    let response = client.accept_invitation(&invitation_token).await?;
+    let invitation_token = &invitation_token;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.accept_invitation(invitation_token).await
+    })
+    .await?;
 
     Ok(ResponseJson(ApiResponse::success(response)))
 }
@@ -185,7 +240,7 @@ async fn list_members(
     State(deployment): State<DeploymentImpl>,
     Path(org_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<ListMembersResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = deployment.remote_client_for_org(org_id).await?;
 
     let response = client.list_members(org_id).await?;
 
@@ -196,7 +251,7 @@ async fn remove_member(
     State(deployment): State<DeploymentImpl>,
     Path((org_id, user_id)): Path<(Uuid, Uuid)>,
 ) -> Result<StatusCode, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = deployment.remote_client_for_org(org_id).await?;
 
     client.remove_member(org_id, user_id).await?;
 
@@ -208,9 +263,75 @@ async fn update_member_role(
     Path((org_id, user_id)): Path<(Uuid, Uuid)>,
     Json(request): Json<UpdateMemberRoleRequest>,
 ) -> Result<ResponseJson<ApiResponse<UpdateMemberRoleResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = deployment.remote_client_for_org(org_id).await?;
 
     let response = client.update_member_role(org_id, user_id, &request).await?;
 
     Ok(ResponseJson(ApiResponse::success(response)))
 }
+
+async fn get_client_config(
+    State(deployment): State<DeploymentImpl>,
+    Path(org_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<OrganizationClientConfig>>, ApiError> {
+    let client = deployment.remote_client_for_org(org_id).await?;
+
+    let config = match client.get_organization_client_config(org_id).await {
+        Ok(config) => {
+            cache_client_config(org_id, &config);
+            config
+        }
+        Err(error) if is_unreachable(&error) => {
+            let cached = CLIENT_CONFIG_CACHE
+                .lock()
+                .ok()
+                .and_then(|cache| cache.get(&org_id).cloned())
+                .filter(|(_, fetched_at)| fetched_at.elapsed() < CLIENT_CONFIG_CACHE_TTL)
+                .map(|(config, _)| config);
+            match cached {
+                Some(config) => {
+                    tracing::warn!(%org_id, %error, "Serving cached organization client config");
+                    config
+                }
+                None => return Err(error.into()),
+            }
+        }
+        Err(error) => {
+            if let Ok(mut cache) = CLIENT_CONFIG_CACHE.lock() {
+                cache.remove(&org_id);
+            }
+            return Err(error.into());
+        }
+    };
+
+    Ok(ResponseJson(ApiResponse::success(config)))
+}
+
+async fn update_client_config(
+    State(deployment): State<DeploymentImpl>,
+    Path(org_id): Path<Uuid>,
+    Json(request): Json<OrganizationClientConfig>,
+) -> Result<ResponseJson<ApiResponse<OrganizationClientConfig>>, ApiError> {
+    let client = deployment.remote_client_for_org(org_id).await?;
+
+    let config = client
+        .update_organization_client_config(org_id, &request)
+        .await?;
+    cache_client_config(org_id, &config);
+
+    Ok(ResponseJson(ApiResponse::success(config)))
+}
+
+fn cache_client_config(org_id: Uuid, config: &OrganizationClientConfig) {
+    if let Ok(mut cache) = CLIENT_CONFIG_CACHE.lock() {
+        cache.insert(org_id, (config.clone(), Instant::now()));
+    }
+}
+
+fn is_unreachable(error: &RemoteClientError) -> bool {
+    match error {
+        RemoteClientError::Transport(_) | RemoteClientError::Timeout => true,
+        RemoteClientError::Http { status, .. } => *status >= 500,
+        _ => false,
+    }
+}
@@ -0,0 +1,333 @@
+//! Legacy local projects.
+
+use std::convert::Infallible;
+
+use axum::{
+    Router,
+    extract::{Json, Path, Query, State},
+    response::{
+        Json as ResponseJson, Sse,
+        sse::{Event, KeepAlive},
+    },
+    routing::{get, post},
+};
+use db::models::{
+    pending_project_link::PendingProjectLink,
+    project::Project,
+    repo::{Repo, RepoError},
+};
+use deployment::Deployment;
+use futures_util::Stream;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    changelog::{Changelog, ChangelogError, build_changelog},
+    github_import::{GitHubImportError, GitHubImportSummary, import_github_issues},
+    remote_client::RemoteClient,
+    task_share::{TaskShareError, plan_project_share, share_project_tasks},
+};
+use tokio::sync::mpsc;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::remote::{hosting_remote, on_hosting_remote},
+};
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/projects/pending-links", get(list_pending_links))
+        .route("/projects/link-remote", post(link_remote_project))
+        .route("/projects/{project_id}/share-all", post(share_all_tasks))
+        .route(
+            "/projects/{project_id}/share-all/preview",
+            get(preview_share_all_tasks),
+        )
+        .route("/projects/{project_id}/changelog", get(get_changelog))
+        .route(
+            "/projects/{project_id}/import-github-issues",
+            post(import_github_issues_into_project),
+        )
+}
+
+/// Exactly what sharing a project's unshared tasks uploads. Nothing is
+/// redacted or added: each issue is created from these fields alone.
+#[derive(Debug, Serialize, TS)]
+pub struct ShareAllPreview {
+    pub remote_project_id: Uuid,
+    pub issues: Vec<SharedTaskPreview>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct SharedTaskPreview {
+    pub task_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    /// Remote status the issue is created in.
+    pub status_name: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct LinkRemoteProjectRequest {
+    pub remote_project_id: Uuid,
+    /// Repository cloned into the new project.
+    pub clone_url: String,
+    /// Directory to clone into; must not exist yet.
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportGitHubIssuesRequest {
+    /// Local repository whose default remote's GitHub issues are imported.
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ChangelogQuery {
+    /// Tag of the previous release; omit to include everything before `to_tag`.
+    pub from_tag: Option<String>,
+    /// Tag of the release; omit for changes not released yet.
+    pub to_tag: Option<String>,
+}
+
+/// Progress of `POST /projects/{id}/share-all`, sent as SSE `data`.
+#[derive(Debug, Serialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ShareAllEvent {
+    Progress {
+        /// Tasks shared so far.
+        shared: usize,
+        /// Unshared tasks found at the start.
+        total: usize,
+    },
+    Finished {
+        shared: usize,
+    },
+    Failed {
+        /// Why sharing stopped. Tasks already shared stay shared, so retrying resumes.
+        message: String,
+    },
+}
+
+/// Remote projects whose shared issues aren't shown because no local project
+/// is linked to them, as recorded by activity sync.
+async fn list_pending_links(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<PendingProjectLink>>>, ApiError> {
+    let links = PendingProjectLink::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(links)))
+}
+
+/// Clone a repository and create a local project for it linked to an
+/// existing remote project, so its shared issues show up locally.
+async fn link_remote_project(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<LinkRemoteProjectRequest>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Project::find_by_remote_project_id(pool, payload.remote_project_id)
+        .await?
+        .is_some()
+    {
+        return Err(ApiError::Conflict(
+            "A local project is already linked to this remote project".to_string(),
+        ));
+    }
+
+    let remote_project = on_hosting_remote(&deployment, |client| async move {
+        client.get_remote_project(payload.remote_project_id).await
+    })
+    .await?;
+
+    let clone_filter = deployment.config().read().await.clone_filter;
+    let repo = deployment
+        .repo()
+        .clone_repo(pool, &payload.clone_url, &payload.path, clone_filter)
+        .await?;
+    let project = match Project::create_linked(
+        pool,
+        &remote_project.name,
+        repo.id,
+        remote_project.id,
+        remote_project.organization_id,
+    )
+    .await
+    {
+        Ok(project) => project,
+        Err(error) => {
+            if let Err(cleanup_error) = deployment.repo().remove_clone(pool, &repo).await {
+                tracing::warn!(
+                    path = %repo.path.display(),
+                    error = %cleanup_error,
+                    "Failed to remove clone after linking failed"
+                );
+            }
+            // The unique index on projects.remote_project_id catches a link
+            // made since the check above.
+            return Err(match error.as_database_error() {
+                Some(db_err) if db_err.is_unique_violation() => ApiError::Conflict(
+                    "A local project is already linked to this remote project".to_string(),
+                ),
+                _ => ApiError::Database(error),
+            });
+        }
+    };
+    if let Err(error) = PendingProjectLink::delete(pool, remote_project.id).await {
+        tracing::warn!(
+            remote_project_id = %remote_project.id,
+            error = %error,
+            "Failed to clear pending project link"
+        );
+    }
+
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+/// Dry run of `share-all`: nothing is written locally or remotely.
+async fn preview_share_all_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ShareAllPreview>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let (remote_project_id, client) = linked_remote_project(&deployment, project_id).await?;
+
+    let issues = plan_project_share(pool, &client, project_id, remote_project_id)
+        .await
+        .map_err(|error| match error {
+            TaskShareError::Remote(error) => ApiError::RemoteClient(error),
+            TaskShareError::Database(error) => ApiError::Database(error),
+            TaskShareError::NoStatuses => ApiError::BadGateway(error.to_string()),
+        })?
+        .into_iter()
+        .map(|issue| SharedTaskPreview {
+            task_id: issue.task_id,
+            title: issue.title,
+            description: issue.description,
+            status_name: issue.status_name,
+        })
+        .collect();
+
+    Ok(ResponseJson(ApiResponse::success(ShareAllPreview {
+        remote_project_id,
+        issues,
+    })))
+}
+
+/// The remote project `project_id` is linked to, with a client for the remote
+/// hosting its organization.
+async fn linked_remote_project(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+) -> Result<(Uuid, RemoteClient), ApiError> {
+    let project = Project::find_by_id(&deployment.db().pool, project_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Project not found".to_string()))?;
+    let remote_project_id = project.remote_project_id.ok_or_else(|| {
+        ApiError::BadRequest("Project is not linked to a remote project".to_string())
+    })?;
+    let client = match project.remote_organization_id {
+        Some(organization_id) => deployment.remote_client_for_org(organization_id).await?,
+        None => {
+            hosting_remote(deployment, |client| async move {
+                client.get_remote_project(remote_project_id).await
+            })
+            .await?
+        }
+    };
+    Ok((remote_project_id, client))
+}
+
+/// Share every local task in the project that isn't shared yet as an issue in
+/// its linked remote project, streaming progress.
+async fn share_all_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let pool = deployment.db().pool.clone();
+    let (remote_project_id, client) = linked_remote_project(&deployment, project_id).await?;
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let progress_tx = tx.clone();
+        let result = share_project_tasks(
+            &pool,
+            &client,
+            project_id,
+            remote_project_id,
+            |shared, total| {
+                let _ = progress_tx.send(ShareAllEvent::Progress { shared, total });
+            },
+        )
+        .await;
+        let event = match result {
+            Ok(shared) => ShareAllEvent::Finished { shared },
+            Err(error) => {
+                tracing::warn!(%project_id, error = %error, "Failed to share project tasks");
+                ShareAllEvent::Failed {
+                    message: error.to_string(),
+                }
+            }
+        };
+        let _ = tx.send(event);
+    });
+
+    let stream = futures_util::stream::unfold(rx, |mut rx| async move {
+        let event = rx.recv().await?;
+        let event = Event::default()
+            .json_data(&event)
+            .unwrap_or_else(|_| Event::default());
+        Some((Ok(event), rx))
+    });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// Import the open GitHub issues of one of the user's repositories into the
+/// project's linked remote project. Issues imported earlier are skipped.
+async fn import_github_issues_into_project(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<ImportGitHubIssuesRequest>,
+) -> Result<ResponseJson<ApiResponse<GitHubImportSummary>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let (remote_project_id, client) = linked_remote_project(&deployment, project_id).await?;
+    let repo = Repo::find_by_id(pool, payload.repo_id)
+        .await?
+        .ok_or(RepoError::NotFound)?;
+    let remote = deployment.git().get_default_remote(&repo.path)?;
+
+    let summary = import_github_issues(&client, &repo.path, &remote.url, remote_project_id)
+        .await
+        .map_err(|error| match error {
+            GitHubImportError::GitHost(error) => ApiError::GitHost(error),
+            GitHubImportError::Remote(error) => ApiError::RemoteClient(error),
+            GitHubImportError::NotGitHub => ApiError::BadRequest(error.to_string()),
+            GitHubImportError::NoStatuses => ApiError::BadGateway(error.to_string()),
+        })?;
+
+    Ok(ResponseJson(ApiResponse::success(summary)))
+}
+
+/// Release notes for the project's done tasks merged between two tags,
+/// grouped by parent task.
+async fn get_changelog(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Query(query): Query<ChangelogQuery>,
+) -> Result<ResponseJson<ApiResponse<Changelog>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Project::find_by_id(pool, project_id).await?.is_none() {
+        return Err(ApiError::BadRequest("Project not found".to_string()));
+    }
+
+    let changelog = build_changelog(pool, project_id, query.from_tag, query.to_tag)
+        .await
+        .map_err(|error| match error {
+            ChangelogError::Database(error) => ApiError::Database(error),
+            ChangelogError::TaskJoin(error) => ApiError::Io(std::io::Error::other(error)),
+            ChangelogError::RefNotFound => ApiError::BadRequest(error.to_string()),
+        })?;
+
+    Ok(ResponseJson(ApiResponse::success(changelog)))
+}
@@ -11,6 +11,7 @@ use serde::Deserialize;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
+use super::on_hosting_remote;
 use crate::{DeploymentImpl, error::ApiError};
 
 #[derive(Debug, Deserialize)]
@@ -34,8 +35,10 @@ async fn list_issue_assignees(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListIssueAssigneesQuery>,
 ) -> Result<ResponseJson<ApiResponse<ListIssueAssigneesResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.list_issue_assignees(query.issue_id).await?;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.list_issue_assignees(query.issue_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -43,8 +46,10 @@ async fn get_issue_assignee(
     State(deployment): State<DeploymentImpl>,
     Path(issue_assignee_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<IssueAssignee>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.get_issue_assignee(issue_assignee_id).await?;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.get_issue_assignee(issue_assignee_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -52,8 +57,11 @@ async fn create_issue_assignee(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreateIssueAssigneeRequest>,
 ) -> Result<ResponseJson<ApiResponse<MutationResponse<IssueAssignee>>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.create_issue_assignee(&request).await?;
+    let request = &request;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.create_issue_assignee(request).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -61,7 +69,9 @@ async fn delete_issue_assignee(
     State(deployment): State<DeploymentImpl>,
     Path(issue_assignee_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
-    let client = deployment.remote_client()?;
-    client.delete_issue_assignee(issue_assignee_id).await?;
+    on_hosting_remote(&deployment, |client| async move {
+        client.delete_issue_assignee(issue_assignee_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(())))
 }
@@ -11,6 +11,7 @@ use axum::{
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
+use super::on_hosting_remote;
 use crate::{DeploymentImpl, error::ApiError};
 
 pub(super) fn router() -> Router<DeploymentImpl> {
@@ -29,8 +30,10 @@ async fn list_issue_relationships(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListIssueRelationshipsQuery>,
 ) -> Result<ResponseJson<ApiResponse<ListIssueRelationshipsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.list_issue_relationships(query.issue_id).await?;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.list_issue_relationships(query.issue_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -38,8 +41,11 @@ async fn create_issue_relationship(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreateIssueRelationshipRequest>,
 ) -> Result<ResponseJson<ApiResponse<MutationResponse<IssueRelationship>>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.create_issue_relationship(&request).await?;
+    let request = &request;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.create_issue_relationship(request).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -47,7 +53,9 @@ async fn delete_issue_relationship(
     State(deployment): State<DeploymentImpl>,
     Path(relationship_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
-    let client = deployment.remote_client()?;
-    client.delete_issue_relationship(relationship_id).await?;
+    on_hosting_remote(&deployment, |client| async move {
+        client.delete_issue_relationship(relationship_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(())))
 }
@@ -9,6 +9,7 @@ use serde::Deserialize;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
+use super::on_hosting_remote;
 use crate::{DeploymentImpl, error::ApiError};
 
 #[derive(Debug, Deserialize)]
@@ -29,8 +30,10 @@ async fn list_issue_tags(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListIssueTagsQuery>,
 ) -> Result<ResponseJson<ApiResponse<ListIssueTagsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.list_issue_tags(query.issue_id).await?;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.list_issue_tags(query.issue_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -38,8 +41,10 @@ async fn get_issue_tag(
     State(deployment): State<DeploymentImpl>,
     Path(issue_tag_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<IssueTag>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.get_issue_tag(issue_tag_id).await?;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.get_issue_tag(issue_tag_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -47,8 +52,11 @@ async fn create_issue_tag(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreateIssueTagRequest>,
 ) -> Result<ResponseJson<ApiResponse<MutationResponse<IssueTag>>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.create_issue_tag(&request).await?;
+    let request = &request;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.create_issue_tag(request).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -56,7 +64,9 @@ async fn delete_issue_tag(
     State(deployment): State<DeploymentImpl>,
     Path(issue_tag_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
-    let client = deployment.remote_client()?;
-    client.delete_issue_tag(issue_tag_id).await?;
+    on_hosting_remote(&deployment, |client| async move {
+        client.delete_issue_tag(issue_tag_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(())))
 }
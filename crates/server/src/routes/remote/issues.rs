@@ -1,17 +1,26 @@
 use api_types::{
-    CreateIssueRequest, Issue, ListIssuesQuery, ListIssuesResponse, MutationResponse,
+    ApplyIssueActionsRequest, ApplyIssueActionsResponse, ConfirmUploadRequest, CreateIssueRequest,
+    InitUploadRequest, Issue, ListIssuesQuery, ListIssuesResponse, MutationResponse,
     SearchIssuesRequest, UpdateIssueRequest,
 };
 use axum::{
     Router,
-    extract::{Json, Path, Query, State},
+    extract::{DefaultBodyLimit, Json, Path, Query, State},
     response::Json as ResponseJson,
     routing::{get, post},
 };
+use deployment::Deployment;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use super::on_hosting_remote;
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::attachments::{
+        AttachmentResponse, PasteImageRequest, PasteImageResponse, decode_pasted_image,
+    },
+};
 
 pub(super) fn router() -> Router<DeploymentImpl> {
     Router::new()
@@ -21,14 +30,46 @@ pub(super) fn router() -> Router<DeploymentImpl> {
             "/issues/{issue_id}",
             get(get_issue).patch(update_issue).delete(delete_issue),
         )
+        .route("/issues/{issue_id}/actions", post(apply_issue_actions))
+        .route(
+            "/issues/{issue_id}/paste-image",
+            // Base64 inflates the 20MB attachment limit by a third.
+            post(paste_image).layer(DefaultBodyLimit::max(28 * 1024 * 1024)),
+        )
 }
 
 async fn list_issues(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListIssuesQuery>,
 ) -> Result<ResponseJson<ApiResponse<ListIssuesResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.list_issues(query.project_id).await?;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        match (query.sort_field, query.sort_direction) {
+            (None, None) => client.list_issues(query.project_id).await,
+            // Sorting is only accepted by the search endpoint's JSON body.
+            (sort_field, sort_direction) => {
+                client
+                    .search_issues(&SearchIssuesRequest {
+                        project_id: query.project_id,
+                        status_id: None,
+                        status_ids: None,
+                        priority: None,
+                        parent_issue_id: None,
+                        search: None,
+                        q: None,
+                        simple_id: None,
+                        assignee_user_id: None,
+                        tag_id: None,
+                        tag_ids: None,
+                        sort_field,
+                        sort_direction,
+                        limit: None,
+                        offset: None,
+                    })
+                    .await
+            }
+        }
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -36,8 +77,11 @@ async fn search_issues(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<SearchIssuesRequest>,
 ) -> Result<ResponseJson<ApiResponse<ListIssuesResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.search_issues(&request).await?;
+    let request = &request;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.search_issues(request).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -45,8 +89,10 @@ async fn get_issue(
     State(deployment): State<DeploymentImpl>,
     Path(issue_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<Issue>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.get_issue(issue_id).await?;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.get_issue(issue_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -54,8 +100,11 @@ async fn create_issue(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreateIssueRequest>,
 ) -> Result<ResponseJson<ApiResponse<MutationResponse<Issue>>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.create_issue(&request).await?;
+    let request = &request;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.create_issue(request).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -64,8 +113,24 @@ async fn update_issue(
     Path(issue_id): Path<Uuid>,
     Json(request): Json<UpdateIssueRequest>,
 ) -> Result<ResponseJson<ApiResponse<MutationResponse<Issue>>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.update_issue(issue_id, &request).await?;
+    let request = &request;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.update_issue(issue_id, request).await
+    })
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+async fn apply_issue_actions(
+    State(deployment): State<DeploymentImpl>,
+    Path(issue_id): Path<Uuid>,
+    Json(request): Json<ApplyIssueActionsRequest>,
+) -> Result<ResponseJson<ApiResponse<ApplyIssueActionsResponse>>, ApiError> {
+    let request = &request;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.apply_issue_actions(issue_id, request).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -73,7 +138,92 @@ async fn delete_issue(
     State(deployment): State<DeploymentImpl>,
     Path(issue_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
-    let client = deployment.remote_client()?;
-    client.delete_issue(issue_id).await?;
+    on_hosting_remote(&deployment, |client| async move {
+        client.delete_issue(issue_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(())))
 }
+
+/// Store a pasted clipboard image and return a markdown reference for the
+/// issue description. With `share`, the image is also attached to the issue
+/// on the remote so other members can see it.
+async fn paste_image(
+    State(deployment): State<DeploymentImpl>,
+    Path(issue_id): Path<Uuid>,
+    Json(request): Json<PasteImageRequest>,
+) -> Result<ResponseJson<ApiResponse<PasteImageResponse>>, ApiError> {
+    let (mime_type, data) = decode_pasted_image(&request.data, request.filename.as_deref())?;
+    let filename = request
+        .filename
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| {
+            let extension = mime_type.strip_prefix("image/").unwrap_or("png");
+            format!("pasted-image.{extension}")
+        });
+
+    let file = deployment.file().store_file(&data, &filename).await?;
+    let attachment = AttachmentResponse::from_file(file);
+
+    let remote_attachment_id = if request.share {
+        let (client, issue) = on_hosting_remote(&deployment, |client| async move {
+            let issue = client.get_issue(issue_id).await?;
+            Ok((client, issue))
+        })
+        .await?;
+        let size_bytes = data.len() as i64;
+
+        let upload = client
+            .init_attachment_upload(&InitUploadRequest {
+                project_id: issue.project_id,
+                filename: filename.clone(),
+                size_bytes,
+                hash: attachment.hash.clone(),
+            })
+            .await?;
+        if !upload.skip_upload {
+            client
+                .upload_to_url(&upload.upload_url, data, &mime_type)
+                .await?;
+        }
+        let remote = client
+            .confirm_attachment_upload(&ConfirmUploadRequest {
+                project_id: issue.project_id,
+                upload_id: upload.upload_id,
+                filename: filename.clone(),
+                content_type: Some(mime_type.clone()),
+                size_bytes,
+                hash: attachment.hash.clone(),
+                issue_id: Some(issue_id),
+                comment_id: None,
+            })
+            .await?;
+        Some(remote.id)
+    } else {
+        None
+    };
+
+    let src = match remote_attachment_id {
+        Some(id) => format!("attachment://{id}"),
+        None => attachment.file_path.clone(),
+    };
+    let label = filename.replace(['[', ']'], "");
+    let markdown = format!("![{label}]({src})");
+
+    deployment
+        .track_if_analytics_allowed(
+            "image_pasted",
+            serde_json::json!({
+                "file_id": attachment.id.to_string(),
+                "size_bytes": attachment.size_bytes,
+                "shared": remote_attachment_id.is_some(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(PasteImageResponse {
+        attachment,
+        remote_attachment_id,
+        markdown,
+    })))
+}
@@ -1,11 +1,17 @@
+use std::future::Future;
+
 use axum::Router;
+use deployment::{Deployment, RemoteClientNotConfigured};
+use services::services::remote_client::{RemoteClient, RemoteClientError};
+use uuid::Uuid;
 
-use crate::DeploymentImpl;
+use crate::{DeploymentImpl, error::ApiError};
 
 mod issue_assignees;
 mod issue_relationships;
 mod issue_tags;
 mod issues;
+mod product_updates;
 mod project_statuses;
 mod projects;
 pub mod pull_requests;
@@ -18,9 +24,77 @@ pub fn router() -> Router<DeploymentImpl> {
         .merge(issue_relationships::router())
         .merge(issue_tags::router())
         .merge(issues::router())
+        .merge(product_updates::router())
         .merge(projects::router())
         .merge(project_statuses::router())
         .merge(pull_requests::router())
         .merge(tags::router())
         .merge(workspaces::router())
 }
+
+/// Runs `request` on the remote hosting what it names. Requests only carry
+/// the id of an issue, project or workspace, not its organization, so the
+/// default remote is asked first and then each organization remote, moving
+/// on while a remote doesn't know the entity or won't show it to this user.
+pub(crate) async fn on_hosting_remote<T, F, Fut>(
+    deployment: &DeploymentImpl,
+    request: F,
+) -> Result<T, ApiError>
+where
+    F: Fn(RemoteClient) -> Fut,
+    Fut: Future<Output = Result<T, RemoteClientError>>,
+{
+    in_turn(deployment.all_remote_clients().await, request).await
+}
+
+/// The remote for which `probe` succeeds, found as in `on_hosting_remote`.
+/// With a single remote it is returned without being probed.
+pub(crate) async fn hosting_remote<T, F, Fut>(
+    deployment: &DeploymentImpl,
+    probe: F,
+) -> Result<RemoteClient, ApiError>
+where
+    F: Fn(RemoteClient) -> Fut,
+    Fut: Future<Output = Result<T, RemoteClientError>>,
+{
+    let clients = deployment.all_remote_clients().await;
+    if let [client] = clients.as_slice() {
+        return Ok(client.clone());
+    }
+    let probe = &probe;
+    in_turn(clients, |client| async move {
+        probe(client.clone()).await?;
+        Ok(client)
+    })
+    .await
+}
+
+/// The remote a local workspace is shared to.
+pub(crate) async fn workspace_remote(
+    deployment: &DeploymentImpl,
+    local_workspace_id: Uuid,
+) -> Result<RemoteClient, ApiError> {
+    hosting_remote(deployment, |client| async move {
+        client.get_workspace_by_local_id(local_workspace_id).await
+    })
+    .await
+}
+
+async fn in_turn<T, F, Fut>(clients: Vec<RemoteClient>, request: F) -> Result<T, ApiError>
+where
+    F: Fn(RemoteClient) -> Fut,
+    Fut: Future<Output = Result<T, RemoteClientError>>,
+{
+    let mut clients = clients.into_iter();
+    let Some(client) = clients.next() else {
+        return Err(RemoteClientNotConfigured.into());
+    };
+    let mut result = request(client).await;
+    for client in clients {
+        match &result {
+            Err(e) if e.may_be_on_another_remote() => result = request(client).await,
+            _ => break,
+        }
+    }
+    Ok(result?)
+}
@@ -0,0 +1,43 @@
+use api_types::ListProductUpdatesResponse;
+use axum::{
+    Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub(super) fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/product-updates", get(list_product_updates))
+        .route("/product-updates/read-all", post(mark_all_read))
+        .route("/product-updates/{id}/read", post(mark_read))
+}
+
+async fn list_product_updates(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ListProductUpdatesResponse>>, ApiError> {
+    let client = deployment.remote_client()?;
+    let response = client.list_product_updates().await?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+async fn mark_read(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ListProductUpdatesResponse>>, ApiError> {
+    let client = deployment.remote_client()?;
+    let response = client.mark_product_update_read(id).await?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+async fn mark_all_read(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<ListProductUpdatesResponse>>, ApiError> {
+    let client = deployment.remote_client()?;
+    let response = client.mark_all_product_updates_read().await?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
@@ -9,6 +9,7 @@ use serde::Deserialize;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
+use super::on_hosting_remote;
 use crate::{DeploymentImpl, error::ApiError};
 
 #[derive(Debug, Deserialize)]
@@ -24,7 +25,9 @@ async fn list_project_statuses(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListProjectStatusesQuery>,
 ) -> Result<ResponseJson<ApiResponse<ListProjectStatusesResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.list_project_statuses(query.project_id).await?;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.list_project_statuses(query.project_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
@@ -1,14 +1,19 @@
-use api_types::{ListProjectsResponse, Project};
+use api_types::{
+    ListProjectsResponse, Project, ProjectCommitSettings, ProjectPrSettings,
+    UpdateProjectCommitSettingsRequest, UpdateProjectPrSettingsRequest,
+};
 use axum::{
     Router,
-    extract::{Path, Query, State},
+    extract::{Json, Path, Query, State},
     response::Json as ResponseJson,
     routing::get,
 };
+use deployment::Deployment;
 use serde::Deserialize;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
+use super::on_hosting_remote;
 use crate::{DeploymentImpl, error::ApiError};
 
 #[derive(Debug, Deserialize)]
@@ -20,13 +25,23 @@ pub(super) fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/projects", get(list_remote_projects))
         .route("/projects/{project_id}", get(get_remote_project))
+        .route(
+            "/projects/{project_id}/pr-settings",
+            get(get_pr_settings).put(update_pr_settings),
+        )
+        .route(
+            "/projects/{project_id}/commit-settings",
+            get(get_commit_settings).put(update_commit_settings),
+        )
 }
 
 async fn list_remote_projects(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListRemoteProjectsQuery>,
 ) -> Result<ResponseJson<ApiResponse<ListProjectsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = deployment
+        .remote_client_for_org(query.organization_id)
+        .await?;
     let response = client.list_remote_projects(query.organization_id).await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
@@ -35,7 +50,63 @@ async fn get_remote_project(
     State(deployment): State<DeploymentImpl>,
     Path(project_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let project = client.get_remote_project(project_id).await?;
+    let project = on_hosting_remote(&deployment, |client| async move {
+        client.get_remote_project(project_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(project)))
 }
+
+/// How pull requests opened from this project's workspaces are created.
+/// Stored on the project, so everyone working on it shares them.
+async fn get_pr_settings(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ProjectPrSettings>>, ApiError> {
+    let settings = on_hosting_remote(&deployment, |client| async move {
+        client.get_project_pr_settings(project_id).await
+    })
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+async fn update_pr_settings(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(request): Json<UpdateProjectPrSettingsRequest>,
+) -> Result<ResponseJson<ApiResponse<ProjectPrSettings>>, ApiError> {
+    let request = &request;
+    let settings = on_hosting_remote(&deployment, |client| async move {
+        client.update_project_pr_settings(project_id, request).await
+    })
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+/// Commit message convention applied to commits made in this project's
+/// workspaces. Stored on the project, so everyone working on it shares it.
+async fn get_commit_settings(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ProjectCommitSettings>>, ApiError> {
+    let settings = on_hosting_remote(&deployment, |client| async move {
+        client.get_project_commit_settings(project_id).await
+    })
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
+
+async fn update_commit_settings(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(request): Json<UpdateProjectCommitSettingsRequest>,
+) -> Result<ResponseJson<ApiResponse<ProjectCommitSettings>>, ApiError> {
+    let request = &request;
+    let settings = on_hosting_remote(&deployment, |client| async move {
+        client
+            .update_project_commit_settings(project_id, request)
+            .await
+    })
+    .await?;
+    Ok(ResponseJson(ApiResponse::success(settings)))
+}
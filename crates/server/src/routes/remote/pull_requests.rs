@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 
+use super::on_hosting_remote;
 use crate::{DeploymentImpl, error::ApiError};
 
 pub fn router() -> Router<DeploymentImpl> {
@@ -23,8 +24,10 @@ async fn list_pull_requests(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListPullRequestsQuery>,
 ) -> Result<ResponseJson<ApiResponse<ListPullRequestsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.list_pull_requests(query.issue_id).await?;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.list_pull_requests(query.issue_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -9,6 +9,7 @@ use serde::Deserialize;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
+use super::on_hosting_remote;
 use crate::{DeploymentImpl, error::ApiError};
 
 #[derive(Debug, Deserialize)]
@@ -26,8 +27,10 @@ async fn list_tags(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ListTagsQuery>,
 ) -> Result<ResponseJson<ApiResponse<ListTagsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.list_tags(query.project_id).await?;
+    let response = on_hosting_remote(&deployment, |client| async move {
+        client.list_tags(query.project_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
@@ -35,7 +38,10 @@ async fn get_tag(
     State(deployment): State<DeploymentImpl>,
     Path(tag_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<Tag>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let response = client.get_tag(tag_id).await?;
+    let response = on_hosting_remote(
+        &deployment,
+        |client| async move { client.get_tag(tag_id).await },
+    )
+    .await?;
     Ok(ResponseJson(ApiResponse::success(response)))
 }
@@ -8,6 +8,7 @@ use axum::{
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
+use super::on_hosting_remote;
 use crate::{DeploymentImpl, error::ApiError};
 
 pub(super) fn router() -> Router<DeploymentImpl> {
@@ -21,7 +22,9 @@ async fn get_workspace_by_local_id(
     State(deployment): State<DeploymentImpl>,
     Path(local_workspace_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
-    let client = deployment.remote_client()?;
-    let workspace = client.get_workspace_by_local_id(local_workspace_id).await?;
+    let workspace = on_hosting_remote(&deployment, |client| async move {
+        client.get_workspace_by_local_id(local_workspace_id).await
+    })
+    .await?;
     Ok(ResponseJson(ApiResponse::success(workspace)))
 }
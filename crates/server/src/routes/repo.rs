@@ -144,6 +144,17 @@ pub async fn update_repo(
     Path(repo_id): Path<Uuid>,
     ResponseJson(payload): ResponseJson<UpdateRepo>,
 ) -> Result<ResponseJson<ApiResponse<Repo>>, ApiError> {
+    if let Some(Some(dirs)) = &payload.sparse_checkout
+        && let Some(bad) = dirs
+            .split(',')
+            .map(str::trim)
+            .filter(|dir| !dir.is_empty())
+            .find(|dir| !git::is_valid_sparse_checkout_dir(dir))
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid sparse checkout directory '{bad}'. Use relative paths inside the repository."
+        )));
+    }
     let repo = Repo::update(&deployment.db().pool, repo_id, &payload).await?;
     Ok(ResponseJson(ApiResponse::success(repo)))
 }
@@ -8,7 +8,7 @@ use db::models::scratch::{CreateScratch, Scratch, ScratchType, UpdateScratch};
 use deployment::Deployment;
 use futures_util::{StreamExt, TryStreamExt};
 use serde::Deserialize;
-use utils::response::ApiResponse;
+use utils::{response::ApiResponse, ws::WsCloseCode};
 use uuid::Uuid;
 
 use crate::{
@@ -125,8 +125,13 @@ async fn handle_scratch_ws(
         .await?
         .map_ok(|msg| msg.to_ws_message_unchecked());
 
+    let shutdown = deployment.shutdown_token();
     loop {
         tokio::select! {
+            _ = shutdown.cancelled() => {
+                let _ = socket.close_with(WsCloseCode::ServerRestarting).await;
+                break;
+            }
             item = stream.next() => {
                 match item {
                     Some(Ok(msg)) => {
@@ -135,7 +140,12 @@ async fn handle_scratch_ws(
                         }
                     }
                     Some(Err(e)) => {
-                        tracing::error!("scratch stream error: {}", e);
+                        if let Some(code) = WsCloseCode::for_stream_error(&e) {
+                            tracing::warn!("closing lagged stream: {}", e);
+                            let _ = socket.close_with(code).await;
+                        } else {
+                            tracing::error!("scratch stream error: {}", e);
+                        }
                         break;
                     }
                     None => break,
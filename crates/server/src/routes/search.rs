@@ -4,7 +4,10 @@ use axum::{
     response::Json as ResponseJson,
     routing::get,
 };
-use db::models::repo::{Repo, SearchResult};
+use db::models::{
+    repo::{Repo, SearchResult},
+    search::GlobalSearchResult,
+};
 use deployment::Deployment;
 use serde::Deserialize;
 use services::services::file_search::{SearchMode, SearchQuery};
@@ -68,8 +71,31 @@ pub async fn search_files(
     Ok(ResponseJson(ApiResponse::success(results)))
 }
 
+const DEFAULT_GLOBAL_SEARCH_LIMIT: i64 = 20;
+const MAX_GLOBAL_SEARCH_LIMIT: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct GlobalSearchQuery {
+    pub q: String,
+    pub limit: Option<i64>,
+}
+
+/// Ranked search across projects, tasks, workspaces and agent turns.
+pub async fn global_search(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<GlobalSearchQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<GlobalSearchResult>>>, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_GLOBAL_SEARCH_LIMIT)
+        .clamp(1, MAX_GLOBAL_SEARCH_LIMIT);
+    let results = GlobalSearchResult::search(&deployment.db().pool, &query.q, limit).await?;
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     Router::new()
         .route("/search", get(search_files))
+        .route("/search/global", get(global_search))
         .with_state(deployment.clone())
 }
@@ -28,9 +28,12 @@ use crate::{
     DeploymentImpl,
     error::ApiError,
     middleware::load_workspace_middleware,
-    routes::attachments::{
-        AttachmentMetadata, AttachmentResponse, content_type_and_disposition_for_attachment,
-        process_file_upload,
+    routes::{
+        attachments::{
+            AttachmentMetadata, AttachmentResponse, content_type_and_disposition_for_attachment,
+            process_file_upload,
+        },
+        remote::hosting_remote,
     },
 };
 
@@ -118,7 +121,10 @@ pub async fn import_issue_attachments(
     State(deployment): State<DeploymentImpl>,
     axum::Json(payload): axum::Json<ImportIssueAttachmentsRequest>,
 ) -> Result<ResponseJson<ApiResponse<ImportIssueAttachmentsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = hosting_remote(&deployment, |client| async move {
+        client.get_issue(payload.issue_id).await
+    })
+    .await?;
     let imported_attachments =
         import_issue_attachments_from_remote(&client, deployment.file(), payload.issue_id).await?;
     let attachment_ids = imported_attachments
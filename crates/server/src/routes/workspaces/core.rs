@@ -1,3 +1,4 @@
+use api_types::Patch;
 use axum::{
     Extension, Json,
     extract::{Query, State},
@@ -16,7 +17,11 @@ use sqlx::Error as SqlxError;
 use utils::response::ApiResponse;
 use workspace_manager::WorkspaceManager;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::remote::{on_hosting_remote, workspace_remote},
+};
 
 #[derive(Debug, Deserialize)]
 pub struct DeleteWorkspaceQuery {
@@ -60,23 +65,24 @@ pub async fn update_workspace(
         .await?
         .ok_or(WorkspaceError::WorkspaceNotFound)?;
 
-    if (request.archived.is_some() || request.name.is_some())
-        && let Ok(client) = deployment.remote_client()
-    {
+    if request.archived.is_some() || request.name.is_some() {
+        let deployment = deployment.clone();
         let ws = updated.clone();
-        let name = request.name.clone();
+        // Locally an empty name is stored as NULL; clear it remotely too.
+        let name = match request.name.clone() {
+            None => Patch::Absent,
+            Some(name) if name.is_empty() => Patch::Null,
+            Some(name) => Patch::Value(name),
+        };
         let archived = request.archived;
         let stats =
             diff_stream::compute_diff_stats(&deployment.db().pool, deployment.git(), &ws).await;
         tokio::spawn(async move {
-            remote_sync::sync_workspace_to_remote(
-                &client,
-                ws.id,
-                name.map(Some),
-                archived,
-                stats.as_ref(),
-            )
-            .await;
+            let Ok(client) = workspace_remote(&deployment, ws.id).await else {
+                return;
+            };
+            remote_sync::sync_workspace_to_remote(&client, ws.id, name, archived, stats.as_ref())
+                .await;
         });
     }
 
@@ -156,24 +162,21 @@ pub async fn delete_workspace(
         .await;
 
     if query.delete_remote {
-        if let Ok(client) = deployment.remote_client() {
-            match client.delete_workspace(workspace_id).await {
-                Ok(()) => {
-                    tracing::info!("Deleted remote workspace for {}", workspace_id);
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to delete remote workspace for {}: {}",
-                        workspace_id,
-                        e
-                    );
-                }
+        match on_hosting_remote(&deployment, |client| async move {
+            client.delete_workspace(workspace_id).await
+        })
+        .await
+        {
+            Ok(()) => {
+                tracing::info!("Deleted remote workspace for {}", workspace_id);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to delete remote workspace for {}: {}",
+                    workspace_id,
+                    e
+                );
             }
-        } else {
-            tracing::debug!(
-                "Remote client not available, skipping remote deletion for {}",
-                workspace_id
-            );
         }
     }
 
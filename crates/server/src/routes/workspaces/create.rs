@@ -4,6 +4,7 @@ use axum::{Json, extract::State, response::Json as ResponseJson};
 use db::models::{
     requests::{
         CreateAndStartWorkspaceRequest, CreateAndStartWorkspaceResponse, CreateWorkspaceApiRequest,
+        LinkedIssueInfo,
     },
     workspace::{CreateWorkspace, Workspace},
 };
@@ -15,23 +16,36 @@ use uuid::Uuid;
 use crate::{
     DeploymentImpl,
     error::ApiError,
-    routes::workspaces::attachments::{
-        ImportedIssueAttachment, import_issue_attachments_from_remote,
+    routes::{
+        remote::{hosting_remote, on_hosting_remote},
+        workspaces::attachments::{ImportedIssueAttachment, import_issue_attachments_from_remote},
     },
 };
 
 pub(crate) async fn create_workspace_record(
     deployment: &DeploymentImpl,
     name: Option<String>,
+    linked_issue: Option<&LinkedIssueInfo>,
 ) -> Result<Workspace, ApiError> {
     let workspace_id = Uuid::new_v4();
     let branch_label = name
         .as_deref()
         .filter(|branch_label| !branch_label.is_empty())
         .unwrap_or("workspace");
+    let task_key = linked_issue_key(deployment, linked_issue).await;
+    let username = deployment
+        .auth_context()
+        .cached_profile()
+        .await
+        .and_then(|profile| profile.username);
     let git_branch_name = deployment
         .container()
-        .git_branch_from_workspace(&workspace_id, branch_label)
+        .git_branch_from_workspace(
+            &workspace_id,
+            branch_label,
+            task_key.as_deref(),
+            username.as_deref(),
+        )
         .await;
 
     let workspace = Workspace::create(
@@ -47,11 +61,38 @@ pub(crate) async fn create_workspace_record(
     Ok(workspace)
 }
 
+/// Key of the linked issue, fetched only when the branch template uses it.
+async fn linked_issue_key(
+    deployment: &DeploymentImpl,
+    linked_issue: Option<&LinkedIssueInfo>,
+) -> Option<String> {
+    let linked_issue = linked_issue?;
+    let template = deployment.config().read().await.git_branch_template.clone();
+    if !git::branch_template_uses(&template, "task_key") {
+        return None;
+    }
+    match on_hosting_remote(deployment, |client| async move {
+        client.get_issue(linked_issue.issue_id).await
+    })
+    .await
+    {
+        Ok(issue) => Some(issue.simple_id),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to fetch issue {} for branch name: {}",
+                linked_issue.issue_id,
+                e
+            );
+            None
+        }
+    }
+}
+
 pub async fn create_workspace(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateWorkspaceApiRequest>,
 ) -> Result<ResponseJson<ApiResponse<Workspace>>, ApiError> {
-    let workspace = create_workspace_record(&deployment, payload.name).await?;
+    let workspace = create_workspace_record(&deployment, payload.name, None).await?;
 
     deployment
         .track_if_analytics_allowed(
@@ -236,7 +277,9 @@ pub async fn create_and_start_workspace(
 
     let mut managed_workspace = deployment
         .workspace_manager()
-        .load_managed_workspace(create_workspace_record(&deployment, name).await?)
+        .load_managed_workspace(
+            create_workspace_record(&deployment, name, linked_issue.as_ref()).await?,
+        )
         .await?;
 
     for repo in &repos {
@@ -251,7 +294,10 @@ pub async fn create_and_start_workspace(
     }
 
     if let Some(linked_issue) = &linked_issue
-        && let Ok(client) = deployment.remote_client()
+        && let Ok(client) = hosting_remote(&deployment, |client| async move {
+            client.get_issue(linked_issue.issue_id).await
+        })
+        .await
     {
         match import_issue_attachments_from_remote(
             &client,
@@ -3,6 +3,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use api_types::Patch;
 use axum::{
     Extension, Json, Router,
     extract::State,
@@ -24,7 +25,12 @@ use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use super::streams::{DiffStreamQuery, stream_workspace_diff_ws};
-use crate::{DeploymentImpl, error::ApiError, middleware::signed_ws::SignedWsUpgrade};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::signed_ws::SignedWsUpgrade,
+    routes::remote::{on_hosting_remote, workspace_remote},
+};
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct RebaseWorkspaceRequest {
@@ -152,15 +158,19 @@ async fn resolve_vibe_kanban_identifier(
     deployment: &DeploymentImpl,
     local_workspace_id: Uuid,
 ) -> String {
-    if let Ok(client) = deployment.remote_client()
-        && let Ok(remote_ws) = client.get_workspace_by_local_id(local_workspace_id).await
-        && let Some(issue_id) = remote_ws.issue_id
-        && let Ok(issue) = client.get_issue(issue_id).await
-    {
+    let issue = on_hosting_remote(deployment, |client| async move {
+        let remote_ws = client.get_workspace_by_local_id(local_workspace_id).await?;
+        match remote_ws.issue_id {
+            Some(issue_id) => client.get_issue(issue_id).await.map(Some),
+            None => Ok(None),
+        }
+    })
+    .await;
+    if let Ok(Some(issue)) = issue {
         if !issue.simple_id.is_empty() {
             return issue.simple_id;
         }
-        return issue_id.to_string();
+        return issue.id.to_string();
     }
     local_workspace_id.to_string()
 }
@@ -240,10 +250,13 @@ pub async fn merge_workspace(
     )
     .await?;
 
-    if let Ok(client) = deployment.remote_client() {
+    {
+        let deployment = deployment.clone();
         let workspace_id = workspace.id;
         tokio::spawn(async move {
-            remote_sync::sync_local_workspace_merge_to_remote(&client, workspace_id).await;
+            if let Ok(client) = workspace_remote(&deployment, workspace_id).await {
+                remote_sync::sync_local_workspace_merge_to_remote(&client, workspace_id).await;
+            }
         });
     }
 
@@ -293,17 +306,24 @@ pub async fn push_workspace_branch(
         .push_to_remote(&worktree_path, &workspace.branch, false)
     {
         Ok(_) => {
-            if let Ok(client) = deployment.remote_client() {
-                let pool = deployment.db().pool.clone();
-                let git = deployment.git().clone();
+            {
+                let deployment = deployment.clone();
                 let mut ws = workspace.clone();
                 ws.container_ref = Some(container_ref.clone());
                 tokio::spawn(async move {
-                    let stats = diff_stream::compute_diff_stats(&pool, &git, &ws).await;
+                    let Ok(client) = workspace_remote(&deployment, ws.id).await else {
+                        return;
+                    };
+                    let stats = diff_stream::compute_diff_stats(
+                        &deployment.db().pool,
+                        deployment.git(),
+                        &ws,
+                    )
+                    .await;
                     remote_sync::sync_workspace_to_remote(
                         &client,
                         ws.id,
-                        None,
+                        Patch::Absent,
                         None,
                         stats.as_ref(),
                     )
@@ -346,14 +366,24 @@ pub async fn force_push_workspace_branch(
         .git()
         .push_to_remote(&worktree_path, &workspace.branch, true)?;
 
-    if let Ok(client) = deployment.remote_client() {
-        let pool = deployment.db().pool.clone();
-        let git = deployment.git().clone();
+    {
+        let deployment = deployment.clone();
         let mut ws = workspace.clone();
         ws.container_ref = Some(container_ref.clone());
         tokio::spawn(async move {
-            let stats = diff_stream::compute_diff_stats(&pool, &git, &ws).await;
-            remote_sync::sync_workspace_to_remote(&client, ws.id, None, None, stats.as_ref()).await;
+            let Ok(client) = workspace_remote(&deployment, ws.id).await else {
+                return;
+            };
+            let stats =
+                diff_stream::compute_diff_stats(&deployment.db().pool, deployment.git(), &ws).await;
+            remote_sync::sync_workspace_to_remote(
+                &client,
+                ws.id,
+                Patch::Absent,
+                None,
+                stats.as_ref(),
+            )
+            .await;
         });
     }
 
@@ -13,7 +13,12 @@ use services::services::{diff_stream, remote_client::RemoteClientError, remote_s
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_workspace_middleware};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_workspace_middleware,
+    routes::remote::{hosting_remote, on_hosting_remote},
+};
 
 #[derive(Debug, Deserialize)]
 pub struct LinkWorkspaceRequest {
@@ -26,7 +31,10 @@ pub async fn link_workspace(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<LinkWorkspaceRequest>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = hosting_remote(&deployment, |client| async move {
+        client.get_remote_project(payload.project_id).await
+    })
+    .await?;
 
     let stats =
         diff_stream::compute_diff_stats(&deployment.db().pool, deployment.git(), &workspace).await;
@@ -91,14 +99,17 @@ pub async fn unlink_workspace(
     AxumPath(workspace_id): AxumPath<uuid::Uuid>,
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let deleted = on_hosting_remote(&deployment, |client| async move {
+        client.delete_workspace(workspace_id).await
+    })
+    .await;
 
-    match client.delete_workspace(workspace_id).await {
+    match deleted {
         Ok(()) => Ok(ResponseJson(ApiResponse::success(()))),
-        Err(RemoteClientError::Http { status: 404, .. }) => {
+        Err(ApiError::RemoteClient(RemoteClientError::Http { status: 404, .. })) => {
             Ok(ResponseJson(ApiResponse::success(())))
         }
-        Err(e) => Err(e.into()),
+        Err(e) => Err(e),
     }
 }
 
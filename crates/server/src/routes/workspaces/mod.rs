@@ -12,6 +12,7 @@ pub mod pr;
 pub mod repos;
 pub mod streams;
 pub mod workspace_summary;
+pub mod worktree_pool;
 
 use axum::{
     Router,
@@ -53,6 +54,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/summaries",
             post(workspace_summary::get_workspace_summaries),
         )
+        .route(
+            "/worktree-pool/prune",
+            post(worktree_pool::prune_worktree_pool),
+        )
         .nest("/{id}", workspace_id_router)
         .nest("/{id}/attachments", attachments::router(deployment))
         .nest("/{id}/links", links::router(deployment));
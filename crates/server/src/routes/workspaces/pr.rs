@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use api_types::{PullRequestStatus, UpsertPullRequestRequest};
+use api_types::{ProjectPrSettings, PullRequestStatus, UpsertPullRequestRequest};
 use axum::{
     Extension, Json, Router,
     extract::{Query, State},
@@ -25,24 +25,30 @@ use executors::actions::{
 use git::{GitCliError, GitRemote, GitServiceError};
 use git_host::{
     CreatePrRequest, GitHostError, GitHostProvider, GitHostService, ProviderKind, UnifiedPrComment,
-    github::GhCli,
+    find_pr_template, github::GhCli,
 };
 use serde::{Deserialize, Serialize};
 use services::services::{
     config::DEFAULT_PR_DESCRIPTION_PROMPT, container::ContainerService, remote_sync,
 };
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{response::ApiResponse, task_trailer::with_task_trailer};
 use uuid::Uuid;
 use workspace_manager::WorkspaceManager;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::remote::{on_hosting_remote, workspace_remote},
+};
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct CreatePrApiRequest {
     pub title: String,
+    /// Left empty, filled in as the project's PR settings say.
     pub body: Option<String>,
     pub target_branch: Option<String>,
+    /// Defaults to the project's PR settings.
     pub draft: Option<bool>,
     pub repo_id: Uuid,
     #[serde(default)]
@@ -185,6 +191,92 @@ async fn trigger_pr_description_follow_up(
     Ok(())
 }
 
+/// The linked issue as it goes into a pull request body.
+struct PrIssueLink {
+    simple_id: String,
+    title: String,
+    description: Option<String>,
+    url: String,
+}
+
+/// What a shared workspace's pull request is created with. Everything is
+/// `None` for workspaces that aren't shared or when the remote can't be
+/// reached.
+#[derive(Default)]
+struct PrContext {
+    settings: Option<ProjectPrSettings>,
+    issue_id: Option<Uuid>,
+    issue: Option<PrIssueLink>,
+}
+
+async fn load_pr_context(deployment: &DeploymentImpl, workspace_id: Uuid) -> PrContext {
+    let Ok((client, remote_workspace)) = on_hosting_remote(deployment, |client| async move {
+        let remote_workspace = client.get_workspace_by_local_id(workspace_id).await?;
+        Ok((client, remote_workspace))
+    })
+    .await
+    else {
+        return PrContext::default();
+    };
+
+    let settings = match client
+        .get_project_pr_settings(remote_workspace.project_id)
+        .await
+    {
+        Ok(settings) => Some(settings),
+        Err(e) => {
+            tracing::warn!("Failed to load PR settings: {}", e);
+            None
+        }
+    };
+
+    let issue = match remote_workspace.issue_id {
+        Some(issue_id) => match client.get_issue(issue_id).await {
+            Ok(issue) => client
+                .web_url(&format!(
+                    "/projects/{}/issues/{}",
+                    issue.project_id, issue.id
+                ))
+                .ok()
+                .map(|url| PrIssueLink {
+                    simple_id: issue.simple_id,
+                    title: issue.title,
+                    description: issue.description,
+                    url: url.to_string(),
+                }),
+            Err(e) => {
+                tracing::warn!("Failed to fetch issue {} for PR body: {}", issue_id, e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    PrContext {
+        settings,
+        issue_id: remote_workspace.issue_id,
+        issue,
+    }
+}
+
+/// Body for a pull request opened without one: the linked issue, then the
+/// repository's template.
+fn default_pr_body(issue: Option<&PrIssueLink>, template: Option<String>) -> Option<String> {
+    let mut sections = Vec::new();
+    if let Some(issue) = issue {
+        let mut section = format!("[{}]({}): {}", issue.simple_id, issue.url, issue.title);
+        if let Some(description) = issue.description.as_deref().map(str::trim)
+            && !description.is_empty()
+        {
+            section.push_str("\n\n");
+            section.push_str(description);
+        }
+        sections.push(section);
+    }
+    sections.extend(template);
+    (!sections.is_empty()).then(|| sections.join("\n\n---\n\n"))
+}
+
 pub async fn create_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
@@ -287,13 +379,45 @@ pub async fn create_pr(
 
     let provider = git_host.provider_kind();
 
+    let PrContext {
+        settings: pr_settings,
+        issue_id,
+        issue,
+    } = load_pr_context(&deployment, workspace.id).await;
+    let mut body = match request.body.clone().filter(|body| !body.trim().is_empty()) {
+        Some(body) => Some(body),
+        None => {
+            let use_template = pr_settings
+                .as_ref()
+                .is_none_or(|settings| settings.use_template);
+            let link_issue = pr_settings
+                .as_ref()
+                .is_none_or(|settings| settings.link_issue);
+            default_pr_body(
+                issue.as_ref().filter(|_| link_issue),
+                use_template
+                    .then(|| find_pr_template(&worktree_path))
+                    .flatten(),
+            )
+        }
+    };
+    // Lets the git host's webhook link the PR to the issue as well.
+    if let Some(issue_id) = issue_id {
+        body = Some(with_task_trailer(
+            body.as_deref().unwrap_or_default(),
+            issue_id,
+        ));
+    }
+
     // Create the PR
     let pr_request = CreatePrRequest {
         title: request.title.clone(),
-        body: request.body.clone(),
+        body,
         head_branch: workspace.branch.clone(),
         base_branch: base_branch.clone(),
-        draft: request.draft,
+        draft: request
+            .draft
+            .or(pr_settings.as_ref().map(|settings| settings.draft)),
         head_repo_url: Some(push_remote.url.clone()),
     };
 
@@ -316,7 +440,8 @@ pub async fn create_pr(
                 tracing::error!("Failed to create local PR record: {}", e);
             }
 
-            if let Ok(client) = deployment.remote_client() {
+            {
+                let deployment = deployment.clone();
                 let request = UpsertPullRequestRequest {
                     url: pr_info.url.clone(),
                     number: pr_info.number as i32,
@@ -327,7 +452,11 @@ pub async fn create_pr(
                     local_workspace_id: workspace.id,
                 };
                 tokio::spawn(async move {
-                    remote_sync::sync_pr_to_remote(&client, request).await;
+                    if let Ok(client) =
+                        workspace_remote(&deployment, request.local_workspace_id).await
+                    {
+                        remote_sync::sync_pr_to_remote(&client, request).await;
+                    }
                 });
             }
 
@@ -483,7 +612,8 @@ pub async fn attach_existing_pr(
             .await?;
         }
 
-        if let Ok(client) = deployment.remote_client() {
+        {
+            let deployment = deployment.clone();
             let pr_status = match pr_info.status {
                 MergeStatus::Open => PullRequestStatus::Open,
                 MergeStatus::Merged => PullRequestStatus::Merged,
@@ -500,15 +630,18 @@ pub async fn attach_existing_pr(
                 local_workspace_id: workspace.id,
             };
             tokio::spawn(async move {
-                remote_sync::sync_pr_to_remote(&client, request).await;
+                if let Ok(client) = workspace_remote(&deployment, request.local_workspace_id).await
+                {
+                    remote_sync::sync_pr_to_remote(&client, request).await;
+                }
             });
         }
 
-        // If PR is merged, archive workspace
+        // If the workspace's PRs are now all merged, archive it
         if matches!(pr_info.status, MergeStatus::Merged) {
-            let open_pr_count = PullRequest::count_open_for_workspace(pool, workspace.id).await?;
+            let status = PullRequest::aggregate_status_for_workspace(pool, workspace.id).await?;
 
-            if open_pr_count == 0 {
+            if matches!(status, Some(MergeStatus::Merged)) {
                 if !workspace.pinned
                     && let Err(e) = deployment.container().archive_workspace(workspace.id).await
                 {
@@ -516,10 +649,10 @@ pub async fn attach_existing_pr(
                 }
             } else {
                 tracing::info!(
-                    "PR #{} was merged, leaving workspace {} active with {} open PR(s)",
+                    "PR #{} was merged, leaving workspace {} active with PRs {:?}",
                     pr_info.number,
                     workspace.id,
-                    open_pr_count
+                    status
                 );
             }
         }
@@ -6,6 +6,7 @@ use axum::{
 use deployment::Deployment;
 use serde::Deserialize;
 use services::services::container::ContainerService;
+use utils::ws::WsCloseCode;
 
 use crate::{
     DeploymentImpl,
@@ -111,8 +112,13 @@ async fn handle_workspaces_ws(
         .await?
         .map_ok(|msg| msg.to_ws_message_unchecked());
 
+    let shutdown = deployment.shutdown_token();
     loop {
         tokio::select! {
+            _ = shutdown.cancelled() => {
+                let _ = socket.close_with(WsCloseCode::ServerRestarting).await;
+                break;
+            }
             item = stream.next() => {
                 match item {
                     Some(Ok(msg)) => {
@@ -121,7 +127,12 @@ async fn handle_workspaces_ws(
                         }
                     }
                     Some(Err(e)) => {
-                        tracing::error!("stream error: {}", e);
+                        if let Some(code) = WsCloseCode::for_stream_error(&e) {
+                            tracing::warn!("closing lagged stream: {}", e);
+                            let _ = socket.close_with(code).await;
+                        } else {
+                            tracing::error!("stream error: {}", e);
+                        }
                         break;
                     }
                     None => break,
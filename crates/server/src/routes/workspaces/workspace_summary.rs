@@ -4,7 +4,7 @@ use axum::{Json, extract::State, response::Json as ResponseJson};
 use db::models::{
     coding_agent_turn::CodingAgentTurn,
     execution_process::{ExecutionProcess, ExecutionProcessStatus},
-    merge::MergeStatus,
+    merge::{Merge, MergeStatus},
     pull_request::PullRequest,
     workspace::Workspace,
 };
@@ -51,6 +51,12 @@ pub struct WorkspaceSummary {
     pub pr_number: Option<i64>,
     /// PR URL for this workspace (if any PR exists)
     pub pr_url: Option<String>,
+    /// Number of PRs opened from this workspace, e.g. a stack
+    pub pr_count: usize,
+    /// Status of all this workspace's PRs taken together (if any PR exists)
+    pub aggregate_pr_status: Option<MergeStatus>,
+    /// Release the workspace's latest merge shipped in, e.g. `v1.2.3`
+    pub release_tag: Option<String>,
 }
 
 /// Response containing summaries for requested workspaces
@@ -111,6 +117,9 @@ pub async fn get_workspace_summaries(
 
     // 6. Get PR status for each workspace
     let pr_statuses = PullRequest::get_latest_for_workspaces(pool, archived).await?;
+    let all_pr_statuses = PullRequest::statuses_for_workspaces(pool, archived).await?;
+
+    let release_tags = Merge::latest_release_tags(pool, archived).await?;
 
     // 7. Compute diff stats for each workspace (in parallel)
     let diff_futures: Vec<_> = workspaces
@@ -159,6 +168,9 @@ pub async fn get_workspace_summaries(
                 pr_status: pr_statuses.get(&id).map(|pr| pr.pr_status.clone()),
                 pr_number: pr_statuses.get(&id).map(|pr| pr.pr_number),
                 pr_url: pr_statuses.get(&id).map(|pr| pr.pr_url.clone()),
+                pr_count: all_pr_statuses.get(&id).map_or(0, Vec::len),
+                aggregate_pr_status: all_pr_statuses.get(&id).and_then(MergeStatus::aggregate),
+                release_tag: release_tags.get(&id).cloned().flatten(),
             }
         })
         .collect();
@@ -0,0 +1,53 @@
+use axum::{extract::Query, response::Json as ResponseJson};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use worktree_manager::{PooledWorktree, WorktreePool};
+
+use crate::error::ApiError;
+
+#[derive(Debug, Serialize, TS)]
+pub struct PooledWorktreeInfo {
+    pub path: String,
+    pub parked_at: DateTime<Utc>,
+    pub bytes: u64,
+}
+
+impl From<PooledWorktree> for PooledWorktreeInfo {
+    fn from(worktree: PooledWorktree) -> Self {
+        Self {
+            path: worktree.path.to_string_lossy().to_string(),
+            parked_at: worktree.parked_at.into(),
+            bytes: worktree.bytes,
+        }
+    }
+}
+
+/// Worktrees in the pool after pruning, and those pruning removed or, on a
+/// dry run, would remove.
+#[derive(Debug, Serialize, TS)]
+pub struct WorktreePoolPruneReport {
+    pub kept: Vec<PooledWorktreeInfo>,
+    pub evicted: Vec<PooledWorktreeInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PruneWorktreePoolQuery {
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// Apply the configured worktree pool limits now. With `?dry_run=true`, only
+/// report what would be removed.
+pub async fn prune_worktree_pool(
+    Query(query): Query<PruneWorktreePoolQuery>,
+) -> Result<ResponseJson<ApiResponse<WorktreePoolPruneReport>>, ApiError> {
+    let report = WorktreePool::prune(query.dry_run).await?;
+    Ok(ResponseJson(ApiResponse::success(
+        WorktreePoolPruneReport {
+            kept: report.kept.into_iter().map(Into::into).collect(),
+            evicted: report.evicted.into_iter().map(Into::into).collect(),
+        },
+    )))
+}
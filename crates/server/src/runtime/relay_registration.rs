@@ -5,8 +5,13 @@ use std::net::SocketAddr;
 
 use anyhow::Context as _;
 use deployment::Deployment as _;
-use relay_tunnel_core::client::{RelayClientConfig, start_relay_client};
-use services::services::{config::Config, remote_client::RemoteClient};
+use relay_tunnel_core::{
+    client::{RelayClientConfig, start_relay_client},
+    wake::SystemResumed,
+};
+use services::services::{
+    config::Config, network_watcher::NetworkChanges, remote_client::RemoteClient,
+};
 
 use crate::DeploymentImpl;
 
@@ -28,6 +33,11 @@ pub fn clean_host_nickname(config: &Config, user_id: &str) -> String {
         .unwrap_or_else(|| default_host_nickname(user_id))
 }
 
+/// The network route changed under an open relay session.
+#[derive(Debug, thiserror::Error)]
+#[error("network route changed")]
+struct NetworkChanged;
+
 struct RelayParams {
     server_addr: SocketAddr,
     remote_client: RemoteClient,
@@ -85,6 +95,7 @@ pub async fn spawn_relay(deployment: &DeploymentImpl) {
     };
 
     let cancel_token = deployment.relay_control().reset().await;
+    let mut network = deployment.network_changes();
 
     tokio::spawn(async move {
         tracing::debug!("Relay auto-reconnect loop started");
@@ -93,8 +104,16 @@ pub async fn spawn_relay(deployment: &DeploymentImpl) {
         let max_delay = std::time::Duration::from_secs(RELAY_RECONNECT_MAX_DELAY_SECS);
 
         while !cancel_token.is_cancelled()
-            && let Err(error) = start_relay(&params, cancel_token.clone()).await
+            && let Err(error) = run_relay_session(&params, &mut network, cancel_token.clone()).await
         {
+            // The old connection died with the suspend or the old route;
+            // reconnect right away instead of backing off.
+            if error.is::<SystemResumed>() || error.is::<NetworkChanged>() {
+                tracing::info!("Relay reconnecting: {error}");
+                delay = std::time::Duration::from_secs(RELAY_RECONNECT_INITIAL_DELAY_SECS);
+                continue;
+            }
+
             tracing::debug!(
                 ?error,
                 retry_in_secs = delay.as_secs(),
@@ -119,6 +138,18 @@ pub async fn stop_relay(deployment: &DeploymentImpl) {
     tracing::debug!("Relay stopped");
 }
 
+/// Run one relay session, ending it early if the network route changes.
+async fn run_relay_session(
+    params: &RelayParams,
+    network: &mut NetworkChanges,
+    shutdown: tokio_util::sync::CancellationToken,
+) -> anyhow::Result<()> {
+    tokio::select! {
+        result = start_relay(params, shutdown) => result,
+        Ok(()) = network.changed() => Err(NetworkChanged.into()),
+    }
+}
+
 /// Start the relay client transport.
 async fn start_relay(
     params: &RelayParams,
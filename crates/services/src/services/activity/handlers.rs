@@ -0,0 +1,205 @@
+//! Storing remote activity events locally, one handler per kind of event.
+//!
+//! The processor fetches and pages; what an event means locally is up to the
+//! handlers registered for its type. A new event family on the remote is
+//! wired in by registering a handler for its types instead of growing the
+//! processor.
+
+use std::{collections::HashMap, sync::Arc};
+
+use api_types::{IssueActivity, IssueActivityEventType, Project as RemoteProject};
+use async_trait::async_trait;
+use db::models::{
+    activity::{Activity, ActivityEventType},
+    project::Project,
+};
+use sqlx::SqliteConnection;
+use uuid::Uuid;
+
+use crate::services::remote_client::RemoteClient;
+
+/// What a handler can look up about the project a page of events belongs to.
+pub struct EventContext<'a> {
+    pub project_id: Uuid,
+    pub client: &'a RemoteClient,
+    /// The remote project as of this sync.
+    pub project: &'a RemoteProject,
+    /// `(simple_id, title)` by issue ID.
+    pub issues: &'a HashMap<Uuid, (String, String)>,
+    /// Status names by status ID.
+    pub statuses: &'a HashMap<Uuid, String>,
+}
+
+#[async_trait]
+pub trait ActivityHandler: Send + Sync {
+    /// Event types this handler stores.
+    fn event_types(&self) -> &'static [IssueActivityEventType];
+
+    /// Store `event`. Runs inside the transaction that checkpoints the
+    /// event's page, so a failure leaves the page to be fetched again.
+    /// Events can arrive more than once, e.g. in a replay, so storing one
+    /// again must be a no-op.
+    async fn handle(
+        &self,
+        conn: &mut SqliteConnection,
+        context: &EventContext<'_>,
+        event: &IssueActivity,
+    ) -> Result<(), sqlx::Error>;
+}
+
+/// Handlers by the event type they store, several per type if need be. The
+/// default sends issue events to [`TimelineHandler`] and project events to
+/// [`ProjectMetadataHandler`].
+#[derive(Clone)]
+pub struct ActivityHandlers {
+    by_type: HashMap<IssueActivityEventType, Vec<Arc<dyn ActivityHandler>>>,
+}
+
+impl ActivityHandlers {
+    /// No handlers; every event is skipped.
+    pub fn empty() -> Self {
+        Self {
+            by_type: HashMap::new(),
+        }
+    }
+
+    /// Also route `handler`'s event types to it, after the handlers
+    /// registered for them before.
+    pub fn register(&mut self, handler: impl ActivityHandler + 'static) {
+        let handler: Arc<dyn ActivityHandler> = Arc::new(handler);
+        for event_type in handler.event_types() {
+            self.by_type
+                .entry(*event_type)
+                .or_default()
+                .push(handler.clone());
+        }
+    }
+
+    /// Pass `event` to each handler registered for its type, in order.
+    /// Returns `false` if there is none, so the event isn't stored.
+    pub(super) async fn dispatch(
+        &self,
+        conn: &mut SqliteConnection,
+        context: &EventContext<'_>,
+        event: &IssueActivity,
+    ) -> Result<bool, sqlx::Error> {
+        let handlers = self
+            .by_type
+            .get(&event.event_type)
+            .map_or(&[][..], Vec::as_slice);
+        if handlers.is_empty() {
+            tracing::debug!(
+                event_type = ?event.event_type,
+                seq = event.seq,
+                "No activity handler registered; leaving event for a later build"
+            );
+            return Ok(false);
+        }
+        for handler in handlers {
+            handler.handle(&mut *conn, context, event).await?;
+        }
+        Ok(true)
+    }
+}
+
+impl Default for ActivityHandlers {
+    fn default() -> Self {
+        let mut handlers = Self::empty();
+        handlers.register(TimelineHandler);
+        handlers.register(ProjectMetadataHandler);
+        handlers
+    }
+}
+
+/// Adds issue events to the `activity` table shown in the timeline.
+pub struct TimelineHandler;
+
+#[async_trait]
+impl ActivityHandler for TimelineHandler {
+    fn event_types(&self) -> &'static [IssueActivityEventType] {
+        &[
+            IssueActivityEventType::Created,
+            IssueActivityEventType::StatusChanged,
+            IssueActivityEventType::Deleted,
+            IssueActivityEventType::DueSoon,
+            IssueActivityEventType::Blocked,
+            IssueActivityEventType::Unblocked,
+            IssueActivityEventType::Mentioned,
+        ]
+    }
+
+    async fn handle(
+        &self,
+        conn: &mut SqliteConnection,
+        context: &EventContext<'_>,
+        event: &IssueActivity,
+    ) -> Result<(), sqlx::Error> {
+        let project_id = context.project_id;
+        let (Some(issue_id), Some(event_type)) = (event.issue_id, event_type(event.event_type))
+        else {
+            return Ok(());
+        };
+        let issue = context.issues.get(&issue_id);
+        let status_name = |id: Option<Uuid>| id.and_then(|id| context.statuses.get(&id).cloned());
+        let activity = Activity {
+            project_id,
+            seq: event.seq,
+            issue_id,
+            issue_simple_id: issue.map(|(simple_id, _)| simple_id.clone()),
+            issue_title: issue.map(|(_, title)| title.clone()),
+            event_type,
+            from_status: status_name(event.from_status_id),
+            to_status: status_name(event.to_status_id),
+            actor_user_id: event.actor_user_id,
+            mutation_id: event.mutation_id.clone(),
+            created_at: event.created_at,
+        };
+        // A retried request of ours can produce the same event twice; keep
+        // only the first copy.
+        if let Some(mutation_id) = &event.mutation_id
+            && context.client.is_own_mutation(mutation_id)
+            && Activity::exists_for_mutation(&mut *conn, &activity).await?
+        {
+            return Ok(());
+        }
+
+        Activity::insert(&mut *conn, &activity).await
+    }
+}
+
+/// Keeps the linked local project's copy of the remote organization
+/// current, so requests follow the project when it's transferred.
+pub struct ProjectMetadataHandler;
+
+#[async_trait]
+impl ActivityHandler for ProjectMetadataHandler {
+    fn event_types(&self) -> &'static [IssueActivityEventType] {
+        &[IssueActivityEventType::ProjectUpdated]
+    }
+
+    async fn handle(
+        &self,
+        conn: &mut SqliteConnection,
+        context: &EventContext<'_>,
+        _event: &IssueActivity,
+    ) -> Result<(), sqlx::Error> {
+        // The organization as of now rather than as of the event; an older
+        // transfer is superseded by wherever the project lives today anyway.
+        Project::set_remote_organization(conn, context.project_id, context.project.organization_id)
+            .await
+    }
+}
+
+/// The timeline's type for an issue event; `None` for other events.
+fn event_type(event_type: IssueActivityEventType) -> Option<ActivityEventType> {
+    Some(match event_type {
+        IssueActivityEventType::Created => ActivityEventType::Created,
+        IssueActivityEventType::StatusChanged => ActivityEventType::StatusChanged,
+        IssueActivityEventType::Deleted => ActivityEventType::Deleted,
+        IssueActivityEventType::DueSoon => ActivityEventType::DueSoon,
+        IssueActivityEventType::Blocked => ActivityEventType::Blocked,
+        IssueActivityEventType::Unblocked => ActivityEventType::Unblocked,
+        IssueActivityEventType::Mentioned => ActivityEventType::Mentioned,
+        IssueActivityEventType::ProjectUpdated | IssueActivityEventType::Unknown => return None,
+    })
+}
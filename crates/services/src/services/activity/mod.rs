@@ -0,0 +1,634 @@
+//! Mirrors remote issue activity into the local `activity` table so the
+//! timeline can be shown offline.
+
+mod handlers;
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    num::NonZeroUsize,
+    sync::{LazyLock, Mutex},
+    thread,
+    time::Duration,
+};
+
+use api_types::{ListIssueActivityResponse, Project as RemoteProject};
+use db::models::{
+    activity::Activity, activity_cursor::ActivityCursor, activity_issue::ActivityIssue,
+    pending_project_link::PendingProjectLink, project::Project,
+};
+use futures::{StreamExt, stream};
+pub use handlers::{
+    ActivityHandler, ActivityHandlers, EventContext, ProjectMetadataHandler, TimelineHandler,
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::remote_client::{RemoteClient, RemoteClientError};
+
+#[derive(Debug, Error)]
+pub enum ActivityProcessorError {
+    #[error(transparent)]
+    Remote(#[from] RemoteClientError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+const ISSUE_PAGE_SIZE: i32 = 500;
+
+/// Projects with a bulk sync running in the background, so repeated feed
+/// requests don't start another.
+static BULK_SYNCS: LazyLock<Mutex<HashSet<Uuid>>> = LazyLock::new(Default::default);
+
+/// How often mirrored projects are synced in the background.
+const BACKGROUND_SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Upper bound on projects synced at once during catch-up, whatever the
+/// core count, so the shared SQLite writer stays available to the UI.
+const MAX_CATCH_UP_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Default, Serialize, TS)]
+pub struct ActivityCatchUpSummary {
+    /// Projects synced successfully.
+    pub projects: usize,
+    /// Events fetched across those projects.
+    pub events: usize,
+    /// Projects whose sync failed; they're retried on the next catch-up.
+    pub failed: Vec<Uuid>,
+}
+
+/// What a project's mirror needs, decided by [`ActivityProcessor::audit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncPlan {
+    /// Nothing newer on the remote and nothing left to prune.
+    UpToDate,
+    /// A partial page behind; the next on-demand sync picks it up.
+    Incremental,
+    /// At least a full page behind; synced right away in the catch-up pool
+    /// rather than making the first feed request wait for it.
+    Bulk,
+}
+
+/// Result of [`ActivityProcessor::sync_project_or_defer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectSync {
+    /// Events fetched and stored.
+    Synced(usize),
+    /// At least a full page behind, too much to sync while a request
+    /// waits. Nothing was stored; `latest_seq` is how far the first page
+    /// reached, a lower bound for the remote's newest event.
+    BulkSyncRequired { latest_seq: i64 },
+}
+
+/// State of one project's mirror as found at startup.
+#[derive(Debug, Clone)]
+pub struct ActivityAuditReport {
+    pub project_id: Uuid,
+    /// Stored cursor before any repair; `None` for projects mirrored before
+    /// cursors existed.
+    pub cursor_seq: Option<i64>,
+    pub pruned_seq: Option<i64>,
+    /// Newest event in the local mirror.
+    pub stored_seq: i64,
+    /// Newest remote event seen by the probe; only a lower bound when
+    /// `remote_has_more` is set.
+    pub remote_seq: i64,
+    pub remote_has_more: bool,
+    /// The cursor was missing or behind the stored events and was moved up.
+    pub repaired: bool,
+    pub plan: SyncPlan,
+}
+
+pub struct ActivityProcessor {
+    pool: SqlitePool,
+    client: RemoteClient,
+    handlers: ActivityHandlers,
+}
+
+impl ActivityProcessor {
+    pub fn new(pool: SqlitePool, client: RemoteClient) -> Self {
+        Self {
+            pool,
+            client,
+            handlers: ActivityHandlers::default(),
+        }
+    }
+
+    /// Sync every project in `organization_id`, several at a time.
+    pub async fn catch_up(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<ActivityCatchUpSummary, ActivityProcessorError> {
+        let projects = self
+            .client
+            .list_remote_projects(organization_id)
+            .await?
+            .projects;
+        let summary = self
+            .sync_projects(projects.iter().map(|project| project.id))
+            .await;
+        self.record_pending_links(organization_id, &projects)
+            .await?;
+        Ok(summary)
+    }
+
+    /// Remember which of `projects` have shared issues but no local project
+    /// linked to them, so the UI can offer to link one, and forget projects
+    /// in `organization_id` the remote no longer lists.
+    async fn record_pending_links(
+        &self,
+        organization_id: Uuid,
+        projects: &[RemoteProject],
+    ) -> Result<(), sqlx::Error> {
+        for project in projects {
+            self.record_pending_link(project).await?;
+        }
+        let listed: HashSet<Uuid> = projects.iter().map(|project| project.id).collect();
+        for link in PendingProjectLink::find_all(&self.pool).await? {
+            if link.organization_id == organization_id && !listed.contains(&link.remote_project_id)
+            {
+                PendingProjectLink::delete(&self.pool, link.remote_project_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record `project` as waiting to be linked if it has mirrored issues
+    /// and no local project is linked to it, or clear it otherwise.
+    async fn record_pending_link(&self, project: &RemoteProject) -> Result<(), sqlx::Error> {
+        let linked = Project::find_by_remote_project_id(&self.pool, project.id)
+            .await?
+            .is_some();
+        let issue_count = ActivityIssue::count_by_project(&self.pool, project.id).await?;
+        if linked || issue_count == 0 {
+            PendingProjectLink::delete(&self.pool, project.id).await
+        } else {
+            PendingProjectLink::upsert(
+                &self.pool,
+                project.id,
+                project.organization_id,
+                &project.name,
+                issue_count,
+            )
+            .await
+        }
+    }
+
+    /// Check every locally mirrored project against its cursor and the
+    /// remote, repair cursors that don't match the stored events, and sync
+    /// the projects that are far behind. Meant to run once at startup, so a
+    /// crash mid-sync is noticed and resolved explicitly.
+    pub async fn audit(&self) -> Result<Vec<ActivityAuditReport>, ActivityProcessorError> {
+        let mut projects: BTreeMap<Uuid, Option<ActivityCursor>> =
+            ActivityCursor::find_all(&self.pool)
+                .await?
+                .into_iter()
+                .map(|cursor| (cursor.project_id, Some(cursor)))
+                .collect();
+        for project_id in Activity::project_ids(&self.pool).await? {
+            projects.entry(project_id).or_default();
+        }
+
+        let mut reports = Vec::with_capacity(projects.len());
+        for (project_id, cursor) in projects {
+            let report = match self.audit_project(project_id, cursor).await {
+                Ok(report) => report,
+                // Signed out: no project can be checked.
+                Err(ActivityProcessorError::Remote(RemoteClientError::Auth)) => {
+                    return Err(RemoteClientError::Auth.into());
+                }
+                Err(error) => {
+                    tracing::warn!(%project_id, %error, "Activity mirror audit failed for project");
+                    continue;
+                }
+            };
+            tracing::info!(
+                project_id = %report.project_id,
+                cursor_seq = ?report.cursor_seq,
+                pruned_seq = ?report.pruned_seq,
+                stored_seq = report.stored_seq,
+                remote_seq = report.remote_seq,
+                remote_has_more = report.remote_has_more,
+                repaired = report.repaired,
+                plan = ?report.plan,
+                "Activity mirror audit"
+            );
+            reports.push(report);
+        }
+
+        let bulk = reports
+            .iter()
+            .filter(|report| report.plan == SyncPlan::Bulk)
+            .map(|report| report.project_id);
+        let summary = self.sync_projects(bulk).await;
+        tracing::info!(
+            audited = reports.len(),
+            repaired = reports.iter().filter(|report| report.repaired).count(),
+            bulk_synced = summary.projects,
+            bulk_failed = summary.failed.len(),
+            events = summary.events,
+            "Activity mirror audit complete"
+        );
+        Ok(reports)
+    }
+
+    async fn audit_project(
+        &self,
+        project_id: Uuid,
+        cursor: Option<ActivityCursor>,
+    ) -> Result<ActivityAuditReport, ActivityProcessorError> {
+        let stored_seq = Activity::latest_seq(&self.pool, project_id).await?;
+        let repaired = cursor.is_none_or(|cursor| stored_seq > cursor.seq);
+        let mut seq = cursor.map_or(stored_seq, |cursor| cursor.seq.max(stored_seq));
+        let pruned_seq = cursor.map_or(stored_seq, |cursor| cursor.pruned_seq);
+        if repaired {
+            ActivityCursor::checkpoint(&self.pool, project_id, seq, pruned_seq, None).await?;
+        }
+        // Events an earlier build had no handler for are fetched again, in
+        // case this one handles them.
+        if let Some(unhandled_seq) = cursor.and_then(|cursor| cursor.unhandled_seq) {
+            ActivityCursor::retry_unhandled(&self.pool, project_id).await?;
+            seq = seq.min(unhandled_seq - 1);
+        }
+
+        let replay_id = cursor.map_or(0, |cursor| cursor.replay_id);
+        // A cursor behind purged history has to start over, a bulk sync.
+        let probe = match self
+            .client
+            .list_issue_activity(project_id, seq, replay_id)
+            .await
+        {
+            Err(error) if error.is_gone() => None,
+            result => Some(result?),
+        };
+        let plan = match &probe {
+            None => SyncPlan::Bulk,
+            Some(probe) if probe.has_more => SyncPlan::Bulk,
+            Some(probe) if !probe.activity.is_empty() || pruned_seq < seq => SyncPlan::Incremental,
+            Some(_) => SyncPlan::UpToDate,
+        };
+
+        Ok(ActivityAuditReport {
+            project_id,
+            cursor_seq: cursor.map(|cursor| cursor.seq),
+            pruned_seq: cursor.map(|cursor| cursor.pruned_seq),
+            stored_seq,
+            remote_seq: probe.as_ref().map_or(0, |probe| probe.latest_seq),
+            remote_has_more: probe.as_ref().is_none_or(|probe| probe.has_more),
+            repaired,
+            plan,
+        })
+    }
+
+    /// Audit the mirror, then keep every mirrored project in sync every
+    /// [`BACKGROUND_SYNC_INTERVAL`], so events are mirrored whether or not
+    /// anyone opens the feed. Stops on `shutdown`.
+    pub fn spawn_background_sync(self, shutdown: CancellationToken) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            match self.audit().await {
+                Ok(_) => {}
+                Err(ActivityProcessorError::Remote(RemoteClientError::Auth)) => {
+                    tracing::debug!("Skipping activity mirror audit: not signed in");
+                }
+                Err(error) => tracing::warn!(%error, "Activity mirror audit failed"),
+            }
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(BACKGROUND_SYNC_INTERVAL) => {}
+                }
+                if !self.client.is_signed_in().await {
+                    continue;
+                }
+                let project_ids = match ActivityCursor::find_all(&self.pool).await {
+                    Ok(cursors) => cursors.into_iter().map(|cursor| cursor.project_id),
+                    Err(error) => {
+                        tracing::warn!(%error, "Failed to load activity cursors");
+                        continue;
+                    }
+                };
+                let summary = self.sync_projects(project_ids).await;
+                // Catches projects linked, and issues deleted, since their
+                // links were recorded.
+                if let Err(error) = PendingProjectLink::prune(&self.pool).await {
+                    tracing::warn!(%error, "Failed to prune pending project links");
+                }
+                tracing::debug!(
+                    projects = summary.projects,
+                    failed = summary.failed.len(),
+                    events = summary.events,
+                    "Activity background sync complete"
+                );
+            }
+        })
+    }
+
+    /// Sync `project_ids` several at a time. Projects are independent, so
+    /// one failing doesn't stop the others.
+    async fn sync_projects(
+        &self,
+        project_ids: impl IntoIterator<Item = Uuid>,
+    ) -> ActivityCatchUpSummary {
+        let mut results = stream::iter(project_ids)
+            .map(|project_id| async move { (project_id, self.sync_project(project_id).await) })
+            .buffer_unordered(catch_up_concurrency());
+
+        let mut summary = ActivityCatchUpSummary::default();
+        while let Some((project_id, result)) = results.next().await {
+            match result {
+                Ok(events) => {
+                    summary.projects += 1;
+                    summary.events += events;
+                }
+                Err(error) => {
+                    tracing::warn!(%project_id, %error, "Activity catch-up failed for project");
+                    summary.failed.push(project_id);
+                }
+            }
+        }
+        summary
+    }
+
+    /// Fetch and store events for `project_id` newer than the last mirrored
+    /// one. Returns the number of events fetched.
+    ///
+    /// Progress is checkpointed after every page, so a sync interrupted by a
+    /// crash or shutdown resumes from the last stored page.
+    pub async fn sync_project(&self, project_id: Uuid) -> Result<usize, ActivityProcessorError> {
+        let mut cursor = self.resume_cursor(project_id).await?;
+        let page = self.first_page(&mut cursor).await?;
+        self.sync_from(project_id, cursor, page).await
+    }
+
+    /// Like [`Self::sync_project`], but when the backlog is more than a page
+    /// return [`ProjectSync::BulkSyncRequired`] without storing anything, so
+    /// the caller can answer from the mirror and sync in the background.
+    pub async fn sync_project_or_defer(
+        &self,
+        project_id: Uuid,
+    ) -> Result<ProjectSync, ActivityProcessorError> {
+        let mut cursor = self.resume_cursor(project_id).await?;
+        let page = self.first_page(&mut cursor).await?;
+        if page.has_more {
+            return Ok(ProjectSync::BulkSyncRequired {
+                latest_seq: page.latest_seq,
+            });
+        }
+        Ok(ProjectSync::Synced(
+            self.sync_from(project_id, cursor, page).await?,
+        ))
+    }
+
+    /// Run [`Self::sync_project`] in the background unless it's already
+    /// running for `project_id`.
+    pub fn spawn_bulk_sync(self, project_id: Uuid) {
+        if !BULK_SYNCS.lock().unwrap().insert(project_id) {
+            return;
+        }
+        tokio::spawn(async move {
+            match self.sync_project(project_id).await {
+                Ok(events) => tracing::info!(%project_id, events, "Activity bulk sync complete"),
+                Err(error) => tracing::warn!(%project_id, %error, "Activity bulk sync failed"),
+            }
+            BULK_SYNCS.lock().unwrap().remove(&project_id);
+        });
+    }
+
+    /// Where to resume mirroring `project_id`. Projects mirrored before
+    /// cursors existed resume from their newest stored event.
+    async fn resume_cursor(&self, project_id: Uuid) -> Result<ActivityCursor, sqlx::Error> {
+        if let Some(cursor) = ActivityCursor::find(&self.pool, project_id).await? {
+            return Ok(cursor);
+        }
+        let seq = Activity::latest_seq(&self.pool, project_id).await?;
+        Ok(ActivityCursor {
+            project_id,
+            seq,
+            pruned_seq: seq,
+            replay_id: 0,
+            unhandled_seq: None,
+        })
+    }
+
+    /// The first page after `cursor`. If an operator asked for some of the
+    /// project's activity to be fetched again since the last sync, the
+    /// cursor is moved back before the earliest range first; events already
+    /// mirrored are skipped when they arrive again. If the remote purged
+    /// history past the cursor, the mirror starts over (see
+    /// [`Self::start_over`]).
+    async fn first_page(
+        &self,
+        cursor: &mut ActivityCursor,
+    ) -> Result<ListIssueActivityResponse, ActivityProcessorError> {
+        let project_id = cursor.project_id;
+        let page = match self
+            .client
+            .list_issue_activity(project_id, cursor.seq, cursor.replay_id)
+            .await
+        {
+            Err(error) if error.is_gone() => {
+                self.start_over(cursor).await?;
+                self.client
+                    .list_issue_activity(project_id, cursor.seq, cursor.replay_id)
+                    .await?
+            }
+            result => result?,
+        };
+        let (Some(replay_id), Some(first_seq)) = (
+            page.replays.iter().map(|replay| replay.id).max(),
+            page.replays.iter().map(|replay| replay.first_seq).min(),
+        ) else {
+            return Ok(page);
+        };
+
+        let seq = cursor.seq.min(first_seq - 1);
+        ActivityCursor::rewind(&self.pool, project_id, seq, cursor.pruned_seq, replay_id).await?;
+        let rewound = seq < cursor.seq;
+        cursor.seq = seq;
+        cursor.replay_id = replay_id;
+        if !rewound {
+            return Ok(page);
+        }
+        tracing::info!(%project_id, seq, replay_id, "Replaying project activity");
+        Ok(self
+            .client
+            .list_issue_activity(project_id, seq, replay_id)
+            .await?)
+    }
+
+    /// The remote purged the history of long-deleted issues past `cursor`,
+    /// so deletions in between can no longer be listed. Drop the cached
+    /// issues, which the next page refetches without the deleted ones, and
+    /// mirror the project from its first event.
+    async fn start_over(&self, cursor: &mut ActivityCursor) -> Result<(), sqlx::Error> {
+        let project_id = cursor.project_id;
+        tracing::info!(%project_id, seq = cursor.seq, "Activity purged past cursor; starting over");
+        ActivityIssue::delete_by_project(&self.pool, project_id).await?;
+        ActivityCursor::reset(&self.pool, project_id).await?;
+        cursor.seq = 0;
+        cursor.pruned_seq = 0;
+        Ok(())
+    }
+
+    /// Store `page`, the first page after `cursor`, and the rest of the
+    /// backlog, then prune deleted issues.
+    async fn sync_from(
+        &self,
+        project_id: Uuid,
+        cursor: ActivityCursor,
+        page: ListIssueActivityResponse,
+    ) -> Result<usize, ActivityProcessorError> {
+        let count = self.mirror_events(project_id, cursor, page).await?;
+
+        // Pruned only now so the events above could still name deleted
+        // issues. Starts from `pruned_seq` rather than where this sync began
+        // so deletions missed by an interrupted sync are still applied.
+        let Some(synced) = ActivityCursor::find(&self.pool, project_id).await? else {
+            return Ok(count);
+        };
+        if synced.seq > synced.pruned_seq {
+            match self
+                .client
+                .list_deleted_issues(project_id, synced.pruned_seq)
+                .await
+            {
+                Ok(deleted) => {
+                    for tombstone in deleted.tombstones {
+                        ActivityIssue::delete(&self.pool, project_id, tombstone.issue_id).await?;
+                    }
+                }
+                // Tombstones past `pruned_seq` were purged; dropping the
+                // cache lets the next sync refetch only issues that exist.
+                Err(error) if error.is_gone() => {
+                    ActivityIssue::delete_by_project(&self.pool, project_id).await?;
+                }
+                Err(error) => return Err(error.into()),
+            }
+            ActivityCursor::mark_pruned(&self.pool, project_id, synced.seq).await?;
+        }
+
+        Ok(count)
+    }
+
+    /// Store events after `cursor.seq`, starting with `page`, checkpointing
+    /// the cursor with each page. Returns the number of events fetched.
+    async fn mirror_events(
+        &self,
+        project_id: Uuid,
+        cursor: ActivityCursor,
+        mut page: ListIssueActivityResponse,
+    ) -> Result<usize, ActivityProcessorError> {
+        let mut since_seq = cursor.seq;
+        if page.activity.is_empty() {
+            return Ok(0);
+        }
+
+        // Names as of now; good enough for a recent-activity feed.
+        let project = self.client.get_remote_project(project_id).await?;
+        let issues = self.issue_names(project_id).await?;
+        let statuses: HashMap<Uuid, String> = self
+            .client
+            .list_project_statuses(project_id)
+            .await?
+            .project_statuses
+            .into_iter()
+            .map(|status| (status.id, status.name))
+            .collect();
+        let context = EventContext {
+            project_id,
+            client: &self.client,
+            project: &project,
+            issues: &issues,
+            statuses: &statuses,
+        };
+
+        let mut count = 0;
+        loop {
+            // One transaction per page rather than per event keeps catch-up
+            // from queueing thousands of tiny writes on the SQLite writer, and
+            // the page's events land together with the checkpoint covering
+            // them.
+            let mut tx = self.pool.begin().await?;
+            let mut unhandled_seq = None;
+            for event in &page.activity {
+                if !self.handlers.dispatch(&mut *tx, &context, event).await? {
+                    unhandled_seq = unhandled_seq.or(Some(event.seq));
+                }
+            }
+            ActivityCursor::checkpoint(
+                &mut *tx,
+                project_id,
+                page.latest_seq,
+                cursor.pruned_seq,
+                unhandled_seq,
+            )
+            .await?;
+            tx.commit().await?;
+            count += page.activity.len();
+
+            if !page.has_more || page.latest_seq <= since_seq {
+                break;
+            }
+            since_seq = page.latest_seq;
+            page = self
+                .client
+                .list_issue_activity(project_id, since_seq, cursor.replay_id)
+                .await?;
+        }
+        self.record_pending_link(&project).await?;
+
+        Ok(count)
+    }
+
+    /// `(simple_id, title)` of every issue in the project. Only issues
+    /// updated since the newest cached one are fetched, so a sync after a
+    /// long gap costs what changed rather than the whole project.
+    async fn issue_names(
+        &self,
+        project_id: Uuid,
+    ) -> Result<HashMap<Uuid, (String, String)>, ActivityProcessorError> {
+        let mut after = ActivityIssue::latest_cursor(&self.pool, project_id).await?;
+        loop {
+            let page = self
+                .client
+                .list_issues_page(project_id, after, ISSUE_PAGE_SIZE)
+                .await?;
+            let page_len = page.issues.len();
+            if let Some(last) = page.issues.last() {
+                after = Some((last.updated_at, last.id));
+            }
+            for issue in page.issues {
+                ActivityIssue::upsert(
+                    &self.pool,
+                    &ActivityIssue {
+                        project_id,
+                        issue_id: issue.id,
+                        simple_id: issue.simple_id,
+                        title: issue.title,
+                        updated_at: issue.updated_at,
+                    },
+                )
+                .await?;
+            }
+            if page_len < ISSUE_PAGE_SIZE as usize {
+                break;
+            }
+        }
+
+        Ok(ActivityIssue::find_by_project(&self.pool, project_id)
+            .await?
+            .into_iter()
+            .map(|issue| (issue.issue_id, (issue.simple_id, issue.title)))
+            .collect())
+    }
+}
+
+/// Half the available cores, so catch-up leaves room for everything else.
+fn catch_up_concurrency() -> usize {
+    let cores = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    (cores / 2).clamp(1, MAX_CATCH_UP_CONCURRENCY)
+}
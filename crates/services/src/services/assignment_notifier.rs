@@ -0,0 +1,113 @@
+//! Desktop notifications for issues assigned to the signed-in user.
+//!
+//! Polls the remote notification inbox for `IssueAssigneeChanged` entries and
+//! raises each one once through [`NotificationService`], linking to the issue.
+//! Controlled by `Config::assignment_notifications_enabled`.
+
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use api_types::{Notification, NotificationType};
+use chrono::{DateTime, Utc};
+use tokio::{sync::RwLock, time::interval};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use super::{
+    config::Config,
+    notification::NotificationService,
+    remote_client::{RemoteClient, RemoteClientError},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+pub struct AssignmentNotifier {
+    client: RemoteClient,
+    notifications: NotificationService,
+    config: Arc<RwLock<Config>>,
+    /// Assignments made before startup are already in the inbox; skip them.
+    started_at: DateTime<Utc>,
+    notified: HashSet<Uuid>,
+}
+
+impl AssignmentNotifier {
+    pub fn spawn(
+        client: RemoteClient,
+        notifications: NotificationService,
+        config: Arc<RwLock<Config>>,
+        shutdown: CancellationToken,
+    ) -> tokio::task::JoinHandle<()> {
+        let mut notifier = Self {
+            client,
+            notifications,
+            config,
+            started_at: Utc::now(),
+            notified: HashSet::new(),
+        };
+        tokio::spawn(async move {
+            let mut interval = interval(POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => break,
+                    _ = interval.tick() => notifier.poll().await,
+                }
+            }
+        })
+    }
+
+    async fn poll(&mut self) {
+        if !self.config.read().await.assignment_notifications_enabled {
+            return;
+        }
+
+        let inbox = match self.client.list_notifications().await {
+            Ok(inbox) => inbox,
+            Err(RemoteClientError::Auth) => {
+                debug!("Assignment notifications skipped: not authenticated");
+                return;
+            }
+            Err(e) => {
+                warn!("Failed to fetch notifications: {}", e);
+                return;
+            }
+        };
+
+        // Forget entries that have left the inbox so the set stays bounded.
+        self.notified
+            .retain(|id| inbox.iter().any(|notification| notification.id == *id));
+
+        for notification in inbox {
+            if !self.is_new_assignment(&notification) {
+                continue;
+            }
+            self.notified.insert(notification.id);
+
+            let payload = &notification.payload;
+            let issue = match (&payload.issue_simple_id, &payload.issue_title) {
+                (Some(simple_id), Some(title)) => format!("{simple_id} {title}"),
+                (_, Some(title)) => title.clone(),
+                _ => "An issue".to_string(),
+            };
+            let message = format!("{issue} was assigned to you");
+            match &payload.deeplink_path {
+                Some(deeplink_path) => {
+                    self.notifications
+                        .notify_link("New assignment", &message, deeplink_path)
+                        .await
+                }
+                None => {
+                    self.notifications
+                        .notify("New assignment", &message, None)
+                        .await
+                }
+            }
+        }
+    }
+
+    fn is_new_assignment(&self, notification: &Notification) -> bool {
+        notification.notification_type == NotificationType::IssueAssigneeChanged
+            && !notification.seen
+            && notification.created_at > self.started_at
+            && !self.notified.contains(&notification.id)
+    }
+}
@@ -1,16 +1,33 @@
 use std::sync::Arc;
 
 use api_types::ProfileResponse;
-use tokio::sync::{Mutex as TokioMutex, OwnedMutexGuard, RwLock};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::{Mutex as TokioMutex, OwnedMutexGuard, RwLock, broadcast};
+use ts_rs::TS;
 
 use super::oauth_credentials::{Credentials, OAuthCredentials};
 
+/// Changes to the remote session, sent to subscribers of
+/// [`AuthContext::subscribe`] and as SSE `data` on `GET /auth/events`.
+#[derive(Debug, Clone, Serialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthEvent {
+    /// The access token lapses at `expires_at` and is about to be renewed.
+    ExpiringSoon { expires_at: DateTime<Utc> },
+    /// A new access token was issued; tokens fetched earlier are stale.
+    Refreshed { expires_at: DateTime<Utc> },
+    /// The remote ended the session, so the user has to sign in again.
+    SessionEnded,
+}
+
 #[derive(Clone)]
 pub struct AuthContext {
     oauth: Arc<OAuthCredentials>,
     profile: Arc<RwLock<Option<ProfileResponse>>>,
     remote_auth_degraded_slug: Arc<RwLock<Option<String>>>,
     refresh_lock: Arc<TokioMutex<()>>,
+    events: broadcast::Sender<AuthEvent>,
 }
 
 impl AuthContext {
@@ -23,6 +40,7 @@ impl AuthContext {
             profile,
             remote_auth_degraded_slug: Arc::new(RwLock::new(None)),
             refresh_lock: Arc::new(TokioMutex::new(())),
+            events: broadcast::channel(16).0,
         }
     }
 
@@ -65,4 +83,15 @@ impl AuthContext {
     pub async fn refresh_guard(&self) -> OwnedMutexGuard<()> {
         self.refresh_lock.clone().lock_owned().await
     }
+
+    /// Hook for holders of an access token, e.g. the frontend, to renew it
+    /// before it lapses and to learn when the session has ended.
+    pub fn subscribe(&self) -> broadcast::Receiver<AuthEvent> {
+        self.events.subscribe()
+    }
+
+    pub fn notify(&self, event: AuthEvent) {
+        // No subscribers is fine.
+        let _ = self.events.send(event);
+    }
 }
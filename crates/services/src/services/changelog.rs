@@ -0,0 +1,194 @@
+//! Release notes for a local project: the done tasks whose merges landed
+//! between two refs, grouped by the parent task they were split from.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+use db::models::merge::Merge;
+use git::GitService;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::task::JoinError;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ChangelogError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    TaskJoin(#[from] JoinError),
+    #[error("release refs not found in any of the project's repositories")]
+    RefNotFound,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct Changelog {
+    pub from_tag: Option<String>,
+    /// `None` runs the changelog up to each repository's `HEAD`.
+    pub to_tag: Option<String>,
+    pub sections: Vec<ChangelogSection>,
+    /// The changelog rendered as release notes.
+    pub markdown: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ChangelogSection {
+    /// Title of the parent task the entries were split from, `None` for
+    /// tasks without one.
+    pub epic: Option<String>,
+    pub entries: Vec<ChangelogEntry>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ChangelogEntry {
+    pub task_id: Uuid,
+    pub title: String,
+}
+
+/// Changelog of the project's done tasks with a merge reachable from
+/// `to_tag` but not from `from_tag`. Repositories missing either ref are
+/// skipped, so a project spanning several repositories only needs the tags
+/// in the ones that are released.
+pub async fn build_changelog(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    from_tag: Option<String>,
+    to_tag: Option<String>,
+) -> Result<Changelog, ChangelogError> {
+    let merges = Merge::find_completed_for_project(pool, project_id).await?;
+
+    let mut commits_by_repo: HashMap<String, Vec<String>> = HashMap::new();
+    for merge in &merges {
+        commits_by_repo
+            .entry(merge.repo_path.clone())
+            .or_default()
+            .push(merge.merge_commit.clone());
+    }
+
+    let (from, to) = (from_tag.clone(), to_tag.clone());
+    let released = tokio::task::spawn_blocking(move || {
+        let git = GitService::new();
+        let to = to.as_deref().unwrap_or("HEAD");
+        let mut released: Option<HashSet<String>> = None;
+        for (repo_path, commits) in commits_by_repo {
+            match git.commits_in_range(Path::new(&repo_path), from.as_deref(), to, &commits) {
+                Ok(Some(found)) => released.get_or_insert_default().extend(found),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::debug!("Skipping {} in changelog: {}", repo_path, e);
+                }
+            }
+        }
+        released
+    })
+    .await?;
+
+    let released = match released {
+        Some(released) => released,
+        None if merges.is_empty() => HashSet::new(),
+        None => return Err(ChangelogError::RefNotFound),
+    };
+
+    let mut sections: Vec<ChangelogSection> = Vec::new();
+    let mut seen = HashSet::new();
+    for merge in merges {
+        if !released.contains(&merge.merge_commit) || !seen.insert(merge.task_id) {
+            continue;
+        }
+        let entry = ChangelogEntry {
+            task_id: merge.task_id,
+            title: merge.task_title,
+        };
+        match sections
+            .iter_mut()
+            .find(|section| section.epic == merge.parent_task_title)
+        {
+            Some(section) => section.entries.push(entry),
+            None => sections.push(ChangelogSection {
+                epic: merge.parent_task_title,
+                entries: vec![entry],
+            }),
+        }
+    }
+    // Stable, so epics keep the order of their first task
+    sections.sort_by_key(|section| section.epic.is_none());
+
+    let markdown = render_markdown(to_tag.as_deref(), &sections);
+    Ok(Changelog {
+        from_tag,
+        to_tag,
+        sections,
+        markdown,
+    })
+}
+
+fn render_markdown(to_tag: Option<&str>, sections: &[ChangelogSection]) -> String {
+    let mut markdown = format!("## {}\n", to_tag.unwrap_or("Unreleased"));
+    if sections.is_empty() {
+        markdown.push_str("\nNo changes.\n");
+        return markdown;
+    }
+
+    let grouped = sections.iter().any(|section| section.epic.is_some());
+    for section in sections {
+        if grouped {
+            markdown.push_str(&format!(
+                "\n### {}\n",
+                section.epic.as_deref().unwrap_or("Other")
+            ));
+        }
+        markdown.push('\n');
+        for entry in &section.entries {
+            // Keep multi-line titles to one list item
+            let title = entry.title.split_whitespace().collect::<Vec<_>>().join(" ");
+            markdown.push_str(&format!("- {title}\n"));
+        }
+    }
+    markdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(epic: Option<&str>, titles: &[&str]) -> ChangelogSection {
+        ChangelogSection {
+            epic: epic.map(str::to_string),
+            entries: titles
+                .iter()
+                .map(|title| ChangelogEntry {
+                    task_id: Uuid::new_v4(),
+                    title: title.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_render_groups_by_epic() {
+        let sections = [
+            section(Some("Billing"), &["Add invoices", "Fix\ntax rounding"]),
+            section(None, &["Bump dependencies"]),
+        ];
+        assert_eq!(
+            render_markdown(Some("v1.2.0"), &sections),
+            "## v1.2.0\n\n### Billing\n\n- Add invoices\n- Fix tax rounding\n\n### Other\n\n- Bump dependencies\n"
+        );
+    }
+
+    #[test]
+    fn test_render_without_epics() {
+        assert_eq!(
+            render_markdown(None, &[section(None, &["Bump dependencies"])]),
+            "## Unreleased\n\n- Bump dependencies\n"
+        );
+        assert_eq!(
+            render_markdown(Some("v1.2.1"), &[]),
+            "## v1.2.1\n\nNo changes.\n"
+        );
+    }
+}
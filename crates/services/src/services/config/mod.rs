@@ -42,6 +42,11 @@ pub type GitHubConfig = versions::v8::GitHubConfig;
 pub type UiLanguage = versions::v8::UiLanguage;
 pub type ShowcaseState = versions::v8::ShowcaseState;
 pub type SendMessageShortcut = versions::v8::SendMessageShortcut;
+pub type LogRetentionConfig = versions::v8::LogRetentionConfig;
+pub type DoNotDisturbConfig = versions::v8::DoNotDisturbConfig;
+pub type DoNotDisturbWindow = versions::v8::DoNotDisturbWindow;
+pub type OrganizationRemote = versions::v8::OrganizationRemote;
+pub type WorktreePoolConfig = versions::v8::WorktreePoolConfig;
 
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
@@ -1,11 +1,17 @@
+use std::{collections::HashMap, time::Duration};
+
 use anyhow::Error;
+use chrono::NaiveTime;
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
+use git::CloneFilter;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use uuid::Uuid;
 pub use v7::{
     EditorConfig, EditorType, GitHubConfig, NotificationConfig, ShowcaseState, SoundFile,
     ThemeMode, UiLanguage,
 };
+use worktree_manager::WorktreePoolPolicy;
 
 use crate::services::config::versions::v7;
 
@@ -13,6 +19,10 @@ fn default_git_branch_prefix() -> String {
     "vk".to_string()
 }
 
+fn default_git_branch_template() -> String {
+    git::DEFAULT_BRANCH_TEMPLATE.to_string()
+}
+
 fn default_pr_auto_description_enabled() -> bool {
     true
 }
@@ -25,6 +35,10 @@ fn default_relay_enabled() -> bool {
     true
 }
 
+fn default_assignment_notifications_enabled() -> bool {
+    true
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
 pub enum SendMessageShortcut {
     #[default]
@@ -32,6 +46,76 @@ pub enum SendMessageShortcut {
     Enter,
 }
 
+/// Limits on stored execution logs. Unset limits are not enforced, so the
+/// default keeps everything.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct LogRetentionConfig {
+    /// Delete logs of processes that finished more than this many days ago.
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+    /// Keep at most this many megabytes of logs per project, oldest first out.
+    #[serde(default)]
+    pub max_project_mb: Option<u32>,
+}
+
+/// Worktrees kept after their workspace is deleted and reused for new
+/// workspaces on the same repository. Off by default.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct WorktreePoolConfig {
+    /// Idle worktrees kept per repository; 0 turns pooling off.
+    #[serde(default)]
+    pub max_per_repo: u32,
+    /// Remove worktrees that sat in the pool for more than this many days.
+    #[serde(default)]
+    pub max_idle_days: Option<u32>,
+    /// Keep the whole pool under this many megabytes, longest idle first out.
+    #[serde(default)]
+    pub max_total_mb: Option<u32>,
+}
+
+impl WorktreePoolConfig {
+    pub fn policy(&self) -> WorktreePoolPolicy {
+        WorktreePoolPolicy {
+            max_per_repo: self.max_per_repo as usize,
+            max_idle: self
+                .max_idle_days
+                .map(|days| Duration::from_secs(u64::from(days) * 24 * 3600)),
+            max_total_bytes: self.max_total_mb.map(|mb| u64::from(mb) * 1024 * 1024),
+        }
+    }
+}
+
+/// A daily quiet period in local time. Windows where `end` is before
+/// `start` run past midnight.
+#[derive(Clone, Debug, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct DoNotDisturbWindow {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+/// Notifications raised during a window are held back and delivered as one
+/// summary when it ends.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct DoNotDisturbConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub windows: Vec<DoNotDisturbWindow>,
+}
+
+/// Remote server hosting an organization that lives outside the default
+/// remote, e.g. in another region. Each host is signed in to separately, and
+/// its credentials are only ever sent back to it.
+#[derive(Clone, Debug, Serialize, Deserialize, TS, PartialEq, Eq)]
+pub struct OrganizationRemote {
+    /// API base URL, like `VK_SHARED_API_BASE`.
+    pub api_base: String,
+    /// Base URL for live sync (Electric shapes and the presence socket).
+    /// Defaults to `api_base`.
+    #[serde(default)]
+    pub sync_base: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -52,6 +136,10 @@ pub struct Config {
     pub language: UiLanguage,
     #[serde(default = "default_git_branch_prefix")]
     pub git_branch_prefix: String,
+    /// Name of workspace branches, built from `{prefix}`, `{id}`,
+    /// `{task_key}`, `{slug}` and `{username}`.
+    #[serde(default = "default_git_branch_template")]
+    pub git_branch_template: String,
     #[serde(default)]
     pub showcases: ShowcaseState,
     #[serde(default = "default_pr_auto_description_enabled")]
@@ -68,6 +156,24 @@ pub struct Config {
     pub relay_enabled: bool,
     #[serde(default)]
     pub host_nickname: Option<String>,
+    #[serde(default)]
+    pub log_retention: LogRetentionConfig,
+    /// Remote project created by onboarding, so it can be torn down later.
+    #[serde(default)]
+    pub sample_project_id: Option<Uuid>,
+    #[serde(default)]
+    pub do_not_disturb: DoNotDisturbConfig,
+    /// Desktop notification when an issue is assigned to you.
+    #[serde(default = "default_assignment_notifications_enabled")]
+    pub assignment_notifications_enabled: bool,
+    /// Organizations served by a remote other than the default one.
+    #[serde(default)]
+    pub organization_remotes: HashMap<Uuid, OrganizationRemote>,
+    #[serde(default)]
+    pub worktree_pool: WorktreePoolConfig,
+    /// Partial clone used when cloning a repository to link a project.
+    #[serde(default)]
+    pub clone_filter: CloneFilter,
 }
 
 impl Config {
@@ -91,6 +197,7 @@ impl Config {
             show_release_notes: old_config.show_release_notes,
             language: old_config.language,
             git_branch_prefix: old_config.git_branch_prefix,
+            git_branch_template: default_git_branch_template(),
             showcases: old_config.showcases,
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
@@ -99,6 +206,13 @@ impl Config {
             send_message_shortcut: SendMessageShortcut::default(),
             relay_enabled: true,
             host_nickname: None,
+            log_retention: LogRetentionConfig::default(),
+            sample_project_id: None,
+            do_not_disturb: DoNotDisturbConfig::default(),
+            assignment_notifications_enabled: true,
+            organization_remotes: HashMap::new(),
+            worktree_pool: WorktreePoolConfig::default(),
+            clone_filter: CloneFilter::default(),
         }
     }
 
@@ -147,6 +261,7 @@ impl Default for Config {
             show_release_notes: false,
             language: UiLanguage::default(),
             git_branch_prefix: default_git_branch_prefix(),
+            git_branch_template: default_git_branch_template(),
             showcases: ShowcaseState::default(),
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
@@ -155,6 +270,13 @@ impl Default for Config {
             send_message_shortcut: SendMessageShortcut::default(),
             relay_enabled: true,
             host_nickname: None,
+            log_retention: LogRetentionConfig::default(),
+            sample_project_id: None,
+            do_not_disturb: DoNotDisturbConfig::default(),
+            assignment_notifications_enabled: true,
+            organization_remotes: HashMap::new(),
+            worktree_pool: WorktreePoolConfig::default(),
+            clone_filter: CloneFilter::default(),
         }
     }
 }
@@ -44,7 +44,7 @@ use executors::{
     profile::{ExecutorConfig, ExecutorProfileId},
 };
 use futures::{StreamExt, future, stream::BoxStream};
-use git::{GitService, GitServiceError};
+use git::{BranchNameVars, GitService, GitServiceError, is_valid_branch_name, render_branch_name};
 use json_patch::Patch;
 use sqlx::Error as SqlxError;
 use thiserror::Error;
@@ -783,14 +783,37 @@ pub trait ContainerService {
 
     async fn git_branch_prefix(&self) -> String;
 
-    async fn git_branch_from_workspace(&self, workspace_id: &Uuid, task_title: &str) -> String {
-        let task_title_id = git_branch_id(task_title);
-        let prefix = self.git_branch_prefix().await;
+    async fn git_branch_template(&self) -> String;
 
-        if prefix.is_empty() {
-            format!("{}-{}", short_uuid(workspace_id), task_title_id)
+    /// Branch for a new workspace, named by the configured template. Falls
+    /// back to `<id>-<slug>` if the template renders an invalid name.
+    async fn git_branch_from_workspace(
+        &self,
+        workspace_id: &Uuid,
+        task_title: &str,
+        task_key: Option<&str>,
+        username: Option<&str>,
+    ) -> String {
+        let id = short_uuid(workspace_id);
+        let slug = git_branch_id(task_title);
+        let prefix = self.git_branch_prefix().await;
+        let template = self.git_branch_template().await;
+
+        let branch = render_branch_name(
+            &template,
+            &BranchNameVars {
+                prefix: &prefix,
+                id: &id,
+                task_key,
+                slug: &slug,
+                username,
+            },
+        );
+        if is_valid_branch_name(&branch) {
+            branch
         } else {
-            format!("{}/{}-{}", prefix, short_uuid(workspace_id), task_title_id)
+            tracing::warn!(%template, %branch, "branch template rendered an invalid name");
+            format!("{id}-{slug}")
         }
     }
 
@@ -1,8 +1,8 @@
 use db::models::{execution_process::ExecutionProcess, scratch::Scratch, workspace::Workspace};
 use futures::StreamExt;
 use serde_json::json;
-use tokio_stream::wrappers::BroadcastStream;
-use utils::log_msg::LogMsg;
+use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
+use utils::{log_msg::LogMsg, ws::BacklogDropped};
 use uuid::Uuid;
 
 use super::{
@@ -132,7 +132,9 @@ impl EventService {
                             None
                         }
                         Ok(other) => Some(Ok(other)), // Pass through non-patch messages
-                        Err(_) => None,               // Filter out broadcast errors
+                        Err(BroadcastStreamRecvError::Lagged(n)) => {
+                            Some(Err(BacklogDropped(n).into()))
+                        }
                     }
                 }
             });
@@ -214,7 +216,9 @@ impl EventService {
                             None
                         }
                         Ok(other) => Some(Ok(other)),
-                        Err(_) => None,
+                        Err(BroadcastStreamRecvError::Lagged(n)) => {
+                            Some(Err(BacklogDropped(n).into()))
+                        }
                     }
                 }
             });
@@ -306,7 +310,7 @@ impl EventService {
                         None
                     }
                     Ok(other) => Some(Ok(other)),
-                    Err(_) => None,
+                    Err(BroadcastStreamRecvError::Lagged(n)) => Some(Err(BacklogDropped(n).into())),
                 }
             },
         );
@@ -0,0 +1,202 @@
+//! Imports a GitHub repository's open issues into a remote project.
+
+use std::{collections::HashSet, path::Path};
+
+use api_types::{BatchMutation, BatchMutationsRequest, CreateIssueRequest, ProjectStatus};
+use git_host::{GitHostError, GitHostService, GitHubIssue};
+use serde::Serialize;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::remote_client::{RemoteClient, RemoteClientError};
+
+/// Issues created per remote batch; the remote accepts at most 50.
+const IMPORT_BATCH_SIZE: usize = 50;
+
+/// Key in an issue's `extension_metadata` recording the GitHub issue it was
+/// imported from (repository, number and URL), used to skip issues that were
+/// already imported.
+const GITHUB_ISSUE_KEY: &str = "github_issue";
+
+#[derive(Debug, Error)]
+pub enum GitHubImportError {
+    #[error(transparent)]
+    GitHost(#[from] GitHostError),
+    #[error(transparent)]
+    Remote(#[from] RemoteClientError),
+    #[error("repository is not hosted on GitHub")]
+    NotGitHub,
+    #[error("remote project has no statuses")]
+    NoStatuses,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct GitHubImportSummary {
+    pub imported: usize,
+    /// Open GitHub issues already imported into the project earlier.
+    pub skipped: usize,
+}
+
+/// Create an issue in `remote_project_id` for every open GitHub issue of the
+/// repository at `remote_url` that hasn't been imported yet. An issue lands
+/// in the first status named like one of its labels (`in-progress`,
+/// `status: done`, ...), otherwise in the project's first visible status.
+pub async fn import_github_issues(
+    client: &RemoteClient,
+    repo_path: &Path,
+    remote_url: &str,
+    remote_project_id: Uuid,
+) -> Result<GitHubImportSummary, GitHubImportError> {
+    let GitHostService::GitHub(github) = GitHostService::from_url(remote_url)? else {
+        return Err(GitHubImportError::NotGitHub);
+    };
+    let github_issues = github.list_open_issues(repo_path, remote_url).await?;
+
+    let existing = client.list_issues(remote_project_id).await?.issues;
+    let imported: HashSet<(String, i64)> = existing
+        .iter()
+        .filter_map(|issue| {
+            let source = &issue.extension_metadata[GITHUB_ISSUE_KEY];
+            // Imports predating the `repo` field only recorded the URL.
+            let repo = source["repo"]
+                .as_str()
+                .map(str::to_owned)
+                .or_else(|| repo_of_issue_url(source["url"].as_str()?))?;
+            Some((repo.to_lowercase(), source["number"].as_i64()?))
+        })
+        .collect();
+    let mut next_sort_order = existing
+        .iter()
+        .map(|issue| issue.sort_order)
+        .fold(0.0, f64::max);
+
+    let (skipped, pending): (Vec<GitHubIssue>, Vec<GitHubIssue>) =
+        github_issues.into_iter().partition(|issue| {
+            repo_of_issue_url(&issue.url)
+                .is_some_and(|repo| imported.contains(&(repo.to_lowercase(), issue.number)))
+        });
+    if pending.is_empty() {
+        return Ok(GitHubImportSummary {
+            imported: 0,
+            skipped: skipped.len(),
+        });
+    }
+
+    let statuses = client
+        .list_project_statuses(remote_project_id)
+        .await?
+        .project_statuses;
+    let fallback = statuses
+        .iter()
+        .filter(|status| !status.hidden)
+        .min_by_key(|status| status.sort_order)
+        .or_else(|| statuses.first())
+        .ok_or(GitHubImportError::NoStatuses)?;
+
+    for batch in pending.chunks(IMPORT_BATCH_SIZE) {
+        let mutations = batch
+            .iter()
+            .map(|issue| {
+                next_sort_order += 1.0;
+                let status = status_for_labels(&issue.labels, &statuses).unwrap_or(fallback);
+                BatchMutation::CreateIssue(CreateIssueRequest {
+                    id: None,
+                    project_id: remote_project_id,
+                    status_id: status.id,
+                    title: issue.title.clone(),
+                    description: (!issue.body.trim().is_empty()).then(|| issue.body.clone()),
+                    priority: None,
+                    start_date: None,
+                    target_date: None,
+                    completed_at: None,
+                    sort_order: next_sort_order,
+                    parent_issue_id: None,
+                    parent_issue_sort_order: None,
+                    extension_metadata: serde_json::json!({
+                        GITHUB_ISSUE_KEY: {
+                            "repo": repo_of_issue_url(&issue.url),
+                            "number": issue.number,
+                            "url": issue.url,
+                        },
+                    }),
+                })
+            })
+            .collect();
+
+        client
+            .apply_mutation_batch(&BatchMutationsRequest { mutations })
+            .await?;
+    }
+
+    Ok(GitHubImportSummary {
+        imported: pending.len(),
+        skipped: skipped.len(),
+    })
+}
+
+/// `owner/name` of the repository an issue URL
+/// (`https://github.com/owner/name/issues/12`) points into. Issue numbers are
+/// only unique within a repository, so imports are keyed on both.
+fn repo_of_issue_url(url: &str) -> Option<String> {
+    let path = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let (repo, _) = path.split_once("/issues/")?;
+    let (_host, repo) = repo.split_once('/')?;
+    Some(repo.to_string())
+}
+
+fn status_for_labels<'a>(
+    labels: &[String],
+    statuses: &'a [ProjectStatus],
+) -> Option<&'a ProjectStatus> {
+    labels.iter().find_map(|label| {
+        statuses
+            .iter()
+            .find(|status| label_matches_status(label, &status.name))
+    })
+}
+
+/// Labels match status names ignoring case, a `status:` prefix and the
+/// separator used between words.
+fn label_matches_status(label: &str, status_name: &str) -> bool {
+    let normalize = |name: &str| {
+        name.split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let label = label.trim();
+    let label = label
+        .get(..7)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("status:"))
+        .map_or(label, |_| &label[7..]);
+    normalize(label) == normalize(status_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{label_matches_status, repo_of_issue_url};
+
+    #[test]
+    fn labels_match_status_names_loosely() {
+        assert!(label_matches_status("in-progress", "In Progress"));
+        assert!(label_matches_status("Status: In_Review", "In review"));
+        assert!(label_matches_status("todo", "Todo"));
+        assert!(!label_matches_status("bug", "Backlog"));
+        assert!(!label_matches_status("in progress soon", "In progress"));
+    }
+
+    #[test]
+    fn issue_urls_name_their_repository() {
+        assert_eq!(
+            repo_of_issue_url("https://github.com/acme/widgets/issues/12").as_deref(),
+            Some("acme/widgets")
+        );
+        assert_eq!(
+            repo_of_issue_url("https://ghe.example.com/acme/widgets/issues/3").as_deref(),
+            Some("acme/widgets")
+        );
+        assert_eq!(repo_of_issue_url("https://github.com/acme/widgets"), None);
+    }
+}
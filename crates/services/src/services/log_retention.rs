@@ -0,0 +1,180 @@
+//! Pruning of execution log files according to [`LogRetentionConfig`].
+//!
+//! Logs live on disk under `sessions/` (one JSONL file per process), so
+//! pruning deletes files; the process rows stay for history. Only finished
+//! processes are considered.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use db::models::execution_process::{ExecutionLogRef, ExecutionProcess};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
+use utils::execution_logs::process_log_file_path;
+use uuid::Uuid;
+
+use super::config::LogRetentionConfig;
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct ProjectLogPrune {
+    /// `None` for workspaces not linked to a task.
+    pub project_id: Option<Uuid>,
+    pub process_count: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct LogPruneReport {
+    pub dry_run: bool,
+    pub process_count: usize,
+    pub bytes: u64,
+    pub projects: Vec<ProjectLogPrune>,
+}
+
+struct SizedLog {
+    log: ExecutionLogRef,
+    bytes: u64,
+}
+
+/// Report what `config` would delete, and delete it unless `dry_run`.
+pub async fn prune_logs(
+    pool: &SqlitePool,
+    config: &LogRetentionConfig,
+    dry_run: bool,
+) -> Result<LogPruneReport, sqlx::Error> {
+    if config.max_age_days.is_none() && config.max_project_mb.is_none() {
+        return Ok(LogPruneReport {
+            dry_run,
+            ..Default::default()
+        });
+    }
+
+    let mut logs = Vec::new();
+    for log in ExecutionProcess::find_finished_log_refs(pool).await? {
+        let path = process_log_file_path(log.session_id, log.execution_id);
+        // Already pruned or never written.
+        let Ok(metadata) = tokio::fs::metadata(&path).await else {
+            continue;
+        };
+        logs.push(SizedLog {
+            log,
+            bytes: metadata.len(),
+        });
+    }
+
+    let selected = select_for_pruning(&logs, config, Utc::now());
+
+    let mut by_project: HashMap<Option<Uuid>, ProjectLogPrune> = HashMap::new();
+    let mut report = LogPruneReport {
+        dry_run,
+        ..Default::default()
+    };
+    for index in selected {
+        let SizedLog { log, bytes } = &logs[index];
+        if !dry_run {
+            let path = process_log_file_path(log.session_id, log.execution_id);
+            if let Err(e) = tokio::fs::remove_file(&path).await {
+                tracing::warn!("Failed to prune log file {}: {}", path.display(), e);
+                continue;
+            }
+        }
+        let project = by_project
+            .entry(log.project_id)
+            .or_insert_with(|| ProjectLogPrune {
+                project_id: log.project_id,
+                ..Default::default()
+            });
+        project.process_count += 1;
+        project.bytes += bytes;
+        report.process_count += 1;
+        report.bytes += bytes;
+    }
+
+    report.projects = by_project.into_values().collect();
+    report.projects.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+    Ok(report)
+}
+
+/// Indexes into `logs` (newest first) that fall outside the retention policy:
+/// older than `max_age_days`, or beyond the newest `max_project_mb` of their
+/// project.
+fn select_for_pruning(
+    logs: &[SizedLog],
+    config: &LogRetentionConfig,
+    now: DateTime<Utc>,
+) -> Vec<usize> {
+    let cutoff: Option<DateTime<Utc>> = config
+        .max_age_days
+        .map(|days| now - Duration::days(i64::from(days)));
+    let budget = config.max_project_mb.map(|mb| u64::from(mb) * 1024 * 1024);
+
+    let mut kept_bytes: HashMap<Option<Uuid>, u64> = HashMap::new();
+    logs.iter()
+        .enumerate()
+        .filter_map(|(index, SizedLog { log, bytes })| {
+            if cutoff.is_some_and(|cutoff| log.completed_at < cutoff) {
+                return Some(index);
+            }
+            let kept = kept_bytes.entry(log.project_id).or_default();
+            if budget.is_some_and(|budget| *kept + bytes > budget) {
+                return Some(index);
+            }
+            *kept += bytes;
+            None
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use db::models::execution_process::ExecutionLogRef;
+    use uuid::Uuid;
+
+    use super::{LogRetentionConfig, SizedLog, select_for_pruning};
+
+    fn log(project_id: Option<Uuid>, age_days: i64, bytes: u64) -> SizedLog {
+        SizedLog {
+            log: ExecutionLogRef {
+                execution_id: Uuid::new_v4(),
+                session_id: Uuid::new_v4(),
+                project_id,
+                completed_at: Utc::now() - Duration::days(age_days),
+            },
+            bytes,
+        }
+    }
+
+    #[test]
+    fn prunes_logs_older_than_max_age() {
+        let logs = vec![log(None, 1, 10), log(None, 40, 10)];
+        let config = LogRetentionConfig {
+            max_age_days: Some(30),
+            max_project_mb: None,
+        };
+
+        assert_eq!(select_for_pruning(&logs, &config, Utc::now()), vec![1]);
+    }
+
+    #[test]
+    fn keeps_newest_logs_within_each_project_budget() {
+        let mb = 1024 * 1024;
+        let (a, b) = (Some(Uuid::new_v4()), Some(Uuid::new_v4()));
+        let logs = vec![
+            log(a, 1, mb),
+            log(b, 1, 2 * mb),
+            log(a, 2, mb),
+            log(a, 3, 1),
+        ];
+        let config = LogRetentionConfig {
+            max_age_days: None,
+            max_project_mb: Some(1),
+        };
+
+        assert_eq!(
+            select_for_pruning(&logs, &config, Utc::now()),
+            vec![1, 2, 3]
+        );
+    }
+}
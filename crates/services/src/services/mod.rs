@@ -1,6 +1,9 @@
+pub mod activity;
 pub mod analytics;
 pub mod approvals;
+pub mod assignment_notifier;
 pub mod auth;
+pub mod changelog;
 pub mod config;
 pub mod container;
 pub mod diff_stream;
@@ -11,8 +14,12 @@ pub mod file_ranker;
 pub mod file_search;
 pub mod filesystem;
 pub mod filesystem_watcher;
+pub mod github_import;
+pub mod log_retention;
+pub mod network_watcher;
 pub mod notification;
 pub mod oauth_credentials;
+pub mod organization_remotes;
 pub mod pr_monitor;
 
 #[cfg(feature = "qa-mode")]
@@ -21,3 +28,4 @@ pub mod queued_message;
 pub mod remote_client;
 pub mod remote_sync;
 pub mod repo;
+pub mod task_share;
@@ -0,0 +1,96 @@
+//! Notices when the machine's network route changes, e.g. switching between
+//! Wi-Fi and a VPN.
+//!
+//! Connections opened on the old route usually don't fail outright; they hang
+//! until a keepalive or request timeout gives up. Consumers subscribe here to
+//! drop them as soon as the route moves.
+
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket},
+    time::Duration,
+};
+
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Public addresses used only to ask the OS which local address it would
+/// route from. Connecting a UDP socket sends no packets.
+const PROBE_V4: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(1, 1, 1, 1)), 53);
+const PROBE_V6: SocketAddr = SocketAddr::new(
+    IpAddr::V6(Ipv6Addr::new(0x2606, 0x4700, 0x4700, 0, 0, 0, 0, 0x1111)),
+    53,
+);
+
+/// Local source addresses of the default IPv4 and IPv6 routes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NetworkRoute {
+    pub v4: Option<IpAddr>,
+    pub v6: Option<IpAddr>,
+}
+
+impl NetworkRoute {
+    fn current() -> Self {
+        Self {
+            v4: route_source("0.0.0.0:0", PROBE_V4),
+            v6: route_source("[::]:0", PROBE_V6),
+        }
+    }
+
+    fn is_offline(&self) -> bool {
+        self.v4.is_none() && self.v6.is_none()
+    }
+}
+
+fn route_source(bind: &str, probe: SocketAddr) -> Option<IpAddr> {
+    let socket = UdpSocket::bind(bind).ok()?;
+    socket.connect(probe).ok()?;
+    Some(socket.local_addr().ok()?.ip())
+}
+
+/// Receives the latest route whenever it changes.
+pub type NetworkChanges = watch::Receiver<NetworkRoute>;
+
+#[derive(Clone)]
+pub struct NetworkWatcher {
+    route: watch::Sender<NetworkRoute>,
+}
+
+impl NetworkWatcher {
+    /// Start polling the default route until `shutdown` is cancelled.
+    pub fn spawn(shutdown: CancellationToken) -> Self {
+        let (route, _) = watch::channel(NetworkRoute::current());
+        let sender = route.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                }
+
+                let observed = NetworkRoute::current();
+                // Going offline alone isn't worth reconnecting over: the
+                // route either comes back unchanged or as a new one.
+                if observed.is_offline() {
+                    debug!("no network route; waiting for one to come back");
+                    continue;
+                }
+                sender.send_if_modified(|published| {
+                    if *published == observed {
+                        return false;
+                    }
+                    info!(from = ?published, to = ?observed, "network route changed");
+                    *published = observed;
+                    true
+                });
+            }
+        });
+        Self { route }
+    }
+
+    pub fn subscribe(&self) -> NetworkChanges {
+        self.route.subscribe()
+    }
+}
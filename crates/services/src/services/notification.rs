@@ -1,17 +1,28 @@
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, Mutex, OnceLock};
 
 use async_trait::async_trait;
+use chrono::{Days, Local, NaiveDateTime};
 use tokio::sync::RwLock;
 use utils::{self, command_ext::NoWindowExt};
 use uuid::Uuid;
 
-use crate::services::config::{Config, SoundFile};
+use crate::services::config::{Config, DoNotDisturbConfig, NotificationConfig, SoundFile};
+
+/// Number of held notification titles listed in a Do Not Disturb summary.
+const DND_SUMMARY_TITLES: usize = 3;
 
 /// Trait for sending push notifications. Implementations can use
 /// platform-specific OS commands, Tauri's notification plugin, etc.
 #[async_trait]
 pub trait PushNotifier: Send + Sync + 'static {
     async fn send(&self, title: &str, message: &str, workspace_id: Option<Uuid>);
+
+    /// Like [`send`](Self::send), but clicking opens `deeplink_path` (e.g.
+    /// `/projects/{id}/issues/{id}`). Notifiers without click handling can
+    /// keep the default.
+    async fn send_link(&self, title: &str, message: &str, _deeplink_path: &str) {
+        self.send(title, message, None).await;
+    }
 }
 
 /// Global push notifier set before server startup (e.g., by the Tauri app).
@@ -53,11 +64,27 @@ impl PushNotifier for DefaultPushNotifier {
     }
 }
 
+#[derive(Debug, Clone)]
+struct HeldNotification {
+    title: String,
+    message: String,
+    workspace_id: Option<Uuid>,
+    deeplink_path: Option<String>,
+}
+
+/// Notifications held back during Do Not Disturb.
+#[derive(Debug, Default)]
+struct DndQueue {
+    held: Vec<HeldNotification>,
+    flush_scheduled: bool,
+}
+
 /// Service for handling cross-platform notifications including sound alerts and push notifications
 #[derive(Clone)]
 pub struct NotificationService {
     config: Arc<RwLock<Config>>,
     push_notifier: Arc<dyn PushNotifier>,
+    dnd_queue: Arc<Mutex<DndQueue>>,
 }
 
 impl std::fmt::Debug for NotificationService {
@@ -73,21 +100,122 @@ impl NotificationService {
         Self {
             config,
             push_notifier: get_global_push_notifier(),
+            dnd_queue: Arc::default(),
         }
     }
 
     /// Send both sound and push notifications if enabled.
     /// `workspace_id` is forwarded to the push notifier so Tauri can emit a
     /// navigation event when the notification is clicked.
+    ///
+    /// During a Do Not Disturb window the notification is held and delivered
+    /// with any others as a single summary when the window ends.
     pub async fn notify(&self, title: &str, message: &str, workspace_id: Option<Uuid>) {
-        let config = self.config.read().await.notifications.clone();
+        self.dispatch(HeldNotification {
+            title: title.to_string(),
+            message: message.to_string(),
+            workspace_id,
+            deeplink_path: None,
+        })
+        .await;
+    }
 
+    /// Like [`notify`](Self::notify), but clicking the push notification
+    /// opens `deeplink_path` in the app.
+    pub async fn notify_link(&self, title: &str, message: &str, deeplink_path: &str) {
+        self.dispatch(HeldNotification {
+            title: title.to_string(),
+            message: message.to_string(),
+            workspace_id: None,
+            deeplink_path: Some(deeplink_path.to_string()),
+        })
+        .await;
+    }
+
+    async fn dispatch(&self, notification: HeldNotification) {
+        let (config, dnd) = {
+            let config = self.config.read().await;
+            (config.notifications.clone(), config.do_not_disturb.clone())
+        };
+
+        if let Some(window_end) = dnd_window_end(&dnd, Local::now().naive_local()) {
+            self.hold(notification, window_end);
+            return;
+        }
+
+        self.deliver(&config, &notification).await;
+    }
+
+    async fn deliver(&self, config: &NotificationConfig, notification: &HeldNotification) {
         if config.sound_enabled {
             Self::play_sound_notification(&config.sound_file).await;
         }
 
         if config.push_enabled {
-            self.push_notifier.send(title, message, workspace_id).await;
+            match &notification.deeplink_path {
+                Some(deeplink_path) => {
+                    self.push_notifier
+                        .send_link(&notification.title, &notification.message, deeplink_path)
+                        .await
+                }
+                None => {
+                    self.push_notifier
+                        .send(
+                            &notification.title,
+                            &notification.message,
+                            notification.workspace_id,
+                        )
+                        .await
+                }
+            }
+        }
+    }
+
+    fn hold(&self, notification: HeldNotification, window_end: NaiveDateTime) {
+        let Ok(mut queue) = self.dnd_queue.lock() else {
+            return;
+        };
+        queue.held.push(notification);
+        if !queue.flush_scheduled {
+            queue.flush_scheduled = true;
+            self.schedule_flush(window_end);
+        }
+    }
+
+    fn schedule_flush(&self, window_end: NaiveDateTime) {
+        let service = self.clone();
+        let delay = (window_end - Local::now().naive_local())
+            .to_std()
+            .unwrap_or_default();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            service.flush_held().await;
+        });
+    }
+
+    /// Deliver held notifications as one summary, or wait for the next
+    /// window end if Do Not Disturb is still on (e.g. back-to-back windows).
+    async fn flush_held(&self) {
+        let (config, dnd) = {
+            let config = self.config.read().await;
+            (config.notifications.clone(), config.do_not_disturb.clone())
+        };
+
+        if let Some(window_end) = dnd_window_end(&dnd, Local::now().naive_local()) {
+            self.schedule_flush(window_end);
+            return;
+        }
+
+        let held = {
+            let Ok(mut queue) = self.dnd_queue.lock() else {
+                return;
+            };
+            queue.flush_scheduled = false;
+            std::mem::take(&mut queue.held)
+        };
+
+        if let Some(summary) = summarize_held(&held) {
+            self.deliver(&config, &summary).await;
         }
     }
 
@@ -151,6 +279,65 @@ impl NotificationService {
     }
 }
 
+/// End of the Do Not Disturb window containing `now`, if any. Overlapping
+/// windows resolve to the latest end.
+fn dnd_window_end(config: &DoNotDisturbConfig, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    if !config.enabled {
+        return None;
+    }
+
+    let (date, time) = (now.date(), now.time());
+    config
+        .windows
+        .iter()
+        .filter_map(|window| {
+            if window.start < window.end {
+                (window.start <= time && time < window.end).then(|| date.and_time(window.end))
+            } else if window.start > window.end {
+                // Runs past midnight.
+                if time >= window.start {
+                    Some((date + Days::new(1)).and_time(window.end))
+                } else {
+                    (time < window.end).then(|| date.and_time(window.end))
+                }
+            } else {
+                None
+            }
+        })
+        .max()
+}
+
+/// A single held notification is delivered as-is; several are collapsed into
+/// a count and the first few titles.
+fn summarize_held(held: &[HeldNotification]) -> Option<HeldNotification> {
+    match held {
+        [] => None,
+        [single] => Some(single.clone()),
+        _ => {
+            let mut lines: Vec<String> = held
+                .iter()
+                .take(DND_SUMMARY_TITLES)
+                .map(|notification| notification.title.clone())
+                .collect();
+            if held.len() > DND_SUMMARY_TITLES {
+                lines.push(format!("and {} more", held.len() - DND_SUMMARY_TITLES));
+            }
+            // Keep click-through only when every notification points at the
+            // same place.
+            let same_target = held.iter().all(|notification| {
+                notification.workspace_id == held[0].workspace_id
+                    && notification.deeplink_path == held[0].deeplink_path
+            });
+            Some(HeldNotification {
+                title: format!("{} notifications during Do Not Disturb", held.len()),
+                message: lines.join("\n"),
+                workspace_id: held[0].workspace_id.filter(|_| same_target),
+                deeplink_path: held[0].deeplink_path.clone().filter(|_| same_target),
+            })
+        }
+    }
+}
+
 // --- Platform-specific push notification helpers (used by DefaultPushNotifier) ---
 
 /// Send macOS notification using osascript
@@ -295,3 +482,43 @@ async fn wsl_to_windows_path(wsl_path: &std::path::Path) -> Option<String> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    use super::dnd_window_end;
+    use crate::services::config::{DoNotDisturbConfig, DoNotDisturbWindow};
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 1, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn overnight_window_ends_the_next_morning() {
+        let config = DoNotDisturbConfig {
+            enabled: true,
+            windows: vec![DoNotDisturbWindow {
+                start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+            }],
+        };
+
+        assert_eq!(dnd_window_end(&config, at(5, 23, 30)), Some(at(6, 8, 0)));
+        assert_eq!(dnd_window_end(&config, at(6, 7, 59)), Some(at(6, 8, 0)));
+        assert_eq!(dnd_window_end(&config, at(6, 8, 0)), None);
+        assert_eq!(
+            dnd_window_end(
+                &DoNotDisturbConfig {
+                    enabled: false,
+                    ..config
+                },
+                at(5, 23, 30)
+            ),
+            None
+        );
+    }
+}
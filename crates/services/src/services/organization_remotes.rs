@@ -0,0 +1,88 @@
+//! Clients for organizations hosted on a remote other than the default one,
+//! e.g. in another region. Each host is signed in to separately, so the
+//! credentials one remote issued are never sent to another.
+
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::{Mutex, RwLock};
+use tokio_util::sync::CancellationToken;
+use url::Url;
+use utils::assets::remote_credentials_path;
+
+use super::{
+    auth::AuthContext,
+    oauth_credentials::OAuthCredentials,
+    remote_client::{RemoteClient, RemoteClientError},
+};
+
+#[derive(Clone)]
+pub struct OrganizationRemoteClients {
+    /// Host of the default remote, whose credentials `default_auth` holds.
+    default_host: Option<String>,
+    default_auth: AuthContext,
+    inner: Arc<Mutex<Inner>>,
+    shutdown: CancellationToken,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Sign-in per host, shared by every API base on it.
+    auth: HashMap<String, AuthContext>,
+    clients: HashMap<String, RemoteClient>,
+}
+
+impl OrganizationRemoteClients {
+    pub fn new(
+        default_api_base: Option<&str>,
+        default_auth: AuthContext,
+        shutdown: CancellationToken,
+    ) -> Self {
+        Self {
+            default_host: default_api_base.and_then(|base| host_key(base).ok()),
+            default_auth,
+            inner: Arc::new(Mutex::new(Inner::default())),
+            shutdown,
+        }
+    }
+
+    /// Client for `api_base`, signed in with what its host issued. A client
+    /// is created, and its credentials loaded, on first use.
+    pub async fn client(&self, api_base: &str) -> Result<RemoteClient, RemoteClientError> {
+        let mut inner = self.inner.lock().await;
+        if let Some(client) = inner.clients.get(api_base) {
+            return Ok(client.clone());
+        }
+
+        let host = host_key(api_base)?;
+        let auth = if self.default_host.as_deref() == Some(host.as_str()) {
+            self.default_auth.clone()
+        } else if let Some(auth) = inner.auth.get(&host) {
+            auth.clone()
+        } else {
+            let oauth = Arc::new(OAuthCredentials::new(remote_credentials_path(&host)));
+            if let Err(e) = oauth.load().await {
+                tracing::warn!(?e, %host, "failed to load remote credentials");
+            }
+            let auth = AuthContext::new(oauth, Arc::new(RwLock::new(None)));
+            inner.auth.insert(host, auth.clone());
+            auth
+        };
+
+        let client = RemoteClient::new(api_base, auth)?;
+        client.spawn_token_refresher(self.shutdown.child_token());
+        inner.clients.insert(api_base.to_string(), client.clone());
+        Ok(client)
+    }
+}
+
+/// `host[:port]` of `api_base`, which scopes the credentials sent to it.
+fn host_key(api_base: &str) -> Result<String, RemoteClientError> {
+    let url = Url::parse(api_base).map_err(|e| RemoteClientError::Url(e.to_string()))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| RemoteClientError::Url(format!("{api_base} has no host")))?;
+    Ok(match url.port_or_known_default() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    })
+}
@@ -1,15 +1,16 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, path::Path, sync::Arc, time::Duration};
 
 use api_types::{PullRequestStatus, UpdatePullRequestApiRequest, UpsertPullRequestRequest};
 use chrono::Utc;
 use db::{
     DBService,
     models::{
-        merge::MergeStatus,
+        merge::{Merge, MergeStatus, UnreleasedMerge},
         pull_request::PullRequest,
         workspace::{Workspace, WorkspaceError},
     },
 };
+use git::GitService;
 use git_host::{GitHostError, GitHostProvider, GitHostService};
 use serde_json::json;
 use sqlx::error::Error as SqlxError;
@@ -45,6 +46,13 @@ impl PrMonitorError {
     }
 }
 
+/// How far back merges are checked for a release tag.
+const RELEASE_TAG_WINDOW_DAYS: i64 = 90;
+
+/// How often merges are checked for a release tag. Releases are cut far less
+/// often than PRs change, and every check fetches each repository's tags.
+const RELEASE_TAG_POLL_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
 /// Service to monitor PRs and update task status when they are merged
 pub struct PrMonitorService<C: ContainerService> {
     db: DBService,
@@ -82,6 +90,7 @@ impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
             self.poll_interval
         );
 
+        let mut release_interval = interval(RELEASE_TAG_POLL_INTERVAL);
         let mut interval = interval(self.poll_interval);
 
         loop {
@@ -91,6 +100,11 @@ impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
                         error!("Error checking open PRs: {}", e);
                     }
                 }
+                _ = release_interval.tick() => {
+                    if let Err(e) = self.tag_released_merges().await {
+                        error!("Error tagging released merges: {}", e);
+                    }
+                }
                 _ = self.sync_notify.notified() => {
                     debug!("PR sync triggered externally");
                 }
@@ -154,10 +168,9 @@ impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
         )
         .await?;
 
-        // If this is a workspace PR and it was merged, try to archive
-        if matches!(&status.status, MergeStatus::Merged)
-            && let Some(workspace_id) = pr.workspace_id
-        {
+        // A workspace may have several PRs, e.g. a stack, so any of them
+        // resolving may finish its work
+        if let Some(workspace_id) = pr.workspace_id {
             self.try_archive_workspace(workspace_id, pr.pr_number)
                 .await?;
         }
@@ -167,7 +180,8 @@ impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
         Ok(())
     }
 
-    /// Archive workspace if all its PRs are merged/closed
+    /// Archive workspace once its PRs taken together are merged, see
+    /// [`MergeStatus::aggregate`]
     async fn try_archive_workspace(
         &self,
         workspace_id: uuid::Uuid,
@@ -177,13 +191,15 @@ impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
             return Ok(());
         };
 
-        let open_pr_count =
-            PullRequest::count_open_for_workspace(&self.db.pool, workspace_id).await?;
+        let prs = PullRequest::find_by_workspace_id(&self.db.pool, workspace_id).await?;
+        let status = MergeStatus::aggregate(prs.iter().map(|pr| &pr.pr_status));
 
-        if open_pr_count == 0 {
+        if matches!(status, Some(MergeStatus::Merged)) {
             info!(
-                "PR #{} was merged, archiving workspace {}",
-                pr_number, workspace.id
+                "PR #{} resolved the last of {} PR(s), archiving workspace {}",
+                pr_number,
+                prs.len(),
+                workspace.id
             );
             if !workspace.pinned
                 && let Err(e) = self.container.archive_workspace(workspace.id).await
@@ -197,19 +213,78 @@ impl<C: ContainerService + Send + Sync + 'static> PrMonitorService<C> {
                     "pr_merged",
                     Some(json!({
                         "workspace_id": workspace.id.to_string(),
+                        "pr_count": prs.len(),
                     })),
                 );
             }
         } else {
             info!(
-                "PR #{} was merged, leaving workspace {} active with {} open PR(s)",
-                pr_number, workspace.id, open_pr_count
+                "PR #{} resolved, leaving workspace {} active with PRs {:?}",
+                pr_number, workspace.id, status
             );
         }
 
         Ok(())
     }
 
+    /// Record the first release tag containing each recent merge that has
+    /// none yet. Merges older than the window are assumed never to ship.
+    /// Each repository's tags are fetched and resolved once per check.
+    async fn tag_released_merges(&self) -> Result<(), PrMonitorError> {
+        let since = Utc::now() - chrono::Duration::days(RELEASE_TAG_WINDOW_DAYS);
+        let unreleased = Merge::find_unreleased(&self.db.pool, since).await?;
+        if unreleased.is_empty() {
+            return Ok(());
+        }
+
+        let tagged = match tokio::task::spawn_blocking(move || {
+            let git = GitService::new();
+            let mut by_repo: HashMap<String, Vec<UnreleasedMerge>> = HashMap::new();
+            for merge in unreleased {
+                by_repo
+                    .entry(merge.repo_path.clone())
+                    .or_default()
+                    .push(merge);
+            }
+
+            let mut tagged = Vec::new();
+            for (repo_path, merges) in by_repo {
+                // PR merge commits and release tags are usually made on the
+                // host, so they're only here once fetched.
+                if let Err(e) = git.fetch_tags(Path::new(&repo_path)) {
+                    debug!("Failed to fetch tags for {}: {}", repo_path, e);
+                }
+                let commits: Vec<String> = merges
+                    .iter()
+                    .map(|merge| merge.merge_commit.clone())
+                    .collect();
+                match git.first_releases_containing(Path::new(&repo_path), &commits) {
+                    Ok(releases) => tagged.extend(merges.into_iter().filter_map(|merge| {
+                        let tag = releases.get(&merge.merge_commit)?.clone();
+                        Some((merge, tag))
+                    })),
+                    Err(e) => debug!("Failed to find release tags in {}: {}", repo_path, e),
+                }
+            }
+            tagged
+        })
+        .await
+        {
+            Ok(tagged) => tagged,
+            Err(e) => {
+                error!("Release tag lookup task failed: {}", e);
+                return Ok(());
+            }
+        };
+
+        for (merge, tag) in &tagged {
+            Merge::set_release_tag(&self.db.pool, &merge.record, tag).await?;
+            info!("Merge commit {} shipped in {}", merge.merge_commit, tag);
+        }
+
+        Ok(())
+    }
+
     /// Sync pending PR status changes to remote server.
     async fn sync_pending_to_remote(&self) {
         let Some(client) = &self.remote_client else {
@@ -1,35 +1,50 @@
 //! OAuth client for authorization-code handoffs with automatic retries.
 
-use std::time::Duration;
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 use api_types::{
-    AcceptInvitationResponse, AuthMethodsResponse, CreateInvitationRequest,
-    CreateInvitationResponse, CreateIssueAssigneeRequest, CreateIssueRelationshipRequest,
-    CreateIssueRequest, CreateIssueTagRequest, CreateOrganizationRequest,
-    CreateOrganizationResponse, CreateWorkspaceRequest, DeleteResponse, DeleteWorkspaceRequest,
+    AcceptInvitationResponse, ApplyIssueActionsRequest, ApplyIssueActionsResponse,
+    AttachmentWithBlob, AuthMethodsResponse, BatchMutationsRequest, BatchMutationsResponse,
+    ConfirmUploadRequest, CreateInvitationRequest, CreateInvitationResponse,
+    CreateIssueAssigneeRequest, CreateIssueRelationshipRequest, CreateIssueRequest,
+    CreateIssueTagRequest, CreateOrganizationRequest, CreateOrganizationResponse,
+    CreateProjectRequest, CreateWorkspaceRequest, DeleteResponse, DeleteWorkspaceRequest,
     GetInvitationResponse, GetOrganizationResponse, HandoffInitRequest, HandoffInitResponse,
-    HandoffRedeemRequest, HandoffRedeemResponse, Issue, IssueAssignee, IssueRelationship, IssueTag,
-    ListAttachmentsResponse, ListInvitationsResponse, ListIssueAssigneesResponse,
+    HandoffRedeemRequest, HandoffRedeemResponse, InitUploadRequest, InitUploadResponse, Issue,
+    IssueAssignee, IssueRelationship, IssueTag, ListAttachmentsResponse, ListDeletedIssuesResponse,
+    ListInvitationsResponse, ListIssueActivityResponse, ListIssueAssigneesResponse,
     ListIssueRelationshipsResponse, ListIssueTagsResponse, ListIssuesResponse, ListMembersResponse,
-    ListOrganizationsResponse, ListProjectStatusesResponse, ListProjectsResponse,
-    ListPullRequestsResponse, ListTagsResponse, LocalLoginRequest, LocalLoginResponse,
-    MutationResponse, Organization, ProfileResponse, PullRequest, RevokeInvitationRequest,
-    SearchIssuesRequest, Tag, TokenRefreshRequest, TokenRefreshResponse, UpdateIssueRequest,
-    UpdateMemberRoleRequest, UpdateMemberRoleResponse, UpdateOrganizationRequest,
+    ListOrganizationsResponse, ListProductUpdatesResponse, ListProjectStatusesResponse,
+    ListProjectsResponse, ListPullRequestsResponse, ListTagsResponse, LocalLoginRequest,
+    LocalLoginResponse, MutationResponse, Notification, Organization, OrganizationClientConfig,
+    ProfileResponse, Project, ProjectCommitSettings, ProjectPrSettings, PullRequest,
+    RevokeInvitationRequest, SearchIssuesRequest, Tag, TokenRefreshRequest, TokenRefreshResponse,
+    UpdateIssueRequest, UpdateMemberRoleRequest, UpdateMemberRoleResponse,
+    UpdateOrganizationRequest, UpdateProjectCommitSettingsRequest, UpdateProjectPrSettingsRequest,
     UpdatePullRequestApiRequest, UpdateWorkspaceRequest, UpsertPullRequestRequest, Workspace,
 };
 use backon::{ExponentialBuilder, Retryable};
-use chrono::Duration as ChronoDuration;
+use chrono::{DateTime, Duration as ChronoDuration, SecondsFormat, Utc};
 use relay_types::{ListRelayHostsResponse, RelayHost};
 use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tracing::warn;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
 use url::Url;
 use utils::jwt::extract_expiration;
 use uuid::Uuid;
 
-use super::{auth::AuthContext, oauth_credentials::Credentials};
+use super::{
+    analytics::generate_user_id,
+    auth::{AuthContext, AuthEvent},
+    network_watcher::NetworkChanges,
+    oauth_credentials::Credentials,
+};
 
 #[derive(Debug, Clone, Error)]
 pub enum RemoteClientError {
@@ -58,6 +73,24 @@ impl RemoteClientError {
         "remote_auth_unavailable"
     }
 
+    /// The remote answered 410 Gone: what was asked for no longer exists.
+    pub fn is_gone(&self) -> bool {
+        matches!(self, Self::Http { status: 410, .. })
+    }
+
+    /// The remote doesn't know what was asked for, or won't show it to this
+    /// user, so it may be hosted on another remote.
+    pub fn may_be_on_another_remote(&self) -> bool {
+        matches!(
+            self,
+            Self::Auth
+                | Self::Http {
+                    status: 403 | 404,
+                    ..
+                }
+        )
+    }
+
     /// Returns true if the error is transient and should be retried.
     fn should_retry(&self) -> bool {
         match self {
@@ -133,6 +166,11 @@ fn map_error_code(code: Option<&str>) -> HandoffErrorCode {
     }
 }
 
+#[derive(Deserialize)]
+struct ListNotificationsResponse {
+    notifications: Vec<Notification>,
+}
+
 #[derive(Deserialize)]
 struct ApiErrorResponse {
     error: String,
@@ -141,15 +179,20 @@ struct ApiErrorResponse {
 /// HTTP client for the remote OAuth server with automatic retries.
 pub struct RemoteClient {
     base: Url,
-    http: Client,
+    /// Shared between clones so [`Self::reset_connections`] reaches every
+    /// holder.
+    http: Arc<RwLock<Client>>,
     auth_context: AuthContext,
+    /// Stable per-machine identifier prefixed to the mutation IDs this
+    /// machine sends.
+    device_id: String,
 }
 
 impl std::fmt::Debug for RemoteClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RemoteClient")
             .field("base", &self.base)
-            .field("http", &self.http)
+            .field("http", &self.http())
             .field("auth_context", &"<present>")
             .finish()
     }
@@ -161,6 +204,7 @@ impl Clone for RemoteClient {
             base: self.base.clone(),
             http: self.http.clone(),
             auth_context: self.auth_context.clone(),
+            device_id: self.device_id.clone(),
         }
     }
 }
@@ -168,9 +212,28 @@ impl Clone for RemoteClient {
 impl RemoteClient {
     const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
     const TOKEN_REFRESH_LEEWAY_SECS: i64 = 20;
+    /// How long before expiry the background refresher renews the access token.
+    /// Must stay well below the remote access-token TTL (two minutes).
+    const PREEMPTIVE_REFRESH_LEEWAY_SECS: i64 = 30;
+    /// Lower bound between background refresh attempts, and the first
+    /// backoff after a failed one.
+    const MIN_PREEMPTIVE_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+    /// Upper bound of the backoff between failed background refreshes.
+    const MAX_PREEMPTIVE_REFRESH_BACKOFF: Duration = Duration::from_secs(300);
+    /// Poll interval for the background refresher while logged out or degraded.
+    const TOKEN_REFRESHER_IDLE_INTERVAL: Duration = Duration::from_secs(60);
 
     pub fn new(base_url: &str, auth_context: AuthContext) -> Result<Self, RemoteClientError> {
         let base = Url::parse(base_url).map_err(|e| RemoteClientError::Url(e.to_string()))?;
+        Ok(Self {
+            base,
+            http: Arc::new(RwLock::new(Self::build_http()?)),
+            auth_context,
+            device_id: generate_user_id(),
+        })
+    }
+
+    fn build_http() -> Result<Client, RemoteClientError> {
         let mut builder = Client::builder()
             .timeout(Self::REQUEST_TIMEOUT)
             .user_agent(concat!("remote-client/", env!("CARGO_PKG_VERSION")));
@@ -180,13 +243,56 @@ impl RemoteClient {
             builder = builder.danger_accept_invalid_certs(true);
         }
 
-        let http = builder
+        builder
             .build()
-            .map_err(|e| RemoteClientError::Transport(e.to_string()))?;
-        Ok(Self {
-            base,
-            http,
-            auth_context,
+            .map_err(|e| RemoteClientError::Transport(e.to_string()))
+    }
+
+    fn http(&self) -> Client {
+        self.http.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Link to a page of the remote web app, which is served from the same
+    /// origin as the API.
+    pub fn web_url(&self, path: &str) -> Result<Url, RemoteClientError> {
+        self.base
+            .join(path)
+            .map_err(|e| RemoteClientError::Url(e.to_string()))
+    }
+
+    /// Replaces the connection pool so later requests, token refreshes
+    /// included, open fresh connections instead of reusing ones bound to a
+    /// network route that no longer exists. In-flight requests finish on the
+    /// old pool.
+    pub fn reset_connections(&self) -> Result<(), RemoteClientError> {
+        let http = Self::build_http()?;
+        *self.http.write().unwrap_or_else(|e| e.into_inner()) = http;
+        Ok(())
+    }
+
+    /// Spawns a task that resets the connection pool whenever the network
+    /// route changes.
+    pub fn spawn_network_change_handler(
+        &self,
+        mut changes: NetworkChanges,
+        shutdown: CancellationToken,
+    ) -> JoinHandle<()> {
+        let client = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    changed = changes.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+                    }
+                }
+                match client.reset_connections() {
+                    Ok(()) => debug!("reset remote connections after network change"),
+                    Err(err) => warn!(?err, "failed to reset remote connections"),
+                }
+            }
         })
     }
 
@@ -195,9 +301,18 @@ impl RemoteClient {
         &self,
     ) -> std::pin::Pin<
         Box<dyn std::future::Future<Output = Result<String, RemoteClientError>> + Send + '_>,
+    > {
+        self.require_token_with_leeway(ChronoDuration::seconds(Self::TOKEN_REFRESH_LEEWAY_SECS))
+    }
+
+    /// Returns a valid access token, refreshing when it expires within `leeway`.
+    fn require_token_with_leeway(
+        &self,
+        leeway: ChronoDuration,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<String, RemoteClientError>> + Send + '_>,
     > {
         Box::pin(async move {
-            let leeway = ChronoDuration::seconds(Self::TOKEN_REFRESH_LEEWAY_SECS);
             let creds = self
                 .auth_context
                 .get_credentials()
@@ -235,6 +350,7 @@ impl RemoteClient {
                 Err(err) if err.is_definitive_auth_failure() => {
                     let _ = self.auth_context.clear_credentials().await;
                     self.auth_context.clear_remote_auth_degraded_slug().await;
+                    self.auth_context.notify(AuthEvent::SessionEnded);
                     Err(err)
                 }
                 Err(err) => {
@@ -266,6 +382,8 @@ impl RemoteClient {
             .await
             .map_err(|e| RemoteClientError::Storage(e.to_string()))?;
         self.auth_context.clear_remote_auth_degraded_slug().await;
+        self.auth_context
+            .notify(AuthEvent::Refreshed { expires_at });
         Ok(new_creds)
     }
 
@@ -287,6 +405,85 @@ impl RemoteClient {
         self.require_token().await
     }
 
+    /// Sign-in this client sends, issued by its host.
+    pub fn auth_context(&self) -> &AuthContext {
+        &self.auth_context
+    }
+
+    /// Whether credentials are stored, i.e. the user is signed in.
+    pub async fn is_signed_in(&self) -> bool {
+        self.auth_context.get_credentials().await.is_some()
+    }
+
+    /// Spawns a task that renews the access token shortly before it expires,
+    /// so long-lived consumers never pick up a token that is about to lapse
+    /// and an idle app doesn't find its session expired on the next request.
+    /// Subscribers of [`AuthContext::subscribe`] are told before each renewal.
+    ///
+    /// Failed renewals are retried with exponential backoff. Once the remote
+    /// rejects the refresh token for good, the task waits for a new sign-in.
+    pub fn spawn_token_refresher(&self, shutdown: CancellationToken) -> JoinHandle<()> {
+        let client = self.clone();
+        tokio::spawn(async move {
+            let leeway = ChronoDuration::seconds(Self::PREEMPTIVE_REFRESH_LEEWAY_SECS);
+            let mut backoff = Self::MIN_PREEMPTIVE_REFRESH_INTERVAL;
+            // Refresh token the remote rejected; not retried.
+            let mut rejected: Option<String> = None;
+            loop {
+                let creds = client
+                    .auth_context
+                    .get_credentials()
+                    .await
+                    .filter(|creds| rejected.as_ref() != Some(&creds.refresh_token));
+                let wait = match &creds {
+                    Some(creds) => creds
+                        .expires_at
+                        .and_then(|expires_at| (expires_at - leeway - Utc::now()).to_std().ok())
+                        .unwrap_or(Duration::ZERO)
+                        .max(backoff),
+                    None => Self::TOKEN_REFRESHER_IDLE_INTERVAL,
+                };
+
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(wait) => {}
+                }
+
+                let Some(creds) = client.auth_context.get_credentials().await else {
+                    continue;
+                };
+                if rejected.as_ref() == Some(&creds.refresh_token) {
+                    continue;
+                }
+                rejected = None;
+                if !creds.expires_soon(leeway) {
+                    continue;
+                }
+                if let Some(expires_at) = creds.expires_at {
+                    client
+                        .auth_context
+                        .notify(AuthEvent::ExpiringSoon { expires_at });
+                }
+
+                match client.require_token_with_leeway(leeway).await {
+                    Ok(_) => {
+                        debug!("pre-emptively refreshed remote access token");
+                        backoff = Self::MIN_PREEMPTIVE_REFRESH_INTERVAL;
+                    }
+                    Err(err) if err.is_definitive_auth_failure() => {
+                        warn!(?err, "remote session ended; waiting for a new sign-in");
+                        rejected = Some(creds.refresh_token);
+                        backoff = Self::MIN_PREEMPTIVE_REFRESH_INTERVAL;
+                    }
+                    Err(err) => {
+                        warn!(?err, ?backoff, "pre-emptive access token refresh failed");
+                        backoff = (backoff * 2).min(Self::MAX_PREEMPTIVE_REFRESH_BACKOFF);
+                    }
+                }
+            }
+        })
+    }
+
     /// Initiates an authorization-code handoff for the given provider.
     pub async fn handoff_init(
         &self,
@@ -329,6 +526,15 @@ impl RemoteClient {
             .await
     }
 
+    fn mutation_id_prefix(&self) -> String {
+        format!("{}:", self.device_id)
+    }
+
+    /// Whether `mutation_id` was sent by a client on this machine.
+    pub fn is_own_mutation(&self, mutation_id: &str) -> bool {
+        mutation_id.starts_with(&self.mutation_id_prefix())
+    }
+
     async fn send<B>(
         &self,
         method: reqwest::Method,
@@ -376,14 +582,21 @@ impl RemoteClient {
             .base
             .join(path)
             .map_err(|e| RemoteClientError::Url(e.to_string()))?;
+        // Shared by every retry so the resulting activity carries one id.
+        let mutation_id = (requires_auth && method != reqwest::Method::GET)
+            .then(|| format!("{}{}", self.mutation_id_prefix(), Uuid::new_v4()));
 
         let operation = || async {
             let mut req = self
-                .http
+                .http()
                 .request(method.clone(), url.clone())
                 .header("X-Client-Version", env!("CARGO_PKG_VERSION"))
                 .header("X-Client-Type", "local-backend");
 
+            if let Some(mutation_id) = &mutation_id {
+                req = req.header("X-Mutation-Id", mutation_id);
+            }
+
             if requires_auth {
                 let token = self.require_token().await?;
                 req = req.bearer_auth(token);
@@ -540,6 +753,34 @@ impl RemoteClient {
             .await
     }
 
+    /// Gets the organization's client defaults.
+    pub async fn get_organization_client_config(
+        &self,
+        org_id: Uuid,
+    ) -> Result<OrganizationClientConfig, RemoteClientError> {
+        self.get_authed(&format!("/v1/organizations/{org_id}/client-config"))
+            .await
+    }
+
+    /// Replaces the organization's client defaults (admin only).
+    pub async fn update_organization_client_config(
+        &self,
+        org_id: Uuid,
+        config: &OrganizationClientConfig,
+    ) -> Result<OrganizationClientConfig, RemoteClientError> {
+        let res = self
+            .send(
+                reqwest::Method::PUT,
+                &format!("/v1/organizations/{org_id}/client-config"),
+                true,
+                Some(config),
+            )
+            .await?;
+        res.json::<OrganizationClientConfig>()
+            .await
+            .map_err(|e| RemoteClientError::Serde(e.to_string()))
+    }
+
     /// Creates a new organization.
     pub async fn create_organization(
         &self,
@@ -698,7 +939,7 @@ impl RemoteClient {
     pub async fn update_workspace(
         &self,
         local_workspace_id: Uuid,
-        name: Option<Option<String>>,
+        name: Patch<String>,
         archived: Option<bool>,
         files_changed: Option<i32>,
         lines_added: Option<i32>,
@@ -712,9 +953,9 @@ impl RemoteClient {
                 local_workspace_id,
                 name,
                 archived,
-                files_changed: files_changed.map(Some),
-                lines_added: lines_added.map(Some),
-                lines_removed: lines_removed.map(Some),
+                files_changed: files_changed.map_or(Patch::Absent, Patch::Value),
+                lines_added: lines_added.map_or(Patch::Absent, Patch::Value),
+                lines_removed: lines_removed.map_or(Patch::Absent, Patch::Value),
             }),
         )
         .await?;
@@ -751,6 +992,35 @@ impl RemoteClient {
         Ok(())
     }
 
+    // ── Product Updates ─────────────────────────────────────────────────
+
+    /// Lists the "what's new" feed for the current user.
+    pub async fn list_product_updates(
+        &self,
+    ) -> Result<ListProductUpdatesResponse, RemoteClientError> {
+        self.get_authed("/v1/product-updates").await
+    }
+
+    /// Marks a product update as read; returns the refreshed feed.
+    pub async fn mark_product_update_read(
+        &self,
+        product_update_id: Uuid,
+    ) -> Result<ListProductUpdatesResponse, RemoteClientError> {
+        self.post_authed(
+            &format!("/v1/product-updates/{product_update_id}/read"),
+            None::<&()>,
+        )
+        .await
+    }
+
+    /// Marks every published product update as read; returns the refreshed feed.
+    pub async fn mark_all_product_updates_read(
+        &self,
+    ) -> Result<ListProductUpdatesResponse, RemoteClientError> {
+        self.post_authed("/v1/product-updates/read-all", None::<&()>)
+            .await
+    }
+
     // ── Issues ──────────────────────────────────────────────────────────
 
     /// Lists issues for a project.
@@ -762,6 +1032,56 @@ impl RemoteClient {
             .await
     }
 
+    /// Lists the current user's undismissed notifications.
+    pub async fn list_notifications(&self) -> Result<Vec<Notification>, RemoteClientError> {
+        let response: ListNotificationsResponse = self.get_authed("/v1/notifications").await?;
+        Ok(response.notifications)
+    }
+
+    /// Lists up to `limit` issues for a project updated after the
+    /// `(updated_at, id)` cursor, oldest first.
+    pub async fn list_issues_page(
+        &self,
+        project_id: Uuid,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i32,
+    ) -> Result<ListIssuesResponse, RemoteClientError> {
+        let mut path = format!("/v1/issues?project_id={project_id}&limit={limit}");
+        if let Some((updated_at, id)) = after {
+            // `Z` suffix keeps the timestamp free of `+`, which needs escaping.
+            let updated_at = updated_at.to_rfc3339_opts(SecondsFormat::Micros, true);
+            path.push_str(&format!("&after_updated_at={updated_at}&after_id={id}"));
+        }
+        self.get_authed(&path).await
+    }
+
+    /// Lists a page of a project's issue activity after the given sequence number,
+    /// with the project's replays after `replays_after`.
+    pub async fn list_issue_activity(
+        &self,
+        project_id: Uuid,
+        since_seq: i64,
+        replays_after: i64,
+    ) -> Result<ListIssueActivityResponse, RemoteClientError> {
+        self.get_authed(&format!(
+            "/v1/issues/activity?project_id={project_id}&since_seq={since_seq}\
+             &include_project_events=true&replays_after={replays_after}"
+        ))
+        .await
+    }
+
+    /// Lists issues deleted from a project after the given activity sequence number.
+    pub async fn list_deleted_issues(
+        &self,
+        project_id: Uuid,
+        since_seq: i64,
+    ) -> Result<ListDeletedIssuesResponse, RemoteClientError> {
+        self.get_authed(&format!(
+            "/v1/issues/deleted?project_id={project_id}&since_seq={since_seq}"
+        ))
+        .await
+    }
+
     /// Searches issues for a project using the canonical JSON request shape.
     pub async fn search_issues(
         &self,
@@ -770,6 +1090,16 @@ impl RemoteClient {
         self.post_authed("/v1/issues/search", Some(request)).await
     }
 
+    /// Applies an ordered batch of actions to an issue in one transaction.
+    pub async fn apply_issue_actions(
+        &self,
+        issue_id: Uuid,
+        request: &ApplyIssueActionsRequest,
+    ) -> Result<ApplyIssueActionsResponse, RemoteClientError> {
+        self.post_authed(&format!("/v1/issues/{issue_id}/actions"), Some(request))
+            .await
+    }
+
     /// Gets a single issue by ID.
     pub async fn get_issue(&self, issue_id: Uuid) -> Result<Issue, RemoteClientError> {
         self.get_authed(&format!("/v1/issues/{issue_id}")).await
@@ -783,6 +1113,14 @@ impl RemoteClient {
         self.post_authed("/v1/issues", Some(request)).await
     }
 
+    /// Applies `request` as a single remote transaction.
+    pub async fn apply_mutation_batch(
+        &self,
+        request: &BatchMutationsRequest,
+    ) -> Result<BatchMutationsResponse, RemoteClientError> {
+        self.post_authed("/v1/mutations/batch", Some(request)).await
+    }
+
     /// Updates an existing issue.
     pub async fn update_issue(
         &self,
@@ -958,6 +1296,74 @@ impl RemoteClient {
             .await
     }
 
+    /// Creates a project with the default statuses.
+    pub async fn create_project(
+        &self,
+        request: &CreateProjectRequest,
+    ) -> Result<MutationResponse<Project>, RemoteClientError> {
+        self.post_authed("/v1/projects", Some(request)).await
+    }
+
+    /// Deletes a project and everything in it.
+    pub async fn delete_project(&self, project_id: Uuid) -> Result<(), RemoteClientError> {
+        self.delete_authed(&format!("/v1/projects/{project_id}"))
+            .await
+    }
+
+    /// Commit message convention for a project's workspaces.
+    pub async fn get_project_commit_settings(
+        &self,
+        project_id: Uuid,
+    ) -> Result<ProjectCommitSettings, RemoteClientError> {
+        self.get_authed(&format!("/v1/projects/{project_id}/commit-settings"))
+            .await
+    }
+
+    pub async fn update_project_commit_settings(
+        &self,
+        project_id: Uuid,
+        request: &UpdateProjectCommitSettingsRequest,
+    ) -> Result<ProjectCommitSettings, RemoteClientError> {
+        let res = self
+            .send(
+                reqwest::Method::PUT,
+                &format!("/v1/projects/{project_id}/commit-settings"),
+                true,
+                Some(request),
+            )
+            .await?;
+        res.json::<ProjectCommitSettings>()
+            .await
+            .map_err(|e| RemoteClientError::Serde(e.to_string()))
+    }
+
+    /// How pull requests are opened from a project's workspaces.
+    pub async fn get_project_pr_settings(
+        &self,
+        project_id: Uuid,
+    ) -> Result<ProjectPrSettings, RemoteClientError> {
+        self.get_authed(&format!("/v1/projects/{project_id}/pr-settings"))
+            .await
+    }
+
+    pub async fn update_project_pr_settings(
+        &self,
+        project_id: Uuid,
+        request: &UpdateProjectPrSettingsRequest,
+    ) -> Result<ProjectPrSettings, RemoteClientError> {
+        let res = self
+            .send(
+                reqwest::Method::PUT,
+                &format!("/v1/projects/{project_id}/pr-settings"),
+                true,
+                Some(request),
+            )
+            .await?;
+        res.json::<ProjectPrSettings>()
+            .await
+            .map_err(|e| RemoteClientError::Serde(e.to_string()))
+    }
+
     // ── Project Statuses ────────────────────────────────────────────────
 
     /// Lists project statuses for a project (used for status name ↔ UUID mapping).
@@ -1015,9 +1421,57 @@ impl RemoteClient {
             .await
     }
 
+    /// Starts a direct-to-storage attachment upload.
+    pub async fn init_attachment_upload(
+        &self,
+        request: &InitUploadRequest,
+    ) -> Result<InitUploadResponse, RemoteClientError> {
+        self.post_authed("/v1/attachments/init", Some(request))
+            .await
+    }
+
+    /// Records an uploaded blob as an attachment.
+    pub async fn confirm_attachment_upload(
+        &self,
+        request: &ConfirmUploadRequest,
+    ) -> Result<AttachmentWithBlob, RemoteClientError> {
+        self.post_authed("/v1/attachments/confirm", Some(request))
+            .await
+    }
+
+    /// Used for uploading to presigned Azure SAS URLs.
+    pub async fn upload_to_url(
+        &self,
+        url: &str,
+        data: Vec<u8>,
+        content_type: &str,
+    ) -> Result<(), RemoteClientError> {
+        let res = self
+            .http()
+            .put(url)
+            .header("x-ms-blob-type", "BlockBlob")
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(data)
+            .send()
+            .await
+            .map_err(map_reqwest_error)?;
+        if !res.status().is_success() {
+            return Err(RemoteClientError::Http {
+                status: res.status().as_u16(),
+                body: res.text().await.unwrap_or_default(),
+            });
+        }
+        Ok(())
+    }
+
     /// Used for fetching from presigned Azure SAS URLs.
     pub async fn download_from_url(&self, url: &str) -> Result<Vec<u8>, RemoteClientError> {
-        let res = self.http.get(url).send().await.map_err(map_reqwest_error)?;
+        let res = self
+            .http()
+            .get(url)
+            .send()
+            .await
+            .map_err(map_reqwest_error)?;
         if !res.status().is_success() {
             return Err(RemoteClientError::Http {
                 status: res.status().as_u16(),
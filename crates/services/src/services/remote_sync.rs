@@ -1,4 +1,4 @@
-use api_types::UpsertPullRequestRequest;
+use api_types::{Patch, UpsertPullRequestRequest};
 use db::models::workspace::Workspace;
 use git::GitService;
 use sqlx::SqlitePool;
@@ -13,7 +13,7 @@ use super::{
 async fn update_workspace_on_remote(
     client: &RemoteClient,
     workspace_id: Uuid,
-    name: Option<Option<String>>,
+    name: Patch<String>,
     archived: Option<bool>,
     stats: Option<&DiffStats>,
 ) {
@@ -51,7 +51,7 @@ async fn update_workspace_on_remote(
 pub async fn sync_workspace_to_remote(
     client: &RemoteClient,
     workspace_id: Uuid,
-    name: Option<Option<String>>,
+    name: Patch<String>,
     archived: Option<bool>,
     stats: Option<&DiffStats>,
 ) {
@@ -211,7 +211,7 @@ pub async fn sync_all_linked_workspaces(
         update_workspace_on_remote(
             client,
             workspace.id,
-            workspace.name.clone().map(Some),
+            Patch::set(workspace.name.clone()),
             Some(workspace.archived),
             stats.as_ref(),
         )
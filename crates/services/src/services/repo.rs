@@ -1,7 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use db::models::repo::{Repo as RepoModel, SearchMatchType, SearchResult};
-use git::{GitService, GitServiceError};
+use git::{CloneFilter, GitService, GitServiceError};
 use sqlx::SqlitePool;
 use thiserror::Error;
 use utils::path::expand_tilde;
@@ -127,6 +127,48 @@ impl RepoService {
         Ok(repo)
     }
 
+    /// Clone `clone_url` into a new directory at `path` and register it.
+    pub async fn clone_repo(
+        &self,
+        pool: &SqlitePool,
+        clone_url: &str,
+        path: &str,
+        filter: CloneFilter,
+    ) -> Result<RepoModel> {
+        let repo_path = self.normalize_path(path)?;
+        if repo_path.exists() {
+            return Err(RepoError::DirectoryAlreadyExists(repo_path));
+        }
+
+        let name = repo_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| RepoError::InvalidFolderName(path.to_string()))?;
+
+        let url = clone_url.to_string();
+        let target = repo_path.clone();
+        tokio::task::spawn_blocking(move || {
+            GitService::clone_repository(&url, &target, None, filter)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+
+        match RepoModel::find_or_create(pool, &repo_path, &name).await {
+            Ok(repo) => Ok(repo),
+            Err(error) => {
+                let _ = tokio::fs::remove_dir_all(&repo_path).await;
+                Err(error.into())
+            }
+        }
+    }
+
+    /// Undo [`Self::clone_repo`]: unregister the repo and delete its clone.
+    pub async fn remove_clone(&self, pool: &SqlitePool, repo: &RepoModel) -> Result<()> {
+        RepoModel::delete(pool, repo.id).await?;
+        tokio::fs::remove_dir_all(&repo.path).await?;
+        Ok(())
+    }
+
     pub async fn search_files(
         &self,
         cache: &FileSearchCache,
@@ -0,0 +1,160 @@
+//! Shares a project's legacy local tasks as remote issues in bulk.
+
+use std::collections::HashSet;
+
+use api_types::{BatchMutation, BatchMutationsRequest, CreateIssueRequest};
+use db::models::task::{Task, TaskStatus};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::remote_client::{RemoteClient, RemoteClientError};
+
+/// Issues created per remote batch; the remote accepts at most 50.
+const SHARE_BATCH_SIZE: usize = 50;
+
+#[derive(Debug, Error)]
+pub enum TaskShareError {
+    #[error(transparent)]
+    Remote(#[from] RemoteClientError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("remote project has no statuses")]
+    NoStatuses,
+}
+
+/// What sharing one task uploads. Every other issue field is left empty.
+#[derive(Debug, Clone)]
+pub struct PlannedIssue {
+    pub task_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub status_id: Uuid,
+    pub status_name: String,
+}
+
+/// The issues sharing `project_id`'s unshared tasks into `remote_project_id`
+/// would create, in order. Only reads from the remote.
+pub async fn plan_project_share(
+    pool: &SqlitePool,
+    client: &RemoteClient,
+    project_id: Uuid,
+    remote_project_id: Uuid,
+) -> Result<Vec<PlannedIssue>, TaskShareError> {
+    let tasks = Task::find_unshared_by_project(pool, project_id).await?;
+    if tasks.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let statuses = client
+        .list_project_statuses(remote_project_id)
+        .await?
+        .project_statuses;
+    let fallback = statuses
+        .iter()
+        .filter(|status| !status.hidden)
+        .min_by_key(|status| status.sort_order)
+        .or_else(|| statuses.first())
+        .ok_or(TaskShareError::NoStatuses)?;
+
+    Ok(tasks
+        .into_iter()
+        .map(|task| {
+            let name = match task.status {
+                TaskStatus::Todo => "to do",
+                TaskStatus::InProgress => "in progress",
+                TaskStatus::InReview => "in review",
+                TaskStatus::Done => "done",
+                TaskStatus::Cancelled => "cancelled",
+            };
+            let status = statuses
+                .iter()
+                .find(|status| status.name.eq_ignore_ascii_case(name))
+                .unwrap_or(fallback);
+            PlannedIssue {
+                task_id: task.id,
+                title: task.title,
+                description: task.description,
+                status_id: status.id,
+                status_name: status.name.clone(),
+            }
+        })
+        .collect())
+}
+
+/// Remote issue id for `task_id` shared into `remote_project_id`. Derived
+/// rather than random, so a retry after a batch landed remotely but wasn't
+/// recorded locally finds the issue instead of creating it again.
+fn shared_issue_id(remote_project_id: Uuid, task_id: Uuid) -> Uuid {
+    Uuid::new_v8((remote_project_id.as_u128() ^ task_id.as_u128()).to_be_bytes())
+}
+
+/// Create a remote issue in `remote_project_id` for every unshared task of
+/// `project_id`, recording each mapping as its batch lands. New issues are
+/// ordered after the project's existing ones.
+/// `on_progress(shared, total)` is called after each batch. Returns the
+/// number of tasks shared; a failed batch leaves earlier ones shared, so the
+/// call can simply be repeated.
+pub async fn share_project_tasks(
+    pool: &SqlitePool,
+    client: &RemoteClient,
+    project_id: Uuid,
+    remote_project_id: Uuid,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Result<usize, TaskShareError> {
+    let plan = plan_project_share(pool, client, project_id, remote_project_id).await?;
+    let total = plan.len();
+    on_progress(0, total);
+
+    let existing = client.list_issues(remote_project_id).await?.issues;
+    let existing_ids: HashSet<Uuid> = existing.iter().map(|issue| issue.id).collect();
+    let mut sort_order = existing
+        .iter()
+        .map(|issue| issue.sort_order)
+        .fold(0.0, f64::max);
+
+    let mut shared = 0;
+    for batch in plan.chunks(SHARE_BATCH_SIZE) {
+        let issue_ids: Vec<Uuid> = batch
+            .iter()
+            .map(|issue| shared_issue_id(remote_project_id, issue.task_id))
+            .collect();
+        let mutations: Vec<BatchMutation> = batch
+            .iter()
+            .zip(&issue_ids)
+            .filter(|(_, issue_id)| !existing_ids.contains(issue_id))
+            .map(|(issue, issue_id)| {
+                sort_order += 1.0;
+                BatchMutation::CreateIssue(CreateIssueRequest {
+                    id: Some(*issue_id),
+                    project_id: remote_project_id,
+                    status_id: issue.status_id,
+                    title: issue.title.clone(),
+                    description: issue.description.clone(),
+                    priority: None,
+                    start_date: None,
+                    target_date: None,
+                    completed_at: None,
+                    sort_order,
+                    parent_issue_id: None,
+                    parent_issue_sort_order: None,
+                    extension_metadata: serde_json::json!({}),
+                })
+            })
+            .collect();
+
+        if !mutations.is_empty() {
+            client
+                .apply_mutation_batch(&BatchMutationsRequest { mutations })
+                .await?;
+        }
+
+        for (issue, issue_id) in batch.iter().zip(issue_ids) {
+            Task::set_remote_issue_id(pool, issue.task_id, issue_id).await?;
+        }
+        shared += batch.len();
+        on_progress(shared, total);
+    }
+
+    Ok(shared)
+}
@@ -40,6 +40,21 @@ pub fn credentials_path() -> std::path::PathBuf {
     asset_dir().join("credentials.json")
 }
 
+/// Credentials for a remote other than the default one, per `host[:port]`.
+pub fn remote_credentials_path(host: &str) -> std::path::PathBuf {
+    let name: String = host
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    asset_dir().join(format!("credentials-{name}.json"))
+}
+
 pub fn trusted_keys_path() -> std::path::PathBuf {
     asset_dir().join("trusted_ed25519_public_keys.json")
 }
@@ -0,0 +1,267 @@
+//! Commit messages in a project's convention, by default Conventional
+//! Commits scoped to the task key (`fix(VK-42): handle expired sessions`),
+//! so changelog tooling keeps working on commits written by agents.
+
+/// Template applied when a project enables the convention without its own.
+pub const DEFAULT_COMMIT_TEMPLATE: &str = "{type}({task_key}): {summary}";
+
+const PLACEHOLDERS: &[&str] = &["type", "task_key", "summary"];
+
+/// Header of a Conventional Commits message, `type(scope)!: description`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalHeader<'a> {
+    pub kind: &'a str,
+    pub scope: Option<&'a str>,
+    pub breaking: bool,
+    pub description: &'a str,
+}
+
+pub fn parse_conventional_header(header: &str) -> Option<ConventionalHeader<'_>> {
+    let (prefix, description) = header.split_once(": ")?;
+    let description = description.trim();
+    if description.is_empty() {
+        return None;
+    }
+    let (prefix, breaking) = match prefix.strip_suffix('!') {
+        Some(prefix) => (prefix, true),
+        None => (prefix, false),
+    };
+    let (kind, scope) = match prefix.split_once('(') {
+        Some((kind, rest)) => {
+            let scope = rest.strip_suffix(')')?;
+            if scope.is_empty() || scope.contains(['(', ')']) {
+                return None;
+            }
+            (kind, Some(scope))
+        }
+        None => (prefix, None),
+    };
+    if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_lowercase()) {
+        return None;
+    }
+    Some(ConventionalHeader {
+        kind,
+        scope,
+        breaking,
+        description,
+    })
+}
+
+/// Check that `template` only uses known placeholders, includes
+/// `{summary}`, and renders Conventional Commits headers with and without a
+/// task key.
+pub fn validate_commit_template(template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            return Err("Unclosed `{` in commit template".to_string());
+        };
+        let placeholder = &rest[start + 1..start + end];
+        if !PLACEHOLDERS.contains(&placeholder) {
+            return Err(format!(
+                "Unknown placeholder `{{{placeholder}}}` in commit template; use one of {}",
+                PLACEHOLDERS
+                    .iter()
+                    .map(|p| format!("{{{p}}}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    if !template.contains("{summary}") {
+        return Err("Commit template must include {summary}".to_string());
+    }
+    if template.contains('\n') {
+        return Err("Commit template must be a single line".to_string());
+    }
+
+    for task_key in [Some("VK-42"), None] {
+        let sample = apply_commit_template(template, "Fix login redirect", task_key);
+        if parse_conventional_header(&sample).is_none() {
+            return Err(format!(
+                "Commit template doesn't render a Conventional Commits header, e.g. `{sample}`"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `message`, e.g. one an agent committed itself, already has the
+/// header `template` would give it.
+pub fn follows_commit_template(template: &str, message: &str, task_key: Option<&str>) -> bool {
+    let header = message.trim().lines().next().unwrap_or_default().trim();
+    parse_conventional_header(header).is_some()
+        && apply_commit_template(template, header, task_key) == header
+}
+
+/// Rewrite `message` with `template`. Its first line becomes `{summary}`,
+/// with the type taken from a Conventional Commits prefix it already has or
+/// guessed from its first word; the rest is kept as the body. A breaking
+/// change `!` is carried over. Without a task key, the `()` it would have
+/// filled is dropped.
+pub fn apply_commit_template(template: &str, message: &str, task_key: Option<&str>) -> String {
+    let message = message.trim();
+    let (header, body) = match message.split_once('\n') {
+        Some((header, body)) => (header.trim(), body.trim()),
+        None => (message, ""),
+    };
+    let (kind, breaking, summary) = match parse_conventional_header(header) {
+        Some(parsed) => (parsed.kind, parsed.breaking, parsed.description),
+        None => (infer_type(header), false, header),
+    };
+
+    let mut rendered = String::with_capacity(template.len() + summary.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        rest = match (&rest[start + 1..start + end], task_key) {
+            ("type", _) => {
+                rendered.push_str(kind);
+                &rest[start + end + 1..]
+            }
+            ("summary", _) => {
+                rendered.push_str(summary);
+                &rest[start + end + 1..]
+            }
+            ("task_key", Some(task_key)) => {
+                rendered.push_str(task_key);
+                &rest[start + end + 1..]
+            }
+            ("task_key", None) => {
+                let after = &rest[start + end + 1..];
+                // Drop brackets that would be left empty.
+                match (rendered.chars().last(), after.chars().next()) {
+                    (Some('('), Some(')')) | (Some('['), Some(']')) => {
+                        rendered.pop();
+                        &after[1..]
+                    }
+                    _ => after,
+                }
+            }
+            _ => &rest[start + end + 1..],
+        };
+    }
+    rendered.push_str(rest);
+    let mut rendered = rendered.trim().to_string();
+    if breaking && let Some(colon) = rendered.find(": ") {
+        rendered.insert(colon, '!');
+    }
+
+    if body.is_empty() {
+        rendered
+    } else {
+        format!("{rendered}\n\n{body}")
+    }
+}
+
+/// Conventional Commits type for a summary that doesn't state one, from its
+/// first word.
+fn infer_type(summary: &str) -> &'static str {
+    let first_word = summary
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match first_word.as_str() {
+        "fix" | "fixes" | "fixed" | "resolve" | "resolves" | "resolved" => "fix",
+        "add" | "adds" | "added" | "implement" | "implements" | "implemented" | "introduce"
+        | "introduces" | "introduced" | "support" | "supports" => "feat",
+        "refactor" | "refactors" | "refactored" | "simplify" | "simplifies" | "simplified"
+        | "extract" | "extracts" | "extracted" | "rename" | "renames" | "renamed" => "refactor",
+        "document" | "documents" | "documented" | "docs" => "docs",
+        "test" | "tests" | "tested" => "test",
+        _ => "chore",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_default_template() {
+        assert_eq!(
+            apply_commit_template(
+                DEFAULT_COMMIT_TEMPLATE,
+                "Fix login redirect\n\nKeeps the return URL.",
+                Some("VK-42")
+            ),
+            "fix(VK-42): Fix login redirect\n\nKeeps the return URL."
+        );
+        assert_eq!(
+            apply_commit_template(DEFAULT_COMMIT_TEMPLATE, "Update deps", None),
+            "chore: Update deps"
+        );
+    }
+
+    #[test]
+    fn test_existing_conventional_type_is_kept() {
+        assert_eq!(
+            apply_commit_template(
+                DEFAULT_COMMIT_TEMPLATE,
+                "perf(api): cache sessions",
+                Some("VK-42")
+            ),
+            "perf(VK-42): cache sessions"
+        );
+    }
+
+    #[test]
+    fn test_breaking_marker_is_kept() {
+        assert_eq!(
+            apply_commit_template(
+                DEFAULT_COMMIT_TEMPLATE,
+                "feat!: drop the v1 API\n\nBREAKING CHANGE: clients must use v2.",
+                Some("VK-42")
+            ),
+            "feat(VK-42)!: drop the v1 API\n\nBREAKING CHANGE: clients must use v2."
+        );
+        assert_eq!(
+            apply_commit_template(
+                "{type}: {summary} [{task_key}]",
+                "feat(api)!: drop v1",
+                None
+            ),
+            "feat!: drop v1"
+        );
+    }
+
+    #[test]
+    fn test_follows_commit_template() {
+        let template = DEFAULT_COMMIT_TEMPLATE;
+        assert!(follows_commit_template(
+            template,
+            "fix(VK-42): handle expiry",
+            Some("VK-42")
+        ));
+        assert!(follows_commit_template(
+            template,
+            "feat(VK-42)!: drop v1\n\nBody.",
+            Some("VK-42")
+        ));
+        assert!(!follows_commit_template(
+            template,
+            "fix: handle expiry",
+            Some("VK-42")
+        ));
+        assert!(!follows_commit_template(template, "Handle expiry", None));
+        assert!(follows_commit_template(
+            template,
+            "fix: handle expiry",
+            None
+        ));
+    }
+
+    #[test]
+    fn test_validate_commit_template() {
+        assert!(validate_commit_template(DEFAULT_COMMIT_TEMPLATE).is_ok());
+        assert!(validate_commit_template("{type}: {summary} [{task_key}]").is_ok());
+        assert!(validate_commit_template("{type}({task_key})").is_err());
+        assert!(validate_commit_template("[{task_key}] {summary}").is_err());
+        assert!(validate_commit_template("{type}({scope}): {summary}").is_err());
+    }
+}
@@ -6,10 +6,13 @@ pub mod approvals;
 pub mod assets;
 pub mod browser;
 pub mod command_ext;
+pub mod commit_message;
 pub mod diff;
 pub mod execution_logs;
 pub mod http_headers;
 pub mod jwt;
+pub mod log_buffer;
+pub mod log_filter;
 pub mod log_msg;
 pub mod msg_store;
 pub mod path;
@@ -19,9 +22,12 @@ pub mod response;
 pub mod sentry;
 pub mod shell;
 pub mod stream_lines;
+pub mod task_trailer;
 pub mod text;
 pub mod tokio;
 pub mod version;
+pub mod webhooks;
+pub mod ws;
 
 /// Cache for WSL2 detection result
 static WSL2_CACHE: OnceLock<bool> = OnceLock::new();
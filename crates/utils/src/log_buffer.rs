@@ -0,0 +1,73 @@
+//! In-memory ring buffer of recent log output, for diagnostic bundles.
+//!
+//! Install by adding a `fmt` layer that writes to [`RecentLogs`]; read back
+//! with [`recent_lines`].
+
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{Mutex, OnceLock},
+};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Number of lines kept; older lines are dropped.
+const CAPACITY: usize = 5000;
+
+static LINES: OnceLock<Mutex<VecDeque<String>>> = OnceLock::new();
+
+fn lines() -> &'static Mutex<VecDeque<String>> {
+    LINES.get_or_init(|| Mutex::new(VecDeque::with_capacity(CAPACITY)))
+}
+
+/// The buffered lines, oldest first.
+pub fn recent_lines() -> Vec<String> {
+    lines()
+        .lock()
+        .map(|lines| lines.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// `MakeWriter` for a `fmt` layer that feeds the ring buffer.
+#[derive(Clone, Copy, Default)]
+pub struct RecentLogs;
+
+impl<'a> MakeWriter<'a> for RecentLogs {
+    type Writer = RecentLogsWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RecentLogsWriter(Vec::new())
+    }
+}
+
+/// Collects one formatted event and appends it to the buffer when dropped.
+pub struct RecentLogsWriter(Vec<u8>);
+
+impl io::Write for RecentLogsWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for RecentLogsWriter {
+    fn drop(&mut self) {
+        if self.0.is_empty() {
+            return;
+        }
+        let text = String::from_utf8_lossy(&self.0);
+        let Ok(mut lines) = lines().lock() else {
+            return;
+        };
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            if lines.len() == CAPACITY {
+                lines.pop_front();
+            }
+            lines.push_back(line.to_string());
+        }
+    }
+}
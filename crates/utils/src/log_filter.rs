@@ -0,0 +1,69 @@
+//! Runtime-reloadable tracing filter.
+//!
+//! Binaries wrap their `EnvFilter` with [`reloadable`] when building the
+//! subscriber; afterwards [`set`] swaps in new directives (e.g.
+//! `info,utils::ws=trace`) without a restart, and [`reset`] restores the
+//! startup filter.
+
+use std::sync::OnceLock;
+
+use thiserror::Error;
+use tracing_subscriber::{EnvFilter, Registry, reload};
+
+struct FilterHandle {
+    handle: reload::Handle<EnvFilter, Registry>,
+    default_directives: String,
+}
+
+static HANDLE: OnceLock<FilterHandle> = OnceLock::new();
+
+#[derive(Debug, Error)]
+pub enum LogFilterError {
+    #[error("log filter is not reloadable in this process")]
+    NotInstalled,
+    #[error("invalid log filter: {0}")]
+    Invalid(#[from] tracing_subscriber::filter::ParseError),
+    #[error("failed to reload log filter: {0}")]
+    Reload(#[from] reload::Error),
+}
+
+/// Build the process's filter from `directives` and keep a handle to it.
+/// Only the first call installs a handle.
+pub fn reloadable(
+    directives: &str,
+) -> Result<reload::Layer<EnvFilter, Registry>, tracing_subscriber::filter::ParseError> {
+    let (layer, handle) = reload::Layer::new(EnvFilter::try_new(directives)?);
+    let _ = HANDLE.set(FilterHandle {
+        handle,
+        default_directives: directives.to_string(),
+    });
+    Ok(layer)
+}
+
+/// The active filter directives.
+pub fn current() -> Result<String, LogFilterError> {
+    let installed = HANDLE.get().ok_or(LogFilterError::NotInstalled)?;
+    Ok(installed.handle.with_current(|filter| filter.to_string())?)
+}
+
+/// The directives the process started with.
+pub fn default_directives() -> Option<&'static str> {
+    HANDLE
+        .get()
+        .map(|installed| installed.default_directives.as_str())
+}
+
+/// Replace the active filter. Invalid directives leave it unchanged.
+pub fn set(directives: &str) -> Result<(), LogFilterError> {
+    let installed = HANDLE.get().ok_or(LogFilterError::NotInstalled)?;
+    let filter = EnvFilter::try_new(directives)?;
+    installed.handle.reload(filter)?;
+    tracing::info!(filter = directives, "Log filter updated");
+    Ok(())
+}
+
+/// Restore the startup filter.
+pub fn reset() -> Result<(), LogFilterError> {
+    let installed = HANDLE.get().ok_or(LogFilterError::NotInstalled)?;
+    set(&installed.default_directives)
+}
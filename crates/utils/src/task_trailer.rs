@@ -0,0 +1,73 @@
+//! `Vibe-Kanban-Task: <issue id>` trailers in commit messages and pull
+//! request bodies, so a pull request opened outside the app can still be
+//! linked back to the shared task it implements.
+
+use uuid::Uuid;
+
+pub const TASK_TRAILER_KEY: &str = "Vibe-Kanban-Task";
+
+pub fn task_trailer(issue_id: Uuid) -> String {
+    format!("{TASK_TRAILER_KEY}: {issue_id}")
+}
+
+/// `message` with the trailer for `issue_id` appended as its own paragraph,
+/// unless it already has it.
+pub fn with_task_trailer(message: &str, issue_id: Uuid) -> String {
+    if parse_task_trailers(message).contains(&issue_id) {
+        return message.to_string();
+    }
+    let message = message.trim_end();
+    if message.is_empty() {
+        task_trailer(issue_id)
+    } else {
+        format!("{message}\n\n{}", task_trailer(issue_id))
+    }
+}
+
+/// Issue IDs from every task trailer line in `text`, in order and without
+/// duplicates. Like git, the key is matched case-insensitively; lines whose
+/// value isn't a UUID are ignored.
+pub fn parse_task_trailers(text: &str) -> Vec<Uuid> {
+    let mut issue_ids = Vec::new();
+    for line in text.lines() {
+        let Some((key, value)) = line.trim().split_once(':') else {
+            continue;
+        };
+        if !key.trim_end().eq_ignore_ascii_case(TASK_TRAILER_KEY) {
+            continue;
+        }
+        if let Ok(issue_id) = Uuid::parse_str(value.trim())
+            && !issue_ids.contains(&issue_id)
+        {
+            issue_ids.push(issue_id);
+        }
+    }
+    issue_ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let issue_id = Uuid::new_v4();
+        let message = with_task_trailer("Fix login\n\nDetails.\n", issue_id);
+        assert_eq!(
+            message,
+            format!("Fix login\n\nDetails.\n\nVibe-Kanban-Task: {issue_id}")
+        );
+        assert_eq!(with_task_trailer(&message, issue_id), message);
+        assert_eq!(parse_task_trailers(&message), vec![issue_id]);
+    }
+
+    #[test]
+    fn test_parse_ignores_other_lines() {
+        let issue_id = Uuid::new_v4();
+        let body = format!(
+            "## Summary\nSee Vibe-Kanban-Task: below\n\nvibe-kanban-task:  {issue_id}\n\
+             Vibe-Kanban-Task: not-a-uuid\nCo-authored-by: someone"
+        );
+        assert_eq!(parse_task_trailers(&body), vec![issue_id]);
+    }
+}
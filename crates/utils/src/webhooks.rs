@@ -0,0 +1,270 @@
+//! Signature verification shared by inbound webhook receivers.
+//!
+//! Covers the two schemes our providers use: a bare HMAC-SHA256 of the body
+//! (GitHub's `X-Hub-Signature-256`) and Svix-style signed messages (Clerk
+//! and other Svix senders). Comparisons are constant-time. [`ReplayCache`]
+//! rejects deliveries that were already accepted.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// How far a signed timestamp may drift from our clock.
+pub const DEFAULT_TOLERANCE: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WebhookError {
+    #[error("missing or malformed signature")]
+    MalformedSignature,
+    #[error("invalid webhook secret")]
+    InvalidSecret,
+    #[error("signature mismatch")]
+    SignatureMismatch,
+    #[error("timestamp outside tolerance")]
+    StaleTimestamp,
+    #[error("delivery already processed")]
+    Replayed,
+}
+
+fn hmac_sha256(secret: &[u8], parts: &[&[u8]]) -> Result<Vec<u8>, WebhookError> {
+    let mut mac = HmacSha256::new_from_slice(secret).map_err(|_| WebhookError::InvalidSecret)?;
+    for part in parts {
+        mac.update(part);
+    }
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// Verify a hex HMAC-SHA256 of `payload`. `prefix` is stripped from the
+/// header first, e.g. `"sha256="` for GitHub.
+pub fn verify_hmac_sha256_hex(
+    secret: &[u8],
+    prefix: &str,
+    signature_header: &str,
+    payload: &[u8],
+) -> Result<(), WebhookError> {
+    let expected = signature_header
+        .strip_prefix(prefix)
+        .and_then(|hex_signature| hex::decode(hex_signature).ok())
+        .ok_or(WebhookError::MalformedSignature)?;
+    let computed = hmac_sha256(secret, &[payload])?;
+    if bool::from(computed.ct_eq(&expected)) {
+        Ok(())
+    } else {
+        Err(WebhookError::SignatureMismatch)
+    }
+}
+
+/// Verify a Svix-style delivery: `signature_header` holds space-separated
+/// `v1,<base64>` signatures over `{id}.{timestamp}.{payload}`, keyed with the
+/// base64 part of a `whsec_` secret. Any matching signature is accepted, so
+/// secrets can be rotated.
+pub fn verify_svix(
+    secret: &str,
+    message_id: &str,
+    timestamp: &str,
+    signature_header: &str,
+    payload: &[u8],
+    tolerance: Duration,
+) -> Result<(), WebhookError> {
+    let key = BASE64
+        .decode(secret.strip_prefix("whsec_").unwrap_or(secret))
+        .map_err(|_| WebhookError::InvalidSecret)?;
+    let seconds: i64 = timestamp
+        .parse()
+        .map_err(|_| WebhookError::MalformedSignature)?;
+    let signed_at = DateTime::from_timestamp(seconds, 0).ok_or(WebhookError::MalformedSignature)?;
+    check_timestamp(signed_at, Utc::now(), tolerance)?;
+
+    let computed = hmac_sha256(
+        &key,
+        &[
+            message_id.as_bytes(),
+            b".",
+            timestamp.as_bytes(),
+            b".",
+            payload,
+        ],
+    )?;
+
+    let mut saw_signature = false;
+    for candidate in signature_header.split_whitespace() {
+        let Some(encoded) = candidate.strip_prefix("v1,") else {
+            continue;
+        };
+        let Ok(expected) = BASE64.decode(encoded) else {
+            continue;
+        };
+        saw_signature = true;
+        if bool::from(computed.ct_eq(&expected)) {
+            return Ok(());
+        }
+    }
+    Err(if saw_signature {
+        WebhookError::SignatureMismatch
+    } else {
+        WebhookError::MalformedSignature
+    })
+}
+
+/// Reject timestamps more than `tolerance` away from `now`, either way.
+pub fn check_timestamp(
+    signed_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    tolerance: Duration,
+) -> Result<(), WebhookError> {
+    let drift = (now - signed_at).abs().to_std().unwrap_or(Duration::MAX);
+    if drift > tolerance {
+        return Err(WebhookError::StaleTimestamp);
+    }
+    Ok(())
+}
+
+/// Bounded memory of recently accepted delivery ids. Entries expire after
+/// `ttl`; the oldest is evicted once `capacity` is reached.
+pub struct ReplayCache {
+    ttl: Duration,
+    capacity: usize,
+    seen: Mutex<SeenDeliveries>,
+}
+
+#[derive(Default)]
+struct SeenDeliveries {
+    ids: HashMap<String, Instant>,
+    order: VecDeque<(String, Instant)>,
+}
+
+impl ReplayCache {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            seen: Mutex::new(SeenDeliveries::default()),
+        }
+    }
+
+    /// Record `delivery_id`, failing if it was already recorded within the TTL.
+    pub fn check_and_insert(&self, delivery_id: &str) -> Result<(), WebhookError> {
+        self.check_and_insert_at(delivery_id, Instant::now())
+    }
+
+    fn check_and_insert_at(&self, delivery_id: &str, now: Instant) -> Result<(), WebhookError> {
+        let mut guard = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        let SeenDeliveries { ids, order } = &mut *guard;
+
+        while let Some((id, seen_at)) = order.front() {
+            if now.duration_since(*seen_at) < self.ttl && order.len() < self.capacity {
+                break;
+            }
+            if ids.get(id) == Some(seen_at) {
+                ids.remove(id);
+            }
+            order.pop_front();
+        }
+
+        if ids.contains_key(delivery_id) {
+            return Err(WebhookError::Replayed);
+        }
+        ids.insert(delivery_id.to_string(), now);
+        order.push_back((delivery_id.to_string(), now));
+        Ok(())
+    }
+}
+
+impl Default for ReplayCache {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(24 * 60 * 60), 10_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hmac_hex_requires_prefix_and_matching_signature() {
+        let signature = format!(
+            "sha256={}",
+            hex::encode(hmac_sha256(b"test-secret", &[b"payload"]).unwrap())
+        );
+
+        assert_eq!(
+            verify_hmac_sha256_hex(b"test-secret", "sha256=", &signature, b"payload"),
+            Ok(())
+        );
+        assert_eq!(
+            verify_hmac_sha256_hex(b"test-secret", "sha256=", &signature, b"tampered"),
+            Err(WebhookError::SignatureMismatch)
+        );
+        assert_eq!(
+            verify_hmac_sha256_hex(b"test-secret", "sha256=", &signature[7..], b"payload"),
+            Err(WebhookError::MalformedSignature)
+        );
+        assert_eq!(
+            verify_hmac_sha256_hex(b"test-secret", "sha256=", "sha256=zz", b"payload"),
+            Err(WebhookError::MalformedSignature)
+        );
+    }
+
+    #[test]
+    fn svix_accepts_any_matching_signature_within_tolerance() {
+        let key = b"svix-test-key";
+        let secret = format!("whsec_{}", BASE64.encode(key));
+        let timestamp = Utc::now().timestamp().to_string();
+        let signed = hmac_sha256(key, &[b"msg_1.", timestamp.as_bytes(), b".{}"]).unwrap();
+        let header = format!("v1,{} v1,{}", BASE64.encode(b"old"), BASE64.encode(signed));
+
+        assert_eq!(
+            verify_svix(
+                &secret,
+                "msg_1",
+                &timestamp,
+                &header,
+                b"{}",
+                DEFAULT_TOLERANCE
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            verify_svix(
+                &secret,
+                "msg_2",
+                &timestamp,
+                &header,
+                b"{}",
+                DEFAULT_TOLERANCE
+            ),
+            Err(WebhookError::SignatureMismatch)
+        );
+        assert_eq!(
+            verify_svix(&secret, "msg_1", "0", &header, b"{}", DEFAULT_TOLERANCE),
+            Err(WebhookError::StaleTimestamp)
+        );
+    }
+
+    #[test]
+    fn replay_cache_rejects_repeats_until_expiry() {
+        let cache = ReplayCache::new(Duration::from_secs(60), 2);
+        let start = Instant::now();
+
+        assert_eq!(cache.check_and_insert_at("a", start), Ok(()));
+        assert_eq!(
+            cache.check_and_insert_at("a", start),
+            Err(WebhookError::Replayed)
+        );
+        assert_eq!(
+            cache.check_and_insert_at("a", start + Duration::from_secs(61)),
+            Ok(())
+        );
+    }
+}
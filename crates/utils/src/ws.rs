@@ -0,0 +1,120 @@
+//! Close codes for server-initiated WebSocket shutdowns.
+//!
+//! A bare close leaves the client guessing whether to reconnect, so streams
+//! that end for a known reason close with a [`WsCloseCode`] from the
+//! 4000-4999 range reserved for applications. Clients map the code to a
+//! [`ReconnectHint`]; unknown codes should be treated as a transient drop.
+
+use std::io;
+
+use axum::extract::ws::{CloseFrame, Message};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WsCloseCode {
+    /// The credentials the connection was opened with are no longer valid.
+    AuthExpired,
+    /// The connection fell behind and missed updates it can't replay.
+    BacklogDropped,
+    /// The server is shutting down or restarting.
+    ServerRestarting,
+}
+
+/// How a client should recover from a [`WsCloseCode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectHint {
+    /// Get fresh credentials before reconnecting.
+    Reauthenticate,
+    /// Reconnect immediately; the new connection starts from a full snapshot.
+    Resync,
+    /// Reconnect after a delay.
+    Backoff,
+}
+
+impl WsCloseCode {
+    pub const ALL: [WsCloseCode; 3] = [
+        WsCloseCode::AuthExpired,
+        WsCloseCode::BacklogDropped,
+        WsCloseCode::ServerRestarting,
+    ];
+
+    pub const fn code(self) -> u16 {
+        match self {
+            WsCloseCode::AuthExpired => 4001,
+            WsCloseCode::BacklogDropped => 4002,
+            WsCloseCode::ServerRestarting => 4003,
+        }
+    }
+
+    /// Close reason sent alongside the code.
+    pub const fn reason(self) -> &'static str {
+        match self {
+            WsCloseCode::AuthExpired => "auth_expired",
+            WsCloseCode::BacklogDropped => "backlog_dropped",
+            WsCloseCode::ServerRestarting => "server_restarting",
+        }
+    }
+
+    pub const fn reconnect_hint(self) -> ReconnectHint {
+        match self {
+            WsCloseCode::AuthExpired => ReconnectHint::Reauthenticate,
+            WsCloseCode::BacklogDropped => ReconnectHint::Resync,
+            WsCloseCode::ServerRestarting => ReconnectHint::Backoff,
+        }
+    }
+
+    pub fn from_code(code: u16) -> Option<Self> {
+        Self::ALL.into_iter().find(|close| close.code() == code)
+    }
+
+    pub fn close_message(self) -> Message {
+        Message::Close(Some(CloseFrame {
+            code: self.code(),
+            reason: self.reason().into(),
+        }))
+    }
+
+    /// The code to close with when a stream fails with `error`, if the
+    /// failure is one the client can recover from.
+    pub fn for_stream_error(error: &io::Error) -> Option<Self> {
+        error
+            .get_ref()
+            .is_some_and(|inner| inner.is::<BacklogDropped>())
+            .then_some(WsCloseCode::BacklogDropped)
+    }
+}
+
+/// A subscriber fell behind its broadcast channel and `0` messages were
+/// dropped. Streams surface this as an error instead of skipping ahead so the
+/// client resyncs rather than silently keeping a stale view.
+#[derive(Debug, Error)]
+#[error("subscriber lagged; {0} messages dropped")]
+pub struct BacklogDropped(pub u64);
+
+impl From<BacklogDropped> for io::Error {
+    fn from(error: BacklogDropped) -> Self {
+        io::Error::other(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_and_lag_maps_to_backlog_dropped() {
+        for close in WsCloseCode::ALL {
+            assert_eq!(WsCloseCode::from_code(close.code()), Some(close));
+        }
+        assert_eq!(WsCloseCode::from_code(1000), None);
+
+        assert_eq!(
+            WsCloseCode::for_stream_error(&BacklogDropped(3).into()),
+            Some(WsCloseCode::BacklogDropped)
+        );
+        assert_eq!(
+            WsCloseCode::for_stream_error(&io::Error::other("boom")),
+            None
+        );
+    }
+}
@@ -15,7 +15,7 @@ use git::{GitService, GitServiceError};
 use thiserror::Error;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
-use worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager};
+use worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager, WorktreePool};
 
 #[derive(Debug, Clone)]
 pub struct RepoWorkspaceInput {
@@ -54,6 +54,8 @@ pub enum WorkspaceError {
     NoRepositories,
     #[error("Partial workspace creation failed: {0}")]
     PartialCreation(String),
+    #[error("Failed to clean up worktrees: {0}")]
+    CleanupFailed(String),
 }
 
 /// Info about a single repo's worktree within a workspace
@@ -323,6 +325,7 @@ impl WorkspaceManager {
                 &worktree_path,
                 &input.target_branch,
                 true,
+                &input.repo.sparse_checkout_dirs(),
             )
             .await
             {
@@ -403,8 +406,13 @@ impl WorkspaceManager {
             );
 
             if git.check_branch_exists(&repo.path, branch_name)? {
-                WorktreeManager::ensure_worktree_exists(&repo.path, branch_name, &worktree_path)
-                    .await?;
+                WorktreeManager::ensure_worktree_exists(
+                    &repo.path,
+                    branch_name,
+                    &worktree_path,
+                    &repo.sparse_checkout_dirs(),
+                )
+                .await?;
             } else {
                 info!(
                     "Workspace branch '{}' missing in repo '{}'; creating from target branch '{}'",
@@ -416,6 +424,7 @@ impl WorkspaceManager {
                     &worktree_path,
                     &input.target_branch,
                     true,
+                    &repo.sparse_checkout_dirs(),
                 )
                 .await?;
             }
@@ -424,22 +433,25 @@ impl WorkspaceManager {
         Ok(())
     }
 
-    /// Clean up all worktrees in a workspace
+    /// Clean up all worktrees in a workspace, pooling them for reuse where
+    /// the pool has room
     pub async fn cleanup_workspace(
         workspace_dir: &Path,
         repos: &[Repo],
     ) -> Result<(), WorkspaceError> {
         info!("Cleaning up workspace at {}", workspace_dir.display());
 
-        let cleanup_data: Vec<WorktreeCleanup> = repos
-            .iter()
-            .map(|repo| {
-                let worktree_path = workspace_dir.join(&repo.name);
-                WorktreeCleanup::new(worktree_path, Some(repo.path.clone()))
-            })
-            .collect();
-
-        WorktreeManager::batch_cleanup_worktrees(&cleanup_data).await?;
+        // Keep going past failures so one broken worktree doesn't leave the
+        // others behind, then report all of them.
+        let mut failures = Vec::new();
+        for repo in repos {
+            let worktree_path = workspace_dir.join(&repo.name);
+            let cleanup = WorktreeCleanup::new(worktree_path, Some(repo.path.clone()));
+            if let Err(e) = WorktreeManager::release_worktree(&cleanup).await {
+                error!("Failed to cleanup worktree for repo '{}': {}", repo.name, e);
+                failures.push(format!("{}: {e}", repo.name));
+            }
+        }
 
         // Remove the workspace directory itself
         if workspace_dir.exists()
@@ -452,7 +464,11 @@ impl WorkspaceManager {
             );
         }
 
-        Ok(())
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(WorkspaceError::CleanupFailed(failures.join("; ")))
+        }
     }
 
     /// Get the base directory for workspaces (same as worktree base dir)
@@ -585,7 +601,7 @@ impl WorkspaceManager {
             };
 
             let path = entry.path();
-            if !path.is_dir() {
+            if !path.is_dir() || WorktreePool::is_pool_dir(&path) {
                 continue;
             }
 
@@ -648,6 +664,10 @@ impl WorkspaceManager {
             );
         }
 
-        Ok(())
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(WorkspaceError::CleanupFailed(failures.join("; ")))
+        }
     }
 }
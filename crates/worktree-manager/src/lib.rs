@@ -1,3 +1,5 @@
 mod worktree_manager;
+mod worktree_pool;
 
 pub use worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager};
+pub use worktree_pool::{PooledWorktree, WorktreePool, WorktreePoolPolicy, WorktreePoolReport};
@@ -12,6 +12,8 @@ use thiserror::Error;
 use tracing::{debug, info, trace};
 use utils::{path::normalize_macos_private_alias, shell::resolve_executable_path};
 
+use crate::worktree_pool::WorktreePool;
+
 // Global synchronization for worktree creation to prevent race conditions
 static WORKTREE_CREATION_LOCKS: LazyLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
@@ -56,13 +58,15 @@ impl WorktreeManager {
         let _ = WORKSPACE_DIR_OVERRIDE.set(path);
     }
 
-    /// Create a worktree with a new branch
+    /// Create a worktree with a new branch. A non-empty `sparse_checkout`
+    /// checks out only those directories.
     pub async fn create_worktree(
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
         base_branch: &str,
         create_branch: bool,
+        sparse_checkout: &[String],
     ) -> Result<(), WorktreeError> {
         if create_branch {
             let repo_path_owned = repo_path.to_path_buf();
@@ -80,7 +84,56 @@ impl WorktreeManager {
             .map_err(|e| WorktreeError::TaskJoin(format!("Task join error: {e}")))??;
         }
 
-        Self::ensure_worktree_exists(repo_path, branch_name, worktree_path).await
+        // Pooled worktrees have everything checked out, so sparse ones are
+        // always created fresh.
+        if create_branch
+            && sparse_checkout.is_empty()
+            && Self::reuse_pooled_worktree(repo_path, branch_name, worktree_path).await
+        {
+            return Ok(());
+        }
+
+        Self::ensure_worktree_exists(repo_path, branch_name, worktree_path, sparse_checkout).await
+    }
+
+    fn creation_lock(worktree_path: &Path) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = WORKTREE_CREATION_LOCKS.lock().unwrap();
+        locks
+            .entry(worktree_path.to_string_lossy().to_string())
+            .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
+            .clone()
+    }
+
+    /// Check out a new branch in a pooled worktree instead of creating one,
+    /// unless something is already at `worktree_path`.
+    async fn reuse_pooled_worktree(
+        repo_path: &Path,
+        branch_name: &str,
+        worktree_path: &Path,
+    ) -> bool {
+        let lock = Self::creation_lock(worktree_path);
+        let _guard = lock.lock().await;
+        !worktree_path.exists() && WorktreePool::take(repo_path, branch_name, worktree_path).await
+    }
+
+    /// Hand a worktree that's no longer needed to the pool for reuse, or
+    /// clean it up if the pool doesn't take it.
+    pub async fn release_worktree(worktree: &WorktreeCleanup) -> Result<(), WorktreeError> {
+        if let Some(repo_path) = &worktree.git_repo_path
+            && WorktreePool::enabled()
+        {
+            let lock = Self::creation_lock(&worktree.worktree_path);
+            let _guard = lock.lock().await;
+            if Self::is_worktree_properly_set_up(repo_path, &worktree.worktree_path)
+                .await
+                .unwrap_or(false)
+                && WorktreePool::park(repo_path, &worktree.worktree_path).await
+            {
+                return Ok(());
+            }
+        }
+
+        Self::cleanup_worktree(worktree).await
     }
 
     /// Ensure worktree exists, recreating if necessary with proper synchronization
@@ -89,6 +142,7 @@ impl WorktreeManager {
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+        sparse_checkout: &[String],
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
 
@@ -112,7 +166,8 @@ impl WorktreeManager {
 
         // If worktree doesn't exist or isn't properly set up, recreate it
         info!("Worktree needs recreation at path: {}", path_str);
-        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path).await
+        Self::recreate_worktree_internal(repo_path, branch_name, worktree_path, sparse_checkout)
+            .await
     }
 
     /// Internal worktree recreation function (always recreates)
@@ -120,6 +175,7 @@ impl WorktreeManager {
         repo_path: &Path,
         branch_name: &str,
         worktree_path: &Path,
+        sparse_checkout: &[String],
     ) -> Result<(), WorktreeError> {
         let path_str = worktree_path.to_string_lossy().to_string();
         let branch_name_owned = branch_name.to_string();
@@ -148,6 +204,7 @@ impl WorktreeManager {
             &branch_name_owned,
             &worktree_path_owned,
             &path_str,
+            sparse_checkout,
         )
         .await
     }
@@ -265,7 +322,7 @@ impl WorktreeManager {
     }
 
     /// Async version of comprehensive cleanup to avoid blocking the main runtime
-    async fn comprehensive_worktree_cleanup_async(
+    pub(crate) async fn comprehensive_worktree_cleanup_async(
         git_repo_path: &Path,
         worktree_path: &Path,
     ) -> Result<(), WorktreeError> {
@@ -305,16 +362,30 @@ impl WorktreeManager {
         branch_name: &str,
         worktree_path: &Path,
         path_str: &str,
+        sparse_checkout: &[String],
     ) -> Result<(), WorktreeError> {
         let git_repo_path = git_repo_path.to_path_buf();
         let branch_name = branch_name.to_string();
         let worktree_path = worktree_path.to_path_buf();
         let path_str = path_str.to_string();
+        let sparse_checkout = sparse_checkout.to_vec();
 
         tokio::task::spawn_blocking(move || -> Result<(), WorktreeError> {
             // Prefer git CLI for worktree add to inherit sparse-checkout semantics
             let git_service = GitService::new();
-            match git_service.add_worktree(&git_repo_path, &worktree_path, &branch_name, false) {
+            let add_worktree = || {
+                if sparse_checkout.is_empty() {
+                    git_service.add_worktree(&git_repo_path, &worktree_path, &branch_name, false)
+                } else {
+                    git_service.add_sparse_worktree(
+                        &git_repo_path,
+                        &worktree_path,
+                        &branch_name,
+                        &sparse_checkout,
+                    )
+                }
+            };
+            match add_worktree() {
                 Ok(()) => {
                     if !worktree_path.exists() {
                         return Err(WorktreeError::Repository(format!(
@@ -339,12 +410,7 @@ impl WorktreeManager {
                     if worktree_path.exists() {
                         std::fs::remove_dir_all(&worktree_path).map_err(WorktreeError::Io)?;
                     }
-                    if let Err(e2) = git_service.add_worktree(
-                        &git_repo_path,
-                        &worktree_path,
-                        &branch_name,
-                        false,
-                    ) {
+                    if let Err(e2) = add_worktree() {
                         return Err(WorktreeError::GitService(e2));
                     }
                     if !worktree_path.exists() {
@@ -553,6 +619,7 @@ async fn create_worktree_when_repo_path_is_a_worktree() {
         &base_worktree_path,
         "main",
         true,
+        &[],
     )
     .await
     .unwrap();
@@ -565,6 +632,7 @@ async fn create_worktree_when_repo_path_is_a_worktree() {
         &child_worktree_path,
         "main",
         true,
+        &[],
     )
     .await
     .unwrap();
@@ -575,6 +643,7 @@ async fn create_worktree_when_repo_path_is_a_worktree() {
         &base_worktree_path,
         "wt-child-branch",
         &child_worktree_path,
+        &[],
     )
     .await
     .unwrap();
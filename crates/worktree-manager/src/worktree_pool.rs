@@ -0,0 +1,306 @@
+//! Worktrees kept after their workspace is deleted, so the next workspace on
+//! the same repository can check its branch out in one instead of
+//! materializing a whole new tree. Ignored files such as dependencies and
+//! build output survive too, which is most of the saving on big repositories.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{LazyLock, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use git::GitService;
+use tracing::{debug, info, warn};
+
+use crate::{WorktreeCleanup, WorktreeError, WorktreeManager};
+
+/// Directory under the worktree base dir holding pooled worktrees. Orphan
+/// cleanup skips it.
+const POOL_DIR_NAME: &str = ".worktree-pool";
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+static POOL_POLICY: LazyLock<RwLock<WorktreePoolPolicy>> =
+    LazyLock::new(|| RwLock::new(WorktreePoolPolicy::default()));
+
+// Serializes taking, parking and pruning so no two callers get the same
+// pooled worktree.
+static POOL_LOCK: LazyLock<tokio::sync::Mutex<()>> = LazyLock::new(|| tokio::sync::Mutex::new(()));
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorktreePoolPolicy {
+    /// Idle worktrees kept per repository; 0 disables pooling.
+    pub max_per_repo: usize,
+    /// Pooled worktrees idle for longer are removed by [`WorktreePool::prune`].
+    pub max_idle: Option<Duration>,
+    /// Total size of the pool above which [`WorktreePool::prune`] removes the
+    /// longest-idle worktrees.
+    pub max_total_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PooledWorktree {
+    pub path: PathBuf,
+    pub parked_at: SystemTime,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorktreePoolReport {
+    pub kept: Vec<PooledWorktree>,
+    pub evicted: Vec<PooledWorktree>,
+}
+
+pub struct WorktreePool;
+
+impl WorktreePool {
+    pub fn set_policy(policy: WorktreePoolPolicy) {
+        *POOL_POLICY.write().unwrap() = policy;
+    }
+
+    pub fn policy() -> WorktreePoolPolicy {
+        POOL_POLICY.read().unwrap().clone()
+    }
+
+    /// Whether released worktrees are pooled at all.
+    pub fn enabled() -> bool {
+        Self::policy().max_per_repo > 0
+    }
+
+    pub fn dir() -> PathBuf {
+        WorktreeManager::get_worktree_base_dir().join(POOL_DIR_NAME)
+    }
+
+    pub fn is_pool_dir(path: &Path) -> bool {
+        path.file_name() == Some(POOL_DIR_NAME.as_ref())
+    }
+
+    /// Pool directory for one repository, named after it for readability and
+    /// a hash of its path for uniqueness. The hash is FNV-1a rather than
+    /// `DefaultHasher`, whose output may change between Rust releases and
+    /// would strand the existing pool.
+    fn repo_dir(repo_path: &Path) -> PathBuf {
+        let canonical = dunce::canonicalize(repo_path).unwrap_or_else(|_| repo_path.to_path_buf());
+        let hash = canonical
+            .as_os_str()
+            .as_encoded_bytes()
+            .iter()
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+            });
+        let name = canonical
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        Self::dir().join(format!("{name}-{hash:016x}"))
+    }
+
+    /// Entries are named after when they were parked, so listing a repo's
+    /// pool needs no extra bookkeeping.
+    fn entry_name(parked_at: SystemTime) -> String {
+        let since_epoch = parked_at.duration_since(UNIX_EPOCH).unwrap_or_default();
+        format!(
+            "{}-{:09}",
+            since_epoch.as_secs(),
+            since_epoch.subsec_nanos()
+        )
+    }
+
+    fn parked_at(entry: &Path) -> Option<SystemTime> {
+        let name = entry.file_name()?.to_str()?;
+        let (secs, nanos) = name.split_once('-')?;
+        Some(UNIX_EPOCH + Duration::new(secs.parse().ok()?, nanos.parse().ok()?))
+    }
+
+    /// Pooled worktrees of one repository, most recently parked first.
+    fn entries(repo_dir: &Path) -> Vec<(PathBuf, SystemTime)> {
+        let Ok(read_dir) = fs::read_dir(repo_dir) else {
+            return Vec::new();
+        };
+        let mut entries: Vec<_> = read_dir
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter_map(|path| Self::parked_at(&path).map(|parked_at| (path, parked_at)))
+            .collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1));
+        entries
+    }
+
+    /// Move `worktree_path` into the pool. Returns false, leaving the
+    /// worktree where it is, when pooling is disabled, the repository's
+    /// share of the pool is full, or the worktree is sparse.
+    pub(crate) async fn park(repo_path: &Path, worktree_path: &Path) -> bool {
+        let policy = Self::policy();
+        if policy.max_per_repo == 0 {
+            return false;
+        }
+
+        let _guard = POOL_LOCK.lock().await;
+        let repo_path = repo_path.to_path_buf();
+        let worktree_path = worktree_path.to_path_buf();
+        let result = tokio::task::spawn_blocking(move || -> Result<bool, WorktreeError> {
+            let repo_dir = Self::repo_dir(&repo_path);
+            if Self::entries(&repo_dir).len() >= policy.max_per_repo {
+                return Ok(false);
+            }
+
+            let git = GitService::new();
+            // A sparse worktree would be missing files for whoever takes it.
+            if git.is_sparse_worktree(&worktree_path) {
+                return Ok(false);
+            }
+            git.park_worktree(&worktree_path)?;
+            fs::create_dir_all(&repo_dir)?;
+            let entry = repo_dir.join(Self::entry_name(SystemTime::now()));
+            git.move_worktree(&repo_path, &worktree_path, &entry)?;
+            debug!(
+                "Parked worktree {} as {}",
+                worktree_path.display(),
+                entry.display()
+            );
+            Ok(true)
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))
+        .and_then(|result| result);
+
+        result.unwrap_or_else(|e| {
+            warn!("Failed to park worktree, removing it instead: {}", e);
+            false
+        })
+    }
+
+    /// Move a pooled worktree of `repo_path` to `worktree_path` and check out
+    /// `branch` in it. Returns false if none could be reused, in which case
+    /// nothing is left at `worktree_path`.
+    pub(crate) async fn take(repo_path: &Path, branch: &str, worktree_path: &Path) -> bool {
+        if !Self::enabled() {
+            return false;
+        }
+
+        let _guard = POOL_LOCK.lock().await;
+        let Some((entry, _)) = Self::entries(&Self::repo_dir(repo_path)).into_iter().next() else {
+            return false;
+        };
+
+        let (repo, from, to, branch_name) = (
+            repo_path.to_path_buf(),
+            entry.clone(),
+            worktree_path.to_path_buf(),
+            branch.to_string(),
+        );
+        let result = tokio::task::spawn_blocking(move || -> Result<(), WorktreeError> {
+            if let Some(parent) = to.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let git = GitService::new();
+            git.move_worktree(&repo, &from, &to)?;
+            git.checkout_worktree_branch(&to, &branch_name)?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))
+        .and_then(|result| result);
+
+        match result {
+            Ok(()) => {
+                info!(
+                    "Reused pooled worktree {} for branch {} at {}",
+                    entry.display(),
+                    branch,
+                    worktree_path.display()
+                );
+                true
+            }
+            Err(e) => {
+                warn!("Failed to reuse pooled worktree {}: {}", entry.display(), e);
+                // The worktree may be at either path depending on where it
+                // failed. The caller holds the lock for `worktree_path`, so
+                // this can't go through `cleanup_worktree`.
+                for path in [entry.as_path(), worktree_path] {
+                    if let Err(e) =
+                        WorktreeManager::comprehensive_worktree_cleanup_async(repo_path, path).await
+                    {
+                        debug!("Cleanup after failed reuse non-fatal error: {}", e);
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    /// Remove pooled worktrees idle for longer than the policy allows, then
+    /// the longest-idle ones until the pool fits its size limit. Disabling
+    /// pooling empties it. With `dry_run`, only report what would go.
+    pub async fn prune(dry_run: bool) -> Result<WorktreePoolReport, WorktreeError> {
+        let policy = Self::policy();
+        let _guard = POOL_LOCK.lock().await;
+
+        let mut pooled = tokio::task::spawn_blocking(|| {
+            let Ok(repo_dirs) = fs::read_dir(Self::dir()) else {
+                return Vec::new();
+            };
+            repo_dirs
+                .filter_map(|entry| entry.ok())
+                .flat_map(|repo_dir| Self::entries(&repo_dir.path()))
+                .map(|(path, parked_at)| PooledWorktree {
+                    bytes: dir_size(&path),
+                    path,
+                    parked_at,
+                })
+                .collect::<Vec<_>>()
+        })
+        .await
+        .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?;
+        pooled.sort_by(|a, b| b.parked_at.cmp(&a.parked_at));
+
+        let now = SystemTime::now();
+        let mut report = WorktreePoolReport::default();
+        let mut total_bytes = 0u64;
+        for worktree in pooled {
+            let idle = now.duration_since(worktree.parked_at).unwrap_or_default();
+            let expired = policy.max_idle.is_some_and(|max_idle| idle > max_idle);
+            let over_size = policy
+                .max_total_bytes
+                .is_some_and(|max| total_bytes + worktree.bytes > max);
+            if policy.max_per_repo == 0 || expired || over_size {
+                report.evicted.push(worktree);
+            } else {
+                total_bytes += worktree.bytes;
+                report.kept.push(worktree);
+            }
+        }
+
+        if !dry_run {
+            for worktree in &report.evicted {
+                let cleanup = WorktreeCleanup::new(worktree.path.clone(), None);
+                if let Err(e) = WorktreeManager::cleanup_worktree(&cleanup).await {
+                    warn!(
+                        "Failed to evict pooled worktree {}: {}",
+                        worktree.path.display(),
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Bytes used by files under `path`, not following symlinks.
+fn dir_size(path: &Path) -> u64 {
+    let Ok(read_dir) = fs::read_dir(path) else {
+        return 0;
+    };
+    read_dir
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
@@ -82,7 +82,13 @@ configureAuthRuntime({
   triggerRefresh: () => tokenManager.triggerRefresh(),
   registerShape: (shape) => tokenManager.registerShape(shape),
   getCurrentUser: () => oauthApi.getCurrentUser(),
+  getOrganizationToken: (organizationId) =>
+    oauthApi
+      .getToken(organizationId)
+      .then((response) => response.access_token)
+      .catch(() => null),
 });
+void tokenManager.watchSessionEvents();
 
 ReactDOM.createRoot(document.getElementById('root')!).render(
   <React.StrictMode>
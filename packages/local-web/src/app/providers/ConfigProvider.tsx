@@ -1,7 +1,10 @@
 import { ReactNode, useCallback, useEffect } from 'react';
 import { configApi } from '@/shared/lib/api';
 import { updateLanguageFromConfig } from '@/i18n/config';
-import { setRemoteApiBase } from '@/shared/lib/remoteApi';
+import {
+  setOrganizationRemotes,
+  setRemoteApiBase,
+} from '@/shared/lib/remoteApi';
 import { useUserSystemController } from '@/shared/hooks/useUserSystemController';
 import { UserSystemContext } from '@/shared/hooks/useUserSystem';
 import { tokenManager } from '@/shared/lib/auth/tokenManager';
@@ -27,6 +30,7 @@ export function UserSystemProvider({ children }: UserSystemProviderProps) {
   // Set runtime remote API base URL for self-hosting support.
   // Must run during render (not in useEffect) so it's set before children mount.
   setRemoteApiBase(userSystemInfo?.shared_api_base);
+  setOrganizationRemotes(value.config?.organization_remotes);
 
   // Sync language with i18n when config changes
   useEffect(() => {
@@ -92,6 +92,9 @@ export function MemberListItem({
               <SelectItem value={MemberRoleEnum.MEMBER}>
                 {t('roles.member')}
               </SelectItem>
+              <SelectItem value={MemberRoleEnum.VIEWER}>
+                {t('roles.viewer')}
+              </SelectItem>
             </SelectContent>
           </Select>
         )}
@@ -6,6 +6,7 @@ import { useActions } from '@/shared/hooks/useActions';
 import { useSyncErrorContext } from '@/shared/hooks/useSyncErrorContext';
 import { useUserOrganizations } from '@/shared/hooks/useUserOrganizations';
 import { useOrganizationStore } from '@/shared/stores/useOrganizationStore';
+import { useOrganizationPresence } from '@/shared/hooks/useOrganizationPresence';
 import {
   Navbar,
   type NavbarSectionItem,
@@ -153,6 +154,10 @@ export function NavbarContainer({
   const selectedOrgId = useOrganizationStore((s) => s.selectedOrgId);
   const orgName =
     orgsData?.organizations.find((o) => o.id === selectedOrgId)?.name ?? '';
+  // Only connected while looking at the organization's boards
+  const { onlineUsers } = useOrganizationPresence(
+    isOnProjectPage ? selectedOrgId : null
+  );
 
   // Get action visibility context (includes all state for visibility/active/enabled)
   const actionCtx = useActionVisibilityContext();
@@ -192,7 +197,9 @@ export function NavbarContainer({
   const navbarTitle = isCreateMode
     ? 'Create Workspace'
     : isOnProjectPage
-      ? orgName
+      ? onlineUsers.length > 1
+        ? `${orgName} · ${onlineUsers.length} online`
+        : orgName
       : selectedWorkspace?.branch;
 
   // Breadcrumbs: Project / Issue / Workspace (only on workspace pages with linked project)
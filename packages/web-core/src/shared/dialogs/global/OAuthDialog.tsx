@@ -24,7 +24,14 @@ import { useTranslation } from 'react-i18next';
 import { defineModal } from '@/shared/lib/modals';
 
 export type OAuthProvider = 'github' | 'google';
-type OAuthDialogProps = { initialProvider?: OAuthProvider };
+type OAuthDialogProps = {
+  initialProvider?: OAuthProvider;
+  /**
+   * Sign in to the remote hosting this organization rather than the default
+   * one, for organizations configured in `organization_remotes`.
+   */
+  organizationId?: string;
+};
 
 type OAuthState =
   | { type: 'select' }
@@ -32,7 +39,8 @@ type OAuthState =
   | { type: 'success'; displayName: string | null }
   | { type: 'error'; message: string };
 
-const OAuthDialogImpl = create<OAuthDialogProps>(({ initialProvider }) => {
+const OAuthDialogImpl = create<OAuthDialogProps>((props) => {
+  const { initialProvider, organizationId } = props;
   const modal = useModal();
   const { t } = useTranslation('common');
   const queryClient = useQueryClient();
@@ -50,8 +58,9 @@ const OAuthDialogImpl = create<OAuthDialogProps>(({ initialProvider }) => {
     isError: isAuthMethodsError,
     refetch: refetchAuthMethods,
   } = useQuery({
-    queryKey: ['auth', 'methods'],
-    queryFn: (): Promise<AuthMethodsResponse> => oauthApi.authMethods(),
+    queryKey: ['auth', 'methods', organizationId ?? null],
+    queryFn: (): Promise<AuthMethodsResponse> =>
+      oauthApi.authMethods(organizationId),
     staleTime: 60_000,
   });
   const hasLocalAuth = authMethods?.local_auth_enabled ?? false;
@@ -90,6 +99,7 @@ const OAuthDialogImpl = create<OAuthDialogProps>(({ initialProvider }) => {
   // Poll for auth status using proper query hook
   const { data: statusData, isError: isStatusError } = useAuthStatus({
     enabled: isPolling,
+    organizationId,
   });
 
   // Handle status check errors
@@ -158,9 +168,9 @@ const OAuthDialogImpl = create<OAuthDialogProps>(({ initialProvider }) => {
       const returnTo = `${window.location.origin}/api/auth/handoff/complete${isTauri ? '?source=desktop' : ''}`;
 
       // Initialize handoff flow
-      initHandoff.mutate({ provider, returnTo });
+      initHandoff.mutate({ provider, returnTo, organizationId });
     },
-    [initHandoff]
+    [initHandoff, organizationId]
   );
 
   const handleClose = () => {
@@ -189,7 +199,8 @@ const OAuthDialogImpl = create<OAuthDialogProps>(({ initialProvider }) => {
     try {
       const profile = await oauthApi.localLogin(
         localEmail.trim(),
-        localPassword
+        localPassword,
+        organizationId
       );
       await reloadSystem();
       await tokenManager.triggerRefresh();
@@ -216,6 +227,7 @@ const OAuthDialogImpl = create<OAuthDialogProps>(({ initialProvider }) => {
     localEmail,
     localPassword,
     modal,
+    organizationId,
     queryClient,
     reloadSystem,
   ]);
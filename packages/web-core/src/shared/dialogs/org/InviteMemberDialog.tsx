@@ -149,6 +149,9 @@ const InviteMemberDialogImpl = create<InviteMemberDialogProps>((props) => {
                 <SelectItem value={MemberRole.ADMIN}>
                   {t('roles.admin')}
                 </SelectItem>
+                <SelectItem value={MemberRole.VIEWER}>
+                  {t('roles.viewer')}
+                </SelectItem>
               </SelectContent>
             </Select>
             <p className="text-xs text-muted-foreground">
@@ -16,6 +16,7 @@ import { useOrganizationMembers } from '@/shared/hooks/useOrganizationMembers';
 import { useOrganizationInvitations } from '@/shared/hooks/useOrganizationInvitations';
 import { useOrganizationMutations } from '@/shared/hooks/useOrganizationMutations';
 import { useAuth } from '@/shared/hooks/auth/useAuth';
+import { useUserSystem } from '@/shared/hooks/useUserSystem';
 import { OAuthDialog } from '@/shared/dialogs/global/OAuthDialog';
 import {
   CreateOrganizationDialog,
@@ -45,6 +46,7 @@ import { SettingsCard, SettingsField } from './SettingsComponents';
 export function OrganizationsSettingsSection() {
   const { t } = useTranslation('organization');
   const { isSignedIn, isLoaded, userId } = useAuth();
+  const { config } = useUserSystem();
   const [error, setError] = useState<string | null>(null);
   const [success, setSuccess] = useState<string | null>(null);
   const [isOpeningBilling, setIsOpeningBilling] = useState(false);
@@ -299,6 +301,20 @@ export function OrganizationsSettingsSection() {
     value: org.id,
     label: org.name,
   }));
+  // Organizations on another remote only show up once it's signed in to
+  const signedOutRemotes = Object.entries(
+    config?.organization_remotes ?? {}
+  ).filter(
+    ([organizationId]) =>
+      !organizations.some((org) => org.id === organizationId)
+  );
+
+  const handleRemoteSignIn = async (organizationId: string) => {
+    const signedIn = await OAuthDialog.show({ organizationId });
+    if (signedIn) {
+      await refetchOrgs();
+    }
+  };
 
   return (
     <>
@@ -364,6 +380,26 @@ export function OrganizationsSettingsSection() {
         </SettingsField>
       </SettingsCard>
 
+      {/* Remotes hosting configured organizations, not signed in to yet */}
+      {signedOutRemotes.length > 0 && (
+        <SettingsCard
+          title={t('settings.otherRemotesTitle')}
+          description={t('settings.otherRemotesDescription')}
+        >
+          {signedOutRemotes.map(([organizationId, remote]) => (
+            <SettingsField key={organizationId} label={remote?.api_base ?? ''}>
+              <PrimaryButton
+                variant="secondary"
+                value={t('loginRequired.action')}
+                onClick={() => void handleRemoteSignIn(organizationId)}
+              >
+                <SignInIcon className="size-icon-xs mr-1" weight="bold" />
+              </PrimaryButton>
+            </SettingsField>
+          ))}
+        </SettingsCard>
+      )}
+
       {/* Pending Invitations (admin only) */}
       {selectedOrg &&
         isAdmin &&
@@ -12,10 +12,12 @@ export function useAuthMutations(options?: UseAuthMutationsOptions) {
     mutationFn: ({
       provider,
       returnTo,
+      organizationId,
     }: {
       provider: string;
       returnTo: string;
-    }) => oauthApi.handoffInit(provider, returnTo),
+      organizationId?: string;
+    }) => oauthApi.handoffInit(provider, returnTo, organizationId),
     onSuccess: (data) => {
       options?.onInitSuccess?.(data);
     },
@@ -5,12 +5,14 @@ import { useAuth } from '@/shared/hooks/auth/useAuth';
 
 interface UseAuthStatusOptions {
   enabled: boolean;
+  /** Check the sign-in to the remote hosting this organization instead. */
+  organizationId?: string;
 }
 
 export function useAuthStatus(options: UseAuthStatusOptions) {
   const query = useQuery({
-    queryKey: ['auth', 'status'],
-    queryFn: () => oauthApi.status(),
+    queryKey: ['auth', 'status', options.organizationId ?? null],
+    queryFn: () => oauthApi.status(options.organizationId),
     enabled: options.enabled,
     refetchInterval: options.enabled ? 1000 : false,
     retry: 3,
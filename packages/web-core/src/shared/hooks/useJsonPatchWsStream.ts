@@ -3,12 +3,17 @@ import { produce } from 'immer';
 import type { Operation } from 'rfc6902';
 import { applyUpsertPatch } from '@/shared/lib/jsonPatch';
 import { openLocalApiWebSocket } from '@/shared/lib/localApiTransport';
+import { reconnectHint } from '@/shared/lib/wsCloseCodes';
 
 type WsJsonPatchMsg = { JsonPatch: Operation[] };
 type WsReadyMsg = { Ready: true };
 type WsFinishedMsg = { finished: boolean };
 type WsMsg = WsJsonPatchMsg | WsReadyMsg | WsFinishedMsg;
 
+// A connection that stays open this long counts as healthy and resets the
+// reconnect backoff.
+const STABLE_CONNECTION_MS = 30_000;
+
 interface UseJsonPatchStreamOptions<T> {
   /**
    * Called once when the stream starts to inject initial data
@@ -109,11 +114,12 @@ export const useJsonPatchWsStream = <T extends object>(
             return;
           }
 
+          let openedAt: number | null = null;
+
           ws.onopen = () => {
+            openedAt = Date.now();
             setError(null);
             setIsConnected(true);
-            // Reset backoff on successful connection
-            retryAttemptsRef.current = 0;
             if (retryTimerRef.current) {
               window.clearTimeout(retryTimerRef.current);
               retryTimerRef.current = null;
@@ -183,7 +189,27 @@ export const useJsonPatchWsStream = <T extends object>(
               return;
             }
 
-            // Otherwise, reconnect on unexpected/error closures
+            if (
+              openedAt !== null &&
+              Date.now() - openedAt >= STABLE_CONNECTION_MS
+            ) {
+              retryAttemptsRef.current = 0;
+            }
+
+            // The server dropped updates for us; the new connection's
+            // snapshot replaces what we have. Only the first resync after a
+            // healthy connection skips the delay, so a stream that keeps
+            // lagging still backs off.
+            if (
+              reconnectHint(evt.code) === 'resync' &&
+              retryAttemptsRef.current === 0
+            ) {
+              retryAttemptsRef.current = 1;
+              setRetryNonce((n) => n + 1);
+              return;
+            }
+
+            // Otherwise, reconnect with backoff
             retryAttemptsRef.current += 1;
             // Only show error if we haven't received any data yet
             if (!dataRef.current && retryAttemptsRef.current > 6) {
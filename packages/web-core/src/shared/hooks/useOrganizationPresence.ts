@@ -0,0 +1,128 @@
+import { useEffect, useState } from 'react';
+import type { PresenceMessage, UserData } from 'shared/remote-types';
+import { createPresenceTicket } from '@/shared/lib/remoteApi';
+import { reconnectHint } from '@/shared/lib/wsCloseCodes';
+
+/** Sent by the server when an operator ends the connection. */
+const POLICY_VIOLATION = 1008;
+
+interface UseOrganizationPresenceResult {
+  /** Users with the organization open, including the current user. */
+  onlineUsers: UserData[];
+  isConnected: boolean;
+}
+
+/**
+ * Keep the organization's presence WebSocket open while `organizationId` is
+ * set, and track who else has the organization open. Each connection uses a
+ * fresh ticket, since tickets can only be used once.
+ */
+export function useOrganizationPresence(
+  organizationId: string | null | undefined
+): UseOrganizationPresenceResult {
+  const [onlineUsers, setOnlineUsers] = useState<UserData[]>([]);
+  const [isConnected, setIsConnected] = useState(false);
+
+  useEffect(() => {
+    if (!organizationId) {
+      setOnlineUsers([]);
+      setIsConnected(false);
+      return;
+    }
+
+    let cancelled = false;
+    let ws: WebSocket | null = null;
+    let retryTimer: number | null = null;
+    let retryAttempts = 0;
+    // Set after the server closed because our session or membership ended
+    let reauthenticating = false;
+
+    const scheduleReconnect = () => {
+      // Exponential backoff with cap: 1s, 2s, 4s, ... 30s
+      const delay = Math.min(30_000, 1000 * Math.pow(2, retryAttempts));
+      retryAttempts += 1;
+      retryTimer = window.setTimeout(() => {
+        retryTimer = null;
+        void connect();
+      }, delay);
+    };
+
+    const applyMessage = (message: PresenceMessage) => {
+      switch (message.type) {
+        case 'presence.snapshot':
+          setOnlineUsers(message.users);
+          break;
+        case 'presence.joined':
+          setOnlineUsers((users) => [
+            ...users.filter((u) => u.user_id !== message.user.user_id),
+            message.user,
+          ]);
+          break;
+        case 'presence.left':
+          setOnlineUsers((users) =>
+            users.filter((u) => u.user_id !== message.user_id)
+          );
+          break;
+        case 'presence.editing':
+          break;
+      }
+    };
+
+    const connect = async () => {
+      let url: string;
+      try {
+        url = (await createPresenceTicket(organizationId)).url;
+      } catch (err) {
+        console.warn('Failed to get presence ticket:', err);
+        // Retrying can't help once we've lost access
+        if (!cancelled && !reauthenticating) scheduleReconnect();
+        return;
+      }
+      if (cancelled) return;
+
+      const socket = new WebSocket(url);
+      ws = socket;
+
+      socket.onopen = () => {
+        retryAttempts = 0;
+        reauthenticating = false;
+        setIsConnected(true);
+      };
+
+      socket.onmessage = (event) => {
+        try {
+          applyMessage(JSON.parse(event.data) as PresenceMessage);
+        } catch (err) {
+          console.error('Failed to process presence message:', err);
+        }
+      };
+
+      socket.onclose = (evt) => {
+        ws = null;
+        setIsConnected(false);
+        if (cancelled) return;
+        // Someone ended the connection on purpose; don't fight it
+        if (evt.code === POLICY_VIOLATION) return;
+        if (reconnectHint(evt.code) === 'reauthenticate') {
+          // The ticket request refreshes the token, and fails if we were
+          // removed from the organization
+          reauthenticating = true;
+          retryAttempts = 0;
+        }
+        scheduleReconnect();
+      };
+    };
+
+    void connect();
+
+    return () => {
+      cancelled = true;
+      if (retryTimer) window.clearTimeout(retryTimer);
+      ws?.close(1000, 'unmounted');
+      setOnlineUsers([]);
+      setIsConnected(false);
+    };
+  }, [organizationId]);
+
+  return { onlineUsers, isConnected };
+}
@@ -100,6 +100,7 @@ import {
   OpenRemoteWorkspaceInEditorRequest,
   OpenRemoteEditorResponse,
   ProfileResponse,
+  AuthEvent,
 } from 'shared/types';
 import type { Project as RemoteProject } from 'shared/remote-types';
 import type { WorkspaceWithSession } from '@/shared/types/attempt';
@@ -1243,49 +1244,72 @@ export type AuthMethodsResponse = {
   oauth_providers: string[];
 };
 
+/**
+ * Query selecting the remote an auth call is for: the one hosting
+ * `organizationId`, or the default remote when it's absent.
+ */
+const organizationQuery = (organizationId?: string): string =>
+  organizationId
+    ? `?organization_id=${encodeURIComponent(organizationId)}`
+    : '';
+
 export const oauthApi = {
-  authMethods: async (): Promise<AuthMethodsResponse> => {
-    const response = await makeRequest('/api/auth/methods', {
-      cache: 'no-store',
-    });
+  authMethods: async (
+    organizationId?: string
+  ): Promise<AuthMethodsResponse> => {
+    const response = await makeRequest(
+      `/api/auth/methods${organizationQuery(organizationId)}`,
+      { cache: 'no-store' }
+    );
     return handleApiResponse<AuthMethodsResponse>(response);
   },
 
   handoffInit: async (
     provider: string,
-    returnTo: string
+    returnTo: string,
+    organizationId?: string
   ): Promise<{ handoff_id: string; authorize_url: string }> => {
     const response = await makeRequest('/api/auth/handoff/init', {
       method: 'POST',
-      body: JSON.stringify({ provider, return_to: returnTo }),
+      body: JSON.stringify({
+        provider,
+        return_to: returnTo,
+        organization_id: organizationId ?? null,
+      }),
     });
     return handleApiResponse<{ handoff_id: string; authorize_url: string }>(
       response
     );
   },
 
-  status: async (): Promise<StatusResponse> => {
-    const response = await makeRequest('/api/auth/status', {
-      cache: 'no-store',
-    });
+  status: async (organizationId?: string): Promise<StatusResponse> => {
+    const response = await makeRequest(
+      `/api/auth/status${organizationQuery(organizationId)}`,
+      { cache: 'no-store' }
+    );
     return handleApiResponse<StatusResponse>(response);
   },
 
   localLogin: async (
     email: string,
-    password: string
+    password: string,
+    organizationId?: string
   ): Promise<ProfileResponse> => {
-    const response = await makeRequest('/api/auth/local/login', {
-      method: 'POST',
-      body: JSON.stringify({ email, password }),
-    });
+    const response = await makeRequest(
+      `/api/auth/local/login${organizationQuery(organizationId)}`,
+      {
+        method: 'POST',
+        body: JSON.stringify({ email, password }),
+      }
+    );
     return handleApiResponse<ProfileResponse>(response);
   },
 
-  logout: async (): Promise<void> => {
-    const response = await makeRequest('/api/auth/logout', {
-      method: 'POST',
-    });
+  logout: async (organizationId?: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/auth/logout${organizationQuery(organizationId)}`,
+      { method: 'POST' }
+    );
     if (!response.ok) {
       throw new ApiError(
         `Logout failed with status ${response.status}`,
@@ -1296,14 +1320,57 @@ export const oauthApi = {
   },
 
   /** Returns the current access token for the remote server (auto-refreshes if needed) */
-  getToken: async (): Promise<TokenResponse> => {
-    const response = await makeRequest('/api/auth/token');
+  getToken: async (organizationId?: string): Promise<TokenResponse> => {
+    const response = await makeRequest(
+      `/api/auth/token${organizationQuery(organizationId)}`
+    );
     if (response.status === 401) {
       throw new ApiError('Unauthorized', 401, response);
     }
     return handleApiResponse<TokenResponse>(response);
   },
 
+  /**
+   * Follows the session's `AuthEvent`s, sent as server-sent events, until
+   * the stream ends or `signal` aborts.
+   */
+  streamEvents: async (
+    onEvent: (event: AuthEvent) => void,
+    signal?: AbortSignal
+  ): Promise<void> => {
+    const response = await makeRequest('/api/auth/events', { signal });
+    if (!response.ok || !response.body) {
+      throw new ApiError(
+        `Auth events failed with status ${response.status}`,
+        response.status,
+        response
+      );
+    }
+
+    const reader = response.body
+      .pipeThrough(new TextDecoderStream())
+      .getReader();
+    let buffer = '';
+    for (;;) {
+      const { done, value } = await reader.read();
+      if (done) return;
+
+      buffer += value;
+      const messages = buffer.split('\n\n');
+      buffer = messages.pop() ?? '';
+      for (const message of messages) {
+        const data = message
+          .split('\n')
+          .filter((line) => line.startsWith('data:'))
+          .map((line) => line.slice('data:'.length).trimStart())
+          .join('\n');
+        if (data) {
+          onEvent(JSON.parse(data) as AuthEvent);
+        }
+      }
+    }
+  },
+
   /** Returns the user ID of the currently authenticated user */
   getCurrentUser: async (): Promise<CurrentUserResponse> => {
     const response = await makeRequest('/api/auth/user');
@@ -7,6 +7,11 @@ export interface AuthRuntime {
   triggerRefresh: () => Promise<string | null>;
   registerShape: (shape: PauseableShape) => () => void;
   getCurrentUser: () => Promise<CurrentUser>;
+  /**
+   * Token for the remote hosting an organization that lives outside the
+   * default remote. That remote is signed in to separately.
+   */
+  getOrganizationToken?: (organizationId: string) => Promise<string | null>;
 }
 
 let authRuntime: AuthRuntime | null = null;
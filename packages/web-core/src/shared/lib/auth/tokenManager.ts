@@ -2,6 +2,7 @@ import { ApiError, oauthApi } from '@/shared/lib/api';
 import { REMOTE_AUTH_UNAVAILABLE_SLUG } from '@/shared/lib/auth/remoteAuthDegraded';
 import { queryClient } from '@/shared/lib/queryClient';
 import { shouldRefreshAccessToken } from 'shared/jwt';
+import type { AuthEvent } from 'shared/types';
 
 const TOKEN_QUERY_KEY = ['auth', 'token'] as const;
 const TOKEN_STALE_TIME = 125 * 1000;
@@ -216,6 +217,45 @@ class TokenManager {
     }
   }
 
+  /**
+   * Follow the server's session events until `signal` aborts, reconnecting
+   * with backoff. A renewed or soon-to-lapse token drops the cached one, and
+   * an ended session prompts a new sign-in before a request is rejected.
+   */
+  async watchSessionEvents(signal?: AbortSignal): Promise<void> {
+    let attempt = 0;
+    while (!signal?.aborted) {
+      try {
+        await oauthApi.streamEvents((event) => {
+          attempt = 0;
+          void this.handleSessionEvent(event);
+        }, signal);
+      } catch {
+        // Reconnect below; the server may be restarting.
+      }
+      if (signal?.aborted) return;
+
+      const delayMs = Math.min(
+        RECOVERY_RETRY_BASE_DELAY_MS * 2 ** attempt,
+        RECOVERY_RETRY_MAX_DELAY_MS
+      );
+      attempt += 1;
+      await new Promise((resolve) => setTimeout(resolve, delayMs));
+    }
+  }
+
+  private async handleSessionEvent(event: AuthEvent): Promise<void> {
+    switch (event.type) {
+      case 'expiring_soon':
+      case 'refreshed':
+        await queryClient.invalidateQueries({ queryKey: TOKEN_QUERY_KEY });
+        break;
+      case 'session_ended':
+        await this.handleUnauthorized();
+        break;
+    }
+  }
+
   private async handleUnauthorized(): Promise<void> {
     this.clearRecoveryLoop();
 
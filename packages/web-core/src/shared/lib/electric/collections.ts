@@ -2,7 +2,12 @@ import { electricCollectionOptions } from '@tanstack/electric-db-collection';
 import { createCollection } from '@tanstack/react-db';
 
 import { getAuthRuntime } from '@/shared/lib/auth/runtime';
-import { getRemoteApiUrl, makeRequest } from '@/shared/lib/remoteApi';
+import {
+  getOrganizationAuth,
+  getOrganizationRemote,
+  getRemoteApiUrl,
+  makeOrganizationRequest,
+} from '@/shared/lib/remoteApi';
 import type { MutationDefinition, ShapeDefinition } from 'shared/remote-types';
 import type { CollectionConfig, SyncError } from '@/shared/lib/electric/types';
 
@@ -343,13 +348,20 @@ function createElectricShapeOptions(args: {
   });
 
   const url = buildUrl(args.shape.url, args.params);
+  // Organization-scoped shapes sync from the remote hosting the organization
+  const organizationId = args.params.organization_id;
+  const remote = getOrganizationRemote(organizationId);
+  const baseUrl = remote
+    ? (remote.sync_base ?? remote.api_base)
+    : getRemoteApiUrl();
+  const auth = getOrganizationAuth(organizationId);
 
   return {
-    url: `${getRemoteApiUrl()}${url}`,
+    url: `${baseUrl}${url}`,
     params: args.params,
     headers: {
       Authorization: async () => {
-        const token = await authRuntime.getToken();
+        const token = await auth.getToken();
         if (!token) {
           isPaused = true;
           return '';
@@ -373,7 +385,7 @@ function createElectricShapeOptions(args: {
       const message = error.message || String(error);
 
       if (status === 401) {
-        authRuntime.triggerRefresh().catch(() => {
+        auth.refresh().catch(() => {
           args.reportError({ status, message });
         });
         return;
@@ -456,7 +468,8 @@ function createFallbackSync(args: {
 
       refreshPromise = (async () => {
         try {
-          const response = await makeRequest(
+          const response = await makeOrganizationRequest(
+            args.params.organization_id,
             buildFallbackRequestPath(args.shape.fallbackUrl, args.params),
             { method: 'GET', cache: 'no-store' }
           );
@@ -621,7 +634,8 @@ function maybeRefreshFallbackAfterMutation(sourceKey: string): void {
 
 function buildMutationHandlers(
   mutation: MutationDefinition<unknown, unknown, unknown>,
-  sourceKey: string
+  sourceKey: string,
+  organizationId: string | undefined
 ) {
   return {
     onInsert: async ({
@@ -630,10 +644,14 @@ function buildMutationHandlers(
       const txids = await Promise.all(
         transaction.mutations.map(async (mutationItem) => {
           const data = mutationItem.modified as Record<string, unknown>;
-          const response = await makeRequest(mutation.url, {
-            method: 'POST',
-            body: JSON.stringify(data),
-          });
+          const response = await makeOrganizationRequest(
+            organizationId,
+            mutation.url,
+            {
+              method: 'POST',
+              body: JSON.stringify(data),
+            }
+          );
 
           if (!response.ok) {
             const message = await parseResponseError(
@@ -674,10 +692,14 @@ function buildMutationHandlers(
           };
         });
 
-        const response = await makeRequest(`${mutation.url}/bulk`, {
-          method: 'POST',
-          body: JSON.stringify({ updates }),
-        });
+        const response = await makeOrganizationRequest(
+          organizationId,
+          `${mutation.url}/bulk`,
+          {
+            method: 'POST',
+            body: JSON.stringify({ updates }),
+          }
+        );
 
         if (!response.ok) {
           const message = await parseResponseError(
@@ -695,7 +717,8 @@ function buildMutationHandlers(
           throw new Error(`Failed to update ${mutation.name}: missing key`);
         }
 
-        const response = await makeRequest(
+        const response = await makeOrganizationRequest(
+          organizationId,
           `${mutation.url}/${mutationItem.key}`,
           {
             method: 'PATCH',
@@ -729,7 +752,8 @@ function buildMutationHandlers(
     }: MutationFnParams): Promise<{ txid: number[] } | void> => {
       const txids = await Promise.all(
         transaction.mutations.map(async (mutationItem) => {
-          const response = await makeRequest(
+          const response = await makeOrganizationRequest(
+            organizationId,
             `${mutation.url}/${mutationItem.key}`,
             {
               method: 'DELETE',
@@ -786,7 +810,7 @@ export function createShapeCollection<TRow extends ElectricRow>(
   });
 
   const mutationHandlers = mutation
-    ? buildMutationHandlers(mutation, sourceKey)
+    ? buildMutationHandlers(mutation, sourceKey, params.organization_id)
     : {};
 
   const electricOptions = electricCollectionOptions({
@@ -204,6 +204,14 @@ export function getGroupedNotificationSegments(
     }
     case 'issue_deleted':
       return [...actor, text(' deleted '), ...issueSegments];
+    case 'issue_due_soon':
+      return [...issueSegments, text(' is due soon')];
+    case 'issue_blocked':
+      return [...issueSegments, text(' was blocked by another issue')];
+    case 'issue_unblocked':
+      return [...issueSegments, text(' is no longer blocked')];
+    case 'issue_mentioned':
+      return [...actor, text(' mentioned you in '), ...issueSegments];
     default:
       return [text('New notification')];
   }
@@ -7,11 +7,13 @@ import type {
   InitUploadRequest,
   InitUploadResponse,
   ListRelayHostsResponse,
+  PresenceTicket,
   RelayHost,
   UpdateIssueRequest,
   UpdateProjectRequest,
   UpdateProjectStatusRequest,
 } from 'shared/remote-types';
+import type { OrganizationRemote } from 'shared/types';
 import { getAuthRuntime } from '@/shared/lib/auth/runtime';
 import { syncRelayApiBaseWithRemote } from '@/shared/lib/relayBackendApi';
 
@@ -44,6 +46,51 @@ export function getRemoteApiUrl(): string {
 // Backward-compatible export — consumers should migrate to getRemoteApiUrl()
 export const REMOTE_API_URL = BUILD_TIME_API_BASE;
 
+// Organizations hosted outside the default remote, keyed by organization id.
+// Set by ConfigProvider from the config's `organization_remotes`.
+let _organizationRemotes: Partial<Record<string, OrganizationRemote>> = {};
+
+export function setOrganizationRemotes(
+  remotes: Partial<Record<string, OrganizationRemote>> | null | undefined
+) {
+  _organizationRemotes = remotes ?? {};
+}
+
+/**
+ * The remote hosting `organizationId`, or `undefined` when it lives on the
+ * default remote.
+ */
+export function getOrganizationRemote(
+  organizationId: string | null | undefined
+): OrganizationRemote | undefined {
+  return organizationId ? _organizationRemotes[organizationId] : undefined;
+}
+
+interface RequestAuth {
+  getToken: () => Promise<string | null>;
+  refresh: () => Promise<string | null>;
+}
+
+/**
+ * Sign-in for the remote hosting `organizationId`. Each remote issues its
+ * own tokens, so one remote's token is never sent to another.
+ */
+export function getOrganizationAuth(
+  organizationId: string | null | undefined
+): RequestAuth {
+  const authRuntime = getAuthRuntime();
+  if (!organizationId || !getOrganizationRemote(organizationId)) {
+    return {
+      getToken: () => authRuntime.getToken(),
+      refresh: () => authRuntime.triggerRefresh(),
+    };
+  }
+  // The local server refreshes the token when it's about to expire
+  const getToken = async () =>
+    (await authRuntime.getOrganizationToken?.(organizationId)) ?? null;
+  return { getToken, refresh: getToken };
+}
+
 export const makeRequest = async (
   path: string,
   options: RequestInit = {},
@@ -52,14 +99,36 @@ export const makeRequest = async (
   return makeAuthenticatedRequest(getRemoteApiUrl(), path, options, retryOn401);
 };
 
+/**
+ * Like `makeRequest`, but sent to the remote hosting `organizationId`.
+ */
+export const makeOrganizationRequest = async (
+  organizationId: string | null | undefined,
+  path: string,
+  options: RequestInit = {},
+  retryOn401 = true
+): Promise<Response> => {
+  const remote = getOrganizationRemote(organizationId);
+  if (!remote) {
+    return makeRequest(path, options, retryOn401);
+  }
+  return makeAuthenticatedRequest(
+    remote.api_base,
+    path,
+    options,
+    retryOn401,
+    getOrganizationAuth(organizationId)
+  );
+};
+
 async function makeAuthenticatedRequest(
   baseUrl: string,
   path: string,
   options: RequestInit = {},
-  retryOn401 = true
+  retryOn401 = true,
+  auth: RequestAuth = getOrganizationAuth(null)
 ): Promise<Response> {
-  const authRuntime = getAuthRuntime();
-  const token = await authRuntime.getToken();
+  const token = await auth.getToken();
   if (!token) {
     throw new Error('Not authenticated');
   }
@@ -80,7 +149,7 @@ async function makeAuthenticatedRequest(
 
   // Handle 401 - token may have expired
   if (response.status === 401 && retryOn401) {
-    const newToken = await authRuntime.triggerRefresh();
+    const newToken = await auth.refresh();
     if (newToken) {
       // Retry the request with the new token
       headers.set('Authorization', `Bearer ${newToken}`);
@@ -367,3 +436,36 @@ export async function fetchAttachmentSasUrl(
   });
   return data.url;
 }
+
+/**
+ * Get a single-use link for opening the organization's presence WebSocket.
+ * Browsers can't send the bearer token with a WebSocket, so the link carries
+ * a short-lived ticket instead.
+ */
+export async function createPresenceTicket(
+  organizationId: string
+): Promise<PresenceTicket> {
+  const response = await makeOrganizationRequest(
+    organizationId,
+    '/v1/ws/tickets',
+    {
+      method: 'POST',
+      body: JSON.stringify({ organization_id: organizationId }),
+    }
+  );
+  if (!response.ok) {
+    throw await parseErrorResponse(response, 'Failed to open presence');
+  }
+  const ticket: PresenceTicket = await response.json();
+  const syncBase = getOrganizationRemote(organizationId)?.sync_base;
+  if (!syncBase) {
+    return ticket;
+  }
+  // Connect through the organization's sync endpoint rather than the host
+  // that issued the ticket
+  const { pathname, search } = new URL(ticket.url);
+  const wsBase = syncBase
+    .replace(/\/+$/, '')
+    .replace(/^http(s?):\/\//, 'ws$1://');
+  return { ...ticket, url: `${wsBase}${pathname}${search}` };
+}
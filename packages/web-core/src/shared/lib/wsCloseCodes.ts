@@ -0,0 +1,26 @@
+/**
+ * Close codes the server sends when it ends a WebSocket stream on purpose.
+ * Mirrors `utils::ws::WsCloseCode` in the Rust server.
+ */
+export const WsCloseCode = {
+  AuthExpired: 4001,
+  BacklogDropped: 4002,
+  ServerRestarting: 4003,
+} as const;
+
+export type ReconnectHint = 'reauthenticate' | 'resync' | 'backoff';
+
+/**
+ * How to recover from a close with `code`. Unknown codes, including abnormal
+ * closures, are treated as transient and retried with backoff.
+ */
+export function reconnectHint(code: number): ReconnectHint {
+  switch (code) {
+    case WsCloseCode.AuthExpired:
+      return 'reauthenticate';
+    case WsCloseCode.BacklogDropped:
+      return 'resync';
+    default:
+      return 'backoff';
+  }
+}
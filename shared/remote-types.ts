@@ -12,11 +12,13 @@ export type NotificationGroupKind = "single" | "issue_changes" | "status_changes
 
 export type NotificationPayload = { deeplink_path?: string | null, issue_id?: string | null, issue_simple_id?: string | null, issue_title?: string | null, actor_user_id?: string | null, comment_preview?: string | null, old_status_id?: string | null, new_status_id?: string | null, old_status_name?: string | null, new_status_name?: string | null, new_title?: string | null, old_priority?: IssuePriority | null, new_priority?: IssuePriority | null, assignee_user_id?: string | null, emoji?: string | null, };
 
-export type NotificationType = "issue_comment_added" | "issue_status_changed" | "issue_assignee_changed" | "issue_priority_changed" | "issue_unassigned" | "issue_comment_reaction" | "issue_deleted" | "issue_title_changed" | "issue_description_changed";
+export type NotificationType = "issue_comment_added" | "issue_status_changed" | "issue_assignee_changed" | "issue_priority_changed" | "issue_unassigned" | "issue_comment_reaction" | "issue_deleted" | "issue_title_changed" | "issue_description_changed" | "issue_due_soon" | "issue_blocked" | "issue_unblocked" | "issue_mentioned";
 
 export type Workspace = { id: string, project_id: string, owner_user_id: string, issue_id: string | null, local_workspace_id: string | null, name: string | null, archived: boolean, files_changed: number | null, lines_added: number | null, lines_removed: number | null, created_at: string, updated_at: string, };
 
-export type ProjectStatus = { id: string, project_id: string, name: string, color: string, sort_order: number, hidden: boolean, created_at: string, };
+export type ProjectStatus = { id: string, project_id: string, name: string, color: string, sort_order: number, hidden: boolean, category: ProjectStatusCategory | null, created_at: string, };
+
+export type ProjectStatusCategory = "backlog" | "todo" | "in_progress" | "in_review" | "done" | "cancelled";
 
 export type Tag = { id: string, project_id: string, name: string, color: string, };
 
@@ -44,14 +46,55 @@ export type IssueCommentReaction = { id: string, comment_id: string, user_id: st
 
 export type IssuePriority = "urgent" | "high" | "medium" | "low";
 
-export type IssueSortField = "sort_order" | "priority" | "created_at" | "updated_at" | "title";
+export type IssueSortField = "sort_order" | "sort_key" | "priority" | "target_date" | "created_at" | "updated_at" | "title";
 
-export type ListIssuesQuery = { project_id: string, };
+export type ListIssuesQuery = { project_id: string, status_id?: string, assignee_user_id?: string, 
+/**
+ * Full-text query over title and description.
+ */
+q?: string, sort_field?: IssueSortField, sort_direction?: SortDirection, 
+/**
+ * Page by `(updated_at, id)` instead of the sort options: return issues
+ * updated after this cursor, oldest first.
+ */
+after_updated_at?: string, 
+/**
+ * Tie-breaker for `after_updated_at`: the `id` of the last issue seen.
+ */
+after_id?: string, 
+/**
+ * Page size for cursor paging. Setting it without a cursor starts from
+ * the first page.
+ */
+limit?: number, };
 
-export type SearchIssuesRequest = { project_id: string, status_id?: string, status_ids?: Array<string>, priority?: IssuePriority, parent_issue_id?: string, search?: string, simple_id?: string, assignee_user_id?: string, tag_id?: string, tag_ids?: Array<string>, sort_field?: IssueSortField, sort_direction?: SortDirection, limit?: number, offset?: number, };
+export type SearchIssuesRequest = { project_id: string, status_id?: string, status_ids?: Array<string>, priority?: IssuePriority, parent_issue_id?: string, search?: string, 
+/**
+ * Full-text query over title and description, in web search syntax
+ * (`"exact phrase"`, `or`, `-excluded`).
+ */
+q?: string, simple_id?: string, assignee_user_id?: string, tag_id?: string, tag_ids?: Array<string>, sort_field?: IssueSortField, sort_direction?: SortDirection, limit?: number, offset?: number, };
 
 export type ListIssuesResponse = { issues: Array<Issue>, total_count: number, limit: number, offset: number, };
 
+export type IssueAction = { "type": "set_status", status_id: string, } | { "type": "assign", user_id: string, } | { "type": "add_tag", tag_id: string, } | { "type": "comment", message: string, };
+
+export type ApplyIssueActionsRequest = { actions: Array<IssueAction>, };
+
+export type ApplyIssueActionsResponse = { issue: Issue, assignees: Array<IssueAssignee>, tags: Array<IssueTag>, comments: Array<IssueComment>, txid: bigint, };
+
+export type BatchMutation = { "type": "create_issue" } & CreateIssueRequest | { "type": "add_issue_tag" } & CreateIssueTagRequest | { "type": "add_issue_assignee" } & CreateIssueAssigneeRequest | { "type": "add_issue_relationship" } & CreateIssueRelationshipRequest;
+
+export type BatchMutationsRequest = { mutations: Array<BatchMutation>, };
+
+export type BatchMutationResult = { "type": "issue", "data": Issue } | { "type": "issue_tag", "data": IssueTag } | { "type": "issue_assignee", "data": IssueAssignee } | { "type": "issue_relationship", "data": IssueRelationship };
+
+export type BatchMutationsResponse = { 
+/**
+ * One result per mutation, in request order.
+ */
+results: Array<BatchMutationResult>, txid: bigint, };
+
 export type PullRequestStatus = "open" | "merged" | "closed";
 
 export type PullRequest = { id: string, url: string, number: number, status: PullRequestStatus, merged_at: string | null, merge_commit_sha: string | null, target_branch_name: string, project_id: string, issue_id: string, workspace_id: string | null, created_at: string, updated_at: string, };
@@ -77,7 +120,7 @@ export type ListRelayHostsResponse = { hosts: Array<RelayHost>, };
 
 export type CreateRemoteSessionResponse = { session_id: string, };
 
-export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER" }
+export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER", VIEWER = "VIEWER" }
 
 export type OrganizationMember = { organization_id: string, user_id: string, role: MemberRole, joined_at: string, last_seen_at: string | null, };
 
@@ -106,9 +149,9 @@ export type CreateProjectStatusRequest = {
  * Optional client-generated ID. If not provided, server generates one.
  * Using client-generated IDs enables stable optimistic updates.
  */
-id?: string, project_id: string, name: string, color: string, sort_order: number, hidden: boolean, };
+id?: string, project_id: string, name: string, color: string, sort_order: number, hidden: boolean, category?: ProjectStatusCategory, };
 
-export type UpdateProjectStatusRequest = { name: string | null, color: string | null, sort_order: number | null, hidden: boolean | null, };
+export type UpdateProjectStatusRequest = { name: string | null, color: string | null, sort_order: number | null, hidden: boolean | null, category?: ProjectStatusCategory | null, };
 
 export type CreateIssueRequest = { 
 /**
@@ -117,7 +160,28 @@ export type CreateIssueRequest = {
  */
 id?: string, project_id: string, status_id: string, title: string, description: string | null, priority: IssuePriority | null, start_date: string | null, target_date: string | null, completed_at: string | null, sort_order: number, parent_issue_id: string | null, parent_issue_sort_order: number | null, extension_metadata: JsonValue, };
 
-export type UpdateIssueRequest = { status_id?: string | null, title?: string | null, description?: string | null | null, priority?: IssuePriority | null | null, start_date?: string | null | null, target_date?: string | null | null, completed_at?: string | null | null, sort_order?: number | null, parent_issue_id?: string | null | null, parent_issue_sort_order?: number | null | null, extension_metadata?: JsonValue | null, };
+export type UpdateIssueRequest = { status_id?: string | null, title?: string | null, description?: string | null, priority?: IssuePriority | null, start_date?: string | null, target_date?: string | null, completed_at?: string | null, sort_order?: number | null, parent_issue_id?: string | null, parent_issue_sort_order?: number | null, extension_metadata?: JsonValue | null, };
+
+export type CreateSubIssueRequest = { 
+/**
+ * Optional client-generated ID. If not provided, server generates one.
+ */
+id?: string, title: string, description: string | null, priority: IssuePriority | null, 
+/**
+ * Defaults to the project's first to-do status.
+ */
+status_id?: string, };
+
+export type IssueTreeNode = { issue: Issue, 
+/**
+ * The issue is in a done status and every sub-issue, at any depth, is
+ * done. Cancelled sub-issues are ignored.
+ */
+done: boolean, 
+/**
+ * Direct sub-issues counted towards `done` (cancelled ones excluded).
+ */
+total_children: number, completed_children: number, children: Array<IssueTreeNode>, };
 
 export type CreateIssueAssigneeRequest = { 
 /**
@@ -183,6 +247,282 @@ export type ExportRequest = { organization_id: string,
  */
 project_ids: Array<string>, include_attachments: boolean, };
 
+export type ExportJobStatus = "pending" | "running" | "completed" | "failed";
+
+export type ExportJob = { id: string, organization_id: string, status: ExportJobStatus, filename: string | null, error: string | null, created_at: string, completed_at: string | null, 
+/**
+ * The archive is deleted after this time.
+ */
+expires_at: string, };
+
+export type ExportJobResponse = { job: ExportJob, 
+/**
+ * Present once the job has completed.
+ */
+download: SignedDownloadUrl | null, };
+
+export type SignedDownloadUrl = { url: string, expires_at: string, };
+
+export type ProjectReport = { id: string, project_id: string, created_by_user_id: string | null, period_start: string, period_end: string, snapshot: ProjectReportSnapshot, created_at: string, };
+
+export type ProjectReportSnapshot = { status_counts: Array<ReportStatusCount>, 
+/**
+ * Issues whose status changed during the report period, latest move first.
+ */
+moved_issues: Array<ReportMovedIssue>, 
+/**
+ * Issues completed during the report period.
+ */
+completed_issues: Array<ReportIssue>, };
+
+export type ReportStatusCount = { status_id: string, status_name: string, count: bigint, };
+
+export type ReportIssue = { issue_id: string, simple_id: string, title: string, completed_at: string | null, };
+
+export type ReportMovedIssue = { issue_id: string, simple_id: string, title: string, from_status_id: string | null, to_status_id: string, moved_at: string, };
+
+export type CreateProjectReportRequest = { 
+/**
+ * Start of the report period. Defaults to seven days before now.
+ */
+since?: string, };
+
+export type ListProjectReportsResponse = { reports: Array<ProjectReport>, };
+
+export type IssueActivityEventType = "created" | "status_changed" | "deleted" | "due_soon" | "blocked" | "unblocked" | "mentioned" | "project_updated";
+
+export type IssueActivity = { seq: bigint, project_id: string, 
+/**
+ * `None` only for [`IssueActivityEventType::ProjectUpdated`].
+ */
+issue_id: string | null, event_type: IssueActivityEventType, from_status_id: string | null, to_status_id: string | null, actor_user_id: string | null, 
+/**
+ * `X-Mutation-Id` of the request that caused the event, if it sent one.
+ */
+mutation_id: string | null, created_at: string, };
+
+export type BoardAtQuery = { timestamp: string, };
+
+export type BoardAtResponse = { project_id: string, timestamp: string, columns: Array<BoardColumnAt>, };
+
+export type BoardColumnAt = { status_id: string, 
+/**
+ * `None` if the status has since been deleted.
+ */
+status_name: string | null, issues: Array<BoardIssueAt>, };
+
+export type BoardIssueAt = { issue_id: string, 
+/**
+ * Current identifiers; `None` if the issue has since been deleted.
+ */
+simple_id: string | null, title: string | null, };
+
+export type ListIssueActivityQuery = { project_id: string, 
+/**
+ * Only return events recorded after this activity sequence number.
+ * A cursor below the project's purged history is rejected with 410;
+ * start over from 0.
+ */
+since_seq?: bigint, 
+/**
+ * Only return events of this type.
+ */
+event_type?: IssueActivityEventType, 
+/**
+ * Comma-separated event types to return, e.g. `created,status_changed`,
+ * for clients that only react to a few kinds of change. Combined with
+ * `event_type` if both are given.
+ */
+event_types?: string, 
+/**
+ * Only return events created at or after this time.
+ */
+since?: string, 
+/**
+ * Only return events created before this time.
+ */
+until?: string, 
+/**
+ * Without `since_seq`, resume after the cursor this device last
+ * acknowledged.
+ */
+device_id?: string, 
+/**
+ * Also return [`IssueActivityEventType::ProjectUpdated`] events. Off by
+ * default because clients that predate them can't parse an event with
+ * no `issue_id`.
+ */
+include_project_events?: boolean, 
+/**
+ * Also return the project's replays with an id above this, so the
+ * client can fetch their ranges again. Omitted by clients that don't
+ * handle replays.
+ */
+replays_after?: bigint, };
+
+/**
+ * A range of a project's activity an operator asked clients to fetch
+ * again, e.g. after a bug left them with cursors past events they never
+ * stored.
+ */
+export type ActivityReplay = { 
+/**
+ * Pass as `replays_after` once the range has been fetched again.
+ */
+id: bigint, first_seq: bigint, last_seq: bigint, };
+
+/**
+ * The last activity sequence number a user's device has processed for a
+ * project.
+ */
+export type ActivityCursor = { project_id: string, device_id: string, seq: bigint, updated_at: string, };
+
+export type GetActivityCursorQuery = { project_id: string, device_id: string, };
+
+export type AckActivityRequest = { project_id: string, 
+/**
+ * Stable identifier the client picks for itself.
+ */
+device_id: string, 
+/**
+ * Highest sequence number processed. A lower value than the stored
+ * one leaves the cursor where it is; a value above the commit-safe
+ * watermark is stored as the watermark.
+ */
+seq: bigint, };
+
+export type ListIssueActivityResponse = { 
+/**
+ * Events in log order.
+ */
+activity: Array<IssueActivity>, 
+/**
+ * Cursor to pass as `since_seq` on the next request.
+ */
+latest_seq: bigint, 
+/**
+ * More events are available after `latest_seq`.
+ */
+has_more: boolean, 
+/**
+ * Replays after `replays_after`, oldest first; empty if it wasn't
+ * given.
+ */
+replays: Array<ActivityReplay>, };
+
+export type ListDeletedIssuesQuery = { project_id: string, 
+/**
+ * Only return deletions recorded after this activity sequence number.
+ * A cursor below the project's purged history is rejected with 410;
+ * start over from 0.
+ */
+since_seq?: bigint, };
+
+export type IssueTombstone = { issue_id: string, seq: bigint, deleted_at: string, };
+
+export type ListDeletedIssuesResponse = { tombstones: Array<IssueTombstone>, 
+/**
+ * Cursor to pass as `since_seq` on the next request. Advances even when
+ * no deletions were returned so clients don't rescan old history.
+ */
+latest_seq: bigint, };
+
+export type ListDueSoonIssuesQuery = { project_id: string, 
+/**
+ * Only return reminders recorded after this activity sequence number.
+ */
+since_seq?: bigint, };
+
+export type IssueDueReminder = { issue_id: string, seq: bigint, target_date: string, reminded_at: string, };
+
+export type ListDueSoonIssuesResponse = { 
+/**
+ * Reminders for issues that are still open and still due on the
+ * announced date.
+ */
+reminders: Array<IssueDueReminder>, 
+/**
+ * Cursor to pass as `since_seq` on the next request.
+ */
+latest_seq: bigint, };
+
+export type OrganizationMemberWithProfile = { user_id: string, role: MemberRole, joined_at: string, first_name: string | null, last_name: string | null, username: string | null, email: string | null, avatar_url: string | null, };
+
+export type ProjectSnapshotResponse = { project: Project, issues: Array<Issue>, statuses: Array<ProjectStatus>, tags: Array<Tag>, members: Array<OrganizationMemberWithProfile>, 
+/**
+ * Every activity event up to this seq is reflected in the snapshot;
+ * follow up with activity after it.
+ */
+latest_seq: bigint, };
+
+/**
+ * Commit message convention for commits made in a project's workspaces,
+ * shared by everyone working on the project.
+ */
+export type ProjectCommitSettings = { 
+/**
+ * Rewrite commit messages into Conventional Commits with the task key.
+ */
+conventional_commits: boolean, 
+/**
+ * Template for the header, e.g. `{type}({task_key}): {summary}`. Unset
+ * uses the default.
+ */
+commit_template: string | null, };
+
+export type UpdateProjectCommitSettingsRequest = { conventional_commits: boolean, commit_template: string | null, };
+
+export type ProjectPrSettings = { 
+/**
+ * Open pull requests as drafts unless the request says otherwise.
+ */
+draft: boolean, 
+/**
+ * Start a body left empty from the repository's pull request template.
+ */
+use_template: boolean, 
+/**
+ * Add the linked issue's title and description to a body left empty,
+ * with a link back to the issue.
+ */
+link_issue: boolean, };
+
+export type UpdateProjectPrSettingsRequest = { draft: boolean, use_template: boolean, link_issue: boolean, };
+
+export type DigestSettings = { 
+/**
+ * Hour of the day, in UTC (0-23). Unset uses the server's default.
+ */
+digest_hour_utc: number | null, };
+
+export type UpdateDigestSettingsRequest = { digest_hour_utc: number | null, };
+
+export type PresenceQuery = { organization_id: string, };
+
+/**
+ * A short-lived, single-use link for opening the organization's WebSocket,
+ * issued by `POST /v1/ws/tickets`. Browsers can't set an `Authorization`
+ * header on a WebSocket, so the ticket in the link stands in for it.
+ */
+export type PresenceTicket = { url: string, expires_at: string, };
+
+/**
+ * Users with the organization open right now.
+ */
+export type PresenceResponse = { organization_id: string, users: Array<UserData>, };
+
+/**
+ * Sent over the organization's WebSocket when someone comes online or
+ * closes their last connection.
+ */
+export type PresenceMessage = { "type": "presence.snapshot", organization_id: string, users: Array<UserData>, } | { "type": "presence.joined", organization_id: string, user: UserData, } | { "type": "presence.left", organization_id: string, user_id: string, } | { "type": "presence.editing", organization_id: string, user_id: string, task_id: string, };
+
+/**
+ * Sent by clients over the organization's WebSocket. These are relayed to
+ * other members and never stored.
+ */
+export type ClientMessage = { "type": "editing", task_id: string, };
+
 // Shape definition interface
 export interface ShapeDefinition<T> {
   readonly table: string;
@@ -231,6 +571,13 @@ export const USERS_SHAPE = defineShape<User>(
   '/v1/fallback/users'
 );
 
+export const ORGANIZATION_ISSUES_SHAPE = defineShape<Issue>(
+  'issues',
+  ['organization_id'] as const,
+  '/v1/shape/issues',
+  '/v1/fallback/organization_issues'
+);
+
 export const PROJECT_TAGS_SHAPE = defineShape<Tag>(
   'tags',
   ['project_id'] as const,
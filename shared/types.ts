@@ -4,11 +4,26 @@
 
 // If you are an AI, and you absolutely have to edit this file, please confirm with the user first.
 
-export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, created_at: Date, updated_at: Date, };
+export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, 
+/**
+ * Comma-separated directories checked out in workspaces, for large
+ * repositories where only part of the tree is needed. Unset checks out
+ * everything.
+ */
+sparse_checkout: string | null, created_at: Date, updated_at: Date, };
 
-export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, 
+/**
+ * Organization owning the linked remote project when last seen, which
+ * decides the remote that requests for the project go to.
+ */
+remote_organization_id: string | null, created_at: Date, updated_at: Date, };
 
-export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, archive_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, default_working_dir?: string | null, };
+/**
+ * Commit message convention for commits made in workspaces of a remote
+ * project.
+ */
+export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, archive_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, default_working_dir?: string | null, sparse_checkout?: string | null, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, 
 /**
@@ -18,11 +33,59 @@ score: bigint, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
+export type GlobalSearchKind = "project" | "task" | "workspace" | "agent_turn";
+
+export type GlobalSearchResult = { kind: GlobalSearchKind, id: string, title: string, 
+/**
+ * Matching excerpt with hits wrapped in `<mark>` tags.
+ */
+snippet: string, 
+/**
+ * Workspace to open for workspace and agent turn results.
+ */
+workspace_id: string | null, 
+/**
+ * BM25 score; lower is a better match.
+ */
+rank: number, };
+
+export type ActivityEventType = "created" | "status_changed" | "deleted" | "due_soon" | "blocked" | "unblocked" | "mentioned";
+
+export type Activity = { 
+/**
+ * Remote project the event belongs to.
+ */
+project_id: string, 
+/**
+ * Position in the remote project's activity log.
+ */
+seq: bigint, issue_id: string, issue_simple_id: string | null, issue_title: string | null, event_type: ActivityEventType, from_status: string | null, to_status: string | null, actor_user_id: string | null, 
+/**
+ * Client mutation ID of the request that caused the event.
+ */
+mutation_id: string | null, created_at: Date, };
+
+export type PendingProjectLink = { remote_project_id: string, organization_id: string, 
+/**
+ * Remote project name as of the last catch-up.
+ */
+name: string, 
+/**
+ * Shared issues that would show up once the project is linked.
+ */
+issue_count: bigint, updated_at: Date, };
+
 export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
 
 export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
 
-export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, created_at: Date, updated_at: Date, };
+export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, 
+/**
+ * Comma-separated directories checked out in workspaces, for large
+ * repositories where only part of the tree is needed. Unset checks out
+ * everything.
+ */
+sparse_checkout: string | null, created_at: Date, updated_at: Date, };
 
 export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
 
@@ -30,6 +93,26 @@ export type CreateTag = { tag_name: string, content: string, };
 
 export type UpdateTag = { tag_name: string | null, content: string | null, };
 
+export type TableSize = { 
+/**
+ * Table name; indexes are folded into their table.
+ */
+name: string, bytes: bigint, };
+
+export type DatabaseSize = { total_bytes: bigint, 
+/**
+ * Space held by free pages, reclaimable by vacuuming.
+ */
+free_bytes: bigint, 
+/**
+ * Whether free pages are reclaimed incrementally, without a full vacuum.
+ */
+incremental_vacuum: boolean, 
+/**
+ * Largest first.
+ */
+tables: Array<TableSize>, };
+
 export type DraftFollowUpData = { message: string, executor_config: ExecutorConfig, };
 
 export type DraftWorkspaceData = { message: string, repos: Array<DraftWorkspaceRepo>, executor_config: ExecutorConfig | null, linked_issue: DraftWorkspaceLinkedIssue | null, attachments: Array<DraftWorkspaceAttachment>, };
@@ -174,9 +257,17 @@ export type ExecutionProcessRepoState = { id: string, execution_process_id: stri
 
 export type Merge = { "type": "direct" } & DirectMerge | { "type": "pr" } & PrMerge;
 
-export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, created_at: string, };
+export type DirectMerge = { id: string, workspace_id: string, repo_id: string, merge_commit: string, target_branch_name: string, 
+/**
+ * First release tag containing `merge_commit`, once one does
+ */
+release_tag: string | null, created_at: string, };
 
-export type PrMerge = { id: string, workspace_id: string, repo_id: string, created_at: string, target_branch_name: string, pr_info: PullRequestInfo, };
+export type PrMerge = { id: string, workspace_id: string, repo_id: string, created_at: string, target_branch_name: string, 
+/**
+ * First release tag containing the PR's merge commit, once one does
+ */
+release_tag: string | null, pr_info: PullRequestInfo, };
 
 export type MergeStatus = "open" | "merged" | "closed" | "unknown";
 
@@ -216,7 +307,7 @@ export type ProviderProfile = { provider: string, username: string | null, displ
 
 export type StatusResponse = { logged_in: boolean, profile: ProfileResponse | null, degraded: boolean | null, };
 
-export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER" }
+export enum MemberRole { ADMIN = "ADMIN", MEMBER = "MEMBER", VIEWER = "VIEWER" }
 
 export enum InvitationStatus { PENDING = "PENDING", ACCEPTED = "ACCEPTED", DECLINED = "DECLINED", EXPIRED = "EXPIRED" }
 
@@ -258,6 +349,60 @@ export type UpdateMemberRoleRequest = { role: MemberRole, };
 
 export type UpdateMemberRoleResponse = { user_id: string, role: MemberRole, };
 
+export type OrganizationAccessPolicy = { 
+/**
+ * CIDR ranges (e.g. `203.0.113.0/24`) member requests must come from.
+ */
+ip_allowlist: Array<string>, 
+/**
+ * Auth session IDs (see `/v1/identity`) of the signed-in devices member
+ * requests must come from.
+ */
+device_ids: Array<string>, };
+
+export type UpdateOrganizationAccessPolicyRequest = { ip_allowlist: Array<string>, device_ids: Array<string>, };
+
+export type OrganizationRetentionPolicy = { 
+/**
+ * Days after a workspace's last update to keep its diff summary (files
+ * changed, lines added/removed). `None` keeps summaries indefinitely.
+ */
+workspace_summary_days: number | null, };
+
+export type UpdateOrganizationRetentionPolicyRequest = { workspace_summary_days: number | null, };
+
+export type OrganizationAutomationPolicy = { 
+/**
+ * Move Done issues back to In progress, and notify their assignees,
+ * when a linked pull request is reverted or CI fails on the default
+ * branch at its merge commit.
+ */
+reopen_on_failure: boolean, };
+
+export type UpdateOrganizationAutomationPolicyRequest = { reopen_on_failure: boolean, };
+
+export type OrganizationClientConfig = { 
+/**
+ * Board filter state, in the frontend's own format.
+ */
+default_board_filters: JsonValue | null, 
+/**
+ * Status name to CSS hex color (`#rrggbb`).
+ */
+status_colors: { [key in string]?: string }, };
+
+export type ProductUpdate = { id: string, title: string, 
+/**
+ * Markdown.
+ */
+body: string, url: string | null, published_at: string, 
+/**
+ * Whether the requesting user has read this update.
+ */
+read: boolean, };
+
+export type ListProductUpdatesResponse = { updates: Array<ProductUpdate>, unread_count: bigint, };
+
 export type RegisterRepoRequest = { path: string, display_name: string | null, };
 
 export type InitRepoRequest = { parent_path: string, folder_name: string, };
@@ -266,6 +411,159 @@ export type TagSearchParams = { search: string | null, };
 
 export type TokenResponse = { access_token: string, expires_at: string | null, };
 
+export type AuthEvent = { "type": "expiring_soon", expires_at: string, } | { "type": "refreshed", expires_at: string, } | { "type": "session_ended" };
+
+export type LogLevelResponse = { 
+/**
+ * Active `RUST_LOG`-style directives.
+ */
+filter: string, 
+/**
+ * Directives the server started with.
+ */
+default_filter: string, };
+
+export type UpdateLogLevelRequest = { 
+/**
+ * New directives, e.g. `info,utils::ws=trace`. `null` restores the
+ * startup filter.
+ */
+filter: string | null, };
+
+export type CreateSampleProjectRequest = { organization_id: string, };
+
+export type SampleProjectResponse = { project_id: string, issue_ids: Array<string>, };
+
+export type ActivityQuery = { 
+/**
+ * Remote project id.
+ */
+project_id: string, 
+/**
+ * Earliest event to include; defaults to the last 24 hours.
+ */
+since?: Date, };
+
+export type ActivityFeedEntry = { summary: string, } & Activity;
+
+/**
+ * How fresh the entries of an [`ActivityFeed`] are.
+ */
+export type ActivitySyncStatus = { "status": "synced" } | { "status": "unavailable" } | { "status": "bulk_sync_required", latest_seq: bigint, };
+
+export type ActivityFeed = { entries: Array<ActivityFeedEntry>, sync: ActivitySyncStatus, };
+
+export type ActivityCatchUpQuery = { 
+/**
+ * Remote organization whose projects should be mirrored.
+ */
+organization_id: string, };
+
+export type ActivityCatchUpSummary = { 
+/**
+ * Projects synced successfully.
+ */
+projects: number, 
+/**
+ * Events fetched across those projects.
+ */
+events: number, 
+/**
+ * Projects whose sync failed; they're retried on the next catch-up.
+ */
+failed: Array<string>, };
+
+export type ShareAllEvent = { "type": "progress", 
+/**
+ * Tasks shared so far.
+ */
+shared: number, 
+/**
+ * Unshared tasks found at the start.
+ */
+total: number, } | { "type": "finished", shared: number, } | { "type": "failed", 
+/**
+ * Why sharing stopped. Tasks already shared stay shared, so retrying resumes.
+ */
+message: string, };
+
+export type ShareAllPreview = { remote_project_id: string, issues: Array<SharedTaskPreview>, };
+
+export type SharedTaskPreview = { task_id: string, title: string, description: string | null, 
+/**
+ * Remote status the issue is created in.
+ */
+status_name: string, };
+
+export type LinkRemoteProjectRequest = { remote_project_id: string, 
+/**
+ * Repository cloned into the new project.
+ */
+clone_url: string, 
+/**
+ * Directory to clone into; must not exist yet.
+ */
+path: string, };
+
+export type ImportGitHubIssuesRequest = { 
+/**
+ * Local repository whose default remote's GitHub issues are imported.
+ */
+repo_id: string, };
+
+export type GitHubImportSummary = { imported: number, 
+/**
+ * Open GitHub issues already imported into the project earlier.
+ */
+skipped: number, };
+
+export type ChangelogQuery = { 
+/**
+ * Tag of the previous release; omit to include everything before `to_tag`.
+ */
+from_tag: string | null, 
+/**
+ * Tag of the release; omit for changes not released yet.
+ */
+to_tag: string | null, };
+
+export type Changelog = { from_tag: string | null, 
+/**
+ * `None` runs the changelog up to each repository's `HEAD`.
+ */
+to_tag: string | null, sections: Array<ChangelogSection>, 
+/**
+ * The changelog rendered as release notes.
+ */
+markdown: string, };
+
+export type ChangelogSection = { 
+/**
+ * Title of the parent task the entries were split from, `None` for
+ * tasks without one.
+ */
+epic: string | null, entries: Array<ChangelogEntry>, };
+
+export type ChangelogEntry = { task_id: string, title: string, };
+
+export type OpenTargetQuery = { 
+/**
+ * `task:{id}` (alias `issue:{id}`), `project:{id}` or `workspace:{id}`.
+ */
+target: string, };
+
+export type OpenTargetKind = "issue" | "project" | "workspace";
+
+export type OpenTargetResponse = { kind: OpenTargetKind, 
+/**
+ * Route to navigate to in the app.
+ */
+path: string, 
+/**
+ * Project to focus, when the target belongs to one.
+ */
+project_id: string | null, issue_id: string | null, workspace_id: string | null, };
+
 export type UserSystemInfo = { version: string, config: Config, machine_id: string, login_status: LoginStatus, remote_auth_degraded: string | null, environment: Environment, 
 /**
  * Capabilities supported per executor (e.g., { "CLAUDE_CODE": ["SESSION_FORK"] })
@@ -354,12 +652,36 @@ export type CreateWorkspaceApiRequest = { name: string | null, };
 
 export type LinkedIssueInfo = { remote_project_id: string, issue_id: string, };
 
-export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+export type CreatePrApiRequest = { title: string, 
+/**
+ * Left empty, filled in as the project's PR settings say.
+ */
+body: string | null, target_branch: string | null, 
+/**
+ * Defaults to the project's PR settings.
+ */
+draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
 
 export type AttachmentResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
 export type AttachmentMetadata = { exists: boolean, file_name: string | null, path: string | null, size_bytes: bigint | null, format: string | null, proxy_url: string | null, };
 
+export type PasteImageRequest = { 
+/**
+ * Base64 image bytes, optionally as a `data:image/...;base64,` URL.
+ */
+data: string, filename: string | null, 
+/**
+ * Also upload the image to the remote attachment store for the issue.
+ */
+share: boolean, };
+
+export type PasteImageResponse = { attachment: AttachmentResponse, remote_attachment_id: string | null, 
+/**
+ * Markdown image reference to insert into the description.
+ */
+markdown: string, };
+
 export type WorkspaceRepoInput = { repo_id: string, target_branch: string, };
 
 export type RunAgentSetupRequest = { executor_profile_id: ExecutorProfileId, };
@@ -476,19 +798,60 @@ pr_number: bigint | null,
 /**
  * PR URL for this workspace (if any PR exists)
  */
-pr_url: string | null, };
+pr_url: string | null, 
+/**
+ * Number of PRs opened from this workspace, e.g. a stack
+ */
+pr_count: number, 
+/**
+ * Status of all this workspace's PRs taken together (if any PR exists)
+ */
+aggregate_pr_status: MergeStatus | null, 
+/**
+ * Release the workspace's latest merge shipped in, e.g. `v1.2.3`
+ */
+release_tag: string | null, };
 
 export type WorkspaceSummaryResponse = { summaries: Array<WorkspaceSummary>, };
 
 export type DiffStats = { files_changed: number, lines_added: number, lines_removed: number, };
 
+export type PooledWorktreeInfo = { path: string, parked_at: string, bytes: bigint, };
+
+/**
+ * Worktrees in the pool after pruning, and those pruning removed or, on a
+ * dry run, would remove.
+ */
+export type WorktreePoolPruneReport = { kept: Array<PooledWorktreeInfo>, evicted: Array<PooledWorktreeInfo>, };
+
 export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
 export type SearchMode = "taskform" | "settings";
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, remote_onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, commit_reminder_enabled: boolean, commit_reminder_prompt: string | null, send_message_shortcut: SendMessageShortcut, relay_enabled: boolean, host_nickname: string | null, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, remote_onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, 
+/**
+ * Name of workspace branches, built from `{prefix}`, `{id}`,
+ * `{task_key}`, `{slug}` and `{username}`.
+ */
+git_branch_template: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, commit_reminder_enabled: boolean, commit_reminder_prompt: string | null, send_message_shortcut: SendMessageShortcut, relay_enabled: boolean, host_nickname: string | null, log_retention: LogRetentionConfig, 
+/**
+ * Remote project created by onboarding, so it can be torn down later.
+ */
+sample_project_id: string | null, do_not_disturb: DoNotDisturbConfig, 
+/**
+ * Desktop notification when an issue is assigned to you.
+ */
+assignment_notifications_enabled: boolean, 
+/**
+ * Organizations served by a remote other than the default one.
+ */
+organization_remotes: { [key in string]?: OrganizationRemote }, worktree_pool: WorktreePoolConfig, 
+/**
+ * Partial clone used when cloning a repository to link a project.
+ */
+clone_filter: CloneFilter, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -510,8 +873,60 @@ export type ShowcaseState = { seen_features: Array<string>, };
 
 export type SendMessageShortcut = "ModifierEnter" | "Enter";
 
+export type LogRetentionConfig = { 
+/**
+ * Delete logs of processes that finished more than this many days ago.
+ */
+max_age_days: number | null, 
+/**
+ * Keep at most this many megabytes of logs per project, oldest first out.
+ */
+max_project_mb: number | null, };
+
+export type DoNotDisturbConfig = { enabled: boolean, windows: Array<DoNotDisturbWindow>, };
+
+export type DoNotDisturbWindow = { start: string, end: string, };
+
+export type OrganizationRemote = { 
+/**
+ * API base URL, like `VK_SHARED_API_BASE`.
+ */
+api_base: string, 
+/**
+ * Base URL for live sync (Electric shapes and the presence socket).
+ * Defaults to `api_base`.
+ */
+sync_base: string | null, };
+
+export type WorktreePoolConfig = { 
+/**
+ * Idle worktrees kept per repository; 0 turns pooling off.
+ */
+max_per_repo: number, 
+/**
+ * Remove worktrees that sat in the pool for more than this many days.
+ */
+max_idle_days: number | null, 
+/**
+ * Keep the whole pool under this many megabytes, longest idle first out.
+ */
+max_total_mb: number | null, };
+
+/**
+ * Objects a partial clone leaves on the remote until they're needed.
+ */
+export type CloneFilter = "none" | "blobless";
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
+export type ProjectLogPrune = { 
+/**
+ * `None` for workspaces not linked to a task.
+ */
+project_id: string | null, process_count: number, bytes: bigint, };
+
+export type LogPruneReport = { dry_run: boolean, process_count: number, bytes: bigint, projects: Array<ProjectLogPrune>, };
+
 export type QueuedMessage = { 
 /**
  * The session this message is queued for